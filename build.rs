@@ -0,0 +1,45 @@
+//! Build script: embeds build metadata for `octoroute_build_info` and `GET /version`
+//!
+//! Exposes `OCTOROUTE_GIT_SHA` and `OCTOROUTE_RUSTC_VERSION` to the crate via
+//! `env!`. Either can be preset in the environment (e.g., in container builds
+//! without a `.git` directory); otherwise they are detected, falling back to
+//! "unknown" so the build never fails for lack of git.
+
+use std::path::Path;
+use std::process::Command;
+
+fn main() {
+    let git_sha = std::env::var("OCTOROUTE_GIT_SHA")
+        .ok()
+        .or_else(|| command_output("git", &["rev-parse", "--short=12", "HEAD"]))
+        .unwrap_or_else(|| "unknown".to_string());
+
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let rustc_version = std::env::var("OCTOROUTE_RUSTC_VERSION")
+        .ok()
+        .or_else(|| command_output(&rustc, &["--version"]))
+        .unwrap_or_else(|| "unknown".to_string());
+
+    println!("cargo:rustc-env=OCTOROUTE_GIT_SHA={}", git_sha);
+    println!("cargo:rustc-env=OCTOROUTE_RUSTC_VERSION={}", rustc_version);
+
+    println!("cargo:rerun-if-env-changed=OCTOROUTE_GIT_SHA");
+    println!("cargo:rerun-if-env-changed=OCTOROUTE_RUSTC_VERSION");
+    // Only watch git state when it exists; a missing path would force a rebuild every time
+    for path in [".git/HEAD", ".git/refs"] {
+        if Path::new(path).exists() {
+            println!("cargo:rerun-if-changed={}", path);
+        }
+    }
+}
+
+/// Run a command and return its trimmed stdout, or None if it fails or prints nothing
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    let trimmed = stdout.trim();
+    (!trimmed.is_empty()).then(|| trimmed.to_string())
+}
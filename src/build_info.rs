@@ -0,0 +1,48 @@
+//! Build metadata for the running binary
+//!
+//! Values are fixed at compile time (see `build.rs`) and exposed through the
+//! `octoroute_build_info` metric and the `GET /version` endpoint so operators can
+//! correlate behavior with the deployed build.
+
+use serde::Serialize;
+
+/// Crate version from `Cargo.toml`
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Short git commit SHA the binary was built from ("unknown" outside a git checkout)
+pub const GIT_SHA: &str = env!("OCTOROUTE_GIT_SHA");
+
+/// `rustc --version` output of the compiler that built the binary
+pub const RUSTC_VERSION: &str = env!("OCTOROUTE_RUSTC_VERSION");
+
+/// Build metadata returned by `GET /version`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct BuildInfo {
+    pub version: &'static str,
+    pub git_sha: &'static str,
+    pub rustc: &'static str,
+}
+
+impl BuildInfo {
+    /// Build metadata of the running binary
+    pub const fn current() -> Self {
+        Self {
+            version: VERSION,
+            git_sha: GIT_SHA,
+            rustc: RUSTC_VERSION,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_info_fields_are_populated() {
+        let info = BuildInfo::current();
+        assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+        assert!(!info.git_sha.is_empty());
+        assert!(!info.rustc.is_empty());
+    }
+}
@@ -2,7 +2,12 @@
 //!
 //! Provides argument parsing and subcommand handling for the Octoroute binary.
 
+use crate::config::Config;
+use crate::error::AppResult;
+use crate::handlers::AppState;
+use crate::router::TargetModel;
 use clap::{Parser, Subcommand};
+use std::sync::Arc;
 
 /// Intelligent multi-model router for self-hosted LLMs
 #[derive(Parser)]
@@ -18,6 +23,22 @@ pub struct Cli {
     #[arg(short, long, default_value = "config.toml", global = true)]
     pub config: String,
 
+    /// Partial config file merged over --config (e.g. local hosts and secrets)
+    #[arg(long, global = true)]
+    pub config_override: Option<String>,
+
+    /// Validate the configuration and exit without starting the server
+    #[arg(long)]
+    pub check: bool,
+
+    /// Log level for this run, overriding RUST_LOG and observability.log_level
+    #[arg(
+        long,
+        global = true,
+        value_parser = ["trace", "debug", "info", "warn", "error"]
+    )]
+    pub log_level: Option<String>,
+
     #[command(subcommand)]
     pub command: Option<Command>,
 }
@@ -32,6 +53,51 @@ pub enum Command {
     },
 }
 
+/// Validate a configuration file the way server startup would, without binding a port
+///
+/// Loads and validates the file, then constructs `AppState` so router construction
+/// errors surface too. The background health checker started by `AppState` is shut
+/// down before returning. Requires a Tokio runtime.
+///
+/// Returns a report of each endpoint's effective traffic share (see
+/// [`traffic_share_report`]) for the caller to print. `override_path` is the
+/// `--config-override` file, merged over `path` (see [`Config::from_files`]).
+pub async fn check_config(path: &str, override_path: Option<&str>) -> AppResult<String> {
+    let config = Config::load(path, override_path.map(std::path::Path::new))?;
+    let report = traffic_share_report(&config);
+    let state = AppState::new(Arc::new(config))?;
+    state.selector().health_checker().shutdown().await;
+    Ok(report)
+}
+
+/// Human-readable effective traffic share per endpoint, followed by any weight warnings
+pub fn traffic_share_report(config: &Config) -> String {
+    let mut report = String::from("Effective traffic share (within each priority group):\n");
+    for (tier_name, tier) in [
+        ("fast", TargetModel::Fast),
+        ("balanced", TargetModel::Balanced),
+        ("deep", TargetModel::Deep),
+    ] {
+        report.push_str(&format!("  {}:\n", tier_name));
+        let endpoints = config.models.tier(tier);
+        for (endpoint, (name, percent)) in
+            endpoints.iter().zip(config.models.effective_weights(tier))
+        {
+            report.push_str(&format!(
+                "    {} (priority {}, weight {}): {:.1}%\n",
+                name,
+                endpoint.priority(),
+                endpoint.weight(),
+                percent
+            ));
+        }
+    }
+    for warning in config.models.weight_warnings() {
+        report.push_str(&format!("warning: {}\n", warning));
+    }
+    report
+}
+
 /// Generate template configuration content
 pub fn generate_config_template() -> &'static str {
     r#"# Octoroute Configuration
@@ -53,9 +119,87 @@ host = "0.0.0.0"
 # Port to listen on
 port = 3000
 
+# Also listen on the IPv6 counterpart of host (0.0.0.0 <-> ::, 127.0.0.1 <-> ::1)
+# dual_stack = false
+
+# Further IP addresses to listen on, all on the same port
+# extra_hosts = ["::1"]
+
 # Default request timeout in seconds (can be overridden per-tier in [timeouts])
 request_timeout_seconds = 30
 
+# SSE keep-alive comment interval while a stream waits for its first token
+# (prevents idle proxy timeouts on slow models; 0 disables)
+sse_keepalive_seconds = 15
+
+# Other endpoints a stream may restart on if it fails before its first token
+# (failures after the first token are never retried; 0 disables)
+# stream_failover_attempts = 2
+
+# Hard limit on total request time including routing and retries (optional)
+# Streaming requests are only bounded until the stream starts
+# max_request_duration_seconds = 120
+
+# Give up on an endpoint that sends no first token within this many ms and
+# try another (optional; targets stuck or queued backends)
+# first_token_timeout_ms = 3000
+
+# Largest accepted request body in bytes; larger requests get 413 (default 10 MiB)
+# max_request_body_bytes = 10485760
+
+# Most messages, and most characters across them, a chat request may carry;
+# larger conversations get 400 before routing (defaults are the maximums)
+# max_messages = 100
+# max_total_prompt_chars = 500000
+
+# Probe every endpoint once at startup before accepting traffic (unreachable
+# endpoints are logged, never fatal)
+# warmup = false
+
+# Gzip chat responses (SSE streams included) for clients sending
+# Accept-Encoding: gzip
+# enable_compression = false
+
+# Wait up to this many ms for a tier_concurrency slot before shedding with
+# 503 (optional; requests are shed immediately when unset)
+# max_queue_wait_ms = 250
+
+# Cap on upstream model calls per request, router retries and completion
+# retries combined (optional; unlimited when unset)
+# max_upstream_calls = 6
+
+# Cap on parallel upstream calls for one request asking for several choices
+# (n > 1); fan_out_overflow = "sequential" queues the rest, "reject" answers 400
+# (optional; unlimited when unset)
+# max_parallel_upstream_per_request = 4
+# fan_out_overflow = "sequential"
+
+# What to do when a backend answers with no content: "return_empty" passes it
+# on, "retry" tries another endpoint of the tier, "error" answers 502
+# on_empty_completion = "return_empty"
+
+# Bearer token for the admin API (endpoint drain, server drain, config reload)
+# (admin endpoints are not served when unset)
+# admin_token = "change-me"
+
+# Keep-alive connection pool for health checks and warmup probes
+# (max_idle_per_host = 0 opens a fresh connection per probe)
+# [server.http_pool]
+# max_idle_per_host = 32
+# idle_timeout_seconds = 90
+
+# Per-tier concurrency budgets: requests beyond a tier's budget get 503
+# (omitted tiers are unlimited)
+# [server.tier_concurrency]
+# deep = 4
+
+# Count requests per OpenAI `user` field and log users over the limit
+# (reject_over_limit = true answers them with 429 until the window ends)
+# [server.user_tracking]
+# max_requests = 600
+# window_seconds = 60
+# reject_over_limit = false
+
 # ─────────────────────────────────────────────────────────────────────────────
 # MODEL TIERS
 # ─────────────────────────────────────────────────────────────────────────────
@@ -69,16 +213,26 @@ request_timeout_seconds = 30
 #
 # Endpoint fields:
 #   - name: Model identifier (for OpenAI-compatible APIs)
+#   - model: Optional model string sent to the backend instead of name
 #   - base_url: API base URL (must end with /v1 for OpenAI-compatible APIs)
+#   - skip_url_check: Allow a base_url without /v1 (gateways with another path)
+#   - health_path: Optional path probed with GET for health checks, e.g. "/health"
+#     (default: HEAD {base_url}/models)
 #   - max_tokens: Maximum tokens for generation
 #   - temperature: Sampling temperature (0.0-2.0)
 #   - weight: Load balancing weight (higher = more traffic)
 #   - priority: Selection priority (higher = tried first)
+#   - request_timeout_seconds: Optional per-endpoint timeout override (1-300)
+#   - tags: Optional labels, e.g. ["code"] to prefer this endpoint for code requests
+#   - cost_per_1k_tokens: Optional price per 1000 tokens, for cost estimates
+#   - context_window: Optional largest prompt in tokens (see context_overflow)
+#   - strip_reasoning_tags: Remove <think>...</think> blocks from responses
+#     (reasoning_tags = ["think"] sets which tags)
 
 # Fast tier - 8B class models
 [[models.fast]]
 name = "your-8b-model"
-base_url = "http://your-server:port/v1"
+base_url = "http://your-server:8000/v1"
 max_tokens = 4096
 temperature = 0.7
 weight = 1.0
@@ -87,7 +241,7 @@ priority = 1
 # Add additional fast endpoints for load balancing:
 # [[models.fast]]
 # name = "your-8b-model"
-# base_url = "http://another-server:port/v1"
+# base_url = "http://another-server:8000/v1"
 # max_tokens = 4096
 # temperature = 0.7
 # weight = 1.0
@@ -96,7 +250,7 @@ priority = 1
 # Balanced tier - 30B class models
 [[models.balanced]]
 name = "your-30b-model"
-base_url = "http://your-server:port/v1"
+base_url = "http://your-server:8000/v1"
 max_tokens = 8192
 temperature = 0.7
 weight = 1.0
@@ -105,7 +259,7 @@ priority = 1
 # Deep tier - 120B+ class models
 [[models.deep]]
 name = "your-120b-model"
-base_url = "http://your-server:port/v1"
+base_url = "http://your-server:8000/v1"
 max_tokens = 16384
 temperature = 0.7
 weight = 1.0
@@ -120,6 +274,7 @@ priority = 1
 #   - "rule": Fast pattern-based routing (~<1ms latency)
 #   - "llm": Intelligent LLM-powered routing (~250ms latency)
 #   - "hybrid": Rule-based first, LLM fallback (recommended)
+#   - "heuristic": Local keyword/length classifier, no router model call
 strategy = "hybrid"
 
 # Default importance level for requests that don't specify one
@@ -130,6 +285,109 @@ default_importance = "normal"
 # The router model analyzes requests and selects the optimal target tier
 router_tier = "balanced"
 
+# Base delay (ms) between router retry attempts; doubles per attempt, with
+# jitter. 0 retries the next router endpoint immediately
+# retry_backoff_ms = 100
+
+# With strategy = "llm": how to route when the router model gives an unusable
+# answer. "error" fails the request, "rule" uses the rule-based rules, and
+# "default_tier" uses the default tier
+# llm_failure_fallback = "error"
+
+# Tier for a router answer that names no tier: "error" fails the request,
+# "balanced" or "default_tier" route there with a warning (llm and hybrid)
+# on_unparseable = "error"
+
+# Prompt-injection guard for the LLM router: the instruction sent after the
+# user request, and whether the request is fenced in <<<USER>>>/<<<END>>>
+# router_guard_suffix = "Based on the above, respond with ONLY one word: FAST, BALANCED, or DEEP."
+# router_prompt_delimiters = true
+
+# House system prompt sent to backends with every completion (optional)
+# Use system_prompt_file = "house-prompt.txt" instead to load it from a file
+# system_prompt = "Follow the house style guide."
+# When the client sends its own system message: "prepend", "replace", or "merge"
+# system_prompt_mode = "prepend"
+
+# Tier for requests that match no rule in rule-only mode (optional)
+# Unset: the tier holding the highest-priority endpoint
+# default_tier = "balanced"
+
+# Sticky session routing (optional, disabled by default)
+# "auto" requests sharing an x-octoroute-session header (or OpenAI "user" field)
+# reuse the first request's tier for this many seconds
+# sticky_session_ttl_seconds = 900
+
+# Serve from a lower tier (deep -> balanced -> fast) when the routed tier has
+# no healthy endpoints, instead of failing the request
+# tier_fallback = false
+
+# Send overflow to lower-priority endpoints when every top-priority endpoint is
+# at its max_in_flight limit (set max_in_flight on [[models.*]] entries)
+# spillover = false
+
+# Within the top priority group, pick among the endpoints with the lowest
+# cost_per_1k_tokens ("cheapest") instead of by weight alone ("weighted").
+# Endpoints without a rate count as "expensive" (default) or "cheap"
+# selection_mode = "weighted"
+# uncosted_endpoints = "expensive"
+
+# Prompts larger than every context_window in the routed tier are rejected
+# with 400 ("reject") or moved up to the next tier that fits ("escalate")
+# context_overflow = "reject"
+
+# Largest token estimate routing decides on; huge prompts route like this size
+# token_estimate_cap = 32768
+
+# Preferred tier per task type (casual_chat, code, creative_writing,
+# deep_analysis, document_summary, question_answer). Rule-based routing sends
+# these straight to the tier; the LLM router gets them as a hint
+# [routing.task_affinity]
+# creative_writing = "deep"
+# casual_chat = "fast"
+
+# Extra words accepted from the LLM router per tier, for router models that
+# answer in another language (FAST/BALANCED/DEEP are always accepted)
+# [routing.tier_keywords]
+# balanced = ["ÉQUILIBRÉ", "均衡"]
+
+# Guidance added to the LLM router prompt per request importance (low,
+# normal, high). None by default
+# [routing.importance_guidance]
+# high = "High importance: prefer BALANCED or DEEP"
+
+# Reuse LLM routing decisions for repeated prompts. "memory" is per
+# instance; "redis" (build with --features redis) is shared by a fleet
+# [routing.cache]
+# backend = "memory"
+# ttl_seconds = 300
+# redis_url = "redis://127.0.0.1:6379"
+
+# Dedicated model for routing decisions, outside the serving tiers. The
+# router_tier endpoints are still used if it fails
+# [routing.router_endpoint]
+# name = "router"
+# base_url = "http://localhost:1240/v1"
+# max_tokens = 16
+# temperature = 0.0
+
+# Infer task_type from the message for /chat requests that omit it
+# (keyword heuristic; an explicit task_type always wins)
+# auto_classify_task = false
+
+# Backend HTTP statuses retried on another endpoint; any other error status
+# is returned to the client right away
+# retryable_statuses = [429, 500, 502, 503, 504]
+
+# Ask the same router endpoint once more after an empty answer (0 or 1)
+# router_same_endpoint_retries = 0
+
+# Router attempts allowed to fail per kind: connection errors (nothing
+# received) vs stream errors and timeouts. Each between 1 and 10
+# [routing.retry_policy]
+# connection_attempts = 3
+# stream_attempts = 1
+
 # ─────────────────────────────────────────────────────────────────────────────
 # OBSERVABILITY
 # ─────────────────────────────────────────────────────────────────────────────
@@ -138,9 +396,41 @@ router_tier = "balanced"
 # Log level: "trace", "debug", "info", "warn", "error"
 log_level = "info"
 
+# Log routed prompts: "none", "hashed", "truncated" (first log_prompt_chars
+# characters), or "full". Prompts may contain sensitive data
+# log_prompts = "none"
+# log_prompt_chars = 200
+
+# Serve /metrics and /health on a separate internal address instead of the
+# public listener, so it can be firewalled
+# metrics_bind = "127.0.0.1:9090"
+
+# Let clients request routing details (tier, strategy, raw router answer) with
+# the x-octoroute-debug: true header. Keep off for untrusted clients
+# router_debug = false
+
+# Name the serving endpoint (and router endpoint) in x-octoroute-endpoint /
+# x-octoroute-router-endpoint response headers
+# endpoint_headers = false
+
 # Prometheus metrics are always available at /metrics on the server port
 # For production, consider using a reverse proxy to restrict access
 
+# ─────────────────────────────────────────────────────────────────────────────
+# STARTUP HEALTH (Optional)
+# ─────────────────────────────────────────────────────────────────────────────
+#
+# Refuse to start if a required tier has no reachable endpoint after the grace
+# period (catches mistyped base_urls before the first request). The probe
+# timeouts bound how long one health check may take to connect and to answer.
+
+# [health]
+# enabled = true
+# require_healthy_at_startup = false
+# startup_grace_period_seconds = 10
+# probe_connect_timeout_ms = 2000
+# probe_read_timeout_ms = 5000
+
 # ─────────────────────────────────────────────────────────────────────────────
 # TIMEOUTS (Optional)
 # ─────────────────────────────────────────────────────────────────────────────
@@ -171,6 +461,24 @@ mod tests {
         let cli = Cli::parse_from(["octoroute"]);
         assert_eq!(cli.config, "config.toml");
         assert!(cli.command.is_none());
+        assert!(!cli.check);
+        assert_eq!(cli.log_level, None);
+    }
+
+    #[test]
+    fn log_level_flag() {
+        let cli = Cli::parse_from(["octoroute", "--log-level", "debug"]);
+        assert_eq!(cli.log_level.as_deref(), Some("debug"));
+
+        assert!(Cli::try_parse_from(["octoroute", "--log-level", "loud"]).is_err());
+    }
+
+    #[test]
+    fn check_flag() {
+        let cli = Cli::parse_from(["octoroute", "--check", "--config", "custom.yaml"]);
+        assert!(cli.check);
+        assert_eq!(cli.config, "custom.yaml");
+        assert!(cli.command.is_none());
     }
 
     #[test]
@@ -2,7 +2,7 @@
 //!
 //! Parses TOML configuration files and provides typed access to settings.
 
-use crate::router::TargetModel;
+use crate::router::{Importance, TargetModel, TaskType};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::str::FromStr;
@@ -17,6 +17,8 @@ pub struct Config {
     pub observability: ObservabilityConfig,
     #[serde(default)]
     pub timeouts: TimeoutsConfig,
+    #[serde(default)]
+    pub health: HealthConfig,
 }
 
 /// Server configuration
@@ -24,14 +26,326 @@ pub struct Config {
 pub struct ServerConfig {
     pub host: String,
     pub port: u16,
+    /// Further IP addresses to listen on, all on `port`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_hosts: Vec<String>,
+    /// Also listen on the other IP family's counterpart of each address
+    ///
+    /// `0.0.0.0` is paired with `::` and `127.0.0.1` with `::1` (and the other
+    /// way round), so one setting serves IPv4 and IPv6 clients alike.
+    #[serde(default)]
+    pub dual_stack: bool,
     #[serde(default = "default_request_timeout")]
     pub request_timeout_seconds: u64,
+    /// Interval between SSE keep-alive comments while a stream waits for its first token
+    ///
+    /// Keeps intermediary proxies from closing idle connections during long
+    /// time-to-first-token on slow models. Comments stop once tokens start flowing.
+    /// Set to 0 to disable.
+    #[serde(default = "default_sse_keepalive")]
+    pub sse_keepalive_seconds: u64,
+    /// Other endpoints to try when a stream fails before its first token
+    ///
+    /// Nothing has reached the client yet, so the failure is recorded for health
+    /// tracking and the completion restarts on another healthy endpoint of the
+    /// tier. Failures after the first token are never retried. Set to 0 to disable.
+    #[serde(default = "default_stream_failover_attempts")]
+    pub stream_failover_attempts: usize,
+    /// Upper bound on the whole request lifecycle, including routing and retries
+    ///
+    /// A safety backstop on top of the per-attempt upstream timeouts: exceeding it
+    /// aborts the request with 504 Gateway Timeout. Streaming responses are only
+    /// bounded until the stream starts. Disabled if not specified.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_request_duration_seconds: Option<u64>,
+    /// Budget in milliseconds for a backend to produce its first content chunk
+    ///
+    /// Aimed at stuck or heavily queued backends: an endpoint that hasn't started
+    /// generating within the budget is abandoned and the request moves to another
+    /// endpoint, well before the endpoint timeout would fire. Once the first chunk
+    /// arrives only the endpoint timeout applies. Disabled if not specified.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_token_timeout_ms: Option<u64>,
+    /// Maximum accepted request body size in bytes
+    ///
+    /// Larger bodies are rejected with 413 Payload Too Large before any routing
+    /// work happens. The default leaves ample room for long prompts.
+    #[serde(default = "default_max_request_body_bytes")]
+    pub max_request_body_bytes: usize,
+    /// Most messages a chat request may carry
+    ///
+    /// Longer conversations are rejected with 400 Bad Request before routing, so
+    /// they never cost a backend call. Defaults to the hard ceiling of 100.
+    #[serde(default = "default_max_messages")]
+    pub max_messages: usize,
+    /// Most characters the messages of a chat request may add up to
+    ///
+    /// Checked alongside `max_messages`. Defaults to the hard ceiling of 500,000.
+    #[serde(default = "default_max_total_prompt_chars")]
+    pub max_total_prompt_chars: usize,
+    /// Probe every endpoint once at startup, before accepting traffic
+    ///
+    /// Gives the health checker a real initial state instead of waiting for the
+    /// first scheduled check. Unreachable endpoints are logged but never block
+    /// startup.
+    #[serde(default)]
+    pub warmup: bool,
+    /// Gzip chat responses, including SSE streams, for clients sending `Accept-Encoding: gzip`
+    ///
+    /// Applied with tower-http's `CompressionLayer` when the routes are built,
+    /// so a change takes a restart. Off by default: most deployments sit on
+    /// fast local links where the CPU cost outweighs the bandwidth saved.
+    #[serde(default)]
+    pub enable_compression: bool,
+    /// Connection pool for Octoroute's own upstream HTTP client
+    #[serde(default)]
+    pub http_pool: HttpPoolConfig,
+    /// Per-tier limits on concurrently served requests
+    #[serde(default)]
+    pub tier_concurrency: TierConcurrencyConfig,
+    /// Wait up to this many milliseconds for a `tier_concurrency` slot before shedding
+    ///
+    /// Selects queueing over immediate shedding: a request for a tier at its
+    /// limit waits in FIFO order for a slot to free up, and only gets the 503
+    /// when none does in time. Smooths out short bursts without letting the
+    /// queue grow unbounded. Requests are shed immediately if not specified.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_queue_wait_ms: Option<u64>,
+    /// Cap on upstream model calls per request, router and completion calls combined
+    ///
+    /// Router retries and completion retries (including streaming failover)
+    /// share this one budget, so retry settings can't multiply into an
+    /// unbounded number of calls. Unlimited if not specified.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_upstream_calls: Option<usize>,
+    /// Most upstream calls one request may have in flight at once
+    ///
+    /// A completion asking for several choices (`n > 1`) makes one upstream
+    /// call per choice. At most this many run in parallel; `fan_out_overflow`
+    /// decides whether the rest wait their turn or the request is refused.
+    /// Unlimited if not specified.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_parallel_upstream_per_request: Option<usize>,
+    /// Handling of a request fanning out past `max_parallel_upstream_per_request`
+    #[serde(default)]
+    pub fan_out_overflow: FanOutOverflow,
+    /// Handling of a completion that comes back with no content
+    #[serde(default)]
+    pub on_empty_completion: EmptyCompletionPolicy,
+    /// Bearer token for the `/admin` API (endpoint drain/undrain)
+    ///
+    /// The admin routes are only mounted when this is set. Skipped when the
+    /// config is serialized, so it can't leak into dumps of it.
+    #[serde(default, skip_serializing)]
+    pub admin_token: Option<String>,
+    /// Per-user request counting keyed by the OpenAI `user` field (disabled if not specified)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_tracking: Option<UserTrackingConfig>,
+}
+
+impl ServerConfig {
+    /// Every address the public listener binds: `host`, then `extra_hosts`,
+    /// each followed by its `dual_stack` counterpart, without duplicates
+    ///
+    /// # Errors
+    /// Returns [`crate::error::AppError::Config`] naming the first address that
+    /// isn't an IP address, or one `dual_stack` has no counterpart for.
+    pub fn listen_addrs(&self) -> crate::error::AppResult<Vec<std::net::SocketAddr>> {
+        use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+        let hosts = std::iter::once(("server.host", &self.host)).chain(
+            self.extra_hosts
+                .iter()
+                .map(|host| ("server.extra_hosts", host)),
+        );
+        let mut addrs = Vec::new();
+        for (field, host) in hosts {
+            let ip: IpAddr = host.parse().map_err(|e| {
+                crate::error::AppError::Config(format!(
+                    "Configuration error: {} '{}' is not a valid IP address: {}. \
+                    Expected format: 0.0.0.0, ::, 127.0.0.1 or ::1",
+                    field, host, e
+                ))
+            })?;
+            let mut ips = vec![ip];
+            if self.dual_stack {
+                let counterpart = match ip {
+                    IpAddr::V4(Ipv4Addr::UNSPECIFIED) => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+                    IpAddr::V6(Ipv6Addr::UNSPECIFIED) => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+                    IpAddr::V4(Ipv4Addr::LOCALHOST) => IpAddr::V6(Ipv6Addr::LOCALHOST),
+                    IpAddr::V6(Ipv6Addr::LOCALHOST) => IpAddr::V4(Ipv4Addr::LOCALHOST),
+                    _ => {
+                        return Err(crate::error::AppError::Config(format!(
+                            "Configuration error: dual_stack has no counterpart for {} '{}'. \
+                            It pairs 0.0.0.0 with :: and 127.0.0.1 with ::1; list other \
+                            addresses in server.extra_hosts instead.",
+                            field, host
+                        )));
+                    }
+                };
+                ips.push(counterpart);
+            }
+            for ip in ips {
+                let addr = std::net::SocketAddr::from((ip, self.port));
+                if !addrs.contains(&addr) {
+                    addrs.push(addr);
+                }
+            }
+        }
+        Ok(addrs)
+    }
+
+    /// The first-token budget (`first_token_timeout_ms`), if configured
+    pub fn first_token_timeout(&self) -> Option<std::time::Duration> {
+        self.first_token_timeout_ms
+            .map(std::time::Duration::from_millis)
+    }
+
+    /// How long to queue for a tier concurrency slot (`max_queue_wait_ms`), if at all
+    pub fn max_queue_wait(&self) -> Option<std::time::Duration> {
+        self.max_queue_wait_ms.map(std::time::Duration::from_millis)
+    }
 }
 
 fn default_request_timeout() -> u64 {
     30
 }
 
+fn default_max_request_body_bytes() -> usize {
+    10 * 1024 * 1024
+}
+
+fn default_max_messages() -> usize {
+    crate::handlers::openai::types::MAX_MESSAGES
+}
+
+fn default_max_total_prompt_chars() -> usize {
+    crate::handlers::openai::types::MAX_TOTAL_CONTENT_LENGTH
+}
+
+fn default_sse_keepalive() -> u64 {
+    15
+}
+
+fn default_stream_failover_attempts() -> usize {
+    2
+}
+
+/// Handling of a request needing more upstream calls than `server.max_parallel_upstream_per_request`
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FanOutOverflow {
+    /// Run the calls anyway, never more than the cap at once
+    #[default]
+    Sequential,
+    /// Fail the request with 400, naming the limit
+    Reject,
+}
+
+/// Handling of a backend answer without any content (`server.on_empty_completion`)
+///
+/// Whitespace-only answers count as empty, as do answers left empty once
+/// reasoning blocks are stripped.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EmptyCompletionPolicy {
+    /// Pass the empty completion through to the client
+    #[default]
+    ReturnEmpty,
+    /// Count it as a failed attempt and try another endpoint of the tier
+    Retry,
+    /// Fail the request with 502 Bad Gateway
+    Error,
+}
+
+/// Connection pooling for the shared upstream HTTP client
+///
+/// Idle keep-alive connections to each backend are kept open and reused by
+/// later requests instead of paying a TCP (and TLS) handshake every time.
+/// Setting `max_idle_per_host = 0` disables reuse.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HttpPoolConfig {
+    /// Maximum idle connections kept open per backend host
+    #[serde(default = "default_pool_max_idle_per_host")]
+    pub max_idle_per_host: usize,
+    /// Seconds an idle connection is kept before it is closed
+    #[serde(default = "default_pool_idle_timeout")]
+    pub idle_timeout_seconds: u64,
+}
+
+impl Default for HttpPoolConfig {
+    fn default() -> Self {
+        Self {
+            max_idle_per_host: default_pool_max_idle_per_host(),
+            idle_timeout_seconds: default_pool_idle_timeout(),
+        }
+    }
+}
+
+fn default_pool_max_idle_per_host() -> usize {
+    32
+}
+
+fn default_pool_idle_timeout() -> u64 {
+    90
+}
+
+/// Per-user request counting for abuse detection (`[server.user_tracking]`)
+///
+/// Requests carrying the OpenAI `user` field are counted per user in fixed
+/// windows, in memory. A user going over `max_requests` in a window is logged
+/// and, with `reject_over_limit`, refused with 429 until the window ends. User
+/// IDs never become metric labels; at most `max_users` are tracked at once.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct UserTrackingConfig {
+    /// Requests a user may send per window before being flagged
+    pub max_requests: u64,
+    /// Length of a counting window in seconds
+    #[serde(default = "default_user_tracking_window")]
+    pub window_seconds: u64,
+    /// Refuse requests over the limit with 429 instead of only logging them
+    #[serde(default)]
+    pub reject_over_limit: bool,
+    /// Most users tracked at once; the stalest window is dropped to make room
+    #[serde(default = "default_user_tracking_max_users")]
+    pub max_users: usize,
+}
+
+fn default_user_tracking_window() -> u64 {
+    60
+}
+
+fn default_user_tracking_max_users() -> usize {
+    10_000
+}
+
+/// Concurrency budget for each tier (`[server.tier_concurrency]`)
+///
+/// Caps how many requests routed to a tier are served at once, so a flood of
+/// slow Deep requests can't take every upstream worker away from Fast ones.
+/// A request arriving while its tier's budget is used up is shed with 503.
+/// Tiers without a value are unlimited.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct TierConcurrencyConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fast: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balanced: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deep: Option<usize>,
+}
+
+impl TierConcurrencyConfig {
+    /// Budget for `tier`, or `None` if the tier is unlimited
+    pub fn for_tier(&self, tier: TargetModel) -> Option<usize> {
+        match tier {
+            TargetModel::Fast => self.fast,
+            TargetModel::Balanced => self.balanced,
+            TargetModel::Deep => self.deep,
+        }
+    }
+}
+
 /// Models configuration (multi-model support)
 ///
 /// Each tier (fast, balanced, deep) can have multiple model endpoints
@@ -43,6 +357,131 @@ pub struct ModelsConfig {
     pub deep: Vec<ModelEndpoint>,
 }
 
+/// Share of a priority group's traffic above which one endpoint triggers a weight warning
+const DOMINANT_TRAFFIC_PERCENT: f64 = 95.0;
+
+impl ModelsConfig {
+    /// Endpoints configured for `tier`
+    pub fn tier(&self, tier: TargetModel) -> &[ModelEndpoint] {
+        match tier {
+            TargetModel::Fast => &self.fast,
+            TargetModel::Balanced => &self.balanced,
+            TargetModel::Deep => &self.deep,
+        }
+    }
+
+    /// Largest prompt `tier` can take, or `None` if any of its endpoints is unlimited
+    ///
+    /// The largest `context_window` in the tier: selection skips endpoints too
+    /// small for a prompt, so the tier fits as long as one endpoint does.
+    pub fn tier_context_window(&self, tier: TargetModel) -> Option<usize> {
+        self.tier(tier)
+            .iter()
+            .map(ModelEndpoint::context_window)
+            .try_fold(0, |largest, window| window.map(|w| largest.max(w)))
+    }
+
+    /// Largest prompt any tier can take, or `None` if some tier is unlimited
+    pub fn largest_context_window(&self) -> Option<usize> {
+        [TargetModel::Fast, TargetModel::Balanced, TargetModel::Deep]
+            .into_iter()
+            .try_fold(0, |largest, tier| {
+                self.tier_context_window(tier).map(|w| largest.max(w))
+            })
+    }
+
+    /// Returns true if any endpoint declares a `context_window`
+    pub fn has_context_windows(&self) -> bool {
+        [&self.fast, &self.balanced, &self.deep]
+            .into_iter()
+            .flatten()
+            .any(|endpoint| endpoint.context_window.is_some())
+    }
+
+    /// Effective traffic percentage of each endpoint in `tier`, in config order
+    ///
+    /// Weights only compete within a priority group, so each endpoint's share is
+    /// its weight over the total weight of endpoints with the same priority, with
+    /// every endpoint assumed healthy. Percentages sum to 100 per priority group.
+    pub fn effective_weights(&self, tier: TargetModel) -> Vec<(String, f64)> {
+        let endpoints = self.tier(tier);
+        endpoints
+            .iter()
+            .map(|endpoint| {
+                let group_weight: f64 = endpoints
+                    .iter()
+                    .filter(|other| other.priority == endpoint.priority)
+                    .map(|other| other.weight)
+                    .sum();
+                (
+                    endpoint.name.clone(),
+                    endpoint.weight / group_weight * 100.0,
+                )
+            })
+            .collect()
+    }
+
+    /// Warnings for priority groups where one endpoint takes nearly all the traffic
+    ///
+    /// Flags any endpoint sharing its priority with others but receiving more than
+    /// 95% of the group's traffic, which usually means a weight was mistyped (e.g.
+    /// `100` instead of `1.0`). Single-endpoint groups are never flagged.
+    pub fn weight_warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        for (tier_name, tier) in [
+            ("fast", TargetModel::Fast),
+            ("balanced", TargetModel::Balanced),
+            ("deep", TargetModel::Deep),
+        ] {
+            let endpoints = self.tier(tier);
+            for (endpoint, (name, percent)) in endpoints.iter().zip(self.effective_weights(tier)) {
+                let group_size = endpoints
+                    .iter()
+                    .filter(|other| other.priority == endpoint.priority)
+                    .count();
+                if group_size > 1 && percent > DOMINANT_TRAFFIC_PERCENT {
+                    warnings.push(format!(
+                        "Endpoint '{}' in tier '{}' receives {:.1}% of priority {} traffic \
+                        (weight {} vs {} other endpoint(s)); check the weights if this is unintended",
+                        name,
+                        tier_name,
+                        percent,
+                        endpoint.priority,
+                        endpoint.weight,
+                        group_size - 1
+                    ));
+                }
+            }
+        }
+        warnings
+    }
+
+    /// Log each endpoint's effective traffic share and any weight warnings
+    pub fn log_traffic_shares(&self) {
+        for (tier_name, tier) in [
+            ("fast", TargetModel::Fast),
+            ("balanced", TargetModel::Balanced),
+            ("deep", TargetModel::Deep),
+        ] {
+            for (endpoint, (name, percent)) in
+                self.tier(tier).iter().zip(self.effective_weights(tier))
+            {
+                tracing::info!(
+                    tier = tier_name,
+                    endpoint = %name,
+                    priority = endpoint.priority,
+                    weight = endpoint.weight,
+                    traffic_percent = format!("{:.1}", percent),
+                    "Effective endpoint traffic share within priority group"
+                );
+            }
+        }
+        for warning in self.weight_warnings() {
+            tracing::warn!("{}", warning);
+        }
+    }
+}
+
 /// Individual model endpoint configuration
 ///
 /// All fields are private to enforce invariants. Configuration is loaded via
@@ -51,6 +490,9 @@ pub struct ModelsConfig {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ModelEndpoint {
     name: String,
+    /// Model string sent to the backend (defaults to `name`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    model: Option<String>,
     base_url: String,
     max_tokens: usize,
     #[serde(default = "default_temperature")]
@@ -61,6 +503,33 @@ pub struct ModelEndpoint {
     /// Priority level - higher priority endpoints are tried first
     #[serde(default = "default_priority")]
     priority: u8,
+    /// Upstream request timeout override in seconds (falls back to tier/server default)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    request_timeout_seconds: Option<u64>,
+    /// Maximum concurrent requests routed to this endpoint (unlimited if not specified)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_in_flight: Option<usize>,
+    /// Free-form labels (e.g., "code") used to steer requests to specialized endpoints
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    /// Price per 1000 tokens (prompt + completion) for cost estimates (uncosted if not specified)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    cost_per_1k_tokens: Option<f64>,
+    /// Largest prompt in estimated tokens the model accepts (unlimited if not specified)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    context_window: Option<usize>,
+    /// Remove reasoning blocks (e.g. `<think>...</think>`) from responses
+    #[serde(default)]
+    strip_reasoning_tags: bool,
+    /// Tag names delimiting reasoning blocks, without angle brackets
+    #[serde(default = "default_reasoning_tags")]
+    reasoning_tags: Vec<String>,
+    /// Accept a `base_url` that doesn't end with `/v1` (non-standard gateways)
+    #[serde(default)]
+    skip_url_check: bool,
+    /// Path under `base_url` probed with GET for health checks (defaults to HEAD `/models`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    health_path: Option<String>,
 }
 
 impl ModelEndpoint {
@@ -69,6 +538,14 @@ impl ModelEndpoint {
         &self.name
     }
 
+    /// Get the model string sent to the backend
+    ///
+    /// This is `model` when configured and `name` otherwise. Selection, health
+    /// tracking and metrics always key on [`Self::name`].
+    pub fn model(&self) -> &str {
+        self.model.as_deref().unwrap_or(&self.name)
+    }
+
     /// Get the endpoint base URL
     pub fn base_url(&self) -> &str {
         &self.base_url
@@ -93,6 +570,74 @@ impl ModelEndpoint {
     pub fn priority(&self) -> u8 {
         self.priority
     }
+
+    /// Get the per-endpoint request timeout override in seconds (if configured)
+    pub fn request_timeout_seconds(&self) -> Option<u64> {
+        self.request_timeout_seconds
+    }
+
+    /// Get the maximum number of concurrent in-flight requests (if configured)
+    ///
+    /// An endpoint at this limit is skipped by selection; see `routing.spillover`
+    /// for what happens when every endpoint in the top priority group is at its limit.
+    pub fn max_in_flight(&self) -> Option<usize> {
+        self.max_in_flight
+    }
+
+    /// Get the tags attached to this endpoint
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    /// Returns true if this endpoint carries every tag in `required`
+    ///
+    /// An empty `required` list matches every endpoint.
+    pub fn has_tags(&self, required: &[String]) -> bool {
+        required.iter().all(|tag| self.tags.contains(tag))
+    }
+
+    /// Get the price per 1000 tokens (if configured)
+    pub fn cost_per_1k_tokens(&self) -> Option<f64> {
+        self.cost_per_1k_tokens
+    }
+
+    /// Estimated cost of a request that used `total_tokens`, or `None` if the
+    /// endpoint has no `cost_per_1k_tokens`
+    pub fn estimated_cost(&self, total_tokens: u32) -> Option<f64> {
+        self.cost_per_1k_tokens
+            .map(|rate| f64::from(total_tokens) / 1000.0 * rate)
+    }
+
+    /// Get the context window in tokens (if configured)
+    pub fn context_window(&self) -> Option<usize> {
+        self.context_window
+    }
+
+    /// Returns true if a prompt of `token_estimate` tokens fits the context window
+    pub fn fits_context(&self, token_estimate: usize) -> bool {
+        self.context_window
+            .is_none_or(|window| token_estimate <= window)
+    }
+
+    /// Whether reasoning blocks are removed from this endpoint's responses
+    pub fn strip_reasoning_tags(&self) -> bool {
+        self.strip_reasoning_tags
+    }
+
+    /// Get the tag names delimiting reasoning blocks (used when stripping is enabled)
+    pub fn reasoning_tags(&self) -> &[String] {
+        &self.reasoning_tags
+    }
+
+    /// Whether the `/v1` suffix check on `base_url` is disabled
+    pub fn skip_url_check(&self) -> bool {
+        self.skip_url_check
+    }
+
+    /// Get the health probe path, or `None` to probe `/models`
+    pub fn health_path(&self) -> Option<&str> {
+        self.health_path.as_deref()
+    }
 }
 
 fn default_temperature() -> f64 {
@@ -107,6 +652,10 @@ fn default_priority() -> u8 {
     1
 }
 
+fn default_reasoning_tags() -> Vec<String> {
+    vec!["think".to_string()]
+}
+
 /// Router query timeout configuration per tier
 ///
 /// Allows different timeout values for router queries based on model size.
@@ -244,6 +793,170 @@ pub struct RoutingConfig {
     /// Can be customized per tier to accommodate different model response times.
     #[serde(default)]
     pub router_timeouts: RouterTimeouts,
+    /// Dedicated endpoint for LLM router queries (`[routing.router_endpoint]`)
+    ///
+    /// When set, routing decisions are asked of this endpoint instead of one
+    /// from `router_tier`, so the router model never competes with serving
+    /// traffic. It belongs to no serving tier and is not health checked; if a
+    /// query to it fails, the router falls back to the `router_tier` endpoints.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub router_endpoint: Option<ModelEndpoint>,
+    /// Sticky session routing TTL in seconds (disabled if not specified)
+    ///
+    /// When set, `model: "auto"` requests carrying an `x-octoroute-session` header
+    /// (or the OpenAI `user` field) reuse the tier chosen for that session's first
+    /// request until the TTL elapses, instead of re-invoking the router. The TTL is
+    /// measured from the routing decision; the next request after expiry is routed
+    /// afresh and starts a new sticky window.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sticky_session_ttl_seconds: Option<u64>,
+    /// Serve from a lower tier when the target tier has no available endpoint
+    ///
+    /// Disabled by default: a request for an exhausted tier fails with 503. When
+    /// enabled, Deep falls back to Balanced then Fast, and Balanced falls back to
+    /// Fast. Each fallback is counted in `octoroute_tier_fallback_total`.
+    #[serde(default)]
+    pub tier_fallback: bool,
+    /// Spill over to the next priority group when the top group is saturated
+    ///
+    /// Disabled by default: when every healthy endpoint in the highest priority
+    /// group is at its `max_in_flight` limit, selection fails rather than sending
+    /// traffic to lower-priority endpoints. When enabled, saturated endpoints are
+    /// skipped like unhealthy ones and the next priority group is used.
+    #[serde(default)]
+    pub spillover: bool,
+    /// How an endpoint is picked within the highest available priority group
+    ///
+    /// Defaults to `weighted` (weighted random). `cheapest` narrows the group
+    /// to its lowest `cost_per_1k_tokens` first and draws by weight among the
+    /// endpoints tied at that price.
+    #[serde(default)]
+    pub selection_mode: SelectionMode,
+    /// Price assumed for endpoints without `cost_per_1k_tokens` in `cheapest` mode
+    #[serde(default)]
+    pub uncosted_endpoints: UncostedEndpoints,
+    /// What happens when a prompt is larger than the routed tier's `context_window`
+    ///
+    /// Defaults to `reject` (400). `escalate` moves the request up to the first
+    /// larger tier whose context window fits it.
+    #[serde(default)]
+    pub context_overflow: ContextOverflow,
+    /// Largest token estimate routing decides on (uncapped if not specified)
+    ///
+    /// The estimate routing rules and the LLM router see is always capped at
+    /// the largest `context_window` any tier accepts, when every tier has one;
+    /// this lowers the cap further. Context window checks still use the full
+    /// estimate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_estimate_cap: Option<usize>,
+    /// Tier used when no routing rule matches in rule-only mode
+    ///
+    /// When unset, the tier holding the highest-priority endpoint is used (see
+    /// `ModelSelector::default_tier`). Setting it decouples the fallback tier
+    /// from endpoint priorities.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_tier: Option<TargetModel>,
+    /// Base delay in milliseconds between LLM router retry attempts
+    ///
+    /// Doubles after each failed attempt, with up to 50% random jitter added so
+    /// concurrent requests don't retry a recovering router endpoint in lockstep.
+    /// `0` retries immediately. Defaults to 100ms.
+    #[serde(default = "default_router_retry_backoff_ms")]
+    pub retry_backoff_ms: u64,
+    /// LLM router attempt budgets per failure kind (`[routing.retry_policy]`)
+    #[serde(default)]
+    pub retry_policy: RouterRetryPolicy,
+    /// Times an empty LLM router answer is asked again of the same endpoint (0 or 1)
+    ///
+    /// Defaults to 0: an empty answer fails the request like any other systemic
+    /// router failure. With 1, a single empty answer is treated as a fluke and
+    /// the query repeated once before failing. Unparseable answers and refusals
+    /// still fail fast.
+    #[serde(default)]
+    pub router_same_endpoint_retries: usize,
+    /// House system prompt sent to backends with every completion
+    ///
+    /// Applied after routing, so it never influences the tier decision. Mutually
+    /// exclusive with `system_prompt_file`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_prompt: Option<String>,
+    /// Path to a file holding the house system prompt (read once at startup)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_prompt_file: Option<String>,
+    /// How the house system prompt combines with a client-supplied system message
+    #[serde(default)]
+    pub system_prompt_mode: SystemPromptMode,
+    /// What `strategy = "llm"` does when the router model fails systemically
+    ///
+    /// Defaults to `error` (the request fails). Transient failures such as
+    /// timeouts are retried as usual and never trigger the fallback.
+    #[serde(default)]
+    pub llm_failure_fallback: LlmFailureFallback,
+    /// Tier to use when the router's answer names no tier at all
+    ///
+    /// Defaults to `error`. Narrower than `llm_failure_fallback`: it only covers
+    /// unparseable answers (not refusals, empty or oversized ones), applies to
+    /// the LLM path of `hybrid` too, and takes precedence when both are set.
+    #[serde(default)]
+    pub on_unparseable: UnparseableFallback,
+    /// Instruction placed after the user request in LLM router prompts
+    ///
+    /// Text after the user input carries the most weight with the router model,
+    /// so this is where the prompt-injection reinforcement lives. Defaults to
+    /// `DEFAULT_ROUTER_GUARD_SUFFIX`; a replacement should still ask for a
+    /// single tier word.
+    #[serde(default = "default_router_guard_suffix")]
+    pub router_guard_suffix: String,
+    /// Fence the user request between `<<<USER>>>` and `<<<END>>>` in router prompts
+    ///
+    /// Enabled by default. Copies of either marker are stripped from the user
+    /// request first, so it cannot close the fence and pose as instructions.
+    #[serde(default = "default_router_prompt_delimiters")]
+    pub router_prompt_delimiters: bool,
+    /// Preferred tier per task type (`[routing.task_affinity]`)
+    #[serde(default)]
+    pub task_affinity: TaskAffinityConfig,
+    /// Extra words the LLM router may answer with per tier (`[routing.tier_keywords]`)
+    #[serde(default)]
+    pub tier_keywords: TierKeywordsConfig,
+    /// Guidance per importance level for the LLM router (`[routing.importance_guidance]`)
+    #[serde(default)]
+    pub importance_guidance: ImportanceGuidanceConfig,
+    /// Cache of LLM routing decisions (`[routing.cache]`, disabled if not specified)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache: Option<RoutingCacheConfig>,
+    /// Infer `task_type` from the message when a `/chat` request omits it
+    ///
+    /// Off by default, which leaves such requests at `question_answer`. A
+    /// `task_type` sent by the client is always used as given.
+    #[serde(default)]
+    pub auto_classify_task: bool,
+    /// HTTP statuses from a backend that fail the request over to another endpoint
+    ///
+    /// Defaults to [`DEFAULT_RETRYABLE_STATUSES`]. Any other error status (a 400
+    /// for a malformed request, say) is returned to the client at once, since
+    /// another endpoint would reject the same request.
+    #[serde(default = "default_retryable_statuses")]
+    pub retryable_statuses: Vec<u16>,
+}
+
+/// Backend statuses retried on another endpoint unless `routing.retryable_statuses` is set
+pub const DEFAULT_RETRYABLE_STATUSES: &[u16] = &[429, 500, 502, 503, 504];
+
+fn default_retryable_statuses() -> Vec<u16> {
+    DEFAULT_RETRYABLE_STATUSES.to_vec()
+}
+
+fn default_router_retry_backoff_ms() -> u64 {
+    crate::router::DEFAULT_ROUTER_RETRY_BACKOFF_MS
+}
+
+fn default_router_guard_suffix() -> String {
+    crate::router::DEFAULT_ROUTER_GUARD_SUFFIX.to_string()
+}
+
+fn default_router_prompt_delimiters() -> bool {
+    true
 }
 
 impl RoutingConfig {
@@ -275,68 +988,501 @@ impl RoutingConfig {
             TargetModel::Deep => self.router_timeouts.deep(),
         }
     }
-}
 
-/// Routing strategy enum
-#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
-#[serde(rename_all = "lowercase")]
-pub enum RoutingStrategy {
-    Rule,
-    Llm,
-    Hybrid,
-    Tool,
+    /// Whether a backend answering with HTTP `status` should be retried elsewhere
+    pub fn is_retryable_status(&self, status: u16) -> bool {
+        self.retryable_statuses.contains(&status)
+    }
 }
 
-/// Observability configuration
-#[derive(Debug, Clone, Deserialize, Serialize)]
-pub struct ObservabilityConfig {
-    #[serde(default = "default_log_level")]
-    pub log_level: String,
+/// Preferred tier for each task type (`[routing.task_affinity]`)
+///
+/// A declarative shortcut for "all creative writing goes to Deep" without
+/// writing rules. The rule-based stage (`rule` and `hybrid` strategies) routes
+/// a task type with an affinity straight to that tier, ahead of the token and
+/// importance rules; the LLM router is told the preference as a hint but still
+/// decides. Task types without an entry use the normal logic.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct TaskAffinityConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub casual_chat: Option<TargetModel>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<TargetModel>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub creative_writing: Option<TargetModel>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deep_analysis: Option<TargetModel>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub document_summary: Option<TargetModel>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub question_answer: Option<TargetModel>,
 }
 
-impl Default for ObservabilityConfig {
-    fn default() -> Self {
-        Self {
-            log_level: default_log_level(),
+impl TaskAffinityConfig {
+    /// Preferred tier for `task_type`, or `None` to use the normal logic
+    pub fn for_task(&self, task_type: TaskType) -> Option<TargetModel> {
+        match task_type {
+            TaskType::CasualChat => self.casual_chat,
+            TaskType::Code => self.code,
+            TaskType::CreativeWriting => self.creative_writing,
+            TaskType::DeepAnalysis => self.deep_analysis,
+            TaskType::DocumentSummary => self.document_summary,
+            TaskType::QuestionAnswer => self.question_answer,
         }
     }
 }
 
-fn default_log_level() -> String {
-    "info".to_string()
+/// Router prompt guidance for each importance level (`[routing.importance_guidance]`)
+///
+/// The LLM router always sees a request's importance, but nothing tells it
+/// what to make of it. Text configured here (for example "High importance:
+/// prefer BALANCED or DEEP") is added to the router prompt for requests of
+/// that importance. Levels without an entry get no guidance, which is the
+/// default for all three.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ImportanceGuidanceConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub low: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub normal: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub high: Option<String>,
 }
 
-/// Per-tier timeout overrides
-///
-/// Allows configuring different timeouts for each model tier.
-/// If a tier timeout is not specified in the config file, the global
-/// `server.request_timeout_seconds` is used as the default.
+impl ImportanceGuidanceConfig {
+    /// Guidance for `importance`, or `None` if none is configured
+    pub fn for_importance(&self, importance: Importance) -> Option<&str> {
+        match importance {
+            Importance::Low => self.low.as_deref(),
+            Importance::Normal => self.normal.as_deref(),
+            Importance::High => self.high.as_deref(),
+        }
+    }
+}
+
+/// Additional keywords accepted from the LLM router for each tier (`[routing.tier_keywords]`)
 ///
-/// # Custom Deserialization
+/// For router models that answer in another language. The English `FAST`,
+/// `BALANCED` and `DEEP` are always accepted; keywords listed here are matched
+/// alongside them, case-insensitively, at word boundaries, and the leftmost
+/// keyword in the answer decides the tier.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct TierKeywordsConfig {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fast: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub balanced: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deep: Vec<String>,
+}
+
+impl TierKeywordsConfig {
+    /// Configured extra keywords for `tier` (without the English default)
+    pub fn for_tier(&self, tier: TargetModel) -> &[String] {
+        match tier {
+            TargetModel::Fast => &self.fast,
+            TargetModel::Balanced => &self.balanced,
+            TargetModel::Deep => &self.deep,
+        }
+    }
+}
+
+/// How many LLM router attempts may fail per failure kind (`[routing.retry_policy]`)
 ///
-/// This type implements custom `Deserialize` to enforce validation at parse time.
-/// All timeout values must be in range (0, 300] seconds. Invalid values are rejected
-/// immediately during TOML parsing, not later during `Config::validate()`.
+/// A connection failure means the router endpoint could not be reached or
+/// refused the query before sending anything; a stream failure means the answer
+/// broke off or did not finish within the router timeout. Each kind stops the
+/// retries once its own budget is used up, and a single request never makes
+/// more than the larger of the two attempts in total. Systemic failures
+/// (unparseable or refused answers) are never retried.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub struct RouterRetryPolicy {
+    /// Attempts that may end in a connection failure (default 2)
+    #[serde(default = "default_router_retry_attempts")]
+    pub connection_attempts: usize,
+    /// Attempts that may end in a stream failure or timeout (default 2)
+    #[serde(default = "default_router_retry_attempts")]
+    pub stream_attempts: usize,
+}
+
+/// Cache of LLM routing decisions (`[routing.cache]`)
 ///
-/// This prevents the temporal gap where invalid `TimeoutsConfig` instances could exist
-/// between deserialization and validation, upholding the principle: "make invalid states
-/// unrepresentable."
-#[derive(Debug, Clone, Default, Serialize)]
-pub struct TimeoutsConfig {
-    /// Timeout for fast tier (8B models) in seconds
-    fast: Option<u64>,
-    /// Timeout for balanced tier (30B models) in seconds
-    balanced: Option<u64>,
-    /// Timeout for deep tier (120B models) in seconds
-    deep: Option<u64>,
+/// A prompt the router model has already classified (same normalized text,
+/// task type and importance) reuses the cached tier instead of querying the
+/// router again until `ttl_seconds` elapse. The `memory` backend is private to
+/// each instance; `redis` lets a fleet share decisions and needs the `redis`
+/// Cargo feature.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct RoutingCacheConfig {
+    /// Where decisions are stored (default `memory`)
+    #[serde(default)]
+    pub backend: RoutingCacheBackend,
+    /// How long a cached decision is reused (default 300)
+    #[serde(default = "default_routing_cache_ttl_seconds")]
+    pub ttl_seconds: u64,
+    /// Most decisions the `memory` backend holds (default 10000)
+    #[serde(default = "default_routing_cache_max_entries")]
+    pub max_entries: usize,
+    /// Redis connection URL, required by the `redis` backend
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redis_url: Option<String>,
+    /// Prefix of the `redis` backend's keys (default `octoroute:route:`)
+    #[serde(default = "default_routing_cache_key_prefix")]
+    pub key_prefix: String,
+    /// Which prompt differences still count as the same prompt (default `whitespace`)
+    #[serde(default)]
+    pub normalization: crate::shared::prompt_cache_key::PromptNormalization,
 }
 
-impl TimeoutsConfig {
-    /// Create a new TimeoutsConfig with validated timeout values
-    ///
-    /// # Arguments
-    ///
-    /// * `fast` - Optional timeout for fast tier in seconds
+/// Storage behind `routing.cache`
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RoutingCacheBackend {
+    /// In-process map, bounded by `max_entries`
+    #[default]
+    Memory,
+    /// Shared Redis server (requires the `redis` Cargo feature)
+    Redis,
+}
+
+fn default_routing_cache_ttl_seconds() -> u64 {
+    300
+}
+
+fn default_routing_cache_max_entries() -> usize {
+    10_000
+}
+
+fn default_routing_cache_key_prefix() -> String {
+    "octoroute:route:".to_string()
+}
+
+impl RoutingCacheConfig {
+    /// How long a cached decision is reused
+    pub fn ttl(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.ttl_seconds)
+    }
+}
+
+/// Upper bound for each `routing.retry_policy` budget
+pub const MAX_ROUTER_RETRY_ATTEMPTS: usize = 10;
+
+fn default_router_retry_attempts() -> usize {
+    2
+}
+
+impl Default for RouterRetryPolicy {
+    fn default() -> Self {
+        Self {
+            connection_attempts: default_router_retry_attempts(),
+            stream_attempts: default_router_retry_attempts(),
+        }
+    }
+}
+
+impl RouterRetryPolicy {
+    /// Most attempts a single routing decision may make
+    pub fn max_attempts(&self) -> usize {
+        self.connection_attempts.max(self.stream_attempts)
+    }
+}
+
+/// Routing strategy enum
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RoutingStrategy {
+    Rule,
+    Llm,
+    Hybrid,
+    Tool,
+    /// Local keyword/length classifier; no router model is queried
+    Heuristic,
+}
+
+/// How `routing.system_prompt` combines with system messages sent by the client
+///
+/// Requests without a system message always get the house prompt prepended.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SystemPromptMode {
+    /// House prompt goes first; client system messages are kept where they are
+    #[default]
+    Prepend,
+    /// Client system messages are dropped in favor of the house prompt
+    Replace,
+    /// Client system messages are folded into a single leading system message,
+    /// after the house prompt
+    Merge,
+}
+
+/// Fallback for a systemic LLM router failure (unparseable, refusal, empty, oversized)
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LlmFailureFallback {
+    /// Fail the request with the router error
+    #[default]
+    Error,
+    /// Route with the rule-based router, using the default tier if no rule matches
+    Rule,
+    /// Route to the default tier (`routing.default_tier` or the highest-priority tier)
+    DefaultTier,
+}
+
+impl LlmFailureFallback {
+    /// Config spelling, for logs and warnings
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Rule => "rule",
+            Self::DefaultTier => "default_tier",
+        }
+    }
+}
+
+/// Endpoint choice within a priority group (`routing.selection_mode`)
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SelectionMode {
+    /// Weighted random over the whole group
+    #[default]
+    Weighted,
+    /// Lowest `cost_per_1k_tokens` first, weighted random among equal prices
+    Cheapest,
+}
+
+/// Price of an endpoint without `cost_per_1k_tokens` (`routing.uncosted_endpoints`)
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum UncostedEndpoints {
+    /// Costlier than any priced endpoint: used only when no priced one is available
+    #[default]
+    Expensive,
+    /// Free (e.g., self-hosted): preferred over every priced endpoint
+    Cheap,
+}
+
+/// Handling of prompts larger than the routed tier's context window (`routing.context_overflow`)
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ContextOverflow {
+    /// Fail the request with 400, naming the limit
+    #[default]
+    Reject,
+    /// Serve it from the next larger tier that fits (Fast → Balanced → Deep)
+    Escalate,
+}
+
+/// Fallback for a router answer that names no tier (`routing.on_unparseable`)
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UnparseableFallback {
+    /// Fail with the unparseable-response error
+    #[default]
+    Error,
+    /// Route to the Balanced tier
+    Balanced,
+    /// Route to the default tier (`routing.default_tier` or the highest-priority tier)
+    DefaultTier,
+}
+
+impl UnparseableFallback {
+    /// Config spelling, for logs and warnings
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Balanced => "balanced",
+            Self::DefaultTier => "default_tier",
+        }
+    }
+}
+
+/// Observability configuration
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ObservabilityConfig {
+    #[serde(default = "default_log_level")]
+    pub log_level: String,
+    /// How the chat handlers log the user prompt next to the routing decision
+    ///
+    /// Defaults to `none`: prompts may hold sensitive content, so logging them
+    /// has to be switched on explicitly.
+    #[serde(default)]
+    pub log_prompts: PromptLogMode,
+    /// Characters kept when `log_prompts = "truncated"`
+    #[serde(default = "default_log_prompt_chars")]
+    pub log_prompt_chars: usize,
+    /// Separate `host:port` listener for `/metrics` and `/health`
+    ///
+    /// Unset by default, and both are served on the public listener. When set,
+    /// they move to this address only, so operators can firewall it off from
+    /// API clients.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metrics_bind: Option<String>,
+    /// Honor the `x-octoroute-debug` request header
+    ///
+    /// When on, a non-streaming completion requested with
+    /// `x-octoroute-debug: true` carries an `octoroute_debug` field with the
+    /// routing decision and the router model's raw answer. Off by default,
+    /// since that answer reveals how the router prompt is built.
+    #[serde(default)]
+    pub router_debug: bool,
+    /// Name the serving endpoint in an `x-octoroute-endpoint` response header
+    ///
+    /// Off by default. When on, chat responses also get an
+    /// `x-octoroute-router-endpoint` header naming the endpoint that answered
+    /// the router query, if an LLM router made the decision.
+    #[serde(default)]
+    pub endpoint_headers: bool,
+}
+
+impl Default for ObservabilityConfig {
+    fn default() -> Self {
+        Self {
+            log_level: default_log_level(),
+            log_prompts: PromptLogMode::default(),
+            log_prompt_chars: default_log_prompt_chars(),
+            metrics_bind: None,
+            router_debug: false,
+            endpoint_headers: false,
+        }
+    }
+}
+
+impl ObservabilityConfig {
+    /// Parsed `metrics_bind` address (validated at config load)
+    pub fn metrics_bind_addr(&self) -> Option<std::net::SocketAddr> {
+        self.metrics_bind
+            .as_deref()
+            .and_then(|bind| bind.parse().ok())
+    }
+}
+
+fn default_log_level() -> String {
+    "info".to_string()
+}
+
+fn default_log_prompt_chars() -> usize {
+    200
+}
+
+/// Prompt logging mode (`observability.log_prompts`)
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PromptLogMode {
+    /// Prompts are never logged
+    #[default]
+    None,
+    /// A stable hash of the prompt, to correlate repeats without the content
+    Hashed,
+    /// The first `log_prompt_chars` characters of the prompt
+    Truncated,
+    /// The whole prompt
+    Full,
+}
+
+impl PromptLogMode {
+    /// Config spelling, for logs
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Hashed => "hashed",
+            Self::Truncated => "truncated",
+            Self::Full => "full",
+        }
+    }
+}
+
+/// Startup health requirements and health probe timeouts
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HealthConfig {
+    /// Track endpoint health at all
+    ///
+    /// When off, no background health task runs and every endpoint counts as
+    /// healthy, so selection never skips one. Meant for single-endpoint local
+    /// development and deterministic tests.
+    #[serde(default = "default_health_enabled")]
+    pub enabled: bool,
+    /// Refuse to start unless every required tier has a reachable endpoint
+    ///
+    /// Endpoints are probed before the listener is bound and re-probed until
+    /// `startup_grace_period_seconds` elapses. Catches mistyped `base_url`s at
+    /// startup instead of on the first request.
+    #[serde(default)]
+    pub require_healthy_at_startup: bool,
+    /// How long to keep re-probing before giving up on an unreachable tier
+    #[serde(default = "default_startup_grace_period")]
+    pub startup_grace_period_seconds: u64,
+    /// How long a health probe may take to open a connection
+    ///
+    /// A host that drops SYNs is reported as a timeout after this long instead
+    /// of waiting on the operating system's connect timeout.
+    #[serde(default = "default_probe_connect_timeout_ms")]
+    pub probe_connect_timeout_ms: u64,
+    /// How long a health probe waits for the endpoint's answer
+    ///
+    /// Catches endpoints that accept connections but never respond.
+    #[serde(default = "default_probe_read_timeout_ms")]
+    pub probe_read_timeout_ms: u64,
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_health_enabled(),
+            require_healthy_at_startup: false,
+            startup_grace_period_seconds: default_startup_grace_period(),
+            probe_connect_timeout_ms: default_probe_connect_timeout_ms(),
+            probe_read_timeout_ms: default_probe_read_timeout_ms(),
+        }
+    }
+}
+
+fn default_health_enabled() -> bool {
+    true
+}
+
+fn default_startup_grace_period() -> u64 {
+    10
+}
+
+fn default_probe_connect_timeout_ms() -> u64 {
+    2000
+}
+
+fn default_probe_read_timeout_ms() -> u64 {
+    5000
+}
+
+/// Per-tier timeout overrides
+///
+/// Allows configuring different timeouts for each model tier.
+/// If a tier timeout is not specified in the config file, the global
+/// `server.request_timeout_seconds` is used as the default.
+///
+/// # Custom Deserialization
+///
+/// This type implements custom `Deserialize` to enforce validation at parse time.
+/// All timeout values must be in range (0, 300] seconds. Invalid values are rejected
+/// immediately during TOML parsing, not later during `Config::validate()`.
+///
+/// This prevents the temporal gap where invalid `TimeoutsConfig` instances could exist
+/// between deserialization and validation, upholding the principle: "make invalid states
+/// unrepresentable."
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TimeoutsConfig {
+    /// Timeout for fast tier (8B models) in seconds
+    fast: Option<u64>,
+    /// Timeout for balanced tier (30B models) in seconds
+    balanced: Option<u64>,
+    /// Timeout for deep tier (120B models) in seconds
+    deep: Option<u64>,
+}
+
+impl TimeoutsConfig {
+    /// Create a new TimeoutsConfig with validated timeout values
+    ///
+    /// # Arguments
+    ///
+    /// * `fast` - Optional timeout for fast tier in seconds
     /// * `balanced` - Optional timeout for balanced tier in seconds
     /// * `deep` - Optional timeout for deep tier in seconds
     ///
@@ -477,52 +1623,169 @@ impl<'de> Deserialize<'de> for TimeoutsConfig {
     }
 }
 
-impl Config {
-    /// Load configuration from a TOML file
-    pub fn from_file<P: AsRef<Path>>(path: P) -> crate::error::AppResult<Self> {
-        let path_display = path.as_ref().display().to_string();
+/// On-disk config file format, detected from the file extension
+enum ConfigFormat {
+    Toml,
+    Yaml,
+}
 
-        // Phase 1: Read file (preserves io::Error context)
-        let content = std::fs::read_to_string(path.as_ref()).map_err(|source| {
-            let remediation = match source.kind() {
-                std::io::ErrorKind::NotFound => {
-                    let current_dir = std::env::current_dir()
-                        .map(|p| p.display().to_string())
-                        .unwrap_or_else(|_| "<unknown>".to_string());
-                    format!(
-                        "\nFile not found. Check that:\n\
-                        1. Path '{}' is correct\n\
-                        2. File exists and is readable\n\
-                        3. Current working directory is: {}",
-                        path_display, current_dir
-                    )
-                }
-                std::io::ErrorKind::PermissionDenied => {
-                    format!(
-                        "\nPermission denied. Check that:\n\
-                        1. File '{}' has read permissions (chmod +r)\n\
-                        2. Parent directories have execute permissions (chmod +x)\n\
-                        3. Process runs as user with file access",
-                        path_display
-                    )
-                }
-                _ => String::new(),
-            };
+/// Detect a config file's format and read it
+fn read_config_file(path: &Path) -> crate::error::AppResult<(ConfigFormat, String)> {
+    let path_display = path.display().to_string();
 
-            crate::error::AppError::ConfigFileRead {
-                path: path_display.clone(),
-                source,
-                remediation,
+    // Phase 0: Pick the parser before touching the file
+    let format = match path.extension().and_then(|ext| ext.to_str()) {
+        None | Some("toml") => ConfigFormat::Toml,
+        Some("yaml") | Some("yml") => ConfigFormat::Yaml,
+        Some(other) => {
+            return Err(crate::error::AppError::ConfigFormatUnsupported {
+                path: path_display,
+                extension: other.to_string(),
+            });
+        }
+    };
+
+    // Phase 1: Read file (preserves io::Error context)
+    let content = std::fs::read_to_string(path).map_err(|source| {
+        let remediation = match source.kind() {
+            std::io::ErrorKind::NotFound => {
+                let current_dir = std::env::current_dir()
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_else(|_| "<unknown>".to_string());
+                format!(
+                    "\nFile not found. Check that:\n\
+                    1. Path '{}' is correct\n\
+                    2. File exists and is readable\n\
+                    3. Current working directory is: {}",
+                    path_display, current_dir
+                )
             }
-        })?;
+            std::io::ErrorKind::PermissionDenied => {
+                format!(
+                    "\nPermission denied. Check that:\n\
+                    1. File '{}' has read permissions (chmod +r)\n\
+                    2. Parent directories have execute permissions (chmod +x)\n\
+                    3. Process runs as user with file access",
+                    path_display
+                )
+            }
+            _ => String::new(),
+        };
+
+        crate::error::AppError::ConfigFileRead {
+            path: path_display.clone(),
+            source,
+            remediation,
+        }
+    })?;
 
-        // Phase 2: Parse TOML (preserves toml::de::Error context)
-        let config: Self = toml::from_str(&content).map_err(|source| {
+    Ok((format, content))
+}
+
+/// Read a (possibly partial) config file as an untyped TOML value for merging
+fn read_config_value(path: &Path) -> crate::error::AppResult<toml::Value> {
+    let path_display = path.display().to_string();
+    let (format, content) = read_config_file(path)?;
+    let table: toml::Table = match format {
+        ConfigFormat::Toml => toml::from_str(&content).map_err(|source| {
             crate::error::AppError::ConfigParseFailed {
-                path: path_display.clone(),
+                path: path_display,
                 source,
             }
-        })?;
+        })?,
+        ConfigFormat::Yaml => serde_yaml::from_str(&content).map_err(|source| {
+            crate::error::AppError::ConfigYamlParseFailed {
+                path: path_display,
+                source,
+            }
+        })?,
+    };
+    Ok(toml::Value::Table(table))
+}
+
+/// Remove the override file's `append_arrays` list and return it
+fn take_append_arrays(overlay: &mut toml::Value) -> Result<Vec<String>, String> {
+    let Some(value) = overlay
+        .as_table_mut()
+        .and_then(|table| table.remove("append_arrays"))
+    else {
+        return Ok(Vec::new());
+    };
+    value
+        .as_array()
+        .and_then(|paths| {
+            paths
+                .iter()
+                .map(|path| path.as_str().map(str::to_string))
+                .collect::<Option<Vec<_>>>()
+        })
+        .ok_or_else(|| {
+            "append_arrays must be a list of dotted key paths, e.g. [\"models.fast\"]".to_string()
+        })
+}
+
+/// Merge `overlay` into `base` (see [`Config::from_files`] for the rules)
+///
+/// `path` is the dotted key path of `base`, matched against `append_arrays`.
+fn merge_config_values(
+    base: &mut toml::Value,
+    overlay: toml::Value,
+    append_arrays: &[String],
+    path: &str,
+) {
+    match (base, overlay) {
+        (toml::Value::Table(base), toml::Value::Table(overlay)) => {
+            for (key, value) in overlay {
+                let child_path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                match base.get_mut(&key) {
+                    Some(existing) => {
+                        merge_config_values(existing, value, append_arrays, &child_path)
+                    }
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (toml::Value::Array(base), toml::Value::Array(overlay))
+            if append_arrays.iter().any(|append| append == path) =>
+        {
+            base.extend(overlay);
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+impl Config {
+    /// Load configuration from a TOML or YAML file
+    ///
+    /// The format is chosen by extension: `.yaml` / `.yml` are parsed as YAML,
+    /// `.toml` (or no extension) as TOML. Any other extension is rejected with
+    /// `AppError::ConfigFormatUnsupported`. Both formats share the same schema
+    /// and validation.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> crate::error::AppResult<Self> {
+        let path_display = path.as_ref().display().to_string();
+        let (format, content) = read_config_file(path.as_ref())?;
+
+        // Phase 2: Parse (preserves the parser's line/column error context)
+        let config: Self = match format {
+            ConfigFormat::Toml => toml::from_str(&content).map_err(|source| {
+                crate::error::AppError::ConfigParseFailed {
+                    path: path_display.clone(),
+                    source,
+                }
+            })?,
+            ConfigFormat::Yaml => serde_yaml::from_str(&content).map_err(|source| {
+                crate::error::AppError::ConfigYamlParseFailed {
+                    path: path_display.clone(),
+                    source,
+                }
+            })?,
+        };
 
         // Phase 3: Validate parsed config (provides contextual reason)
         config
@@ -535,6 +1798,71 @@ impl Config {
         Ok(config)
     }
 
+    /// Load `path`, with `override_path` merged over it when given
+    ///
+    /// Shorthand for [`Config::from_file`] or [`Config::from_files`], used by
+    /// startup, `--check` and config reload alike.
+    pub fn load<P: AsRef<Path>>(
+        path: P,
+        override_path: Option<&Path>,
+    ) -> crate::error::AppResult<Self> {
+        match override_path {
+            Some(override_path) => Self::from_files(path, override_path),
+            None => Self::from_file(path),
+        }
+    }
+
+    /// Load a base config file with a partial override file merged over it
+    ///
+    /// Both files may be TOML or YAML, independently. The override is merged
+    /// before deserialization and validation, so it only needs the settings it
+    /// changes (e.g. `base_url`s or secrets kept out of git):
+    ///
+    /// - Tables merge key by key, recursively
+    /// - Any other value in the override, arrays included, replaces the base value
+    /// - Arrays whose dotted path is listed in the override's top-level
+    ///   `append_arrays` (e.g. `["models.fast"]`) are appended to instead
+    ///
+    /// `append_arrays` is consumed by the merge and never reaches the config.
+    pub fn from_files<P: AsRef<Path>, Q: AsRef<Path>>(
+        base: P,
+        override_path: Q,
+    ) -> crate::error::AppResult<Self> {
+        let override_display = override_path.as_ref().display().to_string();
+        let merged_display = format!(
+            "{} (with overrides from {})",
+            base.as_ref().display(),
+            override_display
+        );
+
+        let mut merged = read_config_value(base.as_ref())?;
+        let mut overlay = read_config_value(override_path.as_ref())?;
+        let append_arrays = take_append_arrays(&mut overlay).map_err(|reason| {
+            crate::error::AppError::ConfigValidationFailed {
+                path: override_display,
+                reason,
+            }
+        })?;
+        merge_config_values(&mut merged, overlay, &append_arrays, "");
+
+        let config: Self =
+            merged
+                .try_into()
+                .map_err(|source| crate::error::AppError::ConfigParseFailed {
+                    path: merged_display.clone(),
+                    source,
+                })?;
+
+        config
+            .validate()
+            .map_err(|e| crate::error::AppError::ConfigValidationFailed {
+                path: merged_display,
+                reason: e.to_string(),
+            })?;
+
+        Ok(config)
+    }
+
     /// Get timeout for a specific model tier
     ///
     /// Returns the per-tier timeout if configured, otherwise falls back to
@@ -566,6 +1894,28 @@ impl Config {
         }
     }
 
+    /// Get the upstream request timeout for a specific endpoint
+    ///
+    /// Resolution order: the endpoint's own `request_timeout_seconds`, then the
+    /// per-tier override in `[timeouts]`, then `server.request_timeout_seconds`.
+    pub fn timeout_for_endpoint(
+        &self,
+        endpoint: &ModelEndpoint,
+        tier: crate::router::TargetModel,
+    ) -> u64 {
+        match endpoint.request_timeout_seconds() {
+            Some(timeout) => {
+                tracing::debug!(
+                    endpoint_name = %endpoint.name(),
+                    timeout_seconds = timeout,
+                    "Using endpoint-specific timeout override"
+                );
+                timeout
+            }
+            None => self.timeout_for_tier(tier),
+        }
+    }
+
     /// Validate configuration after parsing
     ///
     /// This is called automatically by `from_file()`, but can also be called
@@ -579,12 +1929,17 @@ impl Config {
         //   - max_tokens: must fit in u32 (API compatibility)
         //   - base_url: must start with http:// or https://, must end with /v1
         //   - temperature: must be finite number between 0.0 and 2.0
+        //   - request_timeout_seconds: if set, must be in (0, 300] seconds
         //
-        // Validate ModelEndpoint fields across all tiers
+        // Validate ModelEndpoint fields across all tiers (and the dedicated router endpoint)
         for (tier_name, endpoints) in [
-            ("fast", &self.models.fast),
-            ("balanced", &self.models.balanced),
-            ("deep", &self.models.deep),
+            ("fast", self.models.fast.as_slice()),
+            ("balanced", self.models.balanced.as_slice()),
+            ("deep", self.models.deep.as_slice()),
+            (
+                "routing.router_endpoint",
+                self.routing.router_endpoint.as_slice(),
+            ),
         ] {
             for endpoint in endpoints {
                 // Validate weight: must be positive and not NaN
@@ -640,11 +1995,26 @@ impl Config {
                     )));
                 }
 
-                // Validate base_url: must end with /v1 (OpenAI API compatibility)
-                if !endpoint.base_url.ends_with("/v1") {
+                // Validate base_url: must parse as a URL with a host
+                if reqwest::Url::parse(&endpoint.base_url)
+                    .ok()
+                    .and_then(|url| url.host_str().map(|host| !host.is_empty()))
+                    != Some(true)
+                {
+                    return Err(crate::error::AppError::Config(format!(
+                        "Configuration error: Endpoint '{}' in tier '{}' has malformed base_url '{}'. \
+                        base_url must be a full URL with a host (e.g., 'http://host:port/v1').",
+                        endpoint.name, tier_name, endpoint.base_url
+                    )));
+                }
+
+                // Validate base_url: must end with /v1 (OpenAI API compatibility),
+                // unless the endpoint opts out for a gateway with its own layout
+                if !endpoint.skip_url_check && !endpoint.base_url.ends_with("/v1") {
                     return Err(crate::error::AppError::Config(format!(
                         "Configuration error: Endpoint '{}' in tier '{}' has invalid base_url '{}'. \
-                        base_url must end with '/v1' (e.g., 'http://host:port/v1') for OpenAI API compatibility.",
+                        base_url must end with '/v1' (e.g., 'http://host:port/v1') for OpenAI API compatibility. \
+                        Set skip_url_check = true on the endpoint if the gateway serves the API at another path.",
                         endpoint.name, tier_name, endpoint.base_url
                     )));
                 }
@@ -661,25 +2031,123 @@ impl Config {
                         endpoint.name, tier_name, endpoint.temperature
                     )));
                 }
-            }
-        }
 
-        // ═══════════════════════════════════════════════════════════════════════
-        // Phase 2: All-Tier Availability Validation
-        // ═══════════════════════════════════════════════════════════════════════
-        //
-        // P1 FIX: Require ALL tiers (Fast/Balanced/Deep) to have at least one endpoint.
-        //
-        // RATIONALE: Both RuleBasedRouter and LlmBasedRouter can route to ANY tier
-        // based on request characteristics. If a tier is empty and gets selected,
-        // requests fail at runtime with "No available healthy endpoints" instead
-        // of failing at startup validation.
-        //
-        // This validation ensures config errors are caught at startup, not runtime.
-        //
-        // Note: router_tier format is already validated during deserialization (Phase 1).
+                // Validate request_timeout_seconds: same (0, 300] range as server/tier timeouts
+                if let Some(timeout) = endpoint.request_timeout_seconds
+                    && (timeout == 0 || timeout > 300)
+                {
+                    return Err(crate::error::AppError::Config(format!(
+                        "Configuration error: Endpoint '{}' in tier '{}' has invalid request_timeout_seconds {}. \
+                        request_timeout_seconds must be between 1 and 300 seconds.",
+                        endpoint.name, tier_name, timeout
+                    )));
+                }
 
-        // Check Fast tier
+                // Validate model override: a blank model string would be rejected by every backend
+                if endpoint
+                    .model
+                    .as_deref()
+                    .is_some_and(|model| model.trim().is_empty())
+                {
+                    return Err(crate::error::AppError::Config(format!(
+                        "Configuration error: Endpoint '{}' in tier '{}' has an empty model. \
+                        Set it to the backend's model name, or omit it to send the endpoint name.",
+                        endpoint.name, tier_name
+                    )));
+                }
+
+                // Validate max_in_flight: 0 would make the endpoint permanently unselectable
+                if endpoint.max_in_flight == Some(0) {
+                    return Err(crate::error::AppError::Config(format!(
+                        "Configuration error: Endpoint '{}' in tier '{}' has max_in_flight 0. \
+                        max_in_flight must be at least 1; omit it for no limit.",
+                        endpoint.name, tier_name
+                    )));
+                }
+
+                // Validate tags: a blank tag can never be meaningfully required
+                if endpoint.tags.iter().any(|tag| tag.trim().is_empty()) {
+                    return Err(crate::error::AppError::Config(format!(
+                        "Configuration error: Endpoint '{}' in tier '{}' has an empty tag. \
+                        Remove it or give it a name (e.g., tags = [\"code\"]).",
+                        endpoint.name, tier_name
+                    )));
+                }
+
+                // Validate cost rate: a negative or non-finite price would corrupt the cost counter
+                if let Some(rate) = endpoint.cost_per_1k_tokens
+                    && !(rate.is_finite() && rate >= 0.0)
+                {
+                    return Err(crate::error::AppError::Config(format!(
+                        "Configuration error: Endpoint '{}' in tier '{}' has invalid cost_per_1k_tokens {}. \
+                        cost_per_1k_tokens must be a finite number >= 0; omit it to leave the endpoint uncosted.",
+                        endpoint.name, tier_name, rate
+                    )));
+                }
+
+                // Validate context window: 0 would reject every prompt
+                if endpoint.context_window == Some(0) {
+                    return Err(crate::error::AppError::Config(format!(
+                        "Configuration error: Endpoint '{}' in tier '{}' has context_window = 0. \
+                        Set it to the model's context length in tokens, or omit it for no limit.",
+                        endpoint.name, tier_name
+                    )));
+                }
+
+                // Validate reasoning tags: only bare names can be matched as <name>...</name>
+                if endpoint.strip_reasoning_tags {
+                    if endpoint.reasoning_tags.is_empty() {
+                        return Err(crate::error::AppError::Config(format!(
+                            "Configuration error: Endpoint '{}' in tier '{}' sets strip_reasoning_tags \
+                            but reasoning_tags is empty. Omit reasoning_tags to strip <think> blocks.",
+                            endpoint.name, tier_name
+                        )));
+                    }
+                    if let Some(tag) = endpoint.reasoning_tags.iter().find(|tag| {
+                        tag.is_empty()
+                            || tag
+                                .chars()
+                                .any(|c| c.is_whitespace() || matches!(c, '<' | '>' | '/'))
+                    }) {
+                        return Err(crate::error::AppError::Config(format!(
+                            "Configuration error: Endpoint '{}' in tier '{}' has invalid reasoning tag '{}'. \
+                            Use the bare tag name without brackets or spaces (e.g., reasoning_tags = [\"think\"]).",
+                            endpoint.name, tier_name, tag
+                        )));
+                    }
+                }
+
+                // Validate health_path: appended to base_url, so it must be a plain path
+                if let Some(health_path) = &endpoint.health_path
+                    && (!health_path.starts_with('/')
+                        || health_path.contains(['?', '#'])
+                        || health_path.chars().any(char::is_whitespace))
+                {
+                    return Err(crate::error::AppError::Config(format!(
+                        "Configuration error: Endpoint '{}' in tier '{}' has invalid health_path '{}'. \
+                        Use a path starting with '/' (e.g., health_path = \"/health\").",
+                        endpoint.name, tier_name, health_path
+                    )));
+                }
+            }
+        }
+
+        // ═══════════════════════════════════════════════════════════════════════
+        // Phase 2: All-Tier Availability Validation
+        // ═══════════════════════════════════════════════════════════════════════
+        //
+        // P1 FIX: Require ALL tiers (Fast/Balanced/Deep) to have at least one endpoint.
+        //
+        // RATIONALE: Both RuleBasedRouter and LlmBasedRouter can route to ANY tier
+        // based on request characteristics. If a tier is empty and gets selected,
+        // requests fail at runtime with "No available healthy endpoints" instead
+        // of failing at startup validation.
+        //
+        // This validation ensures config errors are caught at startup, not runtime.
+        //
+        // Note: router_tier format is already validated during deserialization (Phase 1).
+
+        // Check Fast tier
         if self.models.fast.is_empty() {
             return Err(crate::error::AppError::Config(
                 "Configuration error: models.fast has no endpoints. \
@@ -766,6 +2234,190 @@ impl Config {
             )));
         }
 
+        // Validate SSE keep-alive interval (0 disables; cap matches the request timeout cap)
+        if self.server.sse_keepalive_seconds > 300 {
+            return Err(crate::error::AppError::Config(format!(
+                "Configuration error: sse_keepalive_seconds cannot exceed 300 seconds, got {}",
+                self.server.sse_keepalive_seconds
+            )));
+        }
+
+        // Validate total request duration backstop (0 would reject every request)
+        if self.server.max_request_duration_seconds == Some(0) {
+            return Err(crate::error::AppError::Config(
+                "Configuration error: max_request_duration_seconds must be greater than 0. \
+                Omit the field to disable the limit."
+                    .to_string(),
+            ));
+        }
+
+        // Validate first-token budget (0 would abandon every endpoint before it can answer)
+        if self.server.first_token_timeout_ms == Some(0) {
+            return Err(crate::error::AppError::Config(
+                "Configuration error: first_token_timeout_ms must be greater than 0. \
+                Omit the field to disable the budget."
+                    .to_string(),
+            ));
+        }
+
+        // Validate queue wait (0 would be shedding under another name)
+        if self.server.max_queue_wait_ms == Some(0) {
+            return Err(crate::error::AppError::Config(
+                "Configuration error: max_queue_wait_ms must be greater than 0. \
+                Omit the field to shed requests immediately."
+                    .to_string(),
+            ));
+        }
+
+        // Validate upstream call budget (0 would fail every request)
+        if self.server.max_upstream_calls == Some(0) {
+            return Err(crate::error::AppError::Config(
+                "Configuration error: max_upstream_calls must be at least 1. \
+                Omit the field for no per-request limit."
+                    .to_string(),
+            ));
+        }
+
+        // Validate fan-out cap (0 would fail every request)
+        if self.server.max_parallel_upstream_per_request == Some(0) {
+            return Err(crate::error::AppError::Config(
+                "Configuration error: max_parallel_upstream_per_request must be at least 1. \
+                Omit the field for no per-request limit."
+                    .to_string(),
+            ));
+        }
+
+        // Startup health checks mean nothing with health tracking off
+        if !self.health.enabled && self.health.require_healthy_at_startup {
+            return Err(crate::error::AppError::Config(
+                "Configuration error: health.require_healthy_at_startup needs health checking; \
+                it cannot be combined with health.enabled = false"
+                    .to_string(),
+            ));
+        }
+
+        // Validate health probe timeouts (0 would fail every probe)
+        for (field, value) in [
+            (
+                "probe_connect_timeout_ms",
+                self.health.probe_connect_timeout_ms,
+            ),
+            ("probe_read_timeout_ms", self.health.probe_read_timeout_ms),
+        ] {
+            if value == 0 {
+                return Err(crate::error::AppError::Config(format!(
+                    "Configuration error: health.{} must be greater than 0",
+                    field
+                )));
+            }
+        }
+
+        // Validate tier concurrency budgets (0 would shed every request for the tier)
+        for (tier_name, tier) in [
+            ("fast", TargetModel::Fast),
+            ("balanced", TargetModel::Balanced),
+            ("deep", TargetModel::Deep),
+        ] {
+            if self.server.tier_concurrency.for_tier(tier) == Some(0) {
+                return Err(crate::error::AppError::Config(format!(
+                    "Configuration error: server.tier_concurrency.{} must be at least 1. \
+                    Omit it for no limit.",
+                    tier_name
+                )));
+            }
+        }
+
+        // Validate user tracking (zeros would flag every user or track none)
+        if let Some(tracking) = &self.server.user_tracking
+            && (tracking.max_requests == 0
+                || tracking.window_seconds == 0
+                || tracking.max_users == 0)
+        {
+            return Err(crate::error::AppError::Config(
+                "Configuration error: server.user_tracking.max_requests, window_seconds and \
+                max_users must all be at least 1. Omit [server.user_tracking] to disable it."
+                    .to_string(),
+            ));
+        }
+
+        // Validate admin token (a blank token would make the admin API trivially open)
+        if let Some(token) = &self.server.admin_token
+            && token.trim().is_empty()
+        {
+            return Err(crate::error::AppError::Config(
+                "Configuration error: server.admin_token cannot be blank. \
+                Omit it to disable the admin API."
+                    .to_string(),
+            ));
+        }
+
+        // Validate prompt log truncation (0 would log an empty prompt)
+        if self.observability.log_prompts == PromptLogMode::Truncated
+            && self.observability.log_prompt_chars == 0
+        {
+            return Err(crate::error::AppError::Config(
+                "Configuration error: observability.log_prompt_chars must be greater than 0 \
+                when log_prompts = \"truncated\""
+                    .to_string(),
+            ));
+        }
+
+        // Every public listen address must parse before the server tries to bind it
+        let listen_addrs = self.server.listen_addrs()?;
+
+        // Validate the metrics listener (it must not collide with the public one)
+        if let Some(bind) = &self.observability.metrics_bind {
+            let addr: std::net::SocketAddr = bind.parse().map_err(|e| {
+                crate::error::AppError::Config(format!(
+                    "Configuration error: observability.metrics_bind '{}' is not a valid \
+                    address: {}. Expected host:port, e.g. \"127.0.0.1:9090\"",
+                    bind, e
+                ))
+            })?;
+            let same_host = listen_addrs.iter().any(|listen| {
+                listen.ip() == addr.ip()
+                    || listen.ip().is_unspecified()
+                    || addr.ip().is_unspecified()
+            });
+            if addr.port() == self.server.port && same_host {
+                return Err(crate::error::AppError::Config(format!(
+                    "Configuration error: observability.metrics_bind '{}' overlaps the public \
+                    listener {}:{}. Use a different port, or omit metrics_bind to serve \
+                    /metrics on the public listener.",
+                    bind, self.server.host, self.server.port
+                )));
+            }
+        }
+
+        // Validate request body limit (0 would reject every request with a body)
+        if self.server.max_request_body_bytes == 0 {
+            return Err(crate::error::AppError::Config(
+                "Configuration error: max_request_body_bytes must be greater than 0".to_string(),
+            ));
+        }
+
+        // Conversation limits can only tighten the ceilings enforced when parsing requests
+        let conversation_limits = [
+            (
+                "max_messages",
+                self.server.max_messages,
+                crate::handlers::openai::types::MAX_MESSAGES,
+            ),
+            (
+                "max_total_prompt_chars",
+                self.server.max_total_prompt_chars,
+                crate::handlers::openai::types::MAX_TOTAL_CONTENT_LENGTH,
+            ),
+        ];
+        for (setting, value, ceiling) in conversation_limits {
+            if value == 0 || value > ceiling {
+                return Err(crate::error::AppError::Config(format!(
+                    "Configuration error: server.{} must be between 1 and {} (got {})",
+                    setting, ceiling, value
+                )));
+            }
+        }
+
         // Per-tier timeout validation is now handled by TimeoutsConfig's custom Deserialize
         // implementation, which calls the validated constructor at parse time.
         // No duplicate validation needed here.
@@ -776,6 +2428,165 @@ impl Config {
             .validate()
             .map_err(|e| crate::error::AppError::Config(format!("Configuration error: {}", e)))?;
 
+        // Validate sticky session TTL (0 would expire every mapping immediately)
+        if self.routing.sticky_session_ttl_seconds == Some(0) {
+            return Err(crate::error::AppError::Config(
+                "Configuration error: routing.sticky_session_ttl_seconds must be greater than 0. \
+                Omit the field to disable sticky routing."
+                    .to_string(),
+            ));
+        }
+
+        // Validate house system prompt source (file contents are checked at startup)
+        if self.routing.system_prompt.is_some() && self.routing.system_prompt_file.is_some() {
+            return Err(crate::error::AppError::Config(
+                "Configuration error: routing.system_prompt and routing.system_prompt_file \
+                are mutually exclusive. Set one or the other."
+                    .to_string(),
+            ));
+        }
+        if let Some(prompt) = &self.routing.system_prompt
+            && prompt.trim().is_empty()
+        {
+            return Err(crate::error::AppError::Config(
+                "Configuration error: routing.system_prompt must not be empty. \
+                Omit the field to send client messages unchanged."
+                    .to_string(),
+            ));
+        }
+
+        // Validate router guard suffix (an empty one leaves the user request last)
+        if self.routing.router_guard_suffix.trim().is_empty() {
+            return Err(crate::error::AppError::Config(
+                "Configuration error: routing.router_guard_suffix must not be empty. \
+                Omit the field to use the default guard instruction."
+                    .to_string(),
+            ));
+        }
+
+        // Validate tier keywords (a blank keyword would match any boundary)
+        for (tier_name, tier) in [
+            ("fast", TargetModel::Fast),
+            ("balanced", TargetModel::Balanced),
+            ("deep", TargetModel::Deep),
+        ] {
+            if self
+                .routing
+                .tier_keywords
+                .for_tier(tier)
+                .iter()
+                .any(|keyword| keyword.trim().is_empty())
+            {
+                return Err(crate::error::AppError::Config(format!(
+                    "Configuration error: routing.tier_keywords.{} contains an empty keyword. \
+                    Remove it; the English tier name is always accepted.",
+                    tier_name
+                )));
+            }
+        }
+
+        // Validate importance guidance (blank text would add an empty prompt line)
+        for (level_name, level) in [
+            ("low", Importance::Low),
+            ("normal", Importance::Normal),
+            ("high", Importance::High),
+        ] {
+            if self
+                .routing
+                .importance_guidance
+                .for_importance(level)
+                .is_some_and(|guidance| guidance.trim().is_empty())
+            {
+                return Err(crate::error::AppError::Config(format!(
+                    "Configuration error: routing.importance_guidance.{} is empty. \
+                    Remove it to give no guidance for that importance level.",
+                    level_name
+                )));
+            }
+        }
+
+        // Validate router retry budgets
+        let retry_policy = &self.routing.retry_policy;
+        for (field, attempts) in [
+            ("connection_attempts", retry_policy.connection_attempts),
+            ("stream_attempts", retry_policy.stream_attempts),
+        ] {
+            if !(1..=MAX_ROUTER_RETRY_ATTEMPTS).contains(&attempts) {
+                return Err(crate::error::AppError::Config(format!(
+                    "Configuration error: routing.retry_policy.{} must be between 1 and {}, got {}. \
+                    Use 1 to disable retries for that kind of failure.",
+                    field, MAX_ROUTER_RETRY_ATTEMPTS, attempts
+                )));
+            }
+        }
+        if self.routing.router_same_endpoint_retries > 1 {
+            return Err(crate::error::AppError::Config(format!(
+                "Configuration error: routing.router_same_endpoint_retries must be 0 or 1, got {}. \
+                Use routing.retry_policy to retry on other router endpoints.",
+                self.routing.router_same_endpoint_retries
+            )));
+        }
+
+        // Validate retryable backend statuses
+        if let Some(status) = self
+            .routing
+            .retryable_statuses
+            .iter()
+            .find(|status| !(400..=599).contains(*status))
+        {
+            return Err(crate::error::AppError::Config(format!(
+                "Configuration error: routing.retryable_statuses contains {}, \
+                which is not an HTTP error status (400-599).",
+                status
+            )));
+        }
+
+        // Validate the routing token estimate cap
+        if self.routing.token_estimate_cap == Some(0) {
+            return Err(crate::error::AppError::Config(
+                "Configuration error: routing.token_estimate_cap must be greater than 0. \
+                Remove it to cap only at the largest context_window."
+                    .to_string(),
+            ));
+        }
+
+        // Validate the routing decision cache
+        if let Some(cache) = &self.routing.cache {
+            if cache.ttl_seconds == 0 {
+                return Err(crate::error::AppError::Config(
+                    "Configuration error: routing.cache.ttl_seconds must be greater than 0. \
+                    Remove [routing.cache] to disable the cache."
+                        .to_string(),
+                ));
+            }
+            if cache.max_entries == 0 {
+                return Err(crate::error::AppError::Config(
+                    "Configuration error: routing.cache.max_entries must be greater than 0."
+                        .to_string(),
+                ));
+            }
+            if cache.backend == RoutingCacheBackend::Redis {
+                if !cfg!(feature = "redis") {
+                    return Err(crate::error::AppError::Config(
+                        "Configuration error: routing.cache.backend = \"redis\" needs octoroute \
+                        built with the 'redis' feature (cargo build --features redis)."
+                            .to_string(),
+                    ));
+                }
+                if cache
+                    .redis_url
+                    .as_deref()
+                    .is_none_or(|url| url.trim().is_empty())
+                {
+                    return Err(crate::error::AppError::Config(
+                        "Configuration error: routing.cache.backend = \"redis\" requires \
+                        routing.cache.redis_url (e.g., 'redis://127.0.0.1:6379')."
+                            .to_string(),
+                    ));
+                }
+            }
+        }
+
         // ═══════════════════════════════════════════════════════════════════════
         // Phase 3: HTTP Client Creation Validation
         // ═══════════════════════════════════════════════════════════════════════
@@ -889,6 +2700,65 @@ router_tier = "balanced"
 log_level = "info"
 "#;
 
+    /// TEST_CONFIG expressed in YAML
+    const TEST_CONFIG_YAML: &str = r#"
+server:
+  host: "0.0.0.0"
+  port: 3000
+  request_timeout_seconds: 30
+
+models:
+  fast:
+    - name: qwen/qwen3-vl-8b
+      base_url: http://192.168.1.67:1234/v1
+      max_tokens: 4096
+      temperature: 0.7
+      weight: 1.0
+      priority: 1
+    - name: qwen/qwen3-vl-8b
+      base_url: http://192.168.1.72:1234/v1
+      max_tokens: 4096
+      temperature: 0.7
+      weight: 1.0
+      priority: 1
+  balanced:
+    - name: qwen/qwen3-30b-a3b-2507
+      base_url: http://192.168.1.61:1234/v1
+      max_tokens: 8192
+      temperature: 0.7
+      weight: 1.0
+      priority: 1
+  deep:
+    - name: /home/steve/dev/llama.cpp/models/gpt-oss-120b-mxfp4.gguf
+      base_url: https://strix-ai.localbrandonfamily.com/v1
+      max_tokens: 16384
+      temperature: 0.7
+      weight: 1.0
+      priority: 1
+
+routing:
+  strategy: hybrid
+  default_importance: normal
+  router_tier: balanced
+
+observability:
+  log_level: info
+"#;
+
+    #[test]
+    fn test_yaml_config_matches_toml_config() {
+        let from_toml = Config::from_str(TEST_CONFIG).expect("should parse TOML config");
+        let from_yaml: Config =
+            serde_yaml::from_str(TEST_CONFIG_YAML).expect("should parse YAML config");
+        from_yaml.validate().expect("YAML config should validate");
+
+        // Config has no PartialEq; compare the serialized forms instead
+        assert_eq!(
+            serde_json::to_value(&from_yaml).unwrap(),
+            serde_json::to_value(&from_toml).unwrap()
+        );
+    }
+
     #[test]
     fn test_config_from_str_parses_successfully() {
         let config = Config::from_str(TEST_CONFIG).expect("should parse config");
@@ -941,6 +2811,105 @@ log_level = "info"
     fn test_config_parses_observability() {
         let config = Config::from_str(TEST_CONFIG).expect("should parse config");
         assert_eq!(config.observability.log_level, "info");
+        assert_eq!(config.observability.log_prompts, PromptLogMode::None);
+        assert_eq!(config.observability.log_prompt_chars, 200);
+    }
+
+    #[test]
+    fn test_log_prompts_parses_and_validates() {
+        let toml = TEST_CONFIG.replace(
+            "[observability]\n",
+            "[observability]\nlog_prompts = \"truncated\"\nlog_prompt_chars = 40\n",
+        );
+        let config = Config::from_str(&toml).expect("should parse config");
+        assert_eq!(config.observability.log_prompts, PromptLogMode::Truncated);
+        assert_eq!(config.observability.log_prompt_chars, 40);
+
+        let zero = toml.replace("log_prompt_chars = 40", "log_prompt_chars = 0");
+        let err = Config::from_str(&zero).expect_err("zero truncation should be rejected");
+        assert!(err.to_string().contains("observability.log_prompt_chars"));
+
+        let unknown = toml.replace("\"truncated\"", "\"redacted\"");
+        assert!(Config::from_str(&unknown).is_err());
+    }
+
+    #[test]
+    fn test_metrics_bind_parses_and_validates() {
+        let config = Config::from_str(TEST_CONFIG).expect("should parse config");
+        assert_eq!(config.observability.metrics_bind_addr(), None);
+
+        let toml = TEST_CONFIG.replace(
+            "[observability]\n",
+            "[observability]\nmetrics_bind = \"127.0.0.1:9090\"\n",
+        );
+        let config = Config::from_str(&toml).expect("should parse config");
+        assert_eq!(
+            config.observability.metrics_bind_addr(),
+            Some("127.0.0.1:9090".parse().unwrap())
+        );
+
+        let invalid = toml.replace("127.0.0.1:9090", "localhost");
+        let err = Config::from_str(&invalid).expect_err("a bare host should be rejected");
+        assert!(err.to_string().contains("observability.metrics_bind"));
+
+        let port = config.server.port;
+        let clash = toml.replace("127.0.0.1:9090", &format!("0.0.0.0:{}", port));
+        let err = Config::from_str(&clash).expect_err("the public address should be rejected");
+        assert!(err.to_string().contains("overlaps the public listener"));
+    }
+
+    #[test]
+    fn test_listen_addrs_expand_dual_stack() {
+        let config = Config::from_str(TEST_CONFIG).expect("should parse config");
+        let port = config.server.port;
+        assert_eq!(
+            config.server.listen_addrs().unwrap(),
+            vec![std::net::SocketAddr::from(([0, 0, 0, 0], port))]
+        );
+
+        let toml = TEST_CONFIG.replace(
+            "host = \"0.0.0.0\"",
+            "host = \"0.0.0.0\"\ndual_stack = true\nextra_hosts = [\"::1\", \"::\"]",
+        );
+        let config = Config::from_str(&toml).expect("should parse config");
+        let addrs: Vec<String> = config
+            .server
+            .listen_addrs()
+            .unwrap()
+            .iter()
+            .map(|addr| addr.to_string())
+            .collect();
+        assert_eq!(
+            addrs,
+            vec![
+                format!("0.0.0.0:{}", port),
+                format!("[::]:{}", port),
+                format!("[::1]:{}", port),
+                format!("127.0.0.1:{}", port),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_listen_addrs_reject_invalid_addresses() {
+        let toml = TEST_CONFIG.replace(
+            "host = \"0.0.0.0\"",
+            "host = \"0.0.0.0\"\nextra_hosts = [\"localhost\"]",
+        );
+        let err = Config::from_str(&toml).expect_err("a host name should be rejected");
+        assert!(
+            err.to_string()
+                .contains("server.extra_hosts 'localhost' is not a valid IP address"),
+            "{}",
+            err
+        );
+
+        let toml = TEST_CONFIG.replace(
+            "host = \"0.0.0.0\"",
+            "host = \"192.168.1.10\"\ndual_stack = true",
+        );
+        let err = Config::from_str(&toml).expect_err("no counterpart for a LAN address");
+        assert!(err.to_string().contains("dual_stack"), "{}", err);
     }
 
     #[test]
@@ -965,6 +2934,11 @@ log_level = "info"
                 .expect("Test operation should succeed"),
             RoutingStrategy::Tool
         );
+        assert_eq!(
+            serde_json::from_str::<RoutingStrategy>(r#""heuristic""#)
+                .expect("Test operation should succeed"),
+            RoutingStrategy::Heuristic
+        );
     }
 
     #[test]
@@ -1212,6 +3186,32 @@ strategy = "rule"
         assert!(err_msg.contains("base_url"));
         assert!(err_msg.contains("/v1"));
         assert!(err_msg.contains("OpenAI API"));
+        assert!(err_msg.contains("skip_url_check"));
+    }
+
+    #[test]
+    fn test_skip_url_check_allows_base_url_without_v1() {
+        let toml = TEST_CONFIG.replacen(
+            "base_url = \"http://192.168.1.67:1234/v1\"",
+            "base_url = \"https://gateway.example.com/openai/deployments/fast\"\nskip_url_check = true",
+            1,
+        );
+        let config = Config::from_str(&toml).expect("skip_url_check should allow any path");
+        assert!(config.models.fast[0].skip_url_check());
+        assert!(!config.models.balanced[0].skip_url_check());
+
+        let flagged = toml.replacen("skip_url_check = true", "skip_url_check = false", 1);
+        let err = Config::from_str(&flagged).expect_err("missing /v1 should be flagged");
+        assert!(err.to_string().contains("must end with '/v1'"));
+    }
+
+    #[test]
+    fn test_config_validation_malformed_base_url_fails() {
+        let mut config = Config::from_str(TEST_CONFIG).expect("Test operation should succeed");
+        config.models.fast[0].base_url = "http://:1234/v1".to_string(); // Invalid: no host
+
+        let err_msg = config.validate().unwrap_err().to_string();
+        assert!(err_msg.contains("malformed base_url"), "{}", err_msg);
     }
 
     #[test]
@@ -1414,6 +3414,1032 @@ fast = 15
         );
     }
 
+    const ENDPOINT_TIMEOUT_CONFIG: &str = r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+request_timeout_seconds = 30
+
+[[models.fast]]
+name = "fast-slow-host"
+base_url = "http://localhost:1234/v1"
+max_tokens = 4096
+request_timeout_seconds = 5
+
+[[models.fast]]
+name = "fast-default"
+base_url = "http://localhost:1235/v1"
+max_tokens = 4096
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "http://localhost:1236/v1"
+max_tokens = 8192
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:1237/v1"
+max_tokens = 16384
+
+[routing]
+strategy = "rule"
+
+[timeouts]
+fast = 15
+"#;
+
+    #[test]
+    fn test_endpoint_request_timeout_overrides_tier_and_global() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+
+        let overridden = &config.models.fast[0];
+        let tier_default = &config.models.fast[1];
+        assert_eq!(overridden.request_timeout_seconds(), Some(5));
+        assert_eq!(tier_default.request_timeout_seconds(), None);
+
+        // Endpoint override wins over [timeouts].fast
+        assert_eq!(
+            config.timeout_for_endpoint(overridden, crate::router::TargetModel::Fast),
+            5
+        );
+        // No endpoint override: falls back to [timeouts].fast
+        assert_eq!(
+            config.timeout_for_endpoint(tier_default, crate::router::TargetModel::Fast),
+            15
+        );
+        // No endpoint or tier override: falls back to server default
+        assert_eq!(
+            config.timeout_for_endpoint(&config.models.deep[0], crate::router::TargetModel::Deep),
+            30
+        );
+    }
+
+    #[test]
+    fn test_endpoint_request_timeout_rejects_zero() {
+        let toml = ENDPOINT_TIMEOUT_CONFIG
+            .replace("request_timeout_seconds = 5", "request_timeout_seconds = 0");
+        let err = Config::from_str(&toml).expect_err("zero endpoint timeout should be rejected");
+        assert!(err.to_string().contains("fast-slow-host"));
+        assert!(err.to_string().contains("request_timeout_seconds"));
+    }
+
+    #[test]
+    fn test_endpoint_request_timeout_rejects_over_300() {
+        let toml = ENDPOINT_TIMEOUT_CONFIG.replace(
+            "request_timeout_seconds = 5",
+            "request_timeout_seconds = 301",
+        );
+        let err = Config::from_str(&toml).expect_err("endpoint timeout > 300 should be rejected");
+        assert!(err.to_string().contains("request_timeout_seconds"));
+    }
+
+    #[test]
+    fn test_sticky_session_ttl_defaults_to_disabled() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert_eq!(config.routing.sticky_session_ttl_seconds, None);
+    }
+
+    #[test]
+    fn test_sticky_session_ttl_parses_and_rejects_zero() {
+        let toml = ENDPOINT_TIMEOUT_CONFIG.replace(
+            "strategy = \"rule\"",
+            "strategy = \"rule\"\nsticky_session_ttl_seconds = 900",
+        );
+        let config = Config::from_str(&toml).expect("should parse config");
+        assert_eq!(config.routing.sticky_session_ttl_seconds, Some(900));
+
+        let toml = toml.replace(
+            "sticky_session_ttl_seconds = 900",
+            "sticky_session_ttl_seconds = 0",
+        );
+        let err = Config::from_str(&toml).expect_err("zero sticky TTL should be rejected");
+        assert!(err.to_string().contains("sticky_session_ttl_seconds"));
+    }
+
+    #[test]
+    fn test_tier_fallback_defaults_to_disabled() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert!(!config.routing.tier_fallback);
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG.replace(
+            "strategy = \"rule\"",
+            "strategy = \"rule\"\ntier_fallback = true",
+        );
+        let config = Config::from_str(&toml).expect("should parse config");
+        assert!(config.routing.tier_fallback);
+    }
+
+    #[test]
+    fn test_default_tier_parses_and_defaults_to_unset() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert_eq!(config.routing.default_tier, None);
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG.replace(
+            "strategy = \"rule\"",
+            "strategy = \"rule\"\ndefault_tier = \"balanced\"",
+        );
+        let config = Config::from_str(&toml).expect("should parse config");
+        assert_eq!(config.routing.default_tier, Some(TargetModel::Balanced));
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG.replace(
+            "strategy = \"rule\"",
+            "strategy = \"rule\"\ndefault_tier = \"medium\"",
+        );
+        assert!(
+            Config::from_str(&toml).is_err(),
+            "unknown tier names should be rejected"
+        );
+    }
+
+    #[test]
+    fn test_router_retry_backoff_parses_with_default() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert_eq!(config.routing.retry_backoff_ms, 100);
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG.replace(
+            "strategy = \"rule\"",
+            "strategy = \"rule\"\nretry_backoff_ms = 0",
+        );
+        let config = Config::from_str(&toml).expect("should parse config");
+        assert_eq!(config.routing.retry_backoff_ms, 0);
+    }
+
+    #[test]
+    fn test_retryable_statuses_parse_and_validate() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert_eq!(
+            config.routing.retryable_statuses,
+            DEFAULT_RETRYABLE_STATUSES.to_vec()
+        );
+        assert!(config.routing.is_retryable_status(429));
+        assert!(!config.routing.is_retryable_status(400));
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG.replace(
+            "strategy = \"rule\"",
+            "strategy = \"rule\"\nretryable_statuses = [503]",
+        );
+        let config = Config::from_str(&toml).expect("should parse config");
+        assert!(config.routing.is_retryable_status(503));
+        assert!(!config.routing.is_retryable_status(429));
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG.replace(
+            "strategy = \"rule\"",
+            "strategy = \"rule\"\nretryable_statuses = [200]",
+        );
+        let err = Config::from_str(&toml).expect_err("200 is not an error status");
+        assert!(err.to_string().contains("retryable_statuses contains 200"));
+    }
+
+    #[test]
+    fn test_system_prompt_parses_and_validates() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert_eq!(config.routing.system_prompt, None);
+        assert_eq!(config.routing.system_prompt_mode, SystemPromptMode::Prepend);
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG.replace(
+            "strategy = \"rule\"",
+            "strategy = \"rule\"\nsystem_prompt = \"Be concise.\"\nsystem_prompt_mode = \"merge\"",
+        );
+        let config = Config::from_str(&toml).expect("should parse config");
+        assert_eq!(config.routing.system_prompt.as_deref(), Some("Be concise."));
+        assert_eq!(config.routing.system_prompt_mode, SystemPromptMode::Merge);
+
+        let both = toml.replace(
+            "system_prompt_mode",
+            "system_prompt_file = \"house.txt\"\nsystem_prompt_mode",
+        );
+        let err = Config::from_str(&both).expect_err("inline and file prompts are exclusive");
+        assert!(err.to_string().contains("mutually exclusive"));
+
+        let empty = toml.replace("\"Be concise.\"", "\"  \"");
+        assert!(Config::from_str(&empty).is_err());
+
+        let unknown_mode = toml.replace("\"merge\"", "\"append\"");
+        assert!(Config::from_str(&unknown_mode).is_err());
+    }
+
+    #[test]
+    fn test_router_prompt_guard_parses_and_validates() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert_eq!(
+            config.routing.router_guard_suffix,
+            crate::router::DEFAULT_ROUTER_GUARD_SUFFIX
+        );
+        assert!(config.routing.router_prompt_delimiters);
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG.replace(
+            "strategy = \"rule\"",
+            "strategy = \"rule\"\nrouter_guard_suffix = \"Answer FAST, BALANCED or DEEP.\"\n\
+             router_prompt_delimiters = false",
+        );
+        let config = Config::from_str(&toml).expect("should parse config");
+        assert_eq!(
+            config.routing.router_guard_suffix,
+            "Answer FAST, BALANCED or DEEP."
+        );
+        assert!(!config.routing.router_prompt_delimiters);
+
+        let blank = toml.replace("\"Answer FAST, BALANCED or DEEP.\"", "\" \"");
+        let err = Config::from_str(&blank).expect_err("blank guard suffix should be rejected");
+        assert!(err.to_string().contains("routing.router_guard_suffix"));
+    }
+
+    #[test]
+    fn test_on_unparseable_parses_with_error_default() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert_eq!(config.routing.on_unparseable, UnparseableFallback::Error);
+
+        for (value, expected) in [
+            ("balanced", UnparseableFallback::Balanced),
+            ("default_tier", UnparseableFallback::DefaultTier),
+            ("error", UnparseableFallback::Error),
+        ] {
+            let toml = ENDPOINT_TIMEOUT_CONFIG.replace(
+                "strategy = \"rule\"",
+                &format!("strategy = \"rule\"\non_unparseable = \"{}\"", value),
+            );
+            let config = Config::from_str(&toml).expect("should parse config");
+            assert_eq!(config.routing.on_unparseable, expected);
+            assert_eq!(expected.as_str(), value);
+        }
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG.replace(
+            "strategy = \"rule\"",
+            "strategy = \"rule\"\non_unparseable = \"deep\"",
+        );
+        assert!(Config::from_str(&toml).is_err());
+    }
+
+    #[test]
+    fn test_llm_failure_fallback_parses_with_error_default() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert_eq!(
+            config.routing.llm_failure_fallback,
+            LlmFailureFallback::Error
+        );
+
+        for (value, expected) in [
+            ("rule", LlmFailureFallback::Rule),
+            ("default_tier", LlmFailureFallback::DefaultTier),
+            ("error", LlmFailureFallback::Error),
+        ] {
+            let toml = ENDPOINT_TIMEOUT_CONFIG.replace(
+                "strategy = \"rule\"",
+                &format!("strategy = \"rule\"\nllm_failure_fallback = \"{}\"", value),
+            );
+            let config = Config::from_str(&toml).expect("should parse config");
+            assert_eq!(config.routing.llm_failure_fallback, expected);
+            assert_eq!(expected.as_str(), value);
+        }
+    }
+
+    #[test]
+    fn test_http_pool_parses_with_defaults() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert_eq!(config.server.http_pool.max_idle_per_host, 32);
+        assert_eq!(config.server.http_pool.idle_timeout_seconds, 90);
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG.replace(
+            "request_timeout_seconds = 30\n",
+            "request_timeout_seconds = 30\n\n[server.http_pool]\nmax_idle_per_host = 0\nidle_timeout_seconds = 15\n",
+        );
+        let config = Config::from_str(&toml).expect("should parse config");
+        assert_eq!(config.server.http_pool.max_idle_per_host, 0);
+        assert_eq!(config.server.http_pool.idle_timeout_seconds, 15);
+    }
+
+    #[test]
+    fn test_tier_concurrency_parses_and_rejects_zero() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        for tier in [TargetModel::Fast, TargetModel::Balanced, TargetModel::Deep] {
+            assert_eq!(config.server.tier_concurrency.for_tier(tier), None);
+        }
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG.replace(
+            "request_timeout_seconds = 30\n",
+            "request_timeout_seconds = 30\n\n[server.tier_concurrency]\ndeep = 4\n",
+        );
+        let config = Config::from_str(&toml).expect("should parse config");
+        assert_eq!(
+            config.server.tier_concurrency.for_tier(TargetModel::Deep),
+            Some(4)
+        );
+        assert_eq!(
+            config.server.tier_concurrency.for_tier(TargetModel::Fast),
+            None
+        );
+
+        let zero = toml.replace("deep = 4", "deep = 0");
+        let err = Config::from_str(&zero).expect_err("a zero budget should be rejected");
+        assert!(err.to_string().contains("server.tier_concurrency.deep"));
+    }
+
+    #[test]
+    fn test_task_affinity_parses_per_task_type() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert_eq!(config.routing.task_affinity, TaskAffinityConfig::default());
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG.replace(
+            "strategy = \"rule\"",
+            "strategy = \"rule\"\n\n[routing.task_affinity]\ncreative_writing = \"deep\"\ncasual_chat = \"fast\"",
+        );
+        let config = Config::from_str(&toml).expect("should parse config");
+        let affinity = &config.routing.task_affinity;
+        assert_eq!(
+            affinity.for_task(TaskType::CreativeWriting),
+            Some(TargetModel::Deep)
+        );
+        assert_eq!(
+            affinity.for_task(TaskType::CasualChat),
+            Some(TargetModel::Fast)
+        );
+        assert_eq!(affinity.for_task(TaskType::Code), None);
+
+        let bad = toml.replace("casual_chat = \"fast\"", "casual_chat = \"tiny\"");
+        assert!(Config::from_str(&bad).is_err());
+    }
+
+    #[test]
+    fn test_tier_keywords_parse_and_reject_blank() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert_eq!(config.routing.tier_keywords, TierKeywordsConfig::default());
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG.replace(
+            "strategy = \"rule\"",
+            "strategy = \"rule\"\n\n[routing.tier_keywords]\nbalanced = [\"ÉQUILIBRÉ\", \"均衡\"]",
+        );
+        let config = Config::from_str(&toml).expect("should parse config");
+        let keywords = &config.routing.tier_keywords;
+        assert_eq!(
+            keywords.for_tier(TargetModel::Balanced),
+            ["ÉQUILIBRÉ".to_string(), "均衡".to_string()]
+        );
+        assert!(keywords.for_tier(TargetModel::Fast).is_empty());
+
+        let blank = toml.replace("\"均衡\"", "\" \"");
+        let err = Config::from_str(&blank).expect_err("a blank keyword should be rejected");
+        assert!(err.to_string().contains("routing.tier_keywords.balanced"));
+    }
+
+    #[test]
+    fn test_importance_guidance_parses_and_rejects_blank() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert_eq!(
+            config.routing.importance_guidance,
+            ImportanceGuidanceConfig::default()
+        );
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG.replace(
+            "strategy = \"rule\"",
+            "strategy = \"rule\"\n\n[routing.importance_guidance]\nhigh = \"High importance: prefer BALANCED or DEEP\"",
+        );
+        let config = Config::from_str(&toml).expect("should parse config");
+        let guidance = &config.routing.importance_guidance;
+        assert_eq!(
+            guidance.for_importance(Importance::High),
+            Some("High importance: prefer BALANCED or DEEP")
+        );
+        assert_eq!(guidance.for_importance(Importance::Low), None);
+
+        let blank = toml.replace("\"High importance: prefer BALANCED or DEEP\"", "\"  \"");
+        let err = Config::from_str(&blank).expect_err("blank guidance should be rejected");
+        assert!(err.to_string().contains("routing.importance_guidance.high"));
+    }
+
+    #[test]
+    fn test_retry_policy_parses_with_defaults_and_rejects_out_of_range() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert_eq!(config.routing.retry_policy, RouterRetryPolicy::default());
+        assert_eq!(config.routing.retry_policy.max_attempts(), 2);
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG.replace(
+            "strategy = \"rule\"",
+            "strategy = \"rule\"\n\n[routing.retry_policy]\nconnection_attempts = 4\nstream_attempts = 1",
+        );
+        let config = Config::from_str(&toml).expect("should parse config");
+        assert_eq!(config.routing.retry_policy.connection_attempts, 4);
+        assert_eq!(config.routing.retry_policy.stream_attempts, 1);
+        assert_eq!(config.routing.retry_policy.max_attempts(), 4);
+
+        for bad in ["stream_attempts = 0", "stream_attempts = 11"] {
+            let invalid = toml.replace("stream_attempts = 1", bad);
+            let err =
+                Config::from_str(&invalid).expect_err("out-of-range budget should be rejected");
+            assert!(
+                err.to_string()
+                    .contains("routing.retry_policy.stream_attempts"),
+                "got: {}",
+                err
+            );
+        }
+    }
+
+    #[test]
+    fn test_router_same_endpoint_retries_parses_and_validates() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert_eq!(config.routing.router_same_endpoint_retries, 0);
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG.replace(
+            "strategy = \"rule\"",
+            "strategy = \"rule\"\nrouter_same_endpoint_retries = 1",
+        );
+        let config = Config::from_str(&toml).expect("should parse config");
+        assert_eq!(config.routing.router_same_endpoint_retries, 1);
+
+        let invalid = toml.replace(
+            "router_same_endpoint_retries = 1",
+            "router_same_endpoint_retries = 2",
+        );
+        let err = Config::from_str(&invalid).expect_err("only one retry is allowed");
+        assert!(err.to_string().contains("router_same_endpoint_retries"));
+    }
+
+    #[test]
+    fn test_user_tracking_parses_with_defaults_and_rejects_zero() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert_eq!(config.server.user_tracking, None);
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG.replace(
+            "request_timeout_seconds = 30\n",
+            "request_timeout_seconds = 30\n\n[server.user_tracking]\nmax_requests = 100\n",
+        );
+        let config = Config::from_str(&toml).expect("should parse config");
+        assert_eq!(
+            config.server.user_tracking,
+            Some(UserTrackingConfig {
+                max_requests: 100,
+                window_seconds: 60,
+                reject_over_limit: false,
+                max_users: 10_000,
+            })
+        );
+
+        let zero = toml.replace("max_requests = 100", "max_requests = 0");
+        let err = Config::from_str(&zero).expect_err("max_requests = 0 should be rejected");
+        assert!(err.to_string().contains("server.user_tracking"));
+    }
+
+    #[test]
+    fn test_admin_token_parses_rejects_blank_and_is_not_serialized() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert_eq!(config.server.admin_token, None);
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG.replace(
+            "request_timeout_seconds = 30\n",
+            "request_timeout_seconds = 30\nadmin_token = \"s3cret-admin\"\n",
+        );
+        let config = Config::from_str(&toml).expect("should parse config");
+        assert_eq!(config.server.admin_token.as_deref(), Some("s3cret-admin"));
+        let serialized = toml::to_string(&config).expect("should serialize config");
+        assert!(!serialized.contains("s3cret-admin"), "got: {}", serialized);
+
+        let blank = toml.replace("\"s3cret-admin\"", "\"  \"");
+        let err = Config::from_str(&blank).expect_err("a blank token should be rejected");
+        assert!(err.to_string().contains("server.admin_token"));
+    }
+
+    #[test]
+    fn test_health_section_parses_with_defaults() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert!(config.health.enabled);
+        assert!(!config.health.require_healthy_at_startup);
+        assert_eq!(config.health.startup_grace_period_seconds, 10);
+
+        let toml = format!(
+            "{}\n[health]\nrequire_healthy_at_startup = true\nstartup_grace_period_seconds = 3\n",
+            ENDPOINT_TIMEOUT_CONFIG
+        );
+        let config = Config::from_str(&toml).expect("should parse config");
+        assert!(config.health.require_healthy_at_startup);
+        assert_eq!(config.health.startup_grace_period_seconds, 3);
+    }
+
+    #[test]
+    fn test_health_probe_timeouts() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert_eq!(config.health.probe_connect_timeout_ms, 2000);
+        assert_eq!(config.health.probe_read_timeout_ms, 5000);
+
+        let toml = format!(
+            "{}\n[health]\nprobe_connect_timeout_ms = 250\nprobe_read_timeout_ms = 800\n",
+            ENDPOINT_TIMEOUT_CONFIG
+        );
+        let config = Config::from_str(&toml).expect("should parse config");
+        assert_eq!(config.health.probe_connect_timeout_ms, 250);
+        assert_eq!(config.health.probe_read_timeout_ms, 800);
+
+        let toml = format!(
+            "{}\n[health]\nprobe_read_timeout_ms = 0\n",
+            ENDPOINT_TIMEOUT_CONFIG
+        );
+        let err = Config::from_str(&toml).expect_err("zero read timeout should be rejected");
+        assert!(
+            err.to_string().contains("health.probe_read_timeout_ms"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_disabled_health_rejects_startup_requirement() {
+        let toml = format!(
+            "{}\n[health]\nenabled = false\nrequire_healthy_at_startup = true\n",
+            ENDPOINT_TIMEOUT_CONFIG
+        );
+        let err = Config::from_str(&toml).expect_err("combination should be rejected");
+        assert!(
+            err.to_string().contains("health.enabled = false"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_weight_warnings_flag_dominant_endpoint_only() {
+        // Two fast endpoints at equal weight: no warning
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert!(config.models.weight_warnings().is_empty());
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG.replace(
+            "request_timeout_seconds = 5\n",
+            "request_timeout_seconds = 5\nweight = 50.0\n",
+        );
+        let config = Config::from_str(&toml).expect("dominant weights are valid config");
+        let warnings = config.models.weight_warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("'fast-slow-host'"));
+        assert!(warnings[0].contains("98.0%"));
+
+        // Moving the other endpoint to its own priority group is intentional
+        let toml = toml.replace(
+            "name = \"fast-default\"",
+            "name = \"fast-default\"\npriority = 2",
+        );
+        let config = Config::from_str(&toml).expect("should parse config");
+        assert!(config.models.weight_warnings().is_empty());
+    }
+
+    #[test]
+    fn test_max_in_flight_parses_and_rejects_zero() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert_eq!(config.models.fast[0].max_in_flight(), None);
+        assert!(!config.routing.spillover);
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG.replacen(
+            "max_tokens = 4096",
+            "max_tokens = 4096\nmax_in_flight = 4",
+            1,
+        );
+        let config = Config::from_str(&toml).expect("should parse config");
+        assert_eq!(config.models.fast[0].max_in_flight(), Some(4));
+
+        let toml = toml.replace("max_in_flight = 4", "max_in_flight = 0");
+        let err = Config::from_str(&toml).expect_err("max_in_flight = 0 should be rejected");
+        assert!(err.to_string().contains("max_in_flight"));
+    }
+
+    #[test]
+    fn test_cost_per_1k_tokens_parses_and_rejects_negative() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert_eq!(config.models.fast[0].cost_per_1k_tokens(), None);
+        assert_eq!(config.models.fast[0].estimated_cost(1000), None);
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG.replacen(
+            "max_tokens = 4096",
+            "max_tokens = 4096\ncost_per_1k_tokens = 0.5",
+            1,
+        );
+        let config = Config::from_str(&toml).expect("should parse config");
+        let endpoint = &config.models.fast[0];
+        assert_eq!(endpoint.cost_per_1k_tokens(), Some(0.5));
+        assert_eq!(endpoint.estimated_cost(3000), Some(1.5));
+        assert_eq!(endpoint.estimated_cost(0), Some(0.0));
+
+        let toml = toml.replace("cost_per_1k_tokens = 0.5", "cost_per_1k_tokens = -1.0");
+        let err = Config::from_str(&toml).expect_err("a negative rate should be rejected");
+        assert!(err.to_string().contains("cost_per_1k_tokens"));
+    }
+
+    #[test]
+    fn test_context_window_parses_and_sizes_tiers() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert!(!config.models.has_context_windows());
+        assert_eq!(config.routing.context_overflow, ContextOverflow::Reject);
+        assert_eq!(config.models.tier_context_window(TargetModel::Fast), None);
+
+        // Both fast endpoints get a window; the tier takes the larger one
+        let toml = ENDPOINT_TIMEOUT_CONFIG
+            .replacen(
+                "max_tokens = 4096",
+                "max_tokens = 4096\ncontext_window = 8000",
+                1,
+            )
+            .replacen(
+                "max_tokens = 4096\n\n",
+                "max_tokens = 4096\ncontext_window = 16000\n\n",
+                1,
+            )
+            .replace(
+                "strategy = \"rule\"",
+                "strategy = \"rule\"\ncontext_overflow = \"escalate\"",
+            );
+        let config = Config::from_str(&toml).expect("should parse config");
+        let endpoint = &config.models.fast[0];
+        assert_eq!(endpoint.context_window(), Some(8000));
+        assert!(endpoint.fits_context(8000));
+        assert!(!endpoint.fits_context(8001));
+        assert!(config.models.has_context_windows());
+        assert_eq!(
+            config.models.tier_context_window(TargetModel::Fast),
+            Some(16000)
+        );
+        assert_eq!(config.models.tier_context_window(TargetModel::Deep), None);
+        assert_eq!(config.routing.context_overflow, ContextOverflow::Escalate);
+
+        let zero = toml.replace("context_window = 8000", "context_window = 0");
+        let err = Config::from_str(&zero).expect_err("a zero window should be rejected");
+        assert!(err.to_string().contains("context_window = 0"));
+    }
+
+    #[test]
+    fn test_token_estimate_cap_parses_and_validates() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert_eq!(config.routing.token_estimate_cap, None);
+        assert_eq!(config.models.largest_context_window(), None);
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG.replace(
+            "strategy = \"rule\"",
+            "strategy = \"rule\"\ntoken_estimate_cap = 4096",
+        );
+        let config = Config::from_str(&toml).expect("should parse config");
+        assert_eq!(config.routing.token_estimate_cap, Some(4096));
+
+        let zero = toml.replace("token_estimate_cap = 4096", "token_estimate_cap = 0");
+        let err = Config::from_str(&zero).expect_err("a zero cap should be rejected");
+        assert!(err.to_string().contains("routing.token_estimate_cap"));
+    }
+
+    #[test]
+    fn test_model_defaults_to_name_and_validates() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        let endpoint = &config.models.fast[0];
+        assert_eq!(endpoint.model(), endpoint.name());
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG.replacen(
+            "max_tokens = 4096",
+            "max_tokens = 4096\nmodel = \"llama-3.1-8b\"",
+            1,
+        );
+        let config = Config::from_str(&toml).expect("should parse config");
+        let endpoint = &config.models.fast[0];
+        assert_eq!(endpoint.model(), "llama-3.1-8b");
+        assert_ne!(endpoint.name(), "llama-3.1-8b");
+
+        let toml = toml.replace("model = \"llama-3.1-8b\"", "model = \" \"");
+        let err = Config::from_str(&toml).expect_err("a blank model should be rejected");
+        assert!(err.to_string().contains("empty model"), "{}", err);
+    }
+
+    #[test]
+    fn test_strip_reasoning_tags_parses_and_validates() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        let endpoint = &config.models.fast[0];
+        assert!(!endpoint.strip_reasoning_tags());
+        assert_eq!(endpoint.reasoning_tags(), ["think"]);
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG.replacen(
+            "max_tokens = 4096",
+            "max_tokens = 4096\nstrip_reasoning_tags = true\nreasoning_tags = [\"think\", \"reasoning\"]",
+            1,
+        );
+        let config = Config::from_str(&toml).expect("should parse config");
+        let endpoint = &config.models.fast[0];
+        assert!(endpoint.strip_reasoning_tags());
+        assert_eq!(endpoint.reasoning_tags(), ["think", "reasoning"]);
+
+        for bad in ["[]", "[\"<think>\"]", "[\"\"]"] {
+            let toml = toml.replace("[\"think\", \"reasoning\"]", bad);
+            let err =
+                Config::from_str(&toml).expect_err("invalid reasoning tags should be rejected");
+            assert!(err.to_string().contains("reasoning"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_health_path_parses_and_validates() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert_eq!(config.models.fast[0].health_path(), None);
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG.replacen(
+            "max_tokens = 4096",
+            "max_tokens = 4096\nhealth_path = \"/health\"",
+            1,
+        );
+        let config = Config::from_str(&toml).expect("should parse config");
+        assert_eq!(config.models.fast[0].health_path(), Some("/health"));
+
+        for bad in ["health", "", "/health?full=1"] {
+            let toml = toml.replace("\"/health\"", &format!("\"{bad}\""));
+            let err = Config::from_str(&toml).expect_err("invalid health_path should be rejected");
+            assert!(err.to_string().contains("health_path"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_selection_mode_parses_with_defaults() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert_eq!(config.routing.selection_mode, SelectionMode::Weighted);
+        assert_eq!(
+            config.routing.uncosted_endpoints,
+            UncostedEndpoints::Expensive
+        );
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG.replace(
+            "strategy = \"rule\"",
+            "strategy = \"rule\"\nselection_mode = \"cheapest\"\nuncosted_endpoints = \"cheap\"",
+        );
+        let config = Config::from_str(&toml).expect("should parse config");
+        assert_eq!(config.routing.selection_mode, SelectionMode::Cheapest);
+        assert_eq!(config.routing.uncosted_endpoints, UncostedEndpoints::Cheap);
+
+        let bad = toml.replace("\"cheapest\"", "\"fastest\"");
+        assert!(Config::from_str(&bad).is_err());
+    }
+
+    #[test]
+    fn test_endpoint_tags_parse_and_reject_blank() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert!(config.models.fast[0].tags().is_empty());
+        assert!(config.models.fast[0].has_tags(&[]));
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG.replacen(
+            "max_tokens = 4096",
+            "max_tokens = 4096\ntags = [\"code\", \"local\"]",
+            1,
+        );
+        let config = Config::from_str(&toml).expect("should parse config");
+        let endpoint = &config.models.fast[0];
+        assert_eq!(endpoint.tags(), ["code".to_string(), "local".to_string()]);
+        assert!(endpoint.has_tags(&["code".to_string()]));
+        assert!(!endpoint.has_tags(&["code".to_string(), "gpu".to_string()]));
+
+        let toml = toml.replace("\"local\"", "\" \"");
+        let err = Config::from_str(&toml).expect_err("blank tag should be rejected");
+        assert!(err.to_string().contains("empty tag"));
+    }
+
+    #[test]
+    fn test_sse_keepalive_defaults_and_validation() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert_eq!(config.server.sse_keepalive_seconds, 15);
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG
+            .replace("port = 3000", "port = 3000\nsse_keepalive_seconds = 0");
+        let config = Config::from_str(&toml).expect("0 should be accepted (disabled)");
+        assert_eq!(config.server.sse_keepalive_seconds, 0);
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG
+            .replace("port = 3000", "port = 3000\nsse_keepalive_seconds = 301");
+        let err = Config::from_str(&toml).expect_err("keep-alive > 300 should be rejected");
+        assert!(err.to_string().contains("sse_keepalive_seconds"));
+    }
+
+    #[test]
+    fn test_stream_failover_attempts_default() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert_eq!(config.server.stream_failover_attempts, 2);
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG
+            .replace("port = 3000", "port = 3000\nstream_failover_attempts = 0");
+        let config = Config::from_str(&toml).expect("0 should be accepted (disabled)");
+        assert_eq!(config.server.stream_failover_attempts, 0);
+    }
+
+    #[test]
+    fn test_max_request_duration_defaults_and_validation() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert_eq!(config.server.max_request_duration_seconds, None);
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG.replace(
+            "port = 3000",
+            "port = 3000\nmax_request_duration_seconds = 120",
+        );
+        let config = Config::from_str(&toml).expect("should parse config");
+        assert_eq!(config.server.max_request_duration_seconds, Some(120));
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG.replace(
+            "port = 3000",
+            "port = 3000\nmax_request_duration_seconds = 0",
+        );
+        let err = Config::from_str(&toml).expect_err("zero duration should be rejected");
+        assert!(err.to_string().contains("max_request_duration_seconds"));
+    }
+
+    #[test]
+    fn test_first_token_timeout_defaults_and_validation() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert_eq!(config.server.first_token_timeout(), None);
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG
+            .replace("port = 3000", "port = 3000\nfirst_token_timeout_ms = 1500");
+        let config = Config::from_str(&toml).expect("should parse config");
+        assert_eq!(
+            config.server.first_token_timeout(),
+            Some(std::time::Duration::from_millis(1500))
+        );
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG
+            .replace("port = 3000", "port = 3000\nfirst_token_timeout_ms = 0");
+        let err = Config::from_str(&toml).expect_err("zero budget should be rejected");
+        assert!(err.to_string().contains("first_token_timeout_ms"));
+    }
+
+    #[test]
+    fn test_max_queue_wait_defaults_and_validation() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert_eq!(config.server.max_queue_wait(), None);
+
+        let toml =
+            ENDPOINT_TIMEOUT_CONFIG.replace("port = 3000", "port = 3000\nmax_queue_wait_ms = 250");
+        let config = Config::from_str(&toml).expect("should parse config");
+        assert_eq!(
+            config.server.max_queue_wait(),
+            Some(std::time::Duration::from_millis(250))
+        );
+
+        let toml =
+            ENDPOINT_TIMEOUT_CONFIG.replace("port = 3000", "port = 3000\nmax_queue_wait_ms = 0");
+        let err = Config::from_str(&toml).expect_err("zero wait should be rejected");
+        assert!(err.to_string().contains("max_queue_wait_ms"));
+    }
+
+    #[test]
+    fn test_max_upstream_calls_defaults_and_validation() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert_eq!(config.server.max_upstream_calls, None);
+
+        let toml =
+            ENDPOINT_TIMEOUT_CONFIG.replace("port = 3000", "port = 3000\nmax_upstream_calls = 4");
+        let config = Config::from_str(&toml).expect("should parse config");
+        assert_eq!(config.server.max_upstream_calls, Some(4));
+
+        let toml =
+            ENDPOINT_TIMEOUT_CONFIG.replace("port = 3000", "port = 3000\nmax_upstream_calls = 0");
+        let err = Config::from_str(&toml).expect_err("zero budget should be rejected");
+        assert!(err.to_string().contains("max_upstream_calls"));
+    }
+
+    #[test]
+    fn test_fan_out_cap_defaults_and_validation() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert_eq!(config.server.max_parallel_upstream_per_request, None);
+        assert_eq!(config.server.fan_out_overflow, FanOutOverflow::Sequential);
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG.replace(
+            "port = 3000",
+            "port = 3000\nmax_parallel_upstream_per_request = 2\nfan_out_overflow = \"reject\"",
+        );
+        let config = Config::from_str(&toml).expect("should parse config");
+        assert_eq!(config.server.max_parallel_upstream_per_request, Some(2));
+        assert_eq!(config.server.fan_out_overflow, FanOutOverflow::Reject);
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG.replace(
+            "port = 3000",
+            "port = 3000\nmax_parallel_upstream_per_request = 0",
+        );
+        let err = Config::from_str(&toml).expect_err("zero cap should be rejected");
+        assert!(
+            err.to_string()
+                .contains("max_parallel_upstream_per_request")
+        );
+    }
+
+    #[test]
+    fn test_on_empty_completion_parses() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert_eq!(
+            config.server.on_empty_completion,
+            EmptyCompletionPolicy::ReturnEmpty
+        );
+
+        for (value, policy) in [
+            ("return_empty", EmptyCompletionPolicy::ReturnEmpty),
+            ("retry", EmptyCompletionPolicy::Retry),
+            ("error", EmptyCompletionPolicy::Error),
+        ] {
+            let toml = ENDPOINT_TIMEOUT_CONFIG.replace(
+                "port = 3000",
+                &format!("port = 3000\non_empty_completion = \"{}\"", value),
+            );
+            let config = Config::from_str(&toml).expect("should parse config");
+            assert_eq!(config.server.on_empty_completion, policy);
+        }
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG.replace(
+            "port = 3000",
+            "port = 3000\non_empty_completion = \"ignore\"",
+        );
+        assert!(Config::from_str(&toml).is_err());
+    }
+
+    #[test]
+    fn test_router_endpoint_parses_and_is_validated() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert!(config.routing.router_endpoint.is_none());
+
+        let toml = format!(
+            "{}\n[routing.router_endpoint]\nname = \"router-1\"\nbase_url = \"http://localhost:1240/v1\"\nmax_tokens = 16\ntemperature = 0.0\n",
+            ENDPOINT_TIMEOUT_CONFIG
+        );
+        let config = Config::from_str(&toml).expect("should parse config");
+        let endpoint = config.routing.router_endpoint.expect("router endpoint set");
+        assert_eq!(endpoint.name(), "router-1");
+        assert_eq!(endpoint.max_tokens(), 16);
+        assert_eq!(endpoint.temperature(), 0.0);
+
+        let toml = toml.replace("http://localhost:1240/v1", "http://localhost:1240");
+        let err = Config::from_str(&toml).expect_err("router endpoint base_url is validated");
+        assert!(
+            err.to_string().contains("routing.router_endpoint"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_routing_cache_defaults_and_validation() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert!(config.routing.cache.is_none());
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG.replace(
+            "strategy = \"rule\"",
+            "strategy = \"rule\"\n\n[routing.cache]\nttl_seconds = 60",
+        );
+        let config = Config::from_str(&toml).expect("should parse config");
+        let cache = config.routing.cache.expect("cache configured");
+        assert_eq!(cache.backend, RoutingCacheBackend::Memory);
+        assert_eq!(cache.ttl(), std::time::Duration::from_secs(60));
+        assert_eq!(cache.max_entries, 10_000);
+        assert_eq!(cache.key_prefix, "octoroute:route:");
+
+        let err = Config::from_str(&toml.replace("ttl_seconds = 60", "ttl_seconds = 0"))
+            .expect_err("zero TTL should be rejected");
+        assert!(err.to_string().contains("routing.cache.ttl_seconds"));
+
+        // Without the feature the backend itself is rejected, with it the missing URL is
+        let err = Config::from_str(&toml.replace("ttl_seconds = 60", "backend = \"redis\""))
+            .expect_err("redis without a URL should be rejected");
+        let expected = if cfg!(feature = "redis") {
+            "redis_url"
+        } else {
+            "'redis' feature"
+        };
+        assert!(err.to_string().contains(expected), "{}", err);
+    }
+
+    #[test]
+    fn test_max_request_body_bytes_defaults_and_validation() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert_eq!(config.server.max_request_body_bytes, 10 * 1024 * 1024);
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG
+            .replace("port = 3000", "port = 3000\nmax_request_body_bytes = 65536");
+        let config = Config::from_str(&toml).expect("should parse config");
+        assert_eq!(config.server.max_request_body_bytes, 65536);
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG
+            .replace("port = 3000", "port = 3000\nmax_request_body_bytes = 0");
+        let err = Config::from_str(&toml).expect_err("zero body limit should be rejected");
+        assert!(err.to_string().contains("max_request_body_bytes"));
+    }
+
+    #[test]
+    fn test_conversation_limits_defaults_and_validation() {
+        let config = Config::from_str(ENDPOINT_TIMEOUT_CONFIG).expect("should parse config");
+        assert_eq!(config.server.max_messages, 100);
+        assert_eq!(config.server.max_total_prompt_chars, 500_000);
+
+        let toml = ENDPOINT_TIMEOUT_CONFIG.replace(
+            "port = 3000",
+            "port = 3000\nmax_messages = 20\nmax_total_prompt_chars = 8000",
+        );
+        let config = Config::from_str(&toml).expect("should parse config");
+        assert_eq!(config.server.max_messages, 20);
+        assert_eq!(config.server.max_total_prompt_chars, 8000);
+
+        for (setting, value) in [
+            ("max_messages", 0),
+            ("max_messages", 101),
+            ("max_total_prompt_chars", 0),
+            ("max_total_prompt_chars", 500_001),
+        ] {
+            let toml = ENDPOINT_TIMEOUT_CONFIG
+                .replace("port = 3000", &format!("port = 3000\n{setting} = {value}"));
+            let err = Config::from_str(&toml).expect_err("out-of-range limit should be rejected");
+            assert!(err.to_string().contains(setting), "{}", err);
+        }
+    }
+
     // ===== Issue #3 Fix: TimeoutsConfig Custom Deserialize Tests =====
     // Tests written FIRST (TDD RED phase) - these should fail until custom Deserialize is implemented
 
@@ -4,7 +4,7 @@
 
 use axum::{
     Json,
-    http::StatusCode,
+    http::{HeaderValue, StatusCode, header},
     response::{IntoResponse, Response},
 };
 use thiserror::Error;
@@ -43,6 +43,17 @@ pub enum ModelQueryError {
         error_message: String,
     },
 
+    /// Backend answered the query with an HTTP error status
+    ///
+    /// Retryable or not depending on the status: see
+    /// `routing.retryable_statuses`.
+    #[error("Backend {endpoint} returned HTTP {status}: {error_message}")]
+    UpstreamStatus {
+        endpoint: String,
+        status: u16,
+        error_message: String,
+    },
+
     /// Query timeout waiting for model response
     ///
     /// Transient error - endpoint may be overloaded or unreachable.
@@ -71,16 +82,30 @@ impl ModelQueryError {
     /// Retryable errors:
     /// - StreamError: Network interruption, may succeed with different endpoint
     /// - Timeout: Endpoint overloaded, may succeed with different endpoint
+    /// - UpstreamStatus: with a status in [`DEFAULT_RETRYABLE_STATUSES`]
     ///
     /// Non-retryable (systemic) errors:
     /// - EmptyResponse: Model malfunction
     /// - UnparseableResponse: Model not following expected format
     /// - AgentOptionsConfigError: Configuration problem
+    ///
+    /// [`DEFAULT_RETRYABLE_STATUSES`]: crate::config::DEFAULT_RETRYABLE_STATUSES
     pub fn is_retryable(&self) -> bool {
-        matches!(
-            self,
-            ModelQueryError::StreamError { .. } | ModelQueryError::Timeout { .. }
-        )
+        match self {
+            ModelQueryError::StreamError { .. } | ModelQueryError::Timeout { .. } => true,
+            ModelQueryError::UpstreamStatus { status, .. } => {
+                crate::config::DEFAULT_RETRYABLE_STATUSES.contains(status)
+            }
+            _ => false,
+        }
+    }
+
+    /// HTTP status the backend answered with, for `UpstreamStatus` errors
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            ModelQueryError::UpstreamStatus { status, .. } => Some(*status),
+            _ => None,
+        }
     }
 }
 
@@ -108,6 +133,21 @@ pub enum AppError {
         source: toml::de::Error,
     },
 
+    /// Failed to parse YAML configuration (`.yaml` / `.yml` files)
+    #[error("Failed to parse config file '{path}': {source}")]
+    ConfigYamlParseFailed {
+        path: String,
+        #[source]
+        source: serde_yaml::Error,
+    },
+
+    /// Config file extension is neither TOML nor YAML
+    #[error(
+        "Unsupported config file format '{path}': unrecognized extension '.{extension}'. \
+        Use .toml, .yaml, or .yml"
+    )]
+    ConfigFormatUnsupported { path: String, extension: String },
+
     /// Config validation failed after successful parsing
     #[error("Config validation failed for '{path}': {reason}")]
     ConfigValidationFailed { path: String, reason: String },
@@ -136,9 +176,56 @@ pub enum AppError {
     #[error("Invalid request: {0}")]
     Validation(String),
 
+    /// Request body exceeded `server.max_request_body_bytes`
+    #[error(
+        "Request body exceeds the maximum size of {limit_bytes} bytes. \
+         Shorten the prompt or raise server.max_request_body_bytes."
+    )]
+    PayloadTooLarge { limit_bytes: usize },
+
+    /// Request body is not valid JSON, or a field has the wrong JSON type
+    ///
+    /// `detail` is the parser's message, including the line and column of the
+    /// problem. `param` is the path of the offending field when one is known
+    /// (e.g. `messages[0].content`) and is reported in the error's `param`.
+    #[error("Malformed JSON request body: {detail}")]
+    RequestDeserialization {
+        detail: String,
+        param: Option<String>,
+    },
+
+    /// An `Idempotency-Key` was reused with a different request body (422)
+    #[error(
+        "Idempotency-Key '{key}' was already used with a different request body. \
+         Use a new key for a new request."
+    )]
+    IdempotencyKeyReused { key: String },
+
+    /// Admin request without a valid `server.admin_token` bearer token (401)
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
+    /// A user went over `server.user_tracking.max_requests` with `reject_over_limit` (429)
+    #[error("Rate limit exceeded: {message}")]
+    RateLimited {
+        message: String,
+        retry_after_seconds: u64,
+    },
+
     #[error("Routing failed: {0}")]
     RoutingFailed(String),
 
+    /// No healthy endpoints are available right now, but some may recover
+    ///
+    /// Unlike `RoutingFailed` (complete exhaustion), this is transient: the
+    /// endpoints are configured but currently unhealthy or saturated. Returned
+    /// as 503 with a `Retry-After` header of `retry_after_seconds`.
+    #[error("Endpoints temporarily unavailable: {message}")]
+    EndpointsUnavailable {
+        message: String,
+        retry_after_seconds: u64,
+    },
+
     /// Hybrid routing failed after LLM fallback
     ///
     /// Preserves context about the hybrid routing attempt including the
@@ -171,6 +258,22 @@ pub enum AppError {
         timeout_seconds: u64,
     },
 
+    /// No first content chunk within `server.first_token_timeout_ms`
+    #[error("No first token from {endpoint} within {timeout_ms} ms")]
+    FirstTokenTimeout { endpoint: String, timeout_ms: u64 },
+
+    /// The whole request (routing + retries) exceeded `server.max_request_duration_seconds`
+    #[error("Request exceeded the maximum duration of {timeout_seconds} seconds")]
+    RequestTimeout { timeout_seconds: u64 },
+
+    /// The client's `x-octoroute-deadline-ms` was too short or elapsed mid-request
+    #[error("Request deadline of {deadline_ms} ms (x-octoroute-deadline-ms) cannot be met")]
+    DeadlineExceeded { deadline_ms: u64 },
+
+    /// `server.max_upstream_calls` was spent before any completion call could be made
+    #[error("Upstream call budget of {limit} calls exhausted before a completion was attempted")]
+    CallBudgetExhausted { limit: usize },
+
     #[error("Health check failed for {endpoint}: {reason}")]
     HealthCheckFailed { endpoint: String, reason: String },
 
@@ -197,20 +300,32 @@ impl AppError {
     /// Returns the OpenAI error type for this error
     fn error_type(&self) -> &'static str {
         match self {
-            Self::Validation(_) => "invalid_request_error",
+            Self::Validation(_)
+            | Self::PayloadTooLarge { .. }
+            | Self::RequestDeserialization { .. }
+            | Self::IdempotencyKeyReused { .. } => "invalid_request_error",
+            Self::Unauthorized(_) => "authentication_error",
+            Self::RateLimited { .. } => "rate_limit_error",
             Self::Config(_)
             | Self::ConfigFileRead { .. }
             | Self::ConfigParseFailed { .. }
+            | Self::ConfigYamlParseFailed { .. }
+            | Self::ConfigFormatUnsupported { .. }
             | Self::ConfigValidationFailed { .. }
             | Self::ConfigFileExists { .. }
             | Self::ConfigFileWrite { .. }
             | Self::RoutingFailed(_)
+            | Self::EndpointsUnavailable { .. }
             | Self::HybridRoutingFailed { .. }
             | Self::HealthCheckFailed { .. }
             | Self::HealthTracking(_)
             | Self::Internal(_) => "server_error",
             Self::StreamInterrupted { .. }
             | Self::EndpointTimeout { .. }
+            | Self::FirstTokenTimeout { .. }
+            | Self::RequestTimeout { .. }
+            | Self::DeadlineExceeded { .. }
+            | Self::CallBudgetExhausted { .. }
             | Self::ModelQuery(_)
             | Self::LlmRouting(_) => "api_error",
         }
@@ -221,20 +336,40 @@ impl IntoResponse for AppError {
     fn into_response(self) -> Response {
         let (status, message) = match &self {
             Self::Validation(msg) => (StatusCode::BAD_REQUEST, msg.clone()),
+            Self::PayloadTooLarge { .. } => (StatusCode::PAYLOAD_TOO_LARGE, self.to_string()),
+            Self::RequestDeserialization { .. } => (StatusCode::BAD_REQUEST, self.to_string()),
+            Self::IdempotencyKeyReused { .. } => {
+                (StatusCode::UNPROCESSABLE_ENTITY, self.to_string())
+            }
+            Self::Unauthorized(_) => (StatusCode::UNAUTHORIZED, self.to_string()),
+            Self::RateLimited { .. } => (StatusCode::TOO_MANY_REQUESTS, self.to_string()),
             Self::Config(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg.clone()),
             Self::ConfigFileRead { .. } => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
             Self::ConfigParseFailed { .. } => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+            Self::ConfigYamlParseFailed { .. } => {
+                (StatusCode::INTERNAL_SERVER_ERROR, self.to_string())
+            }
+            Self::ConfigFormatUnsupported { .. } => {
+                (StatusCode::INTERNAL_SERVER_ERROR, self.to_string())
+            }
             Self::ConfigValidationFailed { .. } => {
                 (StatusCode::INTERNAL_SERVER_ERROR, self.to_string())
             }
             Self::ConfigFileExists { .. } => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
             Self::ConfigFileWrite { .. } => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
             Self::RoutingFailed(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg.clone()),
+            Self::EndpointsUnavailable { .. } => {
+                (StatusCode::SERVICE_UNAVAILABLE, self.to_string())
+            }
             Self::HybridRoutingFailed { .. } => {
                 (StatusCode::INTERNAL_SERVER_ERROR, self.to_string())
             }
             Self::StreamInterrupted { .. } => (StatusCode::BAD_GATEWAY, self.to_string()),
             Self::EndpointTimeout { .. } => (StatusCode::GATEWAY_TIMEOUT, self.to_string()),
+            Self::FirstTokenTimeout { .. } => (StatusCode::GATEWAY_TIMEOUT, self.to_string()),
+            Self::RequestTimeout { .. } => (StatusCode::GATEWAY_TIMEOUT, self.to_string()),
+            Self::DeadlineExceeded { .. } => (StatusCode::GATEWAY_TIMEOUT, self.to_string()),
+            Self::CallBudgetExhausted { .. } => (StatusCode::BAD_GATEWAY, self.to_string()),
             Self::HealthCheckFailed { .. } => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
             Self::HealthTracking(_) => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
             Self::ModelQuery(_) => (StatusCode::BAD_GATEWAY, self.to_string()),
@@ -243,18 +378,40 @@ impl IntoResponse for AppError {
         };
 
         let error_type = self.error_type();
+        let param = match &self {
+            Self::RequestDeserialization { param, .. } => param.clone(),
+            _ => None,
+        };
 
         // Use OpenAI-compatible error format
         let body = Json(serde_json::json!({
             "error": {
                 "message": message,
                 "type": error_type,
-                "param": null,
+                "param": param,
                 "code": null
             }
         }));
 
-        (status, body).into_response()
+        let mut response = (status, body).into_response();
+
+        // Transient exhaustion tells clients when endpoints may have recovered,
+        // and a rate-limited user when their window ends
+        if let Self::EndpointsUnavailable {
+            retry_after_seconds,
+            ..
+        }
+        | Self::RateLimited {
+            retry_after_seconds,
+            ..
+        } = self
+        {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(retry_after_seconds));
+        }
+
+        response
     }
 }
 
@@ -310,6 +467,69 @@ mod tests {
         assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
     }
 
+    #[test]
+    fn test_endpoints_unavailable_returns_503_with_retry_after() {
+        let err = AppError::EndpointsUnavailable {
+            message: "No available Fast tier endpoints".to_string(),
+            retry_after_seconds: 30,
+        };
+        let response = err.into_response();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(
+            response.headers().get(header::RETRY_AFTER).unwrap(),
+            "30",
+            "Transient exhaustion must tell clients when to retry"
+        );
+    }
+
+    #[test]
+    fn test_non_transient_errors_have_no_retry_after() {
+        let errors = vec![
+            AppError::Config("No endpoints configured".to_string()),
+            AppError::RoutingFailed("All Fast tier endpoints exhausted".to_string()),
+        ];
+
+        for err in errors {
+            let description = format!("{:?}", err);
+            let response = err.into_response();
+            assert!(
+                response.headers().get(header::RETRY_AFTER).is_none(),
+                "{} should not carry Retry-After",
+                description
+            );
+        }
+    }
+
+    #[test]
+    fn test_payload_too_large_returns_413() {
+        let err = AppError::PayloadTooLarge { limit_bytes: 1024 };
+        assert!(err.to_string().contains("1024 bytes"));
+        let response = err.into_response();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[test]
+    fn test_idempotency_key_reused_returns_422() {
+        let err = AppError::IdempotencyKeyReused {
+            key: "key-1".to_string(),
+        };
+        assert!(err.to_string().contains("key-1"));
+        let response = err.into_response();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[test]
+    fn test_request_deserialization_returns_400_with_param() {
+        let err = AppError::RequestDeserialization {
+            detail: "temperature: invalid type: string \"hot\", expected f64 at line 1 column 20"
+                .to_string(),
+            param: Some("temperature".to_string()),
+        };
+        assert!(err.to_string().contains("line 1 column 20"));
+        let response = err.into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
     #[test]
     fn test_internal_error_response_status() {
         let err = AppError::Internal("test".to_string());
@@ -346,6 +566,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_request_timeout_error_returns_504_gateway_timeout() {
+        let err = AppError::RequestTimeout {
+            timeout_seconds: 60,
+        };
+        let response = err.into_response();
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+    }
+
+    #[test]
+    fn test_deadline_exceeded_returns_504_gateway_timeout() {
+        let err = AppError::DeadlineExceeded { deadline_ms: 50 };
+        assert_eq!(err.error_type(), "api_error");
+        let response = err.into_response();
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+    }
+
+    #[test]
+    fn test_call_budget_exhausted_returns_502_bad_gateway() {
+        let err = AppError::CallBudgetExhausted { limit: 3 };
+        assert_eq!(err.error_type(), "api_error");
+        let response = err.into_response();
+        assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+    }
+
     #[test]
     fn test_model_query_error_returns_502_bad_gateway() {
         let err = AppError::ModelQuery(ModelQueryError::StreamError {
@@ -0,0 +1,157 @@
+//! Operator endpoints for incident response
+//!
+//! - `POST /admin/endpoints/{name}/drain`: take an endpoint out of selection
+//! - `POST /admin/endpoints/{name}/undrain`: return it to automatic health tracking
+//! - `POST /admin/drain`: report not-ready on `/readyz` so load balancers stop
+//!   sending traffic, while requests already in flight (and any that still
+//!   arrive) are served normally
+//! - `POST /admin/undrain`: report ready again
+//! - `POST /admin/reload`: re-read the config file, like SIGHUP
+//! - `POST /admin/routing/strategy`: switch the routing strategy (e.g. off a
+//!   flaky LLM router to `rule`) until the next reload
+//!
+//! Mounted only when `server.admin_token` is set, behind
+//! [`admin_auth_middleware`](crate::middleware::admin_auth_middleware). Drains
+//! are held in memory and do not survive a restart.
+
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::config::RoutingStrategy;
+use crate::error::{AppError, AppResult};
+use crate::handlers::AppState;
+use crate::models::health::HealthError;
+use crate::reload::{ReloadSummary, ReloadableState};
+
+/// Response for drain/undrain requests
+#[derive(Debug, Serialize)]
+pub struct DrainResponse {
+    endpoint: String,
+    drained: bool,
+    /// Whether selection may use the endpoint now (drain and automatic health combined)
+    healthy: bool,
+}
+
+/// POST /admin/endpoints/{name}/drain handler
+pub async fn drain(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> AppResult<Json<DrainResponse>> {
+    set_drained(&state, name, true).await
+}
+
+/// POST /admin/endpoints/{name}/undrain handler
+pub async fn undrain(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> AppResult<Json<DrainResponse>> {
+    set_drained(&state, name, false).await
+}
+
+/// Response for server drain/undrain requests
+#[derive(Debug, Serialize)]
+pub struct ServerDrainResponse {
+    draining: bool,
+}
+
+/// POST /admin/drain handler
+pub async fn drain_server(State(state): State<AppState>) -> Json<ServerDrainResponse> {
+    set_server_draining(&state, true)
+}
+
+/// POST /admin/undrain handler
+pub async fn undrain_server(State(state): State<AppState>) -> Json<ServerDrainResponse> {
+    set_server_draining(&state, false)
+}
+
+fn set_server_draining(state: &AppState, draining: bool) -> Json<ServerDrainResponse> {
+    let was_draining = state.set_draining(draining);
+    if draining && !was_draining {
+        tracing::warn!("Server draining via admin API; /readyz now reports 503");
+    } else if was_draining && !draining {
+        tracing::warn!("Server drain lifted via admin API; /readyz reports endpoint readiness");
+    }
+    Json(ServerDrainResponse { draining })
+}
+
+/// POST /admin/reload handler
+///
+/// A config that fails to load or validate is a 400 carrying the error, and
+/// the server keeps running on its current config.
+pub async fn reload(State(state): State<ReloadableState>) -> AppResult<Json<ReloadSummary>> {
+    match state.reload().await {
+        Ok(summary) => Ok(Json(summary)),
+        Err(e) => {
+            tracing::warn!(error = %e, "Config reload via admin API failed, keeping current config");
+            Err(AppError::Validation(format!("Config reload failed: {}", e)))
+        }
+    }
+}
+
+/// Request body for `POST /admin/routing/strategy`
+#[derive(Debug, Deserialize)]
+pub struct RoutingStrategyRequest {
+    strategy: RoutingStrategy,
+}
+
+/// Response for routing strategy switches
+#[derive(Debug, Serialize)]
+pub struct RoutingStrategyResponse {
+    previous: RoutingStrategy,
+    strategy: RoutingStrategy,
+}
+
+/// POST /admin/routing/strategy handler
+///
+/// A strategy the configured tiers can't support (an LLM strategy whose
+/// router tier has no endpoints, or `tool`) is a 400, and the current router
+/// stays in place.
+pub async fn switch_routing_strategy(
+    State(state): State<ReloadableState>,
+    Json(request): Json<RoutingStrategyRequest>,
+) -> AppResult<Json<RoutingStrategyResponse>> {
+    match state.switch_routing_strategy(request.strategy).await {
+        Ok(previous) => Ok(Json(RoutingStrategyResponse {
+            previous,
+            strategy: request.strategy,
+        })),
+        Err(e) => {
+            tracing::warn!(
+                strategy = ?request.strategy,
+                error = %e,
+                "Routing strategy switch via admin API rejected"
+            );
+            Err(AppError::Validation(format!(
+                "Cannot switch routing strategy to {:?}: {}",
+                request.strategy, e
+            )))
+        }
+    }
+}
+
+async fn set_drained(
+    state: &AppState,
+    name: String,
+    drained: bool,
+) -> AppResult<Json<DrainResponse>> {
+    let health_checker = state.selector().health_checker();
+    health_checker
+        .set_drained(&name, drained)
+        .await
+        .map_err(|e| match e {
+            HealthError::UnknownEndpoint(name) => {
+                AppError::Validation(format!("Unknown endpoint '{}'", name))
+            }
+            other => AppError::HealthTracking(other),
+        })?;
+
+    let healthy = health_checker.is_healthy(&name).await;
+    Ok(Json(DrainResponse {
+        endpoint: name,
+        drained,
+        healthy,
+    }))
+}
@@ -1,14 +1,30 @@
 //! Chat endpoint handler
 //!
-//! Handles POST /chat requests with intelligent model routing.
+//! Handles POST /chat requests with intelligent model routing. With
+//! `"stream": true` the routed completion is sent as Server-Sent Events in the
+//! `/v1/chat/completions` chunk format instead of a single JSON body.
 
 use crate::config::ModelEndpoint;
 use crate::error::AppError;
-use crate::handlers::AppState;
+use crate::handlers::openai::streaming::{select_stream_endpoint, start_stream};
+use crate::handlers::{AppState, set_endpoint_headers, set_routing_path};
 use crate::middleware::RequestId;
-use crate::router::{Importance, RouteMetadata, RoutingStrategy, TargetModel, TaskType};
-use crate::shared::query::{QueryConfig, execute_query_with_retry, record_routing_metrics};
-use axum::{Extension, Json, extract::State, response::IntoResponse};
+use crate::router::{
+    Importance, RouteMetadata, RoutingStrategy, TargetModel, TaskClassifier, TaskType,
+};
+use crate::shared::call_budget::CallBudget;
+use crate::shared::context_window;
+use crate::shared::conversation_limits;
+use crate::shared::prompt_log::log_routed_prompt;
+use crate::shared::query::{
+    QueryConfig, SamplingParams, execute_query_with_retry, notify_routing_observer,
+    record_routing_decision, record_routing_metrics, routing_metadata, task_type_tags,
+};
+use axum::{
+    Extension, Json,
+    extract::State,
+    response::{IntoResponse, Response},
+};
 use serde::{Deserialize, Deserializer, Serialize};
 
 /// Maximum allowed message length in characters (100K chars)
@@ -22,6 +38,11 @@ pub struct ChatRequest {
     message: String,
     importance: Importance,
     task_type: TaskType,
+    /// Whether `task_type` came from the client rather than the default
+    #[serde(skip)]
+    task_type_explicit: bool,
+    /// Stream the completion as SSE chunks (off by default)
+    stream: bool,
 }
 
 impl ChatRequest {
@@ -40,13 +61,30 @@ impl ChatRequest {
         self.task_type
     }
 
+    /// Whether the client asked for a streamed response
+    pub fn stream(&self) -> bool {
+        self.stream
+    }
+
     /// Convert request to RouteMetadata for routing decisions
     pub fn to_metadata(&self) -> RouteMetadata {
+        self.to_metadata_with(None)
+    }
+
+    /// Like [`ChatRequest::to_metadata`], inferring an omitted task type
+    ///
+    /// When the client sent no `task_type` and `classifier` recognizes the
+    /// message, its answer replaces the default. An explicit `task_type` is
+    /// never overridden.
+    pub fn to_metadata_with(&self, classifier: Option<&dyn TaskClassifier>) -> RouteMetadata {
         let token_estimate = RouteMetadata::estimate_tokens(&self.message);
+        let inferred = classifier
+            .filter(|_| !self.task_type_explicit)
+            .and_then(|classifier| classifier.classify(&self.message));
         RouteMetadata {
             token_estimate,
             importance: self.importance,
-            task_type: self.task_type,
+            task_type: inferred.unwrap_or(self.task_type),
         }
     }
 }
@@ -63,7 +101,9 @@ impl<'de> Deserialize<'de> for ChatRequest {
             #[serde(default)]
             importance: Importance,
             #[serde(default)]
-            task_type: TaskType,
+            task_type: Option<TaskType>,
+            #[serde(default)]
+            stream: bool,
         }
 
         let raw = RawChatRequest::deserialize(deserializer)?;
@@ -87,7 +127,9 @@ impl<'de> Deserialize<'de> for ChatRequest {
         Ok(ChatRequest {
             message: raw.message,
             importance: raw.importance,
-            task_type: raw.task_type,
+            task_type: raw.task_type.unwrap_or_default(),
+            task_type_explicit: raw.task_type.is_some(),
+            stream: raw.stream,
         })
     }
 }
@@ -276,27 +318,49 @@ impl<'de> Deserialize<'de> for ChatResponse {
 /// - Endpoint selection with health checking
 /// - Model query with streaming response
 /// - Health state updates (async lock acquisition)
+///
+/// The `x-octoroute-routing-path` response header repeats `routing_strategy`,
+/// so clients can see whether a hybrid request took the rule fast path or
+/// the LLM fallback without parsing the body.
+///
+/// ## Streaming
+///
+/// A request with `"stream": true` is routed the same way, then streamed like
+/// a `/v1/chat/completions` stream (same chunks, `[DONE]` terminator, failover
+/// before the first token and keep-alive comments). Routing warnings move to
+/// the `x-octoroute-warning` header since there is no JSON body to carry them.
 pub async fn handler(
     State(state): State<AppState>,
     Extension(request_id): Extension<RequestId>,
     Json(request): Json<ChatRequest>,
-) -> Result<impl IntoResponse, AppError> {
+) -> Result<Response, AppError> {
     tracing::debug!(
         request_id = %request_id,
         message_length = request.message().len(),
         importance = ?request.importance(),
         task_type = ?request.task_type(),
+        stream = request.stream(),
         "Received chat request"
     );
 
-    // Convert to metadata for routing
-    let metadata = request.to_metadata();
+    conversation_limits::check(&state.config().server, 1, request.message().chars().count())?;
+
+    // Convert to metadata for routing (filling in an omitted task type if enabled,
+    // with the token estimate capped for routing)
+    let metadata = routing_metadata(
+        &state,
+        request.to_metadata_with(state.task_classifier()),
+        request_id,
+    );
+
+    // Router and completion attempts share one cap on upstream calls
+    let call_budget = CallBudget::new(state.config().server.max_upstream_calls);
 
     // Use router to determine target tier
     let routing_start = std::time::Instant::now();
     let decision = state
         .router()
-        .route(request.message(), &metadata, state.selector())
+        .route_with_budget(request.message(), &metadata, state.selector(), &call_budget)
         .await?;
     let routing_duration_ms = routing_start.elapsed().as_secs_f64() * 1000.0;
 
@@ -311,21 +375,72 @@ pub async fn handler(
 
     // Record routing metrics
     record_routing_metrics(&state, &decision, routing_duration_ms, request_id);
+    record_routing_decision(&state, &decision, request_id);
+
+    // House system prompt (if configured) is added only now, after routing
+    let backend_prompt = state
+        .system_prompt()
+        .map(|system_prompt| system_prompt.backend_prompt_for_message(request.message()));
+    let query_prompt = backend_prompt.as_deref().unwrap_or(request.message());
+
+    // A prompt too large for the tier is rejected or escalated before selection
+    let token_estimate = RouteMetadata::estimate_tokens(query_prompt);
+    let decision =
+        context_window::fit_decision(state.config(), decision, token_estimate, request_id)?;
+    notify_routing_observer(&state, &metadata, &decision);
+    log_routed_prompt(
+        &state.config().observability,
+        request_id,
+        request.message(),
+        decision.target(),
+    );
+
+    // Held until the response is built (or the stream ends); a full tier sheds
+    // with 503 (after any queue wait)
+    let tier_permit = state.tier_budgets().acquire(decision.target()).await?;
+
+    // Streams use endpoint defaults for sampling, like the non-streaming query below
+    if request.stream() {
+        let (tier, endpoint, routing_warnings, failover) = select_stream_endpoint(
+            &state,
+            &decision,
+            context_window::oversized_endpoints(state.config(), token_estimate),
+            task_type_tags(metadata.task_type),
+            &call_budget,
+        )
+        .await?;
+        return start_stream(
+            &state,
+            request_id,
+            query_prompt.to_string(),
+            SamplingParams::default(),
+            endpoint,
+            tier,
+            routing_warnings,
+            Some(failover),
+            call_budget,
+            tier_permit,
+            Some(&decision),
+            None,
+        );
+    }
 
     // Execute query with retry logic (uses shared module)
     // Legacy chat endpoint doesn't support sampling parameters - use endpoint defaults
-    let config = QueryConfig::default();
-    let result = execute_query_with_retry(
-        &state,
-        &decision,
-        request.message(),
-        request_id,
-        &config,
-        None,
-    )
-    .await?;
+    let config = QueryConfig::default()
+        .with_preferred_tags(task_type_tags(metadata.task_type))
+        .with_excluded_endpoints(context_window::oversized_endpoints(
+            state.config(),
+            token_estimate,
+        ))
+        .with_call_budget(call_budget);
+    let result =
+        execute_query_with_retry(&state, &decision, query_prompt, request_id, &config, None)
+            .await?;
 
     // Build response
+    let routing_path = result.strategy;
+    let endpoint_name = result.endpoint.name().to_string();
     let response = if result.warnings.is_empty() {
         ChatResponse::new(
             result.content,
@@ -343,7 +458,15 @@ pub async fn handler(
         )
     };
 
-    Ok(Json(response))
+    let mut response = Json(response).into_response();
+    set_routing_path(&mut response, routing_path);
+    set_endpoint_headers(
+        &mut response,
+        &state.config().observability,
+        &endpoint_name,
+        decision.router_endpoint(),
+    );
+    Ok(response)
 }
 
 #[cfg(test)]
@@ -377,6 +500,39 @@ mod tests {
         assert_eq!(req.task_type(), TaskType::Code);
     }
 
+    #[test]
+    fn test_chat_request_stream_defaults_to_false() {
+        let req: ChatRequest = serde_json::from_str(r#"{"message": "Hi"}"#).unwrap();
+        assert!(!req.stream());
+
+        let req: ChatRequest =
+            serde_json::from_str(r#"{"message": "Hi", "stream": true}"#).unwrap();
+        assert!(req.stream());
+    }
+
+    #[test]
+    fn test_classifier_only_fills_in_omitted_task_type() {
+        let classifier = crate::router::KeywordTaskClassifier;
+
+        let omitted: ChatRequest =
+            serde_json::from_str(r#"{"message": "Write a story about a dragon"}"#).unwrap();
+        assert_eq!(
+            omitted.to_metadata_with(Some(&classifier)).task_type,
+            TaskType::CreativeWriting
+        );
+        assert_eq!(omitted.to_metadata().task_type, TaskType::QuestionAnswer);
+
+        // Explicitly asking for the default still counts as explicit
+        let explicit: ChatRequest = serde_json::from_str(
+            r#"{"message": "Write a story about a dragon", "task_type": "question_answer"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            explicit.to_metadata_with(Some(&classifier)).task_type,
+            TaskType::QuestionAnswer
+        );
+    }
+
     #[test]
     fn test_chat_request_rejects_empty_message() {
         let json = r#"{"message": ""}"#;
@@ -5,7 +5,10 @@
 use axum::{Json, extract::State, http::StatusCode};
 use serde::Serialize;
 
+use crate::config::{RoutingConfig, RoutingStrategy};
 use crate::handlers::AppState;
+use crate::handlers::chat::ModelTier;
+use crate::models::EndpointHealth;
 
 /// Service health status
 ///
@@ -30,6 +33,32 @@ pub enum HealthTrackingStatus {
     Degraded,
 }
 
+/// Most recent failure of an endpoint that has not recovered since
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EndpointFailure {
+    /// Endpoint name
+    pub endpoint: String,
+    /// Failure kind: connection, timeout, http_status, or parse
+    pub kind: &'static str,
+    /// HTTP status code, for `http_status` failures
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    /// Consecutive failures so far
+    pub consecutive_failures: u32,
+}
+
+impl EndpointFailure {
+    /// The endpoint's last failure, or `None` if it has none on record
+    pub fn from_health(health: &EndpointHealth) -> Option<Self> {
+        health.last_failure().map(|kind| Self {
+            endpoint: health.name().to_string(),
+            kind: kind.as_str(),
+            status: kind.status(),
+            consecutive_failures: health.consecutive_failures(),
+        })
+    }
+}
+
 /// Health check response
 ///
 /// Uses type-safe enums for status fields, preventing invalid states at compile time.
@@ -46,6 +75,14 @@ pub struct HealthResponse {
     background_task_status: HealthTrackingStatus,
     /// Number of background task failures (restart attempts)
     background_task_failures: u64,
+    /// Endpoints whose most recent check or request failed, by name
+    endpoint_failures: Vec<EndpointFailure>,
+    /// Routing strategy in use, including a switch made at runtime
+    #[serde(skip_serializing_if = "Option::is_none")]
+    routing_strategy: Option<RoutingStrategy>,
+    /// Tier the router model is queried on, for the llm and hybrid strategies
+    #[serde(skip_serializing_if = "Option::is_none")]
+    router_tier: Option<ModelTier>,
 }
 
 impl HealthResponse {
@@ -92,8 +129,29 @@ impl HealthResponse {
             metrics_recording_status,
             background_task_status,
             background_task_failures,
+            endpoint_failures: Vec::new(),
+            routing_strategy: None,
+            router_tier: None,
         }
     }
+
+    /// Attach the active routing strategy and, if it queries a router model, its tier
+    pub fn with_routing(mut self, routing: &RoutingConfig) -> Self {
+        self.routing_strategy = Some(routing.strategy);
+        self.router_tier = matches!(
+            routing.strategy,
+            RoutingStrategy::Llm | RoutingStrategy::Hybrid
+        )
+        .then(|| routing.router_tier().into());
+        self
+    }
+
+    /// Attach the endpoints currently failing, sorted by name
+    pub fn with_endpoint_failures(mut self, mut failures: Vec<EndpointFailure>) -> Self {
+        failures.sort_by(|a, b| a.endpoint.cmp(&b.endpoint));
+        self.endpoint_failures = failures;
+        self
+    }
 }
 
 /// Health check handler
@@ -104,6 +162,10 @@ impl HealthResponse {
 ///   indicating mark_success/mark_failure operations are failing.
 /// - Metrics recording status is "degraded" if any metrics recording failures have occurred,
 ///   indicating Prometheus metrics recording is failing.
+/// - Endpoint failures list each endpoint whose last check or request failed, with
+///   the failure kind, until it succeeds again.
+/// - Routing strategy is the one serving requests (after any admin switch), with
+///   the router tier for the llm and hybrid strategies.
 pub async fn handler(State(state): State<AppState>) -> (StatusCode, Json<HealthResponse>) {
     let health_tracking_failures = state.metrics().health_tracking_failures_count();
     let metrics_recording_failures = state.metrics().metrics_recording_failures_count();
@@ -112,7 +174,18 @@ pub async fn handler(State(state): State<AppState>) -> (StatusCode, Json<HealthR
         health_tracking_failures,
         metrics_recording_failures,
         background_task_failures,
-    );
+    )
+    .with_endpoint_failures(
+        state
+            .selector()
+            .health_checker()
+            .get_all_statuses()
+            .await
+            .iter()
+            .filter_map(EndpointFailure::from_health)
+            .collect(),
+    )
+    .with_routing(&state.config().routing);
 
     (StatusCode::OK, Json(response))
 }
@@ -236,4 +309,25 @@ router_tier = "balanced"
         assert_eq!(json["health_tracking_status"], "degraded");
         assert_eq!(json["metrics_recording_status"], "degraded");
     }
+
+    #[tokio::test]
+    async fn test_health_handler_reports_rule_strategy_without_router_tier() {
+        let (_, Json(response)) = handler(State(create_test_state())).await;
+
+        let json = serde_json::to_value(&response).expect("Should serialize");
+        assert_eq!(json["routing_strategy"], "rule");
+        assert!(json.get("router_tier").is_none(), "{}", json);
+    }
+
+    #[tokio::test]
+    async fn test_health_handler_reports_hybrid_strategy_and_router_tier() {
+        let state = create_test_state()
+            .with_routing_strategy(RoutingStrategy::Hybrid)
+            .expect("hybrid router should build");
+        let (_, Json(response)) = handler(State(state)).await;
+
+        let json = serde_json::to_value(&response).expect("Should serialize");
+        assert_eq!(json["routing_strategy"], "hybrid");
+        assert_eq!(json["router_tier"], "balanced");
+    }
 }
@@ -1,21 +1,68 @@
 //! Prometheus metrics endpoint
 //!
-//! Exposes metrics in Prometheus text format for scraping.
+//! Exposes metrics in Prometheus text format for scraping. Deployments without
+//! Prometheus can request the same data as JSON (`Accept: application/json`) or
+//! CSV (`Accept: text/csv`) instead.
 //!
 //! This module is only available when the `metrics` feature is enabled.
 
-use axum::{extract::State, http::StatusCode};
+use axum::{
+    Json,
+    extract::State,
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
 
 use crate::handlers::AppState;
+use crate::metrics::MetricSample;
+
+/// Output format selected from the request's `Accept` header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MetricsFormat {
+    Prometheus,
+    Json,
+    Csv,
+}
+
+impl MetricsFormat {
+    /// Pick the first supported media type listed in `Accept`, defaulting to Prometheus text
+    fn from_headers(headers: &HeaderMap) -> Self {
+        let Some(accept) = headers
+            .get(header::ACCEPT)
+            .and_then(|value| value.to_str().ok())
+        else {
+            return Self::Prometheus;
+        };
+
+        for media_type in accept.split(',') {
+            let media_type = media_type.split(';').next().unwrap_or("").trim();
+            if media_type.eq_ignore_ascii_case("application/json") {
+                return Self::Json;
+            }
+            if media_type.eq_ignore_ascii_case("text/csv") {
+                return Self::Csv;
+            }
+        }
+        Self::Prometheus
+    }
+}
+
+/// JSON export body: `{"metrics": [{"name", "labels", "value"}, ...]}`
+#[derive(Debug, Serialize)]
+struct MetricsExport {
+    metrics: Vec<MetricSample>,
+}
 
 /// Metrics handler for Prometheus scraping
 ///
-/// Returns metrics in Prometheus text format.
+/// Returns metrics in Prometheus text format unless the `Accept` header asks
+/// for `application/json` or `text/csv`.
 ///
 /// # Response
 ///
-/// - `200 OK` with metrics in Prometheus text format
-/// - `500 Internal Server Error` if metrics collection fails
+/// - `200 OK` with metrics in the negotiated format
+/// - `500 Internal Server Error` if Prometheus text encoding fails
 ///
 /// # Example
 ///
@@ -24,24 +71,70 @@ use crate::handlers::AppState;
 /// # HELP octoroute_requests_total Total number of chat requests
 /// # TYPE octoroute_requests_total counter
 /// octoroute_requests_total{tier="fast",strategy="rule"} 42
+///
+/// curl -H "Accept: application/json" http://localhost:3000/metrics
+/// {"metrics":[{"name":"octoroute_requests_total","labels":{"strategy":"rule","tier":"fast"},"value":42.0}, ...]}
 /// ```
-pub async fn handler(State(state): State<AppState>) -> (StatusCode, String) {
+pub async fn handler(State(state): State<AppState>, headers: HeaderMap) -> Response {
     let metrics = state.metrics();
-    match metrics.gather() {
-        Ok(output) => (StatusCode::OK, output),
-        Err(e) => {
-            tracing::error!(
-                error = %e,
-                "Failed to gather metrics for Prometheus scraping. \
-                This indicates a metrics encoding issue (invalid UTF-8, \
-                corrupted labels, or encoder failure). Error: {}",
-                e
-            );
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                format!("Failed to gather metrics: {}", e),
-            )
-        }
+    match MetricsFormat::from_headers(&headers) {
+        MetricsFormat::Json => Json(MetricsExport {
+            metrics: metrics.samples(),
+        })
+        .into_response(),
+        MetricsFormat::Csv => (
+            [(header::CONTENT_TYPE, "text/csv; charset=utf-8")],
+            render_csv(&metrics.samples()),
+        )
+            .into_response(),
+        MetricsFormat::Prometheus => match metrics.gather() {
+            Ok(output) => (StatusCode::OK, output).into_response(),
+            Err(e) => {
+                tracing::error!(
+                    error = %e,
+                    "Failed to gather metrics for Prometheus scraping. \
+                    This indicates a metrics encoding issue (invalid UTF-8, \
+                    corrupted labels, or encoder failure). Error: {}",
+                    e
+                );
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("Failed to gather metrics: {}", e),
+                )
+                    .into_response()
+            }
+        },
+    }
+}
+
+/// Render samples as CSV with a `name,labels,value` header
+///
+/// Labels are joined as `key=value` pairs separated by `;` in a single column.
+fn render_csv(samples: &[MetricSample]) -> String {
+    let mut csv = String::from("name,labels,value\n");
+    for sample in samples {
+        let labels = sample
+            .labels
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<_>>()
+            .join(";");
+        csv.push_str(&format!(
+            "{},{},{}\n",
+            csv_field(&sample.name),
+            csv_field(&labels),
+            sample.value
+        ));
+    }
+    csv
+}
+
+/// Quote a CSV field if it contains a delimiter, quote, or newline
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
     }
 }
 
@@ -51,6 +144,35 @@ mod tests {
     use crate::config::Config;
     use std::sync::Arc;
 
+    /// Call the handler with the given `Accept` header and return status, content type, and body
+    async fn scrape_as(state: AppState, accept: Option<&str>) -> (StatusCode, String, String) {
+        let mut headers = HeaderMap::new();
+        if let Some(accept) = accept {
+            headers.insert(header::ACCEPT, accept.parse().unwrap());
+        }
+        let response = handler(State(state), headers).await;
+        let status = response.status();
+        let content_type = response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .map(|v| v.to_str().unwrap().to_string())
+            .unwrap_or_default();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (
+            status,
+            content_type,
+            String::from_utf8(body.to_vec()).unwrap(),
+        )
+    }
+
+    /// Call the handler with no `Accept` header (Prometheus text)
+    async fn scrape(state: AppState) -> (StatusCode, String) {
+        let (status, _, body) = scrape_as(state, None).await;
+        (status, body)
+    }
+
     #[tokio::test]
     async fn test_metrics_handler_returns_prometheus_format() {
         let config_str = r#"
@@ -96,7 +218,7 @@ mod tests {
             .record_request(crate::metrics::Tier::Fast, crate::metrics::Strategy::Rule)
             .unwrap();
 
-        let (status, body) = handler(State(state)).await;
+        let (status, body) = scrape(state).await;
 
         assert_eq!(status, StatusCode::OK);
         assert!(body.contains("# HELP"));
@@ -167,7 +289,7 @@ mod tests {
         for _ in 0..10 {
             let state_clone = Arc::clone(&state);
             let handle = task::spawn(async move {
-                let (status, body) = handler(State(state_clone.as_ref().clone())).await;
+                let (status, body) = scrape(state_clone.as_ref().clone()).await;
                 (status, body)
             });
             handles.push(handle);
@@ -255,7 +377,7 @@ mod tests {
             .record_model_invocation(crate::metrics::Tier::Fast)
             .unwrap();
 
-        let (status, body) = handler(State(state)).await;
+        let (status, body) = scrape(state).await;
 
         assert_eq!(status, StatusCode::OK);
 
@@ -394,7 +516,7 @@ mod tests {
 
         // Don't record any metrics - test with empty registry
 
-        let (status, body) = handler(State(state)).await;
+        let (status, body) = scrape(state).await;
 
         assert_eq!(status, StatusCode::OK, "Should succeed with empty registry");
         assert!(
@@ -402,4 +524,105 @@ mod tests {
             "Should return valid output even with no data"
         );
     }
+
+    fn export_test_state() -> AppState {
+        let config_str = r#"
+            [server]
+            host = "127.0.0.1"
+            port = 3000
+
+            [[models.fast]]
+            name = "test-8b"
+            base_url = "http://localhost:11434/v1"
+            max_tokens = 4096
+
+            [[models.balanced]]
+            name = "test-balanced"
+            base_url = "http://localhost:1235/v1"
+            max_tokens = 8192
+
+            [[models.deep]]
+            name = "test-deep"
+            base_url = "http://localhost:1236/v1"
+            max_tokens = 16384
+
+            [routing]
+            strategy = "rule"
+        "#;
+
+        let config: Config = toml::from_str(config_str).unwrap();
+        let state = AppState::new(Arc::new(config)).unwrap();
+        for _ in 0..3 {
+            state
+                .metrics()
+                .record_request(crate::metrics::Tier::Deep, crate::metrics::Strategy::Rule)
+                .unwrap();
+        }
+        state
+    }
+
+    #[tokio::test]
+    async fn test_default_format_is_prometheus_text() {
+        let (status, content_type, body) = scrape_as(export_test_state(), None).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert!(
+            content_type.starts_with("text/plain"),
+            "Default content type should stay Prometheus text, got: {}",
+            content_type
+        );
+        assert!(body.contains("# TYPE octoroute_requests_total counter"));
+
+        // Accept headers without a supported export type keep the default
+        let (_, content_type, _) = scrape_as(export_test_state(), Some("text/plain, */*")).await;
+        assert!(content_type.starts_with("text/plain"));
+    }
+
+    #[tokio::test]
+    async fn test_json_format_contains_metric_names_and_values() {
+        let (status, content_type, body) =
+            scrape_as(export_test_state(), Some("application/json")).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert!(content_type.starts_with("application/json"));
+
+        let json: serde_json::Value = serde_json::from_str(&body).expect("body should be JSON");
+        let metrics = json["metrics"].as_array().expect("metrics array");
+        let requests = metrics
+            .iter()
+            .find(|m| {
+                m["name"] == "octoroute_requests_total"
+                    && m["labels"]["tier"] == "deep"
+                    && m["labels"]["strategy"] == "rule"
+            })
+            .expect("requests counter should be present with its labels");
+        assert_eq!(requests["value"], 3.0);
+        assert!(
+            metrics.iter().any(|m| m["name"] == "octoroute_build_info"),
+            "Every registered family should be exported"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_csv_format_has_header_and_rows() {
+        let (status, content_type, body) = scrape_as(export_test_state(), Some("text/csv")).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert!(content_type.starts_with("text/csv"));
+
+        let mut lines = body.lines();
+        assert_eq!(lines.next(), Some("name,labels,value"));
+        assert!(
+            lines.any(|l| l == "octoroute_requests_total,strategy=rule;tier=deep,3"),
+            "CSV should contain the requests counter row, got:\n{}",
+            body
+        );
+    }
+
+    #[test]
+    fn test_csv_field_quotes_delimiters() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
 }
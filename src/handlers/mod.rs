@@ -2,17 +2,84 @@
 
 use crate::config::{Config, RoutingStrategy};
 use crate::error::{AppError, AppResult};
-use crate::models::ModelSelector;
-use crate::router::{HybridRouter, LlmBasedRouter, Router, RuleBasedRouter};
+use crate::handlers::openai::completions::{
+    IDEMPOTENCY_CACHE_CAPACITY, IDEMPOTENCY_TTL, IdempotencyCache,
+};
+use crate::handlers::openai::{STICKY_SESSION_CAPACITY, SessionTierCache};
+use crate::models::{InFlightTracker, ModelSelector};
+use crate::router::{
+    HeuristicRouter, HybridRouter, KeywordTaskClassifier, LlmBasedRouter, Router, RoutingObserver,
+    RuleBasedRouter, TargetModel, TaskClassifier,
+};
+use crate::shared::http_client::build_pooled_client;
+use crate::shared::system_prompt::SystemPrompt;
+use crate::shared::tier_budget::TierBudgets;
+use crate::shared::user_tracker::UserRequestTracker;
+use axum::http::{HeaderName, HeaderValue};
+use axum::response::Response;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 
 type MetricsHandle = Arc<crate::metrics::Metrics>;
 
+/// Pause between startup probe rounds while waiting for required tiers
+const STARTUP_PROBE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Response header naming the routing path (`rule` or `llm`) that picked the tier
+///
+/// Set on `/chat` responses and on auto-routed `/v1/chat/completions`
+/// responses. Under the hybrid strategy it tells a rule fast-path match from
+/// an escalation to the LLM router.
+pub const X_OCTOROUTE_ROUTING_PATH: &str = "x-octoroute-routing-path";
+
+/// Response header naming the endpoint that served the request
+pub const X_OCTOROUTE_ENDPOINT: &str = "x-octoroute-endpoint";
+
+/// Response header naming the endpoint that answered the LLM router query
+pub const X_OCTOROUTE_ROUTER_ENDPOINT: &str = "x-octoroute-router-endpoint";
+
+/// Add the [`X_OCTOROUTE_ENDPOINT`] and [`X_OCTOROUTE_ROUTER_ENDPOINT`] headers
+///
+/// Does nothing unless `observability.endpoint_headers` is on. A name that
+/// isn't a valid header value is left out rather than failing the response.
+pub(crate) fn set_endpoint_headers(
+    response: &mut Response,
+    observability: &crate::config::ObservabilityConfig,
+    endpoint: &str,
+    router_endpoint: Option<&str>,
+) {
+    if !observability.endpoint_headers {
+        return;
+    }
+    for (header, name) in [
+        (X_OCTOROUTE_ENDPOINT, Some(endpoint)),
+        (X_OCTOROUTE_ROUTER_ENDPOINT, router_endpoint),
+    ] {
+        if let Some(value) = name.and_then(|name| HeaderValue::from_str(name).ok()) {
+            response
+                .headers_mut()
+                .insert(HeaderName::from_static(header), value);
+        }
+    }
+}
+
+/// Add the [`X_OCTOROUTE_ROUTING_PATH`] header for `strategy` to `response`
+pub(crate) fn set_routing_path(response: &mut Response, strategy: crate::router::RoutingStrategy) {
+    response.headers_mut().insert(
+        HeaderName::from_static(X_OCTOROUTE_ROUTING_PATH),
+        HeaderValue::from_static(strategy.as_str()),
+    );
+}
+
+pub mod admin;
 pub mod chat;
 pub mod health;
 pub mod metrics;
 pub mod models;
 pub mod openai;
+pub mod probes;
+pub mod version;
 
 /// Application state shared across all handlers
 ///
@@ -29,14 +96,35 @@ pub mod openai;
 /// - `Rule`: Only rule-based routing (no balanced tier required)
 /// - `Llm`: Only LLM-based routing (balanced tier required)
 /// - `Hybrid`: Rule-based with LLM fallback (balanced tier required)
+/// - `Heuristic`: Local classifier, no LLM routing (no router tier required)
 ///
-/// Also contains a Prometheus metrics collector for observability.
+/// Also contains a Prometheus metrics collector for observability, a bounded
+/// TTL cache of completed responses for `Idempotency-Key` replay, and (when
+/// `routing.sticky_session_ttl_seconds` is set) the session-to-tier map used
+/// for sticky routing, and (when `routing.system_prompt` or
+/// `routing.system_prompt_file` is set) the resolved house system prompt.
+/// The pooled upstream HTTP client lives here too, so every request shares
+/// one set of keep-alive connections, as do the per-tier concurrency budgets
+/// from `[server.tier_concurrency]`. An optional [`RoutingObserver`], attached
+/// with [`AppState::with_routing_observer`], sees every tier decision. The
+/// [`TaskClassifier`] used by `routing.auto_classify_task` defaults to
+/// [`KeywordTaskClassifier`] and can be replaced with
+/// [`AppState::with_task_classifier`].
 #[derive(Clone)]
 pub struct AppState {
     config: Arc<Config>,
     selector: Arc<ModelSelector>,
     router: Arc<Router>,
     metrics: Arc<crate::metrics::Metrics>,
+    idempotency_cache: Arc<IdempotencyCache>,
+    sticky_sessions: Option<Arc<SessionTierCache>>,
+    system_prompt: Option<Arc<SystemPrompt>>,
+    http_client: reqwest::Client,
+    tier_budgets: Arc<TierBudgets>,
+    user_tracker: Option<Arc<UserRequestTracker>>,
+    draining: Arc<AtomicBool>,
+    routing_observer: Option<Arc<dyn RoutingObserver>>,
+    task_classifier: Arc<dyn TaskClassifier>,
 }
 
 impl AppState {
@@ -49,6 +137,8 @@ impl AppState {
     /// Returns an error if:
     /// - Llm/Hybrid strategy is selected but no balanced tier endpoints are configured
     /// - Router construction fails for any other reason
+    /// - `routing.system_prompt_file` cannot be read or is empty
+    /// - The upstream HTTP client cannot be built
     pub fn new(config: Arc<Config>) -> AppResult<Self> {
         // Initialize metrics first so we can pass them to health checker and routers
         let metrics = {
@@ -58,61 +148,162 @@ impl AppState {
             Arc::new(m)
         };
 
+        Self::with_metrics(config, metrics, None)
+    }
+
+    /// Build the state for a reloaded configuration
+    ///
+    /// Everything derived from the config is rebuilt, including a fresh model
+    /// selector with its own background health checks. Metrics, the server
+    /// drain flag, the idempotency cache, the routing observer, and the task
+    /// classifier carry over from `self`, and so do the in-flight counts of
+    /// endpoints kept by name. The tier budgets, sticky sessions and per-user
+    /// counters carry over when their config section is unchanged, so a reload
+    /// neither frees tier slots that are in use nor forgets sessions and
+    /// request counts; a changed section starts them afresh. The caller shuts
+    /// down `self`'s health checks once the new state is in use.
+    ///
+    /// # Errors
+    /// Same as [`AppState::new`].
+    pub fn reconfigured(&self, config: Arc<Config>) -> AppResult<Self> {
+        let mut state = Self::with_metrics(
+            config,
+            self.metrics.clone(),
+            Some(self.selector.in_flight()),
+        )?;
+        state.idempotency_cache = Arc::clone(&self.idempotency_cache);
+        state.draining = Arc::clone(&self.draining);
+        state.routing_observer = self.routing_observer.clone();
+        state.task_classifier = Arc::clone(&self.task_classifier);
+
+        let (old, new) = (&self.config, &state.config);
+        if old.server.tier_concurrency == new.server.tier_concurrency
+            && old.server.max_queue_wait() == new.server.max_queue_wait()
+        {
+            state.tier_budgets = Arc::clone(&self.tier_budgets);
+        }
+        if old.routing.sticky_session_ttl_seconds == new.routing.sticky_session_ttl_seconds {
+            state.sticky_sessions = self.sticky_sessions.clone();
+        }
+        if old.server.user_tracking == new.server.user_tracking {
+            state.user_tracker = self.user_tracker.clone();
+        }
+        Ok(state)
+    }
+
+    /// Build the state for switching to routing `strategy` at runtime
+    ///
+    /// Only the router is rebuilt (against a copy of the config with the new
+    /// strategy); the selector, its health state and everything else are
+    /// shared with `self`.
+    ///
+    /// # Errors
+    /// Returns a configuration error if `strategy` needs a router tier with no
+    /// endpoints, or is not implemented.
+    pub fn with_routing_strategy(&self, strategy: RoutingStrategy) -> AppResult<Self> {
+        let mut config = (*self.config).clone();
+        config.routing.strategy = strategy;
+        let config = Arc::new(config);
+        let router = build_router(&config, &self.selector, &self.metrics)?;
+        Ok(Self {
+            config,
+            router,
+            ..self.clone()
+        })
+    }
+
+    /// Build the state for `config`; in-flight counts continue from
+    /// `previous_in_flight` when given (see [`InFlightTracker::carried_over`])
+    fn with_metrics(
+        config: Arc<Config>,
+        metrics: MetricsHandle,
+        previous_in_flight: Option<&InFlightTracker>,
+    ) -> AppResult<Self> {
+        let http_client = build_pooled_client(
+            &config.server.http_pool,
+            Duration::from_millis(config.health.probe_connect_timeout_ms),
+        )?;
+        tracing::info!(
+            max_idle_per_host = config.server.http_pool.max_idle_per_host,
+            idle_timeout_seconds = config.server.http_pool.idle_timeout_seconds,
+            "Upstream HTTP connection pool configured"
+        );
+
         // Create selector with metrics integration for health tracking
-        let selector = Arc::new(ModelSelector::new(config.clone(), metrics.clone()));
-
-        // Construct router based on config.routing.strategy
-        let router = match config.routing.strategy {
-            RoutingStrategy::Rule => {
-                // Rule-only routing: no balanced tier required
-                tracing::info!("Initializing rule-based router (no LLM routing)");
-                Arc::new(Router::Rule(RuleBasedRouter::new()))
-            }
-            RoutingStrategy::Llm => {
-                // LLM-only routing: router tier required
-                // Serde validates router_tier format at deserialization time
-                let router_tier = config.routing.router_tier();
-                let router_timeout_secs = config.routing.router_timeout_for_tier(router_tier);
+        let mut selector =
+            ModelSelector::with_http_client(config.clone(), metrics.clone(), http_client.clone());
+        if let Some(previous) = previous_in_flight {
+            selector = selector.with_in_flight(InFlightTracker::carried_over(&config, previous));
+        }
+        let selector = Arc::new(selector);
+        config.models.log_traffic_shares();
 
-                tracing::info!(
-                    "Initializing LLM-based router with {:?} tier for routing decisions (timeout: {}s)",
-                    router_tier,
-                    router_timeout_secs
-                );
+        let router = build_router(&config, &selector, &metrics)?;
 
-                let llm_router = LlmBasedRouter::new(
-                    selector.clone(),
-                    router_tier,
-                    router_timeout_secs,
-                    metrics.clone(),
-                )?;
-                Arc::new(Router::Llm(llm_router))
-            }
-            RoutingStrategy::Hybrid => {
-                // Hybrid routing: router tier required for LLM fallback
-                // Serde validates router_tier format at deserialization time
-                tracing::info!(
-                    "Initializing hybrid router (rule-based with LLM fallback using {:?} tier)",
-                    config.routing.router_tier()
-                );
+        let idempotency_cache = Arc::new(IdempotencyCache::new(
+            IDEMPOTENCY_CACHE_CAPACITY,
+            IDEMPOTENCY_TTL,
+        ));
 
-                let hybrid_router =
-                    HybridRouter::new(config.clone(), selector.clone(), metrics.clone())?;
-                Arc::new(Router::Hybrid(hybrid_router))
-            }
-            RoutingStrategy::Tool => {
-                return Err(AppError::Config(
-                    "Tool-based routing is not yet implemented. Use 'rule', 'llm', or 'hybrid'."
-                        .to_string(),
-                ));
+        let sticky_sessions = config.routing.sticky_session_ttl_seconds.map(|ttl| {
+            tracing::info!(
+                "Sticky session routing enabled (ttl: {}s, capacity: {})",
+                ttl,
+                STICKY_SESSION_CAPACITY
+            );
+            Arc::new(SessionTierCache::new(
+                STICKY_SESSION_CAPACITY,
+                std::time::Duration::from_secs(ttl),
+            ))
+        });
+
+        let system_prompt = SystemPrompt::from_config(&config.routing)?.map(|prompt| {
+            tracing::info!(
+                mode = ?prompt.mode(),
+                length = prompt.text().len(),
+                "House system prompt enabled"
+            );
+            Arc::new(prompt)
+        });
+
+        let tier_budgets = Arc::new(
+            TierBudgets::new(&config.server.tier_concurrency)
+                .with_max_queue_wait(config.server.max_queue_wait()),
+        );
+        for (tier, limit) in [
+            ("fast", config.server.tier_concurrency.fast),
+            ("balanced", config.server.tier_concurrency.balanced),
+            ("deep", config.server.tier_concurrency.deep),
+        ] {
+            if let Some(limit) = limit {
+                tracing::info!(tier, limit, "Tier concurrency budget enabled");
             }
-        };
+        }
+
+        let user_tracker = config.server.user_tracking.clone().map(|tracking| {
+            tracing::info!(
+                max_requests = tracking.max_requests,
+                window_seconds = tracking.window_seconds,
+                reject_over_limit = tracking.reject_over_limit,
+                "Per-user request tracking enabled"
+            );
+            Arc::new(UserRequestTracker::new(tracking))
+        });
 
         Ok(Self {
             config,
             selector,
             router,
             metrics,
+            idempotency_cache,
+            sticky_sessions,
+            system_prompt,
+            http_client,
+            tier_budgets,
+            user_tracker,
+            draining: Arc::new(AtomicBool::new(false)),
+            routing_observer: None,
+            task_classifier: Arc::new(KeywordTaskClassifier),
         })
     }
 
@@ -142,6 +333,215 @@ impl AppState {
     pub fn metrics(&self) -> MetricsHandle {
         self.metrics.clone()
     }
+
+    /// Get reference to the idempotency response cache
+    pub fn idempotency_cache(&self) -> &IdempotencyCache {
+        &self.idempotency_cache
+    }
+
+    /// Get the sticky session map, or `None` if sticky routing is disabled
+    pub fn sticky_sessions(&self) -> Option<&SessionTierCache> {
+        self.sticky_sessions.as_deref()
+    }
+
+    /// Get the house system prompt, or `None` if client messages are sent unchanged
+    pub fn system_prompt(&self) -> Option<&SystemPrompt> {
+        self.system_prompt.as_deref()
+    }
+
+    /// Get the pooled upstream HTTP client
+    ///
+    /// Cloning is cheap and every clone shares the same connection pool.
+    pub fn http_client(&self) -> &reqwest::Client {
+        &self.http_client
+    }
+
+    /// Get the per-tier concurrency budgets
+    ///
+    /// Tiers without a `[server.tier_concurrency]` limit always grant a permit.
+    pub fn tier_budgets(&self) -> &TierBudgets {
+        &self.tier_budgets
+    }
+
+    /// Get the per-user request counters, or `None` if user tracking is disabled
+    pub fn user_tracker(&self) -> Option<&UserRequestTracker> {
+        self.user_tracker.as_deref()
+    }
+
+    /// Notify `observer` of every routing decision (builder pattern)
+    ///
+    /// Replaces any observer attached earlier.
+    pub fn with_routing_observer(mut self, observer: Arc<dyn RoutingObserver>) -> Self {
+        self.routing_observer = Some(observer);
+        self
+    }
+
+    /// Get the routing observer, or `None` if decisions are not observed
+    pub fn routing_observer(&self) -> Option<&dyn RoutingObserver> {
+        self.routing_observer.as_deref()
+    }
+
+    /// Infer missing task types with `classifier` (builder pattern)
+    ///
+    /// Only consulted when `routing.auto_classify_task` is enabled.
+    pub fn with_task_classifier(mut self, classifier: Arc<dyn TaskClassifier>) -> Self {
+        self.task_classifier = classifier;
+        self
+    }
+
+    /// Get the task classifier, or `None` if `routing.auto_classify_task` is off
+    pub fn task_classifier(&self) -> Option<&dyn TaskClassifier> {
+        self.config
+            .routing
+            .auto_classify_task
+            .then_some(self.task_classifier.as_ref())
+    }
+
+    /// Whether the server is draining (`POST /admin/drain`)
+    ///
+    /// A draining server reports not-ready on `/readyz` but keeps serving
+    /// every request it receives.
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::Relaxed)
+    }
+
+    /// Enter or leave the draining state, returning the previous state
+    pub fn set_draining(&self, draining: bool) -> bool {
+        self.draining.swap(draining, Ordering::Relaxed)
+    }
+
+    /// Probe endpoints until every required tier has a reachable one
+    ///
+    /// Backs `health.require_healthy_at_startup`. Required tiers are the ones
+    /// `/readyz` checks. Probe rounds repeat every [`STARTUP_PROBE_INTERVAL`]
+    /// until `health.startup_grace_period_seconds` runs out, and each round is
+    /// recorded like a warmup probe.
+    ///
+    /// # Errors
+    /// Returns a configuration error naming the tiers that still had no
+    /// reachable endpoint when the grace period ended.
+    pub async fn require_healthy_tiers(&self) -> AppResult<()> {
+        let grace_period = self.config.health.startup_grace_period_seconds;
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(grace_period);
+        let required = probes::required_tiers(&self.config);
+
+        loop {
+            let report = self.selector.health_checker().warmup().await;
+            let unavailable: Vec<TargetModel> =
+                required
+                    .iter()
+                    .copied()
+                    .filter(|&tier| {
+                        !self.config.models.tier(tier).iter().any(|endpoint| {
+                            report.reachable.iter().any(|name| name == endpoint.name())
+                        })
+                    })
+                    .collect();
+
+            if unavailable.is_empty() {
+                tracing::info!(
+                    reachable = report.reachable.len(),
+                    unreachable = ?report.unreachable,
+                    "Startup health check passed: every required tier has a reachable endpoint"
+                );
+                return Ok(());
+            }
+
+            let now = tokio::time::Instant::now();
+            if now >= deadline {
+                return Err(AppError::Config(format!(
+                    "Startup health check failed: no reachable endpoint in required tier(s) {:?} \
+                     after {}s (unreachable endpoints: {}). Check each endpoint's base_url, or set \
+                     health.require_healthy_at_startup = false to start anyway.",
+                    unavailable,
+                    grace_period,
+                    report.unreachable.join(", ")
+                )));
+            }
+
+            tracing::warn!(
+                unavailable_tiers = ?unavailable,
+                unreachable = ?report.unreachable,
+                "Required tiers unreachable at startup, probing again"
+            );
+            tokio::time::sleep(STARTUP_PROBE_INTERVAL.min(deadline - now)).await;
+        }
+    }
+}
+
+/// Construct the router for `config.routing.strategy`
+///
+/// # Errors
+/// Returns a configuration error if the strategy needs a router tier with no
+/// endpoints, or is not implemented (`tool`).
+fn build_router(
+    config: &Arc<Config>,
+    selector: &Arc<ModelSelector>,
+    metrics: &MetricsHandle,
+) -> AppResult<Arc<Router>> {
+    Ok(match config.routing.strategy {
+        RoutingStrategy::Rule => {
+            // Rule-only routing: no balanced tier required
+            tracing::info!("Initializing rule-based router (no LLM routing)");
+            Arc::new(Router::Rule(
+                RuleBasedRouter::new().with_task_affinity(config.routing.task_affinity.clone()),
+            ))
+        }
+        RoutingStrategy::Llm => {
+            // LLM-only routing: router tier required
+            // Serde validates router_tier format at deserialization time
+            let router_tier = config.routing.router_tier();
+            let router_timeout_secs = config.routing.router_timeout_for_tier(router_tier);
+
+            tracing::info!(
+                "Initializing LLM-based router with {:?} tier for routing decisions (timeout: {}s)",
+                router_tier,
+                router_timeout_secs
+            );
+
+            let llm_router = LlmBasedRouter::new(
+                selector.clone(),
+                router_tier,
+                router_timeout_secs,
+                metrics.clone(),
+            )?
+            .with_retry_backoff_ms(config.routing.retry_backoff_ms)
+            .with_retry_policy(config.routing.retry_policy)
+            .with_same_endpoint_retries(config.routing.router_same_endpoint_retries)
+            .with_failure_fallback(config.routing.llm_failure_fallback)
+            .with_on_unparseable(config.routing.on_unparseable)
+            .with_guard_suffix(config.routing.router_guard_suffix.clone())
+            .with_prompt_delimiters(config.routing.router_prompt_delimiters)
+            .with_task_affinity(config.routing.task_affinity.clone())
+            .with_tier_keywords(config.routing.tier_keywords.clone())
+            .with_importance_guidance(config.routing.importance_guidance.clone())
+            .with_router_endpoint(config.routing.router_endpoint.clone())
+            .with_cache_config(config.routing.cache.as_ref())?;
+            Arc::new(Router::Llm(Box::new(llm_router)))
+        }
+        RoutingStrategy::Hybrid => {
+            // Hybrid routing: router tier required for LLM fallback
+            // Serde validates router_tier format at deserialization time
+            tracing::info!(
+                "Initializing hybrid router (rule-based with LLM fallback using {:?} tier)",
+                config.routing.router_tier()
+            );
+
+            let hybrid_router =
+                HybridRouter::new(config.clone(), selector.clone(), metrics.clone())?;
+            Arc::new(Router::Hybrid(hybrid_router))
+        }
+        RoutingStrategy::Heuristic => {
+            tracing::info!("Initializing heuristic router (local classifier, no LLM routing)");
+            Arc::new(Router::Heuristic(HeuristicRouter::new()))
+        }
+        RoutingStrategy::Tool => {
+            return Err(AppError::Config(
+                "Tool-based routing is not yet implemented. Use 'rule', 'llm', 'hybrid', or 'heuristic'."
+                    .to_string(),
+            ));
+        }
+    })
 }
 
 #[cfg(test)]
@@ -223,4 +623,49 @@ router_tier = "balanced"
         let _ = state.selector();
         let _ = state.router();
     }
+
+    #[tokio::test]
+    async fn test_reconfigured_keeps_tier_budget_when_section_unchanged() {
+        let mut config = create_test_config();
+        config.server.tier_concurrency.deep = Some(1);
+        let state = AppState::new(Arc::new(config.clone())).expect("AppState::new should succeed");
+        let _permit = state
+            .tier_budgets()
+            .try_acquire(TargetModel::Deep)
+            .expect("first deep permit should be granted");
+
+        // An unrelated change keeps the budget, so the held slot stays taken
+        let mut unrelated = config.clone();
+        unrelated.server.request_timeout_seconds = 60;
+        let reloaded = state
+            .reconfigured(Arc::new(unrelated))
+            .expect("reconfigured should succeed");
+        assert_eq!(
+            reloaded.tier_budgets().available(TargetModel::Deep),
+            Some(0)
+        );
+
+        // A changed budget starts afresh
+        let mut resized = config;
+        resized.server.tier_concurrency.deep = Some(2);
+        let resized = state
+            .reconfigured(Arc::new(resized))
+            .expect("reconfigured should succeed");
+        assert_eq!(resized.tier_budgets().available(TargetModel::Deep), Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_reconfigured_keeps_in_flight_counts() {
+        let config = Arc::new(create_test_config());
+        let state = AppState::new(config.clone()).expect("AppState::new should succeed");
+        let guard = state.selector().in_flight().acquire("fast-1");
+
+        let reloaded = state
+            .reconfigured(config)
+            .expect("reconfigured should succeed");
+        assert_eq!(reloaded.selector().in_flight().count("fast-1"), 1);
+
+        drop(guard);
+        assert_eq!(reloaded.selector().in_flight().count("fast-1"), 0);
+    }
 }
@@ -2,24 +2,38 @@
 //!
 //! Handles POST /v1/chat/completions requests (both streaming and non-streaming).
 
+use crate::config::ModelEndpoint;
 use crate::error::AppError;
-use crate::handlers::AppState;
+use crate::handlers::{AppState, set_endpoint_headers, set_routing_path};
 use crate::middleware::RequestId;
+use crate::models::ExclusionSet;
+use crate::router::RouteMetadata;
+use crate::shared::call_budget::CallBudget;
+use crate::shared::context_window;
+use crate::shared::conversation_limits;
+use crate::shared::fan_out;
+use crate::shared::prompt_cache_key::fnv1a_64;
+use crate::shared::prompt_log::log_routed_prompt;
 use crate::shared::query::{
-    QueryConfig, SamplingParams, execute_query_with_retry, query_model, record_routing_metrics,
+    QueryConfig, SamplingParams, execute_query_with_retry, notify_routing_observer,
+    record_routing_metrics, resolve_max_tokens, run_completion, task_type_tags,
 };
+use crate::shared::ttl_cache::TtlCache;
 use axum::{
     Extension, Json,
     extract::State,
-    http::{HeaderName, HeaderValue},
+    http::{HeaderMap, HeaderName, HeaderValue},
     response::{IntoResponse, Response},
 };
+use futures::stream::{self, StreamExt, TryStreamExt};
+use std::time::Duration;
 
 use super::extractor::OpenAiJson;
-use super::find_endpoint_by_name;
 use super::types::{
-    ChatCompletion, ChatCompletionRequest, ModelChoice, TimestampResult, current_timestamp,
+    ChatCompletion, ChatCompletionRequest, ModelChoice, RoutingDebug, TimestampResult,
+    current_timestamp,
 };
+use super::{route_auto, session_key, track_user};
 
 /// Custom header for surfacing non-fatal warnings to OpenAI API clients.
 ///
@@ -30,6 +44,174 @@ use super::types::{
 /// Semicolons are used instead of commas since warning messages may contain commas.
 pub const X_OCTOROUTE_WARNING: &str = "x-octoroute-warning";
 
+/// Request header carrying a client-chosen idempotency key.
+///
+/// When present on a non-streaming request, the first successful response for the
+/// key is cached and replayed for subsequent identical requests with the same key
+/// (and `user`), so client retries do not re-query the backend.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Request header asking for the `octoroute_debug` response field
+///
+/// Only honored when `observability.router_debug` is enabled.
+pub const X_OCTOROUTE_DEBUG: &str = "x-octoroute-debug";
+
+/// How long a completed response stays available for idempotent replay
+pub const IDEMPOTENCY_TTL: Duration = Duration::from_secs(600);
+
+/// Maximum number of idempotency keys retained at once
+pub const IDEMPOTENCY_CACHE_CAPACITY: usize = 1024;
+
+/// Maximum accepted length of an `Idempotency-Key` header value
+const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
+
+/// A successful non-streaming response retained for idempotent replay
+#[derive(Debug, Clone)]
+pub struct CachedCompletion {
+    /// Hash of the request that produced the response (see [`request_hash`])
+    request_hash: u64,
+    completion: ChatCompletion,
+    warnings: Vec<String>,
+}
+
+/// Idempotency cache key: the `Idempotency-Key` header value, scoped to `user`
+///
+/// Two users who happen to pick the same key never see each other's responses.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IdempotencyKey {
+    header: String,
+    user: Option<String>,
+}
+
+/// Cache of completed responses keyed by `Idempotency-Key` header value and `user`
+pub type IdempotencyCache = TtlCache<IdempotencyKey, CachedCompletion>;
+
+/// A request carrying an `Idempotency-Key`, with the hash its replay must match
+struct IdempotentRequest {
+    key: IdempotencyKey,
+    request_hash: u64,
+}
+
+impl IdempotentRequest {
+    fn new(header: String, request: &ChatCompletionRequest) -> Result<Self, AppError> {
+        Ok(Self {
+            key: IdempotencyKey {
+                header,
+                user: request.user().map(str::to_string),
+            },
+            request_hash: request_hash(request)?,
+        })
+    }
+}
+
+/// Whether the operator allows debug output and the client sent `x-octoroute-debug: true`
+fn debug_requested(state: &AppState, headers: &HeaderMap) -> bool {
+    state.config().observability.router_debug
+        && headers
+            .get(X_OCTOROUTE_DEBUG)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.trim().eq_ignore_ascii_case("true"))
+}
+
+/// Hash of the request body as deserialized, so formatting differences don't count
+fn request_hash(request: &ChatCompletionRequest) -> Result<u64, AppError> {
+    serde_json::to_string(request)
+        .map(|body| fnv1a_64(&body))
+        .map_err(|e| AppError::Internal(format!("Failed to serialize request: {}", e)))
+}
+
+/// Extract and validate the `Idempotency-Key` header, if present
+///
+/// # Errors
+/// Returns `AppError::Validation` if the header is empty, longer than
+/// 255 characters, or not visible ASCII.
+fn idempotency_key(headers: &HeaderMap) -> Result<Option<String>, AppError> {
+    let Some(value) = headers.get(IDEMPOTENCY_KEY_HEADER) else {
+        return Ok(None);
+    };
+
+    let key = value
+        .to_str()
+        .map_err(|_| AppError::Validation("Idempotency-Key must be visible ASCII".to_string()))?
+        .trim();
+
+    if key.is_empty() {
+        return Err(AppError::Validation(
+            "Idempotency-Key cannot be empty".to_string(),
+        ));
+    }
+    if key.len() > MAX_IDEMPOTENCY_KEY_LEN {
+        return Err(AppError::Validation(format!(
+            "Idempotency-Key exceeds maximum length of {} characters",
+            MAX_IDEMPOTENCY_KEY_LEN
+        )));
+    }
+
+    Ok(Some(key.to_string()))
+}
+
+/// Attach warnings to the body, cache the completion under the idempotency key (if any),
+/// and build the response
+fn finish_completion(
+    state: &AppState,
+    idempotency_key: Option<IdempotentRequest>,
+    completion: ChatCompletion,
+    warnings: Vec<String>,
+) -> Response {
+    let completion = completion.with_warnings(warnings.clone());
+    let response = build_response_with_warnings(&completion, &warnings);
+    if let Some(IdempotentRequest { key, request_hash }) = idempotency_key {
+        state.idempotency_cache().insert(
+            key,
+            CachedCompletion {
+                request_hash,
+                completion,
+                warnings,
+            },
+        );
+    }
+    response
+}
+
+/// Price each choice at the `cost_per_1k_tokens` of the endpoint that served it
+/// and add the costs to the cost metric
+///
+/// `served` holds the endpoint and tier behind each of `completion.choices`, in
+/// order. Every choice was its own upstream call, so each is charged for the
+/// prompt plus its own answer. Returns the completion with the summed
+/// `octoroute_cost` extension set, or unchanged if no serving endpoint has a rate.
+fn attach_cost(
+    state: &AppState,
+    served: &[(&ModelEndpoint, crate::router::TargetModel)],
+    completion: ChatCompletion,
+    request_id: RequestId,
+) -> ChatCompletion {
+    let mut total_cost = None;
+    for (choice, (endpoint, tier)) in completion.choices.iter().zip(served) {
+        // Same ~4 chars/token estimate as `usage`
+        let total_tokens = completion.usage.prompt_tokens()
+            + (choice.message.content().chars().count() / 4) as u32;
+        let Some(cost) = endpoint.estimated_cost(total_tokens) else {
+            continue;
+        };
+        state.metrics().record_request_cost((*tier).into(), cost);
+        tracing::debug!(
+            request_id = %request_id,
+            endpoint_name = %endpoint.name(),
+            tier = ?tier,
+            choice = choice.index,
+            total_tokens = total_tokens,
+            cost = cost,
+            "Estimated choice cost"
+        );
+        *total_cost.get_or_insert(0.0) += cost;
+    }
+    match total_cost {
+        Some(cost) => completion.with_cost(Some(cost)),
+        None => completion,
+    }
+}
+
 /// Build a JSON response with optional warning header.
 ///
 /// If warnings are present, adds an `X-Octoroute-Warning` header with a
@@ -140,38 +322,131 @@ fn build_response_with_warnings<T: serde::Serialize>(body: T, warnings: &[String
 /// - Content: text deltas (`delta.content: "..."`)
 /// - Finish: completion signal (`finish_reason: "stop"`)
 /// - Done: `data: [DONE]`
+///
+/// # Idempotency
+///
+/// Non-streaming requests may carry an `Idempotency-Key` header. The first
+/// successful response for a key (per `user`, when set) is cached for
+/// [`IDEMPOTENCY_TTL`]; later requests with the same key and body receive the
+/// cached response without querying a backend, and a different body reusing
+/// the key is rejected with 422. Failed requests are not cached, so retrying
+/// after an error re-executes the query. Streaming requests ignore the header.
+///
+/// # Debug Mode
+///
+/// With `observability.router_debug` enabled, a tier-routed non-streaming
+/// request sent with `x-octoroute-debug: true` gets an `octoroute_debug` field
+/// holding the routing decision and the router model's raw answer.
+///
+/// # Multiple Choices
+///
+/// A non-streaming request with `n > 1` makes one upstream call per choice.
+/// At most `server.max_parallel_upstream_per_request` of them run at once;
+/// with `server.fan_out_overflow = "reject"` a wider request fails with 400
+/// before routing instead.
+///
+/// # Routing Path
+///
+/// `model: "auto"` responses carry an `x-octoroute-routing-path` header
+/// (`rule` or `llm`) naming the routing path that chose the tier. Requests
+/// for an explicit tier or model, and idempotent replays, don't route and
+/// get no header.
 pub async fn handler(
     State(state): State<AppState>,
     Extension(request_id): Extension<RequestId>,
+    headers: HeaderMap,
     OpenAiJson(request): OpenAiJson<ChatCompletionRequest>,
 ) -> Result<Response, AppError> {
     tracing::debug!(
         request_id = %request_id,
+        user = request.user().unwrap_or_default(),
         model = ?request.model(),
         messages_count = request.messages().len(),
         stream = request.stream(),
         "Received chat completions request"
     );
 
+    // Oversized conversations are turned away before they count against anything
+    conversation_limits::check(
+        &state.config().server,
+        request.messages().len(),
+        request.total_content_length(),
+    )?;
+
+    // Each requested choice is its own upstream call; cap how many run at once
+    let choices = request.n().unwrap_or(1) as usize;
+    let fan_out_width = fan_out::parallel_width(&state.config().server, choices)?;
+
+    // Count the request against its user before any routing work
+    track_user(&state, &request, request_id)?;
+
     // Dispatch to streaming handler if requested
     if request.stream() {
-        return super::streaming::handler(State(state), Extension(request_id), Json(request)).await;
+        return super::streaming::handler(
+            State(state),
+            Extension(request_id),
+            headers,
+            Json(request),
+        )
+        .await;
     }
 
-    // Convert messages to a single prompt for routing and query
+    // Replay a previously completed response for the same idempotency key
+    let idempotency_key = idempotency_key(&headers)?
+        .map(|header| IdempotentRequest::new(header, &request))
+        .transpose()?;
+    if let Some(idempotent) = &idempotency_key
+        && let Some(cached) = state.idempotency_cache().get(&idempotent.key)
+    {
+        if cached.request_hash != idempotent.request_hash {
+            return Err(AppError::IdempotencyKeyReused {
+                key: idempotent.key.header.clone(),
+            });
+        }
+        tracing::info!(
+            request_id = %request_id,
+            idempotency_key = %idempotent.key.header,
+            model = %cached.completion.model,
+            "Replaying cached response for idempotency key"
+        );
+        return Ok(build_response_with_warnings(
+            &cached.completion,
+            &cached.warnings,
+        ));
+    }
+
+    // Convert messages to a single prompt for routing; the query prompt also carries
+    // the house system prompt, which is applied after routing so it can't sway the tier
     let prompt = request.to_prompt_string();
-    let prompt_chars = prompt.chars().count();
+    let backend_prompt = state
+        .system_prompt()
+        .map(|system_prompt| system_prompt.backend_prompt(request.messages()));
+    let query_prompt = backend_prompt.as_deref().unwrap_or(&prompt);
+    let prompt_chars = query_prompt.chars().count();
+    let token_estimate = RouteMetadata::estimate_tokens(query_prompt);
 
     // Extract sampling parameters from request (overrides endpoint defaults)
     let sampling_params = SamplingParams {
         temperature: request.temperature(),
         max_tokens: request.max_tokens(),
+        top_p: request.top_p(),
+        presence_penalty: request.presence_penalty(),
+        frequency_penalty: request.frequency_penalty(),
+        logit_bias: request.logit_bias().cloned(),
+        logprobs: request.logprobs(),
+        top_logprobs: request.top_logprobs(),
     };
 
     // Handle specific model requests differently - query the exact endpoint requested
     if let ModelChoice::Specific(name) = request.model() {
-        // Find and use the specific endpoint (no tier selection)
-        let (tier, endpoint) = find_endpoint_by_name(state.config(), name)?;
+        // Use the specific endpoint if it is healthy (no tier selection)
+        let (endpoint, tier) = state
+            .selector()
+            .select_named(name, &ExclusionSet::new())
+            .await?;
+        let endpoint = endpoint.clone();
+        context_window::check_endpoint(&endpoint, token_estimate)?;
+        log_routed_prompt(&state.config().observability, request_id, &prompt, tier);
 
         tracing::info!(
             request_id = %request_id,
@@ -188,83 +463,50 @@ pub async fn handler(
         record_routing_metrics(&state, &decision, 0.0, request_id);
 
         // Query the specific endpoint directly (no retry to different endpoints)
-        let timeout_seconds = state.config().timeout_for_tier(tier);
-        let content = match query_model(
-            &endpoint,
-            &prompt,
-            timeout_seconds,
-            request_id,
-            1,
-            1,
-            Some(&sampling_params),
-        )
-        .await
-        {
-            Ok(content) => content,
-            Err(e) => {
-                // Mark endpoint as failed for health tracking (parity with tier-based routing)
-                if let Err(health_err) = state
-                    .selector()
-                    .health_checker()
-                    .mark_failure(endpoint.name())
-                    .await
-                {
-                    tracing::warn!(
-                        request_id = %request_id,
-                        endpoint_name = %endpoint.name(),
-                        query_error = %e,
-                        health_error = %health_err,
-                        "Health tracking failed while marking endpoint failure"
-                    );
-                    state
-                        .metrics()
-                        .health_tracking_failure(endpoint.name(), health_err.error_type());
-                }
-                return Err(e);
-            }
-        };
+        // Nowhere else to go for a named endpoint, so "retry" empty completions fail like "error"
+        let (state_ref, endpoint_ref, sampling_ref) = (&state, &endpoint, &sampling_params);
+        let answers = stream::iter(0..choices)
+            .map(move |_| async move {
+                // Each concurrent call holds its own slot; a full tier sheds with 503
+                let _tier_permit = state_ref.tier_budgets().acquire(tier).await?;
+                let mut health_warnings = Vec::new();
+                let answer = run_completion(
+                    state_ref,
+                    endpoint_ref,
+                    tier,
+                    query_prompt,
+                    request_id,
+                    1,
+                    1,
+                    Some(sampling_ref),
+                    &mut health_warnings,
+                )
+                .await?;
+                Ok::<_, AppError>((answer, health_warnings))
+            })
+            .buffered(fan_out_width)
+            .try_collect::<Vec<_>>()
+            .await?;
 
         // Record model invocation for observability (same as tier-based routing)
-        let tier_enum = match tier {
-            crate::router::TargetModel::Fast => crate::metrics::Tier::Fast,
-            crate::router::TargetModel::Balanced => crate::metrics::Tier::Balanced,
-            crate::router::TargetModel::Deep => crate::metrics::Tier::Deep,
-        };
-        if let Err(e) = state.metrics().record_model_invocation(tier_enum) {
-            state
-                .metrics()
-                .metrics_recording_failure("record_model_invocation");
-            tracing::error!(
-                request_id = %request_id,
-                error = %e,
-                tier = ?tier_enum,
-                "Metrics recording failed. Observability degraded but request continues."
-            );
-        }
+        state
+            .metrics()
+            .try_record_model_invocation(tier.into(), Some(request_id));
 
-        // Mark endpoint as healthy on success, collect warnings
         let mut warnings: Vec<String> = Vec::new();
-        if let Err(e) = state
-            .selector()
-            .health_checker()
-            .mark_success(endpoint.name())
-            .await
-        {
-            tracing::warn!(
-                request_id = %request_id,
-                endpoint_name = %endpoint.name(),
-                error = %e,
-                "Health tracking failed for specific model query"
-            );
-            // Record in metrics for observability parity with tier-based routing
-            state
-                .metrics()
-                .health_tracking_failure(endpoint.name(), e.error_type());
-            // Surface to client via warning header
-            warnings.push(format!(
-                "Health tracking failed: {} (endpoint health state may be stale)",
-                e
-            ));
+        if let (_, Some(clamp_warning)) = resolve_max_tokens(&endpoint, request.max_tokens()) {
+            warnings.push(clamp_warning);
+        }
+        let mut contents = Vec::with_capacity(answers.len());
+        let mut logprobs = Vec::with_capacity(answers.len());
+        for (answer, health_warnings) in answers {
+            contents.push(answer.text);
+            logprobs.push(answer.logprobs);
+            for warning in health_warnings {
+                if !warnings.contains(&warning) {
+                    warnings.push(warning);
+                }
+            }
         }
 
         let TimestampResult {
@@ -274,42 +516,51 @@ pub async fn handler(
         if let Some(w) = clock_warning {
             warnings.push(w);
         }
+        // `choices` is at least 1, so the first answer is always there
+        let content = contents.remove(0);
         let response =
-            ChatCompletion::new(content, endpoint.name().to_string(), prompt_chars, created);
+            ChatCompletion::new(content, endpoint.name().to_string(), prompt_chars, created)
+                .with_extra_choices(contents)
+                .with_logprobs(logprobs);
+        let served = vec![(&endpoint, tier); response.choices.len()];
+        let response = attach_cost(&state, &served, response, request_id);
 
         tracing::info!(
             request_id = %request_id,
+            user = request.user().unwrap_or_default(),
             model = %response.model,
             response_length = response.choices[0].message.content().len(),
             warnings_count = warnings.len(),
             "Chat completion successful (specific model)"
         );
 
-        return Ok(build_response_with_warnings(response, &warnings));
+        let mut response = finish_completion(&state, idempotency_key, response, warnings);
+        set_endpoint_headers(
+            &mut response,
+            &state.config().observability,
+            endpoint.name(),
+            None,
+        );
+        return Ok(response);
     }
 
+    // Router and completion attempts share one cap on upstream calls
+    let call_budget = CallBudget::new(state.config().server.max_upstream_calls);
+
     // For tier-based routing (auto, fast, balanced, deep)
     let decision = match request.model() {
         ModelChoice::Auto => {
-            // Use router to determine tier (auto-detection)
-            let metadata = request.to_route_metadata();
-            let routing_start = std::time::Instant::now();
-            let decision = state
-                .router()
-                .route(&prompt, &metadata, state.selector())
-                .await?;
-            let routing_duration_ms = routing_start.elapsed().as_secs_f64() * 1000.0;
-
-            tracing::info!(
-                request_id = %request_id,
-                target_tier = ?decision.target(),
-                routing_strategy = ?decision.strategy(),
-                routing_duration_ms = %routing_duration_ms,
-                "Routing decision made (auto)"
-            );
-
-            record_routing_metrics(&state, &decision, routing_duration_ms, request_id);
-            decision
+            // Use router to determine tier (auto-detection, honoring sticky sessions)
+            let session_key = session_key(&headers, &request);
+            route_auto(
+                &state,
+                &request,
+                &prompt,
+                session_key.as_deref(),
+                request_id,
+                &call_budget,
+            )
+            .await?
         }
         ModelChoice::Fast | ModelChoice::Balanced | ModelChoice::Deep => {
             // Direct tier selection (bypass routing)
@@ -336,24 +587,70 @@ pub async fn handler(
         }
         ModelChoice::Specific(_) => unreachable!("handled above"),
     };
+    // A prompt too large for the tier is rejected or escalated before selection
+    let decision =
+        context_window::fit_decision(state.config(), decision, token_estimate, request_id)?;
+    notify_routing_observer(&state, &request.to_route_metadata(), &decision);
 
-    // Execute query with retry logic (selects from tier)
-    let config = QueryConfig::default();
-    let result = execute_query_with_retry(
-        &state,
-        &decision,
-        &prompt,
+    log_routed_prompt(
+        &state.config().observability,
         request_id,
-        &config,
-        Some(&sampling_params),
-    )
-    .await?;
+        &prompt,
+        decision.target(),
+    );
+
+    // Execute query with retry logic (selects from tier, preferring endpoints tagged for the task)
+    let config = QueryConfig::default()
+        .with_preferred_tags(task_type_tags(request.to_route_metadata().task_type))
+        .with_excluded_endpoints(context_window::oversized_endpoints(
+            state.config(),
+            token_estimate,
+        ))
+        .with_call_budget(call_budget);
+    let (state_ref, decision_ref, config_ref) = (&state, &decision, &config);
+    let sampling_ref = &sampling_params;
+    let mut results = stream::iter(0..choices)
+        .map(move |_| async move {
+            // Each concurrent call holds its own slot; a full tier sheds with 503
+            // (after any queue wait)
+            let _tier_permit = state_ref
+                .tier_budgets()
+                .acquire(decision_ref.target())
+                .await?;
+            execute_query_with_retry(
+                state_ref,
+                decision_ref,
+                query_prompt,
+                request_id,
+                config_ref,
+                Some(sampling_ref),
+            )
+            .await
+        })
+        .buffered(fan_out_width)
+        .try_collect::<Vec<_>>()
+        .await?;
+    // `choices` is at least 1; the first answer decides the reported model
+    let result = results.remove(0);
 
     // Use the endpoint that was actually selected
     let response_model = result.endpoint.name().to_string();
 
-    // Collect all warnings (from query + clock)
+    // Collect all warnings (from every query + clock)
     let mut warnings = result.warnings;
+    let mut extra_choices = Vec::with_capacity(results.len());
+    let mut extra_served = Vec::with_capacity(results.len());
+    let mut logprobs = vec![result.logprobs];
+    for extra in results {
+        for warning in extra.warnings {
+            if !warnings.contains(&warning) {
+                warnings.push(warning);
+            }
+        }
+        extra_choices.push(extra.content);
+        extra_served.push((extra.endpoint, extra.tier));
+        logprobs.push(extra.logprobs);
+    }
     let TimestampResult {
         timestamp: created,
         warning: clock_warning,
@@ -363,10 +660,25 @@ pub async fn handler(
     }
 
     // Build OpenAI-compatible response
-    let response = ChatCompletion::new(result.content, response_model, prompt_chars, created);
+    let response = ChatCompletion::new(result.content, response_model, prompt_chars, created)
+        .with_extra_choices(extra_choices)
+        .with_logprobs(logprobs);
+    // Each choice is priced at the endpoint that served it
+    let served: Vec<_> = std::iter::once((&result.endpoint, result.tier))
+        .chain(
+            extra_served
+                .iter()
+                .map(|(endpoint, tier)| (endpoint, *tier)),
+        )
+        .collect();
+    let mut response = attach_cost(&state, &served, response, request_id);
+    if debug_requested(&state, &headers) {
+        response = response.with_debug(RoutingDebug::from_decision(&decision));
+    }
 
     tracing::info!(
         request_id = %request_id,
+        user = request.user().unwrap_or_default(),
         model = %response.model,
         response_length = response.choices[0].message.content().len(),
         warnings_count = warnings.len(),
@@ -374,7 +686,17 @@ pub async fn handler(
     );
 
     // Return response with warning header if there were non-fatal issues
-    Ok(build_response_with_warnings(response, &warnings))
+    let mut response = finish_completion(&state, idempotency_key, response, warnings);
+    if matches!(request.model(), ModelChoice::Auto) {
+        set_routing_path(&mut response, decision.strategy());
+    }
+    set_endpoint_headers(
+        &mut response,
+        &state.config().observability,
+        result.endpoint.name(),
+        decision.router_endpoint(),
+    );
+    Ok(response)
 }
 
 #[cfg(test)]
@@ -382,6 +704,49 @@ mod tests {
     #[allow(unused_imports)]
     use super::*;
 
+    // -------------------------------------------------------------------------
+    // Idempotency-Key Header Tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_idempotency_key_absent_is_none() {
+        let headers = HeaderMap::new();
+        assert_eq!(idempotency_key(&headers).unwrap(), None);
+    }
+
+    #[test]
+    fn test_idempotency_key_is_trimmed() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            IDEMPOTENCY_KEY_HEADER,
+            HeaderValue::from_static(" abc-123 "),
+        );
+        assert_eq!(
+            idempotency_key(&headers).unwrap(),
+            Some("abc-123".to_string())
+        );
+    }
+
+    #[test]
+    fn test_idempotency_key_rejects_empty_and_oversized() {
+        let mut headers = HeaderMap::new();
+        headers.insert(IDEMPOTENCY_KEY_HEADER, HeaderValue::from_static("   "));
+        assert!(matches!(
+            idempotency_key(&headers),
+            Err(AppError::Validation(_))
+        ));
+
+        let oversized = "k".repeat(MAX_IDEMPOTENCY_KEY_LEN + 1);
+        headers.insert(
+            IDEMPOTENCY_KEY_HEADER,
+            HeaderValue::from_str(&oversized).unwrap(),
+        );
+        assert!(matches!(
+            idempotency_key(&headers),
+            Err(AppError::Validation(_))
+        ));
+    }
+
     // -------------------------------------------------------------------------
     // Warning Header Truncation Tests
     // -------------------------------------------------------------------------
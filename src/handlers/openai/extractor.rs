@@ -4,13 +4,16 @@
 //! when deserialization fails. This ensures compatibility with OpenAI SDKs
 //! like LangChain and the official OpenAI Python/JS libraries.
 
+use crate::error::AppError;
 use axum::{
     Json,
-    extract::{FromRequest, Request, rejection::JsonRejection},
-    http::StatusCode,
+    body::Bytes,
+    extract::{FromRequest, Request, rejection::BytesRejection},
+    http::{HeaderMap, StatusCode, header},
     response::{IntoResponse, Response},
 };
 use serde::de::DeserializeOwned;
+use serde_json::error::Category;
 
 /// OpenAI-compatible error response structure
 ///
@@ -88,30 +91,51 @@ impl OpenAiError {
 
 /// OpenAI-compatible JSON extraction error
 ///
-/// Wraps Axum's `JsonRejection` to produce OpenAI-formatted error responses.
-/// Uses appropriate HTTP status codes based on the type of rejection:
-/// - JSON syntax errors → 400 Bad Request
-/// - Data validation errors → 422 Unprocessable Entity
+/// Produces OpenAI-formatted error responses with status codes based on what
+/// went wrong:
+/// - Malformed JSON (syntax errors, truncated bodies, wrong-typed fields)
+///   → 400 Bad Request via [`AppError::RequestDeserialization`]
+/// - Well-formed JSON rejected by request validation → 422 Unprocessable Entity
 /// - Missing content type → 415 Unsupported Media Type
-pub struct OpenAiJsonRejection(JsonRejection);
+pub enum OpenAiJsonRejection {
+    /// `Content-Type` is missing or not JSON
+    MissingJsonContentType,
+    /// The request body could not be read
+    Body(BytesRejection),
+    /// The body is not valid JSON, or a field has the wrong JSON type
+    Malformed {
+        detail: String,
+        param: Option<String>,
+    },
+    /// The body is valid JSON but fails request validation
+    Invalid {
+        message: String,
+        param: Option<String>,
+    },
+}
 
 impl IntoResponse for OpenAiJsonRejection {
     fn into_response(self) -> Response {
-        let (status, message) = match &self.0 {
-            JsonRejection::JsonSyntaxError(_) => (StatusCode::BAD_REQUEST, self.0.body_text()),
-            JsonRejection::JsonDataError(_) => {
-                (StatusCode::UNPROCESSABLE_ENTITY, self.0.body_text())
-            }
-            JsonRejection::MissingJsonContentType(_) => (
+        let (status, error) = match self {
+            Self::MissingJsonContentType => (
                 StatusCode::UNSUPPORTED_MEDIA_TYPE,
-                "Content-Type must be application/json".to_string(),
+                OpenAiError::invalid_request("Content-Type must be application/json"),
+            ),
+            Self::Body(rejection) => (
+                StatusCode::BAD_REQUEST,
+                OpenAiError::invalid_request(rejection.body_text()),
             ),
-            _ => {
-                // BytesRejection or future rejection types
-                (StatusCode::BAD_REQUEST, self.0.body_text())
+            Self::Malformed { detail, param } => {
+                return AppError::RequestDeserialization { detail, param }.into_response();
             }
+            Self::Invalid { message, param } => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                match param {
+                    Some(param) => OpenAiError::invalid_param(message, param),
+                    None => OpenAiError::invalid_request(message),
+                },
+            ),
         };
-        let error = OpenAiError::invalid_request(message);
         (status, Json(error)).into_response()
     }
 }
@@ -137,17 +161,78 @@ pub struct OpenAiJson<T>(pub T);
 
 impl<S, T> FromRequest<S> for OpenAiJson<T>
 where
-    Json<T>: FromRequest<S, Rejection = JsonRejection>,
     S: Send + Sync,
     T: DeserializeOwned,
 {
     type Rejection = OpenAiJsonRejection;
 
     async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
-        match Json::<T>::from_request(req, state).await {
-            Ok(Json(value)) => Ok(OpenAiJson(value)),
-            Err(rejection) => Err(OpenAiJsonRejection(rejection)),
+        if !has_json_content_type(req.headers()) {
+            return Err(OpenAiJsonRejection::MissingJsonContentType);
         }
+        let bytes = Bytes::from_request(req, state)
+            .await
+            .map_err(OpenAiJsonRejection::Body)?;
+        deserialize_body(&bytes).map(OpenAiJson)
+    }
+}
+
+/// Whether the request declares a JSON body (`application/json` or `application/*+json`)
+fn has_json_content_type(headers: &HeaderMap) -> bool {
+    let Some(content_type) = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return false;
+    };
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    mime == "application/json" || (mime.starts_with("application/") && mime.ends_with("+json"))
+}
+
+/// Deserialize a request body, tracking the path of the field that failed
+fn deserialize_body<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, OpenAiJsonRejection> {
+    let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+    let value = serde_path_to_error::deserialize(&mut deserializer).map_err(|err| {
+        let param = Some(err.path().to_string()).filter(|path| path != ".");
+        if is_malformed(err.inner()) {
+            OpenAiJsonRejection::Malformed {
+                detail: err.to_string(),
+                param,
+            }
+        } else {
+            OpenAiJsonRejection::Invalid {
+                message: format!(
+                    "Failed to deserialize the JSON body into the target type: {}",
+                    err
+                ),
+                param,
+            }
+        }
+    })?;
+    // Trailing characters after the value
+    deserializer
+        .end()
+        .map_err(|err| OpenAiJsonRejection::Malformed {
+            detail: err.to_string(),
+            param: None,
+        })?;
+    Ok(value)
+}
+
+/// Whether a parse failure means the JSON itself is malformed
+///
+/// Syntax errors and truncated input are malformed, as are values of the wrong
+/// JSON type, which serde reports as "invalid type: ..." (`de::Error::invalid_type`).
+/// Every other data error comes from the request types' own validation.
+fn is_malformed(err: &serde_json::Error) -> bool {
+    match err.classify() {
+        Category::Syntax | Category::Eof | Category::Io => true,
+        Category::Data => err.to_string().starts_with("invalid type:"),
     }
 }
 
@@ -178,6 +263,57 @@ mod tests {
         assert_eq!(json["error"]["type"], "server_error");
     }
 
+    #[derive(Debug, serde::Deserialize)]
+    #[allow(dead_code)]
+    struct Sample {
+        name: String,
+        limits: Vec<u32>,
+    }
+
+    #[test]
+    fn test_truncated_body_is_malformed() {
+        let result = deserialize_body::<Sample>(br#"{"name": "a", "limits": [1,"#);
+        assert!(matches!(
+            result,
+            Err(OpenAiJsonRejection::Malformed { ref detail, .. }) if detail.contains("line 1")
+        ));
+    }
+
+    #[test]
+    fn test_wrong_type_is_malformed_with_field_path() {
+        let result = deserialize_body::<Sample>(br#"{"name": "a", "limits": [1, "two"]}"#);
+        match result {
+            Err(OpenAiJsonRejection::Malformed { param, .. }) => {
+                assert_eq!(param.as_deref(), Some("limits[1]"));
+            }
+            _ => panic!("expected a malformed rejection"),
+        }
+    }
+
+    #[test]
+    fn test_missing_field_is_invalid_not_malformed() {
+        let result = deserialize_body::<Sample>(br#"{"name": "a"}"#);
+        assert!(matches!(result, Err(OpenAiJsonRejection::Invalid { .. })));
+    }
+
+    #[test]
+    fn test_json_content_type_detection() {
+        let mut headers = HeaderMap::new();
+        assert!(!has_json_content_type(&headers));
+        headers.insert(header::CONTENT_TYPE, "text/plain".parse().unwrap());
+        assert!(!has_json_content_type(&headers));
+        headers.insert(
+            header::CONTENT_TYPE,
+            "application/json; charset=utf-8".parse().unwrap(),
+        );
+        assert!(has_json_content_type(&headers));
+        headers.insert(
+            header::CONTENT_TYPE,
+            "application/vnd.api+json".parse().unwrap(),
+        );
+        assert!(has_json_content_type(&headers));
+    }
+
     #[test]
     fn test_openai_error_invalid_param() {
         let error = OpenAiError::invalid_param("invalid value", "temperature");
@@ -4,9 +4,15 @@
 //! - `POST /v1/chat/completions` - Chat completions with SSE streaming
 //! - `GET /v1/models` - List available models
 
-use crate::config::{Config, ModelEndpoint};
 use crate::error::AppError;
-use crate::router::TargetModel;
+use crate::handlers::AppState;
+use crate::middleware::RequestId;
+use crate::router::{RoutingDecision, RoutingStrategy, TargetModel};
+use crate::shared::call_budget::CallBudget;
+use crate::shared::query::{record_routing_decision, record_routing_metrics, routing_metadata};
+use crate::shared::ttl_cache::TtlCache;
+use axum::http::HeaderMap;
+use types::ChatCompletionRequest;
 
 pub mod completions;
 pub mod extractor;
@@ -17,119 +23,176 @@ pub mod types;
 // Re-export the warning header constant for consumers
 pub use completions::X_OCTOROUTE_WARNING;
 
-/// Find an endpoint by name across all tiers
+/// Request header identifying a conversation for sticky routing
 ///
-/// Searches through fast, balanced, and deep tiers to find an endpoint
-/// with the specified name. Returns the tier and endpoint if found.
+/// Takes precedence over the OpenAI `user` field when both are present.
+pub const X_OCTOROUTE_SESSION: &str = "x-octoroute-session";
+
+/// Maximum number of sessions tracked for sticky routing at once
+pub const STICKY_SESSION_CAPACITY: usize = 10_000;
+
+/// Session ID to routed tier mapping used for sticky routing
+pub type SessionTierCache = TtlCache<String, TargetModel>;
+
+/// Count the request against its `user` (`[server.user_tracking]`)
 ///
-/// # Arguments
-/// * `config` - The application configuration containing model endpoints
-/// * `name` - The endpoint name to search for
+/// A no-op without user tracking or without a non-empty `user` field. The
+/// first request over the limit in a window is logged as a warning; with
+/// `reject_over_limit`, every request over it is refused until the window ends.
 ///
-/// # Returns
-/// * `Ok((TargetModel, ModelEndpoint))` - The tier and endpoint if found
-/// * `Err(AppError::Validation)` - If no endpoint with the name exists
-pub(crate) fn find_endpoint_by_name(
-    config: &Config,
-    name: &str,
-) -> Result<(TargetModel, ModelEndpoint), AppError> {
-    // Search fast tier
-    for endpoint in &config.models.fast {
-        if endpoint.name() == name {
-            return Ok((TargetModel::Fast, endpoint.clone()));
-        }
+/// # Errors
+/// Returns [`AppError::RateLimited`] (429 with `Retry-After`) when the user is
+/// over the limit and `reject_over_limit` is set.
+pub(crate) fn track_user(
+    state: &AppState,
+    request: &ChatCompletionRequest,
+    request_id: RequestId,
+) -> Result<(), AppError> {
+    let Some(tracker) = state.user_tracker() else {
+        return Ok(());
+    };
+    let Some(user) = request.user().map(str::trim).filter(|u| !u.is_empty()) else {
+        return Ok(());
+    };
+
+    let counted = tracker.record(user);
+    if !tracker.is_over_limit(&counted) {
+        return Ok(());
     }
 
-    // Search balanced tier
-    for endpoint in &config.models.balanced {
-        if endpoint.name() == name {
-            return Ok((TargetModel::Balanced, endpoint.clone()));
-        }
+    let max_requests = tracker.config().max_requests;
+    if counted.count == max_requests + 1 {
+        tracing::warn!(
+            request_id = %request_id,
+            user = %user,
+            max_requests,
+            window_seconds = tracker.config().window_seconds,
+            "User exceeded the per-user request limit for this window"
+        );
     }
 
-    // Search deep tier
-    for endpoint in &config.models.deep {
-        if endpoint.name() == name {
-            return Ok((TargetModel::Deep, endpoint.clone()));
-        }
+    if tracker.config().reject_over_limit {
+        return Err(AppError::RateLimited {
+            message: format!(
+                "user '{}' sent more than {} requests in {}s (server.user_tracking)",
+                user,
+                max_requests,
+                tracker.config().window_seconds
+            ),
+            // Round up so a retry never lands just before the window ends
+            retry_after_seconds: counted.window_remaining.as_secs()
+                + u64::from(counted.window_remaining.subsec_nanos() > 0),
+        });
     }
+    Ok(())
+}
 
-    Err(AppError::Validation(format!(
-        "Model '{}' not found. Available models: auto, fast, balanced, deep, or a specific endpoint name from config.",
-        name
-    )))
+/// Determine the sticky routing key for a request
+///
+/// Uses the `x-octoroute-session` header if present and non-empty, otherwise
+/// the OpenAI `user` field. Returns `None` if neither identifies a session.
+pub(crate) fn session_key(headers: &HeaderMap, request: &ChatCompletionRequest) -> Option<String> {
+    headers
+        .get(X_OCTOROUTE_SESSION)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .or_else(|| request.user().map(str::trim).filter(|s| !s.is_empty()))
+        .map(str::to_string)
+}
+
+/// Route a `model: "auto"` request, honoring sticky sessions when enabled
+///
+/// If sticky routing is configured and the session was routed within the TTL,
+/// the stored tier is reused and the router is not invoked. Otherwise the router
+/// decides and, for requests with a session key, the chosen tier is remembered.
+/// Router queries are spent from `call_budget`.
+pub(crate) async fn route_auto(
+    state: &AppState,
+    request: &ChatCompletionRequest,
+    prompt: &str,
+    session_key: Option<&str>,
+    request_id: RequestId,
+    call_budget: &CallBudget,
+) -> Result<RoutingDecision, AppError> {
+    let sticky = state.sticky_sessions().zip(session_key);
+
+    if let Some((sessions, key)) = sticky
+        && let Some(tier) = sessions.get(&key.to_string())
+    {
+        tracing::info!(
+            request_id = %request_id,
+            session = %key,
+            target_tier = ?tier,
+            "Sticky session routing (router skipped)"
+        );
+        let decision = RoutingDecision::new(tier, RoutingStrategy::Rule);
+        record_routing_metrics(state, &decision, 0.0, request_id);
+        return Ok(decision);
+    }
+
+    let metadata = routing_metadata(state, request.to_route_metadata(), request_id);
+    let routing_start = std::time::Instant::now();
+    let decision = state
+        .router()
+        .route_with_budget(prompt, &metadata, state.selector(), call_budget)
+        .await?;
+    let routing_duration_ms = routing_start.elapsed().as_secs_f64() * 1000.0;
+
+    tracing::info!(
+        request_id = %request_id,
+        target_tier = ?decision.target(),
+        routing_strategy = ?decision.strategy(),
+        routing_duration_ms = %routing_duration_ms,
+        stream = request.stream(),
+        "Routing decision made (auto)"
+    );
+
+    record_routing_metrics(state, &decision, routing_duration_ms, request_id);
+    record_routing_decision(state, &decision, request_id);
+
+    if let Some((sessions, key)) = sticky {
+        sessions.insert(key.to_string(), decision.target());
+    }
+
+    Ok(decision)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    fn create_test_config() -> Config {
-        let toml = r#"
-[server]
-host = "127.0.0.1"
-port = 3000
-
-[[models.fast]]
-name = "fast-model"
-base_url = "http://localhost:1234/v1"
-max_tokens = 2048
-temperature = 0.7
-weight = 1.0
-priority = 1
-
-[[models.balanced]]
-name = "balanced-model"
-base_url = "http://localhost:1235/v1"
-max_tokens = 4096
-temperature = 0.7
-weight = 1.0
-priority = 1
-
-[[models.deep]]
-name = "deep-model"
-base_url = "http://localhost:1236/v1"
-max_tokens = 8192
-temperature = 0.7
-weight = 1.0
-priority = 1
-
-[routing]
-strategy = "rule"
-"#;
-        toml::from_str(toml).expect("should parse test config")
+    fn request_with_user(user: Option<&str>) -> ChatCompletionRequest {
+        let mut builder = ChatCompletionRequest::builder()
+            .model(types::ModelChoice::Auto)
+            .user_message("Hello");
+        if let Some(user) = user {
+            builder = builder.user(user);
+        }
+        builder.build().expect("valid request")
     }
 
     #[test]
-    fn test_find_endpoint_by_name_fast() {
-        let config = create_test_config();
-        let (tier, endpoint) = find_endpoint_by_name(&config, "fast-model").unwrap();
-        assert_eq!(tier, TargetModel::Fast);
-        assert_eq!(endpoint.name(), "fast-model");
-    }
+    fn test_session_key_prefers_header_over_user_field() {
+        let mut headers = HeaderMap::new();
+        headers.insert(X_OCTOROUTE_SESSION, "conv-1".parse().unwrap());
+        let request = request_with_user(Some("alice"));
 
-    #[test]
-    fn test_find_endpoint_by_name_balanced() {
-        let config = create_test_config();
-        let (tier, endpoint) = find_endpoint_by_name(&config, "balanced-model").unwrap();
-        assert_eq!(tier, TargetModel::Balanced);
-        assert_eq!(endpoint.name(), "balanced-model");
+        assert_eq!(session_key(&headers, &request), Some("conv-1".to_string()));
     }
 
     #[test]
-    fn test_find_endpoint_by_name_deep() {
-        let config = create_test_config();
-        let (tier, endpoint) = find_endpoint_by_name(&config, "deep-model").unwrap();
-        assert_eq!(tier, TargetModel::Deep);
-        assert_eq!(endpoint.name(), "deep-model");
+    fn test_session_key_falls_back_to_user_field() {
+        let mut headers = HeaderMap::new();
+        headers.insert(X_OCTOROUTE_SESSION, "  ".parse().unwrap());
+        let request = request_with_user(Some("alice"));
+
+        assert_eq!(session_key(&headers, &request), Some("alice".to_string()));
     }
 
     #[test]
-    fn test_find_endpoint_by_name_not_found() {
-        let config = create_test_config();
-        let result = find_endpoint_by_name(&config, "nonexistent");
-        assert!(result.is_err());
-        let err = result.unwrap_err();
-        assert!(err.to_string().contains("not found"));
+    fn test_session_key_none_without_header_or_user() {
+        let request = request_with_user(None);
+        assert_eq!(session_key(&HeaderMap::new(), &request), None);
     }
 }
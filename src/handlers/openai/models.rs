@@ -4,6 +4,7 @@
 
 use crate::handlers::AppState;
 use axum::{Json, extract::State, response::IntoResponse};
+use std::collections::HashMap;
 
 use super::types::{ModelObject, ModelsListResponse};
 
@@ -20,6 +21,12 @@ use super::types::{ModelObject, ModelsListResponse};
 ///   - `object`: "model"
 ///   - `created`: Unix timestamp
 ///   - `owned_by`: "octoroute" for tiers, "user" for configured endpoints
+///   - `octoroute_tier` / `octoroute_healthy`: Tier and health status
+///     (tier entries are healthy if any of their endpoints is)
+///   - `octoroute_weight` / `octoroute_priority`: Selection settings
+///     (configured endpoints only)
+///
+/// The `octoroute_*` fields are omitted for `auto`, which has no fixed tier.
 ///
 /// # Available Models
 ///
@@ -32,23 +39,39 @@ use super::types::{ModelObject, ModelsListResponse};
 /// Plus all configured endpoint names from config.toml, which bypass
 /// routing and directly use that specific endpoint.
 pub async fn handler(State(state): State<AppState>) -> impl IntoResponse {
-    // Start with tier-based virtual models
-    let mut models = vec![
-        ModelObject::new("auto", "octoroute"),
-        ModelObject::new("fast", "octoroute"),
-        ModelObject::new("balanced", "octoroute"),
-        ModelObject::new("deep", "octoroute"),
+    let health: HashMap<String, bool> = state
+        .selector()
+        .health_checker()
+        .get_all_statuses()
+        .await
+        .into_iter()
+        .map(|status| (status.name().to_string(), status.is_healthy()))
+        .collect();
+    let is_healthy = |name: &str| health.get(name).copied().unwrap_or(false);
+
+    let config = state.config();
+    let tiers = [
+        ("fast", &config.models.fast),
+        ("balanced", &config.models.balanced),
+        ("deep", &config.models.deep),
     ];
 
-    // Add configured endpoint names from each tier
-    for endpoint in &state.config().models.fast {
-        models.push(ModelObject::new(endpoint.name(), "user"));
-    }
-    for endpoint in &state.config().models.balanced {
-        models.push(ModelObject::new(endpoint.name(), "user"));
+    // Start with tier-based virtual models
+    let mut models = vec![ModelObject::new("auto", "octoroute")];
+    for (tier, endpoints) in &tiers {
+        let tier_healthy = endpoints.iter().any(|e| is_healthy(e.name()));
+        models.push(ModelObject::new(*tier, "octoroute").with_tier(*tier, tier_healthy));
     }
-    for endpoint in &state.config().models.deep {
-        models.push(ModelObject::new(endpoint.name(), "user"));
+
+    // Add configured endpoint names from each tier
+    for (tier, endpoints) in &tiers {
+        for endpoint in endpoints.iter() {
+            models.push(
+                ModelObject::new(endpoint.name(), "user")
+                    .with_tier(*tier, is_healthy(endpoint.name()))
+                    .with_selection(endpoint.weight(), endpoint.priority()),
+            );
+        }
     }
 
     Json(ModelsListResponse::new(models))
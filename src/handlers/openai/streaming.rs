@@ -1,18 +1,56 @@
 //! OpenAI-compatible streaming chat completions handler
 //!
-//! Handles POST /v1/chat/completions requests with stream: true.
+//! Handles POST /v1/chat/completions requests with stream: true. Streaming
+//! `/chat` requests reuse the same stream once routed (see [`start_stream`]).
 //!
 //! # Limitations
 //!
-//! **Retry Logic**: Unlike non-streaming requests, streaming does not support
-//! automatic retry on endpoint failure. If the selected endpoint fails, the
-//! stream returns an error event and terminates. This is because SSE streams
-//! cannot be restarted once the HTTP response headers are sent.
+//! **Retry Logic**: A tier-routed stream that fails before its first token
+//! (connection error, error as the first item, or first-token timeout) is
+//! restarted on another healthy endpoint, up to `server.stream_failover_attempts`
+//! times. Once the first token has been sent the endpoint is committed: a later
+//! failure returns an error event and terminates the stream, because data already
+//! sent to the client cannot be taken back. Requests naming a specific endpoint
+//! are never moved.
 //!
-//! **Health Tracking**: Initial query failures are tracked for endpoint health.
-//! Mid-stream failures are logged and reported to the client but do not affect
-//! health tracking, as they typically indicate transient network issues rather
-//! than endpoint health problems.
+//! **Health Tracking**: Failures before the first token are tracked for endpoint
+//! health. Mid-stream failures are logged, reported to the client and counted in
+//! `octoroute_mid_stream_failures_total`, but do not affect health tracking, as
+//! they typically indicate transient network issues rather than endpoint health
+//! problems.
+//!
+//! **Timeouts**: The endpoint timeout bounds connection plus time-to-first-token.
+//! Once content is flowing, the stream is not subject to the request timeout.
+//!
+//! **Finish Reason**: The final chunk carries the `finish_reason` the backend
+//! reported (`stop`, `length`, `content_filter` or `tool_calls`). A backend
+//! that reports none, or one clients wouldn't know, gets `tool_calls` when it
+//! called a tool and `stop` otherwise; `length` is never inferred.
+//!
+//! **Tool Calls**: The backend's `delta.tool_calls` fragments are forwarded as
+//! they arrive, with the backend's `index`, `id`, `type` and `function` fields
+//! untouched, so clients concatenating fragments per index rebuild each call
+//! exactly as the backend sent it.
+//!
+//! **Non-Streaming Backends**: A backend that ignores `stream: true` answers
+//! with one complete JSON body. Its content type gives it away on that same
+//! response (see [`crate::shared::upstream`]): the message is sent as a single
+//! content chunk, and each tool call as one complete fragment, followed by the
+//! usual finish chunk and `[DONE]`. The query is never repeated.
+//!
+//! **Backend Statuses**: A backend that rejects the query with an HTTP status
+//! in `routing.retryable_statuses` fails over like any other start failure.
+//! Other statuses end the stream with an error chunk right away.
+//!
+//! **Keep-Alive**: While waiting for the first token, an SSE comment
+//! (`: keep-alive`) is sent every `server.sse_keepalive_seconds` so idle-timeout
+//! proxies don't close the connection. Comments stop once tokens flow. SSE
+//! comment lines are ignored by spec-compliant clients, including OpenAI SDKs.
+//!
+//! **Client Disconnects**: When the client goes away, hyper drops the response
+//! body, which drops the upstream response and closes the backend connection. Streams dropped before completion are counted in
+//! `octoroute_client_disconnects_total`. Disconnects are noticed on the next
+//! write attempt (a keep-alive comment or a token).
 //!
 //! # Serialization Safety
 //!
@@ -28,22 +66,37 @@
 //! OOM during serialization. Property-based tests in `tests/openai_streaming.rs`
 //! verify serialization succeeds for all valid inputs.
 
+use crate::config::{EmptyCompletionPolicy, ModelEndpoint};
 use crate::error::AppError;
-use crate::handlers::AppState;
+use crate::handlers::{AppState, set_endpoint_headers, set_routing_path};
 use crate::metrics::Metrics;
 use crate::middleware::RequestId;
-use crate::models::ModelSelector;
-use crate::shared::query::record_routing_metrics;
-use std::sync::Arc;
+use crate::models::health::RECOVERY_RETRY_AFTER_SECS;
+use crate::models::{EndpointName, ExclusionSet, HealthFailureKind, InFlightGuard};
+use crate::router::RouteMetadata;
+use crate::shared::call_budget::CallBudget;
+use crate::shared::context_window;
+use crate::shared::prompt_log::log_routed_prompt;
+use crate::shared::query::{
+    SamplingParams, notify_routing_observer, record_attempt_failure, record_attempt_success,
+    record_routing_metrics, resolve_max_tokens, select_endpoint, task_type_tags,
+    tier_fallback_warning,
+};
+use crate::shared::reasoning::ReasoningFilter;
+use crate::shared::tier_budget::TierPermit;
+use crate::shared::upstream::{self, UpstreamError};
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 
-use super::find_endpoint_by_name;
+use super::completions::X_OCTOROUTE_WARNING;
+use super::{route_auto, session_key};
 use axum::{
     Extension, Json,
     extract::State,
+    http::{HeaderMap, HeaderName, HeaderValue},
     response::{
         IntoResponse, Response,
-        sse::{Event, KeepAlive, Sse},
+        sse::{Event, Sse},
     },
 };
 use futures::stream::{self, StreamExt};
@@ -51,7 +104,8 @@ use std::convert::Infallible;
 use std::time::Duration;
 
 use super::types::{
-    ChatCompletionChunk, ChatCompletionRequest, ModelChoice, TimestampResult, current_timestamp,
+    ChatCompletionChunk, ChatCompletionRequest, FinishReason, ModelChoice, TimestampResult,
+    current_timestamp,
 };
 
 /// Serialize a chunk to JSON, returning a fallback error event on failure.
@@ -88,6 +142,107 @@ fn serialize_chunk(chunk: &ChatCompletionChunk, request_id: &RequestId) -> Strin
     }
 }
 
+/// Records a client disconnect if dropped before the stream finished
+///
+/// Owned by the SSE stream returned from [`create_sse_stream`], so it is dropped
+/// together with the upstream model stream when the response body is dropped.
+/// The endpoint name is shared so a pre-first-token failover can re-attribute it.
+struct DisconnectGuard {
+    endpoint_name: Arc<Mutex<String>>,
+    request_id: RequestId,
+    metrics: Arc<Metrics>,
+    completed: bool,
+}
+
+impl DisconnectGuard {
+    fn new(endpoint_name: String, request_id: RequestId, metrics: Arc<Metrics>) -> Self {
+        Self {
+            endpoint_name: Arc::new(Mutex::new(endpoint_name)),
+            request_id,
+            metrics,
+            completed: false,
+        }
+    }
+}
+
+impl Drop for DisconnectGuard {
+    fn drop(&mut self) {
+        if !self.completed {
+            let endpoint_name = self
+                .endpoint_name
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            tracing::info!(
+                request_id = %self.request_id,
+                endpoint_name = %endpoint_name,
+                "Client disconnected before stream completed, upstream query aborted"
+            );
+            self.metrics.client_disconnect(&endpoint_name);
+        }
+    }
+}
+
+/// Wrap a stream so that dropping it before exhaustion counts as a client disconnect
+///
+/// The guard is disarmed only when the inner stream returns `None`. The inner
+/// stream is never polled again once the wrapper is dropped, and dropping it
+/// releases everything the upstream query holds. The wrapper is fused: body
+/// layers such as response compression may poll it again after it has ended.
+fn guard_client_disconnect<S>(
+    inner: S,
+    guard: DisconnectGuard,
+) -> impl futures::Stream<Item = S::Item>
+where
+    S: futures::Stream + Unpin,
+{
+    stream::unfold((inner, guard), |(mut inner, mut guard)| async move {
+        match inner.next().await {
+            Some(item) => Some((item, (inner, guard))),
+            None => {
+                guard.completed = true;
+                None
+            }
+        }
+    })
+    .fuse()
+}
+
+/// SSE comment text sent while waiting for the first token
+const KEEPALIVE_COMMENT: &str = "keep-alive";
+
+/// Emit keep-alive comments every `interval` until `response` resolves
+///
+/// Yields a single-event stream per keep-alive tick, then the stream produced by
+/// `response` (flatten the result to get SSE events). The first comment is sent
+/// one full interval after the request starts. With `interval` of `None`,
+/// `response` is awaited without any keep-alive.
+fn with_first_token_keepalive<F>(
+    response: F,
+    interval: Option<Duration>,
+) -> impl futures::Stream<Item = stream::BoxStream<'static, Result<Event, Infallible>>>
+where
+    F: std::future::Future<Output = stream::BoxStream<'static, Result<Event, Infallible>>>
+        + Send
+        + 'static,
+{
+    let Some(period) = interval else {
+        return stream::once(response).left_stream();
+    };
+
+    let ticker = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+    stream::unfold(Some((Box::pin(response), ticker)), |state| async move {
+        let (mut response, mut ticker) = state?;
+        tokio::select! {
+            events = &mut response => Some((events, None)),
+            _ = ticker.tick() => {
+                let comment = stream::iter([Ok(Event::default().comment(KEEPALIVE_COMMENT))]);
+                Some((comment.boxed(), Some((response, ticker))))
+            }
+        }
+    })
+    .right_stream()
+}
+
 /// POST /v1/chat/completions handler for streaming requests
 ///
 /// Returns Server-Sent Events (SSE) stream of chat completion chunks.
@@ -110,10 +265,12 @@ fn serialize_chunk(chunk: &ChatCompletionChunk, request_id: &RequestId) -> Strin
 ///
 /// 1. Initial chunk: role announcement (`delta.role: "assistant"`)
 /// 2. Content chunks: text deltas (`delta.content: "..."`)
-/// 3. Finish chunk: completion signal (`finish_reason: "stop"`)
+/// 3. Tool call chunks: one per tool call fragment (`delta.tool_calls: [...]`)
+/// 4. Finish chunk: completion signal (`finish_reason: "stop"`)
 pub async fn handler(
     State(state): State<AppState>,
     Extension(request_id): Extension<RequestId>,
+    headers: HeaderMap,
     Json(request): Json<ChatCompletionRequest>,
 ) -> Result<Response, AppError> {
     tracing::debug!(
@@ -123,126 +280,236 @@ pub async fn handler(
         "Received streaming chat completions request"
     );
 
-    // Convert messages to a single prompt for routing and query
+    // Convert messages to a single prompt for routing and query (the house system
+    // prompt, if any, is applied once routing is done)
     let prompt = request.to_prompt_string();
+    let backend_prompt = state
+        .system_prompt()
+        .map(|system_prompt| system_prompt.backend_prompt(request.messages()));
+    let token_estimate =
+        RouteMetadata::estimate_tokens(backend_prompt.as_deref().unwrap_or(&prompt));
 
     // Extract sampling parameters from request (overrides endpoint defaults)
     let request_temperature = request.temperature();
     let request_max_tokens = request.max_tokens();
 
+    // Router queries, the first stream attempt and any failovers share one cap
+    let call_budget = CallBudget::new(state.config().server.max_upstream_calls);
+
     // Handle specific model requests differently - use the exact endpoint requested
     // Track tier for metrics recording (both specific and tier-based paths)
-    let (endpoint, target_tier) = if let ModelChoice::Specific(name) = request.model() {
-        // Find and use the specific endpoint directly (no tier selection)
-        let (tier, endpoint) = find_endpoint_by_name(state.config(), name)?;
+    // The tier permit is held for the whole stream (see create_sse_stream)
+    // The routing path is only reported when the router chose the tier
+    let (endpoint, target_tier, routing_warnings, failover, tier_permit, auto_decision) =
+        if let ModelChoice::Specific(name) = request.model() {
+            // Use the specific endpoint if it is healthy (no tier selection)
+            let (endpoint, tier) = state
+                .selector()
+                .select_named(name, &ExclusionSet::new())
+                .await?;
+            let endpoint = endpoint.clone();
+            context_window::check_endpoint(&endpoint, token_estimate)?;
+            // The first stream attempt is a completion call like any other
+            if !call_budget.try_spend() {
+                return Err(call_budget.exhausted_error());
+            }
+            log_routed_prompt(&state.config().observability, request_id, &prompt, tier);
+            let tier_permit = state.tier_budgets().acquire(tier).await?;
 
-        tracing::info!(
-            request_id = %request_id,
-            model_name = %name,
-            endpoint_name = %endpoint.name(),
-            target_tier = ?tier,
-            "Specific model selection - streaming directly to endpoint"
-        );
+            tracing::info!(
+                request_id = %request_id,
+                model_name = %name,
+                endpoint_name = %endpoint.name(),
+                target_tier = ?tier,
+                "Specific model selection - streaming directly to endpoint"
+            );
 
-        // Record routing metrics for observability parity with tier-based routing
-        // Creates a synthetic RoutingDecision since no actual routing occurred
-        let decision =
-            crate::router::RoutingDecision::new(tier, crate::router::RoutingStrategy::Rule);
-        record_routing_metrics(&state, &decision, 0.0, request_id);
-
-        (endpoint, tier)
-    } else {
-        // For tier-based routing (auto, fast, balanced, deep)
-        let decision = match request.model() {
-            ModelChoice::Auto => {
-                // Use router to determine tier (auto-detection)
-                let metadata = request.to_route_metadata();
-                let routing_start = std::time::Instant::now();
-                let decision = state
-                    .router()
-                    .route(&prompt, &metadata, state.selector())
-                    .await?;
-                let routing_duration_ms = routing_start.elapsed().as_secs_f64() * 1000.0;
+            // Record routing metrics for observability parity with tier-based routing
+            // Creates a synthetic RoutingDecision since no actual routing occurred
+            let decision =
+                crate::router::RoutingDecision::new(tier, crate::router::RoutingStrategy::Rule);
+            record_routing_metrics(&state, &decision, 0.0, request_id);
 
-                tracing::info!(
-                    request_id = %request_id,
-                    target_tier = ?decision.target(),
-                    routing_strategy = ?decision.strategy(),
-                    routing_duration_ms = %routing_duration_ms,
-                    "Routing decision made (auto, streaming)"
-                );
+            (endpoint, tier, Vec::new(), None, tier_permit, None)
+        } else {
+            // For tier-based routing (auto, fast, balanced, deep)
+            let decision = match request.model() {
+                ModelChoice::Auto => {
+                    // Use router to determine tier (auto-detection, honoring sticky sessions)
+                    let session_key = session_key(&headers, &request);
+                    route_auto(
+                        &state,
+                        &request,
+                        &prompt,
+                        session_key.as_deref(),
+                        request_id,
+                        &call_budget,
+                    )
+                    .await?
+                }
+                ModelChoice::Fast | ModelChoice::Balanced | ModelChoice::Deep => {
+                    // Direct tier selection (bypass routing)
+                    // Convert model choice to target tier - match arm guarantees this succeeds
+                    let tier = match request.model() {
+                        ModelChoice::Fast => crate::router::TargetModel::Fast,
+                        ModelChoice::Balanced => crate::router::TargetModel::Balanced,
+                        ModelChoice::Deep => crate::router::TargetModel::Deep,
+                        _ => unreachable!("outer match arm guarantees Fast/Balanced/Deep"),
+                    };
+                    let decision = crate::router::RoutingDecision::new(
+                        tier,
+                        crate::router::RoutingStrategy::Rule,
+                    );
 
-                record_routing_metrics(&state, &decision, routing_duration_ms, request_id);
-                decision
-            }
-            ModelChoice::Fast | ModelChoice::Balanced | ModelChoice::Deep => {
-                // Direct tier selection (bypass routing)
-                // Convert model choice to target tier - match arm guarantees this succeeds
-                let tier = match request.model() {
-                    ModelChoice::Fast => crate::router::TargetModel::Fast,
-                    ModelChoice::Balanced => crate::router::TargetModel::Balanced,
-                    ModelChoice::Deep => crate::router::TargetModel::Deep,
-                    _ => unreachable!("outer match arm guarantees Fast/Balanced/Deep"),
-                };
-                let decision =
-                    crate::router::RoutingDecision::new(tier, crate::router::RoutingStrategy::Rule);
-
-                tracing::info!(
-                    request_id = %request_id,
-                    target_tier = ?tier,
-                    "Direct tier selection (streaming)"
-                );
+                    tracing::info!(
+                        request_id = %request_id,
+                        target_tier = ?tier,
+                        "Direct tier selection (streaming)"
+                    );
 
-                // Record metrics for observability parity with auto-routing
-                // Duration is 0.0 since no actual routing computation happens
-                record_routing_metrics(&state, &decision, 0.0, request_id);
-                decision
-            }
-            ModelChoice::Specific(_) => unreachable!("handled above"),
+                    // Record metrics for observability parity with auto-routing
+                    // Duration is 0.0 since no actual routing computation happens
+                    record_routing_metrics(&state, &decision, 0.0, request_id);
+                    decision
+                }
+                ModelChoice::Specific(_) => unreachable!("handled above"),
+            };
+            // A prompt too large for the tier is rejected or escalated before selection
+            let decision =
+                context_window::fit_decision(state.config(), decision, token_estimate, request_id)?;
+            notify_routing_observer(&state, &request.to_route_metadata(), &decision);
+
+            log_routed_prompt(
+                &state.config().observability,
+                request_id,
+                &prompt,
+                decision.target(),
+            );
+
+            // A full tier sheds with 503 (after any queue wait) before an endpoint is picked
+            let tier_permit = state.tier_budgets().acquire(decision.target()).await?;
+
+            let excluded = context_window::oversized_endpoints(state.config(), token_estimate);
+            let preferred_tags = task_type_tags(request.to_route_metadata().task_type);
+            let (tier, endpoint, routing_warnings, failover) =
+                select_stream_endpoint(&state, &decision, excluded, preferred_tags, &call_budget)
+                    .await?;
+            let auto_decision = matches!(request.model(), ModelChoice::Auto).then_some(decision);
+            (
+                endpoint,
+                tier,
+                routing_warnings,
+                Some(failover),
+                tier_permit,
+                auto_decision,
+            )
         };
 
-        // Select endpoint from target tier
-        let failed_endpoints = crate::models::ExclusionSet::new();
-        let endpoint = state
-            .selector()
-            .select(decision.target(), &failed_endpoints)
-            .await
-            .ok_or_else(|| {
-                AppError::RoutingFailed(format!(
-                    "No available healthy endpoints for tier {:?}",
-                    decision.target()
-                ))
-            })?
-            .clone();
-        (endpoint, decision.target())
+    // Routing is done: apply the house system prompt (if configured) to the query prompt
+    let prompt = backend_prompt.unwrap_or(prompt);
+
+    // Sampling parameters from the request override endpoint defaults
+    let sampling = SamplingParams {
+        temperature: request_temperature,
+        max_tokens: request_max_tokens,
+        top_p: request.top_p(),
+        presence_penalty: request.presence_penalty(),
+        frequency_penalty: request.frequency_penalty(),
+        logit_bias: request.logit_bias().cloned(),
+        logprobs: request.logprobs(),
+        top_logprobs: request.top_logprobs(),
     };
 
-    // Build AgentOptions with effective parameters (request overrides > endpoint defaults)
-    let effective_max_tokens = request_max_tokens.unwrap_or(endpoint.max_tokens() as u32);
-    let effective_temperature = request_temperature
-        .map(|t| t as f32)
-        .unwrap_or(endpoint.temperature() as f32);
-
-    let options = open_agent::AgentOptions::builder()
-        .model(endpoint.name())
-        .base_url(endpoint.base_url())
-        .max_tokens(effective_max_tokens)
-        .temperature(effective_temperature)
-        .build()
-        .map_err(|e| {
-            tracing::error!(
-                request_id = %request_id,
-                endpoint_name = %endpoint.name(),
-                max_tokens = effective_max_tokens,
-                temperature = effective_temperature,
-                error = %e,
-                "Failed to build AgentOptions for streaming"
-            );
-            AppError::ModelQuery(crate::error::ModelQueryError::AgentOptionsConfigError {
-                endpoint: endpoint.base_url().to_string(),
-                details: format!("{}", e),
-            })
+    start_stream(
+        &state,
+        request_id,
+        prompt,
+        sampling,
+        endpoint,
+        target_tier,
+        routing_warnings,
+        failover,
+        call_budget,
+        tier_permit,
+        auto_decision.as_ref(),
+        request.user(),
+    )
+}
+
+/// Pick the first endpoint for a stream to the tier chosen by routing
+///
+/// Spends the first completion call from `call_budget` and returns the tier
+/// and endpoint selected (from a lower tier if fallback is enabled), the
+/// routing warnings to send as a header, and the failover for restarts. The
+/// stream spends any further calls from the same budget.
+pub(crate) async fn select_stream_endpoint(
+    state: &AppState,
+    decision: &crate::router::RoutingDecision,
+    excluded: ExclusionSet,
+    preferred_tags: Vec<String>,
+    call_budget: &CallBudget,
+) -> Result<
+    (
+        crate::router::TargetModel,
+        ModelEndpoint,
+        Vec<String>,
+        StreamFailover,
+    ),
+    AppError,
+> {
+    let (tier, endpoint) = select_endpoint(state, decision.target(), &excluded, &preferred_tags)
+        .await
+        .ok_or_else(|| AppError::EndpointsUnavailable {
+            message: format!(
+                "No available healthy endpoints for tier {:?}",
+                decision.target()
+            ),
+            retry_after_seconds: RECOVERY_RETRY_AFTER_SECS,
         })?;
+    // The first stream attempt is a completion call like any other
+    if !call_budget.try_spend() {
+        return Err(call_budget.exhausted_error());
+    }
+    let routing_warnings = decision
+        .warnings()
+        .iter()
+        .cloned()
+        .chain((tier != decision.target()).then(|| tier_fallback_warning(decision.target(), tier)))
+        .collect();
+    let failover = StreamFailover {
+        requested_tier: decision.target(),
+        preferred_tags,
+        excluded,
+        attempts: state.config().server.stream_failover_attempts,
+    };
+    Ok((tier, endpoint, routing_warnings, failover))
+}
+
+/// Start streaming `prompt` from `endpoint` and build the SSE response
+///
+/// Shared by `/v1/chat/completions` and the legacy `/chat` endpoint once
+/// routing is done. Pre-stream warnings (routing, `max_tokens` clamping) go
+/// out in the warning header; `auto_decision` is the routing
+/// decision when the router chose the tier, for the routing path header.
+/// Failover restarts spend from `call_budget`, which must already be charged
+/// for the first attempt.
+#[allow(clippy::too_many_arguments)] // Everything routing decided, handed over to the stream
+pub(crate) fn start_stream(
+    state: &AppState,
+    request_id: RequestId,
+    prompt: String,
+    sampling: SamplingParams,
+    endpoint: ModelEndpoint,
+    target_tier: crate::router::TargetModel,
+    routing_warnings: Vec<String>,
+    failover: Option<StreamFailover>,
+    call_budget: CallBudget,
+    tier_permit: TierPermit,
+    auto_decision: Option<&crate::router::RoutingDecision>,
+    user: Option<&str>,
+) -> Result<Response, AppError> {
+    // Requests above the endpoint cap are clamped; the warning goes out as a response header
+    let (_, max_tokens_warning) = resolve_max_tokens(&endpoint, sampling.max_tokens);
 
     // Generate unique ID and timestamp for this completion
     let completion_id = format!("chatcmpl-{}", uuid::Uuid::new_v4().simple());
@@ -255,151 +522,397 @@ pub async fn handler(
     if let Some(w) = clock_warning {
         tracing::warn!(request_id = %request_id, warning = %w, "Clock error during streaming");
     }
-    let response_model = endpoint.name().to_string();
-
-    // Get timeout for this tier (same as non-streaming handler)
-    let timeout_seconds = state.config().timeout_for_tier(target_tier);
 
     tracing::info!(
         request_id = %request_id,
+        user = user.unwrap_or_default(),
         completion_id = %completion_id,
-        model = %response_model,
         endpoint_name = %endpoint.name(),
-        timeout_seconds = timeout_seconds,
+        timeout_seconds = state.config().timeout_for_endpoint(&endpoint, target_tier),
+        stream_failover = failover.is_some(),
         "Starting streaming response"
     );
 
-    // Create the SSE stream (pass selector for health tracking, metrics for observability)
-    // Model invocation is recorded inside create_sse_stream on success only
+    // Keep-alive comments only cover the wait for the first token (0 disables)
+    let keepalive = match state.config().server.sse_keepalive_seconds {
+        0 => None,
+        secs => Some(Duration::from_secs(secs)),
+    };
+
+    // Create the SSE stream (state gives it health tracking, metrics, and endpoint
+    // selection for failover). Model invocation is recorded inside on success only
+    let in_flight = state.selector().in_flight().acquire(endpoint.name());
     let stream = create_sse_stream(
         prompt,
-        options,
+        sampling,
         completion_id,
-        response_model,
         created,
         request_id,
-        endpoint.name().to_string(),
+        endpoint.clone(),
         target_tier,
-        timeout_seconds,
-        state.selector_arc(),
-        state.metrics(),
+        keepalive,
+        in_flight,
+        tier_permit,
+        failover,
+        call_budget,
+        state.clone(),
     );
+    let mut response = Sse::new(stream).into_response();
 
-    Ok(Sse::new(stream)
-        .keep_alive(
-            KeepAlive::new().interval(Duration::from_secs(15)).text(":"), // SSE comment for keep-alive (axum adds newlines)
-        )
-        .into_response())
+    // Headers are still writable here (the stream hasn't started), so surface
+    // pre-stream warnings the same way the non-streaming handler does
+    if let Some(warning) = &max_tokens_warning {
+        tracing::debug!(
+            request_id = %request_id,
+            endpoint_name = %endpoint.name(),
+            warning = %warning,
+            "Requested max_tokens clamped to endpoint limit (streaming)"
+        );
+    }
+    let header_warnings: Vec<String> = routing_warnings
+        .into_iter()
+        .chain(max_tokens_warning)
+        .collect();
+    if !header_warnings.is_empty()
+        && let Ok(header_value) = HeaderValue::from_str(&header_warnings.join("; "))
+    {
+        response
+            .headers_mut()
+            .insert(HeaderName::from_static(X_OCTOROUTE_WARNING), header_value);
+    }
+    if let Some(decision) = auto_decision {
+        set_routing_path(&mut response, decision.strategy());
+    }
+    // Names the first endpoint; a pre-first-token failover can't update sent headers
+    set_endpoint_headers(
+        &mut response,
+        &state.config().observability,
+        endpoint.name(),
+        auto_decision.and_then(|decision| decision.router_endpoint()),
+    );
+
+    Ok(response)
+}
+
+/// Where a stream that fails before its first token may be restarted
+///
+/// Only tier-routed requests carry one: a request naming a specific endpoint
+/// is never moved to another.
+pub(crate) struct StreamFailover {
+    /// Tier chosen by routing (replacements may come from a fallback tier)
+    requested_tier: crate::router::TargetModel,
+    preferred_tags: Vec<String>,
+    /// Endpoints never eligible, such as those too small for the prompt
+    excluded: ExclusionSet,
+    /// Further endpoints to try after the first one fails; each restart also
+    /// spends one call from the request's call budget
+    attempts: usize,
+}
+
+/// Why an upstream stream produced no first token
+enum StartFailure {
+    /// Connection error or error status, or the stream errored before its first chunk
+    Query(UpstreamError),
+    /// No first chunk within the endpoint timeout (seconds)
+    Timeout(u64),
+    /// No first chunk within `server.first_token_timeout_ms` (milliseconds)
+    FirstTokenTimeout(u64),
+    /// No first chunk before the client's deadline (its `x-octoroute-deadline-ms`)
+    DeadlineExceeded(u64),
+    /// The stream ended without content and `server.on_empty_completion` rejects that
+    Empty,
+}
+
+impl StartFailure {
+    /// Sanitized error text for the client (no internal error details)
+    fn client_message(&self, request_id: RequestId) -> String {
+        match self {
+            Self::Query(_) => format!(
+                "[Error: Failed to start model query. Request ID: {}. Please retry.]",
+                request_id
+            ),
+            Self::Timeout(_) | Self::FirstTokenTimeout(_) => format!(
+                "[Error: Request timed out. Request ID: {}. Please retry.]",
+                request_id
+            ),
+            Self::DeadlineExceeded(deadline_ms) => format!(
+                "[Error: Request deadline of {} ms could not be met. Request ID: {}.]",
+                deadline_ms, request_id
+            ),
+            Self::Empty => format!(
+                "[Error: Model returned an empty completion. Request ID: {}. Please retry.]",
+                request_id
+            ),
+        }
+    }
+
+    /// HTTP status the backend rejected the query with, if it did
+    fn status(&self) -> Option<u16> {
+        match self {
+            Self::Query(error) => error.status(),
+            _ => None,
+        }
+    }
+
+    /// How the failure counts against the endpoint's health
+    fn health_failure_kind(&self) -> HealthFailureKind {
+        match self {
+            Self::Query(UpstreamError::Body(_)) => HealthFailureKind::Parse,
+            Self::Query(error) => error
+                .status()
+                .map_or(HealthFailureKind::Connection, HealthFailureKind::HttpStatus),
+            Self::Timeout(_) | Self::FirstTokenTimeout(_) | Self::DeadlineExceeded(_) => {
+                HealthFailureKind::Timeout
+            }
+            Self::Empty => HealthFailureKind::Parse,
+        }
+    }
 }
 
 /// Create an SSE stream from the model query
 ///
+/// # Timeout Semantics
+///
+/// The endpoint timeout bounds the time until the first content chunk arrives
+/// (connection + time-to-first-token). After that, the stream runs to completion
+/// without a deadline so long generations aren't killed mid-response. A client
+/// deadline on `request_id` that is closer replaces the endpoint timeout; when
+/// it passes, the stream ends with an error without failing over and without
+/// counting against the endpoint's health.
+///
+/// # Failover
+///
+/// If the query fails or times out before the first content chunk, nothing has
+/// been sent to the client but keep-alive comments, so with `failover` set the
+/// completion restarts on another healthy endpoint of the tier, up to
+/// `failover.attempts` times and while `call_budget` lasts. The role chunk and
+/// `model` field name whichever endpoint ends up serving. Once the first chunk
+/// has arrived the endpoint is committed: later errors are reported in-stream and never
+/// retried.
+///
 /// # Note on Health Tracking
 ///
-/// Health tracking is performed for initial query failures only. Mid-stream
+/// Every endpoint that fails before its first token is marked failed. Mid-stream
 /// failures are logged and reported to the client but do not affect health
 /// tracking, as they typically indicate transient issues rather than endpoint
 /// health problems.
 ///
 /// Health tracking failures are recorded in metrics for observability parity
 /// with the non-streaming handler.
-#[allow(clippy::too_many_arguments)] // Needed for health tracking and metrics
+///
+/// # Keep-Alive
+///
+/// If `keepalive` is set, SSE comments are emitted at that interval until the
+/// first token (or a final startup error/timeout) arrives; none are sent afterwards.
+///
+/// # Client Disconnects
+///
+/// The returned stream carries a [`DisconnectGuard`]: if it is dropped before
+/// `[DONE]` is emitted (including while waiting for the first token), the
+/// disconnect is logged and recorded in metrics against the endpoint serving
+/// at that moment.
+#[allow(clippy::too_many_arguments)] // Needed for failover, health tracking and metrics
 fn create_sse_stream(
     prompt: String,
-    options: open_agent::AgentOptions,
+    sampling: SamplingParams,
     completion_id: String,
-    model: String,
     created: i64,
     request_id: RequestId,
-    endpoint_name: String,
+    endpoint: ModelEndpoint,
     target_tier: crate::router::TargetModel,
-    timeout_seconds: u64,
-    selector: Arc<ModelSelector>,
-    metrics: Arc<Metrics>,
+    keepalive: Option<Duration>,
+    in_flight: InFlightGuard,
+    tier_permit: TierPermit,
+    failover: Option<StreamFailover>,
+    call_budget: CallBudget,
+    state: AppState,
 ) -> impl futures::Stream<Item = Result<Event, Infallible>> {
-    stream::once(async move {
-        // Start the model query with timeout (consistent with non-streaming handler)
-        let timeout_duration = Duration::from_secs(timeout_seconds);
-        let query_result = tokio::time::timeout(
-            timeout_duration,
-            open_agent::query(&prompt, &options),
-        )
-        .await;
+    let selector = state.selector_arc();
+    let metrics = state.metrics();
+    let guard = DisconnectGuard::new(endpoint.name().to_string(), request_id, metrics.clone());
+    let serving_endpoint = guard.endpoint_name.clone();
 
-        let model_stream = match query_result {
-            Ok(Ok(s)) => s,
-            Ok(Err(e)) => {
-                // Query failed (connection error, etc.)
-                tracing::error!(
-                    request_id = %request_id,
-                    endpoint_name = %endpoint_name,
-                    error = %e,
-                    "Failed to start streaming query"
-                );
+    let response = async move {
+        let mut endpoint = endpoint;
+        let mut target_tier = target_tier;
+        let mut in_flight = in_flight;
+        let mut failovers_left = failover.as_ref().map_or(0, |f| f.attempts);
+        let mut failed_endpoints = failover
+            .as_ref()
+            .map(|f| f.excluded.clone())
+            .unwrap_or_default();
+
+        let model_stream = loop {
+            // Start the model query with timeout covering connection AND first token.
+            // Once tokens are flowing the timeout no longer applies - long generations
+            // are legitimate and must not be cut off mid-stream. A shorter first-token
+            // budget (server.first_token_timeout_ms) takes its place when configured.
+            let timeout_seconds = state.config().timeout_for_endpoint(&endpoint, target_tier);
+            // A client deadline closer than the endpoint timeout takes its place
+            let deadline = request_id.deadline().filter(|deadline| {
+                deadline
+                    .tighter_than(Duration::from_secs(timeout_seconds))
+                    .is_some()
+            });
+            let endpoint_timeout = match deadline {
+                Some(deadline) => deadline.remaining(),
+                None => Duration::from_secs(timeout_seconds),
+            };
+            let first_token_timeout = state
+                .config()
+                .server
+                .first_token_timeout()
+                .filter(|budget| *budget < endpoint_timeout);
+            let start_timeout = first_token_timeout.unwrap_or(endpoint_timeout);
+            let body = upstream::request_body(&endpoint, &prompt, Some(&sampling));
+            let query_result = tokio::time::timeout(start_timeout, async {
+                let mut model_stream =
+                    upstream::stream_completion(state.http_client(), &endpoint, &body).await?;
+                let first_delta = model_stream.next().await;
+                Ok::<_, UpstreamError>((first_delta, model_stream))
+            })
+            .await;
 
-                // Mark endpoint as failed for health tracking
-                if let Err(health_err) = selector.health_checker().mark_failure(&endpoint_name).await
+            let failure = match query_result {
+                // No content or tool call at all, at most a finish reason
+                Ok(Ok((first_delta, rest)))
+                    if first_delta.as_ref().is_none_or(|delta| {
+                        delta.as_ref().is_ok_and(|delta| !delta.has_output())
+                    }) =>
                 {
+                    let policy = state.config().server.on_empty_completion;
+                    metrics.empty_completion(endpoint.name());
                     tracing::warn!(
                         request_id = %request_id,
-                        endpoint_name = %endpoint_name,
-                        error = %health_err,
-                        "Health tracking failed for streaming query failure"
+                        endpoint_name = %endpoint.name(),
+                        policy = ?policy,
+                        "Endpoint returned an empty completion stream"
                     );
-                    // Record in metrics for observability parity with non-streaming handler
-                    metrics.health_tracking_failure(&endpoint_name, health_err.error_type());
+                    if policy == EmptyCompletionPolicy::ReturnEmpty {
+                        break stream::iter(first_delta).chain(rest).boxed();
+                    }
+                    StartFailure::Empty
                 }
+                // An error as the very first item means nothing was generated either
+                Ok(Ok((Some(Err(e)), _))) => StartFailure::Query(e),
+                // Re-attach the already-received first chunk in front of the remaining stream
+                Ok(Ok((first_delta, rest))) => {
+                    break stream::iter(first_delta).chain(rest).boxed();
+                }
+                Ok(Err(e)) => StartFailure::Query(e),
+                Err(_elapsed) => match (first_token_timeout, deadline) {
+                    (Some(budget), _) => StartFailure::FirstTokenTimeout(budget.as_millis() as u64),
+                    (None, Some(deadline)) => StartFailure::DeadlineExceeded(deadline.budget_ms()),
+                    (None, None) => StartFailure::Timeout(timeout_seconds),
+                },
+            };
 
-                // Return an error event (sanitized - don't expose internal error details)
-                // Include request ID for support correlation
-                let error_chunk = ChatCompletionChunk::content(
-                    &completion_id,
-                    &model,
-                    created,
-                    &format!("[Error: Failed to start model query. Request ID: {}. Please retry.]", request_id),
-                );
-                return stream::iter(vec![
-                    Ok(Event::default().data(serialize_chunk(&error_chunk, &request_id))),
-                    Ok(Event::default().data("[DONE]")),
-                ])
-                .boxed();
-            }
-            Err(_elapsed) => {
-                // Timeout waiting for initial connection
-                tracing::error!(
+            match &failure {
+                StartFailure::Query(error) => tracing::error!(
+                    request_id = %request_id,
+                    endpoint_name = %endpoint.name(),
+                    error = %error,
+                    "Failed to start streaming query"
+                ),
+                StartFailure::Timeout(timeout_seconds) => tracing::error!(
                     request_id = %request_id,
-                    endpoint_name = %endpoint_name,
+                    endpoint_name = %endpoint.name(),
                     timeout_seconds = timeout_seconds,
-                    "Streaming query timed out waiting for initial connection"
-                );
+                    "Streaming query timed out waiting for first token"
+                ),
+                StartFailure::FirstTokenTimeout(timeout_ms) => tracing::error!(
+                    request_id = %request_id,
+                    endpoint_name = %endpoint.name(),
+                    first_token_timeout_ms = timeout_ms,
+                    "Streaming query produced no first token within the first-token budget"
+                ),
+                StartFailure::DeadlineExceeded(deadline_ms) => tracing::warn!(
+                    request_id = %request_id,
+                    endpoint_name = %endpoint.name(),
+                    deadline_ms = deadline_ms,
+                    "Client deadline passed while waiting for the first token"
+                ),
+                // Logged where it was detected
+                StartFailure::Empty => {}
+            }
 
-                // Mark endpoint as failed for health tracking
-                if let Err(health_err) = selector.health_checker().mark_failure(&endpoint_name).await
-                {
-                    tracing::warn!(
-                        request_id = %request_id,
-                        endpoint_name = %endpoint_name,
-                        error = %health_err,
-                        "Health tracking failed for streaming query timeout"
-                    );
-                    metrics.health_tracking_failure(&endpoint_name, health_err.error_type());
+            // on_empty_completion = "error" reports an empty stream without failing it
+            // over, and so do a status outside routing.retryable_statuses and a
+            // passed client deadline
+            let report_only = matches!(failure, StartFailure::DeadlineExceeded(_))
+                || (matches!(failure, StartFailure::Empty)
+                    && state.config().server.on_empty_completion == EmptyCompletionPolicy::Error)
+                || failure
+                    .status()
+                    .is_some_and(|status| !state.config().routing.is_retryable_status(status));
+
+            // Headers are already sent, so a health tracking warning can only be logged
+            record_attempt_failure(
+                &state,
+                endpoint.name(),
+                (!report_only).then(|| failure.health_failure_kind()),
+                request_id,
+            )
+            .await;
+
+            // Nothing has reached the client yet, so the completion can restart elsewhere
+            failed_endpoints.insert(EndpointName::from(&endpoint));
+            let replacement = match &failover {
+                Some(failover) if !report_only && failovers_left > 0 && call_budget.try_spend() => {
+                    select_endpoint(
+                        &state,
+                        failover.requested_tier,
+                        &failed_endpoints,
+                        &failover.preferred_tags,
+                    )
+                    .await
                 }
+                _ => None,
+            };
 
-                // Return a timeout error event
+            // Headers are already sent, so a clamping warning for the replacement is dropped
+            let Some((next_tier, next_endpoint)) = replacement else {
+                // Include request ID for support correlation
                 let error_chunk = ChatCompletionChunk::content(
                     &completion_id,
-                    &model,
+                    endpoint.name(),
                     created,
-                    &format!("[Error: Request timed out. Request ID: {}. Please retry.]", request_id),
+                    &failure.client_message(request_id),
                 );
                 return stream::iter(vec![
                     Ok(Event::default().data(serialize_chunk(&error_chunk, &request_id))),
                     Ok(Event::default().data("[DONE]")),
                 ])
                 .boxed();
-            }
+            };
+
+            failovers_left -= 1;
+            tracing::warn!(
+                request_id = %request_id,
+                failed_endpoint = %endpoint.name(),
+                endpoint_name = %next_endpoint.name(),
+                target_tier = ?next_tier,
+                failovers_left = failovers_left,
+                "Stream failed before first token, restarting on another endpoint"
+            );
+
+            // Move the in-flight slot and disconnect attribution to the new endpoint
+            in_flight = selector.in_flight().acquire(next_endpoint.name());
+            *serving_endpoint
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()) =
+                next_endpoint.name().to_string();
+            endpoint = next_endpoint;
+            target_tier = next_tier;
         };
 
+        let model = endpoint.name().to_string();
+        let endpoint_name = model.clone();
+
+        // Reasoning is held back across chunks, so one filter serves the whole stream
+        let reasoning_filter =
+            ReasoningFilter::for_endpoint(&endpoint).map(|filter| Arc::new(Mutex::new(filter)));
+
         // Create initial chunk with role announcement
         let initial = ChatCompletionChunk::initial(&completion_id, &model, created);
         let initial_event = Ok(Event::default().data(serialize_chunk(&initial, &request_id)));
@@ -418,89 +931,118 @@ fn create_sse_stream(
         // error_occurred.load() in success_tracker will always see any .store() from
         // content_stream. SeqCst ordering provides the memory visibility guarantee.
         let error_occurred = Arc::new(AtomicBool::new(false));
+        // The last finish reason the backend reported (same ordering argument)
+        let backend_finish_reason = Arc::new(Mutex::new(None));
+        // Whether any tool call fragment was forwarded (same ordering argument)
+        let saw_tool_calls = Arc::new(AtomicBool::new(false));
+        // Whether the backend sent any log-probabilities (same ordering argument)
+        let saw_logprobs = Arc::new(AtomicBool::new(false));
+        let logprobs_requested = sampling.logprobs == Some(true);
 
-        // Map model stream to SSE events
+        // Map model stream to SSE events (a delta may carry text and tool call fragments)
         let content_stream = model_stream
-            .filter_map({
+            .flat_map({
                 let completion_id = completion_id.clone();
                 let model = model.clone();
                 let request_id = request_id;
                 let endpoint_name = endpoint_name.clone();
                 let error_occurred = error_occurred.clone();
+                let backend_finish_reason = backend_finish_reason.clone();
+                let saw_tool_calls = saw_tool_calls.clone();
+                let saw_logprobs = saw_logprobs.clone();
                 let metrics = metrics.clone();
+                let reasoning_filter = reasoning_filter.clone();
                 move |result| {
-                    let completion_id = completion_id.clone();
-                    let model = model.clone();
-                    let request_id = request_id;
-                    let endpoint_name = endpoint_name.clone();
-                    let error_occurred = error_occurred.clone();
-                    let metrics = metrics.clone();
-                    async move {
-                        match result {
-                            Ok(block) => {
-                                use open_agent::ContentBlock;
-                                match block {
-                                    ContentBlock::Text(text_block) => {
-                                        let chunk = ChatCompletionChunk::content(
-                                            &completion_id,
-                                            &model,
-                                            created,
-                                            &text_block.text,
-                                        );
-                                        Some(Ok(Event::default().data(
-                                            serialize_chunk(&chunk, &request_id),
-                                        )))
-                                    }
-                                    other_block => {
-                                        // Log warning for non-text blocks (consistent with non-streaming)
-                                        tracing::warn!(
-                                            request_id = %request_id,
-                                            endpoint_name = %endpoint_name,
-                                            block_type = ?other_block,
-                                            "Received non-text content block, skipping (not supported - text blocks only)"
-                                        );
-                                        None
-                                    }
+                    let mut events = Vec::new();
+                    match result {
+                        Ok(delta) => {
+                            if delta.logprobs.is_some() {
+                                saw_logprobs.store(true, Ordering::SeqCst);
+                            }
+                            if let Some(reason) = delta.finish_reason {
+                                *backend_finish_reason
+                                    .lock()
+                                    .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(reason);
+                            }
+                            if let Some(content) = delta.content.filter(|text| !text.is_empty()) {
+                                let text = match &reasoning_filter {
+                                    // Nothing to send while reasoning is being dropped
+                                    Some(filter) => filter
+                                        .lock()
+                                        .unwrap_or_else(|poisoned| poisoned.into_inner())
+                                        .push(&content),
+                                    None => content,
+                                };
+                                // Held-back reasoning takes its log-probabilities with it
+                                if !text.is_empty() {
+                                    let chunk = ChatCompletionChunk::content(
+                                        &completion_id,
+                                        &model,
+                                        created,
+                                        &text,
+                                    )
+                                    .with_logprobs(delta.logprobs);
+                                    events.push(Ok(
+                                        Event::default().data(serialize_chunk(&chunk, &request_id))
+                                    ));
                                 }
                             }
-                            Err(e) => {
-                                // Mark that an error occurred so we skip the misleading finish chunk
-                                error_occurred.store(true, Ordering::SeqCst);
-
-                                // Record metric for observability (doesn't affect health tracking)
-                                metrics.mid_stream_failure(&endpoint_name);
-
-                                // Propagate error to client instead of silent drop
-                                tracing::error!(
-                                    request_id = %request_id,
-                                    endpoint_name = %endpoint_name,
-                                    error = %e,
-                                    "Stream error during content delivery - notifying client"
-                                );
-                                // Send error indication to client (sanitized message)
-                                // NOTE: SSE data fields cannot contain newlines - removed \n\n prefix
-                                // Include request ID for support correlation
-                                let error_chunk = ChatCompletionChunk::content(
+                            for fragment in delta.tool_calls {
+                                saw_tool_calls.store(true, Ordering::SeqCst);
+                                let chunk = ChatCompletionChunk::tool_call(
                                     &completion_id,
                                     &model,
                                     created,
-                                    &format!("[Stream Error: Content may be incomplete. Request ID: {}. Please retry.]", request_id),
+                                    fragment,
                                 );
-                                Some(Ok(Event::default().data(
-                                    serialize_chunk(&error_chunk, &request_id),
-                                )))
+                                events.push(Ok(
+                                    Event::default().data(serialize_chunk(&chunk, &request_id))
+                                ));
                             }
                         }
+                        Err(e) => {
+                            // Mark that an error occurred so we skip the misleading finish chunk
+                            error_occurred.store(true, Ordering::SeqCst);
+
+                            // Record metric for observability (doesn't affect health tracking)
+                            metrics.mid_stream_failure(&endpoint_name);
+
+                            // Propagate error to client instead of silent drop
+                            tracing::error!(
+                                request_id = %request_id,
+                                endpoint_name = %endpoint_name,
+                                error = %e,
+                                "Stream error during content delivery - notifying client"
+                            );
+                            // Send error indication to client (sanitized message)
+                            // NOTE: SSE data fields cannot contain newlines - removed \n\n prefix
+                            // Include request ID for support correlation
+                            let error_chunk = ChatCompletionChunk::content(
+                                &completion_id,
+                                &model,
+                                created,
+                                &format!("[Stream Error: Content may be incomplete. Request ID: {}. Please retry.]", request_id),
+                            );
+                            events.push(Ok(
+                                Event::default().data(serialize_chunk(&error_chunk, &request_id))
+                            ));
+                        }
                     }
+                    stream::iter(events)
                 }
             })
             .boxed();
 
-        // Create finish events - skip finish_reason: "stop" if error occurred
+        // Create finish events - skip the finish chunk if error occurred
         // Sending finish_reason: "stop" after an error is semantically incorrect
-        let finish_chunk = ChatCompletionChunk::finish(&completion_id, &model, created);
         let finish_events = {
             let error_occurred = error_occurred.clone();
+            let backend_finish_reason = backend_finish_reason.clone();
+            let saw_tool_calls = saw_tool_calls.clone();
+            let saw_logprobs = saw_logprobs.clone();
+            let completion_id = completion_id.clone();
+            let model = model.clone();
+            let endpoint_name = endpoint_name.clone();
             let request_id = request_id_for_finish;
             stream::once(async move {
                 if error_occurred.load(Ordering::SeqCst) {
@@ -511,11 +1053,51 @@ fn create_sse_stream(
                     );
                     vec![Ok(Event::default().data("[DONE]"))]
                 } else {
-                    // Normal completion - send finish chunk then [DONE]
-                    vec![
-                        Ok(Event::default().data(serialize_chunk(&finish_chunk, &request_id))),
-                        Ok(Event::default().data("[DONE]")),
-                    ]
+                    // Normal completion - flush text held back by the reasoning filter,
+                    // then send finish chunk and [DONE]
+                    if logprobs_requested && !saw_logprobs.load(Ordering::SeqCst) {
+                        // The warning header is long gone; this is all that can be said
+                        tracing::warn!(
+                            request_id = %request_id,
+                            endpoint_name = %endpoint_name,
+                            "logprobs requested but the endpoint did not return them"
+                        );
+                    }
+                    let mut events = Vec::new();
+                    let held_back = reasoning_filter.map(|filter| {
+                        filter
+                            .lock()
+                            .unwrap_or_else(|poisoned| poisoned.into_inner())
+                            .finish()
+                    });
+                    if let Some(text) = held_back.filter(|text| !text.is_empty()) {
+                        let chunk =
+                            ChatCompletionChunk::content(&completion_id, &model, created, &text);
+                        events.push(Ok(
+                            Event::default().data(serialize_chunk(&chunk, &request_id))
+                        ));
+                    }
+                    // The backend's own reason when it gave one; `length` is never guessed
+                    let reported = *backend_finish_reason
+                        .lock()
+                        .unwrap_or_else(|poisoned| poisoned.into_inner());
+                    let finish_reason =
+                        reported.unwrap_or(if saw_tool_calls.load(Ordering::SeqCst) {
+                            FinishReason::ToolCalls
+                        } else {
+                            FinishReason::Stop
+                        });
+                    let finish_chunk = ChatCompletionChunk::finish_with_reason(
+                        &completion_id,
+                        &model,
+                        created,
+                        finish_reason,
+                    );
+                    events.push(Ok(
+                        Event::default().data(serialize_chunk(&finish_chunk, &request_id))
+                    ));
+                    events.push(Ok(Event::default().data("[DONE]")));
+                    events
                 }
             })
             .flat_map(stream::iter)
@@ -526,12 +1108,18 @@ fn create_sse_stream(
         // with the non-streaming handler (completions.rs)
         // Only mark success and record metrics if no error occurred during streaming
         let success_tracker = {
-            let selector = selector.clone();
+            let state = state.clone();
             let metrics = metrics.clone();
             let endpoint_name = endpoint_name.clone();
             let request_id = request_id_for_finish;
             let error_occurred = error_occurred.clone();
             stream::once(async move {
+                // Release the in-flight slot and tier permit once the upstream stream is
+                // exhausted. Early error returns above and client disconnects drop them
+                // with the stream.
+                drop(in_flight);
+                drop(tier_permit);
+
                 // Only mark success and record metrics if no error occurred
                 if error_occurred.load(Ordering::SeqCst) {
                     tracing::debug!(
@@ -539,40 +1127,12 @@ fn create_sse_stream(
                         endpoint_name = %endpoint_name,
                         "Skipping health/metrics tracking due to stream error"
                     );
+                    record_attempt_failure(&state, &endpoint_name, None, request_id).await;
                 } else {
                     // Record model invocation only on success (parity with non-streaming handler)
-                    let tier_enum = match target_tier {
-                        crate::router::TargetModel::Fast => crate::metrics::Tier::Fast,
-                        crate::router::TargetModel::Balanced => crate::metrics::Tier::Balanced,
-                        crate::router::TargetModel::Deep => crate::metrics::Tier::Deep,
-                    };
-                    if let Err(e) = metrics.record_model_invocation(tier_enum) {
-                        metrics.metrics_recording_failure("record_model_invocation");
-                        tracing::error!(
-                            request_id = %request_id,
-                            error = %e,
-                            tier = ?tier_enum,
-                            "Metrics recording failed. Observability degraded but stream continues."
-                        );
-                    }
-
-                    // Mark endpoint as healthy
-                    if let Err(e) = selector.health_checker().mark_success(&endpoint_name).await {
-                        tracing::warn!(
-                            request_id = %request_id,
-                            endpoint_name = %endpoint_name,
-                            error = %e,
-                            "Health tracking failed for successful streaming completion"
-                        );
-                        // Record in metrics for observability parity with non-streaming handler
-                        metrics.health_tracking_failure(&endpoint_name, e.error_type());
-                    } else {
-                        tracing::debug!(
-                            request_id = %request_id,
-                            endpoint_name = %endpoint_name,
-                            "Marked endpoint healthy after successful stream completion"
-                        );
-                    }
+                    metrics.try_record_model_invocation(target_tier.into(), Some(request_id));
+                    // Marks the endpoint healthy; a tracking failure is already logged there
+                    record_attempt_success(&state, &endpoint_name, request_id).await;
                 }
                 // Return None to not emit any event - this is just for side effects
                 None::<Result<Event, Infallible>>
@@ -586,6 +1146,169 @@ fn create_sse_stream(
             .chain(finish_events)
             .chain(success_tracker)
             .boxed()
-    })
-    .flatten()
+    };
+
+    let events = with_first_token_keepalive(response, keepalive)
+        .flatten()
+        .boxed();
+
+    guard_client_disconnect(events, guard)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+    use std::task::Poll;
+
+    /// Sets a flag when dropped, standing in for the upstream connection
+    struct DropFlag(Arc<AtomicBool>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    fn test_metrics() -> Arc<Metrics> {
+        Arc::new(Metrics::new().expect("should create metrics"))
+    }
+
+    #[tokio::test]
+    async fn test_dropped_stream_stops_polling_upstream_and_records_disconnect() {
+        let metrics = test_metrics();
+        let polls = Arc::new(AtomicUsize::new(0));
+        let upstream_dropped = Arc::new(AtomicBool::new(false));
+
+        // Endless upstream: only stops if the consumer goes away
+        let upstream = {
+            let polls = polls.clone();
+            let flag = DropFlag(upstream_dropped.clone());
+            stream::poll_fn(move |_| {
+                let _keep_alive = &flag;
+                polls.fetch_add(1, Ordering::SeqCst);
+                Poll::Ready(Some(Ok::<_, Infallible>(Event::default().data("token"))))
+            })
+            .boxed()
+        };
+
+        let guard = DisconnectGuard::new("deep-1".to_string(), RequestId::new(), metrics.clone());
+        let mut sse = Box::pin(guard_client_disconnect(upstream, guard));
+
+        assert!(sse.next().await.is_some());
+        assert_eq!(metrics.client_disconnects_count(), 0);
+
+        // Client disconnects: hyper drops the response body
+        drop(sse);
+
+        assert_eq!(
+            polls.load(Ordering::SeqCst),
+            1,
+            "Upstream must not be polled after the client disconnects"
+        );
+        assert!(
+            upstream_dropped.load(Ordering::SeqCst),
+            "Upstream stream should be dropped (connection released)"
+        );
+        assert_eq!(metrics.client_disconnects_count(), 1);
+    }
+
+    /// Stand-in for a slow backend: first token after `delay`
+    async fn slow_response(
+        delay: Duration,
+    ) -> stream::BoxStream<'static, Result<Event, Infallible>> {
+        tokio::time::sleep(delay).await;
+        stream::iter([Ok(Event::default().data("token"))]).boxed()
+    }
+
+    /// Collect events with the virtual time at which each was produced
+    async fn collect_timed(
+        events: impl futures::Stream<Item = Result<Event, Infallible>>,
+    ) -> Vec<(Duration, String)> {
+        let start = tokio::time::Instant::now();
+        let mut events = Box::pin(events);
+        let mut out = Vec::new();
+        while let Some(Ok(event)) = events.next().await {
+            out.push((start.elapsed(), format!("{:?}", event)));
+        }
+        out
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_keepalive_comments_emitted_at_cadence_before_first_token() {
+        let events = with_first_token_keepalive(
+            slow_response(Duration::from_secs(35)),
+            Some(Duration::from_secs(10)),
+        )
+        .flatten();
+
+        let timed = collect_timed(events).await;
+
+        assert_eq!(timed.len(), 4, "3 keep-alives then the token: {:?}", timed);
+        for (i, (at, event)) in timed[..3].iter().enumerate() {
+            assert_eq!(*at, Duration::from_secs(10 * (i as u64 + 1)));
+            assert!(event.contains(KEEPALIVE_COMMENT), "got {}", event);
+        }
+        assert_eq!(timed[3].0, Duration::from_secs(35));
+        assert!(timed[3].1.contains("token"));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_keepalive_stops_once_tokens_flow() {
+        let response = async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            // Tokens keep arriving slowly after the first one
+            stream::iter(0..3)
+                .then(|i| async move {
+                    tokio::time::sleep(Duration::from_secs(20)).await;
+                    Ok(Event::default().data(format!("token-{}", i)))
+                })
+                .boxed()
+        };
+
+        let events = with_first_token_keepalive(response, Some(Duration::from_secs(2))).flatten();
+        let timed = collect_timed(events).await;
+
+        let keepalives = timed
+            .iter()
+            .filter(|(_, e)| e.contains(KEEPALIVE_COMMENT))
+            .count();
+        assert_eq!(
+            keepalives, 2,
+            "Only ticks before the first token: {:?}",
+            timed
+        );
+        assert!(
+            timed
+                .iter()
+                .skip_while(|(_, e)| e.contains(KEEPALIVE_COMMENT))
+                .all(|(_, e)| !e.contains(KEEPALIVE_COMMENT)),
+            "No keep-alive comments after tokens begin"
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_keepalive_disabled_emits_no_comments() {
+        let events =
+            with_first_token_keepalive(slow_response(Duration::from_secs(35)), None).flatten();
+        let timed = collect_timed(events).await;
+
+        assert_eq!(timed.len(), 1);
+        assert!(timed[0].1.contains("token"));
+    }
+
+    #[tokio::test]
+    async fn test_completed_stream_is_not_a_disconnect() {
+        let metrics = test_metrics();
+        let upstream = stream::iter(vec![
+            Ok::<_, Infallible>(Event::default().data("token")),
+            Ok(Event::default().data("[DONE]")),
+        ]);
+
+        let guard = DisconnectGuard::new("fast-1".to_string(), RequestId::new(), metrics.clone());
+        let events: Vec<_> = guard_client_disconnect(upstream, guard).collect().await;
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(metrics.client_disconnects_count(), 0);
+    }
 }
@@ -3,13 +3,31 @@
 //! These types follow the OpenAI Chat Completions API specification.
 //! Validation is enforced during deserialization - invalid instances cannot exist.
 
-use crate::router::{Importance, RouteMetadata, TargetModel, TaskType};
+use crate::handlers::chat::ModelTier;
+use crate::router::{
+    Importance, RouteMetadata, RoutingDecision, RoutingStrategy, TargetModel, TaskType,
+};
 use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant, SystemTime, SystemTimeError, UNIX_EPOCH};
 
 /// Maximum allowed total content length across all messages (500K chars)
-const MAX_TOTAL_CONTENT_LENGTH: usize = 500_000;
+///
+/// A hard ceiling; `server.max_total_prompt_chars` can set a lower limit.
+pub const MAX_TOTAL_CONTENT_LENGTH: usize = 500_000;
 /// Maximum number of messages allowed
-const MAX_MESSAGES: usize = 100;
+///
+/// A hard ceiling; `server.max_messages` can set a lower limit.
+pub const MAX_MESSAGES: usize = 100;
+/// Bound on each `logit_bias` value, per the OpenAI API (-100 bans, 100 forces)
+const MAX_LOGIT_BIAS: i32 = 100;
+
+/// Most alternatives `top_logprobs` may ask for per token, per the OpenAI API
+const MAX_TOP_LOGPROBS: u8 = 20;
+
+/// Most choices `n` may ask for in one request, per the OpenAI API
+pub const MAX_CHOICES: u32 = 128;
 
 // =============================================================================
 // OpenAI API Object Type Constants
@@ -28,18 +46,38 @@ pub const OBJECT_MODEL: &str = "model";
 // Shared Validation Logic
 // =============================================================================
 
+/// Sampling fields of a request, borrowed for [`validate_request_fields`]
+#[derive(Debug, Clone, Copy, Default)]
+struct SamplingFields<'a> {
+    temperature: Option<f64>,
+    top_p: Option<f64>,
+    presence_penalty: Option<f64>,
+    frequency_penalty: Option<f64>,
+    logit_bias: Option<&'a BTreeMap<u32, i32>>,
+    logprobs: Option<bool>,
+    top_logprobs: Option<u8>,
+    max_tokens: Option<u32>,
+}
+
 /// Validate ChatCompletionRequest fields
 ///
 /// This is the single source of truth for request validation, used by both
 /// the builder and serde deserializer to ensure consistent validation rules.
 fn validate_request_fields(
     messages: &[ChatMessage],
-    temperature: Option<f64>,
-    top_p: Option<f64>,
-    presence_penalty: Option<f64>,
-    frequency_penalty: Option<f64>,
-    max_tokens: Option<u32>,
+    sampling: SamplingFields<'_>,
 ) -> Result<(), String> {
+    let SamplingFields {
+        temperature,
+        top_p,
+        presence_penalty,
+        frequency_penalty,
+        logit_bias,
+        logprobs,
+        top_logprobs,
+        max_tokens,
+    } = sampling;
+
     // Validation 1: Messages array not empty
     if messages.is_empty() {
         return Err("messages array cannot be empty".to_string());
@@ -73,13 +111,13 @@ fn validate_request_fields(
         }
     }
 
-    // Validation 5: top_p range (0.0, 1.0]
+    // Validation 5: top_p range [0.0, 1.0]
     if let Some(top_p) = top_p {
         if top_p.is_nan() || top_p.is_infinite() {
             return Err("top_p must be a finite number".to_string());
         }
-        if top_p <= 0.0 || top_p > 1.0 {
-            return Err("top_p must be between 0.0 (exclusive) and 1.0 (inclusive)".to_string());
+        if !(0.0..=1.0).contains(&top_p) {
+            return Err("top_p must be between 0.0 and 1.0".to_string());
         }
     }
 
@@ -103,7 +141,32 @@ fn validate_request_fields(
         }
     }
 
-    // Validation 8: max_tokens > 0
+    // Validation 8: logit_bias values in [-100, 100] (keys are parsed as token IDs)
+    if let Some((token, bias)) = logit_bias
+        .into_iter()
+        .flatten()
+        .find(|(_, bias)| !(-MAX_LOGIT_BIAS..=MAX_LOGIT_BIAS).contains(*bias))
+    {
+        return Err(format!(
+            "logit_bias for token {} must be between -{} and {} (got {})",
+            token, MAX_LOGIT_BIAS, MAX_LOGIT_BIAS, bias
+        ));
+    }
+
+    // Validation 9: top_logprobs in [0, 20], and only alongside logprobs: true
+    if let Some(top) = top_logprobs {
+        if top > MAX_TOP_LOGPROBS {
+            return Err(format!(
+                "top_logprobs must be between 0 and {} (got {})",
+                MAX_TOP_LOGPROBS, top
+            ));
+        }
+        if logprobs != Some(true) {
+            return Err("top_logprobs requires logprobs to be true".to_string());
+        }
+    }
+
+    // Validation 10: max_tokens > 0
     if let Some(max) = max_tokens
         && max == 0
     {
@@ -113,6 +176,26 @@ fn validate_request_fields(
     Ok(())
 }
 
+/// Validate the number of requested choices (`n`)
+///
+/// Kept apart from [`validate_request_fields`] since it depends on `stream`:
+/// streamed responses carry a single choice.
+fn validate_choice_count(n: Option<u32>, stream: bool) -> Result<(), String> {
+    let Some(n) = n else {
+        return Ok(());
+    };
+    if !(1..=MAX_CHOICES).contains(&n) {
+        return Err(format!(
+            "n must be between 1 and {} (got {})",
+            MAX_CHOICES, n
+        ));
+    }
+    if n > 1 && stream {
+        return Err("n greater than 1 is not supported with stream: true".to_string());
+    }
+    Ok(())
+}
+
 // =============================================================================
 // Model Choice - Maps OpenAI `model` field to Octoroute tiers
 // =============================================================================
@@ -149,7 +232,7 @@ impl<'de> Deserialize<'de> for ModelChoice {
             "deep" => ModelChoice::Deep,
             _ => {
                 // Validate that specific model names are non-empty and trim whitespace
-                // Trimming prevents routing failures (find_endpoint_by_name does exact comparison)
+                // Trimming prevents routing failures (select_named does exact comparison)
                 let trimmed = s.trim();
                 if trimmed.is_empty() {
                     return Err(serde::de::Error::custom("model name cannot be empty"));
@@ -302,6 +385,15 @@ impl ChatMessage {
     }
 }
 
+/// Flatten messages into the `Role: content` prompt format sent to backends
+pub fn messages_to_prompt(messages: &[ChatMessage]) -> String {
+    messages
+        .iter()
+        .map(|m| format!("{:?}: {}", m.role(), m.content()))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
 impl<'de> Deserialize<'de> for ChatMessage {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -355,6 +447,17 @@ pub struct ChatCompletionRequest {
     presence_penalty: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     frequency_penalty: Option<f64>,
+    /// Token ID -> bias (-100 to 100)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    logit_bias: Option<BTreeMap<u32, i32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    logprobs: Option<bool>,
+    /// Most likely alternatives per token (0 to 20, needs `logprobs: true`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_logprobs: Option<u8>,
+    /// Number of choices to generate (1 to 128, non-streaming only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    n: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     user: Option<String>,
 }
@@ -387,6 +490,10 @@ pub struct ChatCompletionRequestBuilder {
     top_p: Option<f64>,
     presence_penalty: Option<f64>,
     frequency_penalty: Option<f64>,
+    logit_bias: Option<BTreeMap<u32, i32>>,
+    logprobs: Option<bool>,
+    top_logprobs: Option<u8>,
+    n: Option<u32>,
     user: Option<String>,
 }
 
@@ -459,7 +566,7 @@ impl ChatCompletionRequestBuilder {
         self
     }
 
-    /// Set top_p (nucleus sampling, 0.0 exclusive to 1.0 inclusive)
+    /// Set top_p (nucleus sampling, 0.0 to 1.0)
     pub fn top_p(mut self, top_p: f64) -> Self {
         self.top_p = Some(top_p);
         self
@@ -477,6 +584,30 @@ impl ChatCompletionRequestBuilder {
         self
     }
 
+    /// Set logit bias (token ID -> bias, each -100 to 100)
+    pub fn logit_bias(mut self, logit_bias: BTreeMap<u32, i32>) -> Self {
+        self.logit_bias = Some(logit_bias);
+        self
+    }
+
+    /// Request token log-probabilities
+    pub fn logprobs(mut self, logprobs: bool) -> Self {
+        self.logprobs = Some(logprobs);
+        self
+    }
+
+    /// Set how many alternatives per token to return (0 to 20, needs `logprobs`)
+    pub fn top_logprobs(mut self, top_logprobs: u8) -> Self {
+        self.top_logprobs = Some(top_logprobs);
+        self
+    }
+
+    /// Set the number of choices to generate (1 to 128, not with streaming)
+    pub fn n(mut self, n: u32) -> Self {
+        self.n = Some(n);
+        self
+    }
+
     /// Set the user identifier
     pub fn user(mut self, user: impl Into<String>) -> Self {
         self.user = Some(user.into());
@@ -491,12 +622,18 @@ impl ChatCompletionRequestBuilder {
         // Use shared validation logic
         validate_request_fields(
             &self.messages,
-            self.temperature,
-            self.top_p,
-            self.presence_penalty,
-            self.frequency_penalty,
-            self.max_tokens,
+            SamplingFields {
+                temperature: self.temperature,
+                top_p: self.top_p,
+                presence_penalty: self.presence_penalty,
+                frequency_penalty: self.frequency_penalty,
+                logit_bias: self.logit_bias.as_ref(),
+                logprobs: self.logprobs,
+                top_logprobs: self.top_logprobs,
+                max_tokens: self.max_tokens,
+            },
         )?;
+        validate_choice_count(self.n, self.stream)?;
 
         Ok(ChatCompletionRequest {
             model: self.model,
@@ -507,6 +644,10 @@ impl ChatCompletionRequestBuilder {
             top_p: self.top_p,
             presence_penalty: self.presence_penalty,
             frequency_penalty: self.frequency_penalty,
+            logit_bias: self.logit_bias,
+            logprobs: self.logprobs,
+            top_logprobs: self.top_logprobs,
+            n: self.n,
             user: self.user,
         })
     }
@@ -545,15 +686,51 @@ impl ChatCompletionRequest {
         self.max_tokens
     }
 
+    /// Get top_p if set
+    pub fn top_p(&self) -> Option<f64> {
+        self.top_p
+    }
+
+    /// Get presence_penalty if set
+    pub fn presence_penalty(&self) -> Option<f64> {
+        self.presence_penalty
+    }
+
+    /// Get frequency_penalty if set
+    pub fn frequency_penalty(&self) -> Option<f64> {
+        self.frequency_penalty
+    }
+
+    /// Get logit_bias (token ID -> bias) if set
+    pub fn logit_bias(&self) -> Option<&BTreeMap<u32, i32>> {
+        self.logit_bias.as_ref()
+    }
+
+    /// Get logprobs if set
+    pub fn logprobs(&self) -> Option<bool> {
+        self.logprobs
+    }
+
+    /// Get top_logprobs if set
+    pub fn top_logprobs(&self) -> Option<u8> {
+        self.top_logprobs
+    }
+
+    /// Get the number of requested choices if set
+    pub fn n(&self) -> Option<u32> {
+        self.n
+    }
+
+    /// Get the end-user identifier if set
+    pub fn user(&self) -> Option<&str> {
+        self.user.as_deref()
+    }
+
     /// Convert messages to a single prompt string for routing
     ///
     /// Combines all messages into a format suitable for routing analysis.
     pub fn to_prompt_string(&self) -> String {
-        self.messages
-            .iter()
-            .map(|m| format!("{:?}: {}", m.role(), m.content()))
-            .collect::<Vec<_>>()
-            .join("\n\n")
+        messages_to_prompt(&self.messages)
     }
 
     /// Get just the last user message content (for simpler routing)
@@ -565,12 +742,17 @@ impl ChatCompletionRequest {
             .map(|m| m.content())
     }
 
+    /// Characters across every message in the conversation
+    pub fn total_content_length(&self) -> usize {
+        self.messages.iter().map(|m| m.content_length()).sum()
+    }
+
     /// Convert to RouteMetadata for routing decisions
     ///
-    /// Uses auto-detection for task type based on message content.
+    /// The token estimate covers the whole conversation, since every message is
+    /// sent to the backend. Task type is auto-detected from the last user message.
     pub fn to_route_metadata(&self) -> RouteMetadata {
-        let total_chars: usize = self.messages.iter().map(|m| m.content_length()).sum();
-        let token_estimate = total_chars / 4; // Simple heuristic
+        let token_estimate = self.total_content_length() / 4; // Simple heuristic
 
         let task_type = self.infer_task_type();
 
@@ -667,6 +849,10 @@ impl<'de> Deserialize<'de> for ChatCompletionRequest {
             top_p: Option<f64>,
             presence_penalty: Option<f64>,
             frequency_penalty: Option<f64>,
+            logit_bias: Option<BTreeMap<u32, i32>>,
+            logprobs: Option<bool>,
+            top_logprobs: Option<u8>,
+            n: Option<u32>,
             user: Option<String>,
         }
 
@@ -675,13 +861,19 @@ impl<'de> Deserialize<'de> for ChatCompletionRequest {
         // Use shared validation logic, converting String error to serde error
         validate_request_fields(
             &raw.messages,
-            raw.temperature,
-            raw.top_p,
-            raw.presence_penalty,
-            raw.frequency_penalty,
-            raw.max_tokens,
+            SamplingFields {
+                temperature: raw.temperature,
+                top_p: raw.top_p,
+                presence_penalty: raw.presence_penalty,
+                frequency_penalty: raw.frequency_penalty,
+                logit_bias: raw.logit_bias.as_ref(),
+                logprobs: raw.logprobs,
+                top_logprobs: raw.top_logprobs,
+                max_tokens: raw.max_tokens,
+            },
         )
         .map_err(serde::de::Error::custom)?;
+        validate_choice_count(raw.n, raw.stream).map_err(serde::de::Error::custom)?;
 
         Ok(ChatCompletionRequest {
             model: raw.model,
@@ -692,6 +884,10 @@ impl<'de> Deserialize<'de> for ChatCompletionRequest {
             top_p: raw.top_p,
             presence_penalty: raw.presence_penalty,
             frequency_penalty: raw.frequency_penalty,
+            logit_bias: raw.logit_bias,
+            logprobs: raw.logprobs,
+            top_logprobs: raw.top_logprobs,
+            n: raw.n,
             user: raw.user,
         })
     }
@@ -708,6 +904,7 @@ pub enum FinishReason {
     Stop,
     Length,
     ContentFilter,
+    ToolCalls,
 }
 
 /// Usage statistics for a chat completion response.
@@ -867,6 +1064,11 @@ impl AssistantMessage {
 pub struct Choice {
     pub index: u32,
     pub message: AssistantMessage,
+    /// Token log-probabilities, passed through from the backend when it returns them
+    ///
+    /// Never fabricated: omitted unless the backend supplied them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logprobs: Option<serde_json::Value>,
     pub finish_reason: FinishReason,
 }
 
@@ -879,6 +1081,55 @@ pub struct ChatCompletion {
     pub model: String,
     pub choices: Vec<Choice>,
     pub usage: Usage,
+    /// Octoroute vendor extension: non-fatal degraded-mode notices for this request
+    ///
+    /// Mirrors the `X-Octoroute-Warning` header (health tracking failures, clamped
+    /// `max_tokens`, tier fallback, clock errors) in the body for clients that
+    /// cannot read response headers. Omitted when there are no warnings.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub octoroute_warnings: Vec<String>,
+    /// Octoroute vendor extension: estimated cost of this request
+    ///
+    /// `usage.total_tokens` priced at the serving endpoint's `cost_per_1k_tokens`.
+    /// Omitted when the endpoint has no rate configured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub octoroute_cost: Option<f64>,
+    /// Octoroute vendor extension: how this request was routed
+    ///
+    /// Only present in debug mode (`x-octoroute-debug: true` with
+    /// `observability.router_debug` enabled).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub octoroute_debug: Option<RoutingDebug>,
+}
+
+/// Characters of the router's raw answer kept in [`RoutingDebug::router_response`]
+pub const MAX_DEBUG_ROUTER_RESPONSE_CHARS: usize = 512;
+
+/// Routing details returned in debug mode
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoutingDebug {
+    /// Tier chosen by routing (the response may come from a fallback tier)
+    pub tier: ModelTier,
+    pub strategy: RoutingStrategy,
+    /// Raw router model answer, truncated; absent when no LLM was asked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub router_response: Option<String>,
+}
+
+impl RoutingDebug {
+    /// Debug details for `decision`, truncating the router answer
+    pub fn from_decision(decision: &RoutingDecision) -> Self {
+        Self {
+            tier: decision.target().into(),
+            strategy: decision.strategy(),
+            router_response: decision.router_response().map(|response| {
+                response
+                    .chars()
+                    .take(MAX_DEBUG_ROUTER_RESPONSE_CHARS)
+                    .collect()
+            }),
+        }
+    }
 }
 
 impl ChatCompletion {
@@ -901,65 +1152,146 @@ impl ChatCompletion {
             choices: vec![Choice {
                 index: 0,
                 message: AssistantMessage::new(content),
+                logprobs: None,
                 finish_reason: FinishReason::Stop,
             }],
             usage: Usage::estimate(prompt_chars, completion_chars),
+            octoroute_warnings: Vec::new(),
+            octoroute_cost: None,
+            octoroute_debug: None,
+        }
+    }
+
+    /// Append further choices (for `n > 1`), numbered after the existing ones
+    ///
+    /// Their estimated tokens are added to `usage.completion_tokens`; the
+    /// prompt is only counted once.
+    pub fn with_extra_choices(mut self, contents: Vec<String>) -> Self {
+        let extra_chars: usize = contents.iter().map(|c| c.chars().count()).sum();
+        let completion_tokens = self.usage.completion_tokens() + (extra_chars / 4) as u32;
+        self.usage = Usage::new(self.usage.prompt_tokens(), completion_tokens);
+        let first_index = self.choices.len() as u32;
+        self.choices.extend(
+            contents
+                .into_iter()
+                .zip(first_index..)
+                .map(|(content, index)| Choice {
+                    index,
+                    message: AssistantMessage::new(content),
+                    logprobs: None,
+                    finish_reason: FinishReason::Stop,
+                }),
+        );
+        self
+    }
+
+    /// Attach each choice's log-probabilities, in choice order
+    pub fn with_logprobs(
+        mut self,
+        logprobs: impl IntoIterator<Item = Option<serde_json::Value>>,
+    ) -> Self {
+        for (choice, logprobs) in self.choices.iter_mut().zip(logprobs) {
+            choice.logprobs = logprobs;
         }
+        self
+    }
+
+    /// Attach the estimated cost to the `octoroute_cost` extension field
+    pub fn with_cost(mut self, cost: Option<f64>) -> Self {
+        self.octoroute_cost = cost;
+        self
+    }
+
+    /// Attach warnings to the `octoroute_warnings` extension field
+    pub fn with_warnings(mut self, warnings: Vec<String>) -> Self {
+        self.octoroute_warnings = warnings;
+        self
+    }
+
+    /// Attach routing details to the `octoroute_debug` extension field
+    pub fn with_debug(mut self, debug: RoutingDebug) -> Self {
+        self.octoroute_debug = Some(debug);
+        self
     }
 }
 
-/// Get the current Unix timestamp for response creation.
-///
-/// Returns the current time as seconds since UNIX epoch. If the system clock
-/// is misconfigured (before UNIX epoch), returns 0 and logs a warning.
-///
-/// # Arguments
-/// * `metrics` - Optional metrics to track clock errors for observability
-/// * `request_id` - Optional request ID for log correlation
-///
-/// # Note
-/// Clock errors are rare but indicate serious system misconfiguration.
-/// The metric `octoroute_clock_errors_total` should trigger alerts.
 /// Result of timestamp generation, including any warnings
 pub struct TimestampResult {
-    /// Unix timestamp (0 if clock error)
+    /// Unix timestamp (estimated from the monotonic clock after a clock error)
     pub timestamp: i64,
     /// Warning message if clock error occurred
     pub warning: Option<String>,
 }
 
+/// Wall-clock seconds and the monotonic instant they were read at
+///
+/// Captured from the first good clock reading. After a clock error, `created`
+/// is extrapolated from it instead of being reported as 0.
+static CLOCK_BASELINE: OnceLock<(u64, Instant)> = OnceLock::new();
+
+/// Baseline for a clock that has never read correctly (2025-01-01T00:00:00Z)
+const CLOCK_FALLBACK_FLOOR_SECS: u64 = 1_735_689_600;
+
 /// Get current Unix timestamp for response generation
 ///
 /// Returns timestamp and optional warning if clock error detected.
 /// Callers should add the warning to their response headers if present.
+///
+/// If the system clock reads before the UNIX epoch, the timestamp is the
+/// first good reading of this process plus the monotonic time elapsed since,
+/// so `created` stays plausible and non-decreasing. The error is logged and
+/// counted in `octoroute_clock_errors_total`, which should trigger alerts.
 pub fn current_timestamp(
     metrics: Option<&crate::metrics::Metrics>,
     request_id: Option<&crate::middleware::RequestId>,
 ) -> TimestampResult {
-    match std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH) {
-        Ok(d) => TimestampResult {
-            timestamp: d.as_secs() as i64,
-            warning: None,
-        },
+    timestamp_from_clock(
+        SystemTime::now().duration_since(UNIX_EPOCH),
+        metrics,
+        request_id,
+    )
+}
+
+/// [`current_timestamp`] for a given wall-clock reading
+fn timestamp_from_clock(
+    clock: Result<Duration, SystemTimeError>,
+    metrics: Option<&crate::metrics::Metrics>,
+    request_id: Option<&crate::middleware::RequestId>,
+) -> TimestampResult {
+    match clock {
+        Ok(d) => {
+            CLOCK_BASELINE.get_or_init(|| (d.as_secs(), Instant::now()));
+            TimestampResult {
+                timestamp: d.as_secs() as i64,
+                warning: None,
+            }
+        }
         Err(e) => {
+            let (baseline_secs, baseline_at) =
+                *CLOCK_BASELINE.get_or_init(|| (CLOCK_FALLBACK_FLOOR_SECS, Instant::now()));
+            let timestamp = baseline_secs + baseline_at.elapsed().as_secs();
             if let Some(rid) = request_id {
                 tracing::warn!(
                     request_id = %rid,
                     error = %e,
-                    "System clock appears to be before UNIX epoch - using 0 as timestamp"
+                    timestamp = timestamp,
+                    "System clock appears to be before UNIX epoch - estimating timestamp from monotonic clock"
                 );
             } else {
                 tracing::warn!(
                     error = %e,
-                    "System clock appears to be before UNIX epoch - using 0 as timestamp"
+                    timestamp = timestamp,
+                    "System clock appears to be before UNIX epoch - estimating timestamp from monotonic clock"
                 );
             }
             if let Some(m) = metrics {
                 m.clock_error();
             }
             TimestampResult {
-                timestamp: 0,
-                warning: Some("system-clock-error: timestamp may be invalid".to_string()),
+                timestamp: timestamp as i64,
+                warning: Some(
+                    "system-clock-error: timestamp estimated from monotonic clock".to_string(),
+                ),
             }
         }
     }
@@ -976,6 +1308,46 @@ pub struct Delta {
     pub role: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub content: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_calls: Option<Vec<ToolCallDelta>>,
+}
+
+/// Tool call fragment in a streaming chunk
+///
+/// Clients concatenate `function.arguments` across chunks with the same
+/// `index`; `id`, `type` and `function.name` come with the first fragment only.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolCallDelta {
+    pub index: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub call_type: Option<String>,
+    pub function: FunctionCallDelta,
+}
+
+/// Function name and argument fragment of a [`ToolCallDelta`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FunctionCallDelta {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<String>,
+}
+
+impl ToolCallDelta {
+    /// A whole tool call as a single fragment at `index`
+    pub fn complete(index: u32, id: &str, name: &str, arguments: &str) -> Self {
+        Self {
+            index,
+            id: Some(id.to_string()),
+            call_type: Some("function".to_string()),
+            function: FunctionCallDelta {
+                name: Some(name.to_string()),
+                arguments: Some(arguments.to_string()),
+            },
+        }
+    }
 }
 
 /// A single choice in a streaming chunk
@@ -983,6 +1355,9 @@ pub struct Delta {
 pub struct ChunkChoice {
     pub index: u32,
     pub delta: Delta,
+    /// Log-probabilities for this chunk's tokens, when the backend returns them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logprobs: Option<serde_json::Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub finish_reason: Option<FinishReason>,
 }
@@ -1009,8 +1384,9 @@ impl ChatCompletionChunk {
                 index: 0,
                 delta: Delta {
                     role: Some("assistant".to_string()),
-                    content: None,
+                    ..Delta::default()
                 },
+                logprobs: None,
                 finish_reason: None,
             }],
         }
@@ -1026,16 +1402,54 @@ impl ChatCompletionChunk {
             choices: vec![ChunkChoice {
                 index: 0,
                 delta: Delta {
-                    role: None,
                     content: Some(content.to_string()),
+                    ..Delta::default()
+                },
+                logprobs: None,
+                finish_reason: None,
+            }],
+        }
+    }
+
+    /// Attach the backend's log-probabilities for this chunk's tokens
+    pub fn with_logprobs(mut self, logprobs: Option<serde_json::Value>) -> Self {
+        if let Some(choice) = self.choices.first_mut() {
+            choice.logprobs = logprobs;
+        }
+        self
+    }
+
+    /// Create a chunk carrying one tool call fragment
+    pub fn tool_call(id: &str, model: &str, created: i64, tool_call: ToolCallDelta) -> Self {
+        Self {
+            id: id.to_string(),
+            object: OBJECT_CHAT_COMPLETION_CHUNK.to_string(),
+            created,
+            model: model.to_string(),
+            choices: vec![ChunkChoice {
+                index: 0,
+                delta: Delta {
+                    tool_calls: Some(vec![tool_call]),
+                    ..Delta::default()
                 },
+                logprobs: None,
                 finish_reason: None,
             }],
         }
     }
 
-    /// Create a final chunk with finish reason
+    /// Create a final chunk with `finish_reason: "stop"`
     pub fn finish(id: &str, model: &str, created: i64) -> Self {
+        Self::finish_with_reason(id, model, created, FinishReason::Stop)
+    }
+
+    /// Create a final chunk with the given finish reason
+    pub fn finish_with_reason(
+        id: &str,
+        model: &str,
+        created: i64,
+        finish_reason: FinishReason,
+    ) -> Self {
         Self {
             id: id.to_string(),
             object: OBJECT_CHAT_COMPLETION_CHUNK.to_string(),
@@ -1044,7 +1458,8 @@ impl ChatCompletionChunk {
             choices: vec![ChunkChoice {
                 index: 0,
                 delta: Delta::default(),
-                finish_reason: Some(FinishReason::Stop),
+                logprobs: None,
+                finish_reason: Some(finish_reason),
             }],
         }
     }
@@ -1055,12 +1470,28 @@ impl ChatCompletionChunk {
 // =============================================================================
 
 /// A model object for the models list endpoint
+///
+/// The `octoroute_*` fields are vendor extensions describing the routing
+/// topology. They are omitted when not applicable (e.g. `auto`), and standard
+/// OpenAI clients ignore them.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModelObject {
     pub id: String,
     pub object: String,
     pub created: i64,
     pub owned_by: String,
+    /// Tier this model routes to ("fast", "balanced", or "deep")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub octoroute_tier: Option<String>,
+    /// Endpoint health; for tier entries, whether any endpoint in the tier is healthy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub octoroute_healthy: Option<bool>,
+    /// Load balancing weight (configured endpoints only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub octoroute_weight: Option<f64>,
+    /// Selection priority (configured endpoints only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub octoroute_priority: Option<u8>,
 }
 
 impl ModelObject {
@@ -1071,8 +1502,26 @@ impl ModelObject {
             object: OBJECT_MODEL.to_string(),
             created: 0, // OpenAI uses 0 for many models
             owned_by: owned_by.into(),
+            octoroute_tier: None,
+            octoroute_healthy: None,
+            octoroute_weight: None,
+            octoroute_priority: None,
         }
     }
+
+    /// Attach the tier and current health status
+    pub fn with_tier(mut self, tier: impl Into<String>, healthy: bool) -> Self {
+        self.octoroute_tier = Some(tier.into());
+        self.octoroute_healthy = Some(healthy);
+        self
+    }
+
+    /// Attach an endpoint's load balancing weight and priority
+    pub fn with_selection(mut self, weight: f64, priority: u8) -> Self {
+        self.octoroute_weight = Some(weight);
+        self.octoroute_priority = Some(priority);
+        self
+    }
 }
 
 /// Response for GET /v1/models
@@ -1232,7 +1681,7 @@ mod tests {
     #[test]
     fn test_model_choice_deserialize_trims_whitespace() {
         // Whitespace-padded model names should be trimmed to prevent routing failures
-        // (find_endpoint_by_name does exact string comparison)
+        // (select_named does exact string comparison)
         let json = r#""  qwen3-8b  ""#;
         let model: ModelChoice = serde_json::from_str(json).unwrap();
         assert_eq!(model, ModelChoice::Specific("qwen3-8b".to_string()));
@@ -1410,13 +1859,25 @@ mod tests {
         let json = r#"{
             "model": "auto",
             "messages": [{"role": "user", "content": "Hi"}],
-            "top_p": 0.0
+            "top_p": 1.5
         }"#;
         let result = serde_json::from_str::<ChatCompletionRequest>(json);
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("top_p"));
     }
 
+    #[test]
+    fn test_request_accepts_top_p_bounds() {
+        for top_p in ["0.0", "1.0"] {
+            let json = format!(
+                r#"{{"model": "auto", "messages": [{{"role": "user", "content": "Hi"}}], "top_p": {top_p}}}"#
+            );
+            let request: ChatCompletionRequest =
+                serde_json::from_str(&json).expect("top_p bounds are inclusive");
+            assert_eq!(request.top_p(), Some(top_p.parse().unwrap()));
+        }
+    }
+
     #[test]
     fn test_request_rejects_zero_max_tokens() {
         let json = r#"{
@@ -1470,6 +1931,141 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_request_deserializes_penalties_and_logit_bias() {
+        let json = r#"{
+            "model": "auto",
+            "messages": [{"role": "user", "content": "Hi"}],
+            "presence_penalty": 0.5,
+            "frequency_penalty": -1.0,
+            "logit_bias": {"50256": -100, "1234": 25}
+        }"#;
+        let request: ChatCompletionRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.presence_penalty(), Some(0.5));
+        assert_eq!(request.frequency_penalty(), Some(-1.0));
+        let logit_bias = request.logit_bias().expect("logit_bias should be set");
+        assert_eq!(logit_bias.get(&50256), Some(&-100));
+        assert_eq!(logit_bias.get(&1234), Some(&25));
+
+        let minimal: ChatCompletionRequest = serde_json::from_str(
+            r#"{"model": "auto", "messages": [{"role": "user", "content": "Hi"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(minimal.presence_penalty(), None);
+        assert_eq!(minimal.frequency_penalty(), None);
+        assert!(minimal.logit_bias().is_none());
+        // Absent parameters stay absent when the request is serialized
+        let serialized = serde_json::to_string(&minimal).unwrap();
+        assert!(!serialized.contains("penalty"), "got: {}", serialized);
+        assert!(!serialized.contains("logit_bias"), "got: {}", serialized);
+    }
+
+    #[test]
+    fn test_request_rejects_out_of_range_logit_bias() {
+        let json = r#"{
+            "model": "auto",
+            "messages": [{"role": "user", "content": "Hi"}],
+            "logit_bias": {"50256": 150}
+        }"#;
+        let err = serde_json::from_str::<ChatCompletionRequest>(json).unwrap_err();
+        assert!(err.to_string().contains("logit_bias"), "got: {}", err);
+        assert!(err.to_string().contains("50256"), "got: {}", err);
+
+        let built = ChatCompletionRequest::builder()
+            .user_message("Hi")
+            .logit_bias(BTreeMap::from([(50256, -101)]))
+            .build();
+        assert!(built.unwrap_err().contains("logit_bias"));
+    }
+
+    #[test]
+    fn test_request_deserializes_logprobs() {
+        let json = r#"{
+            "model": "auto",
+            "messages": [{"role": "user", "content": "Hi"}],
+            "logprobs": true,
+            "top_logprobs": 20
+        }"#;
+        let request: ChatCompletionRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.logprobs(), Some(true));
+        assert_eq!(request.top_logprobs(), Some(20));
+
+        let minimal: ChatCompletionRequest = serde_json::from_str(
+            r#"{"model": "auto", "messages": [{"role": "user", "content": "Hi"}]}"#,
+        )
+        .unwrap();
+        assert!(minimal.logprobs().is_none());
+        assert!(minimal.top_logprobs().is_none());
+        let serialized = serde_json::to_string(&minimal).unwrap();
+        assert!(!serialized.contains("logprobs"), "got: {}", serialized);
+    }
+
+    #[test]
+    fn test_request_validates_top_logprobs() {
+        let json = r#"{
+            "model": "auto",
+            "messages": [{"role": "user", "content": "Hi"}],
+            "logprobs": false,
+            "top_logprobs": 21
+        }"#;
+        let err = serde_json::from_str::<ChatCompletionRequest>(json).unwrap_err();
+        assert!(err.to_string().contains("top_logprobs"), "got: {}", err);
+
+        // top_logprobs is meaningless without logprobs: true
+        let built = ChatCompletionRequest::builder()
+            .user_message("Hi")
+            .top_logprobs(0)
+            .build();
+        assert!(built.unwrap_err().contains("requires logprobs"));
+
+        for top in [0, 20] {
+            let built = ChatCompletionRequest::builder()
+                .user_message("Hi")
+                .logprobs(true)
+                .top_logprobs(top)
+                .build();
+            assert!(built.is_ok(), "top_logprobs={}", top);
+        }
+    }
+
+    #[test]
+    fn test_request_validates_n() {
+        let json = r#"{"model": "auto", "messages": [{"role": "user", "content": "Hi"}], "n": 3}"#;
+        let request: ChatCompletionRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.n(), Some(3));
+
+        for n in [0, MAX_CHOICES + 1] {
+            let built = ChatCompletionRequest::builder()
+                .user_message("Hi")
+                .n(n)
+                .build();
+            assert!(built.unwrap_err().contains("n must be between"));
+        }
+
+        // Streamed responses carry a single choice
+        let json = r#"{"model": "auto", "messages": [{"role": "user", "content": "Hi"}], "n": 2, "stream": true}"#;
+        let err = serde_json::from_str::<ChatCompletionRequest>(json).unwrap_err();
+        assert!(err.to_string().contains("stream"), "got: {}", err);
+        let built = ChatCompletionRequest::builder()
+            .user_message("Hi")
+            .n(1)
+            .stream(true)
+            .build();
+        assert!(built.is_ok());
+    }
+
+    #[test]
+    fn test_extra_choices_are_numbered_and_counted() {
+        let completion = ChatCompletion::new("abcd".to_string(), "fast-1".to_string(), 8, 0)
+            .with_extra_choices(vec!["efgh".to_string(), "ijklmnop".to_string()]);
+
+        let indices: Vec<u32> = completion.choices.iter().map(|c| c.index).collect();
+        assert_eq!(indices, [0, 1, 2]);
+        assert_eq!(completion.usage.prompt_tokens(), 2);
+        assert_eq!(completion.usage.completion_tokens(), 4);
+        assert_eq!(completion.usage.total_tokens(), 6);
+    }
+
     #[test]
     fn test_request_to_prompt_string() {
         let json = r#"{
@@ -1532,6 +2128,29 @@ mod tests {
         assert_eq!(metadata.task_type, TaskType::QuestionAnswer);
     }
 
+    #[test]
+    fn test_route_metadata_token_estimate_sums_all_messages() {
+        let json = format!(
+            r#"{{
+                "model": "auto",
+                "messages": [
+                    {{"role": "system", "content": "{}"}},
+                    {{"role": "user", "content": "{}"}},
+                    {{"role": "assistant", "content": "{}"}},
+                    {{"role": "user", "content": "{}"}}
+                ]
+            }}"#,
+            "s".repeat(400),
+            "u".repeat(800),
+            "a".repeat(1200),
+            "q".repeat(40)
+        );
+        let req: ChatCompletionRequest = serde_json::from_str(&json).unwrap();
+        assert_eq!(req.total_content_length(), 2440);
+        // 2440 chars / 4, not just the 10 tokens of the last user message
+        assert_eq!(req.to_route_metadata().token_estimate, 610);
+    }
+
     // -------------------------------------------------------------------------
     // ChatCompletion Response Tests
     // -------------------------------------------------------------------------
@@ -1569,6 +2188,30 @@ mod tests {
         assert!(json.contains("\"role\":\"assistant\""));
     }
 
+    #[test]
+    fn test_chat_completion_omits_empty_warnings() {
+        let response = ChatCompletion::new("Test".to_string(), "model".to_string(), 4, 0);
+        let json = serde_json::to_value(&response).unwrap();
+
+        assert!(
+            json.get("octoroute_warnings").is_none(),
+            "Empty warnings should be omitted, got: {}",
+            json
+        );
+    }
+
+    #[test]
+    fn test_chat_completion_serializes_warnings() {
+        let response = ChatCompletion::new("Test".to_string(), "model".to_string(), 4, 0)
+            .with_warnings(vec!["Health tracking degraded".to_string()]);
+        let json = serde_json::to_value(&response).unwrap();
+
+        assert_eq!(
+            json["octoroute_warnings"],
+            serde_json::json!(["Health tracking degraded"])
+        );
+    }
+
     // -------------------------------------------------------------------------
     // Usage Tests
     // -------------------------------------------------------------------------
@@ -1682,6 +2325,45 @@ mod tests {
         assert_eq!(chunk.choices[0].finish_reason, Some(FinishReason::Stop));
     }
 
+    #[test]
+    fn test_chunk_tool_call() {
+        let chunk = ChatCompletionChunk::tool_call(
+            "test-id",
+            "model",
+            12345,
+            ToolCallDelta::complete(1, "call_1", "get_weather", r#"{"city":"Paris"}"#),
+        );
+        let json: serde_json::Value = serde_json::to_value(&chunk).unwrap();
+        let delta = &json["choices"][0]["delta"];
+
+        assert!(delta.get("content").is_none(), "got: {}", json);
+        assert_eq!(
+            delta["tool_calls"],
+            serde_json::json!([{
+                "index": 1,
+                "id": "call_1",
+                "type": "function",
+                "function": {"name": "get_weather", "arguments": "{\"city\":\"Paris\"}"}
+            }])
+        );
+    }
+
+    #[test]
+    fn test_chunk_finish_with_length_reason() {
+        let chunk = ChatCompletionChunk::finish_with_reason(
+            "test-id",
+            "model",
+            12345,
+            FinishReason::Length,
+        );
+        let json = serde_json::to_string(&chunk).unwrap();
+        assert!(
+            json.contains("\"finish_reason\":\"length\""),
+            "got: {}",
+            json
+        );
+    }
+
     // -------------------------------------------------------------------------
     // ModelsListResponse Tests
     // -------------------------------------------------------------------------
@@ -1707,6 +2389,22 @@ mod tests {
         assert!(json.contains("\"id\":\"test-model\""));
         assert!(json.contains("\"object\":\"model\""));
         assert!(json.contains("\"owned_by\":\"owner\""));
+        assert!(
+            !json.contains("octoroute_"),
+            "vendor fields should be omitted when unset"
+        );
+    }
+
+    #[test]
+    fn test_model_object_serializes_routing_metadata() {
+        let model = ModelObject::new("qwen3-8b", "user")
+            .with_tier("fast", false)
+            .with_selection(2.0, 3);
+        let json: serde_json::Value = serde_json::to_value(&model).unwrap();
+        assert_eq!(json["octoroute_tier"], "fast");
+        assert_eq!(json["octoroute_healthy"], false);
+        assert_eq!(json["octoroute_weight"], 2.0);
+        assert_eq!(json["octoroute_priority"], 3);
     }
 
     // -------------------------------------------------------------------------
@@ -1895,4 +2593,31 @@ mod tests {
         assert_eq!(msg.role(), MessageRole::Assistant);
         assert_eq!(msg.content(), "test content");
     }
+
+    // -------------------------------------------------------------------------
+    // Timestamp Tests
+    // -------------------------------------------------------------------------
+
+    /// A wall-clock reading from before the UNIX epoch
+    fn clock_error() -> Result<Duration, SystemTimeError> {
+        UNIX_EPOCH.duration_since(UNIX_EPOCH + Duration::from_secs(1))
+    }
+
+    #[test]
+    fn test_clock_error_yields_plausible_timestamp_and_counts_metric() {
+        let metrics = crate::metrics::Metrics::new().expect("should create metrics");
+
+        let result = timestamp_from_clock(clock_error(), Some(&metrics), None);
+
+        assert!(result.timestamp >= CLOCK_FALLBACK_FLOOR_SECS as i64);
+        assert!(result.warning.is_some());
+        assert_eq!(metrics.clock_errors_count(), 1);
+    }
+
+    #[test]
+    fn test_clock_error_timestamps_never_decrease() {
+        let first = timestamp_from_clock(clock_error(), None, None);
+        let second = timestamp_from_clock(clock_error(), None, None);
+        assert!(second.timestamp >= first.timestamp);
+    }
 }
@@ -0,0 +1,213 @@
+//! Kubernetes-style liveness and readiness probes
+//!
+//! - `GET /livez`: 200 while the process is running and serving HTTP
+//! - `GET /readyz`: 200 only when every tier the server depends on has at least
+//!   one healthy endpoint that has passed a health check, and the server is not
+//!   draining; 503 otherwise
+//!
+//! `/health` remains the detailed diagnostic endpoint; these probes are cheap,
+//! stable signals for orchestrators.
+
+use axum::{Json, extract::State, http::StatusCode};
+use serde::Serialize;
+use std::collections::HashSet;
+
+use crate::config::{Config, RoutingStrategy};
+use crate::handlers::AppState;
+use crate::handlers::chat::ModelTier;
+use crate::router::TargetModel;
+
+/// Readiness state reported by `/readyz`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadinessStatus {
+    /// Every required tier has a healthy, verified endpoint
+    Ready,
+    /// At least one required tier has no healthy endpoint, or none checked yet
+    NotReady,
+    /// Taken out of rotation by `POST /admin/drain`; requests are still served
+    Draining,
+}
+
+/// Readiness probe response
+#[derive(Debug, Serialize)]
+pub struct ReadinessResponse {
+    status: ReadinessStatus,
+    /// Required tiers with no healthy, verified endpoint (empty when ready)
+    unavailable_tiers: Vec<ModelTier>,
+}
+
+/// GET /livez handler
+///
+/// Always returns 200 OK: if the process can answer, it is alive.
+pub async fn livez() -> StatusCode {
+    StatusCode::OK
+}
+
+/// GET /readyz handler
+///
+/// Returns 200 OK when every required tier (see [`required_tiers`]) has at least
+/// one healthy endpoint, or 503 Service Unavailable listing the tiers that don't.
+/// Endpoints only count once a health check has succeeded against them (see
+/// [`EndpointHealth::is_verified`]), so a freshly started server is not ready
+/// until its first probe round, rather than ready on the optimistic defaults.
+/// Uses a single health checker snapshot, so the probe never queries backends.
+/// A draining server answers 503 without consulting endpoint health. With
+/// `[health].enabled = false`, every configured endpoint counts as healthy.
+///
+/// [`EndpointHealth::is_verified`]: crate::models::EndpointHealth::is_verified
+pub async fn readyz(State(state): State<AppState>) -> (StatusCode, Json<ReadinessResponse>) {
+    if state.is_draining() {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ReadinessResponse {
+                status: ReadinessStatus::Draining,
+                unavailable_tiers: Vec::new(),
+            }),
+        );
+    }
+
+    // With health tracking off every endpoint counts, checked or not
+    let health_checker = state.selector().health_checker();
+    let tracking = health_checker.is_enabled();
+    let healthy: HashSet<String> = health_checker
+        .get_all_statuses()
+        .await
+        .into_iter()
+        .filter(|status| !tracking || (status.is_healthy() && status.is_verified()))
+        .map(|status| status.name().to_string())
+        .collect();
+
+    let config = state.config();
+    let unavailable_tiers: Vec<ModelTier> = required_tiers(config)
+        .into_iter()
+        .filter(|&tier| {
+            !tier_endpoints(config, tier)
+                .iter()
+                .any(|endpoint| healthy.contains(endpoint.name()))
+        })
+        .map(ModelTier::from)
+        .collect();
+
+    if unavailable_tiers.is_empty() {
+        (
+            StatusCode::OK,
+            Json(ReadinessResponse {
+                status: ReadinessStatus::Ready,
+                unavailable_tiers,
+            }),
+        )
+    } else {
+        tracing::warn!(
+            unavailable_tiers = ?unavailable_tiers,
+            "Readiness check failed: required tiers have no healthy, verified endpoints"
+        );
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ReadinessResponse {
+                status: ReadinessStatus::NotReady,
+                unavailable_tiers,
+            }),
+        )
+    }
+}
+
+/// Tiers that must have a healthy endpoint for the server to accept traffic
+///
+/// - Routing targets: every tier, unless `routing.tier_fallback` is enabled, in
+///   which case Fast alone can serve any request
+/// - LLM and hybrid strategies additionally need the router tier to make decisions
+///   (the heuristic strategy decides locally and does not)
+pub(crate) fn required_tiers(config: &Config) -> Vec<TargetModel> {
+    let mut tiers = if config.routing.tier_fallback {
+        vec![TargetModel::Fast]
+    } else {
+        vec![TargetModel::Fast, TargetModel::Balanced, TargetModel::Deep]
+    };
+
+    if matches!(
+        config.routing.strategy,
+        RoutingStrategy::Llm | RoutingStrategy::Hybrid
+    ) {
+        let router_tier = config.routing.router_tier();
+        if !tiers.contains(&router_tier) {
+            tiers.push(router_tier);
+        }
+    }
+
+    tiers
+}
+
+fn tier_endpoints(config: &Config, tier: TargetModel) -> &[crate::config::ModelEndpoint] {
+    match tier {
+        TargetModel::Fast => &config.models.fast,
+        TargetModel::Balanced => &config.models.balanced,
+        TargetModel::Deep => &config.models.deep,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_with(strategy: &str, router_tier: &str, tier_fallback: bool) -> Config {
+        let toml = format!(
+            r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+
+[[models.fast]]
+name = "fast-1"
+base_url = "http://localhost:1234/v1"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "http://localhost:1235/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:1236/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "{strategy}"
+router_tier = "{router_tier}"
+tier_fallback = {tier_fallback}
+"#
+        );
+        toml::from_str(&toml).expect("should parse test config")
+    }
+
+    #[test]
+    fn test_required_tiers_without_fallback_is_every_tier() {
+        let config = config_with("rule", "balanced", false);
+        assert_eq!(
+            required_tiers(&config),
+            vec![TargetModel::Fast, TargetModel::Balanced, TargetModel::Deep]
+        );
+    }
+
+    #[test]
+    fn test_required_tiers_with_fallback_rule_is_fast_only() {
+        let config = config_with("rule", "deep", true);
+        assert_eq!(required_tiers(&config), vec![TargetModel::Fast]);
+    }
+
+    #[test]
+    fn test_required_tiers_with_fallback_llm_includes_router_tier() {
+        let config = config_with("llm", "deep", true);
+        assert_eq!(
+            required_tiers(&config),
+            vec![TargetModel::Fast, TargetModel::Deep]
+        );
+    }
+
+    #[test]
+    fn test_required_tiers_heuristic_ignores_router_tier() {
+        let config = config_with("heuristic", "deep", true);
+        assert_eq!(required_tiers(&config), vec![TargetModel::Fast]);
+    }
+}
@@ -0,0 +1,13 @@
+//! Version endpoint
+//!
+//! Exposes the running build's version metadata via GET /version.
+
+use crate::build_info::BuildInfo;
+use axum::Json;
+
+/// GET /version handler
+///
+/// Returns the same metadata as the `octoroute_build_info` metric labels.
+pub async fn handler() -> Json<BuildInfo> {
+    Json(BuildInfo::current())
+}
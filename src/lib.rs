@@ -3,6 +3,7 @@
 //! This library provides intelligent routing between multiple local LLM endpoints
 //! based on task complexity, importance, and resource availability.
 
+pub mod build_info;
 pub mod cli;
 pub mod config;
 pub mod error;
@@ -10,6 +11,8 @@ pub mod handlers;
 pub mod metrics;
 pub mod middleware;
 pub mod models;
+pub mod reload;
 pub mod router;
+pub mod server;
 pub mod shared;
 pub mod telemetry;
@@ -2,20 +2,16 @@
 //!
 //! Starts an Axum web server that routes LLM requests to optimal model endpoints.
 
-use axum::{
-    Router, middleware,
-    routing::{get, post},
-};
 use clap::Parser;
 use octoroute::{
     cli::{Cli, Command, generate_config_template},
     config::Config,
     error::AppError,
-    handlers::{self, AppState},
-    middleware::request_id_middleware,
-    telemetry,
+    handlers::AppState,
+    reload::ReloadableState,
+    server, telemetry,
 };
-use std::net::SocketAddr;
+use std::future::IntoFuture;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -30,8 +26,32 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
+    let config_override = cli.config_override.as_deref();
+    if cli.check {
+        check_config(&cli.config, config_override).await;
+    }
+
     // No subcommand - start the server
-    run_server(&cli.config).await
+    run_server(&cli.config, config_override, cli.log_level.as_deref()).await
+}
+
+/// Handle `--check` - validate the config, report the result, and exit
+///
+/// Prints the effective traffic share report and "OK", exiting 0, when the config
+/// loads and the application state can be built; otherwise prints the error to
+/// stderr and exits 1. Never binds a port.
+async fn check_config(config_path: &str, config_override: Option<&str>) -> ! {
+    match octoroute::cli::check_config(config_path, config_override).await {
+        Ok(report) => {
+            print!("{}", report);
+            println!("OK");
+            std::process::exit(0);
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
 }
 
 /// Handle the `config` subcommand - generate template configuration
@@ -101,12 +121,20 @@ fn handle_config_command(output: Option<String>) -> Result<(), AppError> {
 }
 
 /// Run the Octoroute server
-async fn run_server(config_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+///
+/// `config_override` is the `--config-override` file, merged over the config
+/// at startup and on every reload. `log_level` is the `--log-level` flag, which
+/// takes precedence over RUST_LOG and `observability.log_level`.
+async fn run_server(
+    config_path: &str,
+    config_override: Option<&str>,
+    log_level: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
     // Load configuration
-    let config = Config::from_file(config_path)?;
+    let config = Config::load(config_path, config_override.map(std::path::Path::new))?;
 
     // Initialize telemetry
-    telemetry::init(&config.observability.log_level);
+    telemetry::init(log_level, &config.observability.log_level);
 
     tracing::info!(
         "Starting Octoroute server on {}:{}",
@@ -121,63 +149,153 @@ async fn run_server(config_path: &str) -> Result<(), Box<dyn std::error::Error>>
     // Create application state (fails if router construction fails)
     let state = AppState::new(config.clone())?;
 
-    // Clone state for shutdown handler (state is moved to router)
+    // Optional startup probing before the listener is bound. Requiring healthy
+    // tiers probes every endpoint too, so it takes the place of warmup.
+    if config.health.require_healthy_at_startup {
+        state.require_healthy_tiers().await?;
+    } else if config.server.warmup {
+        let report = state.selector().health_checker().warmup().await;
+        if report.unreachable.is_empty() {
+            tracing::info!(
+                reachable = report.reachable.len(),
+                "Warmup complete: all endpoints reachable"
+            );
+        } else {
+            tracing::warn!(
+                reachable = report.reachable.len(),
+                unreachable = ?report.unreachable,
+                "Warmup complete: some endpoints unreachable, continuing startup"
+            );
+        }
+    }
+
+    // Both listeners serve the same reloadable state; SIGHUP and POST /admin/reload swap it
+    let state = ReloadableState::new(state, config_path).with_config_override(config_override);
     let shutdown_state = state.clone();
+    spawn_reload_on_sighup(state.clone());
 
-    // Build router with state and middleware
-    let app = Router::new()
-        // Legacy endpoints
-        .route("/health", get(handlers::health::handler))
-        .route("/chat", post(handlers::chat::handler))
-        .route("/models", get(handlers::models::handler))
-        .route("/metrics", get(handlers::metrics::handler))
-        // OpenAI-compatible endpoints
-        .route(
-            "/v1/chat/completions",
-            post(handlers::openai::completions::handler),
-        )
-        .route("/v1/models", get(handlers::openai::models::handler))
-        .with_state(state)
-        .layer(middleware::from_fn(request_id_middleware));
-
-    // Create socket address
-    let ip_addr = config
-        .server
-        .host
-        .parse::<std::net::IpAddr>()
-        .map_err(|e| {
-            format!(
-                "Invalid IP address '{}' in config: {}. Expected format: 0.0.0.0 or 127.0.0.1",
-                config.server.host, e
-            )
-        })?;
-
-    let addr = SocketAddr::from((ip_addr, config.server.port));
-
-    tracing::info!("Listening on {}", addr);
-    tracing::info!("Health check available at http://{}/health", addr);
+    // Build the public router, and the internal one when metrics get their own listener
+    let app = server::public_app(state.clone());
+    let metrics_app = config
+        .observability
+        .metrics_bind_addr()
+        .map(|addr| (addr, server::metrics_app(state)));
+
+    // Public listen addresses (validated at config load); the first one is
+    // used in the startup log lines below
+    let addrs = config.server.listen_addrs()?;
+    let addr = addrs[0];
+    let diagnostics_addr = metrics_app.as_ref().map_or(addr, |(addr, _)| *addr);
+    tracing::info!(
+        "Health check available at http://{}/health",
+        diagnostics_addr
+    );
+    tracing::info!(
+        "Liveness/readiness probes at http://{}/livez and /readyz",
+        addr
+    );
     tracing::info!("Legacy chat endpoint at http://{}/chat", addr);
     tracing::info!("Legacy models status at http://{}/models", addr);
-    tracing::info!("Metrics endpoint at http://{}/metrics", addr);
+    tracing::info!("Metrics endpoint at http://{}/metrics", diagnostics_addr);
+    tracing::info!("Version info at http://{}/version", addr);
     tracing::info!("OpenAI-compatible endpoints:");
     tracing::info!("  POST http://{}/v1/chat/completions", addr);
     tracing::info!("  GET  http://{}/v1/models", addr);
+    if config.server.admin_token.is_some() {
+        tracing::info!(
+            "Admin endpoints at http://{}/admin/endpoints/{{name}}/drain and /undrain",
+            addr
+        );
+        tracing::info!(
+            "Server drain at http://{}/admin/drain and /admin/undrain",
+            addr
+        );
+        tracing::info!("Config reload at http://{}/admin/reload", addr);
+    }
+
+    // The internal listener is bound before the public one, so a port clash fails
+    // startup instead of silently leaving /metrics unreachable
+    let metrics_server = match metrics_app {
+        Some((metrics_addr, metrics_app)) => {
+            let listener = tokio::net::TcpListener::bind(metrics_addr).await?;
+            tracing::info!("Internal metrics listener on {}", metrics_addr);
+            Some(tokio::spawn(async move {
+                if let Err(e) = axum::serve(listener, metrics_app).await {
+                    tracing::error!(error = %e, "Internal metrics listener failed");
+                }
+            }))
+        }
+        None => None,
+    };
 
-    // Start server with graceful shutdown
-    let listener = tokio::net::TcpListener::bind(addr).await?;
-    axum::serve(listener, app)
-        .with_graceful_shutdown(shutdown_signal(shutdown_state))
-        .await?;
+    // Bind every public address before serving any, so one bad address fails startup
+    let mut listeners = Vec::with_capacity(addrs.len());
+    for addr in &addrs {
+        listeners.push(
+            server::bind_listener(*addr)
+                .map_err(|e| format!("Failed to bind public listener on {}: {}", addr, e))?,
+        );
+        tracing::info!("Listening on {}", addr);
+    }
+
+    // One shutdown signal stops every listener gracefully
+    let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+    tokio::spawn(async move {
+        shutdown_signal(shutdown_state).await;
+        let _ = shutdown_tx.send(true);
+    });
+    let servers = listeners.into_iter().map(|listener| {
+        let mut shutdown_rx = shutdown_rx.clone();
+        axum::serve(listener, app.clone())
+            .with_graceful_shutdown(async move {
+                let _ = shutdown_rx.wait_for(|stop| *stop).await;
+            })
+            .into_future()
+    });
+    futures::future::try_join_all(servers).await?;
+
+    // Metrics scrapes have nothing to finish, so the internal listener just stops
+    if let Some(metrics_server) = metrics_server {
+        metrics_server.abort();
+    }
 
     tracing::info!("Server shutdown complete");
 
     Ok(())
 }
 
+/// Reload the config file on every SIGHUP
+///
+/// A failed reload is logged and the server keeps its current config. Not
+/// available on non-Unix platforms; use `POST /admin/reload` there.
+fn spawn_reload_on_sighup(state: ReloadableState) {
+    #[cfg(unix)]
+    tokio::spawn(async move {
+        use tokio::signal::unix::{SignalKind, signal};
+
+        let mut hangup = match signal(SignalKind::hangup()) {
+            Ok(hangup) => hangup,
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to install SIGHUP handler, config reload via signal disabled");
+                return;
+            }
+        };
+        while hangup.recv().await.is_some() {
+            tracing::info!("Received SIGHUP, reloading configuration");
+            if let Err(e) = state.reload().await {
+                tracing::error!(error = %e, "Config reload failed, keeping current config");
+            }
+        }
+    });
+
+    #[cfg(not(unix))]
+    drop(state);
+}
+
 /// Wait for SIGTERM or SIGINT signal for graceful shutdown
 ///
 /// Cancels background health checking task when shutdown signal is received.
-async fn shutdown_signal(state: AppState) {
+async fn shutdown_signal(state: ReloadableState) {
     use tokio::signal;
 
     let ctrl_c = async {
@@ -206,6 +324,6 @@ async fn shutdown_signal(state: AppState) {
         },
     }
 
-    // Cancel background health checking task
-    state.selector().health_checker().shutdown().await;
+    // Cancel background health checking task of whichever state is current
+    state.current().selector().health_checker().shutdown().await;
 }
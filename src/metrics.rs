@@ -4,6 +4,7 @@
 //! - Request counts by tier and routing strategy
 //! - Routing decision latency
 //! - Model invocations by tier
+//! - Success and failure counts per endpoint
 //!
 //! Metrics are exposed via the `/metrics` endpoint in Prometheus text format.
 //!
@@ -15,12 +16,29 @@
 //! octoroute = { version = "1.0", features = ["metrics"] }
 //! ```
 
+use crate::middleware::RequestId;
 use prometheus::{
-    CounterVec, Encoder, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, Opts, Registry,
-    TextEncoder,
+    CounterVec, Encoder, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGaugeVec, Opts,
+    Registry, TextEncoder, proto::MetricType,
 };
+use serde::Serialize;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
+/// One exported metric value, equivalent to a single line of Prometheus text output
+///
+/// Histograms expand into `_bucket` (with an `le` label), `_sum`, and `_count`
+/// samples, exactly as the text format does.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MetricSample {
+    /// Sample name (metric family name, plus a suffix for histogram parts)
+    pub name: String,
+    /// Label names and values, sorted by name
+    pub labels: BTreeMap<String, String>,
+    /// Current value
+    pub value: f64,
+}
+
 /// Model tier enum for type-safe metrics labels
 ///
 /// Prevents cardinality explosion by restricting tier values to
@@ -46,6 +64,37 @@ impl Tier {
     }
 }
 
+impl From<crate::router::TargetModel> for Tier {
+    fn from(target: crate::router::TargetModel) -> Self {
+        match target {
+            crate::router::TargetModel::Fast => Tier::Fast,
+            crate::router::TargetModel::Balanced => Tier::Balanced,
+            crate::router::TargetModel::Deep => Tier::Deep,
+        }
+    }
+}
+
+/// Outcome of one upstream attempt against an endpoint
+///
+/// Label values for `octoroute_endpoint_requests_total`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndpointOutcome {
+    /// The endpoint answered (for streams: the stream completed)
+    Success,
+    /// The attempt failed (error status, timeout, connection or stream error)
+    Failure,
+}
+
+impl EndpointOutcome {
+    /// Convert outcome to Prometheus label string
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EndpointOutcome::Success => "success",
+            EndpointOutcome::Failure => "failure",
+        }
+    }
+}
+
 /// Routing strategy enum for type-safe metrics labels
 ///
 /// Prevents cardinality explosion by restricting strategy values to
@@ -99,13 +148,24 @@ impl Strategy {
 pub struct Metrics {
     pub registry: Arc<Registry>,
     requests_total: CounterVec,
+    routing_decisions: IntCounterVec,
     routing_duration: HistogramVec,
+    router_llm_duration: HistogramVec,
     model_invocations: CounterVec,
     health_tracking_failures: IntCounterVec,
+    health_failures: IntCounterVec,
     metrics_recording_failures: IntCounterVec,
     background_task_failures: IntCounterVec,
     clock_errors: IntCounter,
     mid_stream_failures: IntCounterVec,
+    client_disconnects: IntCounterVec,
+    tier_fallbacks: IntCounterVec,
+    router_unparseable_fallbacks: IntCounterVec,
+    router_tier_exhaustions: IntCounterVec,
+    request_cost: CounterVec,
+    endpoint_requests: IntCounterVec,
+    empty_completions: IntCounterVec,
+    route_token_estimate: HistogramVec,
 }
 
 impl Metrics {
@@ -139,6 +199,26 @@ impl Metrics {
             &["tier", "strategy"],
         )?;
 
+        // Counter: Routing decisions by the tier the router chose
+        //
+        // Recorded only when a router actually decides (auto-routed requests),
+        // never for explicit tier/model requests or sticky session replays, so the
+        // tier mix reflects router behavior alone. A sudden shift (e.g. an updated
+        // router model sending everything to Deep) shows up as drift here even
+        // while requests_total is dominated by explicit-tier traffic.
+        //
+        // Hybrid is suppressed like requests_total: the concrete path (rule or
+        // llm) is recorded instead.
+        //
+        // Cardinality: 3 tiers × 2 strategies (Rule, Llm) = 6 time series
+        let routing_decisions = IntCounterVec::new(
+            Opts::new(
+                "octoroute_routing_decisions_total",
+                "Total routing decisions by target tier and routing strategy",
+            ),
+            &["target_tier", "strategy"],
+        )?;
+
         // Histogram: Routing decision latency by strategy
         let routing_duration = HistogramVec::new(
             HistogramOpts::new(
@@ -149,6 +229,23 @@ impl Metrics {
             &["strategy"],
         )?;
 
+        // Histogram: LLM router query latency by the tier making the decision
+        //
+        // Recorded around each router query attempt (success or failure), so slow
+        // router tiers stand out separately from the rule fast path.
+        //
+        // Cardinality: 3 tiers = 3 time series
+        let router_llm_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "octoroute_router_llm_duration_ms",
+                "LLM router query latency in milliseconds by router tier",
+            )
+            .buckets(vec![
+                10.0, 50.0, 100.0, 250.0, 500.0, 1000.0, 2500.0, 5000.0, 10000.0, 30000.0,
+            ]),
+            &["tier"],
+        )?;
+
         // Counter: Model invocations by tier
         let model_invocations = CounterVec::new(
             Opts::new(
@@ -173,6 +270,25 @@ impl Metrics {
             &["endpoint", "error_type"],
         )?;
 
+        // Counter: Endpoint failures (health checks and requests) by kind
+        //
+        // Unlike health_tracking_failures, this counts the endpoints themselves
+        // failing, so "backend down" (connection) can be told apart from
+        // "backend returning 500s" (http_status).
+        //
+        // Labels:
+        // - endpoint: Endpoint that failed (e.g., "fast-1")
+        // - kind: connection, timeout, http_status, or parse
+        //
+        // Cardinality: N endpoints × 4 kinds = 4N time series (bounded by endpoint count)
+        let health_failures = IntCounterVec::new(
+            Opts::new(
+                "octoroute_health_failures_total",
+                "Total number of endpoint failures seen by health checks and requests, by endpoint and failure kind",
+            ),
+            &["endpoint", "kind"],
+        )?;
+
         // Counter: Metrics recording operation failures with operation label
         //
         // Labels:
@@ -258,26 +374,233 @@ impl Metrics {
             &["endpoint"],
         )?;
 
+        // Counter: Streaming responses abandoned by the client before completion
+        //
+        // Incremented when the SSE response body is dropped (client closed the
+        // connection) before the stream finished. The upstream query is aborted at
+        // that point, so a high rate means backend work is being started and thrown
+        // away - often a sign that clients time out before first token.
+        //
+        // Labels:
+        // - endpoint: Which endpoint was serving the abandoned stream
+        //
+        // Cardinality: N endpoints = N time series (bounded by endpoint count)
+        let client_disconnects = IntCounterVec::new(
+            Opts::new(
+                "octoroute_client_disconnects_total",
+                "Total number of streaming responses abandoned by the client before completion, \
+                by endpoint. The upstream query is aborted when this happens.",
+            ),
+            &["endpoint"],
+        )?;
+
+        // Counter: Requests served by a lower tier than the one routed to
+        //
+        // Incremented when cross-tier fallback (`routing.tier_fallback`) serves a
+        // request from a different tier because the routed tier had no available
+        // endpoints. Sustained increments mean the requested tier lacks capacity
+        // (e.g., Deep endpoints down and traffic degrading to Balanced).
+        //
+        // Labels:
+        // - requested_tier: Tier chosen by routing
+        // - served_tier: Tier that actually served the request
+        //
+        // Cardinality: at most 3 pairs (deep->balanced, deep->fast, balanced->fast)
+        let tier_fallbacks = IntCounterVec::new(
+            Opts::new(
+                "octoroute_tier_fallback_total",
+                "Total number of requests served by a lower tier than routed, \
+                by requested and served tier. Indicates capacity problems in the requested tier.",
+            ),
+            &["requested_tier", "served_tier"],
+        )?;
+
+        // Counter: Unparseable router answers routed via `routing.on_unparseable`
+        //
+        // Each increment is a routing decision the router model didn't actually
+        // make. A steady rate points at a router model or prompt that no longer
+        // follows the one-word instruction.
+        //
+        // Labels:
+        // - tier: Tier the request was routed to instead
+        //
+        // Cardinality: at most 3 time series
+        let router_unparseable_fallbacks = IntCounterVec::new(
+            Opts::new(
+                "octoroute_router_unparseable_fallback_total",
+                "Total number of unparseable LLM router answers replaced by the \
+                routing.on_unparseable fallback tier, by fallback tier.",
+            ),
+            &["tier"],
+        )?;
+
+        // Counter: Router attempts that found no router tier endpoint to query
+        //
+        // Counted once per failed selection, so a request retrying against an
+        // unavailable router tier adds one per attempt. Kept apart from serving
+        // tier failures so router infrastructure problems stand out.
+        //
+        // Labels:
+        // - tier: The routing.router_tier
+        // - reason: no_endpoints, all_unhealthy, all_excluded (every endpoint
+        //   already failed this request) or mixed
+        //
+        // Cardinality: at most 12 time series (3 tiers x 4 reasons)
+        let router_tier_exhaustions = IntCounterVec::new(
+            Opts::new(
+                "octoroute_router_tier_exhaustion_total",
+                "Total number of LLM router attempts that found no router tier \
+                endpoint to query, by router tier and reason.",
+            ),
+            &["tier", "reason"],
+        )?;
+
+        // Counter: Estimated cost of completed requests (`cost_per_1k_tokens`)
+        //
+        // Summed in the operator's own currency unit from estimated token usage,
+        // so it tracks relative spend rather than an exact bill. Requests served
+        // by endpoints without a rate add nothing.
+        //
+        // Labels:
+        // - tier: Tier whose endpoint served the request
+        //
+        // Cardinality: at most 3 time series
+        let request_cost = CounterVec::new(
+            Opts::new(
+                "octoroute_request_cost_total",
+                "Estimated cost of completed requests from token usage and the serving \
+                endpoint's cost_per_1k_tokens, by tier.",
+            ),
+            &["tier"],
+        )?;
+
+        // Counter: Upstream attempts per endpoint by outcome
+        //
+        // `model_invocations` counts successes per tier, which hides a single bad
+        // endpoint among healthy siblings. This counts every completion attempt
+        // (retries and streaming failover included) and every router query, so a
+        // per-endpoint failure ratio shows which endpoint is misbehaving.
+        //
+        // Labels:
+        // - endpoint: Endpoint name from configuration
+        // - outcome: success | failure
+        //
+        // Cardinality: N endpoints × 2 outcomes (bounded by endpoint count)
+        let endpoint_requests = IntCounterVec::new(
+            Opts::new(
+                "octoroute_endpoint_requests_total",
+                "Total number of upstream attempts by endpoint and outcome (success/failure), \
+                including retries, streaming failover and LLM router queries.",
+            ),
+            &["endpoint", "outcome"],
+        )?;
+
+        // Counter: Completions that came back without any content
+        //
+        // Counted whatever `server.on_empty_completion` then does with them. A
+        // backend that keeps answering with nothing is usually misconfigured
+        // (wrong chat template, exhausted context) rather than down, so health
+        // checks alone won't point at it.
+        //
+        // Labels:
+        // - endpoint: Endpoint that returned the empty completion
+        //
+        // Cardinality: N endpoints = N time series (bounded by endpoint count)
+        let empty_completions = IntCounterVec::new(
+            Opts::new(
+                "octoroute_empty_completions_total",
+                "Total number of completions (streaming or not) returned without any content, \
+                by endpoint.",
+            ),
+            &["endpoint"],
+        )?;
+
+        // Histogram: Token estimate routing decided on
+        //
+        // Observed once per routed request, after capping (see
+        // `routing.token_estimate_cap`), so it shows the sizes actually driving
+        // routing. Buckets include the rule router's 256/1024/2048 thresholds.
+        //
+        // Labels:
+        // - capped: "true" when the prompt's own estimate was above the cap
+        //
+        // Cardinality: 2 time series
+        let route_token_estimate = HistogramVec::new(
+            HistogramOpts::new(
+                "octoroute_route_token_estimate",
+                "Prompt token estimate used for routing decisions, after capping",
+            )
+            .buckets(vec![
+                64.0, 256.0, 1024.0, 2048.0, 4096.0, 8192.0, 16384.0, 32768.0, 65536.0, 131072.0,
+            ]),
+            &["capped"],
+        )?;
+
+        // Gauge: Build metadata of the running binary (value is always 1)
+        //
+        // Follows the Prometheus `*_build_info` convention: the information lives in
+        // the labels, so it can be joined onto other series, e.g.
+        // `rate(octoroute_requests_total[5m]) * on() group_left(version) octoroute_build_info`.
+        //
+        // Cardinality: 1 time series (labels are fixed at compile time)
+        let build_info = IntGaugeVec::new(
+            Opts::new(
+                "octoroute_build_info",
+                "Build information for the running Octoroute binary. Always 1; see labels.",
+            ),
+            &["version", "git_sha", "rustc"],
+        )?;
+        build_info
+            .with_label_values(&[
+                crate::build_info::VERSION,
+                crate::build_info::GIT_SHA,
+                crate::build_info::RUSTC_VERSION,
+            ])
+            .set(1);
+
         // Register all metrics
         registry.register(Box::new(requests_total.clone()))?;
+        registry.register(Box::new(routing_decisions.clone()))?;
         registry.register(Box::new(routing_duration.clone()))?;
+        registry.register(Box::new(router_llm_duration.clone()))?;
         registry.register(Box::new(model_invocations.clone()))?;
         registry.register(Box::new(health_tracking_failures.clone()))?;
+        registry.register(Box::new(health_failures.clone()))?;
         registry.register(Box::new(metrics_recording_failures.clone()))?;
         registry.register(Box::new(background_task_failures.clone()))?;
         registry.register(Box::new(clock_errors.clone()))?;
         registry.register(Box::new(mid_stream_failures.clone()))?;
+        registry.register(Box::new(client_disconnects.clone()))?;
+        registry.register(Box::new(tier_fallbacks.clone()))?;
+        registry.register(Box::new(router_unparseable_fallbacks.clone()))?;
+        registry.register(Box::new(router_tier_exhaustions.clone()))?;
+        registry.register(Box::new(request_cost.clone()))?;
+        registry.register(Box::new(endpoint_requests.clone()))?;
+        registry.register(Box::new(empty_completions.clone()))?;
+        registry.register(Box::new(route_token_estimate.clone()))?;
+        registry.register(Box::new(build_info))?;
 
         Ok(Self {
             registry: Arc::new(registry),
             requests_total,
+            routing_decisions,
             routing_duration,
+            router_llm_duration,
             model_invocations,
             health_tracking_failures,
+            health_failures,
             metrics_recording_failures,
             background_task_failures,
             clock_errors,
             mid_stream_failures,
+            client_disconnects,
+            tier_fallbacks,
+            router_unparseable_fallbacks,
+            router_tier_exhaustions,
+            request_cost,
+            endpoint_requests,
+            empty_completions,
+            route_token_estimate,
         })
     }
 
@@ -313,6 +636,65 @@ impl Metrics {
         Ok(())
     }
 
+    /// Record a routing decision by the tier the router chose
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the metric is not registered.
+    ///
+    /// # Cardinality Safety
+    ///
+    /// Same as [`Metrics::record_request`]: 3 tiers × 2 strategies, with Hybrid
+    /// never recorded.
+    pub fn record_routing_decision(
+        &self,
+        target_tier: Tier,
+        strategy: Strategy,
+    ) -> Result<(), prometheus::Error> {
+        let Some(strategy_label) = strategy.metric_label() else {
+            tracing::debug!(
+                target_tier = %target_tier.as_str(),
+                strategy = %strategy.as_str(),
+                "Skipping routing decision metric for hybrid meta-strategy"
+            );
+            return Ok(());
+        };
+
+        self.routing_decisions
+            .get_metric_with_label_values(&[target_tier.as_str(), strategy_label])?
+            .inc();
+        Ok(())
+    }
+
+    /// Get the routing decision count for a target tier and strategy
+    ///
+    /// Returns 0 for Hybrid, which is never recorded. Reads a gather rather
+    /// than looking the counter up, which would create a zero series for a
+    /// combination that never happened.
+    pub fn routing_decisions_count(&self, target_tier: Tier, strategy: Strategy) -> u64 {
+        let Some(strategy) = strategy.metric_label() else {
+            return 0;
+        };
+        self.registry
+            .gather()
+            .iter()
+            .find(|mf| mf.name() == "octoroute_routing_decisions_total")
+            .and_then(|mf| {
+                mf.get_metric().iter().find(|m| {
+                    let label = |name: &str| {
+                        m.get_label()
+                            .iter()
+                            .find(|pair| pair.name() == name)
+                            .map(|pair| pair.value().to_string())
+                    };
+                    label("target_tier").as_deref() == Some(target_tier.as_str())
+                        && label("strategy").as_deref() == Some(strategy)
+                })
+            })
+            .map(|m| m.counter.value.unwrap_or(0.0) as u64)
+            .unwrap_or(0)
+    }
+
     /// Record routing decision duration
     ///
     /// # Arguments
@@ -342,22 +724,7 @@ impl Metrics {
         strategy: Strategy,
         duration_ms: f64,
     ) -> Result<(), prometheus::Error> {
-        // Validate duration is finite (not NaN or Infinity)
-        if !duration_ms.is_finite() {
-            return Err(prometheus::Error::Msg(format!(
-                "Histogram value must be finite (not NaN or Infinity), got: {}. \
-                NaN and infinity values corrupt histogram percentiles.",
-                duration_ms
-            )));
-        }
-
-        // Validate duration is non-negative (logically required for durations)
-        if duration_ms < 0.0 {
-            return Err(prometheus::Error::Msg(format!(
-                "Histogram value must be non-negative (duration cannot be negative), got: {}",
-                duration_ms
-            )));
-        }
+        validate_duration(duration_ms)?;
 
         let Some(strategy_label) = strategy.metric_label() else {
             tracing::debug!(
@@ -374,6 +741,38 @@ impl Metrics {
         Ok(())
     }
 
+    /// Record the latency of one LLM router query attempt
+    ///
+    /// # Arguments
+    ///
+    /// * `tier` - The router tier that was queried for the routing decision
+    /// * `duration_ms` - The duration in milliseconds (must be finite and non-negative)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the metric is not registered or `duration_ms` is NaN,
+    /// infinite, or negative (same rules as `record_routing_duration`).
+    pub fn record_router_llm_duration(
+        &self,
+        tier: Tier,
+        duration_ms: f64,
+    ) -> Result<(), prometheus::Error> {
+        validate_duration(duration_ms)?;
+
+        self.router_llm_duration
+            .get_metric_with_label_values(&[tier.as_str()])?
+            .observe(duration_ms);
+        Ok(())
+    }
+
+    /// Get the number of router query latencies recorded for a router tier
+    pub fn router_llm_duration_count(&self, tier: Tier) -> u64 {
+        self.router_llm_duration
+            .get_metric_with_label_values(&[tier.as_str()])
+            .map(|histogram| histogram.get_sample_count())
+            .unwrap_or(0)
+    }
+
     /// Record a model invocation
     ///
     /// # Arguments
@@ -395,6 +794,88 @@ impl Metrics {
         Ok(())
     }
 
+    /// Record a request like [`Metrics::record_request`], absorbing any failure
+    ///
+    /// The `try_record_*` methods are what request paths should call: a failure
+    /// only increments `octoroute_metrics_recording_failures_total` and is
+    /// logged, so there is no error for a handler to propagate into a response.
+    pub fn try_record_request(
+        &self,
+        tier: Tier,
+        strategy: Strategy,
+        request_id: Option<RequestId>,
+    ) {
+        self.absorb_failure(
+            "record_request",
+            self.record_request(tier, strategy),
+            request_id,
+        );
+    }
+
+    /// Record a routing decision, absorbing any failure (see [`Metrics::try_record_request`])
+    pub fn try_record_routing_decision(
+        &self,
+        target_tier: Tier,
+        strategy: Strategy,
+        request_id: Option<RequestId>,
+    ) {
+        self.absorb_failure(
+            "record_routing_decision",
+            self.record_routing_decision(target_tier, strategy),
+            request_id,
+        );
+    }
+
+    /// Record routing duration, absorbing any failure (see [`Metrics::try_record_request`])
+    pub fn try_record_routing_duration(
+        &self,
+        strategy: Strategy,
+        duration_ms: f64,
+        request_id: Option<RequestId>,
+    ) {
+        self.absorb_failure(
+            "record_routing_duration",
+            self.record_routing_duration(strategy, duration_ms),
+            request_id,
+        );
+    }
+
+    /// Record router query latency, absorbing any failure (see [`Metrics::try_record_request`])
+    pub fn try_record_router_llm_duration(&self, tier: Tier, duration_ms: f64) {
+        self.absorb_failure(
+            "record_router_llm_duration",
+            self.record_router_llm_duration(tier, duration_ms),
+            None,
+        );
+    }
+
+    /// Record a model invocation, absorbing any failure (see [`Metrics::try_record_request`])
+    pub fn try_record_model_invocation(&self, tier: Tier, request_id: Option<RequestId>) {
+        self.absorb_failure(
+            "record_model_invocation",
+            self.record_model_invocation(tier),
+            request_id,
+        );
+    }
+
+    /// Count and log a failed recording instead of returning it
+    fn absorb_failure(
+        &self,
+        operation: &'static str,
+        result: Result<(), prometheus::Error>,
+        request_id: Option<RequestId>,
+    ) {
+        if let Err(e) = result {
+            self.metrics_recording_failure(operation);
+            tracing::error!(
+                request_id = request_id.as_ref().map(tracing::field::display),
+                operation,
+                error = %e,
+                "Metrics recording failed. Observability degraded but request continues."
+            );
+        }
+    }
+
     /// Record a health tracking operation failure
     ///
     /// Increments the counter when mark_success() or mark_failure() operations
@@ -469,6 +950,26 @@ impl Metrics {
             .unwrap_or(0)
     }
 
+    /// Record an endpoint failure of the given kind
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - The endpoint that failed
+    /// * `kind` - Failure kind label (see `HealthFailureKind::as_str`)
+    pub fn health_failure(&self, endpoint: &str, kind: &str) {
+        self.health_failures
+            .with_label_values(&[endpoint, kind])
+            .inc();
+    }
+
+    /// Get the failure count for a specific endpoint and kind
+    pub fn health_failures_count(&self, endpoint: &str, kind: &str) -> u64 {
+        self.health_failures
+            .get_metric_with_label_values(&[endpoint, kind])
+            .map(|counter| counter.get())
+            .unwrap_or(0)
+    }
+
     /// Record a metrics recording operation failure
     ///
     /// Increments the counter when record_request(), record_routing_duration(),
@@ -479,8 +980,13 @@ impl Metrics {
     /// This metric increments when metrics recording operations fail, specifically:
     /// - `record_request()` fails (Prometheus registry error, label mismatch)
     /// - `record_routing_duration()` fails (invalid duration, registry error)
+    /// - `record_routing_decision()` fails (registry error)
+    /// - `record_router_llm_duration()` fails (invalid duration, registry error)
     /// - `record_model_invocation()` fails (registry error)
     ///
+    /// Request paths record through the `try_record_*` methods, which count
+    /// failures here instead of returning them.
+    ///
     /// ## Alerting Threshold
     ///
     /// **Recommended alert**: > 5 failures in 1 hour indicates a systemic issue:
@@ -503,6 +1009,8 @@ impl Metrics {
     /// - `operation`: Name of the metric operation that failed - must be one of:
     ///   - "record_request": Request counter recording failed
     ///   - "record_routing_duration": Routing duration histogram recording failed
+    ///   - "record_routing_decision": Routing decision counter recording failed
+    ///   - "record_router_llm_duration": Router query latency histogram recording failed
     ///   - "record_model_invocation": Model invocation counter recording failed
     pub fn metrics_recording_failure(&self, operation: &str) {
         self.metrics_recording_failures
@@ -585,7 +1093,8 @@ impl Metrics {
     /// ## Impact
     ///
     /// When the clock is misconfigured:
-    /// - Response timestamps default to 0 (epoch)
+    /// - Response timestamps are estimated from the process's first good clock
+    ///   reading plus monotonic time elapsed (see `current_timestamp`)
     /// - Downstream systems may malfunction (sorting, caching, validation)
     /// - Logging timestamps may be incorrect
     ///
@@ -626,6 +1135,168 @@ impl Metrics {
             .inc();
     }
 
+    /// Get the mid-stream failure count for a specific endpoint
+    pub fn mid_stream_failures_count(&self, endpoint: &str) -> u64 {
+        self.mid_stream_failures
+            .get_metric_with_label_values(&[endpoint])
+            .map(|counter| counter.get())
+            .unwrap_or(0)
+    }
+
+    /// Record a streaming response abandoned by the client
+    ///
+    /// Call this when the SSE body is dropped before the stream completed
+    /// (the client disconnected). Does not affect endpoint health.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - The endpoint that was serving the stream
+    pub fn client_disconnect(&self, endpoint: &str) {
+        self.client_disconnects.with_label_values(&[endpoint]).inc();
+    }
+
+    /// Get the total count of client disconnects across all endpoints
+    pub fn client_disconnects_count(&self) -> u64 {
+        self.registry
+            .gather()
+            .iter()
+            .find(|mf| mf.name() == "octoroute_client_disconnects_total")
+            .map(|mf| {
+                mf.get_metric()
+                    .iter()
+                    .map(|m| m.counter.value.unwrap_or(0.0) as u64)
+                    .sum()
+            })
+            .unwrap_or(0)
+    }
+
+    /// Record a request served by a different tier than it was routed to
+    ///
+    /// # Arguments
+    ///
+    /// * `requested` - The tier chosen by routing
+    /// * `served` - The tier whose endpoint actually served the request
+    pub fn tier_fallback(&self, requested: Tier, served: Tier) {
+        self.tier_fallbacks
+            .with_label_values(&[requested.as_str(), served.as_str()])
+            .inc();
+    }
+
+    /// Get the tier fallback count for a specific requested/served pair
+    pub fn tier_fallback_count(&self, requested: Tier, served: Tier) -> u64 {
+        self.tier_fallbacks
+            .get_metric_with_label_values(&[requested.as_str(), served.as_str()])
+            .map(|counter| counter.get())
+            .unwrap_or(0)
+    }
+
+    /// Record an unparseable router answer replaced by the `on_unparseable` tier
+    pub fn router_unparseable_fallback(&self, tier: Tier) {
+        self.router_unparseable_fallbacks
+            .with_label_values(&[tier.as_str()])
+            .inc();
+    }
+
+    /// Get the unparseable-answer fallback count for a fallback tier
+    pub fn router_unparseable_fallback_count(&self, tier: Tier) -> u64 {
+        self.router_unparseable_fallbacks
+            .get_metric_with_label_values(&[tier.as_str()])
+            .map(|counter| counter.get())
+            .unwrap_or(0)
+    }
+
+    /// Record a router attempt that found no router tier endpoint to query
+    ///
+    /// `reason` is a [`SelectError::reason`](crate::models::selector::SelectError::reason) label.
+    pub fn router_tier_exhaustion(&self, tier: Tier, reason: &str) {
+        self.router_tier_exhaustions
+            .with_label_values(&[tier.as_str(), reason])
+            .inc();
+    }
+
+    /// Get the router tier exhaustion count for a router tier and reason
+    pub fn router_tier_exhaustion_count(&self, tier: Tier, reason: &str) -> u64 {
+        self.router_tier_exhaustions
+            .get_metric_with_label_values(&[tier.as_str(), reason])
+            .map(|counter| counter.get())
+            .unwrap_or(0)
+    }
+
+    /// Add the estimated cost of a completed request served by `tier`
+    pub fn record_request_cost(&self, tier: Tier, cost: f64) {
+        self.request_cost
+            .with_label_values(&[tier.as_str()])
+            .inc_by(cost);
+    }
+
+    /// Get the accumulated estimated cost for a tier
+    pub fn request_cost_total(&self, tier: Tier) -> f64 {
+        self.request_cost
+            .get_metric_with_label_values(&[tier.as_str()])
+            .map(|counter| counter.get())
+            .unwrap_or(0.0)
+    }
+
+    /// Record the outcome of one upstream attempt against `endpoint`
+    ///
+    /// Endpoint names come from configuration, so cardinality is bounded.
+    pub fn endpoint_request(&self, endpoint: &str, outcome: EndpointOutcome) {
+        self.endpoint_requests
+            .with_label_values(&[endpoint, outcome.as_str()])
+            .inc();
+    }
+
+    /// Get the attempt count for an endpoint and outcome
+    pub fn endpoint_requests_count(&self, endpoint: &str, outcome: EndpointOutcome) -> u64 {
+        self.endpoint_requests
+            .get_metric_with_label_values(&[endpoint, outcome.as_str()])
+            .map(|counter| counter.get())
+            .unwrap_or(0)
+    }
+
+    /// Record a completion from `endpoint` that had no content
+    pub fn empty_completion(&self, endpoint: &str) {
+        self.empty_completions.with_label_values(&[endpoint]).inc();
+    }
+
+    /// Get the empty completion count for a specific endpoint
+    pub fn empty_completions_count(&self, endpoint: &str) -> u64 {
+        self.empty_completions
+            .get_metric_with_label_values(&[endpoint])
+            .map(|counter| counter.get())
+            .unwrap_or(0)
+    }
+
+    /// Record the token estimate a routing decision was made on
+    ///
+    /// `capped` is whether the prompt's own estimate was larger than `estimate`.
+    pub fn route_token_estimate(&self, estimate: usize, capped: bool) {
+        self.route_token_estimate
+            .with_label_values(&[if capped { "true" } else { "false" }])
+            .observe(estimate as f64);
+    }
+
+    /// Get the number of routing token estimates recorded, and their sum
+    pub fn route_token_estimate_stats(&self, capped: bool) -> (u64, f64) {
+        self.route_token_estimate
+            .get_metric_with_label_values(&[if capped { "true" } else { "false" }])
+            .map(|histogram| (histogram.get_sample_count(), histogram.get_sample_sum()))
+            .unwrap_or((0, 0.0))
+    }
+
+    /// Gather all metrics as flat name/labels/value samples
+    ///
+    /// Used by the `/metrics` JSON and CSV exports for consumers that don't
+    /// speak the Prometheus text format. Samples are ordered by metric family
+    /// name, as returned by the registry.
+    pub fn samples(&self) -> Vec<MetricSample> {
+        self.registry
+            .gather()
+            .iter()
+            .flat_map(family_samples)
+            .collect()
+    }
+
     /// Gather all metrics and encode them in Prometheus text format
     ///
     /// # Returns
@@ -685,6 +1356,99 @@ impl Metrics {
     }
 }
 
+/// Reject durations that would corrupt histogram statistics
+///
+/// NaN and infinity make every percentile NaN; negative values are logically
+/// invalid for durations.
+fn validate_duration(duration_ms: f64) -> Result<(), prometheus::Error> {
+    // Validate duration is finite (not NaN or Infinity)
+    if !duration_ms.is_finite() {
+        return Err(prometheus::Error::Msg(format!(
+            "Histogram value must be finite (not NaN or Infinity), got: {}. \
+            NaN and infinity values corrupt histogram percentiles.",
+            duration_ms
+        )));
+    }
+
+    // Validate duration is non-negative (logically required for durations)
+    if duration_ms < 0.0 {
+        return Err(prometheus::Error::Msg(format!(
+            "Histogram value must be non-negative (duration cannot be negative), got: {}",
+            duration_ms
+        )));
+    }
+
+    Ok(())
+}
+
+/// Flatten one metric family into samples (see [`Metrics::samples`])
+fn family_samples(family: &prometheus::proto::MetricFamily) -> Vec<MetricSample> {
+    let name = family.name();
+    let mut samples = Vec::new();
+
+    for metric in family.get_metric() {
+        let labels: BTreeMap<String, String> = metric
+            .get_label()
+            .iter()
+            .map(|pair| (pair.name().to_string(), pair.value().to_string()))
+            .collect();
+        let sample = |suffix: &str, labels: BTreeMap<String, String>, value: f64| MetricSample {
+            name: format!("{}{}", name, suffix),
+            labels,
+            value,
+        };
+
+        match family.get_field_type() {
+            MetricType::COUNTER => {
+                samples.push(sample("", labels, metric.counter.value.unwrap_or(0.0)));
+            }
+            MetricType::GAUGE => {
+                samples.push(sample("", labels, metric.gauge.value.unwrap_or(0.0)));
+            }
+            MetricType::HISTOGRAM => {
+                let histogram = &metric.histogram;
+                let bucket = |le: String, count: u64| {
+                    let mut bucket_labels = labels.clone();
+                    bucket_labels.insert("le".to_string(), le);
+                    sample("_bucket", bucket_labels, count as f64)
+                };
+
+                // The +Inf bucket is implicit in the registry, as in the text encoder
+                let mut inf_seen = false;
+                for b in histogram.get_bucket() {
+                    let upper_bound = b.upper_bound();
+                    if upper_bound.is_sign_positive() && upper_bound.is_infinite() {
+                        inf_seen = true;
+                        samples.push(bucket("+Inf".to_string(), b.cumulative_count()));
+                    } else {
+                        samples.push(bucket(upper_bound.to_string(), b.cumulative_count()));
+                    }
+                }
+                if !inf_seen {
+                    samples.push(bucket("+Inf".to_string(), histogram.get_sample_count()));
+                }
+
+                samples.push(sample("_sum", labels.clone(), histogram.get_sample_sum()));
+                samples.push(sample(
+                    "_count",
+                    labels,
+                    histogram.get_sample_count() as f64,
+                ));
+            }
+            other => {
+                // Octoroute registers only counters, gauges, and histograms
+                tracing::debug!(
+                    metric = name,
+                    metric_type = ?other,
+                    "Skipping unsupported metric type in sample export"
+                );
+            }
+        }
+    }
+
+    samples
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -707,9 +1471,9 @@ mod tests {
         metrics.metrics_recording_failure("record_request"); // Increment metrics recording failures with test label
 
         let metric_families = metrics.registry.gather();
-        // Should have 6 metric families: requests_total, routing_duration, model_invocations,
-        // health_tracking_failures, metrics_recording_failures, clock_errors
-        assert_eq!(metric_families.len(), 6, "Expected 6 metric families");
+        // Should have 7 metric families: requests_total, routing_duration, model_invocations,
+        // health_tracking_failures, metrics_recording_failures, clock_errors, build_info
+        assert_eq!(metric_families.len(), 7, "Expected 7 metric families");
 
         // Verify metric names
         let names: Vec<String> = metric_families
@@ -722,6 +1486,133 @@ mod tests {
         assert!(names.contains(&"octoroute_health_tracking_failures_total".to_string()));
         assert!(names.contains(&"octoroute_metrics_recording_failures_total".to_string()));
         assert!(names.contains(&"octoroute_clock_errors_total".to_string()));
+        assert!(names.contains(&"octoroute_build_info".to_string()));
+    }
+
+    #[test]
+    fn test_routing_decisions_count_per_tier_and_strategy() {
+        let metrics = Metrics::new().expect("Failed to create test metrics");
+
+        for (tier, strategy) in [
+            (Tier::Fast, Strategy::Rule),
+            (Tier::Fast, Strategy::Rule),
+            (Tier::Deep, Strategy::Llm),
+            (Tier::Balanced, Strategy::Rule),
+            (Tier::Deep, Strategy::Llm),
+            (Tier::Deep, Strategy::Llm),
+        ] {
+            metrics
+                .record_routing_decision(tier, strategy)
+                .expect("Test operation should succeed");
+        }
+
+        assert_eq!(
+            metrics.routing_decisions_count(Tier::Fast, Strategy::Rule),
+            2
+        );
+        assert_eq!(
+            metrics.routing_decisions_count(Tier::Balanced, Strategy::Rule),
+            1
+        );
+        assert_eq!(
+            metrics.routing_decisions_count(Tier::Deep, Strategy::Llm),
+            3
+        );
+        assert_eq!(
+            metrics.routing_decisions_count(Tier::Deep, Strategy::Rule),
+            0
+        );
+
+        let output = metrics.gather().expect("Failed to gather test metrics");
+        assert!(output.lines().any(|line| {
+            line.starts_with("octoroute_routing_decisions_total{")
+                && line.contains("target_tier=\"deep\"")
+                && line.contains("strategy=\"llm\"")
+                && line.ends_with(" 3")
+        }));
+    }
+
+    #[test]
+    fn test_routing_decisions_suppress_hybrid() {
+        let metrics = Metrics::new().expect("Failed to create test metrics");
+
+        metrics
+            .record_routing_decision(Tier::Fast, Strategy::Hybrid)
+            .expect("Hybrid should be skipped, not fail");
+
+        assert_eq!(
+            metrics.routing_decisions_count(Tier::Fast, Strategy::Hybrid),
+            0
+        );
+        let output = metrics.gather().expect("Failed to gather test metrics");
+        assert!(!output.contains("octoroute_routing_decisions_total{"));
+    }
+
+    #[test]
+    fn test_build_info_gauge_is_present_with_version_label() {
+        let metrics = Metrics::new().expect("Failed to create test metrics");
+
+        let output = metrics.gather().expect("Failed to gather test metrics");
+        let line = output
+            .lines()
+            .find(|l| l.starts_with("octoroute_build_info{"))
+            .expect("build_info should be exported without any recording");
+
+        assert!(
+            line.contains(&format!("version=\"{}\"", crate::build_info::VERSION)),
+            "build_info should carry the crate version, got: {}",
+            line
+        );
+        assert!(line.contains("git_sha=\""));
+        assert!(line.contains("rustc=\""));
+        assert!(
+            line.ends_with(" 1"),
+            "build_info value should be 1, got: {}",
+            line
+        );
+    }
+
+    #[test]
+    fn test_samples_match_recorded_values() {
+        let metrics = Metrics::new().expect("Failed to create test metrics");
+        metrics
+            .record_request(Tier::Fast, Strategy::Rule)
+            .expect("Test operation should succeed");
+        metrics
+            .record_request(Tier::Fast, Strategy::Rule)
+            .expect("Test operation should succeed");
+        metrics
+            .record_routing_duration(Strategy::Rule, 1.5)
+            .expect("Test operation should succeed");
+
+        let samples = metrics.samples();
+
+        let requests = samples
+            .iter()
+            .find(|s| s.name == "octoroute_requests_total")
+            .expect("requests counter should be exported");
+        assert_eq!(requests.labels["tier"], "fast");
+        assert_eq!(requests.labels["strategy"], "rule");
+        assert_eq!(requests.value, 2.0);
+
+        let count = samples
+            .iter()
+            .find(|s| s.name == "octoroute_routing_duration_ms_count")
+            .expect("histogram count should be exported");
+        assert_eq!(count.value, 1.0);
+        let sum = samples
+            .iter()
+            .find(|s| s.name == "octoroute_routing_duration_ms_sum")
+            .expect("histogram sum should be exported");
+        assert_eq!(sum.value, 1.5);
+        assert!(
+            samples
+                .iter()
+                .any(|s| s.name == "octoroute_routing_duration_ms_bucket"
+                    && s.labels.get("le").map(String::as_str) == Some("+Inf")
+                    && s.value == 1.0),
+            "histogram should include the +Inf bucket"
+        );
     }
 
     #[test]
@@ -744,6 +1635,30 @@ mod tests {
         assert!(output.contains("strategy=\"rule\""));
     }
 
+    #[test]
+    fn test_record_router_llm_duration_is_labeled_by_tier() {
+        let metrics = Metrics::new().expect("Failed to create metrics");
+        metrics
+            .record_router_llm_duration(Tier::Balanced, 420.0)
+            .expect("Test operation should succeed");
+
+        assert_eq!(metrics.router_llm_duration_count(Tier::Balanced), 1);
+        assert_eq!(metrics.router_llm_duration_count(Tier::Fast), 0);
+        let output = metrics.gather().expect("Failed to gather metrics");
+        assert!(output.contains("octoroute_router_llm_duration_ms_count{tier=\"balanced\"} 1"));
+
+        assert!(
+            metrics
+                .record_router_llm_duration(Tier::Fast, f64::NAN)
+                .is_err()
+        );
+        assert!(
+            metrics
+                .record_router_llm_duration(Tier::Fast, -1.0)
+                .is_err()
+        );
+    }
+
     #[test]
     fn test_record_routing_duration_observes_histogram() {
         let metrics = Metrics::new().expect("Failed to create test metrics");
@@ -1330,6 +2245,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_try_record_counts_failures_instead_of_returning_them() {
+        let metrics = Metrics::new().expect("Failed to create test metrics");
+
+        metrics.try_record_routing_duration(Strategy::Rule, f64::NAN, Some(RequestId::new()));
+        metrics.try_record_router_llm_duration(Tier::Balanced, -1.0);
+        assert_eq!(metrics.metrics_recording_failures_count(), 2);
+        assert_eq!(metrics.router_llm_duration_count(Tier::Balanced), 0);
+
+        // Successful recordings are not counted as failures
+        metrics.try_record_request(Tier::Fast, Strategy::Rule, None);
+        metrics.try_record_routing_decision(Tier::Fast, Strategy::Llm, None);
+        metrics.try_record_model_invocation(Tier::Fast, None);
+        assert_eq!(metrics.metrics_recording_failures_count(), 2);
+        assert_eq!(
+            metrics.routing_decisions_count(Tier::Fast, Strategy::Llm),
+            1
+        );
+    }
+
     // ===== GAP #2 Fix: High-Concurrency Stress Test (1000+ tasks) =====
 
     #[test]
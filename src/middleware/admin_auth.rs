@@ -0,0 +1,71 @@
+//! Authentication for the `/admin` API
+//!
+//! Admin requests must carry `Authorization: Bearer <server.admin_token>`.
+//! Anything else is rejected with 401 before the handler runs. The routes are
+//! only mounted when a token is configured, so an unset token also denies.
+
+use crate::error::AppError;
+use crate::handlers::AppState;
+use crate::middleware::RequestId;
+use axum::{
+    extract::{Request, State},
+    http::header,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+/// Middleware that admits only requests bearing the configured admin token
+pub async fn admin_auth_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let presented = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    let authorized = match (state.config().server.admin_token.as_deref(), presented) {
+        (Some(expected), Some(presented)) => tokens_match(expected, presented),
+        _ => false,
+    };
+
+    if !authorized {
+        tracing::warn!(
+            request_id = ?request.extensions().get::<RequestId>().map(|id| id.to_string()),
+            uri = %request.uri(),
+            token_presented = presented.is_some(),
+            "Rejected admin request without a valid admin token"
+        );
+        return AppError::Unauthorized(
+            "admin endpoints require 'Authorization: Bearer <server.admin_token>'".to_string(),
+        )
+        .into_response();
+    }
+
+    next.run(request).await
+}
+
+/// Compare tokens without returning early on the first differing byte
+fn tokens_match(expected: &str, presented: &str) -> bool {
+    expected.len() == presented.len()
+        && expected
+            .bytes()
+            .zip(presented.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokens_match_requires_exact_token() {
+        assert!(tokens_match("s3cret", "s3cret"));
+        assert!(!tokens_match("s3cret", "s3creT"));
+        assert!(!tokens_match("s3cret", "s3cre"));
+        assert!(!tokens_match("s3cret", ""));
+    }
+}
@@ -0,0 +1,78 @@
+//! Request body size limit middleware
+//!
+//! Enforces `server.max_request_body_bytes` with an OpenAI-formatted 413 response.
+//! Axum's built-in extractor limit rejects oversized bodies with a plain-text
+//! message that clients can't parse, so the check happens here instead.
+//!
+//! Requests declaring a `Content-Length` over the limit are rejected without
+//! reading the body. Bodies without a length (chunked transfer) are buffered up
+//! to the limit and rejected as soon as they exceed it.
+
+use crate::error::AppError;
+use crate::handlers::AppState;
+use crate::middleware::RequestId;
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::header,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use futures::StreamExt;
+
+/// Middleware that rejects request bodies larger than `server.max_request_body_bytes`
+pub async fn body_limit_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let limit_bytes = state.config().server.max_request_body_bytes;
+    let request_id = request.extensions().get::<RequestId>().copied();
+
+    let declared_length = request
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<usize>().ok());
+
+    if let Some(length) = declared_length {
+        if length > limit_bytes {
+            tracing::warn!(
+                request_id = ?request_id.map(|id| id.to_string()),
+                uri = %request.uri(),
+                content_length = length,
+                max_request_body_bytes = limit_bytes,
+                "Request body exceeds size limit, rejecting"
+            );
+            return AppError::PayloadTooLarge { limit_bytes }.into_response();
+        }
+        return next.run(request).await;
+    }
+
+    // No declared length: buffer up to the limit so oversized chunked bodies are caught too
+    let (parts, body) = request.into_parts();
+    let mut stream = body.into_data_stream();
+    let mut buffered = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        let chunk = match chunk {
+            Ok(chunk) => chunk,
+            Err(e) => {
+                return AppError::Validation(format!("Failed to read request body: {}", e))
+                    .into_response();
+            }
+        };
+        if buffered.len() + chunk.len() > limit_bytes {
+            tracing::warn!(
+                request_id = ?request_id.map(|id| id.to_string()),
+                uri = %parts.uri,
+                max_request_body_bytes = limit_bytes,
+                "Streamed request body exceeds size limit, rejecting"
+            );
+            return AppError::PayloadTooLarge { limit_bytes }.into_response();
+        }
+        buffered.extend_from_slice(&chunk);
+    }
+
+    next.run(Request::from_parts(parts, Body::from(buffered)))
+        .await
+}
@@ -1,5 +1,11 @@
 //! Middleware modules for request processing
 
+pub mod admin_auth;
+pub mod body_limit;
 pub mod request_id;
+pub mod request_timeout;
 
+pub use admin_auth::admin_auth_middleware;
+pub use body_limit::body_limit_middleware;
 pub use request_id::{REQUEST_ID_HEADER, RequestId, request_id_middleware};
+pub use request_timeout::{DEADLINE_HEADER, Deadline, request_timeout_middleware};
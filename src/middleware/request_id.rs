@@ -3,6 +3,7 @@
 //! Generates a unique UUID for each incoming request and makes it available
 //! throughout the request lifecycle via Axum extensions.
 
+use crate::middleware::request_timeout::Deadline;
 use axum::{extract::Request, http::HeaderValue, middleware::Next, response::Response};
 use uuid::Uuid;
 
@@ -10,23 +11,45 @@ use uuid::Uuid;
 pub const REQUEST_ID_HEADER: &str = "x-request-id";
 
 /// Request ID wrapper type for Axum extensions
+///
+/// Also carries the client's [`Deadline`], if it sent one, so that every
+/// upstream query made for the request can stop waiting once it has passed.
 #[derive(Debug, Clone, Copy)]
-pub struct RequestId(pub Uuid);
+pub struct RequestId {
+    id: Uuid,
+    deadline: Option<Deadline>,
+}
 
 impl RequestId {
     /// Generate a new random request ID
     pub fn new() -> Self {
-        Self(Uuid::new_v4())
+        Self {
+            id: Uuid::new_v4(),
+            deadline: None,
+        }
+    }
+
+    /// The same request ID, bound by `deadline`
+    pub fn with_deadline(self, deadline: Deadline) -> Self {
+        Self {
+            deadline: Some(deadline),
+            ..self
+        }
+    }
+
+    /// The client's deadline for this request, if it sent one
+    pub fn deadline(&self) -> Option<Deadline> {
+        self.deadline
     }
 
     /// Get the UUID value
     pub fn as_uuid(&self) -> Uuid {
-        self.0
+        self.id
     }
 
     /// Get the string representation
     pub fn as_str(&self) -> String {
-        self.0.to_string()
+        self.id.to_string()
     }
 }
 
@@ -38,7 +61,7 @@ impl Default for RequestId {
 
 impl std::fmt::Display for RequestId {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+        write!(f, "{}", self.id)
     }
 }
 
@@ -0,0 +1,170 @@
+//! Total request duration middleware
+//!
+//! Enforces `server.max_request_duration_seconds` across the whole request
+//! lifecycle (routing, endpoint selection, and every retry attempt). Per-attempt
+//! upstream timeouts bound a single query, but retries and backoff can still add
+//! up; this middleware is the backstop that guarantees an upper bound.
+//!
+//! Clients can tighten the bound for one request with [`DEADLINE_HEADER`], a
+//! relative deadline in milliseconds. The shorter of the two applies, and a
+//! deadline below [`MIN_DEADLINE_MS`] is rejected before any upstream query is
+//! made, since its result would arrive too late to be used.
+//!
+//! The limit covers the time until the handler returns a response. For streaming
+//! responses that is the moment the SSE stream starts, so an in-progress stream
+//! is never cut off by this limit. The client's deadline also travels on the
+//! [`RequestId`] as a [`Deadline`], which caps the endpoint and first-token
+//! timeouts of each upstream query, including the wait for a stream's first
+//! token after the response has started.
+
+use crate::error::AppError;
+use crate::handlers::AppState;
+use crate::middleware::RequestId;
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Header carrying the client's deadline in milliseconds from receipt
+pub const DEADLINE_HEADER: &str = "x-octoroute-deadline-ms";
+
+/// Shortest deadline worth starting a router or completion query for
+pub const MIN_DEADLINE_MS: u64 = 100;
+
+/// A client deadline from [`DEADLINE_HEADER`], fixed when the request arrived
+#[derive(Debug, Clone, Copy)]
+pub struct Deadline {
+    at: Instant,
+    budget_ms: u64,
+}
+
+impl Deadline {
+    /// A deadline `budget_ms` milliseconds from now
+    pub fn after_ms(budget_ms: u64) -> Self {
+        Self {
+            at: Instant::now() + Duration::from_millis(budget_ms),
+            budget_ms,
+        }
+    }
+
+    /// The deadline as the client sent it, in milliseconds from receipt
+    pub fn budget_ms(&self) -> u64 {
+        self.budget_ms
+    }
+
+    /// Time left until the deadline, zero once it has passed
+    pub fn remaining(&self) -> Duration {
+        self.at.saturating_duration_since(Instant::now())
+    }
+
+    /// The time left, when it is shorter than `timeout` and so replaces it
+    pub fn tighter_than(&self, timeout: Duration) -> Option<Duration> {
+        Some(self.remaining()).filter(|remaining| *remaining < timeout)
+    }
+
+    /// The error for a query cut short by this deadline
+    pub fn exceeded(&self) -> AppError {
+        AppError::DeadlineExceeded {
+            deadline_ms: self.budget_ms,
+        }
+    }
+}
+
+/// Middleware that aborts requests exceeding their duration limit
+///
+/// The limit is `server.max_request_duration_seconds`, tightened by a
+/// [`DEADLINE_HEADER`] deadline when the client sends one. Passes requests
+/// through untouched when neither applies. When the limit elapses, the in-flight
+/// handler future is dropped (cancelling any pending upstream query) and a 504
+/// Gateway Timeout is returned.
+pub async fn request_timeout_middleware(
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let deadline_ms = match parse_deadline_ms(&request) {
+        Ok(deadline_ms) => deadline_ms,
+        Err(e) => return e.into_response(),
+    };
+    let max_duration_seconds = state.config().server.max_request_duration_seconds;
+
+    let request_id = request.extensions().get::<RequestId>().copied();
+    let uri = request.uri().clone();
+
+    if let Some(deadline_ms) = deadline_ms
+        && deadline_ms < MIN_DEADLINE_MS
+    {
+        tracing::warn!(
+            request_id = ?request_id.map(|id| id.to_string()),
+            uri = %uri,
+            deadline_ms = deadline_ms,
+            min_deadline_ms = MIN_DEADLINE_MS,
+            "Client deadline too short to route and complete, rejecting"
+        );
+        return AppError::DeadlineExceeded { deadline_ms }.into_response();
+    }
+
+    // Upstream queries read the deadline from the request ID
+    if let Some(deadline_ms) = deadline_ms {
+        let deadline = Deadline::after_ms(deadline_ms);
+        request
+            .extensions_mut()
+            .insert(request_id.unwrap_or_default().with_deadline(deadline));
+    }
+
+    // The client's deadline wins when it is the tighter of the two
+    let (limit, error) = match (deadline_ms, max_duration_seconds) {
+        (None, None) => return next.run(request).await,
+        (Some(deadline_ms), Some(seconds)) if deadline_ms >= seconds.saturating_mul(1000) => (
+            Duration::from_secs(seconds),
+            AppError::RequestTimeout {
+                timeout_seconds: seconds,
+            },
+        ),
+        (None, Some(seconds)) => (
+            Duration::from_secs(seconds),
+            AppError::RequestTimeout {
+                timeout_seconds: seconds,
+            },
+        ),
+        (Some(deadline_ms), _) => (
+            Duration::from_millis(deadline_ms),
+            AppError::DeadlineExceeded { deadline_ms },
+        ),
+    };
+
+    match tokio::time::timeout(limit, next.run(request)).await {
+        Ok(response) => response,
+        Err(_) => {
+            tracing::warn!(
+                request_id = ?request_id.map(|id| id.to_string()),
+                uri = %uri,
+                max_request_duration_seconds = ?max_duration_seconds,
+                deadline_ms = ?deadline_ms,
+                "Request exceeded maximum duration, aborting"
+            );
+            error.into_response()
+        }
+    }
+}
+
+/// Read [`DEADLINE_HEADER`] as a whole number of milliseconds
+fn parse_deadline_ms(request: &Request) -> Result<Option<u64>, AppError> {
+    let Some(value) = request.headers().get(DEADLINE_HEADER) else {
+        return Ok(None);
+    };
+    value
+        .to_str()
+        .ok()
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map(Some)
+        .ok_or_else(|| {
+            AppError::Validation(format!(
+                "{} must be a whole number of milliseconds",
+                DEADLINE_HEADER
+            ))
+        })
+}
@@ -36,7 +36,7 @@ impl ModelClient {
     pub fn new(endpoint: ModelEndpoint) -> AppResult<Self> {
         // Build AgentOptions from ModelEndpoint
         let options = AgentOptions::builder()
-            .model(endpoint.name())
+            .model(endpoint.model())
             .base_url(endpoint.base_url())
             .max_tokens(endpoint.max_tokens() as u32)
             .temperature(endpoint.temperature() as f32)
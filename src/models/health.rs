@@ -4,6 +4,8 @@
 //! Endpoints that fail consecutive checks are marked unhealthy and excluded from selection.
 
 use crate::config::{Config, ModelEndpoint};
+use crate::error::{AppError, ModelQueryError};
+use crate::router::llm_based::LlmRouterError;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -16,6 +18,108 @@ const HEALTH_CHECK_INTERVAL_SECS: u64 = 30;
 const HEALTH_CHECK_STALE_THRESHOLD_SECS: u64 = 60;
 const MAX_BACKGROUND_TASK_RESTARTS: u32 = 5;
 
+/// Suggested `Retry-After` delay when a tier has no healthy endpoints
+///
+/// Unhealthy endpoints only recover on the next background check, so clients
+/// retrying sooner than one check interval would almost certainly fail again.
+pub const RECOVERY_RETRY_AFTER_SECS: u64 = HEALTH_CHECK_INTERVAL_SECS;
+
+/// Outcome of [`HealthChecker::warmup`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WarmupReport {
+    /// Endpoints that answered the probe with a 2xx status
+    pub reachable: Vec<String>,
+    /// Endpoints that failed the probe (error status, timeout, or connection error)
+    pub unreachable: Vec<String>,
+}
+
+/// Why an endpoint failed a health check or a request
+///
+/// Tracked per endpoint and counted in `octoroute_health_failures_total`, so
+/// operators can tell a backend that is down from one answering with errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthFailureKind {
+    /// The endpoint could not be reached (refused, reset, DNS failure)
+    Connection,
+    /// No answer within the health check or request timeout
+    Timeout,
+    /// The endpoint answered with a non-2xx status
+    HttpStatus(u16),
+    /// The endpoint answered with something that is not a valid response
+    Parse,
+}
+
+impl HealthFailureKind {
+    /// Label used in metrics and the `/health` response
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HealthFailureKind::Connection => "connection",
+            HealthFailureKind::Timeout => "timeout",
+            HealthFailureKind::HttpStatus(_) => "http_status",
+            HealthFailureKind::Parse => "parse",
+        }
+    }
+
+    /// The HTTP status code, for `HttpStatus` failures
+    pub fn status(self) -> Option<u16> {
+        match self {
+            HealthFailureKind::HttpStatus(status) => Some(status),
+            _ => None,
+        }
+    }
+
+    /// Classify a failed model or router query
+    ///
+    /// The SDK does not expose HTTP status codes, so errors that are neither a
+    /// timeout, a malformed answer nor a status read from the error message
+    /// count as connection failures.
+    pub fn from_query_error(error: &AppError) -> Self {
+        match error {
+            AppError::ModelQuery(ModelQueryError::UpstreamStatus { status, .. }) => {
+                HealthFailureKind::HttpStatus(*status)
+            }
+            AppError::EndpointTimeout { .. }
+            | AppError::FirstTokenTimeout { .. }
+            | AppError::ModelQuery(ModelQueryError::Timeout { .. })
+            | AppError::LlmRouting(LlmRouterError::Timeout { .. }) => HealthFailureKind::Timeout,
+            AppError::ModelQuery(
+                ModelQueryError::EmptyResponse { .. } | ModelQueryError::UnparseableResponse { .. },
+            )
+            | AppError::LlmRouting(
+                LlmRouterError::EmptyResponse { .. }
+                | LlmRouterError::UnparseableResponse { .. }
+                | LlmRouterError::SizeExceeded { .. },
+            ) => HealthFailureKind::Parse,
+            _ => HealthFailureKind::Connection,
+        }
+    }
+
+    /// Classify a failed health check request
+    ///
+    /// Transport errors carry an I/O error somewhere in their source chain; an
+    /// error without one means the endpoint answered with something that is not
+    /// HTTP (or broke off mid-response).
+    fn from_reqwest(error: &reqwest::Error) -> Self {
+        if error.is_timeout() {
+            return HealthFailureKind::Timeout;
+        }
+        if error.is_connect() {
+            return HealthFailureKind::Connection;
+        }
+        if let Some(status) = error.status() {
+            return HealthFailureKind::HttpStatus(status.as_u16());
+        }
+        let mut source = std::error::Error::source(error);
+        while let Some(cause) = source {
+            if cause.is::<std::io::Error>() {
+                return HealthFailureKind::Connection;
+            }
+            source = cause.source();
+        }
+        HealthFailureKind::Parse
+    }
+}
+
 /// Status of the background health checking task
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BackgroundTaskStatus {
@@ -297,8 +401,15 @@ pub struct EndpointHealth {
     name: String,
     base_url: String,
     healthy: bool,
+    /// Manual override set through the admin API; wins over `healthy`
+    drained: bool,
     last_check: Instant,
     consecutive_failures: u32,
+    /// Kind of the most recent failure, cleared by the next success
+    last_failure: Option<HealthFailureKind>,
+    /// Whether any success has been recorded; until then `healthy` is only the
+    /// optimistic starting assumption
+    verified: bool,
 }
 
 impl EndpointHealth {
@@ -308,8 +419,11 @@ impl EndpointHealth {
             name,
             base_url,
             healthy: true,
+            drained: false,
             last_check: Instant::now(),
             consecutive_failures: 0,
+            last_failure: None,
+            verified: false,
         }
     }
 
@@ -323,9 +437,14 @@ impl EndpointHealth {
         &self.base_url
     }
 
-    /// Check if the endpoint is currently healthy
+    /// Check if the endpoint is currently healthy (never while drained)
     pub fn is_healthy(&self) -> bool {
-        self.healthy
+        self.healthy && !self.drained
+    }
+
+    /// Check if an operator has drained the endpoint
+    pub fn is_drained(&self) -> bool {
+        self.drained
     }
 
     /// Get the last health check time
@@ -337,6 +456,19 @@ impl EndpointHealth {
     pub fn consecutive_failures(&self) -> u32 {
         self.consecutive_failures
     }
+
+    /// Get the kind of the most recent failure, if it has not recovered since
+    pub fn last_failure(&self) -> Option<HealthFailureKind> {
+        self.last_failure
+    }
+
+    /// Check if a health check (or a request) has ever succeeded against the endpoint
+    ///
+    /// A never-checked endpoint counts as healthy for selection but not for
+    /// `/readyz`, which waits for real evidence before reporting ready.
+    pub fn is_verified(&self) -> bool {
+        self.verified
+    }
 }
 
 /// Health checker for model endpoints
@@ -352,6 +484,8 @@ pub struct HealthChecker {
     app_metrics: Option<Arc<crate::metrics::Metrics>>,
     /// Background health checking task handle for graceful shutdown
     background_task: Arc<Mutex<Option<tokio::task::JoinHandle<()>>>>,
+    /// Shared pooled client; when unset, each check builds its own client
+    http_client: Option<reqwest::Client>,
 }
 
 impl std::fmt::Debug for HealthChecker {
@@ -369,6 +503,7 @@ impl std::fmt::Debug for HealthChecker {
                 },
             )
             .field("background_task", &"<Mutex<JoinHandle>>")
+            .field("http_client", &self.http_client.is_some())
             .finish()
     }
 }
@@ -413,6 +548,7 @@ impl HealthChecker {
             metrics: Arc::new(HealthMetrics::new()),
             app_metrics: None,
             background_task: Arc::new(Mutex::new(None)),
+            http_client: None,
         }
     }
 
@@ -476,17 +612,41 @@ impl HealthChecker {
             metrics: Arc::new(HealthMetrics::new()),
             app_metrics: Some(app_metrics),
             background_task: Arc::new(Mutex::new(None)),
+            http_client: None,
         }
     }
 
+    /// Send health checks through a shared pooled client
+    ///
+    /// Lets checks reuse keep-alive connections (see `[server].http_pool`)
+    /// instead of opening a fresh connection to every endpoint on every cycle.
+    /// The connect timeout is a client setting, so `client` should be built
+    /// with `[health].probe_connect_timeout_ms`; the read timeout is applied
+    /// per probe either way.
+    pub fn with_http_client(mut self, client: reqwest::Client) -> Self {
+        self.http_client = Some(client);
+        self
+    }
+
     /// Get reference to health metrics for monitoring
     pub fn metrics(&self) -> &Arc<HealthMetrics> {
         &self.metrics
     }
 
+    /// Whether health tracking is on (`[health].enabled`)
+    pub fn is_enabled(&self) -> bool {
+        self.config.health.enabled
+    }
+
+    /// Whether a background health check task has been started and not shut down
+    pub async fn has_background_task(&self) -> bool {
+        self.background_task.lock().await.is_some()
+    }
+
     /// Check if an endpoint is currently healthy
     ///
-    /// Returns `false` for unknown endpoints (with warning logged).
+    /// Returns `false` for unknown endpoints (with warning logged), and `true`
+    /// for every known endpoint when health tracking is disabled.
     ///
     /// # Performance
     /// - **Time complexity**: O(1) HashMap lookup
@@ -497,7 +657,8 @@ impl HealthChecker {
         let status = self.health_status.read().await;
 
         match status.get(endpoint_name) {
-            Some(h) => h.healthy,
+            // With `[health].enabled = false` every known endpoint is healthy
+            Some(h) => !self.is_enabled() || h.is_healthy(),
             None => {
                 // DEFENSIVE: Log unknown endpoint checks
                 // This catches typos, race conditions (config reload mid-request),
@@ -527,6 +688,33 @@ impl HealthChecker {
     ///
     /// Returns an error if the endpoint name is unknown.
     pub async fn mark_failure(&self, endpoint_name: &str) -> Result<(), HealthError> {
+        self.mark_failure_inner(endpoint_name, None).await
+    }
+
+    /// Mark an endpoint as having failed for a known reason
+    ///
+    /// Same as [`mark_failure`](Self::mark_failure), and additionally keeps the
+    /// kind as the endpoint's `last_failure` and counts it in
+    /// `octoroute_health_failures_total` when Prometheus metrics are attached.
+    ///
+    /// Returns an error if the endpoint name is unknown.
+    pub async fn record_failure(
+        &self,
+        endpoint_name: &str,
+        kind: HealthFailureKind,
+    ) -> Result<(), HealthError> {
+        self.mark_failure_inner(endpoint_name, Some(kind)).await?;
+        if let Some(ref app_metrics) = self.app_metrics {
+            app_metrics.health_failure(endpoint_name, kind.as_str());
+        }
+        Ok(())
+    }
+
+    async fn mark_failure_inner(
+        &self,
+        endpoint_name: &str,
+        kind: Option<HealthFailureKind>,
+    ) -> Result<(), HealthError> {
         let mut status = self.health_status.write().await;
 
         // Defense-in-depth: Validate endpoint name exists in health_status
@@ -555,6 +743,9 @@ impl HealthChecker {
 
         health.consecutive_failures += 1;
         health.last_check = Instant::now();
+        if kind.is_some() {
+            health.last_failure = kind;
+        }
 
         // After 3 consecutive failures, mark as unhealthy
         if health.consecutive_failures >= CONSECUTIVE_FAILURES_THRESHOLD {
@@ -564,6 +755,7 @@ impl HealthChecker {
                     endpoint_name = %health.name,
                     endpoint_url = %health.base_url,
                     consecutive_failures = health.consecutive_failures,
+                    failure_kind = kind.map(HealthFailureKind::as_str),
                     "Endpoint marked as unhealthy after 3 consecutive failures"
                 );
             }
@@ -573,6 +765,7 @@ impl HealthChecker {
                 endpoint_name = %health.name,
                 endpoint_url = %health.base_url,
                 consecutive_failures = health.consecutive_failures,
+                failure_kind = kind.map(HealthFailureKind::as_str),
                 "Endpoint failure recorded (still healthy)"
             );
         }
@@ -621,7 +814,9 @@ impl HealthChecker {
         let was_unhealthy = !health.healthy;
 
         health.consecutive_failures = 0;
+        health.last_failure = None;
         health.healthy = true;
+        health.verified = true;
         health.last_check = Instant::now();
 
         if was_unhealthy {
@@ -642,6 +837,33 @@ impl HealthChecker {
         Ok(())
     }
 
+    /// Drain an endpoint (`drained = true`) or return it to service (`false`)
+    ///
+    /// A drained endpoint reports unhealthy until it is undrained, whatever the
+    /// background checks observe; those keep tracking its real health, which
+    /// applies again on undrain. The override lives in memory only and is gone
+    /// after a restart.
+    ///
+    /// Returns an error if the endpoint name is unknown.
+    pub async fn set_drained(&self, endpoint_name: &str, drained: bool) -> Result<(), HealthError> {
+        let mut status = self.health_status.write().await;
+        let Some(health) = status.get_mut(endpoint_name) else {
+            return Err(HealthError::UnknownEndpoint(endpoint_name.to_string()));
+        };
+
+        if health.drained != drained {
+            tracing::warn!(
+                endpoint_name = %health.name,
+                endpoint_url = %health.base_url,
+                drained = drained,
+                "Endpoint {} by operator",
+                if drained { "drained" } else { "undrained" }
+            );
+        }
+        health.drained = drained;
+        Ok(())
+    }
+
     /// Get all health statuses for display/debugging
     pub async fn get_all_statuses(&self) -> Vec<EndpointHealth> {
         let status = self.health_status.read().await;
@@ -650,24 +872,35 @@ impl HealthChecker {
 
     /// Check a single endpoint's health via HTTP HEAD request
     ///
+    /// Probes `HEAD {base_url}/models`, or `GET {base_url}{health_path}` when
+    /// the endpoint configures a `health_path`. Either way a 2xx is healthy.
+    ///
     /// Returns:
-    /// - `Ok(true)` if endpoint is healthy (2xx response)
-    /// - `Ok(false)` if endpoint is unhealthy (non-2xx, timeout, connection error)
+    /// - `Ok(None)` if endpoint is healthy (2xx response)
+    /// - `Ok(Some(kind))` if endpoint is unhealthy, with why (non-2xx, timeout,
+    ///   connection error, invalid response)
     /// - `Err(HealthError::HttpClientCreationFailed)` if HTTP client creation fails
     ///   (indicates systemic issue, not endpoint-specific problem)
-    async fn check_endpoint(&self, endpoint: &ModelEndpoint) -> Result<bool, HealthError> {
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(5))
-            .build()
-            .map_err(|e| {
+    async fn check_endpoint(
+        &self,
+        endpoint: &ModelEndpoint,
+    ) -> Result<Option<HealthFailureKind>, HealthError> {
+        let probe = &self.config.health;
+        let client = match &self.http_client {
+            Some(client) => client.clone(),
+            None => reqwest::Client::builder()
+                .connect_timeout(Duration::from_millis(probe.probe_connect_timeout_ms))
+                .build()
+                .map_err(|e| {
                 tracing::error!(
                     error = %e,
                     "FATAL: Failed to create HTTP client for health checks. \
                     This indicates a systemic issue (TLS config, resource exhaustion, library bug), \
                     not an endpoint failure. All health checks will fail."
                 );
-                HealthError::HttpClientCreationFailed(e.to_string())
-            })?;
+                    HealthError::HttpClientCreationFailed(e.to_string())
+                })?,
+        };
 
         // IMPORTANT: Health check URL construction
         // Config validation (config.rs:523-534) ENFORCES that base_url ends with "/v1"
@@ -699,9 +932,21 @@ impl HealthChecker {
             });
         }
 
-        let url = format!("{}/models", base_url);
+        // A configured health_path replaces the default /models probe
+        let (method, url) = match endpoint.health_path() {
+            Some(health_path) => (
+                reqwest::Method::GET,
+                format!("{}{}", base_url.trim_end_matches('/'), health_path),
+            ),
+            None => (reqwest::Method::HEAD, format!("{}/models", base_url)),
+        };
 
-        match client.head(&url).send().await {
+        match client
+            .request(method, &url)
+            .timeout(Duration::from_millis(probe.probe_read_timeout_ms))
+            .send()
+            .await
+        {
             Ok(response) => {
                 let is_success = response.status().is_success();
                 tracing::debug!(
@@ -711,20 +956,84 @@ impl HealthChecker {
                     healthy = is_success,
                     "Health check completed"
                 );
-                Ok(is_success)
+                Ok(
+                    (!is_success)
+                        .then(|| HealthFailureKind::HttpStatus(response.status().as_u16())),
+                )
             }
             Err(e) => {
+                let kind = HealthFailureKind::from_reqwest(&e);
                 tracing::debug!(
                     endpoint_name = %endpoint.name(),
                     url = %url,
                     error = %e,
+                    failure_kind = kind.as_str(),
                     "Health check failed"
                 );
-                Ok(false)
+                Ok(Some(kind))
             }
         }
     }
 
+    /// Probe every configured endpoint once, concurrently, before serving traffic
+    ///
+    /// Seeds the health state with a real observation instead of the optimistic
+    /// "all healthy" starting point. Results are recorded exactly like a scheduled
+    /// check, so a single failed probe counts toward the consecutive failure
+    /// threshold without marking the endpoint unhealthy on its own. Never fails:
+    /// unreachable endpoints are reported and left to the background checks.
+    pub async fn warmup(&self) -> WarmupReport {
+        let endpoints: Vec<&ModelEndpoint> = self
+            .config
+            .models
+            .fast
+            .iter()
+            .chain(self.config.models.balanced.iter())
+            .chain(self.config.models.deep.iter())
+            .collect();
+
+        let probes = endpoints.iter().map(|endpoint| async move {
+            let outcome = match self.check_endpoint(endpoint).await {
+                Ok(failure) => Ok(failure),
+                Err(e) => {
+                    tracing::warn!(
+                        endpoint_name = %endpoint.name(),
+                        error = %e,
+                        "Warmup probe could not be sent"
+                    );
+                    Err(())
+                }
+            };
+            (endpoint.name().to_string(), outcome)
+        });
+        let results = futures::future::join_all(probes).await;
+
+        let mut report = WarmupReport::default();
+        for (name, outcome) in results {
+            let reachable = matches!(outcome, Ok(None));
+            let recorded = match outcome {
+                Ok(None) => self.mark_success(&name).await,
+                Ok(Some(kind)) => self.record_failure(&name, kind).await,
+                Err(()) => self.mark_failure(&name).await,
+            };
+            if let Err(e) = recorded {
+                tracing::warn!(
+                    endpoint_name = %name,
+                    error = %e,
+                    "Failed to record warmup probe result"
+                );
+            }
+
+            if reachable {
+                report.reachable.push(name);
+            } else {
+                report.unreachable.push(name);
+            }
+        }
+
+        report
+    }
+
     /// Run health checks on all endpoints once
     async fn run_health_checks(&self) {
         let endpoints: Vec<ModelEndpoint> = {
@@ -738,7 +1047,7 @@ impl HealthChecker {
 
         for endpoint in endpoints {
             match self.check_endpoint(&endpoint).await {
-                Ok(true) => {
+                Ok(None) => {
                     // Endpoint is healthy
                     if let Err(e) = self.mark_success(endpoint.name()).await {
                         // Surface the failure via Prometheus metrics if available with labels
@@ -789,9 +1098,9 @@ impl HealthChecker {
                         }
                     }
                 }
-                Ok(false) => {
+                Ok(Some(kind)) => {
                     // Endpoint is unhealthy
-                    if let Err(e) = self.mark_failure(endpoint.name()).await {
+                    if let Err(e) = self.record_failure(endpoint.name(), kind).await {
                         // Surface the failure via Prometheus metrics if available with labels
                         if let Some(ref app_metrics) = self.app_metrics {
                             app_metrics.health_tracking_failure(endpoint.name(), e.error_type());
@@ -1001,7 +1310,8 @@ impl HealthChecker {
     ///
     /// Cancels the background health check task, allowing for graceful server shutdown.
     /// This method should be called during server shutdown to prevent the background task
-    /// from preventing clean process termination.
+    /// from preventing clean process termination. Does nothing if no task was started
+    /// (e.g. with `[health].enabled = false`).
     ///
     /// # Example
     /// ```no_run
@@ -1131,6 +1441,87 @@ router_tier = "balanced"
         // One success should recover
         checker.mark_success("fast-1").await.unwrap();
         assert!(checker.is_healthy("fast-1").await);
+    }
+
+    #[tokio::test]
+    async fn test_endpoint_is_unverified_until_first_success() {
+        let config = Arc::new(create_test_config());
+        let checker = HealthChecker::new(config);
+        let verified = |statuses: Vec<EndpointHealth>| {
+            statuses
+                .into_iter()
+                .find(|h| h.name() == "fast-1")
+                .expect("fast-1 should be tracked")
+                .is_verified()
+        };
+
+        assert!(checker.is_healthy("fast-1").await);
+        assert!(!verified(checker.get_all_statuses().await));
+
+        checker.mark_failure("fast-1").await.unwrap();
+        assert!(!verified(checker.get_all_statuses().await));
+
+        checker.mark_success("fast-1").await.unwrap();
+        assert!(verified(checker.get_all_statuses().await));
+    }
+
+    #[tokio::test]
+    async fn test_record_failure_keeps_last_kind_until_success() {
+        let config = Arc::new(create_test_config());
+        let checker = HealthChecker::new(config);
+        let last_failure = |statuses: Vec<EndpointHealth>| {
+            statuses
+                .into_iter()
+                .find(|h| h.name() == "fast-1")
+                .and_then(|h| h.last_failure())
+        };
+
+        checker
+            .record_failure("fast-1", HealthFailureKind::Timeout)
+            .await
+            .unwrap();
+        checker
+            .record_failure("fast-1", HealthFailureKind::HttpStatus(503))
+            .await
+            .unwrap();
+        // An unclassified failure keeps the last known kind
+        checker.mark_failure("fast-1").await.unwrap();
+        assert!(!checker.is_healthy("fast-1").await);
+        assert_eq!(
+            last_failure(checker.get_all_statuses().await),
+            Some(HealthFailureKind::HttpStatus(503))
+        );
+
+        checker.mark_success("fast-1").await.unwrap();
+        assert_eq!(last_failure(checker.get_all_statuses().await), None);
+    }
+
+    #[tokio::test]
+    async fn test_drain_overrides_automatic_health_until_undrained() {
+        let config = Arc::new(create_test_config());
+        let checker = HealthChecker::new(config);
+
+        checker.set_drained("fast-1", true).await.unwrap();
+        assert!(!checker.is_healthy("fast-1").await);
+        assert!(checker.is_healthy("fast-2").await);
+
+        // Successful checks don't bring a drained endpoint back
+        checker.mark_success("fast-1").await.unwrap();
+        assert!(!checker.is_healthy("fast-1").await);
+
+        // Failures are still tracked while drained and apply after undrain
+        for _ in 0..3 {
+            checker.mark_failure("fast-1").await.unwrap();
+        }
+        checker.set_drained("fast-1", false).await.unwrap();
+        assert!(!checker.is_healthy("fast-1").await);
+        checker.mark_success("fast-1").await.unwrap();
+        assert!(checker.is_healthy("fast-1").await);
+
+        assert!(matches!(
+            checker.set_drained("nope", true).await,
+            Err(HealthError::UnknownEndpoint(_))
+        ));
 
         // Consecutive failure count should be reset
         let statuses = checker.get_all_statuses().await;
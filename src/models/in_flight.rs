@@ -0,0 +1,150 @@
+//! Per-endpoint in-flight request tracking
+//!
+//! Counts requests currently being served by each endpoint so the selector can
+//! skip endpoints that have reached their configured `max_in_flight` limit.
+//! A slot is held by an [`InFlightGuard`] for the lifetime of the upstream
+//! query (for streaming, until the stream ends or the client disconnects).
+
+use crate::config::{Config, ModelEndpoint};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// In-flight request counters for every configured endpoint
+///
+/// The set of endpoints is fixed at construction (config is immutable), so the
+/// map itself needs no lock; each counter is an independent atomic.
+#[derive(Debug, Default)]
+pub struct InFlightTracker {
+    counts: HashMap<String, Arc<AtomicUsize>>,
+}
+
+impl InFlightTracker {
+    /// Create a tracker with a zeroed counter for every endpoint in `config`
+    pub fn new(config: &Config) -> Self {
+        let counts = config
+            .models
+            .fast
+            .iter()
+            .chain(config.models.balanced.iter())
+            .chain(config.models.deep.iter())
+            .map(|endpoint| (endpoint.name().to_string(), Arc::new(AtomicUsize::new(0))))
+            .collect();
+        Self { counts }
+    }
+
+    /// Create a tracker for `config` that keeps counting on `previous`'s counters
+    ///
+    /// Endpoints `config` shares with `previous` (by name) keep their counter,
+    /// so requests started before a config reload still count against the new
+    /// selector until they finish. Other endpoints start at zero.
+    pub fn carried_over(config: &Config, previous: &InFlightTracker) -> Self {
+        let mut tracker = Self::new(config);
+        for (name, count) in &mut tracker.counts {
+            if let Some(previous) = previous.counts.get(name) {
+                *count = Arc::clone(previous);
+            }
+        }
+        tracker
+    }
+
+    /// Number of requests currently in flight to `endpoint_name` (0 if unknown)
+    pub fn count(&self, endpoint_name: &str) -> usize {
+        self.counts
+            .get(endpoint_name)
+            .map(|count| count.load(Ordering::Acquire))
+            .unwrap_or(0)
+    }
+
+    /// Returns true if the endpoint is below its `max_in_flight` limit (or has none)
+    ///
+    /// This is a point-in-time check: concurrent selections may both observe the
+    /// last free slot, so the limit can be briefly exceeded by the number of
+    /// simultaneous selections. That is acceptable for load spreading, which is
+    /// what the limit is for.
+    pub fn has_capacity(&self, endpoint: &ModelEndpoint) -> bool {
+        match endpoint.max_in_flight() {
+            Some(limit) => self.count(endpoint.name()) < limit,
+            None => true,
+        }
+    }
+
+    /// Claim an in-flight slot for `endpoint_name`, released when the guard drops
+    ///
+    /// Unknown endpoint names yield a guard that tracks nothing.
+    pub fn acquire(&self, endpoint_name: &str) -> InFlightGuard {
+        let count = self.counts.get(endpoint_name).cloned();
+        if let Some(count) = &count {
+            count.fetch_add(1, Ordering::AcqRel);
+        }
+        InFlightGuard { count }
+    }
+}
+
+/// RAII handle for one in-flight request; decrements the endpoint counter on drop
+#[derive(Debug)]
+pub struct InFlightGuard {
+    count: Option<Arc<AtomicUsize>>,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        if let Some(count) = &self.count {
+            count.fetch_sub(1, Ordering::AcqRel);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::selector::create_test_config;
+
+    #[test]
+    fn test_guard_increments_and_releases_on_drop() {
+        let tracker = InFlightTracker::new(&create_test_config());
+
+        let first = tracker.acquire("fast-1");
+        let second = tracker.acquire("fast-1");
+        assert_eq!(tracker.count("fast-1"), 2);
+        assert_eq!(tracker.count("fast-2"), 0);
+
+        drop(first);
+        assert_eq!(tracker.count("fast-1"), 1);
+        drop(second);
+        assert_eq!(tracker.count("fast-1"), 0);
+    }
+
+    #[test]
+    fn test_unknown_endpoint_is_not_tracked() {
+        let tracker = InFlightTracker::new(&create_test_config());
+
+        let _guard = tracker.acquire("no-such-endpoint");
+        assert_eq!(tracker.count("no-such-endpoint"), 0);
+    }
+
+    #[test]
+    fn test_carried_over_tracker_shares_counters_of_kept_endpoints() {
+        let config = create_test_config();
+        let previous = InFlightTracker::new(&config);
+        let guard = previous.acquire("fast-1");
+
+        let tracker = InFlightTracker::carried_over(&config, &previous);
+        assert_eq!(tracker.count("fast-1"), 1);
+        assert_eq!(tracker.count("fast-2"), 0);
+
+        // A request started before the swap releases its slot in the new tracker
+        drop(guard);
+        assert_eq!(tracker.count("fast-1"), 0);
+    }
+
+    #[test]
+    fn test_endpoint_without_limit_always_has_capacity() {
+        let config = create_test_config();
+        let tracker = InFlightTracker::new(&config);
+        let endpoint = &config.models.fast[0];
+
+        let _guards: Vec<_> = (0..100).map(|_| tracker.acquire(endpoint.name())).collect();
+        assert!(tracker.has_capacity(endpoint));
+    }
+}
@@ -6,9 +6,11 @@
 pub mod client;
 pub mod endpoint_name;
 pub mod health;
+pub mod in_flight;
 pub mod selector;
 
 pub use client::ModelClient;
 pub use endpoint_name::{EndpointName, ExclusionSet};
-pub use health::{EndpointHealth, HealthChecker, HealthError};
-pub use selector::{ModelSelector, TierSelector};
+pub use health::{EndpointHealth, HealthChecker, HealthError, HealthFailureKind, WarmupReport};
+pub use in_flight::{InFlightGuard, InFlightTracker};
+pub use selector::{ModelSelector, SelectError, TierSelector};
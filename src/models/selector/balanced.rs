@@ -6,7 +6,7 @@
 use crate::config::ModelEndpoint;
 use crate::error::{AppError, AppResult};
 use crate::models::endpoint_name::ExclusionSet;
-use crate::models::selector::ModelSelector;
+use crate::models::selector::{ModelSelector, SelectError};
 use crate::router::TargetModel;
 use std::sync::Arc;
 
@@ -77,6 +77,11 @@ impl TierSelector {
         self.inner.select(self.tier, exclude).await
     }
 
+    /// Select as [`select`](Self::select) does, reporting why selection failed
+    pub async fn try_select(&self, exclude: &ExclusionSet) -> Result<&ModelEndpoint, SelectError> {
+        self.inner.try_select(self.tier, exclude).await
+    }
+
     /// Get the tier this selector operates on
     pub fn tier(&self) -> TargetModel {
         self.tier
@@ -87,6 +92,16 @@ impl TierSelector {
         self.inner.endpoint_count(self.tier)
     }
 
+    /// Get the default tier of the underlying ModelSelector (see `ModelSelector::default_tier`)
+    pub fn default_tier(&self) -> Option<TargetModel> {
+        self.inner.default_tier()
+    }
+
+    /// Get the in-flight tracker shared with the underlying ModelSelector
+    pub fn in_flight(&self) -> &crate::models::InFlightTracker {
+        self.inner.in_flight()
+    }
+
     /// Get a reference to the health checker for external use (e.g., marking success/failure)
     pub fn health_checker(&self) -> &Arc<crate::models::health::HealthChecker> {
         self.inner.health_checker()
@@ -4,38 +4,113 @@
 //!
 //! Production code is in this file, tests are organized in sibling modules:
 //! - tests_basic: Basic selection, endpoint counting, empty tiers
+//! - tests_cheapest: Cost-based selection (`selection_mode = "cheapest"`)
+//! - tests_default_tier: Rule-mode fallback tier (explicit and priority-based)
 //! - tests_priority: Priority-based filtering
-//! - tests_weighted: Weighted random distribution
+//! - tests_weighted: Weighted random distribution and the cumulative-weight step
+//! - tests_effective_weights: Traffic percentages reported per priority group
 //! - tests_exclusion: Exclusion set handling for retry logic
+//! - tests_fallback: Cross-tier fallback and its metric
+//! - tests_spillover: In-flight limits and priority spillover
+//! - tests_tags: Tag-filtered selection
+//! - tests_named: Explicit selection by endpoint name
+//! - tests_select_error: Failure reasons reported by `try_select`
 
 mod balanced;
 
 pub use balanced::TierSelector;
 
-use crate::config::{Config, ModelEndpoint};
+use crate::config::{Config, ModelEndpoint, SelectionMode, UncostedEndpoints};
+use crate::error::AppError;
 use crate::models::endpoint_name::{EndpointName, ExclusionSet};
-use crate::models::health::HealthChecker;
+use crate::models::health::{HealthChecker, RECOVERY_RETRY_AFTER_SECS};
+use crate::models::in_flight::InFlightTracker;
 use crate::router::TargetModel;
 use rand::Rng;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicUsize, Ordering};
 
+/// Why [`ModelSelector::try_select`] found no endpoint
+///
+/// Lets callers report or react to the failure without recounting the tier
+/// themselves. Counts are over the endpoints configured for the tier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum SelectError {
+    /// The tier has no endpoints at all (a configuration problem)
+    #[error("no endpoints configured")]
+    NoEndpointsConfigured,
+    /// Every endpoint is unhealthy (or drained); they may recover
+    #[error("all endpoints unhealthy")]
+    AllUnhealthy,
+    /// Every endpoint is in the caller's exclusion set, e.g. all failed this request
+    #[error("all endpoints excluded")]
+    AllExcluded,
+    /// A mix of excluded, unhealthy and busy endpoints
+    ///
+    /// `healthy` counts endpoints that are healthy and not excluded but were
+    /// still passed over (at their `max_in_flight` limit, or outside the top
+    /// priority group without spillover); the rest are unhealthy.
+    #[error("no endpoint available ({healthy} healthy but busy, {excluded} excluded)")]
+    MixedUnavailable { healthy: usize, excluded: usize },
+}
+
+impl SelectError {
+    /// Label used in metrics
+    pub fn reason(self) -> &'static str {
+        match self {
+            Self::NoEndpointsConfigured => "no_endpoints",
+            Self::AllUnhealthy => "all_unhealthy",
+            Self::AllExcluded => "all_excluded",
+            Self::MixedUnavailable { .. } => "mixed",
+        }
+    }
+
+    /// Categorize a tier where nothing could be selected
+    fn unavailable(configured: usize, unhealthy: usize, excluded: usize) -> Self {
+        if excluded == configured {
+            Self::AllExcluded
+        } else if unhealthy == configured {
+            Self::AllUnhealthy
+        } else {
+            Self::MixedUnavailable {
+                healthy: configured - unhealthy - excluded,
+                excluded,
+            }
+        }
+    }
+}
+
 /// Selects appropriate model endpoint from multi-model configuration
 ///
 /// Implements priority-based selection with health filtering and weighted distribution:
 /// - Filters out unhealthy endpoints
 /// - Selects from highest available priority tier
 /// - Uses weighted random selection within priority tier
-#[derive(Debug)]
 pub struct ModelSelector {
     config: Arc<Config>,
     health_checker: Arc<HealthChecker>,
+    metrics: Arc<crate::metrics::Metrics>,
+    in_flight: InFlightTracker,
     // Selection counters for metrics tracking
     fast_counter: AtomicUsize,
     balanced_counter: AtomicUsize,
     deep_counter: AtomicUsize,
 }
 
+impl std::fmt::Debug for ModelSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ModelSelector")
+            .field("config", &"<Config>")
+            .field("health_checker", &self.health_checker)
+            .field("metrics", &"<Metrics>")
+            .field("in_flight", &self.in_flight)
+            .field("fast_counter", &self.fast_counter)
+            .field("balanced_counter", &self.balanced_counter)
+            .field("deep_counter", &self.deep_counter)
+            .finish()
+    }
+}
+
 impl ModelSelector {
     /// Create a new ModelSelector from configuration
     ///
@@ -45,30 +120,78 @@ impl ModelSelector {
     /// * `config` - Application configuration
     /// * `metrics` - Prometheus metrics for surfacing health tracking failures
     pub fn new(config: Arc<Config>, metrics: Arc<crate::metrics::Metrics>) -> Self {
-        let health_checker = Arc::new(HealthChecker::new_with_metrics(config.clone(), metrics));
+        let health_checker = HealthChecker::new_with_metrics(config.clone(), metrics.clone());
+        Self::with_health_checker(config, metrics, health_checker)
+    }
+
+    /// Create a ModelSelector whose health checks share a pooled HTTP client
+    ///
+    /// Same as [`ModelSelector::new`], but health checks reuse connections from
+    /// `http_client` (built from `[server].http_pool`).
+    pub fn with_http_client(
+        config: Arc<Config>,
+        metrics: Arc<crate::metrics::Metrics>,
+        http_client: reqwest::Client,
+    ) -> Self {
+        let health_checker = HealthChecker::new_with_metrics(config.clone(), metrics.clone())
+            .with_http_client(http_client);
+        Self::with_health_checker(config, metrics, health_checker)
+    }
 
-        // Start background health checking
-        health_checker.clone().start_background_checks();
+    fn with_health_checker(
+        config: Arc<Config>,
+        metrics: Arc<crate::metrics::Metrics>,
+        health_checker: HealthChecker,
+    ) -> Self {
+        let health_checker = Arc::new(health_checker);
+
+        // Start background health checking, unless health tracking is off
+        if config.health.enabled {
+            health_checker.clone().start_background_checks();
+        } else {
+            tracing::info!(
+                "Health checking disabled ([health].enabled = false): \
+                no background checks, all endpoints treated as healthy"
+            );
+        }
 
         Self {
+            in_flight: InFlightTracker::new(&config),
             config,
             health_checker,
+            metrics,
             fast_counter: AtomicUsize::new(0),
             balanced_counter: AtomicUsize::new(0),
             deep_counter: AtomicUsize::new(0),
         }
     }
 
+    /// Replace the in-flight tracker (builder pattern), e.g. with one carried
+    /// over from the selector this one replaces (see [`InFlightTracker::carried_over`])
+    pub fn with_in_flight(mut self, in_flight: InFlightTracker) -> Self {
+        self.in_flight = in_flight;
+        self
+    }
+
     /// Get a reference to the health checker for external use (e.g., retry logic)
     pub fn health_checker(&self) -> &Arc<HealthChecker> {
         &self.health_checker
     }
 
+    /// Get the per-endpoint in-flight request tracker
+    ///
+    /// Callers hold an [`InFlightGuard`](crate::models::InFlightGuard) from
+    /// `in_flight().acquire(..)` for the duration of each upstream query.
+    pub fn in_flight(&self) -> &InFlightTracker {
+        &self.in_flight
+    }
+
     /// Select an endpoint for the given target model tier using priority + weighted random selection
     ///
     /// Implements priority-based selection with health filtering, exclusion, and weighted distribution:
     /// - Filters out unhealthy endpoints first
     /// - Filters out endpoints in the exclusion set (for retry logic)
+    /// - Filters out endpoints at their `max_in_flight` limit (see `routing.spillover`)
     /// - Filters to only the highest available priority tier
     /// - Within that priority tier, uses weighted random selection
     /// - Higher priority = tried first, higher weight = more traffic within priority tier
@@ -83,12 +206,54 @@ impl ModelSelector {
     /// - **Async**: Single RwLock read for health status (non-blocking if no writers)
     /// - **Expected latency**: <1ms for typical configurations (1-10 endpoints per tier)
     ///
-    /// Returns None if the requested tier has no healthy, non-excluded endpoints available.
+    /// Returns None if the requested tier has no healthy, non-excluded endpoints
+    /// available; [`try_select`](Self::try_select) says why.
     pub async fn select(
         &self,
         target: TargetModel,
         exclude: &ExclusionSet,
     ) -> Option<&ModelEndpoint> {
+        self.try_select(target, exclude).await.ok()
+    }
+
+    /// Select as [`select`](Self::select) does, reporting why selection failed
+    pub async fn try_select(
+        &self,
+        target: TargetModel,
+        exclude: &ExclusionSet,
+    ) -> Result<&ModelEndpoint, SelectError> {
+        self.select_filtered(target, exclude, &[]).await
+    }
+
+    /// Select an endpoint carrying every tag in `required_tags`
+    ///
+    /// Endpoints missing any required tag are dropped before the health, priority,
+    /// and weighted selection steps of [`select`](Self::select), which is this
+    /// method with no required tags.
+    ///
+    /// Returns None if no healthy, non-excluded endpoint in the tier has all the tags.
+    pub async fn select_with_tags(
+        &self,
+        target: TargetModel,
+        exclude: &ExclusionSet,
+        required_tags: &[String],
+    ) -> Option<&ModelEndpoint> {
+        self.select_filtered(target, exclude, required_tags)
+            .await
+            .ok()
+    }
+
+    /// Shared selection behind [`try_select`](Self::try_select) and
+    /// [`select_with_tags`](Self::select_with_tags)
+    ///
+    /// Endpoints without the required tags don't count as configured, so a tier
+    /// where none carry them reports [`SelectError::NoEndpointsConfigured`].
+    async fn select_filtered(
+        &self,
+        target: TargetModel,
+        exclude: &ExclusionSet,
+        required_tags: &[String],
+    ) -> Result<&ModelEndpoint, SelectError> {
         let (endpoints, counter) = match target {
             TargetModel::Fast => (&self.config.models.fast, &self.fast_counter),
             TargetModel::Balanced => (&self.config.models.balanced, &self.balanced_counter),
@@ -100,16 +265,19 @@ impl ModelSelector {
                 tier = ?target,
                 "No endpoints configured for tier - check config.toml"
             );
-            return None;
+            return Err(SelectError::NoEndpointsConfigured);
         }
 
-        // Filter to only healthy and non-excluded endpoints
+        // Filter to only tagged, non-excluded, and healthy endpoints
+        let mut configured = 0;
+        let mut unhealthy = 0;
+        let mut excluded = 0;
         let mut available_endpoints = Vec::new();
         for endpoint in endpoints.iter() {
-            // Skip unhealthy endpoints
-            if !self.health_checker.is_healthy(endpoint.name()).await {
+            if !endpoint.has_tags(required_tags) {
                 continue;
             }
+            configured += 1;
 
             // Skip excluded endpoints (e.g., already failed in this request)
             if exclude.contains(&EndpointName::from(endpoint)) {
@@ -118,20 +286,39 @@ impl ModelSelector {
                     endpoint_name = %endpoint.name(),
                     "Skipping excluded endpoint"
                 );
+                excluded += 1;
+                continue;
+            }
+
+            // Skip unhealthy endpoints
+            if !self.health_checker.is_healthy(endpoint.name()).await {
+                unhealthy += 1;
                 continue;
             }
 
             available_endpoints.push(endpoint);
         }
 
-        if available_endpoints.is_empty() {
+        if configured == 0 {
             tracing::error!(
                 tier = ?target,
                 total_endpoints = endpoints.len(),
-                excluded_count = exclude.len(),
+                required_tags = ?required_tags,
+                "No endpoints in tier carry the required tags"
+            );
+            return Err(SelectError::NoEndpointsConfigured);
+        }
+
+        if available_endpoints.is_empty() {
+            tracing::error!(
+                tier = ?target,
+                total_endpoints = configured,
+                unhealthy_count = unhealthy,
+                excluded_count = excluded,
+                required_tags = ?required_tags,
                 "No available endpoints for tier - all endpoints either unhealthy or excluded"
             );
-            return None;
+            return Err(SelectError::unavailable(configured, unhealthy, excluded));
         }
 
         tracing::debug!(
@@ -142,6 +329,36 @@ impl ModelSelector {
             "Filtered to healthy and non-excluded endpoints"
         );
 
+        // Skip endpoints at their max_in_flight limit. With spillover, saturated
+        // endpoints drop out like unhealthy ones so a lower priority group can take
+        // over; without it, only the top priority group is eligible, so saturating
+        // that group fails the selection instead of spilling lower.
+        let available_endpoints: Vec<&ModelEndpoint> = if self.config.routing.spillover {
+            available_endpoints
+                .into_iter()
+                .filter(|e| self.in_flight.has_capacity(e))
+                .collect()
+        } else {
+            let top_priority = available_endpoints
+                .iter()
+                .map(|e| e.priority())
+                .max()
+                .expect("Defensive check: available_endpoints cannot be empty due to early return above");
+            available_endpoints
+                .into_iter()
+                .filter(|e| e.priority() == top_priority && self.in_flight.has_capacity(e))
+                .collect()
+        };
+
+        if available_endpoints.is_empty() {
+            tracing::warn!(
+                tier = ?target,
+                spillover = self.config.routing.spillover,
+                "No endpoint with in-flight capacity - priority group saturated"
+            );
+            return Err(SelectError::unavailable(configured, unhealthy, excluded));
+        }
+
         // Find highest priority among available endpoints and filter to only that tier
         let max_priority = available_endpoints
             .iter()
@@ -165,6 +382,15 @@ impl ModelSelector {
             "Filtered to highest priority tier among available endpoints"
         );
 
+        // In cheapest mode only the lowest-priced endpoints of the group compete
+        let highest_priority_endpoints = match self.config.routing.selection_mode {
+            SelectionMode::Weighted => highest_priority_endpoints,
+            SelectionMode::Cheapest => cheapest_endpoints(
+                &highest_priority_endpoints,
+                self.config.routing.uncosted_endpoints,
+            ),
+        };
+
         // Increment selection counter for metrics (atomic operation)
         counter.fetch_add(1, Ordering::Relaxed);
 
@@ -194,36 +420,150 @@ impl ModelSelector {
         let mut rng = rand::rng();
         let random_weight = rng.random_range(0.0..total_weight);
 
-        // Select endpoint using cumulative weight distribution within priority tier
-        let mut cumulative_weight = 0.0;
-        for (index, endpoint) in highest_priority_endpoints.iter().enumerate() {
-            cumulative_weight += endpoint.weight();
-            if random_weight < cumulative_weight {
-                tracing::debug!(
-                    tier = ?target,
-                    priority = max_priority,
+        let endpoint = select_by_random_weight(&highest_priority_endpoints, random_weight);
+        tracing::debug!(
+            tier = ?target,
+            priority = max_priority,
+            endpoint_name = %endpoint.name(),
+            endpoint_url = %endpoint.base_url(),
+            weight = endpoint.weight(),
+            random_weight = random_weight,
+            total_weight = total_weight,
+            "Selected endpoint via weighted random selection"
+        );
+        Ok(endpoint)
+    }
+
+    /// Select an endpoint, falling back to lower tiers if the target tier is unavailable
+    ///
+    /// Tries `target` first, then each lower tier in order (Deep → Balanced → Fast).
+    /// Fast has no lower tier, so a Fast request never falls back. The exclusion
+    /// set applies to every tier tried.
+    ///
+    /// When an endpoint from a lower tier is returned, `octoroute_tier_fallback_total`
+    /// is incremented with the requested and served tiers.
+    ///
+    /// # Returns
+    /// The tier that was actually selected from and the endpoint, or `None` if the
+    /// target tier and all lower tiers have no healthy, non-excluded endpoints.
+    pub async fn select_with_fallback(
+        &self,
+        target: TargetModel,
+        exclude: &ExclusionSet,
+    ) -> Option<(TargetModel, &ModelEndpoint)> {
+        let lower_tiers: &[TargetModel] = match target {
+            TargetModel::Deep => &[TargetModel::Balanced, TargetModel::Fast],
+            TargetModel::Balanced => &[TargetModel::Fast],
+            TargetModel::Fast => &[],
+        };
+
+        if let Some(endpoint) = self.select(target, exclude).await {
+            return Some((target, endpoint));
+        }
+
+        for &tier in lower_tiers {
+            if let Some(endpoint) = self.select(tier, exclude).await {
+                tracing::warn!(
+                    requested_tier = ?target,
+                    served_tier = ?tier,
                     endpoint_name = %endpoint.name(),
-                    endpoint_url = %endpoint.base_url(),
-                    weight = endpoint.weight(),
-                    index = index,
-                    total_weight = total_weight,
-                    "Selected endpoint via weighted random selection"
+                    "Requested tier unavailable, falling back to lower tier"
                 );
-                return Some(endpoint);
+                self.metrics.tier_fallback(target.into(), tier.into());
+                return Some((tier, endpoint));
             }
         }
 
-        // Fallback: return last endpoint if rounding errors prevent selection
-        let last_endpoint = highest_priority_endpoints
-            .last()
-            .expect("Defensive check: highest_priority_endpoints cannot be empty");
-        tracing::warn!(
-            tier = ?target,
-            priority = max_priority,
-            endpoint_name = %last_endpoint.name(),
-            "Fallback to last endpoint (likely floating-point rounding)"
-        );
-        Some(last_endpoint)
+        None
+    }
+
+    /// Select a specific endpoint by name, for explicit `model: "<endpoint-name>"` requests
+    ///
+    /// Looks the name up across all tiers and applies the same health and
+    /// exclusion filtering as [`select`](Self::select), but never substitutes
+    /// another endpoint: callers decide whether to fail or fall back. Priority,
+    /// weight and in-flight limits do not apply. A successful selection counts
+    /// toward the endpoint's tier like any other.
+    ///
+    /// # Errors
+    /// - [`AppError::Validation`] if no endpoint has this name
+    /// - [`AppError::EndpointsUnavailable`] if the endpoint exists but is
+    ///   unhealthy or excluded (e.g. already failed in this request)
+    pub async fn select_named(
+        &self,
+        name: &str,
+        exclude: &ExclusionSet,
+    ) -> Result<(&ModelEndpoint, TargetModel), AppError> {
+        let tiers = [
+            (
+                TargetModel::Fast,
+                &self.config.models.fast,
+                &self.fast_counter,
+            ),
+            (
+                TargetModel::Balanced,
+                &self.config.models.balanced,
+                &self.balanced_counter,
+            ),
+            (
+                TargetModel::Deep,
+                &self.config.models.deep,
+                &self.deep_counter,
+            ),
+        ];
+        let Some((tier, endpoint, counter)) =
+            tiers.into_iter().find_map(|(tier, endpoints, counter)| {
+                endpoints
+                    .iter()
+                    .find(|endpoint| endpoint.name() == name)
+                    .map(|endpoint| (tier, endpoint, counter))
+            })
+        else {
+            return Err(AppError::Validation(format!(
+                "Model '{}' not found. Available models: auto, fast, balanced, deep, or a specific endpoint name from config.",
+                name
+            )));
+        };
+
+        if !self.health_checker.is_healthy(name).await {
+            tracing::warn!(
+                tier = ?tier,
+                endpoint_name = %name,
+                "Explicitly requested endpoint is unhealthy"
+            );
+            return Err(AppError::EndpointsUnavailable {
+                message: format!("Endpoint '{}' is currently unhealthy", name),
+                retry_after_seconds: RECOVERY_RETRY_AFTER_SECS,
+            });
+        }
+
+        if exclude.contains(&EndpointName::from(endpoint)) {
+            tracing::debug!(
+                tier = ?tier,
+                endpoint_name = %name,
+                "Explicitly requested endpoint is excluded"
+            );
+            return Err(AppError::EndpointsUnavailable {
+                message: format!("Endpoint '{}' already failed for this request", name),
+                retry_after_seconds: RECOVERY_RETRY_AFTER_SECS,
+            });
+        }
+
+        counter.fetch_add(1, Ordering::Relaxed);
+        Ok((endpoint, tier))
+    }
+
+    /// Returns true if any endpoint configured for `target` carries every tag in `tags`
+    ///
+    /// Health is not considered; this answers whether a tag requirement is
+    /// satisfiable by the tier's configuration at all.
+    pub fn tier_has_tags(&self, target: TargetModel, tags: &[String]) -> bool {
+        let endpoints = match target {
+            TargetModel::Fast => &self.config.models.fast,
+            TargetModel::Balanced => &self.config.models.balanced,
+            TargetModel::Deep => &self.config.models.deep,
+        };
+        endpoints.iter().any(|endpoint| endpoint.has_tags(tags))
     }
 
     /// Get the number of available endpoints for a target tier
@@ -235,15 +575,26 @@ impl ModelSelector {
         }
     }
 
+    /// Effective traffic percentage per endpoint in `target`, within each priority group
+    ///
+    /// Reports how configured weights translate into traffic when every endpoint
+    /// is healthy (weights 1, 2, 3 in one group give 16.7%, 33.3%, 50%). Health is
+    /// not considered. See [`ModelsConfig::effective_weights`](crate::config::ModelsConfig::effective_weights).
+    pub fn effective_weights(&self, target: TargetModel) -> Vec<(String, f64)> {
+        self.config.models.effective_weights(target)
+    }
+
     /// Get the default tier when no routing rule matches
     ///
-    /// Selects the tier with the highest priority endpoint across ALL tiers
-    /// (fast, balanced, deep). This is used as a fallback when rule-based routing
-    /// returns None and LLM routing is not available.
+    /// Uses `routing.default_tier` when it is set and that tier has endpoints.
+    /// Otherwise selects the tier with the highest priority endpoint across ALL
+    /// tiers (fast, balanced, deep). This is used as a fallback when rule-based
+    /// routing returns None and LLM routing is not available.
     ///
     /// # Selection Logic
-    /// 1. Find the maximum priority value across all configured endpoints in all tiers
-    /// 2. Return the first tier (in order: Fast, Balanced, Deep) that has an endpoint with that priority
+    /// 1. If `routing.default_tier` names a tier with endpoints, return it
+    /// 2. Find the maximum priority value across all configured endpoints in all tiers
+    /// 3. Return the first tier (in order: Fast, Balanced, Deep) that has an endpoint with that priority
     ///
     /// # Returns
     /// Returns `Some(TargetModel)` with the tier of the highest-priority endpoint,
@@ -259,6 +610,12 @@ impl ModelSelector {
     /// default_tier() returns Deep (priority 3 is highest)
     /// ```
     pub fn default_tier(&self) -> Option<TargetModel> {
+        if let Some(tier) = self.config.routing.default_tier
+            && self.endpoint_count(tier) > 0
+        {
+            return Some(tier);
+        }
+
         // Find max priority across all tiers
         let all_endpoints = self
             .config
@@ -306,14 +663,90 @@ impl ModelSelector {
     }
 }
 
+/// Pick the endpoint whose cumulative weight range contains `random_weight`
+///
+/// The weighted step of [`ModelSelector::select`], without the randomness:
+/// endpoint `i` owns `[w0 + .. + w(i-1), w0 + .. + wi)`, so a `random_weight`
+/// drawn uniformly from `[0, total_weight)` picks each endpoint in proportion
+/// to its weight. A value at or past the last boundary (floating-point
+/// rounding can leave the summed weights just below the drawn value) falls
+/// back to the last endpoint.
+///
+/// # Panics
+/// Panics if `endpoints` is empty; `select` only calls it with at least one.
+pub fn select_by_random_weight<'a>(
+    endpoints: &[&'a ModelEndpoint],
+    random_weight: f64,
+) -> &'a ModelEndpoint {
+    let mut cumulative_weight = 0.0;
+    for endpoint in endpoints {
+        cumulative_weight += endpoint.weight();
+        if random_weight < cumulative_weight {
+            return endpoint;
+        }
+    }
+
+    // Fallback: return last endpoint if rounding errors prevent selection
+    let last_endpoint = endpoints
+        .last()
+        .expect("select_by_random_weight requires at least one endpoint");
+    tracing::warn!(
+        endpoint_name = %last_endpoint.name(),
+        random_weight = random_weight,
+        cumulative_weight = cumulative_weight,
+        "Fallback to last endpoint (likely floating-point rounding)"
+    );
+    last_endpoint
+}
+
+/// The endpoints sharing the lowest `cost_per_1k_tokens` (`selection_mode = "cheapest"`)
+///
+/// Endpoints without a rate count as free or as costlier than any priced
+/// endpoint, per `uncosted`. Returns an empty list only for an empty input.
+pub fn cheapest_endpoints<'a>(
+    endpoints: &[&'a ModelEndpoint],
+    uncosted: UncostedEndpoints,
+) -> Vec<&'a ModelEndpoint> {
+    let cost = |endpoint: &ModelEndpoint| match (endpoint.cost_per_1k_tokens(), uncosted) {
+        (Some(rate), _) => rate,
+        (None, UncostedEndpoints::Cheap) => 0.0,
+        (None, UncostedEndpoints::Expensive) => f64::INFINITY,
+    };
+    let lowest = endpoints
+        .iter()
+        .map(|endpoint| cost(endpoint))
+        .fold(f64::INFINITY, f64::min);
+    endpoints
+        .iter()
+        .filter(|endpoint| cost(endpoint) == lowest)
+        .copied()
+        .collect()
+}
+
 // Test modules
 #[cfg(test)]
 mod tests_basic;
 #[cfg(test)]
+mod tests_cheapest;
+#[cfg(test)]
+mod tests_default_tier;
+#[cfg(test)]
+mod tests_effective_weights;
+#[cfg(test)]
 mod tests_exclusion;
 #[cfg(test)]
+mod tests_fallback;
+#[cfg(test)]
+mod tests_named;
+#[cfg(test)]
 mod tests_priority;
 #[cfg(test)]
+mod tests_select_error;
+#[cfg(test)]
+mod tests_spillover;
+#[cfg(test)]
+mod tests_tags;
+#[cfg(test)]
 mod tests_weighted;
 
 /// Shared test helper: Create standard test configuration
@@ -0,0 +1,151 @@
+//! Cost-based selection tests
+//!
+//! Tests that `selection_mode = "cheapest"` prefers the lowest
+//! `cost_per_1k_tokens` in the top priority group, that equal prices fall
+//! through to weighted selection, and how unpriced endpoints are ranked.
+
+use super::*;
+use crate::models::endpoint_name::ExclusionSet;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+fn test_metrics() -> Arc<crate::metrics::Metrics> {
+    Arc::new(crate::metrics::Metrics::new().expect("should create metrics"))
+}
+
+/// Fast tier: two endpoints at 0.5, one at 2.0 and one unpriced, all priority 1
+fn create_costed_config(routing: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+
+[[models.fast]]
+name = "fast-cheap-a"
+base_url = "http://localhost:1234/v1"
+max_tokens = 2048
+cost_per_1k_tokens = 0.5
+
+[[models.fast]]
+name = "fast-cheap-b"
+base_url = "http://localhost:1235/v1"
+max_tokens = 2048
+cost_per_1k_tokens = 0.5
+
+[[models.fast]]
+name = "fast-pricey"
+base_url = "http://localhost:1236/v1"
+max_tokens = 2048
+cost_per_1k_tokens = 2.0
+
+[[models.fast]]
+name = "fast-unpriced"
+base_url = "http://localhost:1237/v1"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "http://localhost:1238/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:1239/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+{routing}
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+/// Names of the endpoints picked over 200 selections
+async fn selected_names(selector: &ModelSelector, exclude: &ExclusionSet) -> HashSet<String> {
+    let mut names = HashSet::new();
+    for _ in 0..200 {
+        let endpoint = selector
+            .select(TargetModel::Fast, exclude)
+            .await
+            .expect("fast tier should have an endpoint");
+        names.insert(endpoint.name().to_string());
+    }
+    names
+}
+
+#[tokio::test]
+async fn test_cheapest_prefers_lowest_cost_and_ties_use_weighted_selection() {
+    let config = Arc::new(create_costed_config("selection_mode = \"cheapest\""));
+    let selector = ModelSelector::new(config, test_metrics());
+
+    let names = selected_names(&selector, &ExclusionSet::new()).await;
+
+    // Both 0.5 endpoints share the traffic; pricier and unpriced ones get none
+    let expected: HashSet<String> = ["fast-cheap-a", "fast-cheap-b"]
+        .iter()
+        .map(|n| n.to_string())
+        .collect();
+    assert_eq!(names, expected);
+}
+
+#[tokio::test]
+async fn test_cheapest_moves_to_next_price_when_cheapest_unavailable() {
+    let config = Arc::new(create_costed_config("selection_mode = \"cheapest\""));
+    let selector = ModelSelector::new(config, test_metrics());
+
+    let mut exclude = ExclusionSet::new();
+    exclude.insert(EndpointName::from("fast-cheap-a"));
+    exclude.insert(EndpointName::from("fast-cheap-b"));
+    let names = selected_names(&selector, &exclude).await;
+    assert_eq!(names, HashSet::from(["fast-pricey".to_string()]));
+
+    // Unpriced endpoints count as the most expensive by default
+    exclude.insert(EndpointName::from("fast-pricey"));
+    let names = selected_names(&selector, &exclude).await;
+    assert_eq!(names, HashSet::from(["fast-unpriced".to_string()]));
+}
+
+#[tokio::test]
+async fn test_cheapest_with_cheap_uncosted_prefers_unpriced_endpoint() {
+    let config = Arc::new(create_costed_config(
+        "selection_mode = \"cheapest\"\nuncosted_endpoints = \"cheap\"",
+    ));
+    let selector = ModelSelector::new(config, test_metrics());
+
+    let names = selected_names(&selector, &ExclusionSet::new()).await;
+    assert_eq!(names, HashSet::from(["fast-unpriced".to_string()]));
+}
+
+#[tokio::test]
+async fn test_weighted_mode_ignores_cost() {
+    let config = Arc::new(create_costed_config(""));
+    let selector = ModelSelector::new(config, test_metrics());
+
+    let names = selected_names(&selector, &ExclusionSet::new()).await;
+    assert_eq!(
+        names.len(),
+        4,
+        "every endpoint should be picked, got {:?}",
+        names
+    );
+}
+
+#[test]
+fn test_cheapest_endpoints_keeps_every_tie() {
+    let config = create_costed_config("");
+    let endpoints: Vec<&ModelEndpoint> = config.models.fast.iter().collect();
+
+    let cheapest = cheapest_endpoints(&endpoints, UncostedEndpoints::Expensive);
+    let names: Vec<&str> = cheapest.iter().map(|e| e.name()).collect();
+    assert_eq!(names, ["fast-cheap-a", "fast-cheap-b"]);
+
+    // With only unpriced endpoints left, they are all tied
+    let unpriced: Vec<&ModelEndpoint> = endpoints[3..].to_vec();
+    assert_eq!(
+        cheapest_endpoints(&unpriced, UncostedEndpoints::Expensive).len(),
+        1
+    );
+    assert!(cheapest_endpoints(&[], UncostedEndpoints::Cheap).is_empty());
+}
@@ -0,0 +1,66 @@
+//! Default tier tests
+//!
+//! Tests that default_tier honors an explicit routing.default_tier and falls
+//! back to the highest-priority tier when it is unset.
+
+use super::*;
+use std::sync::Arc;
+
+fn test_metrics() -> Arc<crate::metrics::Metrics> {
+    Arc::new(crate::metrics::Metrics::new().expect("should create metrics"))
+}
+
+/// Deep tier carries the highest priority; `extra_routing` is appended to [routing]
+fn create_priority_config(extra_routing: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+
+[[models.fast]]
+name = "fast-1"
+base_url = "http://localhost:1234/v1"
+max_tokens = 2048
+priority = 1
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "http://localhost:1235/v1"
+max_tokens = 4096
+priority = 2
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:1236/v1"
+max_tokens = 8192
+priority = 3
+
+[routing]
+strategy = "rule"
+{extra_routing}
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+#[tokio::test]
+async fn test_default_tier_unset_uses_highest_priority_tier() {
+    let config = Arc::new(create_priority_config(""));
+    assert_eq!(config.routing.default_tier, None);
+    let selector = ModelSelector::new(config, test_metrics());
+
+    assert_eq!(selector.default_tier(), Some(TargetModel::Deep));
+}
+
+#[tokio::test]
+async fn test_explicit_default_tier_is_honored() {
+    let config = Arc::new(create_priority_config(r#"default_tier = "fast""#));
+    let selector = ModelSelector::new(config, test_metrics());
+
+    assert_eq!(
+        selector.default_tier(),
+        Some(TargetModel::Fast),
+        "explicit default_tier should win over endpoint priorities"
+    );
+}
@@ -0,0 +1,125 @@
+//! Effective weight tests
+//!
+//! Tests that effective_weights reports each endpoint's traffic percentage
+//! within its priority group, matching the configured weights.
+
+use super::*;
+use std::sync::Arc;
+
+fn test_metrics() -> Arc<crate::metrics::Metrics> {
+    Arc::new(crate::metrics::Metrics::new().expect("should create metrics"))
+}
+
+/// Fast tier: weights 1, 2, 3 at priority 2 plus weights 1, 3 at priority 1
+fn create_weighted_config() -> Config {
+    let toml = r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+
+[[models.fast]]
+name = "fast-1"
+base_url = "http://localhost:1234/v1"
+max_tokens = 2048
+weight = 1.0
+priority = 2
+
+[[models.fast]]
+name = "fast-2"
+base_url = "http://localhost:1235/v1"
+max_tokens = 2048
+weight = 2.0
+priority = 2
+
+[[models.fast]]
+name = "fast-3"
+base_url = "http://localhost:1236/v1"
+max_tokens = 2048
+weight = 3.0
+priority = 2
+
+[[models.fast]]
+name = "fast-backup-1"
+base_url = "http://localhost:1237/v1"
+max_tokens = 2048
+weight = 1.0
+priority = 1
+
+[[models.fast]]
+name = "fast-backup-2"
+base_url = "http://localhost:1238/v1"
+max_tokens = 2048
+weight = 3.0
+priority = 1
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "http://localhost:1239/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:1240/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#;
+    toml::from_str(toml).expect("should parse TOML config")
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 0.01,
+        "expected {:.2}%, got {:.2}%",
+        expected,
+        actual
+    );
+}
+
+#[tokio::test]
+async fn test_effective_weights_match_configured_weights() {
+    let selector = ModelSelector::new(Arc::new(create_weighted_config()), test_metrics());
+
+    let weights = selector.effective_weights(TargetModel::Fast);
+    let names: Vec<&str> = weights.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(
+        names,
+        [
+            "fast-1",
+            "fast-2",
+            "fast-3",
+            "fast-backup-1",
+            "fast-backup-2"
+        ]
+    );
+
+    assert_close(weights[0].1, 100.0 / 6.0);
+    assert_close(weights[1].1, 200.0 / 6.0);
+    assert_close(weights[2].1, 50.0);
+    assert_close(weights[3].1, 25.0);
+    assert_close(weights[4].1, 75.0);
+}
+
+#[tokio::test]
+async fn test_effective_weights_sum_to_100_per_priority_group() {
+    let config = Arc::new(create_weighted_config());
+    let selector = ModelSelector::new(config.clone(), test_metrics());
+
+    for tier in [TargetModel::Fast, TargetModel::Balanced, TargetModel::Deep] {
+        let endpoints = config.models.tier(tier);
+        let weights = selector.effective_weights(tier);
+        let priorities: std::collections::BTreeSet<u8> =
+            endpoints.iter().map(|e| e.priority()).collect();
+
+        for priority in priorities {
+            let group_total: f64 = endpoints
+                .iter()
+                .zip(&weights)
+                .filter(|(endpoint, _)| endpoint.priority() == priority)
+                .map(|(_, (_, percent))| percent)
+                .sum();
+            assert_close(group_total, 100.0);
+        }
+    }
+}
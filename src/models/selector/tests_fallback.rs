@@ -0,0 +1,84 @@
+//! Cross-tier fallback tests
+//!
+//! Tests select_with_fallback: serving from lower tiers when the target tier is
+//! unavailable, and the octoroute_tier_fallback_total metric labels.
+
+use super::*;
+use crate::metrics::{Metrics, Tier};
+use crate::models::endpoint_name::{EndpointName, ExclusionSet};
+use std::sync::Arc;
+
+fn selector_with_metrics() -> (ModelSelector, Arc<Metrics>) {
+    let metrics = Arc::new(Metrics::new().expect("should create metrics"));
+    let config = Arc::new(create_test_config());
+    (ModelSelector::new(config, metrics.clone()), metrics)
+}
+
+fn exclude(names: &[&str]) -> ExclusionSet {
+    let mut set = ExclusionSet::new();
+    for name in names {
+        set.insert(EndpointName::from(*name));
+    }
+    set
+}
+
+#[tokio::test]
+async fn test_fallback_not_used_when_target_available() {
+    let (selector, metrics) = selector_with_metrics();
+
+    let (tier, endpoint) = selector
+        .select_with_fallback(TargetModel::Deep, &ExclusionSet::new())
+        .await
+        .expect("deep tier should be available");
+
+    assert_eq!(tier, TargetModel::Deep);
+    assert_eq!(endpoint.name(), "deep-1");
+    assert_eq!(metrics.tier_fallback_count(Tier::Deep, Tier::Balanced), 0);
+    assert_eq!(metrics.tier_fallback_count(Tier::Deep, Tier::Fast), 0);
+}
+
+#[tokio::test]
+async fn test_fallback_serves_next_lower_tier_and_records_labels() {
+    let (selector, metrics) = selector_with_metrics();
+
+    let (tier, endpoint) = selector
+        .select_with_fallback(TargetModel::Deep, &exclude(&["deep-1"]))
+        .await
+        .expect("should fall back to balanced");
+
+    assert_eq!(tier, TargetModel::Balanced);
+    assert_eq!(endpoint.name(), "balanced-1");
+    assert_eq!(metrics.tier_fallback_count(Tier::Deep, Tier::Balanced), 1);
+    assert_eq!(metrics.tier_fallback_count(Tier::Deep, Tier::Fast), 0);
+}
+
+#[tokio::test]
+async fn test_fallback_skips_unavailable_intermediate_tier() {
+    let (selector, metrics) = selector_with_metrics();
+
+    let (tier, _) = selector
+        .select_with_fallback(TargetModel::Deep, &exclude(&["deep-1", "balanced-1"]))
+        .await
+        .expect("should fall back to fast");
+
+    assert_eq!(tier, TargetModel::Fast);
+    assert_eq!(metrics.tier_fallback_count(Tier::Deep, Tier::Fast), 1);
+    assert_eq!(metrics.tier_fallback_count(Tier::Deep, Tier::Balanced), 0);
+}
+
+#[tokio::test]
+async fn test_fast_tier_never_falls_back() {
+    let (selector, metrics) = selector_with_metrics();
+
+    let result = selector
+        .select_with_fallback(TargetModel::Fast, &exclude(&["fast-1", "fast-2"]))
+        .await;
+
+    assert!(result.is_none(), "Fast has no lower tier to fall back to");
+    assert!(
+        !metrics
+            .gather()
+            .unwrap()
+            .contains("octoroute_tier_fallback_total{")
+    );
+}
@@ -0,0 +1,95 @@
+//! Explicit selection by endpoint name
+//!
+//! Tests that select_named finds endpoints in every tier, respects health and
+//! exclusion, and tells unknown names apart from unavailable endpoints.
+
+use super::*;
+use crate::models::endpoint_name::ExclusionSet;
+use std::sync::Arc;
+
+fn test_metrics() -> Arc<crate::metrics::Metrics> {
+    Arc::new(crate::metrics::Metrics::new().expect("should create metrics"))
+}
+
+fn selector() -> ModelSelector {
+    ModelSelector::new(Arc::new(create_test_config()), test_metrics())
+}
+
+#[tokio::test]
+async fn test_select_named_finds_endpoint_in_each_tier() {
+    let selector = selector();
+    let exclude = ExclusionSet::new();
+
+    for (name, tier) in [
+        ("fast-2", TargetModel::Fast),
+        ("balanced-1", TargetModel::Balanced),
+        ("deep-1", TargetModel::Deep),
+    ] {
+        let (endpoint, selected_tier) = selector
+            .select_named(name, &exclude)
+            .await
+            .expect("healthy named endpoint should be selected");
+        assert_eq!(endpoint.name(), name);
+        assert_eq!(selected_tier, tier);
+    }
+}
+
+#[tokio::test]
+async fn test_select_named_rejects_unhealthy_endpoint() {
+    let selector = selector();
+    for _ in 0..3 {
+        selector
+            .health_checker()
+            .mark_failure("fast-1")
+            .await
+            .expect("mark_failure should succeed");
+    }
+
+    let err = selector
+        .select_named("fast-1", &ExclusionSet::new())
+        .await
+        .expect_err("unhealthy endpoint must not be selected");
+
+    match err {
+        AppError::EndpointsUnavailable { message, .. } => {
+            assert!(message.contains("fast-1") && message.contains("unhealthy"));
+        }
+        other => panic!("expected EndpointsUnavailable, got: {:?}", other),
+    }
+
+    // Its healthy sibling is unaffected, and is never substituted for it
+    let (endpoint, _) = selector
+        .select_named("fast-2", &ExclusionSet::new())
+        .await
+        .expect("healthy sibling should still be selectable");
+    assert_eq!(endpoint.name(), "fast-2");
+}
+
+#[tokio::test]
+async fn test_select_named_rejects_excluded_endpoint() {
+    let selector = selector();
+    let mut exclude = ExclusionSet::new();
+    exclude.insert(EndpointName::from("deep-1"));
+
+    let err = selector
+        .select_named("deep-1", &exclude)
+        .await
+        .expect_err("excluded endpoint must not be selected");
+
+    assert!(matches!(err, AppError::EndpointsUnavailable { .. }));
+}
+
+#[tokio::test]
+async fn test_select_named_unknown_name_is_validation_error() {
+    let selector = selector();
+
+    let err = selector
+        .select_named("nonexistent", &ExclusionSet::new())
+        .await
+        .expect_err("unknown name should fail");
+
+    match err {
+        AppError::Validation(message) => assert!(message.contains("not found")),
+        other => panic!("expected Validation, got: {:?}", other),
+    }
+}
@@ -0,0 +1,181 @@
+//! Selection failure reason tests
+//!
+//! Tests that `try_select` reports why nothing was selected, for each
+//! combination of configured, unhealthy, excluded and saturated endpoints.
+
+use super::*;
+use crate::models::endpoint_name::ExclusionSet;
+use std::sync::Arc;
+
+fn test_metrics() -> Arc<crate::metrics::Metrics> {
+    Arc::new(crate::metrics::Metrics::new().expect("should create metrics"))
+}
+
+async fn mark_unhealthy(selector: &ModelSelector, name: &str) {
+    for _ in 0..3 {
+        selector
+            .health_checker()
+            .mark_failure(name)
+            .await
+            .expect("mark_failure should succeed");
+    }
+}
+
+fn exclusions(names: &[&str]) -> ExclusionSet {
+    names.iter().map(|name| EndpointName::from(*name)).collect()
+}
+
+#[tokio::test]
+async fn test_empty_tier_reports_no_endpoints_configured() {
+    let toml_config = r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+
+[models]
+fast = []
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "http://localhost:1236/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:1237/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+router_tier = "balanced"
+"#;
+    let config: Config = toml::from_str(toml_config).expect("should parse TOML");
+    let selector = ModelSelector::new(Arc::new(config), test_metrics());
+
+    let result = selector
+        .try_select(TargetModel::Fast, &ExclusionSet::new())
+        .await;
+    assert_eq!(result.unwrap_err(), SelectError::NoEndpointsConfigured);
+}
+
+#[tokio::test]
+async fn test_every_endpoint_unhealthy_reports_all_unhealthy() {
+    let selector = ModelSelector::new(Arc::new(create_test_config()), test_metrics());
+    mark_unhealthy(&selector, "fast-1").await;
+    mark_unhealthy(&selector, "fast-2").await;
+
+    let result = selector
+        .try_select(TargetModel::Fast, &ExclusionSet::new())
+        .await;
+    assert_eq!(result.unwrap_err(), SelectError::AllUnhealthy);
+}
+
+#[tokio::test]
+async fn test_every_endpoint_excluded_reports_all_excluded() {
+    let selector = ModelSelector::new(Arc::new(create_test_config()), test_metrics());
+    // An unhealthy endpoint that is also excluded still counts as excluded
+    mark_unhealthy(&selector, "fast-2").await;
+
+    let result = selector
+        .try_select(TargetModel::Fast, &exclusions(&["fast-1", "fast-2"]))
+        .await;
+    assert_eq!(result.unwrap_err(), SelectError::AllExcluded);
+}
+
+#[tokio::test]
+async fn test_unhealthy_and_excluded_reports_mixed() {
+    let selector = ModelSelector::new(Arc::new(create_test_config()), test_metrics());
+    mark_unhealthy(&selector, "fast-2").await;
+
+    let result = selector
+        .try_select(TargetModel::Fast, &exclusions(&["fast-1"]))
+        .await;
+    assert_eq!(
+        result.unwrap_err(),
+        SelectError::MixedUnavailable {
+            healthy: 0,
+            excluded: 1
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_saturated_endpoint_counts_as_healthy_in_mixed() {
+    let toml_config = r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+
+[[models.fast]]
+name = "fast-1"
+base_url = "http://localhost:1234/v1"
+max_tokens = 2048
+max_in_flight = 1
+
+[[models.fast]]
+name = "fast-2"
+base_url = "http://localhost:1235/v1"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "http://localhost:1236/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:1237/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#;
+    let config: Config = toml::from_str(toml_config).expect("should parse TOML");
+    let selector = ModelSelector::new(Arc::new(config), test_metrics());
+    let _held = selector.in_flight().acquire("fast-1");
+
+    let result = selector
+        .try_select(TargetModel::Fast, &exclusions(&["fast-2"]))
+        .await;
+    assert_eq!(
+        result.unwrap_err(),
+        SelectError::MixedUnavailable {
+            healthy: 1,
+            excluded: 1
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_select_discards_the_reason() {
+    let selector = ModelSelector::new(Arc::new(create_test_config()), test_metrics());
+
+    let excluded = exclusions(&["fast-1", "fast-2"]);
+    assert!(
+        selector
+            .select(TargetModel::Fast, &excluded)
+            .await
+            .is_none()
+    );
+
+    let endpoint = selector
+        .try_select(TargetModel::Fast, &exclusions(&["fast-1"]))
+        .await
+        .expect("fast-2 is still available");
+    assert_eq!(endpoint.name(), "fast-2");
+}
+
+#[test]
+fn test_reason_labels() {
+    assert_eq!(SelectError::NoEndpointsConfigured.reason(), "no_endpoints");
+    assert_eq!(SelectError::AllUnhealthy.reason(), "all_unhealthy");
+    assert_eq!(SelectError::AllExcluded.reason(), "all_excluded");
+    assert_eq!(
+        SelectError::MixedUnavailable {
+            healthy: 1,
+            excluded: 1
+        }
+        .reason(),
+        "mixed"
+    );
+}
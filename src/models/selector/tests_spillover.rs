@@ -0,0 +1,131 @@
+//! In-flight limit and priority spillover tests
+//!
+//! Tests that endpoints at their max_in_flight limit are skipped, and that a
+//! saturated top priority group spills to the next group only when
+//! routing.spillover is enabled.
+
+use super::*;
+use crate::models::endpoint_name::ExclusionSet;
+use std::sync::Arc;
+
+fn test_metrics() -> Arc<crate::metrics::Metrics> {
+    Arc::new(crate::metrics::Metrics::new().expect("should create metrics"))
+}
+
+/// Fast tier: primary (priority 2, max_in_flight 1) and backup (priority 1, unlimited)
+fn create_spillover_config(spillover: bool) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+
+[[models.fast]]
+name = "fast-primary"
+base_url = "http://localhost:1234/v1"
+max_tokens = 2048
+priority = 2
+max_in_flight = 1
+
+[[models.fast]]
+name = "fast-backup"
+base_url = "http://localhost:1235/v1"
+max_tokens = 2048
+priority = 1
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "http://localhost:1236/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:1237/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+spillover = {spillover}
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+#[tokio::test]
+async fn test_top_priority_group_used_while_below_limit() {
+    for spillover in [false, true] {
+        let selector =
+            ModelSelector::new(Arc::new(create_spillover_config(spillover)), test_metrics());
+
+        let endpoint = selector
+            .select(TargetModel::Fast, &ExclusionSet::new())
+            .await
+            .expect("should select an endpoint");
+        assert_eq!(endpoint.name(), "fast-primary");
+    }
+}
+
+#[tokio::test]
+async fn test_saturated_top_group_spills_to_next_priority_group() {
+    let selector = ModelSelector::new(Arc::new(create_spillover_config(true)), test_metrics());
+    let _held = selector.in_flight().acquire("fast-primary");
+
+    let endpoint = selector
+        .select(TargetModel::Fast, &ExclusionSet::new())
+        .await
+        .expect("should spill over to the backup group");
+    assert_eq!(endpoint.name(), "fast-backup");
+}
+
+#[tokio::test]
+async fn test_saturated_top_group_fails_without_spillover() {
+    let selector = ModelSelector::new(Arc::new(create_spillover_config(false)), test_metrics());
+    let _held = selector.in_flight().acquire("fast-primary");
+
+    let result = selector
+        .select(TargetModel::Fast, &ExclusionSet::new())
+        .await;
+    assert!(
+        result.is_none(),
+        "Without spillover a saturated top group must not fall to lower priority"
+    );
+}
+
+#[tokio::test]
+async fn test_released_slot_makes_endpoint_selectable_again() {
+    let selector = ModelSelector::new(Arc::new(create_spillover_config(false)), test_metrics());
+
+    let held = selector.in_flight().acquire("fast-primary");
+    assert!(
+        selector
+            .select(TargetModel::Fast, &ExclusionSet::new())
+            .await
+            .is_none()
+    );
+
+    drop(held);
+    let endpoint = selector
+        .select(TargetModel::Fast, &ExclusionSet::new())
+        .await
+        .expect("slot was released");
+    assert_eq!(endpoint.name(), "fast-primary");
+}
+
+#[tokio::test]
+async fn test_unhealthy_top_group_still_falls_back_without_spillover() {
+    // Spillover only governs capacity; health-based priority fallback is unchanged
+    let selector = ModelSelector::new(Arc::new(create_spillover_config(false)), test_metrics());
+    for _ in 0..3 {
+        selector
+            .health_checker()
+            .mark_failure("fast-primary")
+            .await
+            .unwrap();
+    }
+
+    let endpoint = selector
+        .select(TargetModel::Fast, &ExclusionSet::new())
+        .await
+        .expect("unhealthy primary should fall back to backup");
+    assert_eq!(endpoint.name(), "fast-backup");
+}
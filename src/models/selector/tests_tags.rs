@@ -0,0 +1,155 @@
+//! Tag-filtered selection tests
+//!
+//! Tests that select_with_tags only returns endpoints carrying every required
+//! tag, and that an empty requirement behaves like plain select.
+
+use super::*;
+use crate::models::endpoint_name::ExclusionSet;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+fn test_metrics() -> Arc<crate::metrics::Metrics> {
+    Arc::new(crate::metrics::Metrics::new().expect("should create metrics"))
+}
+
+/// Fast tier: a general endpoint, a code endpoint, and a local code endpoint
+fn create_tagged_config() -> Config {
+    let toml = r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+
+[[models.fast]]
+name = "fast-general"
+base_url = "http://localhost:1234/v1"
+max_tokens = 2048
+
+[[models.fast]]
+name = "fast-code"
+base_url = "http://localhost:1235/v1"
+max_tokens = 2048
+tags = ["code"]
+
+[[models.fast]]
+name = "fast-code-local"
+base_url = "http://localhost:1236/v1"
+max_tokens = 2048
+tags = ["code", "local"]
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "http://localhost:1237/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:1238/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#;
+    toml::from_str(toml).expect("should parse TOML config")
+}
+
+fn tags(values: &[&str]) -> Vec<String> {
+    values.iter().map(|t| t.to_string()).collect()
+}
+
+#[tokio::test]
+async fn test_select_with_tags_only_returns_tagged_endpoints() {
+    let selector = ModelSelector::new(Arc::new(create_tagged_config()), test_metrics());
+    let exclude = ExclusionSet::new();
+    let required = tags(&["code"]);
+
+    let mut selected = HashSet::new();
+    for _ in 0..100 {
+        let endpoint = selector
+            .select_with_tags(TargetModel::Fast, &exclude, &required)
+            .await
+            .expect("tagged endpoints are available");
+        selected.insert(endpoint.name().to_string());
+    }
+
+    assert_eq!(
+        selected,
+        HashSet::from(["fast-code".to_string(), "fast-code-local".to_string()]),
+        "Only endpoints tagged 'code' should be selected"
+    );
+}
+
+#[tokio::test]
+async fn test_select_with_tags_requires_all_tags() {
+    let selector = ModelSelector::new(Arc::new(create_tagged_config()), test_metrics());
+    let exclude = ExclusionSet::new();
+
+    for _ in 0..20 {
+        let endpoint = selector
+            .select_with_tags(TargetModel::Fast, &exclude, &tags(&["code", "local"]))
+            .await
+            .expect("one endpoint has both tags");
+        assert_eq!(endpoint.name(), "fast-code-local");
+    }
+
+    assert!(
+        selector
+            .select_with_tags(TargetModel::Fast, &exclude, &tags(&["code", "gpu"]))
+            .await
+            .is_none(),
+        "No endpoint carries both tags"
+    );
+}
+
+#[tokio::test]
+async fn test_select_with_tags_respects_health_and_exclusion() {
+    let selector = ModelSelector::new(Arc::new(create_tagged_config()), test_metrics());
+    let required = tags(&["code"]);
+
+    for _ in 0..3 {
+        selector
+            .health_checker()
+            .mark_failure("fast-code")
+            .await
+            .expect("mark_failure should succeed");
+    }
+    let mut exclude = ExclusionSet::new();
+    exclude.insert(EndpointName::from("fast-code-local"));
+
+    assert!(
+        selector
+            .select_with_tags(TargetModel::Fast, &exclude, &required)
+            .await
+            .is_none(),
+        "Tagged endpoints are unhealthy or excluded; untagged ones must not be used"
+    );
+}
+
+#[tokio::test]
+async fn test_empty_tag_requirement_behaves_like_select() {
+    let selector = ModelSelector::new(Arc::new(create_tagged_config()), test_metrics());
+    let exclude = ExclusionSet::new();
+
+    let mut selected = HashSet::new();
+    for _ in 0..200 {
+        let endpoint = selector
+            .select_with_tags(TargetModel::Fast, &exclude, &[])
+            .await
+            .expect("fast tier has endpoints");
+        selected.insert(endpoint.name().to_string());
+    }
+
+    assert_eq!(
+        selected.len(),
+        3,
+        "Empty tag requirement should consider every endpoint, like select()"
+    );
+}
+
+#[tokio::test]
+async fn test_tier_has_tags() {
+    let selector = ModelSelector::new(Arc::new(create_tagged_config()), test_metrics());
+
+    assert!(selector.tier_has_tags(TargetModel::Fast, &tags(&["code"])));
+    assert!(!selector.tier_has_tags(TargetModel::Balanced, &tags(&["code"])));
+    assert!(selector.tier_has_tags(TargetModel::Balanced, &[]));
+}
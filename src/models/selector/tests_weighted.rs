@@ -517,3 +517,82 @@ router_tier = "balanced"
         (heavy_count as f64 / SAMPLE_SIZE as f64) * 100.0
     );
 }
+
+// Cumulative-weight step (select_by_random_weight), no randomness involved
+
+/// Fast endpoints weighted 3.0, 2.0, 1.0: ranges [0, 3), [3, 5), [5, 6)
+fn weighted_fast_endpoints() -> Vec<ModelEndpoint> {
+    let toml_config = r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+
+[[models.fast]]
+name = "fast-1"
+base_url = "http://localhost:1234/v1"
+max_tokens = 2048
+weight = 3.0
+
+[[models.fast]]
+name = "fast-2"
+base_url = "http://localhost:1235/v1"
+max_tokens = 2048
+weight = 2.0
+
+[[models.fast]]
+name = "fast-3"
+base_url = "http://localhost:1236/v1"
+max_tokens = 2048
+weight = 1.0
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "http://localhost:1237/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:1238/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#;
+    let config: Config = toml::from_str(toml_config).expect("should parse TOML");
+    config.models.fast
+}
+
+#[test]
+fn test_select_by_random_weight_cumulative_boundaries() {
+    let endpoints = weighted_fast_endpoints();
+    let refs: Vec<&ModelEndpoint> = endpoints.iter().collect();
+    let pick = |random_weight: f64| select_by_random_weight(&refs, random_weight).name();
+
+    assert_eq!(pick(0.0), "fast-1");
+    assert_eq!(pick(2.999), "fast-1");
+    // Ranges are half-open: a boundary belongs to the next endpoint
+    assert_eq!(pick(3.0), "fast-2");
+    assert_eq!(pick(4.999), "fast-2");
+    assert_eq!(pick(5.0), "fast-3");
+    assert_eq!(pick(5.999), "fast-3");
+}
+
+#[test]
+fn test_select_by_random_weight_falls_back_to_last_endpoint() {
+    let endpoints = weighted_fast_endpoints();
+    let refs: Vec<&ModelEndpoint> = endpoints.iter().collect();
+
+    // At or past the total weight no range matches (what rounding can produce)
+    assert_eq!(select_by_random_weight(&refs, 6.0).name(), "fast-3");
+    assert_eq!(select_by_random_weight(&refs, 6.0 + 1e-9).name(), "fast-3");
+}
+
+#[test]
+fn test_select_by_random_weight_single_endpoint() {
+    let endpoints = weighted_fast_endpoints();
+    let refs = vec![&endpoints[1]];
+
+    assert_eq!(select_by_random_weight(&refs, 0.0).name(), "fast-2");
+    assert_eq!(select_by_random_weight(&refs, 1.999).name(), "fast-2");
+    assert_eq!(select_by_random_weight(&refs, 2.0).name(), "fast-2");
+}
@@ -0,0 +1,254 @@
+//! Config reload without a restart
+//!
+//! SIGHUP and `POST /admin/reload` both go through [`ReloadableState::reload`];
+//! `POST /admin/routing/strategy` swaps only the router, through
+//! [`ReloadableState::switch_routing_strategy`].
+//! The config file (with any `--config-override` file merged over it) is read
+//! and validated first; only when that succeeds is a new [`AppState`] built
+//! and swapped in, so a broken file leaves the running config in place. Requests already in flight finish on the state they
+//! started with, and every request after the swap sees the new one.
+//!
+//! Metrics, the server drain flag, endpoint drains, the idempotency cache, the
+//! routing observer, the task classifier, and the in-flight counts of
+//! endpoints that keep their name survive a reload. The tier concurrency
+//! budgets, sticky sessions, and per-user request counters survive too unless
+//! their config section (`[server.tier_concurrency]` with `max_queue_wait_ms`,
+//! `routing.sticky_session_ttl_seconds`, `[server.user_tracking]`) changed, in
+//! which case they start empty. Endpoint health starts over, as at
+//! startup, except that endpoints already verified healthy at an unchanged URL
+//! stay verified for `/readyz`. Settings that shape the listeners themselves are reported in
+//! [`ReloadSummary::restart_required`] and keep their old values until the
+//! process restarts.
+
+use crate::config::{Config, RoutingStrategy};
+use crate::error::{AppError, AppResult};
+use crate::handlers::AppState;
+use axum::extract::FromRef;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+/// Application state that can be replaced while the server runs
+///
+/// This is the state of the routers built by [`crate::server`]. Handlers keep
+/// extracting `State<AppState>`, which resolves to the state current when the
+/// request arrived.
+#[derive(Clone)]
+pub struct ReloadableState {
+    current: Arc<RwLock<AppState>>,
+    /// File the config is re-read from; `None` for states built in code
+    config_path: Option<Arc<PathBuf>>,
+    /// Partial config merged over `config_path` on every reload (`--config-override`)
+    config_override: Option<Arc<PathBuf>>,
+    /// Serializes reloads so two of them never interleave their swaps
+    reloading: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl ReloadableState {
+    /// Wrap `state`, reloading from the config file at `config_path`
+    pub fn new(state: AppState, config_path: impl Into<PathBuf>) -> Self {
+        Self {
+            current: Arc::new(RwLock::new(state)),
+            config_path: Some(Arc::new(config_path.into())),
+            config_override: None,
+            reloading: Arc::new(tokio::sync::Mutex::new(())),
+        }
+    }
+
+    /// Merge `override_path` over the config file on each reload (builder pattern)
+    pub fn with_config_override(mut self, override_path: Option<impl Into<PathBuf>>) -> Self {
+        self.config_override = override_path.map(|path| Arc::new(path.into()));
+        self
+    }
+
+    /// The state requests arriving now are served with
+    pub fn current(&self) -> AppState {
+        self.current
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    /// Re-read the config file and swap in state built from it
+    ///
+    /// # Errors
+    /// Returns the load or validation error when the file cannot be used, and
+    /// [`AppError::Config`] when this state has no config file. The running
+    /// state is unchanged in both cases.
+    pub async fn reload(&self) -> AppResult<ReloadSummary> {
+        let Some(path) = self.config_path.as_deref() else {
+            return Err(AppError::Config(
+                "Config reload is unavailable: the server was not started from a config file"
+                    .to_string(),
+            ));
+        };
+        let _reloading = self.reloading.lock().await;
+
+        let config = Config::load(path, self.config_override.as_deref().map(PathBuf::as_path))?;
+        let old = self.current();
+        let new = old.reconfigured(Arc::new(config))?;
+
+        // Operator drains outlive a reload for endpoints that still exist, and so
+        // does a passed health check for endpoints still at the same URL, so
+        // `/readyz` doesn't drop out until the next probe round
+        let new_checker = new.selector().health_checker();
+        let new_urls: HashMap<String, String> = new_checker
+            .get_all_statuses()
+            .await
+            .into_iter()
+            .map(|health| (health.name().to_string(), health.base_url().to_string()))
+            .collect();
+        for health in old.selector().health_checker().get_all_statuses().await {
+            if health.is_drained() {
+                let _ = new_checker.set_drained(health.name(), true).await;
+            }
+            if health.is_verified()
+                && health.consecutive_failures() == 0
+                && new_urls.get(health.name()).map(String::as_str) == Some(health.base_url())
+            {
+                let _ = new_checker.mark_success(health.name()).await;
+            }
+        }
+
+        let summary = ReloadSummary::between(old.config(), new.config(), path);
+        *self
+            .current
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = new;
+        old.selector().health_checker().shutdown().await;
+
+        tracing::info!(
+            config_path = %summary.config_path,
+            endpoints = summary.endpoints,
+            added = ?summary.added,
+            removed = ?summary.removed,
+            restart_required = ?summary.restart_required,
+            "Configuration reloaded"
+        );
+        Ok(summary)
+    }
+}
+
+impl ReloadableState {
+    /// Swap in a router for routing `strategy`, keeping everything else
+    ///
+    /// Backs `POST /admin/routing/strategy`. Returns the strategy in use before
+    /// the switch. Requests already in flight finish with the old router. The
+    /// switch lasts until the next reload, which restores the config file's
+    /// strategy.
+    ///
+    /// # Errors
+    /// Returns the router construction error (e.g. a router tier with no
+    /// endpoints) and leaves the running state unchanged.
+    pub async fn switch_routing_strategy(
+        &self,
+        strategy: RoutingStrategy,
+    ) -> AppResult<RoutingStrategy> {
+        let _reloading = self.reloading.lock().await;
+
+        let old = self.current();
+        let previous = old.config().routing.strategy;
+        let new = old.with_routing_strategy(strategy)?;
+        *self
+            .current
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = new;
+
+        tracing::warn!(
+            previous = ?previous,
+            strategy = ?strategy,
+            "Routing strategy switched at runtime; a config reload restores the configured strategy"
+        );
+        Ok(previous)
+    }
+}
+
+/// States built in code (tests, embedding) serve normally but cannot reload
+impl From<AppState> for ReloadableState {
+    fn from(state: AppState) -> Self {
+        Self {
+            current: Arc::new(RwLock::new(state)),
+            config_path: None,
+            config_override: None,
+            reloading: Arc::new(tokio::sync::Mutex::new(())),
+        }
+    }
+}
+
+impl FromRef<ReloadableState> for AppState {
+    fn from_ref(state: &ReloadableState) -> Self {
+        state.current()
+    }
+}
+
+/// What a successful reload changed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReloadSummary {
+    /// File the config was read from
+    pub config_path: String,
+    /// Endpoints configured across all tiers after the reload
+    pub endpoints: usize,
+    /// Endpoint names new in this config
+    pub added: Vec<String>,
+    /// Endpoint names no longer configured
+    pub removed: Vec<String>,
+    /// Changed settings that only apply after a restart
+    pub restart_required: Vec<&'static str>,
+}
+
+impl ReloadSummary {
+    fn between(old: &Config, new: &Config, path: &Path) -> Self {
+        let old_names = endpoint_names(old);
+        let new_names = endpoint_names(new);
+
+        let restart_required = [
+            ("server.host", old.server.host != new.server.host),
+            ("server.port", old.server.port != new.server.port),
+            (
+                "server.max_request_body_bytes",
+                old.server.max_request_body_bytes != new.server.max_request_body_bytes,
+            ),
+            // The compression layer is configured when the routes are built
+            (
+                "server.enable_compression",
+                old.server.enable_compression != new.server.enable_compression,
+            ),
+            // The admin routes are mounted only when a token is set; a changed token applies
+            (
+                "server.admin_token",
+                old.server.admin_token.is_some() != new.server.admin_token.is_some(),
+            ),
+            (
+                "observability.metrics_bind",
+                old.observability.metrics_bind != new.observability.metrics_bind,
+            ),
+            (
+                "observability.log_level",
+                old.observability.log_level != new.observability.log_level,
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(setting, changed)| changed.then_some(setting))
+        .collect();
+
+        Self {
+            config_path: path.display().to_string(),
+            endpoints: new_names.len(),
+            added: new_names.difference(&old_names).cloned().collect(),
+            removed: old_names.difference(&new_names).cloned().collect(),
+            restart_required,
+        }
+    }
+}
+
+fn endpoint_names(config: &Config) -> BTreeSet<String> {
+    config
+        .models
+        .fast
+        .iter()
+        .chain(&config.models.balanced)
+        .chain(&config.models.deep)
+        .map(|endpoint| endpoint.name().to_string())
+        .collect()
+}
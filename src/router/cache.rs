@@ -0,0 +1,298 @@
+//! Storage for cached LLM routing decisions (`[routing.cache]`)
+//!
+//! Asking the router model about a prompt it has already classified costs a
+//! full router query for an answer that is almost always the same. The LLM
+//! router looks each prompt up in a [`RoutingCache`] first and stores fresh
+//! decisions there.
+//!
+//! [`InMemoryRoutingCache`] keeps decisions inside one process. Behind the
+//! `redis` Cargo feature, [`RedisRoutingCache`] stores them in Redis so every
+//! instance of a fleet benefits from a decision any of them made.
+//! [`from_config`] picks the backend named by `routing.cache.backend`.
+
+use super::{RouteMetadata, TargetModel};
+use crate::config::{RoutingCacheBackend, RoutingCacheConfig};
+use crate::error::{AppError, AppResult};
+use crate::shared::prompt_cache_key::{PromptNormalization, fnv1a_64, normalize_prompt_for_cache};
+use crate::shared::ttl_cache::TtlCache;
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Key/value store for routing decisions
+///
+/// Keys come from [`routing_cache_key`]. Implementations must be safe to share
+/// between requests; a failed lookup or store is logged by the router and
+/// treated as a miss, so errors never fail a request.
+#[async_trait]
+pub trait RoutingCache: Send + Sync {
+    /// The live decision stored under `key`, if any
+    async fn get(&self, key: u64) -> AppResult<Option<TargetModel>>;
+
+    /// Store `tier` under `key` for `ttl`, replacing any earlier decision
+    async fn put(&self, key: u64, tier: TargetModel, ttl: Duration) -> AppResult<()>;
+}
+
+/// Cache key for routing `prompt` with `meta`
+///
+/// The router prompt includes the task type and importance, so they are part
+/// of the key; the token estimate follows from the prompt and is left out.
+pub fn routing_cache_key(
+    prompt: &str,
+    meta: &RouteMetadata,
+    normalization: PromptNormalization,
+) -> u64 {
+    fnv1a_64(&format!(
+        "{:?}\n{:?}\n{}",
+        meta.task_type,
+        meta.importance,
+        normalize_prompt_for_cache(prompt, normalization)
+    ))
+}
+
+/// Build the cache `config` describes
+///
+/// # Errors
+/// Returns [`AppError::Config`] for a `redis` backend whose URL doesn't parse,
+/// or when octoroute was built without the `redis` feature.
+pub fn from_config(config: &RoutingCacheConfig) -> AppResult<Arc<dyn RoutingCache>> {
+    match config.backend {
+        RoutingCacheBackend::Memory => Ok(Arc::new(InMemoryRoutingCache::new(config.max_entries))),
+        #[cfg(feature = "redis")]
+        RoutingCacheBackend::Redis => Ok(Arc::new(RedisRoutingCache::new(
+            config.redis_url.as_deref().unwrap_or_default(),
+            config.key_prefix.clone(),
+        )?)),
+        #[cfg(not(feature = "redis"))]
+        RoutingCacheBackend::Redis => Err(AppError::Config(
+            "routing.cache.backend = \"redis\" needs octoroute built with the 'redis' feature"
+                .to_string(),
+        )),
+    }
+}
+
+/// Per-process routing cache, bounded to `max_entries` decisions
+///
+/// When full, expired decisions are dropped first, then the one closest to
+/// expiry (see [`TtlCache`]).
+#[derive(Debug)]
+pub struct InMemoryRoutingCache {
+    entries: TtlCache<u64, TargetModel>,
+}
+
+impl InMemoryRoutingCache {
+    /// Empty cache holding at most `max_entries` decisions
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            // Every entry is stored with its own TTL, so the cache-wide one is unused
+            entries: TtlCache::new(max_entries, Duration::ZERO),
+        }
+    }
+}
+
+#[async_trait]
+impl RoutingCache for InMemoryRoutingCache {
+    async fn get(&self, key: u64) -> AppResult<Option<TargetModel>> {
+        Ok(self.entries.get(&key))
+    }
+
+    async fn put(&self, key: u64, tier: TargetModel, ttl: Duration) -> AppResult<()> {
+        self.entries.insert_with_ttl(key, tier, ttl);
+        Ok(())
+    }
+}
+
+/// Longest a Redis command (or connecting for the first one) may take
+///
+/// A lookup that takes longer fails and is treated as a miss, so a slow or
+/// unreachable Redis costs each routed request at most this much.
+#[cfg(feature = "redis")]
+pub const REDIS_TIMEOUT: Duration = Duration::from_millis(250);
+
+/// Routing cache shared through Redis
+///
+/// Decisions are stored as `{key_prefix}{key:016x}` = `fast`/`balanced`/`deep`
+/// with a Redis expiry, so instances pointing at the same server and prefix
+/// share them. The connection manager is created on first use and reconnects
+/// by itself after the connection drops; every command is bounded by
+/// [`REDIS_TIMEOUT`].
+#[cfg(feature = "redis")]
+pub struct RedisRoutingCache {
+    client: redis::Client,
+    connection: tokio::sync::OnceCell<redis::aio::ConnectionManager>,
+    key_prefix: String,
+}
+
+#[cfg(feature = "redis")]
+impl RedisRoutingCache {
+    /// Cache on the Redis server at `url`, namespacing keys with `key_prefix`
+    ///
+    /// Only parses the URL; no connection is made until the first lookup.
+    pub fn new(url: &str, key_prefix: String) -> AppResult<Self> {
+        let client = redis::Client::open(url).map_err(|e| {
+            AppError::Config(format!(
+                "routing.cache.redis_url '{}' is not a valid Redis URL: {}",
+                url, e
+            ))
+        })?;
+        Ok(Self {
+            client,
+            connection: tokio::sync::OnceCell::new(),
+            key_prefix,
+        })
+    }
+
+    fn redis_key(&self, key: u64) -> String {
+        format!("{}{:016x}", self.key_prefix, key)
+    }
+
+    async fn connection(&self) -> AppResult<redis::aio::ConnectionManager> {
+        let init = self
+            .connection
+            .get_or_try_init(|| redis::aio::ConnectionManager::new(self.client.clone()));
+        within_timeout("connection", init).await.cloned()
+    }
+}
+
+/// Run a Redis `operation`, failing it after [`REDIS_TIMEOUT`]
+#[cfg(feature = "redis")]
+async fn within_timeout<T>(
+    operation: &str,
+    command: impl std::future::Future<Output = redis::RedisResult<T>>,
+) -> AppResult<T> {
+    match tokio::time::timeout(REDIS_TIMEOUT, command).await {
+        Ok(result) => result.map_err(|e| {
+            AppError::Internal(format!("Routing cache: Redis {} failed: {}", operation, e))
+        }),
+        Err(_elapsed) => Err(AppError::Internal(format!(
+            "Routing cache: Redis {} timed out after {}ms",
+            operation,
+            REDIS_TIMEOUT.as_millis()
+        ))),
+    }
+}
+
+#[cfg(feature = "redis")]
+#[async_trait]
+impl RoutingCache for RedisRoutingCache {
+    async fn get(&self, key: u64) -> AppResult<Option<TargetModel>> {
+        use redis::AsyncCommands;
+
+        let mut connection = self.connection().await?;
+        let value: Option<String> =
+            within_timeout("GET", connection.get(self.redis_key(key))).await?;
+        // Anything but a tier name (e.g. written by another tool) counts as a miss
+        Ok(value.and_then(|tier| match tier.as_str() {
+            "fast" => Some(TargetModel::Fast),
+            "balanced" => Some(TargetModel::Balanced),
+            "deep" => Some(TargetModel::Deep),
+            _ => None,
+        }))
+    }
+
+    async fn put(&self, key: u64, tier: TargetModel, ttl: Duration) -> AppResult<()> {
+        use redis::AsyncCommands;
+
+        let tier = match tier {
+            TargetModel::Fast => "fast",
+            TargetModel::Balanced => "balanced",
+            TargetModel::Deep => "deep",
+        };
+        let mut connection = self.connection().await?;
+        within_timeout(
+            "SET",
+            connection.set_ex::<_, _, ()>(self.redis_key(key), tier, ttl.as_secs().max(1)),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::router::{Importance, TaskType};
+
+    const TTL: Duration = Duration::from_secs(60);
+
+    #[tokio::test]
+    async fn test_in_memory_put_then_get() {
+        let cache = InMemoryRoutingCache::new(10);
+        assert_eq!(cache.get(1).await.unwrap(), None);
+
+        cache.put(1, TargetModel::Deep, TTL).await.unwrap();
+        assert_eq!(cache.get(1).await.unwrap(), Some(TargetModel::Deep));
+
+        cache.put(1, TargetModel::Fast, TTL).await.unwrap();
+        assert_eq!(cache.get(1).await.unwrap(), Some(TargetModel::Fast));
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_entry_expires_after_its_ttl() {
+        let cache = InMemoryRoutingCache::new(10);
+        cache
+            .put(1, TargetModel::Balanced, Duration::from_millis(20))
+            .await
+            .unwrap();
+        cache.put(2, TargetModel::Balanced, TTL).await.unwrap();
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        assert_eq!(cache.get(1).await.unwrap(), None);
+        assert_eq!(cache.get(2).await.unwrap(), Some(TargetModel::Balanced));
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_capacity_is_bounded() {
+        let cache = InMemoryRoutingCache::new(2);
+        for key in 0..5 {
+            cache.put(key, TargetModel::Fast, TTL).await.unwrap();
+        }
+        assert_eq!(cache.entries.len(), 2);
+        assert_eq!(cache.get(4).await.unwrap(), Some(TargetModel::Fast));
+    }
+
+    #[tokio::test]
+    async fn test_handles_to_one_cache_share_decisions() {
+        let cache: Arc<dyn RoutingCache> = Arc::new(InMemoryRoutingCache::new(10));
+        let other = Arc::clone(&cache);
+
+        cache.put(7, TargetModel::Deep, TTL).await.unwrap();
+        assert_eq!(other.get(7).await.unwrap(), Some(TargetModel::Deep));
+    }
+
+    #[test]
+    fn test_key_ignores_whitespace_but_not_metadata() {
+        let meta = RouteMetadata::new(10);
+        let key = routing_cache_key(
+            "Explain  the\nborrow checker",
+            &meta,
+            PromptNormalization::Whitespace,
+        );
+        assert_eq!(
+            key,
+            routing_cache_key(
+                "Explain the borrow checker",
+                &meta,
+                PromptNormalization::Whitespace
+            )
+        );
+
+        let code = RouteMetadata {
+            task_type: TaskType::Code,
+            ..meta
+        };
+        let urgent = RouteMetadata {
+            importance: Importance::High,
+            ..meta
+        };
+        let plain = "Explain the borrow checker";
+        assert_ne!(
+            key,
+            routing_cache_key(plain, &code, PromptNormalization::Whitespace)
+        );
+        assert_ne!(
+            key,
+            routing_cache_key(plain, &urgent, PromptNormalization::Whitespace)
+        );
+    }
+}
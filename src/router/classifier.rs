@@ -0,0 +1,143 @@
+//! Task type inference for requests that don't state one
+//!
+//! With `routing.auto_classify_task` enabled, a `/chat` request without a
+//! `task_type` field has one filled in by the [`TaskClassifier`] on
+//! [`AppState`](crate::handlers::AppState) before routing. A task type the
+//! client sent is never replaced. The default [`KeywordTaskClassifier`] is a
+//! cheap keyword heuristic; embedders can attach their own with
+//! [`AppState::with_task_classifier`](crate::handlers::AppState::with_task_classifier).
+
+use super::TaskType;
+
+/// Infers a [`TaskType`] from the prompt text
+///
+/// Runs inline on the request path before routing, so it should be fast.
+/// Returning `None` leaves the request at the default task type.
+pub trait TaskClassifier: Send + Sync {
+    fn classify(&self, prompt: &str) -> Option<TaskType>;
+}
+
+/// Case-insensitive English keyword heuristic
+///
+/// Checks, in order: code fences, creative writing, summarization, analysis,
+/// programming vocabulary, then greetings. The first match wins, so a request
+/// to "summarize this function" is a summary rather than code. Prompts that
+/// match nothing get `None`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KeywordTaskClassifier;
+
+const CREATIVE_PHRASES: &[&str] = &[
+    "write a story",
+    "write a poem",
+    "short story",
+    "poem",
+    "haiku",
+    "fiction",
+    "song lyrics",
+];
+
+const SUMMARY_PHRASES: &[&str] = &["summarize", "summarise", "summary", "tldr", "tl;dr"];
+
+const ANALYSIS_PHRASES: &[&str] = &[
+    "analyze",
+    "analyse",
+    "analysis",
+    "compare",
+    "evaluate",
+    "trade-off",
+    "tradeoff",
+    "pros and cons",
+];
+
+const CODE_PHRASES: &[&str] = &[
+    "function",
+    "compile",
+    "debug",
+    "stack trace",
+    "refactor",
+    "programming",
+    "source code",
+    "unit test",
+];
+
+const GREETINGS: &[&str] = &["hello", "hi", "hey", "good morning", "how are you"];
+
+impl TaskClassifier for KeywordTaskClassifier {
+    fn classify(&self, prompt: &str) -> Option<TaskType> {
+        let prompt = prompt.to_lowercase();
+        let contains_any = |phrases: &[&str]| phrases.iter().any(|p| prompt.contains(p));
+
+        if prompt.contains("```") {
+            Some(TaskType::Code)
+        } else if contains_any(CREATIVE_PHRASES) {
+            Some(TaskType::CreativeWriting)
+        } else if contains_any(SUMMARY_PHRASES) {
+            Some(TaskType::DocumentSummary)
+        } else if contains_any(ANALYSIS_PHRASES) {
+            Some(TaskType::DeepAnalysis)
+        } else if contains_any(CODE_PHRASES) {
+            Some(TaskType::Code)
+        } else if is_greeting(&prompt) {
+            Some(TaskType::CasualChat)
+        } else {
+            None
+        }
+    }
+}
+
+/// Whether a (lowercased) prompt opens with a greeting word
+///
+/// Only the start counts, so "which is the tallest building" is not a "hi".
+fn is_greeting(prompt: &str) -> bool {
+    let opening = prompt.trim_start();
+    GREETINGS.iter().any(|greeting| {
+        opening
+            .strip_prefix(greeting)
+            .is_some_and(|rest| rest.chars().next().is_none_or(|c| !c.is_alphanumeric()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_representative_prompts() {
+        let classifier = KeywordTaskClassifier;
+        let cases = [
+            (
+                "Why does this panic?\n```rust\nlet x = v[3];\n```",
+                TaskType::Code,
+            ),
+            ("How do I debug a segfault in C?", TaskType::Code),
+            (
+                "Write a story about a lighthouse keeper",
+                TaskType::CreativeWriting,
+            ),
+            ("Compose a haiku about autumn", TaskType::CreativeWriting),
+            ("Summarize this article for me", TaskType::DocumentSummary),
+            (
+                "TL;DR of the meeting notes please",
+                TaskType::DocumentSummary,
+            ),
+            (
+                "Compare Postgres and MySQL for analytics",
+                TaskType::DeepAnalysis,
+            ),
+            ("Hello!", TaskType::CasualChat),
+            ("hey, how's it going", TaskType::CasualChat),
+        ];
+        for (prompt, expected) in cases {
+            assert_eq!(classifier.classify(prompt), Some(expected), "{}", prompt);
+        }
+    }
+
+    #[test]
+    fn test_unmatched_prompts_are_left_unclassified() {
+        let classifier = KeywordTaskClassifier;
+        assert_eq!(classifier.classify("What is the capital of France?"), None);
+        // Greeting words only count at the start of the prompt
+        assert_eq!(classifier.classify("Which hill is highest?"), None);
+        assert_eq!(classifier.classify("Is this a hint?"), None);
+    }
+}
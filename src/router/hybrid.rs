@@ -7,6 +7,7 @@ use crate::config::Config;
 use crate::error::AppResult;
 use crate::models::selector::ModelSelector;
 use crate::router::{LlmBasedRouter, LlmRouter, RouteMetadata, RoutingDecision, RuleBasedRouter};
+use crate::shared::call_budget::CallBudget;
 use std::sync::Arc;
 
 /// Hybrid router combining rule-based and LLM-based strategies
@@ -36,9 +37,21 @@ impl HybridRouter {
         let router_timeout_secs = config.routing.router_timeout_for_tier(router_tier);
 
         let llm_router =
-            LlmBasedRouter::new(selector.clone(), router_tier, router_timeout_secs, metrics)?;
+            LlmBasedRouter::new(selector.clone(), router_tier, router_timeout_secs, metrics)?
+                .with_retry_backoff_ms(config.routing.retry_backoff_ms)
+                .with_retry_policy(config.routing.retry_policy)
+                .with_same_endpoint_retries(config.routing.router_same_endpoint_retries)
+                .with_on_unparseable(config.routing.on_unparseable)
+                .with_guard_suffix(config.routing.router_guard_suffix.clone())
+                .with_prompt_delimiters(config.routing.router_prompt_delimiters)
+                .with_task_affinity(config.routing.task_affinity.clone())
+                .with_tier_keywords(config.routing.tier_keywords.clone())
+                .with_importance_guidance(config.routing.importance_guidance.clone())
+                .with_router_endpoint(config.routing.router_endpoint.clone())
+                .with_cache_config(config.routing.cache.as_ref())?;
         Ok(Self {
-            rule_router: RuleBasedRouter::new(),
+            rule_router: RuleBasedRouter::new()
+                .with_task_affinity(config.routing.task_affinity.clone()),
             llm_router: Arc::new(llm_router),
             selector,
         })
@@ -83,6 +96,19 @@ impl HybridRouter {
         &self,
         user_prompt: &str,
         meta: &RouteMetadata,
+    ) -> AppResult<RoutingDecision> {
+        self.route_with_budget(user_prompt, meta, &CallBudget::unlimited())
+            .await
+    }
+
+    /// Route as [`HybridRouter::route`] does, spending LLM fallback queries from `budget`
+    ///
+    /// Rule matches make no upstream call and leave the budget untouched.
+    pub async fn route_with_budget(
+        &self,
+        user_prompt: &str,
+        meta: &RouteMetadata,
+        budget: &CallBudget,
     ) -> AppResult<RoutingDecision> {
         // Try rule-based first (fast path)
         // Rule router returns Ok(Some) if rule matched, Ok(None) if no match
@@ -121,7 +147,7 @@ impl HybridRouter {
 
                 let decision = self
                     .llm_router
-                    .route(user_prompt, meta)
+                    .route_with_budget(user_prompt, meta, budget)
                     .await
                     .map_err(|e| {
                         // Log hybrid routing context but propagate original error
@@ -0,0 +1,119 @@
+//! Retry backoff tests
+//!
+//! Uses paused tokio time so the spacing between router attempts is measured
+//! exactly without slowing the test suite down.
+
+use super::*;
+use crate::config::Config;
+use crate::models::ModelSelector;
+use std::sync::Arc;
+use tokio::time::Instant;
+
+const CONFIG_TOML: &str = r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+request_timeout_seconds = 30
+
+[[models.fast]]
+name = "fast-1"
+base_url = "http://localhost:11434/v1"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "http://localhost:1234/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:8080/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "llm"
+router_tier = "balanced"
+"#;
+
+/// Router whose only (balanced) endpoint is unhealthy, so every attempt fails
+/// at selection without touching the network
+async fn router_with_unhealthy_tier(retry_backoff_ms: u64) -> LlmBasedRouter {
+    let config: Config = toml::from_str(CONFIG_TOML).expect("should parse config");
+    let metrics = Arc::new(crate::metrics::Metrics::new().unwrap());
+    let selector = Arc::new(ModelSelector::new(Arc::new(config), metrics.clone()));
+    for _ in 0..3 {
+        selector
+            .health_checker()
+            .mark_failure("balanced-1")
+            .await
+            .expect("mark_failure should succeed");
+    }
+
+    LlmBasedRouter::new(selector, TargetModel::Balanced, 10, metrics)
+        .expect("balanced tier is configured")
+        .with_retry_backoff_ms(retry_backoff_ms)
+}
+
+#[test]
+fn test_retry_backoff_doubles_with_bounded_jitter() {
+    for _ in 0..50 {
+        let first = retry_backoff(200, 1).as_millis();
+        let second = retry_backoff(200, 2).as_millis();
+        assert!((200..=300).contains(&first), "attempt 1 got {}ms", first);
+        assert!((400..=600).contains(&second), "attempt 2 got {}ms", second);
+    }
+}
+
+#[test]
+fn test_retry_backoff_is_capped() {
+    let backoff = retry_backoff(u64::MAX, 10).as_millis() as u64;
+    let cap = crate::shared::query::MAX_BACKOFF_MS;
+    assert!((cap..=cap + cap / 2).contains(&backoff));
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_retries_are_spaced_by_configured_backoff() {
+    let router = router_with_unhealthy_tier(200).await;
+    let meta = RouteMetadata::new(10);
+
+    let start = Instant::now();
+    let result = router.route("Hello", &meta).await;
+    let elapsed = start.elapsed();
+
+    assert!(
+        matches!(result, Err(AppError::EndpointsUnavailable { .. })),
+        "expected transient exhaustion, got {:?}",
+        result
+    );
+    // Two attempts, one backoff between them: 200ms base + up to 50% jitter
+    assert!(
+        elapsed >= Duration::from_millis(200) && elapsed <= Duration::from_millis(300),
+        "attempts should be spaced by the configured backoff, elapsed {:?}",
+        elapsed
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_zero_backoff_retries_immediately() {
+    let router = router_with_unhealthy_tier(0).await;
+    let meta = RouteMetadata::new(10);
+
+    let start = Instant::now();
+    let result = router.route("Hello", &meta).await;
+
+    assert!(result.is_err());
+    assert_eq!(start.elapsed(), Duration::ZERO);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_dropping_route_cancels_backoff() {
+    let router = router_with_unhealthy_tier(10_000).await;
+    let meta = RouteMetadata::new(10);
+
+    let start = Instant::now();
+    let result =
+        tokio::time::timeout(Duration::from_millis(50), router.route("Hello", &meta)).await;
+
+    assert!(result.is_err(), "route should still be backing off");
+    assert_eq!(start.elapsed(), Duration::from_millis(50));
+}
@@ -0,0 +1,155 @@
+//! Tests for parse_routing_decision_bounded
+//!
+//! Besides the fixed cases, property tests feed random and adversarial
+//! strings through the parser: it must never panic, and any tier it returns
+//! must name a keyword standing as a whole word in the input.
+
+use super::*;
+use proptest::prelude::*;
+
+/// Keyword the parser matches for `tier` (default keywords only)
+fn keyword(tier: TargetModel) -> &'static str {
+    match tier {
+        TargetModel::Fast => "FAST",
+        TargetModel::Balanced => "BALANCED",
+        TargetModel::Deep => "DEEP",
+    }
+}
+
+fn has_keyword_at_boundary(response: &str, tier: TargetModel) -> bool {
+    let normalized = strip_markdown(response).to_uppercase();
+    LlmBasedRouter::find_word_boundary(&normalized, keyword(tier)).is_some()
+}
+
+#[test]
+fn test_bounded_parse_matches_unbounded_within_window() {
+    let result = LlmBasedRouter::parse_routing_decision_bounded("I'd pick **DEEP** here", 64);
+    assert_eq!(result.unwrap(), TargetModel::Deep);
+}
+
+#[test]
+fn test_keyword_beyond_window_is_not_found() {
+    let response = format!("{} FAST", "x ".repeat(100));
+    let result = LlmBasedRouter::parse_routing_decision_bounded(&response, 100);
+    assert!(matches!(
+        result,
+        Err(AppError::LlmRouting(
+            LlmRouterError::UnparseableResponse { .. }
+        ))
+    ));
+}
+
+#[test]
+fn test_word_cut_by_window_edge_is_dropped() {
+    // "FASTER" cut to "FAST" must not be read as a Fast decision
+    let result = LlmBasedRouter::parse_routing_decision_bounded("FASTER please", 4);
+    assert!(result.is_err());
+
+    // A combining mark after the cut still belongs to the word
+    let result = LlmBasedRouter::parse_routing_decision_bounded("DEEP\u{301}", 4);
+    assert!(result.is_err());
+
+    // A keyword ending exactly at the edge, before a space, still counts
+    let result = LlmBasedRouter::parse_routing_decision_bounded("FAST and more", 4);
+    assert_eq!(result.unwrap(), TargetModel::Fast);
+
+    // Markdown is stripped per whitespace-separated word, so the cut can't split
+    // one either: "BALANCED_" alone would lose the underscore
+    let result = LlmBasedRouter::parse_routing_decision_bounded("é BALANCED_😀", 12);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_window_inside_multibyte_char_does_not_panic() {
+    for max_scan in 0..8 {
+        let result = LlmBasedRouter::parse_routing_decision_bounded("é😀 DEEP", max_scan);
+        assert!(result.is_err(), "max_scan {max_scan}");
+    }
+    let result = LlmBasedRouter::parse_routing_decision_bounded("é😀 DEEP", 11);
+    assert_eq!(result.unwrap(), TargetModel::Deep);
+}
+
+#[test]
+fn test_extremely_long_input_is_bounded() {
+    let huge = "BREAKFAST ".repeat(1_000_000);
+    let response = format!("{huge} BALANCED");
+    let result = LlmBasedRouter::parse_routing_decision_bounded(&response, MAX_ROUTER_RESPONSE);
+    assert!(result.is_err());
+
+    let response = format!("BALANCED {huge}");
+    let result = LlmBasedRouter::parse_routing_decision_bounded(&response, MAX_ROUTER_RESPONSE);
+    assert_eq!(result.unwrap(), TargetModel::Balanced);
+}
+
+#[test]
+fn test_input_full_of_refusal_keywords() {
+    let response = "SORRY CANNOT ERROR UNABLE FAST ".repeat(200_000);
+    let result = LlmBasedRouter::parse_routing_decision_bounded(&response, MAX_ROUTER_RESPONSE);
+    assert!(matches!(
+        result,
+        Err(AppError::LlmRouting(LlmRouterError::Refusal { .. }))
+    ));
+
+    // Refusals past the window are never looked at
+    let response = format!("DEEP {}", "SORRY ".repeat(200_000));
+    let result = LlmBasedRouter::parse_routing_decision_bounded(&response, 5);
+    assert_eq!(result.unwrap(), TargetModel::Deep);
+}
+
+/// Fragments that sit near keyword and boundary edge cases
+const FRAGMENTS: &[&str] = &[
+    "FAST",
+    "BALANCED",
+    "DEEP",
+    "fast",
+    "deep",
+    "BREAKFAST",
+    "STEADFAST",
+    "DEEPER",
+    "SORRY",
+    "CAN'T",
+    "ERROR",
+    " ",
+    "\n",
+    "_",
+    "*",
+    "`",
+    "```",
+    "\"",
+    "-",
+    "é",
+    "\u{301}",
+    "你",
+    "😀",
+    "<<<USER>>>",
+    "<<<END>>>",
+    "x",
+    "1",
+];
+
+fn adversarial_response() -> impl Strategy<Value = String> {
+    prop::collection::vec(prop::sample::select(FRAGMENTS), 0..64)
+        .prop_map(|fragments| fragments.concat())
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(2000))]
+
+    #[test]
+    fn prop_random_input_never_panics(response in any::<String>(), max_scan in 0usize..2048) {
+        let _ = LlmBasedRouter::parse_routing_decision_bounded(&response, max_scan);
+    }
+
+    #[test]
+    fn prop_tier_only_for_keyword_at_boundary(
+        response in adversarial_response(),
+        max_scan in 0usize..512,
+    ) {
+        if let Ok(tier) = LlmBasedRouter::parse_routing_decision_bounded(&response, max_scan) {
+            let window = LlmBasedRouter::scan_window(&response, max_scan);
+            prop_assert!(window.len() <= max_scan);
+            prop_assert!(has_keyword_at_boundary(window, tier), "window {:?}", window);
+            prop_assert!(has_keyword_at_boundary(&response, tier), "response {:?}", response);
+        }
+    }
+}
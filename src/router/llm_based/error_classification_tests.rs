@@ -62,6 +62,10 @@ fn test_transient_errors_are_retryable() {
             error_message: "network timeout".to_string(),
         }),
         AppError::RoutingFailed("No healthy endpoints available".to_string()),
+        AppError::EndpointsUnavailable {
+            message: "No available Balanced tier endpoints".to_string(),
+            retry_after_seconds: 30,
+        },
     ];
 
     for error in transient_errors {
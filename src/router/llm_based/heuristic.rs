@@ -0,0 +1,211 @@
+//! Heuristic router: an `LlmRouter` that decides without a model call
+//!
+//! Scores the prompt on cheap local features (length, analysis keywords,
+//! task type, importance) and maps the score to a tier. Routing takes
+//! microseconds and never touches the network, at the cost of the nuance an
+//! LLM router provides. Selected with `strategy = "heuristic"`.
+
+use super::LlmRouter;
+use crate::error::AppResult;
+use crate::router::{
+    Importance, RouteMetadata, RoutingDecision, RoutingStrategy, TargetModel, TaskType,
+};
+use async_trait::async_trait;
+
+/// Phrases that signal multi-step reasoning or in-depth work
+const DEEP_KEYWORDS: &[&str] = &[
+    "analyze",
+    "analyse",
+    "analysis",
+    "architecture",
+    "compare",
+    "comprehensive",
+    "derive",
+    "design a",
+    "detailed",
+    "evaluate",
+    "in depth",
+    "in-depth",
+    "prove",
+    "step by step",
+    "step-by-step",
+    "trade-off",
+    "tradeoff",
+];
+
+/// Keyword matches beyond this count add no further weight
+const MAX_KEYWORD_SCORE: i32 = 2;
+
+/// Prompts under this many estimated tokens lean toward the fast tier
+const SHORT_PROMPT_TOKENS: usize = 64;
+
+/// Prompts at or above this many estimated tokens lean toward a larger tier
+const LONG_PROMPT_TOKENS: usize = 512;
+
+/// Prompts at or above this many estimated tokens lean strongly toward deep
+const VERY_LONG_PROMPT_TOKENS: usize = 2048;
+
+/// Keyword/length classifier implementing [`LlmRouter`]
+///
+/// # Scoring
+///
+/// | Feature                         | Score |
+/// |---------------------------------|-------|
+/// | < 64 tokens                     | -1    |
+/// | ≥ 512 tokens / ≥ 2048 tokens    | +1 / +2 |
+/// | Each analysis keyword (max 2)   | +1    |
+/// | DeepAnalysis task               | +2    |
+/// | Code or CreativeWriting task    | +1    |
+/// | CasualChat task                 | -1    |
+/// | High / Low importance           | +1 / -1 |
+///
+/// A total of -1 or less routes to Fast, 0 or 1 to Balanced, 2 or more to Deep.
+///
+/// Decisions are reported with [`RoutingStrategy::Rule`]: like rule routing,
+/// no model is queried, which is what the strategy label distinguishes in
+/// metrics and responses.
+#[derive(Debug, Clone, Default)]
+pub struct HeuristicRouter;
+
+impl HeuristicRouter {
+    /// Create a new heuristic router
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Choose a tier from prompt and metadata features
+    pub fn classify(&self, user_prompt: &str, meta: &RouteMetadata) -> TargetModel {
+        match Self::score(user_prompt, meta) {
+            ..=-1 => TargetModel::Fast,
+            0..=1 => TargetModel::Balanced,
+            _ => TargetModel::Deep,
+        }
+    }
+
+    fn score(user_prompt: &str, meta: &RouteMetadata) -> i32 {
+        let mut score = 0;
+
+        score += match meta.token_estimate {
+            t if t >= VERY_LONG_PROMPT_TOKENS => 2,
+            t if t >= LONG_PROMPT_TOKENS => 1,
+            t if t < SHORT_PROMPT_TOKENS => -1,
+            _ => 0,
+        };
+
+        let prompt = user_prompt.to_lowercase();
+        let keyword_hits = DEEP_KEYWORDS
+            .iter()
+            .filter(|keyword| prompt.contains(*keyword))
+            .count();
+        score += (keyword_hits as i32).min(MAX_KEYWORD_SCORE);
+
+        score += match meta.task_type {
+            TaskType::DeepAnalysis => 2,
+            TaskType::Code | TaskType::CreativeWriting => 1,
+            TaskType::CasualChat => -1,
+            TaskType::QuestionAnswer | TaskType::DocumentSummary => 0,
+        };
+
+        score += match meta.importance {
+            Importance::High => 1,
+            Importance::Normal => 0,
+            Importance::Low => -1,
+        };
+
+        score
+    }
+}
+
+#[async_trait]
+impl LlmRouter for HeuristicRouter {
+    async fn route(&self, user_prompt: &str, meta: &RouteMetadata) -> AppResult<RoutingDecision> {
+        let target = self.classify(user_prompt, meta);
+
+        tracing::debug!(
+            target_model = ?target,
+            token_estimate = meta.token_estimate,
+            importance = ?meta.importance,
+            task_type = ?meta.task_type,
+            "Heuristic router decision"
+        );
+
+        Ok(RoutingDecision::new(target, RoutingStrategy::Rule))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meta_for(prompt: &str) -> RouteMetadata {
+        RouteMetadata::new(RouteMetadata::estimate_tokens(prompt))
+    }
+
+    #[tokio::test]
+    async fn test_short_chat_routes_to_fast() {
+        let router = HeuristicRouter::new();
+        let prompt = "Hey, how's it going?";
+
+        let decision = router
+            .route(prompt, &meta_for(prompt))
+            .await
+            .expect("heuristic routing never fails");
+
+        assert_eq!(decision.target(), TargetModel::Fast);
+        assert_eq!(decision.strategy(), RoutingStrategy::Rule);
+    }
+
+    #[tokio::test]
+    async fn test_long_analysis_routes_to_deep() {
+        let router = HeuristicRouter::new();
+        let prompt = format!(
+            "Provide a detailed analysis of the trade-offs between these two storage \
+             engine designs, step by step.\n\n{}",
+            "The first engine uses a log-structured merge tree with tiered compaction. ".repeat(40)
+        );
+
+        let decision = router.route(&prompt, &meta_for(&prompt)).await.unwrap();
+
+        assert_eq!(decision.target(), TargetModel::Deep);
+    }
+
+    #[tokio::test]
+    async fn test_plain_question_routes_to_balanced() {
+        let router = HeuristicRouter::new();
+        let prompt = format!(
+            "What is the difference between a process and a thread? {}",
+            "Please keep the answer focused on operating systems concepts. ".repeat(6)
+        );
+
+        let decision = router.route(&prompt, &meta_for(&prompt)).await.unwrap();
+
+        assert_eq!(decision.target(), TargetModel::Balanced);
+    }
+
+    #[test]
+    fn test_metadata_shifts_the_decision() {
+        let router = HeuristicRouter::new();
+        let prompt = "Summarize this paragraph.";
+
+        let casual = meta_for(prompt).with_task_type(TaskType::CasualChat);
+        assert_eq!(router.classify(prompt, &casual), TargetModel::Fast);
+
+        let deep = meta_for(prompt)
+            .with_task_type(TaskType::DeepAnalysis)
+            .with_importance(Importance::High);
+        assert_eq!(router.classify(prompt, &deep), TargetModel::Deep);
+    }
+
+    #[test]
+    fn test_keyword_weight_is_capped() {
+        let router = HeuristicRouter::new();
+        // Every keyword, but a short, low-importance casual message
+        let prompt = DEEP_KEYWORDS.join(" ");
+        let meta = RouteMetadata::new(10)
+            .with_task_type(TaskType::CasualChat)
+            .with_importance(Importance::Low);
+
+        // -1 (short) + 2 (capped keywords) - 1 (casual) - 1 (low) = -1
+        assert_eq!(router.classify(&prompt, &meta), TargetModel::Fast);
+    }
+}
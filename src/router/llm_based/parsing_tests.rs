@@ -410,3 +410,252 @@ fn test_parse_routing_decision_case_sensitivity_for_invalid() {
         );
     }
 }
+
+#[test]
+fn test_find_word_boundary_unicode_neighbours() {
+    // Letters of other alphabets are part of the word, like ASCII letters
+    assert_eq!(
+        LlmBasedRouter::find_word_boundary("ПBALANCED", "BALANCED"),
+        None
+    );
+    assert_eq!(
+        LlmBasedRouter::find_word_boundary("BALANCEDЖ", "BALANCED"),
+        None
+    );
+    assert_eq!(LlmBasedRouter::find_word_boundary("DEEPΩ", "DEEP"), None);
+    assert_eq!(LlmBasedRouter::find_word_boundary("λFAST", "FAST"), None);
+
+    // Non-letters from outside ASCII are boundaries
+    assert_eq!(
+        LlmBasedRouter::find_word_boundary("«DEEP»", "DEEP"),
+        Some(2)
+    );
+    assert_eq!(
+        LlmBasedRouter::find_word_boundary("—FAST—", "FAST"),
+        Some(3)
+    );
+
+    // A keyword next to Han or kana is still its own word
+    assert_eq!(
+        LlmBasedRouter::find_word_boundary("你FAST好", "FAST"),
+        Some(3)
+    );
+    assert_eq!(
+        LlmBasedRouter::find_word_boundary("BALANCEDな", "BALANCED"),
+        Some(0)
+    );
+
+    // A later standalone occurrence is found after a rejected one
+    assert_eq!(
+        LlmBasedRouter::find_word_boundary("ЖDEEP or DEEP", "DEEP"),
+        Some(10)
+    );
+}
+
+#[test]
+fn test_find_word_boundary_combining_marks() {
+    // The mark belongs to the letter before it, so the keyword is not whole
+    assert_eq!(
+        LlmBasedRouter::find_word_boundary("FAST\u{301}", "FAST"),
+        None
+    );
+    // A marked letter right before the keyword joins it; a space separates them
+    assert_eq!(
+        LlmBasedRouter::find_word_boundary("E\u{301}DEEP", "DEEP"),
+        None
+    );
+    assert_eq!(
+        LlmBasedRouter::find_word_boundary("CAFE\u{301} DEEP", "DEEP"),
+        Some(7)
+    );
+}
+
+#[test]
+fn test_parse_routing_decision_unicode_word_boundaries() {
+    assert_eq!(
+        LlmBasedRouter::parse_routing_decision("Ответ: BALANCED").unwrap(),
+        TargetModel::Balanced
+    );
+    assert_eq!(
+        LlmBasedRouter::parse_routing_decision("建议使用DEEP模型").unwrap(),
+        TargetModel::Deep
+    );
+    // Glued to a Cyrillic word it is not a keyword; the later standalone one wins
+    assert_eq!(
+        LlmBasedRouter::parse_routing_decision("ПFAST, then DEEP").unwrap(),
+        TargetModel::Deep
+    );
+
+    // Existing ASCII behavior is unchanged
+    for response in ["BREAKFAST", "STEADFAST", "SUPER_FAST"] {
+        assert!(
+            LlmBasedRouter::parse_routing_decision(response).is_err(),
+            "'{}' should not parse",
+            response
+        );
+    }
+}
+
+fn localized_keywords() -> TierKeywordsConfig {
+    TierKeywordsConfig {
+        fast: vec!["RAPIDE".to_string(), "快速".to_string()],
+        balanced: vec!["ÉQUILIBRÉ".to_string(), "均衡".to_string()],
+        deep: vec!["approfondi".to_string(), "深度".to_string()],
+    }
+}
+
+#[test]
+fn test_parse_routing_decision_custom_tier_keywords() {
+    let keywords = localized_keywords();
+    let parse =
+        |response: &str| LlmBasedRouter::parse_routing_decision_with_keywords(response, &keywords);
+
+    // French answers, including lowercase with accents
+    assert_eq!(parse("ÉQUILIBRÉ").unwrap(), TargetModel::Balanced);
+    assert_eq!(parse("équilibré").unwrap(), TargetModel::Balanced);
+    assert_eq!(parse("Je choisis APPROFONDI.").unwrap(), TargetModel::Deep);
+    // Chinese answers, with and without surrounding Han text
+    assert_eq!(parse("均衡").unwrap(), TargetModel::Balanced);
+    assert_eq!(parse("建议使用快速模型").unwrap(), TargetModel::Fast);
+
+    // Leftmost keyword wins across languages
+    assert_eq!(parse("RAPIDE ou DEEP").unwrap(), TargetModel::Fast);
+    assert_eq!(parse("DEEP, pas RAPIDE").unwrap(), TargetModel::Deep);
+    assert_eq!(parse("深度还是均衡").unwrap(), TargetModel::Deep);
+
+    // Custom keywords respect word boundaries too
+    assert!(parse("SURRAPIDE").is_err());
+    assert!(parse("DÉSÉQUILIBRÉ").is_err());
+
+    // English defaults are still accepted alongside the custom keywords
+    assert_eq!(parse("BALANCED").unwrap(), TargetModel::Balanced);
+}
+
+#[test]
+fn test_parse_routing_decision_defaults_without_custom_keywords() {
+    let keywords = TierKeywordsConfig::default();
+    for (response, expected) in [
+        ("FAST", TargetModel::Fast),
+        ("I recommend BALANCED", TargetModel::Balanced),
+        ("deep", TargetModel::Deep),
+    ] {
+        assert_eq!(
+            LlmBasedRouter::parse_routing_decision_with_keywords(response, &keywords).unwrap(),
+            expected
+        );
+    }
+    // Localized words mean nothing until configured
+    assert!(LlmBasedRouter::parse_routing_decision_with_keywords("ÉQUILIBRÉ", &keywords).is_err());
+    assert!(LlmBasedRouter::parse_routing_decision("均衡").is_err());
+}
+
+#[test]
+fn test_parse_routing_decision_backtick_fenced() {
+    for (response, expected) in [
+        ("```\nBALANCED\n```", TargetModel::Balanced),
+        ("```text\nfast\n```\n", TargetModel::Fast),
+        ("`DEEP`", TargetModel::Deep),
+        ("```DEEP```", TargetModel::Deep),
+    ] {
+        assert_eq!(
+            LlmBasedRouter::parse_routing_decision(response).unwrap(),
+            expected,
+            "{:?}",
+            response
+        );
+    }
+}
+
+#[test]
+fn test_parse_routing_decision_bold_wrapped() {
+    for (response, expected) in [
+        ("**FAST**", TargetModel::Fast),
+        ("__BALANCED__", TargetModel::Balanced),
+        ("_deep_", TargetModel::Deep),
+        ("I'd pick ***FAST*** here.", TargetModel::Fast),
+    ] {
+        assert_eq!(
+            LlmBasedRouter::parse_routing_decision(response).unwrap(),
+            expected,
+            "{:?}",
+            response
+        );
+    }
+}
+
+#[test]
+fn test_parse_routing_decision_quoted() {
+    for (response, expected) in [
+        ("\"FAST\"", TargetModel::Fast),
+        ("'balanced'", TargetModel::Balanced),
+        ("“DEEP”", TargetModel::Deep),
+        ("  \"**BALANCED**\"  \n", TargetModel::Balanced),
+    ] {
+        assert_eq!(
+            LlmBasedRouter::parse_routing_decision(response).unwrap(),
+            expected,
+            "{:?}",
+            response
+        );
+    }
+}
+
+#[test]
+fn test_parse_routing_decision_markdown_keeps_word_boundaries() {
+    // Stripping wrappers never joins a keyword onto its neighbours
+    assert!(LlmBasedRouter::parse_routing_decision("**BREAKFAST**").is_err());
+    assert!(LlmBasedRouter::parse_routing_decision("`FAST_PATH`").is_err());
+    // Apostrophes inside words still trip refusal detection
+    assert!(LlmBasedRouter::parse_routing_decision("**I can't decide: FAST?**").is_err());
+}
+
+#[test]
+fn test_parse_ranked_response_orders_by_score() {
+    let response = r#"[{"tier": "FAST", "score": 0.1}, {"tier": "deep", "score": 0.7}, {"tier": "Balanced", "score": 0.2}]"#;
+    assert_eq!(
+        LlmBasedRouter::parse_ranked_response(response),
+        Some(vec![
+            (TargetModel::Deep, 0.7),
+            (TargetModel::Balanced, 0.2),
+            (TargetModel::Fast, 0.1),
+        ])
+    );
+}
+
+#[test]
+fn test_parse_ranked_response_inside_code_fence() {
+    let response = "```json\n[{\"tier\": \"BALANCED\", \"score\": 0.9}]\n```";
+    assert_eq!(
+        LlmBasedRouter::parse_ranked_response(response),
+        Some(vec![(TargetModel::Balanced, 0.9)])
+    );
+}
+
+#[test]
+fn test_parse_ranked_response_rejects_malformed_rankings() {
+    for response in [
+        "BALANCED",
+        "[]",
+        r#"[{"tier": "DEEP", "score": 0.5"#,
+        r#"[{"tier": "HUGE", "score": 0.5}]"#,
+        r#"[{"tier": "DEEP"}]"#,
+        r#"[{"tier": "DEEP", "score": 0.5}, {"tier": "deep", "score": 0.4}]"#,
+    ] {
+        assert_eq!(
+            LlmBasedRouter::parse_ranked_response(response),
+            None,
+            "{:?}",
+            response
+        );
+    }
+}
+
+#[test]
+fn test_ranked_response_parses_as_its_top_tier() {
+    // `route` on a ranked answer takes the first tier named
+    let response = r#"[{"tier": "DEEP", "score": 0.7}, {"tier": "FAST", "score": 0.3}]"#;
+    assert_eq!(
+        LlmBasedRouter::parse_routing_decision(response).unwrap(),
+        TargetModel::Deep
+    );
+}
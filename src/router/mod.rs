@@ -2,15 +2,27 @@
 //!
 //! Provides different routing strategies to select the optimal model for a request.
 
+pub mod cache;
+pub mod classifier;
 pub mod hybrid;
 pub mod llm_based;
+pub mod observer;
 pub mod rule_based;
 
+pub use cache::{InMemoryRoutingCache, RoutingCache};
+pub use classifier::{KeywordTaskClassifier, TaskClassifier};
 pub use hybrid::HybridRouter;
-pub use llm_based::{LlmBasedRouter, LlmRouter};
+pub use llm_based::{
+    DEFAULT_ROUTER_GUARD_SUFFIX, DEFAULT_ROUTER_RETRY_BACKOFF_MS, HeuristicRouter, LlmBasedRouter,
+    LlmRouter,
+};
+pub use observer::{NoopRoutingObserver, RoutingObserver};
 pub use rule_based::RuleBasedRouter;
 
-use crate::error::AppResult;
+use crate::config::{LlmFailureFallback, TaskAffinityConfig};
+use crate::error::{AppError, AppResult};
+use crate::shared::call_budget::CallBudget;
+use llm_based::LlmRouterError;
 use serde::{Deserialize, Deserializer, Serialize, de};
 
 /// Target model selection (generic tiers)
@@ -124,6 +136,12 @@ pub struct RoutingDecision {
     /// Non-fatal warnings encountered during routing (omitted if empty)
     #[serde(skip_serializing_if = "Vec::is_empty")]
     warnings: Vec<String>,
+    /// Raw answer of the router model, for LLM decisions (debug output only)
+    #[serde(skip)]
+    router_response: Option<String>,
+    /// Name of the endpoint that answered the router query, for LLM decisions
+    #[serde(skip)]
+    router_endpoint: Option<String>,
 }
 
 impl RoutingDecision {
@@ -133,6 +151,8 @@ impl RoutingDecision {
             target,
             strategy,
             warnings: Vec::new(),
+            router_response: None,
+            router_endpoint: None,
         }
     }
 
@@ -165,6 +185,39 @@ impl RoutingDecision {
         self.warnings.push(warning);
         self
     }
+
+    /// Serve the decision from `target` instead, keeping strategy and warnings
+    pub fn with_target(mut self, target: TargetModel) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Record the router model's raw answer (builder pattern)
+    pub fn with_router_response(mut self, response: String) -> Self {
+        self.router_response = Some(response);
+        self
+    }
+
+    /// Get the router model's raw answer, if an LLM made this decision
+    ///
+    /// Surfaced to clients only in debug mode (`observability.router_debug`).
+    pub fn router_response(&self) -> Option<&str> {
+        self.router_response.as_deref()
+    }
+
+    /// Record which endpoint answered the router query (builder pattern)
+    pub fn with_router_endpoint(mut self, name: impl Into<String>) -> Self {
+        self.router_endpoint = Some(name.into());
+        self
+    }
+
+    /// Get the name of the endpoint that answered the router query, if any
+    ///
+    /// Surfaced as `x-octoroute-router-endpoint` when
+    /// `observability.endpoint_headers` is on.
+    pub fn router_endpoint(&self) -> Option<&str> {
+        self.router_endpoint.as_deref()
+    }
 }
 
 /// Request importance level
@@ -239,25 +292,47 @@ impl RouteMetadata {
 /// - `Rule`: Only rule-based routing (no LLM routing, no balanced tier required)
 /// - `Llm`: Only LLM-based routing (requires balanced tier configured)
 /// - `Hybrid`: Rule-based with LLM fallback (requires balanced tier configured)
+/// - `Heuristic`: Local keyword/length classifier (no LLM routing, no router tier required)
 ///
 /// This design allows deployments to opt-out of LLM routing (and its balanced tier requirement)
 /// by setting `strategy = "rule"` in configuration.
 pub enum Router {
     /// Rule-based router (deterministic, fast, no LLM required)
     Rule(RuleBasedRouter),
-    /// LLM-based router (intelligent, requires balanced tier)
-    Llm(LlmBasedRouter),
+    /// LLM-based router (intelligent, requires balanced tier; boxed, it is much larger than the rest)
+    Llm(Box<LlmBasedRouter>),
     /// Hybrid router (rule-based with LLM fallback, requires balanced tier)
     Hybrid(HybridRouter),
+    /// Heuristic router (local classifier, no model call)
+    Heuristic(HeuristicRouter),
 }
 
 impl Router {
     /// Route a request using the configured strategy
     ///
+    /// Same as [`Router::route_with_metadata`], which it delegates to; kept so
+    /// existing callers don't change.
+    ///
+    /// # Errors
+    /// Same as [`Router::route_with_metadata`].
+    pub async fn route(
+        &self,
+        user_prompt: &str,
+        meta: &RouteMetadata,
+        selector: &crate::models::ModelSelector,
+    ) -> AppResult<RoutingDecision> {
+        self.route_with_metadata(user_prompt, meta, selector).await
+    }
+
+    /// Route a request using caller-supplied metadata
+    ///
     /// Delegates to the appropriate router implementation based on the variant.
     /// All routers return a RoutingDecision containing the target tier and
     /// the strategy that made the decision.
     ///
+    /// The metadata is used as-is: no token estimation is performed, so a count
+    /// from a real tokenizer drives threshold-based rules directly.
+    ///
     /// # Arguments
     /// * `user_prompt` - The user's prompt/message
     /// * `meta` - Request metadata (token estimate, importance, task type)
@@ -265,59 +340,158 @@ impl Router {
     ///
     /// # Errors
     /// Returns an error if:
-    /// - LLM routing fails (network error, no healthy balanced endpoints, etc.)
+    /// - LLM routing fails (network error, no healthy balanced endpoints, etc.),
+    ///   unless the failure is systemic and `routing.llm_failure_fallback` routes
+    ///   around it (LLM strategy only)
     /// - Rule routing with no match and no default tier available
-    pub async fn route(
+    pub async fn route_with_metadata(
         &self,
         user_prompt: &str,
         meta: &RouteMetadata,
         selector: &crate::models::ModelSelector,
+    ) -> AppResult<RoutingDecision> {
+        self.route_with_budget(user_prompt, meta, selector, &CallBudget::unlimited())
+            .await
+    }
+
+    /// Route a request using default metadata derived from the prompt
+    ///
+    /// Estimates the token count from `user_prompt` (see
+    /// [`RouteMetadata::estimate_tokens`]) with default importance and task type,
+    /// then delegates to [`Router::route_with_metadata`]. Callers that already
+    /// know the importance, task type, or an accurate token count should call
+    /// `route_with_metadata`.
+    ///
+    /// # Errors
+    /// Same as [`Router::route_with_metadata`].
+    pub async fn route_prompt(
+        &self,
+        user_prompt: &str,
+        selector: &crate::models::ModelSelector,
+    ) -> AppResult<RoutingDecision> {
+        let meta = RouteMetadata::new(RouteMetadata::estimate_tokens(user_prompt));
+        self.route_with_metadata(user_prompt, &meta, selector).await
+    }
+
+    /// Route as [`Router::route_with_metadata`] does, spending router queries from `budget`
+    ///
+    /// Handlers pass the request's [`CallBudget`] (`server.max_upstream_calls`)
+    /// here and then on to the completion, so both share one cap.
+    pub async fn route_with_budget(
+        &self,
+        user_prompt: &str,
+        meta: &RouteMetadata,
+        selector: &crate::models::ModelSelector,
+        budget: &CallBudget,
     ) -> AppResult<RoutingDecision> {
         match self {
             Router::Rule(r) => {
                 // Rule router returns Option - if None, use default tier fallback
                 match r.route(user_prompt, meta, selector).await? {
                     Some(decision) => Ok(decision),
-                    None => {
-                        // No rule matched - use default tier for rule-only mode
-                        let default_target = selector.default_tier().ok_or_else(|| {
-                            crate::error::AppError::Config(
-                                "No routing rule matched and no endpoints configured for default fallback"
-                                    .to_string(),
-                            )
-                        })?;
-
-                        // Verify default tier has healthy endpoints
-                        let exclusion_set = crate::models::ExclusionSet::new();
-                        if selector
-                            .select(default_target, &exclusion_set)
-                            .await
-                            .is_none()
-                        {
-                            return Err(crate::error::AppError::RoutingFailed(format!(
-                                "No rule matched and default tier {:?} has no healthy endpoints available",
-                                default_target
-                            )));
-                        }
-
-                        tracing::info!(
-                            default_tier = ?default_target,
-                            token_estimate = meta.token_estimate,
-                            importance = ?meta.importance,
-                            task_type = ?meta.task_type,
-                            "No rule matched, using default tier (rule-only mode)"
-                        );
-
-                        Ok(RoutingDecision::new(default_target, RoutingStrategy::Rule))
-                    }
+                    None => default_tier_decision(meta, selector).await,
                 }
             }
-            Router::Llm(r) => r.route(user_prompt, meta).await,
-            Router::Hybrid(r) => r.route(user_prompt, meta).await,
+            Router::Llm(r) => match r.route_with_budget(user_prompt, meta, budget).await {
+                Err(AppError::LlmRouting(error))
+                    if !error.is_retryable()
+                        && r.failure_fallback() != LlmFailureFallback::Error =>
+                {
+                    llm_failure_fallback(
+                        r.failure_fallback(),
+                        r.task_affinity(),
+                        error,
+                        user_prompt,
+                        meta,
+                        selector,
+                    )
+                    .await
+                }
+                result => result,
+            },
+            Router::Hybrid(r) => r.route_with_budget(user_prompt, meta, budget).await,
+            Router::Heuristic(r) => r.route(user_prompt, meta).await,
         }
     }
 }
 
+/// Rule-only fallback when no rule matches: the default tier, if it has a healthy endpoint
+async fn default_tier_decision(
+    meta: &RouteMetadata,
+    selector: &crate::models::ModelSelector,
+) -> AppResult<RoutingDecision> {
+    let default_target = selector.default_tier().ok_or_else(|| {
+        AppError::Config(
+            "No routing rule matched and no endpoints configured for default fallback".to_string(),
+        )
+    })?;
+
+    // Verify default tier has healthy endpoints
+    let exclusion_set = crate::models::ExclusionSet::new();
+    if let Err(reason) = selector.try_select(default_target, &exclusion_set).await {
+        return Err(AppError::EndpointsUnavailable {
+            message: format!(
+                "No rule matched and default tier {:?} has no healthy endpoints available ({})",
+                default_target, reason
+            ),
+            retry_after_seconds: crate::models::health::RECOVERY_RETRY_AFTER_SECS,
+        });
+    }
+
+    tracing::info!(
+        default_tier = ?default_target,
+        token_estimate = meta.token_estimate,
+        importance = ?meta.importance,
+        task_type = ?meta.task_type,
+        "No rule matched, using default tier (rule-only mode)"
+    );
+
+    Ok(RoutingDecision::new(default_target, RoutingStrategy::Rule))
+}
+
+/// Route without the LLM after it failed systemically (`routing.llm_failure_fallback`)
+///
+/// The decision carries a warning naming the router error so clients and logs
+/// show that the LLM was bypassed.
+async fn llm_failure_fallback(
+    fallback: LlmFailureFallback,
+    task_affinity: &TaskAffinityConfig,
+    error: LlmRouterError,
+    user_prompt: &str,
+    meta: &RouteMetadata,
+    selector: &crate::models::ModelSelector,
+) -> AppResult<RoutingDecision> {
+    let decision = match fallback {
+        LlmFailureFallback::Error => return Err(AppError::LlmRouting(error)),
+        LlmFailureFallback::Rule => {
+            match RuleBasedRouter::new()
+                .with_task_affinity(task_affinity.clone())
+                .route(user_prompt, meta, selector)
+                .await?
+            {
+                Some(decision) => decision,
+                None => default_tier_decision(meta, selector).await?,
+            }
+        }
+        LlmFailureFallback::DefaultTier => default_tier_decision(meta, selector).await?,
+    };
+
+    tracing::warn!(
+        fallback = fallback.as_str(),
+        target_tier = ?decision.target(),
+        error = %error,
+        "LLM router failed systemically, routing via fallback"
+    );
+
+    let warning = format!(
+        "LLM router failed ({}); routed to {:?} via '{}' fallback",
+        error,
+        decision.target(),
+        fallback.as_str()
+    );
+    Ok(decision.with_warning(warning))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -497,4 +671,97 @@ mod tests {
         assert_eq!(decision1, decision2);
         assert_ne!(decision1, decision3);
     }
+
+    fn rule_router_selector() -> crate::models::ModelSelector {
+        let toml = r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+
+[[models.fast]]
+name = "fast-1"
+base_url = "http://localhost:1234/v1"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "http://localhost:1235/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:1236/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#;
+        let config: crate::config::Config = toml::from_str(toml).expect("should parse config");
+        let metrics =
+            std::sync::Arc::new(crate::metrics::Metrics::new().expect("should create metrics"));
+        crate::models::ModelSelector::new(std::sync::Arc::new(config), metrics)
+    }
+
+    #[tokio::test]
+    async fn test_route_with_metadata_uses_supplied_token_estimate() {
+        let router = Router::Rule(RuleBasedRouter::new());
+        let selector = rule_router_selector();
+        // Short prompt: the heuristic would estimate only a few tokens
+        let prompt = "Write a function";
+
+        let small = RouteMetadata::new(100).with_task_type(TaskType::Code);
+        let decision = router
+            .route_with_metadata(prompt, &small, &selector)
+            .await
+            .unwrap();
+        assert_eq!(decision.target(), TargetModel::Balanced);
+
+        // Supplied estimate above the 1024-token code threshold routes to Deep
+        // even though the prompt itself is tiny
+        let large = RouteMetadata::new(2000).with_task_type(TaskType::Code);
+        let decision = router
+            .route_with_metadata(prompt, &large, &selector)
+            .await
+            .unwrap();
+        assert_eq!(decision.target(), TargetModel::Deep);
+
+        // `route` delegates to it
+        let decision = router.route(prompt, &large, &selector).await.unwrap();
+        assert_eq!(decision.target(), TargetModel::Deep);
+    }
+
+    #[tokio::test]
+    async fn test_route_with_metadata_supplied_estimate_enables_balanced_rule() {
+        let router = Router::Rule(RuleBasedRouter::new());
+        let selector = rule_router_selector();
+        let prompt = "Explain this";
+
+        // 500 tokens of question-answer falls in the balanced band (200..2048)
+        let meta = RouteMetadata::new(500);
+        let decision = router
+            .route_with_metadata(prompt, &meta, &selector)
+            .await
+            .unwrap();
+        assert_eq!(decision.target(), TargetModel::Balanced);
+        assert_eq!(decision.strategy(), RoutingStrategy::Rule);
+    }
+
+    #[tokio::test]
+    async fn test_route_prompt_estimates_tokens() {
+        let router = Router::Rule(RuleBasedRouter::new());
+        let selector = rule_router_selector();
+
+        // 1000 chars -> 250 estimated tokens of question-answer -> Balanced
+        let long_prompt = "a".repeat(1000);
+        let decision = router.route_prompt(&long_prompt, &selector).await.unwrap();
+        assert_eq!(decision.target(), TargetModel::Balanced);
+
+        // Same result as supplying the equivalent metadata explicitly
+        let meta = RouteMetadata::new(RouteMetadata::estimate_tokens(&long_prompt));
+        let explicit = router
+            .route_with_metadata(&long_prompt, &meta, &selector)
+            .await
+            .unwrap();
+        assert_eq!(decision, explicit);
+    }
 }
@@ -0,0 +1,26 @@
+//! Hook for reacting to routing decisions
+//!
+//! Embedders that build their own [`AppState`](crate::handlers::AppState) can
+//! attach a [`RoutingObserver`] to audit decisions or feed an external system
+//! without patching the handlers. The observer runs inline on the request
+//! path, so it should return quickly and hand slow work off to a task.
+
+use super::{RouteMetadata, RoutingDecision};
+
+/// Called with every tier decision made for a chat request
+///
+/// Invoked on `/chat` and `/v1/chat/completions` (streaming and non-streaming)
+/// once the tier is final, after any sticky session replay or context window
+/// escalation, and before an endpoint is selected. Requests naming a specific
+/// endpoint skip routing and are not reported.
+pub trait RoutingObserver: Send + Sync {
+    fn on_decision(&self, prompt_meta: &RouteMetadata, decision: &RoutingDecision);
+}
+
+/// Observer that ignores every decision
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopRoutingObserver;
+
+impl RoutingObserver for NoopRoutingObserver {
+    fn on_decision(&self, _prompt_meta: &RouteMetadata, _decision: &RoutingDecision) {}
+}
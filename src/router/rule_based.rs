@@ -7,19 +7,33 @@
 //! - Task type and complexity
 //! - Token count estimates
 //! - User-specified importance level
+//!
+//! A `[routing.task_affinity]` entry for the request's task type overrides all
+//! of the above.
 
 use super::{Importance, RouteMetadata, RoutingDecision, RoutingStrategy, TargetModel, TaskType};
+use crate::config::TaskAffinityConfig;
 use crate::error::AppResult;
 use crate::models::ModelSelector;
 
 /// Rule-based router that uses fast pattern matching
 #[derive(Debug, Clone, Default)]
-pub struct RuleBasedRouter;
+pub struct RuleBasedRouter {
+    task_affinity: TaskAffinityConfig,
+}
 
 impl RuleBasedRouter {
     /// Create a new rule-based router
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Route task types with a configured affinity straight to their tier
+    ///
+    /// See `routing.task_affinity`. Affinities take precedence over every rule.
+    pub fn with_task_affinity(mut self, task_affinity: TaskAffinityConfig) -> Self {
+        self.task_affinity = task_affinity;
+        self
     }
 
     /// Route a request based on metadata using rule-based logic
@@ -70,6 +84,16 @@ impl RuleBasedRouter {
         use Importance::*;
         use TaskType::*;
 
+        // Rule 0: Operator-configured task affinity wins over the built-in rules
+        if let Some(target) = self.task_affinity.for_task(meta.task_type) {
+            tracing::debug!(
+                task_type = ?meta.task_type,
+                target_tier = ?target,
+                "Task affinity matched"
+            );
+            return Some(target);
+        }
+
         // Rule 1: Trivial/casual tasks → Fast tier
         if matches!(meta.task_type, CasualChat)
             && meta.token_estimate < 256
@@ -507,4 +531,69 @@ log_level = "info"
         assert_eq!(decision.target(), TargetModel::Fast);
         assert_eq!(decision.strategy(), RoutingStrategy::Rule);
     }
+
+    // Rule 0: task affinity
+    fn affinity_router() -> RuleBasedRouter {
+        RuleBasedRouter::new().with_task_affinity(TaskAffinityConfig {
+            casual_chat: Some(TargetModel::Deep),
+            code: Some(TargetModel::Fast),
+            creative_writing: Some(TargetModel::Balanced),
+            deep_analysis: Some(TargetModel::Fast),
+            document_summary: Some(TargetModel::Deep),
+            question_answer: Some(TargetModel::Fast),
+        })
+    }
+
+    #[test]
+    fn test_task_affinity_maps_each_task_type_to_its_tier() {
+        let router = affinity_router();
+        let cases = [
+            (TaskType::CasualChat, TargetModel::Deep),
+            (TaskType::Code, TargetModel::Fast),
+            (TaskType::CreativeWriting, TargetModel::Balanced),
+            (TaskType::DeepAnalysis, TargetModel::Fast),
+            (TaskType::DocumentSummary, TargetModel::Deep),
+            (TaskType::QuestionAnswer, TargetModel::Fast),
+        ];
+
+        for (task_type, expected) in cases {
+            // Token counts and importance that would pick another tier (or none) by rule
+            for meta in [
+                RouteMetadata::new(100).with_task_type(task_type),
+                RouteMetadata::new(5000)
+                    .with_task_type(task_type)
+                    .with_importance(Importance::High),
+            ] {
+                assert_eq!(
+                    router.evaluate_rules(&meta),
+                    Some(expected),
+                    "{:?} should follow its affinity",
+                    task_type
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_unmapped_task_types_fall_through_to_rules() {
+        let router = RuleBasedRouter::new().with_task_affinity(TaskAffinityConfig {
+            creative_writing: Some(TargetModel::Balanced),
+            ..TaskAffinityConfig::default()
+        });
+
+        // Rule 3: small code request → Balanced
+        let code = RouteMetadata::new(100).with_task_type(TaskType::Code);
+        assert_eq!(router.evaluate_rules(&code), Some(TargetModel::Balanced));
+
+        // Rule 2 would send creative writing to Deep; the affinity overrides it
+        let creative = RouteMetadata::new(100).with_task_type(TaskType::CreativeWriting);
+        assert_eq!(
+            router.evaluate_rules(&creative),
+            Some(TargetModel::Balanced)
+        );
+
+        // No rule matches a short question, with or without unrelated affinities
+        let question = RouteMetadata::new(100).with_task_type(TaskType::QuestionAnswer);
+        assert_eq!(router.evaluate_rules(&question), None);
+    }
 }
@@ -0,0 +1,137 @@
+//! HTTP route assembly for the listeners started by `main`
+//!
+//! The public listener serves the API with the full middleware stack. With
+//! `observability.metrics_bind` set, `/metrics` and `/health` move to a second,
+//! internal listener so they can be firewalled off from API clients.
+//!
+//! Both routers share one [`ReloadableState`], so a config reload reaches
+//! every listener at once. The public router is served on every address in
+//! `server.listen_addrs()`, each bound with [`bind_listener`].
+
+use crate::handlers;
+use crate::middleware::{
+    admin_auth_middleware, body_limit_middleware, request_id_middleware, request_timeout_middleware,
+};
+use crate::reload::ReloadableState;
+use axum::{
+    Router,
+    extract::DefaultBodyLimit,
+    middleware,
+    routing::{get, post},
+};
+use std::net::SocketAddr;
+use tower_http::compression::{CompressionLayer, predicate::SizeAbove};
+
+/// Pending connections queued per listener before the kernel refuses more
+const LISTEN_BACKLOG: i32 = 1024;
+
+/// Bind a TCP listener on `addr`
+///
+/// IPv6 sockets are bound v6-only. On most systems `[::]` otherwise accepts
+/// IPv4 clients too and claims the port for both families, so a `0.0.0.0`
+/// listener on the same port (as `server.dual_stack` sets up) would fail to bind.
+pub fn bind_listener(addr: SocketAddr) -> std::io::Result<tokio::net::TcpListener> {
+    use socket2::{Domain, Protocol, Socket, Type};
+
+    let socket = Socket::new(Domain::for_address(addr), Type::STREAM, Some(Protocol::TCP))?;
+    if addr.is_ipv6() {
+        socket.set_only_v6(true)?;
+    }
+    // Same as `TcpListener::bind`: restarts needn't wait out TIME_WAIT connections
+    #[cfg(unix)]
+    socket.set_reuse_address(true)?;
+    socket.bind(&addr.into())?;
+    socket.listen(LISTEN_BACKLOG)?;
+    socket.set_nonblocking(true)?;
+    tokio::net::TcpListener::from_std(socket.into())
+}
+
+/// Routes of the public API listener
+///
+/// Includes `/metrics` and `/health` unless `observability.metrics_bind` moves
+/// them to [`metrics_app`], and the admin API when `server.admin_token` is set.
+pub fn public_app(state: impl Into<ReloadableState>) -> Router {
+    let state = state.into();
+    let current = state.current();
+    let config = current.config();
+    let max_request_body_bytes = config.server.max_request_body_bytes;
+    // Chat responses are gzipped for clients that ask, when `server.enable_compression` is on.
+    // Unlike the default predicate, SSE streams are compressed too
+    let compression = CompressionLayer::new()
+        .gzip(config.server.enable_compression)
+        .compress_when(SizeAbove::default());
+
+    let mut routes = Router::new()
+        // Legacy endpoints
+        .route("/livez", get(handlers::probes::livez))
+        .route("/readyz", get(handlers::probes::readyz))
+        .route(
+            "/chat",
+            post(handlers::chat::handler).layer(compression.clone()),
+        )
+        .route("/models", get(handlers::models::handler))
+        .route("/version", get(handlers::version::handler))
+        // OpenAI-compatible endpoints
+        .route(
+            "/v1/chat/completions",
+            post(handlers::openai::completions::handler).layer(compression),
+        )
+        .route("/v1/models", get(handlers::openai::models::handler));
+
+    if config.observability.metrics_bind.is_none() {
+        routes = routes.merge(diagnostic_routes());
+    }
+
+    // Admin API exists only when a token is configured, and always requires it
+    if config.server.admin_token.is_some() {
+        routes = routes.merge(
+            Router::new()
+                .route(
+                    "/admin/endpoints/{name}/drain",
+                    post(handlers::admin::drain),
+                )
+                .route(
+                    "/admin/endpoints/{name}/undrain",
+                    post(handlers::admin::undrain),
+                )
+                .route("/admin/drain", post(handlers::admin::drain_server))
+                .route("/admin/undrain", post(handlers::admin::undrain_server))
+                .route("/admin/reload", post(handlers::admin::reload))
+                .route(
+                    "/admin/routing/strategy",
+                    post(handlers::admin::switch_routing_strategy),
+                )
+                .route_layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    admin_auth_middleware,
+                )),
+        );
+    }
+
+    routes
+        .with_state(state.clone())
+        // Total duration backstop runs inside the request ID layer so 504s still carry an ID
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            request_timeout_middleware,
+        ))
+        // Oversized bodies get an OpenAI-formatted 413; the extractor limit is raised
+        // to match so Axum's own 2 MiB default doesn't reject bodies first
+        .layer(middleware::from_fn_with_state(state, body_limit_middleware))
+        .layer(DefaultBodyLimit::max(max_request_body_bytes))
+        .layer(middleware::from_fn(request_id_middleware))
+}
+
+/// Routes of the internal `observability.metrics_bind` listener
+pub fn metrics_app(state: impl Into<ReloadableState>) -> Router {
+    diagnostic_routes()
+        .with_state(state.into())
+        .layer(middleware::from_fn(request_id_middleware))
+}
+
+/// `/metrics` and `/health`, which expose endpoint names and traffic
+fn diagnostic_routes() -> Router<ReloadableState> {
+    Router::new()
+        .route("/health", get(handlers::health::handler))
+        .route("/metrics", get(handlers::metrics::handler))
+}
@@ -0,0 +1,102 @@
+//! Per-request cap on upstream model calls (`server.max_upstream_calls`)
+//!
+//! Router retries (`routing.retry_policy`) and completion retries
+//! (`routing.max_retries`, streaming failover) are each bounded on their own,
+//! but stacked together one request can fan out into many upstream calls. A
+//! [`CallBudget`] is created per request and handed to both the router and the
+//! completion path; every upstream call spends one unit, and once the budget
+//! is empty no further calls are made.
+
+use crate::error::AppError;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Upstream calls remaining for one request
+///
+/// Clones share the same counter, so a budget can be moved into a streaming
+/// task while the handler keeps its own handle.
+#[derive(Debug, Clone)]
+pub struct CallBudget {
+    limit: Option<usize>,
+    remaining: Arc<AtomicUsize>,
+}
+
+impl CallBudget {
+    /// Budget of `limit` calls; `None` is unlimited
+    pub fn new(limit: Option<usize>) -> Self {
+        Self {
+            limit,
+            remaining: Arc::new(AtomicUsize::new(limit.unwrap_or(usize::MAX))),
+        }
+    }
+
+    /// Budget that never runs out
+    pub fn unlimited() -> Self {
+        Self::new(None)
+    }
+
+    /// Spend one call, returning `false` (and spending nothing) if none are left
+    pub fn try_spend(&self) -> bool {
+        if self.limit.is_none() {
+            return true;
+        }
+        self.remaining
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| n.checked_sub(1))
+            .is_ok()
+    }
+
+    /// Calls left, or `None` if the budget is unlimited
+    pub fn remaining(&self) -> Option<usize> {
+        self.limit.map(|_| self.remaining.load(Ordering::Acquire))
+    }
+
+    /// The configured limit, or `None` if unlimited
+    pub fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    /// Error for a request that ran out of budget before making a completion call
+    pub fn exhausted_error(&self) -> AppError {
+        AppError::CallBudgetExhausted {
+            limit: self.limit.unwrap_or_default(),
+        }
+    }
+}
+
+impl Default for CallBudget {
+    fn default() -> Self {
+        Self::unlimited()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_budget_spends_down_to_zero() {
+        let budget = CallBudget::new(Some(2));
+        assert!(budget.try_spend());
+        assert_eq!(budget.remaining(), Some(1));
+        assert!(budget.try_spend());
+        assert!(!budget.try_spend());
+        assert_eq!(budget.remaining(), Some(0));
+    }
+
+    #[test]
+    fn test_clones_share_the_counter() {
+        let budget = CallBudget::new(Some(1));
+        let clone = budget.clone();
+        assert!(clone.try_spend());
+        assert!(!budget.try_spend());
+    }
+
+    #[test]
+    fn test_unlimited_budget_never_runs_out() {
+        let budget = CallBudget::unlimited();
+        for _ in 0..1000 {
+            assert!(budget.try_spend());
+        }
+        assert_eq!(budget.remaining(), None);
+    }
+}
@@ -0,0 +1,257 @@
+//! Pre-flight prompt size checks against endpoint `context_window`s
+//!
+//! A prompt larger than a model's context window fails at the backend with an
+//! error that says little about why. Once routing has picked a tier, the
+//! estimated prompt size is compared with the tier's largest window: a prompt
+//! that fits nowhere in the tier is rejected with a 400 naming the limit, or
+//! moved up to a larger tier with `routing.context_overflow = "escalate"`.
+//! Endpoints in the tier that are too small are then excluded from selection.
+//!
+//! All checks are skipped when no endpoint declares a `context_window`.
+
+use crate::config::{Config, ContextOverflow, ModelEndpoint};
+use crate::error::{AppError, AppResult};
+use crate::middleware::RequestId;
+use crate::models::{EndpointName, ExclusionSet};
+use crate::router::{RoutingDecision, TargetModel};
+
+/// Tiers a prompt may escalate to from `tier`, smallest first
+fn larger_tiers(tier: TargetModel) -> &'static [TargetModel] {
+    match tier {
+        TargetModel::Fast => &[TargetModel::Balanced, TargetModel::Deep],
+        TargetModel::Balanced => &[TargetModel::Deep],
+        TargetModel::Deep => &[],
+    }
+}
+
+/// Whether a prompt of `token_estimate` tokens fits some endpoint of `tier`
+fn tier_fits(config: &Config, tier: TargetModel, token_estimate: usize) -> bool {
+    config
+        .models
+        .tier_context_window(tier)
+        .is_none_or(|window| token_estimate <= window)
+}
+
+/// Check `decision` against the prompt size, escalating its tier if configured
+///
+/// # Errors
+/// Returns [`AppError::Validation`] (400) when the prompt fits no endpoint of
+/// the routed tier and either escalation is off or no larger tier fits it.
+pub fn fit_decision(
+    config: &Config,
+    decision: RoutingDecision,
+    token_estimate: usize,
+    request_id: RequestId,
+) -> AppResult<RoutingDecision> {
+    let routed = decision.target();
+    if !config.models.has_context_windows() || tier_fits(config, routed, token_estimate) {
+        return Ok(decision);
+    }
+    // Only reachable when the routed tier has a finite window
+    let window = config
+        .models
+        .tier_context_window(routed)
+        .unwrap_or_default();
+
+    let escalated = match config.routing.context_overflow {
+        ContextOverflow::Reject => None,
+        ContextOverflow::Escalate => larger_tiers(routed)
+            .iter()
+            .copied()
+            .find(|&tier| tier_fits(config, tier, token_estimate)),
+    };
+
+    match escalated {
+        Some(tier) => {
+            tracing::info!(
+                request_id = %request_id,
+                token_estimate,
+                routed_tier = ?routed,
+                context_window = window,
+                served_tier = ?tier,
+                "Prompt exceeds the routed tier's context window, escalating"
+            );
+            Ok(decision.with_target(tier).with_warning(format!(
+                "Prompt of about {} tokens exceeds the {:?} tier's context window of {} \
+                 tokens; served by {:?} tier instead",
+                token_estimate, routed, window, tier
+            )))
+        }
+        None => {
+            tracing::warn!(
+                request_id = %request_id,
+                token_estimate,
+                routed_tier = ?routed,
+                context_window = window,
+                context_overflow = ?config.routing.context_overflow,
+                "Prompt exceeds the routed tier's context window, rejecting"
+            );
+            let hint = match config.routing.context_overflow {
+                ContextOverflow::Reject => {
+                    "Shorten the prompt, request a larger tier, or set \
+                     routing.context_overflow = \"escalate\"."
+                }
+                ContextOverflow::Escalate => "No larger tier fits it either; shorten the prompt.",
+            };
+            Err(AppError::Validation(format!(
+                "Prompt of about {} tokens exceeds the {} token context window of the {:?} tier. {}",
+                token_estimate, window, routed, hint
+            )))
+        }
+    }
+}
+
+/// Check a prompt against the endpoint a request named explicitly
+///
+/// # Errors
+/// Returns [`AppError::Validation`] (400) when the prompt exceeds the
+/// endpoint's `context_window`. Named endpoints are never escalated.
+pub fn check_endpoint(endpoint: &ModelEndpoint, token_estimate: usize) -> AppResult<()> {
+    match endpoint.context_window() {
+        Some(window) if token_estimate > window => Err(AppError::Validation(format!(
+            "Prompt of about {} tokens exceeds the {} token context window of model '{}'. \
+             Shorten the prompt or use a model with a larger context window.",
+            token_estimate,
+            window,
+            endpoint.name()
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Token estimate routing decides on for a prompt of `token_estimate` tokens
+///
+/// Capped at the largest `context_window` any tier accepts (a larger prompt
+/// fits nowhere, whichever tier the router picks) and at
+/// `routing.token_estimate_cap`. Uncapped when neither applies.
+pub fn routing_token_estimate(config: &Config, token_estimate: usize) -> usize {
+    [
+        config.models.largest_context_window(),
+        config.routing.token_estimate_cap,
+    ]
+    .into_iter()
+    .flatten()
+    .fold(token_estimate, usize::min)
+}
+
+/// Endpoints, in every tier, too small for a prompt of `token_estimate` tokens
+///
+/// Seeds a request's exclusion set so selection (including tier fallback and
+/// stream failover) never picks them.
+pub fn oversized_endpoints(config: &Config, token_estimate: usize) -> ExclusionSet {
+    [TargetModel::Fast, TargetModel::Balanced, TargetModel::Deep]
+        .into_iter()
+        .flat_map(|tier| config.models.tier(tier))
+        .filter(|endpoint| !endpoint.fits_context(token_estimate))
+        .map(EndpointName::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::router::RoutingStrategy;
+
+    /// Fast capped at 1000 tokens (one endpoint at 500), Balanced at 4000, Deep unlimited
+    fn config(overflow: &str) -> Config {
+        let toml = format!(
+            r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+
+[[models.fast]]
+name = "fast-small"
+base_url = "http://localhost:1234/v1"
+max_tokens = 512
+context_window = 500
+
+[[models.fast]]
+name = "fast-1"
+base_url = "http://localhost:1235/v1"
+max_tokens = 512
+context_window = 1000
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "http://localhost:1236/v1"
+max_tokens = 1024
+context_window = 4000
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:1237/v1"
+max_tokens = 2048
+
+[routing]
+strategy = "rule"
+context_overflow = "{overflow}"
+"#
+        );
+        toml::from_str(&toml).expect("should parse config")
+    }
+
+    fn fast_decision() -> RoutingDecision {
+        RoutingDecision::new(TargetModel::Fast, RoutingStrategy::Rule)
+    }
+
+    #[test]
+    fn test_fitting_prompt_keeps_decision() {
+        let config = config("reject");
+        let decision = fit_decision(&config, fast_decision(), 800, RequestId::new()).unwrap();
+        assert_eq!(decision, fast_decision());
+    }
+
+    #[test]
+    fn test_escalates_to_smallest_tier_that_fits() {
+        let config = config("escalate");
+
+        let decision = fit_decision(&config, fast_decision(), 2000, RequestId::new()).unwrap();
+        assert_eq!(decision.target(), TargetModel::Balanced);
+        assert_eq!(decision.strategy(), RoutingStrategy::Rule);
+        assert!(decision.warnings()[0].contains("context window of 1000"));
+
+        let decision = fit_decision(&config, fast_decision(), 50_000, RequestId::new()).unwrap();
+        assert_eq!(decision.target(), TargetModel::Deep);
+    }
+
+    #[test]
+    fn test_reject_names_the_limit() {
+        let config = config("reject");
+        let err = fit_decision(&config, fast_decision(), 2000, RequestId::new()).unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+        assert!(
+            err.to_string().contains("1000 token context window"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_oversized_endpoints_and_named_endpoint_check() {
+        let config = config("reject");
+
+        let oversized = oversized_endpoints(&config, 800);
+        assert_eq!(
+            oversized,
+            ExclusionSet::from([EndpointName::from("fast-small")])
+        );
+        assert!(oversized_endpoints(&config, 100).is_empty());
+
+        assert!(check_endpoint(&config.models.fast[1], 1000).is_ok());
+        let err = check_endpoint(&config.models.fast[1], 1001).unwrap_err();
+        assert!(err.to_string().contains("model 'fast-1'"), "{}", err);
+    }
+
+    #[test]
+    fn test_routing_token_estimate_caps_only_when_configured() {
+        // Deep is unlimited, so no context window caps the estimate
+        let mut config = config("reject");
+        assert_eq!(routing_token_estimate(&config, 50_000), 50_000);
+
+        config.routing.token_estimate_cap = Some(2048);
+        assert_eq!(routing_token_estimate(&config, 50_000), 2048);
+        assert_eq!(routing_token_estimate(&config, 2048), 2048);
+        assert_eq!(routing_token_estimate(&config, 300), 300);
+    }
+}
@@ -0,0 +1,59 @@
+//! Per-request conversation limits (`server.max_messages`, `server.max_total_prompt_chars`)
+//!
+//! Checked right after a chat request is parsed, before routing or any backend
+//! call, so an oversized conversation costs nothing but the 400 it gets back.
+//! Request parsing still enforces the hard ceilings these settings sit under.
+
+use crate::config::ServerConfig;
+use crate::error::{AppError, AppResult};
+
+/// Reject a conversation with more messages or characters than configured
+///
+/// # Errors
+/// Returns [`AppError::Validation`] naming the exceeded setting.
+pub fn check(server: &ServerConfig, messages: usize, total_chars: usize) -> AppResult<()> {
+    if messages > server.max_messages {
+        return Err(AppError::Validation(format!(
+            "conversation has {} messages, more than the {} allowed (server.max_messages)",
+            messages, server.max_messages
+        )));
+    }
+    if total_chars > server.max_total_prompt_chars {
+        return Err(AppError::Validation(format!(
+            "conversation has {} characters, more than the {} allowed \
+            (server.max_total_prompt_chars)",
+            total_chars, server.max_total_prompt_chars
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server(max_messages: usize, max_total_prompt_chars: usize) -> ServerConfig {
+        let toml = format!(
+            "host = \"127.0.0.1\"\nport = 3000\nmax_messages = {max_messages}\n\
+            max_total_prompt_chars = {max_total_prompt_chars}"
+        );
+        toml::from_str(&toml).expect("should parse server config")
+    }
+
+    #[test]
+    fn test_check_allows_limits_and_rejects_beyond() {
+        let server = server(3, 100);
+        assert!(check(&server, 3, 100).is_ok());
+
+        let err = check(&server, 4, 10).unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+        assert!(err.to_string().contains("server.max_messages"), "{}", err);
+
+        let err = check(&server, 1, 101).unwrap_err();
+        assert!(
+            err.to_string().contains("server.max_total_prompt_chars"),
+            "{}",
+            err
+        );
+    }
+}
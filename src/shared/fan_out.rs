@@ -0,0 +1,71 @@
+//! Per-request fan-out cap (`server.max_parallel_upstream_per_request`)
+//!
+//! A completion asking for `n` choices makes `n` upstream calls. Left alone,
+//! one request could open as many concurrent calls against a backend, so the
+//! width of that fan-out is capped. Checked before routing, so a rejected
+//! request costs no upstream call.
+
+use crate::config::{FanOutOverflow, ServerConfig};
+use crate::error::{AppError, AppResult};
+
+/// How many of `calls` upstream calls may run at once for one request
+///
+/// Returns `calls` without a cap, and the cap itself when `calls` exceeds it
+/// under [`FanOutOverflow::Sequential`].
+///
+/// # Errors
+/// Returns [`AppError::Validation`] when `calls` exceeds the cap under
+/// [`FanOutOverflow::Reject`].
+pub fn parallel_width(server: &ServerConfig, calls: usize) -> AppResult<usize> {
+    let Some(cap) = server.max_parallel_upstream_per_request else {
+        return Ok(calls);
+    };
+    if calls <= cap {
+        return Ok(calls);
+    }
+    match server.fan_out_overflow {
+        FanOutOverflow::Sequential => Ok(cap),
+        FanOutOverflow::Reject => Err(AppError::Validation(format!(
+            "request needs {} parallel upstream calls, more than the {} allowed \
+            (server.max_parallel_upstream_per_request)",
+            calls, cap
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server(extra: &str) -> ServerConfig {
+        let toml = format!("host = \"127.0.0.1\"\nport = 3000\n{extra}");
+        toml::from_str(&toml).expect("should parse server config")
+    }
+
+    #[test]
+    fn test_width_is_unbounded_without_a_cap() {
+        assert_eq!(parallel_width(&server(""), 50).unwrap(), 50);
+    }
+
+    #[test]
+    fn test_sequential_overflow_narrows_to_the_cap() {
+        let server = server("max_parallel_upstream_per_request = 4");
+        assert_eq!(parallel_width(&server, 3).unwrap(), 3);
+        assert_eq!(parallel_width(&server, 10).unwrap(), 4);
+    }
+
+    #[test]
+    fn test_reject_overflow_refuses_beyond_the_cap() {
+        let server = server("max_parallel_upstream_per_request = 4\nfan_out_overflow = \"reject\"");
+        assert_eq!(parallel_width(&server, 4).unwrap(), 4);
+
+        let err = parallel_width(&server, 5).unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+        assert!(
+            err.to_string()
+                .contains("server.max_parallel_upstream_per_request"),
+            "{}",
+            err
+        );
+    }
+}
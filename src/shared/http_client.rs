@@ -0,0 +1,30 @@
+//! Shared, pooled HTTP client for upstream requests
+//!
+//! One `reqwest::Client` is built at startup from `[server].http_pool` and
+//! shared by everything in Octoroute that talks to backends directly. The
+//! client is internally reference-counted, so cloning it is cheap and every
+//! clone draws from the same connection pool.
+
+use crate::config::HttpPoolConfig;
+use crate::error::{AppError, AppResult};
+use std::time::Duration;
+
+/// Build the upstream HTTP client with the configured connection pool
+///
+/// `connect_timeout` bounds every new connection the pool opens; health
+/// checks are the client's main user, so callers pass
+/// `[health].probe_connect_timeout_ms`.
+///
+/// # Errors
+/// Returns an error if the TLS backend cannot be initialized.
+pub fn build_pooled_client(
+    pool: &HttpPoolConfig,
+    connect_timeout: Duration,
+) -> AppResult<reqwest::Client> {
+    reqwest::Client::builder()
+        .pool_max_idle_per_host(pool.max_idle_per_host)
+        .pool_idle_timeout(Duration::from_secs(pool.idle_timeout_seconds))
+        .connect_timeout(connect_timeout)
+        .build()
+        .map_err(|e| AppError::Internal(format!("Failed to build upstream HTTP client: {}", e)))
+}
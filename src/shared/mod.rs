@@ -3,4 +3,17 @@
 //! This module contains logic that is shared between the legacy `/chat`
 //! endpoint and the OpenAI-compatible `/v1/chat/completions` endpoint.
 
+pub mod call_budget;
+pub mod context_window;
+pub mod conversation_limits;
+pub mod fan_out;
+pub mod http_client;
+pub mod prompt_cache_key;
+pub mod prompt_log;
 pub mod query;
+pub mod reasoning;
+pub mod system_prompt;
+pub mod tier_budget;
+pub mod ttl_cache;
+pub mod upstream;
+pub mod user_tracker;
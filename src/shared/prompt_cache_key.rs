@@ -0,0 +1,110 @@
+//! Deterministic cache keys for prompts
+//!
+//! A cache keyed by prompt (such as a routing-decision cache) hits more often
+//! when prompts that differ only in insignificant whitespace map to the same
+//! key. [`normalize_prompt_for_cache`] trims the prompt and collapses internal
+//! whitespace runs, optionally lowercasing it too, since for some workloads
+//! case carries meaning. [`prompt_cache_key`] hashes the normalized form.
+
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// How much of a prompt's formatting a cache key ignores
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PromptNormalization {
+    /// Hash the prompt exactly as sent
+    Off,
+    /// Trim, and collapse each internal whitespace run to a single space
+    #[default]
+    Whitespace,
+    /// As `Whitespace`, and lowercase the prompt as well
+    WhitespaceAndCase,
+}
+
+/// FNV-1a 64-bit hash of `text`
+///
+/// Stable across processes and releases, unlike `std`'s default hasher, so
+/// keys can be compared between instances or persisted.
+pub(crate) fn fnv1a_64(text: &str) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    text.bytes().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    })
+}
+
+/// The form of `prompt` that cache keys are derived from
+///
+/// Borrows the prompt unchanged with [`PromptNormalization::Off`].
+pub fn normalize_prompt_for_cache(
+    prompt: &str,
+    normalization: PromptNormalization,
+) -> Cow<'_, str> {
+    if normalization == PromptNormalization::Off {
+        return Cow::Borrowed(prompt);
+    }
+    let collapsed = prompt.split_whitespace().collect::<Vec<_>>().join(" ");
+    if normalization == PromptNormalization::WhitespaceAndCase {
+        Cow::Owned(collapsed.to_lowercase())
+    } else {
+        Cow::Owned(collapsed)
+    }
+}
+
+/// Deterministic cache key for `prompt` under `normalization`
+pub fn prompt_cache_key(prompt: &str, normalization: PromptNormalization) -> u64 {
+    fnv1a_64(&normalize_prompt_for_cache(prompt, normalization))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPACED: &str = "  Explain\tthe borrow\n\n checker  ";
+    const PLAIN: &str = "Explain the borrow checker";
+
+    #[test]
+    fn test_whitespace_differences_hash_equally_when_normalized() {
+        assert_eq!(
+            normalize_prompt_for_cache(SPACED, PromptNormalization::Whitespace),
+            PLAIN
+        );
+        assert_eq!(
+            prompt_cache_key(SPACED, PromptNormalization::Whitespace),
+            prompt_cache_key(PLAIN, PromptNormalization::Whitespace)
+        );
+    }
+
+    #[test]
+    fn test_whitespace_differences_hash_differently_when_off() {
+        assert_eq!(
+            normalize_prompt_for_cache(SPACED, PromptNormalization::Off),
+            SPACED
+        );
+        assert_ne!(
+            prompt_cache_key(SPACED, PromptNormalization::Off),
+            prompt_cache_key(PLAIN, PromptNormalization::Off)
+        );
+    }
+
+    #[test]
+    fn test_case_is_kept_unless_lowercasing() {
+        let shouted = "EXPLAIN the Borrow checker";
+        assert_ne!(
+            prompt_cache_key(shouted, PromptNormalization::Whitespace),
+            prompt_cache_key(PLAIN, PromptNormalization::Whitespace)
+        );
+        assert_eq!(
+            prompt_cache_key(shouted, PromptNormalization::WhitespaceAndCase),
+            prompt_cache_key(SPACED, PromptNormalization::WhitespaceAndCase)
+        );
+    }
+
+    #[test]
+    fn test_keys_are_stable_fnv1a() {
+        assert_eq!(fnv1a_64(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a_64("a"), 0xaf63_dc4c_8601_ec8c);
+    }
+}
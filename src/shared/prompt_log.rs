@@ -0,0 +1,159 @@
+//! Prompt logging with redaction (`observability.log_prompts`)
+//!
+//! Both chat endpoints log the prompt they routed next to the routing decision,
+//! in the form the operator chose: nothing, a stable hash for correlating
+//! repeated prompts, a truncated prefix, or the full text. The event carries
+//! the request ID, so it lines up with the rest of the request's log lines.
+
+use crate::config::{ObservabilityConfig, PromptLogMode};
+use crate::middleware::RequestId;
+use crate::router::TargetModel;
+use crate::shared::prompt_cache_key::fnv1a_64;
+
+/// FNV-1a 64-bit hash of `text`, as 16 hex digits
+///
+/// Stable across processes and releases, so the same prompt hashes the same
+/// in every log line. It identifies repeats; it does not hide short or
+/// guessable prompts from someone able to hash candidates.
+fn stable_hash(text: &str) -> String {
+    format!("{:016x}", fnv1a_64(text))
+}
+
+/// The prompt as it may appear in logs, or `None` if it must not be logged
+pub fn loggable_prompt(mode: PromptLogMode, max_chars: usize, prompt: &str) -> Option<String> {
+    match mode {
+        PromptLogMode::None => None,
+        PromptLogMode::Hashed => Some(format!("fnv1a:{}", stable_hash(prompt))),
+        PromptLogMode::Truncated => {
+            // Char-based so multi-byte UTF-8 is never split
+            let mut chars = prompt.chars();
+            let truncated: String = chars.by_ref().take(max_chars).collect();
+            if chars.next().is_some() {
+                Some(format!("{}...", truncated))
+            } else {
+                Some(truncated)
+            }
+        }
+        PromptLogMode::Full => Some(prompt.to_string()),
+    }
+}
+
+/// Log the routed prompt per `observability.log_prompts` (no event when `none`)
+pub fn log_routed_prompt(
+    config: &ObservabilityConfig,
+    request_id: RequestId,
+    prompt: &str,
+    target_tier: TargetModel,
+) {
+    let Some(logged) = loggable_prompt(config.log_prompts, config.log_prompt_chars, prompt) else {
+        return;
+    };
+    tracing::info!(
+        request_id = %request_id,
+        target_tier = ?target_tier,
+        prompt_log_mode = config.log_prompts.as_str(),
+        prompt_chars = prompt.chars().count(),
+        prompt = %logged,
+        "Routed prompt"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    const PROMPT: &str = "My card number is 4111 1111 1111 1111, why was it declined?";
+
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Run `log_routed_prompt` with `mode` and return everything it logged
+    fn logged_output(mode: PromptLogMode, max_chars: usize) -> String {
+        let logs = CapturedLogs::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer({
+                let logs = logs.clone();
+                move || logs.clone()
+            })
+            .with_ansi(false)
+            .finish();
+        let config = ObservabilityConfig {
+            log_prompts: mode,
+            log_prompt_chars: max_chars,
+            ..ObservabilityConfig::default()
+        };
+
+        tracing::subscriber::with_default(subscriber, || {
+            log_routed_prompt(&config, RequestId::new(), PROMPT, TargetModel::Fast);
+        });
+
+        let bytes = logs.0.lock().unwrap().clone();
+        String::from_utf8(bytes).expect("logs should be UTF-8")
+    }
+
+    #[test]
+    fn test_hashed_logs_stable_hash_not_content() {
+        let first = loggable_prompt(PromptLogMode::Hashed, 200, PROMPT).unwrap();
+        let second = loggable_prompt(PromptLogMode::Hashed, 200, PROMPT).unwrap();
+        let other = loggable_prompt(PromptLogMode::Hashed, 200, "Hello").unwrap();
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+        // Known FNV-1a value, so the hash can't drift between releases
+        assert_eq!(stable_hash("a"), "af63dc4c8601ec8c");
+
+        let output = logged_output(PromptLogMode::Hashed, 200);
+        assert!(output.contains(&first), "got: {}", output);
+        assert!(!output.contains("4111"), "content leaked: {}", output);
+    }
+
+    #[test]
+    fn test_truncated_logs_at_most_max_chars() {
+        let logged = loggable_prompt(PromptLogMode::Truncated, 10, PROMPT).unwrap();
+        assert_eq!(logged, "My card nu...");
+
+        let short = loggable_prompt(PromptLogMode::Truncated, 10, "Hi").unwrap();
+        assert_eq!(short, "Hi");
+
+        // Multi-byte characters count as one and are never split
+        let emoji = loggable_prompt(PromptLogMode::Truncated, 2, "🦀🦀🦀").unwrap();
+        assert_eq!(emoji, "🦀🦀...");
+
+        let output = logged_output(PromptLogMode::Truncated, 10);
+        assert!(output.contains("My card nu..."), "got: {}", output);
+        assert!(!output.contains("4111"), "content leaked: {}", output);
+    }
+
+    #[test]
+    fn test_full_logs_whole_prompt() {
+        let output = logged_output(PromptLogMode::Full, 10);
+        assert!(output.contains(PROMPT), "got: {}", output);
+    }
+
+    #[test]
+    fn test_none_logs_neither_content_nor_hash() {
+        assert_eq!(loggable_prompt(PromptLogMode::None, 200, PROMPT), None);
+
+        let output = logged_output(PromptLogMode::None, 200);
+        assert!(
+            output.is_empty(),
+            "nothing should be logged, got: {}",
+            output
+        );
+    }
+}
@@ -3,12 +3,19 @@
 //! This module provides reusable query execution that can be used by both
 //! the legacy `/chat` endpoint and the OpenAI-compatible `/v1/chat/completions` endpoint.
 
-use crate::config::ModelEndpoint;
+use crate::config::{EmptyCompletionPolicy, ModelEndpoint};
 use crate::error::{AppError, AppResult, ModelQueryError};
 use crate::handlers::AppState;
+use crate::metrics::EndpointOutcome;
 use crate::middleware::RequestId;
-use crate::models::{EndpointName, ExclusionSet};
-use crate::router::{RoutingDecision, RoutingStrategy, TargetModel};
+use crate::models::health::RECOVERY_RETRY_AFTER_SECS;
+use crate::models::{EndpointName, ExclusionSet, HealthFailureKind};
+use crate::router::{RouteMetadata, RoutingDecision, RoutingStrategy, TargetModel, TaskType};
+use crate::shared::call_budget::CallBudget;
+use crate::shared::context_window;
+use crate::shared::reasoning::ReasoningFilter;
+use crate::shared::upstream;
+use std::collections::BTreeMap;
 use std::time::Duration;
 
 /// Default maximum number of retry attempts
@@ -29,6 +36,12 @@ pub struct QueryConfig {
     max_retries: usize,
     /// Base backoff in milliseconds (doubles each retry)
     retry_backoff_ms: u64,
+    /// Endpoint tags to prefer when selecting within a tier (see [`task_type_tags`])
+    preferred_tags: Vec<String>,
+    /// Endpoints never selected for this request (e.g., too small for the prompt)
+    excluded_endpoints: ExclusionSet,
+    /// Per-request upstream call budget shared with routing (`server.max_upstream_calls`)
+    call_budget: CallBudget,
 }
 
 /// Optional sampling parameters that override endpoint defaults
@@ -36,15 +49,25 @@ pub struct QueryConfig {
 /// These parameters are passed through from OpenAI-compatible requests.
 /// When `None`, the endpoint's configured defaults are used.
 ///
-/// Note: The underlying open-agent-sdk only supports `temperature` and `max_tokens`.
-/// Other OpenAI parameters (top_p, presence_penalty, frequency_penalty) are accepted
-/// by the API for compatibility but are not forwarded to the backend.
+/// Every parameter that is set is sent upstream (see [`upstream::request_body`]).
 #[derive(Debug, Clone, Default)]
 pub struct SamplingParams {
     /// Override temperature (0.0 to 2.0)
     pub temperature: Option<f64>,
     /// Override max_tokens
     pub max_tokens: Option<u32>,
+    /// Nucleus sampling (0.0 to 1.0)
+    pub top_p: Option<f64>,
+    /// Presence penalty (-2.0 to 2.0)
+    pub presence_penalty: Option<f64>,
+    /// Frequency penalty (-2.0 to 2.0)
+    pub frequency_penalty: Option<f64>,
+    /// Token ID -> bias (-100 to 100)
+    pub logit_bias: Option<BTreeMap<u32, i32>>,
+    /// Return token log-probabilities
+    pub logprobs: Option<bool>,
+    /// Most likely alternatives per token (0 to 20)
+    pub top_logprobs: Option<u8>,
 }
 
 impl QueryConfig {
@@ -59,9 +82,30 @@ impl QueryConfig {
         Ok(Self {
             max_retries,
             retry_backoff_ms,
+            preferred_tags: Vec::new(),
+            excluded_endpoints: ExclusionSet::new(),
+            call_budget: CallBudget::unlimited(),
         })
     }
 
+    /// Prefer endpoints carrying all of `tags` when selecting within a tier
+    pub fn with_preferred_tags(mut self, tags: Vec<String>) -> Self {
+        self.preferred_tags = tags;
+        self
+    }
+
+    /// Never select `endpoints` for this request
+    pub fn with_excluded_endpoints(mut self, endpoints: ExclusionSet) -> Self {
+        self.excluded_endpoints = endpoints;
+        self
+    }
+
+    /// Spend completion attempts from `budget`, the one routing already drew from
+    pub fn with_call_budget(mut self, budget: CallBudget) -> Self {
+        self.call_budget = budget;
+        self
+    }
+
     /// Get the maximum number of retry attempts
     pub fn max_retries(&self) -> usize {
         self.max_retries
@@ -71,6 +115,21 @@ impl QueryConfig {
     pub fn retry_backoff_ms(&self) -> u64 {
         self.retry_backoff_ms
     }
+
+    /// Get the endpoint tags preferred during selection
+    pub fn preferred_tags(&self) -> &[String] {
+        &self.preferred_tags
+    }
+
+    /// Get the endpoints excluded from selection up front
+    pub fn excluded_endpoints(&self) -> &ExclusionSet {
+        &self.excluded_endpoints
+    }
+
+    /// Get the upstream call budget completion attempts are spent from
+    pub fn call_budget(&self) -> &CallBudget {
+        &self.call_budget
+    }
 }
 
 impl Default for QueryConfig {
@@ -106,11 +165,52 @@ pub fn calculate_backoff(config: &QueryConfig, attempt: usize) -> u64 {
         .min(MAX_BACKOFF_MS)
 }
 
+/// Resolve the effective `max_tokens` for a request against an endpoint's cap
+///
+/// Requests asking for more than the endpoint's configured `max_tokens` are
+/// clamped to the cap instead of being forwarded (backends reject them with
+/// confusing errors). When the request doesn't set `max_tokens`, the endpoint
+/// default is used.
+///
+/// # Returns
+/// The effective value, plus a warning message when clamping occurred.
+pub fn resolve_max_tokens(
+    endpoint: &ModelEndpoint,
+    requested: Option<u32>,
+) -> (u32, Option<String>) {
+    // Config::validate() guarantees max_tokens fits in u32
+    let cap = endpoint.max_tokens() as u32;
+    match requested {
+        Some(max_tokens) if max_tokens > cap => (
+            cap,
+            Some(format!(
+                "max_tokens {} exceeds endpoint '{}' limit, clamped to {}",
+                max_tokens,
+                endpoint.name(),
+                cap
+            )),
+        ),
+        Some(max_tokens) => (max_tokens, None),
+        None => (cap, None),
+    }
+}
+
+/// A model's answer to one completion query
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ModelAnswer {
+    /// The response text, with reasoning blocks removed
+    pub text: String,
+    /// Token log-probabilities, when requested and returned by the backend
+    pub logprobs: Option<serde_json::Value>,
+}
+
 /// Result of a successful query execution
 #[derive(Debug, Clone)]
 pub struct QueryResult {
     /// The response content from the model
     pub content: String,
+    /// Token log-probabilities, when requested and returned by the backend
+    pub logprobs: Option<serde_json::Value>,
     /// The endpoint that was used
     pub endpoint: ModelEndpoint,
     /// The tier that was used
@@ -128,56 +228,32 @@ pub struct QueryResult {
 /// not retry on failure.
 ///
 /// # Arguments
+/// * `client` - The pooled upstream HTTP client
 /// * `endpoint` - The model endpoint to query
 /// * `prompt` - The prompt to send (can be a single message or combined messages)
 /// * `timeout_seconds` - Maximum time to wait for response
-/// * `request_id` - Request ID for logging
+/// * `first_token_timeout` - Maximum time to wait for the first content chunk, if any
+/// * `request_id` - Request ID for logging; its client deadline, if any, caps both timeouts
 /// * `attempt` - Current attempt number (for logging)
 /// * `max_retries` - Total number of retries (for logging)
 /// * `sampling_params` - Optional sampling parameters to override endpoint defaults
 ///
 /// # Returns
-/// The response text on success, or an `AppError` on failure.
+/// The answer on success, or an `AppError` on failure.
+#[allow(clippy::too_many_arguments)] // Timeouts, logging context and sampling overrides
 pub async fn query_model(
+    client: &reqwest::Client,
     endpoint: &ModelEndpoint,
     prompt: &str,
     timeout_seconds: u64,
+    first_token_timeout: Option<Duration>,
     request_id: RequestId,
     attempt: usize,
     max_retries: usize,
     sampling_params: Option<&SamplingParams>,
-) -> AppResult<String> {
-    // Determine effective sampling parameters (request overrides > endpoint defaults)
-    let effective_max_tokens = sampling_params
-        .and_then(|p| p.max_tokens)
-        .unwrap_or(endpoint.max_tokens() as u32);
-    let effective_temperature = sampling_params
-        .and_then(|p| p.temperature)
-        .map(|t| t as f32)
-        .unwrap_or(endpoint.temperature() as f32);
-
-    // Build AgentOptions with effective parameters
-    let options = open_agent::AgentOptions::builder()
-        .model(endpoint.name())
-        .base_url(endpoint.base_url())
-        .max_tokens(effective_max_tokens)
-        .temperature(effective_temperature)
-        .build()
-        .map_err(|e| {
-            tracing::error!(
-                request_id = %request_id,
-                endpoint_name = %endpoint.name(),
-                endpoint_url = %endpoint.base_url(),
-                max_tokens = effective_max_tokens,
-                temperature = effective_temperature,
-                error = %e,
-                "Failed to build AgentOptions from endpoint configuration"
-            );
-            AppError::ModelQuery(ModelQueryError::AgentOptionsConfigError {
-                endpoint: endpoint.base_url().to_string(),
-                details: format!("{}", e),
-            })
-        })?;
+) -> AppResult<ModelAnswer> {
+    // Request overrides > endpoint defaults
+    let body = upstream::request_body(endpoint, prompt, sampling_params);
 
     tracing::debug!(
         request_id = %request_id,
@@ -187,46 +263,91 @@ pub async fn query_model(
         "Starting model query"
     );
 
-    let timeout_duration = Duration::from_secs(timeout_seconds);
+    // A client deadline closer than the endpoint timeout takes its place
+    let deadline = request_id.deadline().filter(|deadline| {
+        deadline
+            .tighter_than(Duration::from_secs(timeout_seconds))
+            .is_some()
+    });
+    let timeout_duration = match deadline {
+        Some(deadline) => deadline.remaining(),
+        None => Duration::from_secs(timeout_seconds),
+    };
+    let first_token_timeout = first_token_timeout.filter(|budget| *budget < timeout_duration);
 
     use futures::StreamExt;
     let timeout_result = tokio::time::timeout(timeout_duration, async {
-        // Query model and get stream
-        let mut stream = open_agent::query(prompt, &options).await.map_err(|e| {
-            tracing::error!(
-                request_id = %request_id,
-                endpoint_name = %endpoint.name(),
-                error = %e,
-                "Failed to query model"
-            );
-            AppError::ModelQuery(ModelQueryError::StreamError {
-                endpoint: endpoint.base_url().to_string(),
-                bytes_received: 0,
-                error_message: format!("{}", e),
-            })
-        })?;
+        // Query model and wait for the first chunk, within the first-token budget if set
+        let start = async {
+            let mut stream = upstream::stream_completion(client, endpoint, &body)
+                .await
+                .map_err(|e| {
+                    tracing::error!(
+                        request_id = %request_id,
+                        endpoint_name = %endpoint.name(),
+                        error = %e,
+                        "Failed to query model"
+                    );
+                    let error_message = e.to_string();
+                    AppError::ModelQuery(match e.status() {
+                        Some(status) => ModelQueryError::UpstreamStatus {
+                            endpoint: endpoint.base_url().to_string(),
+                            status,
+                            error_message,
+                        },
+                        None => ModelQueryError::StreamError {
+                            endpoint: endpoint.base_url().to_string(),
+                            bytes_received: 0,
+                            error_message,
+                        },
+                    })
+                })?;
+            let first_delta = stream.next().await;
+            Ok::<_, AppError>((first_delta, stream))
+        };
+        let (first_delta, rest) = match first_token_timeout {
+            Some(budget) => tokio::time::timeout(budget, start)
+                .await
+                .map_err(|_elapsed| {
+                    tracing::error!(
+                        request_id = %request_id,
+                        endpoint_name = %endpoint.name(),
+                        first_token_timeout_ms = budget.as_millis() as u64,
+                        attempt = attempt,
+                        max_retries = max_retries,
+                        "No first token within the first-token budget"
+                    );
+                    AppError::FirstTokenTimeout {
+                        endpoint: endpoint.base_url().to_string(),
+                        timeout_ms: budget.as_millis() as u64,
+                    }
+                })??,
+            None => start.await?,
+        };
+        let mut stream = futures::stream::iter(first_delta).chain(rest).boxed();
 
         // Collect response from stream
         let mut response_text = String::new();
-        let mut block_count = 0;
+        let mut logprobs = None;
+        let mut chunk_count = 0;
         while let Some(result) = stream.next().await {
             match result {
-                Ok(block) => {
-                    block_count += 1;
-                    use open_agent::ContentBlock;
-                    match block {
-                        ContentBlock::Text(text_block) => {
-                            response_text.push_str(&text_block.text);
-                        }
-                        other_block => {
-                            tracing::warn!(
-                                request_id = %request_id,
-                                endpoint_name = %endpoint.name(),
-                                block_type = ?other_block,
-                                block_number = block_count,
-                                "Received non-text content block, skipping (not supported - text blocks only)"
-                            );
-                        }
+                Ok(delta) => {
+                    chunk_count += 1;
+                    if let Some(text) = delta.content {
+                        response_text.push_str(&text);
+                    }
+                    if let Some(chunk_logprobs) = delta.logprobs {
+                        upstream::merge_logprobs(&mut logprobs, chunk_logprobs);
+                    }
+                    if !delta.tool_calls.is_empty() {
+                        tracing::warn!(
+                            request_id = %request_id,
+                            endpoint_name = %endpoint.name(),
+                            tool_calls = delta.tool_calls.len(),
+                            chunk_number = chunk_count,
+                            "Received tool call fragments, skipping (not supported - text only)"
+                        );
                     }
                 }
                 Err(e) => {
@@ -235,31 +356,42 @@ pub async fn query_model(
                         endpoint_name = %endpoint.name(),
                         endpoint_url = %endpoint.base_url(),
                         error = %e,
-                        block_count = block_count,
+                        chunk_count = chunk_count,
                         partial_response_length = response_text.len(),
-                        "Stream error after {} blocks ({} chars received). \
+                        "Stream error after {} chunks ({} chars received). \
                         Discarding partial response and triggering retry.",
-                        block_count,
+                        chunk_count,
                         response_text.len()
                     );
                     return Err(AppError::StreamInterrupted {
                         endpoint: endpoint.base_url().to_string(),
                         bytes_received: response_text.len(),
-                        blocks_received: block_count,
+                        blocks_received: chunk_count,
                     });
                 }
             }
         }
 
-        Ok::<String, AppError>(response_text)
+        Ok::<_, AppError>((response_text, logprobs))
     })
     .await;
 
     // Handle timeout result
-    let response_text = match timeout_result {
-        Ok(Ok(text)) => text,
+    let (response_text, logprobs) = match timeout_result {
+        Ok(Ok(answer)) => answer,
         Ok(Err(e)) => return Err(e),
         Err(_elapsed) => {
+            if let Some(deadline) = deadline {
+                tracing::warn!(
+                    request_id = %request_id,
+                    endpoint_name = %endpoint.name(),
+                    deadline_ms = deadline.budget_ms(),
+                    attempt = attempt,
+                    max_retries = max_retries,
+                    "Client deadline passed while waiting for the model"
+                );
+                return Err(deadline.exceeded());
+            }
             tracing::error!(
                 request_id = %request_id,
                 endpoint_name = %endpoint.name(),
@@ -280,13 +412,287 @@ pub async fn query_model(
         }
     };
 
+    // Reasoning blocks are removed from the whole response at once
+    let response_text = match ReasoningFilter::for_endpoint(endpoint) {
+        Some(filter) => filter.strip(&response_text),
+        None => response_text,
+    };
+
     tracing::info!(
         endpoint_name = %endpoint.name(),
         response_length = response_text.len(),
         "Model query completed successfully"
     );
 
-    Ok(response_text)
+    Ok(ModelAnswer {
+        text: response_text,
+        logprobs,
+    })
+}
+
+/// Whether a failed attempt should end the request rather than move on
+///
+/// True for an empty completion under `server.on_empty_completion = "error"`
+/// and for a backend status outside `routing.retryable_statuses`: another
+/// endpoint would answer the same way, and neither says anything about this
+/// endpoint's health. Also true once the client's deadline has passed, which
+/// leaves no time for another attempt and isn't the endpoint's fault.
+pub fn is_final_failure(state: &AppState, error: &AppError) -> bool {
+    match error {
+        AppError::DeadlineExceeded { .. } => true,
+        AppError::ModelQuery(ModelQueryError::EmptyResponse { .. }) => {
+            state.config().server.on_empty_completion == EmptyCompletionPolicy::Error
+        }
+        AppError::ModelQuery(query_error) => query_error
+            .status()
+            .is_some_and(|status| !state.config().routing.is_retryable_status(status)),
+        _ => false,
+    }
+}
+
+/// Record a successful upstream attempt and mark `endpoint_name` healthy
+///
+/// Returns a warning for the client if health tracking failed.
+pub async fn record_attempt_success(
+    state: &AppState,
+    endpoint_name: &str,
+    request_id: RequestId,
+) -> Option<String> {
+    state
+        .metrics()
+        .endpoint_request(endpoint_name, EndpointOutcome::Success);
+    let health_err = state
+        .selector()
+        .health_checker()
+        .mark_success(endpoint_name)
+        .await
+        .err()?;
+
+    tracing::warn!(
+        request_id = %request_id,
+        endpoint_name = %endpoint_name,
+        error = %health_err,
+        "Health tracking skipped: {} (request continues with successful response)",
+        health_err
+    );
+    state
+        .metrics()
+        .health_tracking_failure(endpoint_name, health_err.error_type());
+    Some(format!(
+        "Health tracking failed: {} (endpoint health state may be stale)",
+        health_err
+    ))
+}
+
+/// Record a failed upstream attempt against `endpoint_name`
+///
+/// `health` is how the failure counts against the endpoint's health, or `None`
+/// when it doesn't (see [`is_final_failure`]). Returns a warning for the client
+/// if health tracking failed.
+pub async fn record_attempt_failure(
+    state: &AppState,
+    endpoint_name: &str,
+    health: Option<HealthFailureKind>,
+    request_id: RequestId,
+) -> Option<String> {
+    state
+        .metrics()
+        .endpoint_request(endpoint_name, EndpointOutcome::Failure);
+    let health_err = state
+        .selector()
+        .health_checker()
+        .record_failure(endpoint_name, health?)
+        .await
+        .err()?;
+
+    tracing::warn!(
+        request_id = %request_id,
+        endpoint_name = %endpoint_name,
+        error = %health_err,
+        "Health tracking skipped: {} (endpoint failure not recorded)",
+        health_err
+    );
+    state
+        .metrics()
+        .health_tracking_failure(endpoint_name, health_err.error_type());
+    Some(format!(
+        "Health tracking failed: {} (endpoint health state may be stale)",
+        health_err
+    ))
+}
+
+/// One completion attempt against `endpoint`, with the bookkeeping around it
+///
+/// Holds an in-flight slot for the query, applies the endpoint's timeouts and
+/// `server.on_empty_completion`, records the attempt in
+/// `octoroute_endpoint_requests_total` and updates endpoint health: a success
+/// marks the endpoint healthy and a failure is recorded unless
+/// [`is_final_failure`]. Health tracking problems, and an answer without the
+/// log-probabilities the request asked for, are added to `warnings`.
+///
+/// Both chat handlers query through here, directly for a named endpoint and
+/// via [`execute_query_with_retry`] for a tier, so the two can't drift apart.
+#[allow(clippy::too_many_arguments)] // Logging context and sampling overrides, as in query_model
+pub async fn run_completion(
+    state: &AppState,
+    endpoint: &ModelEndpoint,
+    tier: TargetModel,
+    prompt: &str,
+    request_id: RequestId,
+    attempt: usize,
+    max_attempts: usize,
+    sampling_params: Option<&SamplingParams>,
+    warnings: &mut Vec<String>,
+) -> AppResult<ModelAnswer> {
+    // Endpoint override > tier override > server default
+    let timeout_seconds = state.config().timeout_for_endpoint(endpoint, tier);
+    let result = {
+        let _in_flight = state.selector().in_flight().acquire(endpoint.name());
+        query_model(
+            state.http_client(),
+            endpoint,
+            prompt,
+            timeout_seconds,
+            state.config().server.first_token_timeout(),
+            request_id,
+            attempt,
+            max_attempts,
+            sampling_params,
+        )
+        .await
+    }
+    .and_then(|answer| {
+        let text = check_empty_completion(state, endpoint, answer.text, request_id)?;
+        Ok(ModelAnswer { text, ..answer })
+    });
+
+    let health_warning = match &result {
+        Ok(_) => record_attempt_success(state, endpoint.name(), request_id).await,
+        Err(e) => {
+            let health =
+                (!is_final_failure(state, e)).then(|| HealthFailureKind::from_query_error(e));
+            record_attempt_failure(state, endpoint.name(), health, request_id).await
+        }
+    };
+    warnings.extend(health_warning);
+    if let Ok(answer) = &result
+        && answer.logprobs.is_none()
+        && sampling_params.and_then(|p| p.logprobs) == Some(true)
+    {
+        warnings.push(missing_logprobs_warning(endpoint));
+    }
+    result
+}
+
+/// Apply `server.on_empty_completion` to a completion from `endpoint`
+///
+/// A completion with nothing but whitespace is counted in
+/// `octoroute_empty_completions_total`. It is passed through under
+/// `return_empty` and otherwise rejected.
+///
+/// # Errors
+/// Returns [`ModelQueryError::EmptyResponse`] for an empty completion under the
+/// `retry` and `error` policies; retrying it is up to the caller.
+pub fn check_empty_completion(
+    state: &AppState,
+    endpoint: &ModelEndpoint,
+    text: String,
+    request_id: RequestId,
+) -> AppResult<String> {
+    if !text.trim().is_empty() {
+        return Ok(text);
+    }
+    let policy = state.config().server.on_empty_completion;
+    state.metrics().empty_completion(endpoint.name());
+    tracing::warn!(
+        request_id = %request_id,
+        endpoint_name = %endpoint.name(),
+        policy = ?policy,
+        "Endpoint returned an empty completion"
+    );
+    match policy {
+        EmptyCompletionPolicy::ReturnEmpty => Ok(text),
+        EmptyCompletionPolicy::Retry | EmptyCompletionPolicy::Error => {
+            Err(AppError::ModelQuery(ModelQueryError::EmptyResponse {
+                endpoint: endpoint.base_url().to_string(),
+            }))
+        }
+    }
+}
+
+/// Endpoint tags that suit a task type
+///
+/// Code requests prefer endpoints tagged `code`; other task types have no
+/// preference.
+pub fn task_type_tags(task_type: TaskType) -> Vec<String> {
+    match task_type {
+        TaskType::Code => vec!["code".to_string()],
+        _ => Vec::new(),
+    }
+}
+
+/// Select an endpoint for `target`, honoring `routing.tier_fallback`
+///
+/// With fallback disabled this is a plain tier selection. With it enabled, a
+/// lower tier is used when `target` has no healthy, non-excluded endpoint.
+///
+/// When `preferred_tags` is non-empty and some endpoint in `target` carries all
+/// of them, those endpoints are tried first. If none of them is available the
+/// whole tier is considered, so a tag never makes a request fail that would
+/// otherwise succeed.
+///
+/// # Returns
+/// The tier actually served and the selected endpoint, or `None` if no tier
+/// that may be tried has an available endpoint.
+pub async fn select_endpoint(
+    state: &AppState,
+    target: TargetModel,
+    exclude: &ExclusionSet,
+    preferred_tags: &[String],
+) -> Option<(TargetModel, ModelEndpoint)> {
+    let selector = state.selector();
+
+    if !preferred_tags.is_empty() && selector.tier_has_tags(target, preferred_tags) {
+        if let Some(endpoint) = selector
+            .select_with_tags(target, exclude, preferred_tags)
+            .await
+        {
+            return Some((target, endpoint.clone()));
+        }
+        tracing::debug!(
+            tier = ?target,
+            preferred_tags = ?preferred_tags,
+            "No available endpoint with preferred tags, selecting from the whole tier"
+        );
+    }
+
+    let selected = if state.config().routing.tier_fallback {
+        selector.select_with_fallback(target, exclude).await
+    } else {
+        selector
+            .select(target, exclude)
+            .await
+            .map(|ep| (target, ep))
+    };
+    selected.map(|(tier, endpoint)| (tier, endpoint.clone()))
+}
+
+/// Format the warning attached to responses served from a fallback tier
+pub fn tier_fallback_warning(requested: TargetModel, served: TargetModel) -> String {
+    format!(
+        "No healthy {:?} endpoints available; served by {:?} tier instead",
+        requested, served
+    )
+}
+
+/// Warning for an answer from `endpoint` without the requested log-probabilities
+///
+/// Sent instead of fabricating them when the backend doesn't support `logprobs`.
+pub fn missing_logprobs_warning(endpoint: &ModelEndpoint) -> String {
+    format!(
+        "logprobs requested but endpoint '{}' did not return them",
+        endpoint.name()
+    )
 }
 
 /// Execute a query with retry logic
@@ -297,6 +703,7 @@ pub async fn query_model(
 /// - Request-scoped exclusion of failed endpoints
 /// - Global health tracking
 /// - Exponential backoff between retries
+/// - Stopping early once the request's [`CallBudget`] is spent
 /// - Metrics recording
 ///
 /// # Arguments
@@ -318,7 +725,7 @@ pub async fn execute_query_with_retry(
     sampling_params: Option<&SamplingParams>,
 ) -> AppResult<QueryResult> {
     let mut last_error = None;
-    let mut failed_endpoints = ExclusionSet::new();
+    let mut failed_endpoints = config.excluded_endpoints().clone();
     let mut warnings: Vec<String> = Vec::new();
 
     // Add any warnings from the routing decision
@@ -326,15 +733,25 @@ pub async fn execute_query_with_retry(
 
     for attempt in 1..=config.max_retries() {
         // Select endpoint from target tier (with health filtering + priority + exclusion)
-        let endpoint = match state
-            .selector()
-            .select(decision.target(), &failed_endpoints)
-            .await
+        let (tier, endpoint) = match select_endpoint(
+            state,
+            decision.target(),
+            &failed_endpoints,
+            config.preferred_tags(),
+        )
+        .await
         {
-            Some(ep) => ep.clone(),
+            Some(selected) => selected,
             None => {
                 let total_configured = state.selector().endpoint_count(decision.target());
-                let excluded_count = failed_endpoints.len();
+                // Only the target tier's exclusions say whether it can still recover
+                let excluded_count = state
+                    .config()
+                    .models
+                    .tier(decision.target())
+                    .iter()
+                    .filter(|endpoint| failed_endpoints.contains(&EndpointName::from(*endpoint)))
+                    .count();
 
                 tracing::error!(
                     request_id = %request_id,
@@ -348,7 +765,7 @@ pub async fn execute_query_with_retry(
                     total_configured,
                     excluded_count
                 );
-                last_error = Some(AppError::RoutingFailed(format!(
+                let message = format!(
                     "No available healthy endpoints for tier {:?} \
                     (configured: {}, excluded: {}, attempt {}/{})",
                     decision.target(),
@@ -356,7 +773,17 @@ pub async fn execute_query_with_retry(
                     excluded_count,
                     attempt,
                     config.max_retries()
-                )));
+                );
+                // Endpoints not yet tried in this request are unhealthy or saturated
+                // and may recover; if every endpoint already failed, retrying won't help
+                last_error = Some(if excluded_count < total_configured {
+                    AppError::EndpointsUnavailable {
+                        message,
+                        retry_after_seconds: RECOVERY_RETRY_AFTER_SECS,
+                    }
+                } else {
+                    AppError::RoutingFailed(message)
+                });
 
                 // Add exponential backoff before retry (capped to prevent overflow)
                 if attempt < config.max_retries() {
@@ -376,83 +803,84 @@ pub async fn execute_query_with_retry(
             "Attempting model query"
         );
 
-        // Get timeout for this tier
-        let timeout_seconds = state.config().timeout_for_tier(decision.target());
+        // Routing may already have spent some of the request's upstream calls
+        if !config.call_budget().try_spend() {
+            tracing::warn!(
+                request_id = %request_id,
+                attempt = attempt,
+                limit = ?config.call_budget().limit(),
+                "Upstream call budget exhausted (server.max_upstream_calls), not retrying"
+            );
+            return Err(last_error.unwrap_or_else(|| config.call_budget().exhausted_error()));
+        }
 
-        // Try to query this endpoint
-        match query_model(
+        let query_result = run_completion(
+            state,
             &endpoint,
+            tier,
             prompt,
-            timeout_seconds,
             request_id,
             attempt,
             config.max_retries(),
             sampling_params,
+            &mut warnings,
         )
-        .await
-        {
-            Ok(response_text) => {
-                // Success! Mark endpoint as healthy
-                if let Err(e) = state
-                    .selector()
-                    .health_checker()
-                    .mark_success(endpoint.name())
-                    .await
+        .await;
+
+        match query_result {
+            Ok(answer) => {
+                if tier != decision.target() {
+                    warnings.push(tier_fallback_warning(decision.target(), tier));
+                }
+
+                // Surface max_tokens clamping (the clamped value was already sent upstream)
+                if let (_, Some(clamp_warning)) =
+                    resolve_max_tokens(&endpoint, sampling_params.and_then(|p| p.max_tokens))
                 {
-                    tracing::warn!(
+                    tracing::debug!(
                         request_id = %request_id,
                         endpoint_name = %endpoint.name(),
-                        error = %e,
-                        "Health tracking skipped: {} (request continues with successful response)",
-                        e
+                        warning = %clamp_warning,
+                        "Requested max_tokens clamped to endpoint limit"
                     );
-                    state
-                        .metrics()
-                        .health_tracking_failure(endpoint.name(), e.error_type());
-                    warnings.push(format!(
-                        "Health tracking failed: {} (endpoint health state may be stale)",
-                        e
-                    ));
+                    warnings.push(clamp_warning);
                 }
 
                 tracing::info!(
                     request_id = %request_id,
                     endpoint_name = %endpoint.name(),
-                    response_length = response_text.len(),
-                    model_tier = ?decision.target(),
+                    response_length = answer.text.len(),
+                    model_tier = ?tier,
                     attempt = attempt,
                     "Query completed successfully"
                 );
 
                 // Record successful model invocation
-                let tier_enum = match decision.target() {
-                    TargetModel::Fast => crate::metrics::Tier::Fast,
-                    TargetModel::Balanced => crate::metrics::Tier::Balanced,
-                    TargetModel::Deep => crate::metrics::Tier::Deep,
-                };
-
-                if let Err(e) = state.metrics().record_model_invocation(tier_enum) {
-                    state
-                        .metrics()
-                        .metrics_recording_failure("record_model_invocation");
-                    tracing::error!(
-                        request_id = %request_id,
-                        error = %e,
-                        tier = ?tier_enum,
-                        "Metrics recording failed. Observability degraded but request continues."
-                    );
-                }
+                state
+                    .metrics()
+                    .try_record_model_invocation(tier.into(), Some(request_id));
 
                 return Ok(QueryResult {
-                    content: response_text,
+                    content: answer.text,
+                    logprobs: answer.logprobs,
                     endpoint,
-                    tier: decision.target(),
+                    tier,
                     strategy: decision.strategy(),
                     warnings,
                 });
             }
             Err(e) => {
-                // Failure - mark for health tracking and exclude from retries
+                // "error" empty completions and non-retryable statuses end the request
+                if is_final_failure(state, &e) {
+                    tracing::warn!(
+                        request_id = %request_id,
+                        endpoint_name = %endpoint.name(),
+                        error = %e,
+                        "Endpoint query failed in a way another endpoint would repeat, not retrying"
+                    );
+                    return Err(e);
+                }
+
                 tracing::warn!(
                     request_id = %request_id,
                     endpoint_name = %endpoint.name(),
@@ -462,29 +890,6 @@ pub async fn execute_query_with_retry(
                     "Endpoint query failed, excluding from retries"
                 );
 
-                // Mark endpoint as failed for global health tracking
-                if let Err(health_err) = state
-                    .selector()
-                    .health_checker()
-                    .mark_failure(endpoint.name())
-                    .await
-                {
-                    tracing::warn!(
-                        request_id = %request_id,
-                        endpoint_name = %endpoint.name(),
-                        error = %health_err,
-                        "Health tracking skipped: {} (request continues with retry logic)",
-                        health_err
-                    );
-                    state
-                        .metrics()
-                        .health_tracking_failure(endpoint.name(), health_err.error_type());
-                    warnings.push(format!(
-                        "Health tracking failed: {} (endpoint health state may be stale)",
-                        health_err
-                    ));
-                }
-
                 // Exclude from this request's retries
                 failed_endpoints.insert(EndpointName::from(&endpoint));
                 last_error = Some(e);
@@ -529,6 +934,30 @@ pub async fn execute_query_with_retry(
     }))
 }
 
+/// Cap `metadata`'s token estimate for routing and record it
+///
+/// See [`context_window::routing_token_estimate`]; the capped estimate is
+/// observed in `octoroute_route_token_estimate`.
+pub fn routing_metadata(
+    state: &AppState,
+    mut metadata: RouteMetadata,
+    request_id: RequestId,
+) -> RouteMetadata {
+    let estimate = context_window::routing_token_estimate(state.config(), metadata.token_estimate);
+    let capped = estimate < metadata.token_estimate;
+    if capped {
+        tracing::debug!(
+            request_id = %request_id,
+            token_estimate = metadata.token_estimate,
+            routing_token_estimate = estimate,
+            "Token estimate capped for routing"
+        );
+    }
+    state.metrics().route_token_estimate(estimate, capped);
+    metadata.token_estimate = estimate;
+    metadata
+}
+
 /// Record routing metrics
 ///
 /// Records the routing decision metrics (tier, strategy, duration).
@@ -551,26 +980,45 @@ pub fn record_routing_metrics(
         RoutingStrategy::Llm => crate::metrics::Strategy::Llm,
     };
 
-    if let Err(e) = metrics.record_request(tier_enum, strategy_enum) {
-        metrics.metrics_recording_failure("record_request");
-        tracing::error!(
-            request_id = %request_id,
-            error = %e,
-            tier = ?tier_enum,
-            strategy = ?strategy_enum,
-            "Metrics recording failed. Observability degraded but request continues."
-        );
-    }
+    metrics.try_record_request(tier_enum, strategy_enum, Some(request_id));
+    metrics.try_record_routing_duration(strategy_enum, routing_duration_ms, Some(request_id));
+}
 
-    if let Err(e) = metrics.record_routing_duration(strategy_enum, routing_duration_ms) {
-        metrics.metrics_recording_failure("record_routing_duration");
-        tracing::error!(
-            request_id = %request_id,
-            error = %e,
-            strategy = ?strategy_enum,
-            duration_ms = routing_duration_ms,
-            "Metrics recording failed. Observability degraded but request continues."
-        );
+/// Record a decision the router made, for decision drift tracking
+///
+/// Call only where a router actually ran, not for explicit tier requests or
+/// sticky session replays, so `octoroute_routing_decisions_total` reflects
+/// router behavior. Failures are logged but do not cause the request to fail.
+pub fn record_routing_decision(
+    state: &AppState,
+    decision: &RoutingDecision,
+    request_id: RequestId,
+) {
+    let metrics = state.metrics();
+    let tier_enum = crate::metrics::Tier::from(decision.target());
+    let strategy_enum = match decision.strategy() {
+        RoutingStrategy::Rule => crate::metrics::Strategy::Rule,
+        RoutingStrategy::Llm => crate::metrics::Strategy::Llm,
+    };
+
+    tracing::debug!(
+        request_id = %request_id,
+        target_tier = %tier_enum.as_str(),
+        strategy = %strategy_enum.as_str(),
+        "Recording routing decision"
+    );
+
+    metrics.try_record_routing_decision(tier_enum, strategy_enum, Some(request_id));
+}
+
+/// Pass the final routing decision to the [`RoutingObserver`](crate::router::RoutingObserver), if any
+pub fn notify_routing_observer(
+    state: &AppState,
+    prompt_meta: &RouteMetadata,
+    decision: &RoutingDecision,
+) {
+    if let Some(observer) = state.routing_observer() {
+        observer.on_decision(prompt_meta, decision);
     }
 }
 
@@ -578,11 +1026,59 @@ pub fn record_routing_metrics(
 mod tests {
     use super::*;
 
+    fn endpoint_with_max_tokens(max_tokens: usize) -> ModelEndpoint {
+        let toml = format!(
+            r#"
+name = "capped-endpoint"
+base_url = "http://localhost:1234/v1"
+max_tokens = {}
+"#,
+            max_tokens
+        );
+        toml::from_str(&toml).expect("should parse endpoint")
+    }
+
+    #[test]
+    fn test_resolve_max_tokens_clamps_to_endpoint_cap() {
+        let endpoint = endpoint_with_max_tokens(2048);
+        let (effective, warning) = resolve_max_tokens(&endpoint, Some(8192));
+        assert_eq!(effective, 2048);
+        let warning = warning.expect("clamping should produce a warning");
+        assert!(warning.contains("8192"));
+        assert!(warning.contains("2048"));
+        assert!(warning.contains("capped-endpoint"));
+    }
+
+    #[test]
+    fn test_resolve_max_tokens_passes_through_within_limit() {
+        let endpoint = endpoint_with_max_tokens(2048);
+        assert_eq!(resolve_max_tokens(&endpoint, Some(512)), (512, None));
+        // Exactly at the cap is not clamped
+        assert_eq!(resolve_max_tokens(&endpoint, Some(2048)), (2048, None));
+    }
+
+    #[test]
+    fn test_resolve_max_tokens_defaults_to_endpoint_cap() {
+        let endpoint = endpoint_with_max_tokens(2048);
+        assert_eq!(resolve_max_tokens(&endpoint, None), (2048, None));
+    }
+
     #[test]
     fn test_query_config_default() {
         let config = QueryConfig::default();
         assert_eq!(config.max_retries(), DEFAULT_MAX_RETRIES);
         assert_eq!(config.retry_backoff_ms(), DEFAULT_RETRY_BACKOFF_MS);
+        assert!(config.preferred_tags().is_empty());
+    }
+
+    #[test]
+    fn test_task_type_tags() {
+        assert_eq!(task_type_tags(TaskType::Code), vec!["code".to_string()]);
+        assert!(task_type_tags(TaskType::CasualChat).is_empty());
+        assert!(task_type_tags(TaskType::QuestionAnswer).is_empty());
+
+        let config = QueryConfig::default().with_preferred_tags(task_type_tags(TaskType::Code));
+        assert_eq!(config.preferred_tags(), ["code".to_string()]);
     }
 
     #[test]
@@ -0,0 +1,201 @@
+//! Reasoning block removal (`strip_reasoning_tags`)
+//!
+//! Reasoning models wrap their chain of thought in tags such as
+//! `<think>...</think>` ahead of the answer. Endpoints with
+//! `strip_reasoning_tags = true` have those blocks removed before content
+//! reaches the client. The filter works on arbitrary chunk boundaries: text
+//! that might be the start of a tag is held back until it is known to be one
+//! or not, and everything inside a block is dropped until its closing tag, so
+//! a stream never leaks part of the reasoning.
+
+use crate::config::ModelEndpoint;
+
+/// Streaming filter that drops `<tag>...</tag>` blocks for a set of tag names
+#[derive(Debug, Clone)]
+pub struct ReasoningFilter {
+    tags: Vec<String>,
+    /// Text received but not yet emitted or dropped
+    pending: String,
+    /// Index into `tags` of the block currently being dropped
+    inside: Option<usize>,
+    /// Whether any visible text has been emitted yet
+    emitted: bool,
+    /// Trim whitespace left behind by a block that preceded any visible text
+    trim_leading: bool,
+}
+
+impl ReasoningFilter {
+    /// Create a filter dropping blocks delimited by any of `tags` (names without brackets)
+    pub fn new(tags: &[String]) -> Self {
+        Self {
+            tags: tags.to_vec(),
+            pending: String::new(),
+            inside: None,
+            emitted: false,
+            trim_leading: false,
+        }
+    }
+
+    /// The filter configured for `endpoint`, or `None` if it keeps reasoning
+    pub fn for_endpoint(endpoint: &ModelEndpoint) -> Option<Self> {
+        endpoint
+            .strip_reasoning_tags()
+            .then(|| Self::new(endpoint.reasoning_tags()))
+    }
+
+    /// Feed the next chunk, returning the text that can be emitted now
+    ///
+    /// The result may be empty while a block or a possible tag is pending.
+    pub fn push(&mut self, chunk: &str) -> String {
+        self.pending.push_str(chunk);
+        let mut visible = String::new();
+
+        loop {
+            match self.inside {
+                Some(index) => {
+                    let close = format!("</{}>", self.tags[index]);
+                    match self.pending.find(&close) {
+                        Some(pos) => {
+                            self.pending.drain(..pos + close.len());
+                            self.inside = None;
+                            self.trim_leading = !self.emitted;
+                        }
+                        None => {
+                            // Drop the block so far, keeping only what may begin the closing tag
+                            let keep = partial_suffix(&self.pending, std::slice::from_ref(&close));
+                            self.pending.drain(..self.pending.len() - keep);
+                            break;
+                        }
+                    }
+                }
+                None => {
+                    let opening = self.opening_tags();
+                    let found = opening
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(index, tag)| self.pending.find(tag).map(|pos| (pos, index)))
+                        .min();
+                    match found {
+                        Some((pos, index)) => {
+                            self.emit(&mut visible, pos);
+                            self.pending.drain(..opening[index].len());
+                            self.inside = Some(index);
+                        }
+                        None => {
+                            let keep = partial_suffix(&self.pending, &opening);
+                            self.emit(&mut visible, self.pending.len() - keep);
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+        visible
+    }
+
+    /// End of input: the held-back text, or nothing if a block never closed
+    pub fn finish(&mut self) -> String {
+        if self.inside.take().is_some() {
+            self.pending.clear();
+            return String::new();
+        }
+        let mut visible = String::new();
+        self.emit(&mut visible, self.pending.len());
+        visible
+    }
+
+    /// Filter a complete response in one go
+    pub fn strip(mut self, text: &str) -> String {
+        let mut visible = self.push(text);
+        visible.push_str(&self.finish());
+        visible
+    }
+
+    fn opening_tags(&self) -> Vec<String> {
+        self.tags.iter().map(|tag| format!("<{}>", tag)).collect()
+    }
+
+    /// Move the first `len` bytes of `pending` to `visible`
+    fn emit(&mut self, visible: &mut String, len: usize) {
+        let drained: String = self.pending.drain(..len).collect();
+        let text = if self.trim_leading {
+            drained.trim_start()
+        } else {
+            &drained
+        };
+        if !text.is_empty() {
+            self.emitted = true;
+            self.trim_leading = false;
+            visible.push_str(text);
+        }
+    }
+}
+
+/// Length of the longest suffix of `text` that is a proper prefix of one of `tags`
+///
+/// Tags start with `<`, so candidates are the suffixes beginning at a `<`,
+/// which also keeps the split on a char boundary.
+fn partial_suffix(text: &str, tags: &[String]) -> usize {
+    text.match_indices('<')
+        .map(|(pos, _)| &text[pos..])
+        .find(|suffix| {
+            tags.iter()
+                .any(|tag| tag.len() > suffix.len() && tag.starts_with(suffix))
+        })
+        .map_or(0, str::len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter() -> ReasoningFilter {
+        ReasoningFilter::new(&["think".to_string(), "reasoning".to_string()])
+    }
+
+    #[test]
+    fn test_strips_blocks_and_following_whitespace() {
+        let text = "<think>Let me work this out.</think>\n\nThe answer is 4.";
+        assert_eq!(filter().strip(text), "The answer is 4.");
+
+        let text = "Intro <reasoning>hidden</reasoning> middle <think>x</think>end";
+        assert_eq!(filter().strip(text), "Intro  middle end");
+    }
+
+    #[test]
+    fn test_text_without_tags_is_unchanged() {
+        let text = "if a < b && b > c { <div> }";
+        assert_eq!(filter().strip(text), text);
+    }
+
+    #[test]
+    fn test_tags_split_across_chunks_never_leak() {
+        let chunks = [
+            "Sure. <th",
+            "ink>secret ",
+            "plan</thi",
+            "nk> Done",
+            " <",
+            "b>ok",
+        ];
+        let mut filter = filter();
+        let mut out = Vec::new();
+        for chunk in chunks {
+            let visible = filter.push(chunk);
+            assert!(!visible.contains("secret") && !visible.contains("plan"));
+            out.push(visible);
+        }
+        out.push(filter.finish());
+
+        assert_eq!(out.concat(), "Sure.  Done <b>ok");
+        // The possible tag start was held back rather than sent
+        assert_eq!(out[0], "Sure. ");
+    }
+
+    #[test]
+    fn test_unclosed_block_is_dropped() {
+        let mut filter = filter();
+        assert_eq!(filter.push("Answer <think>never fini"), "Answer ");
+        assert_eq!(filter.finish(), "");
+    }
+}
@@ -0,0 +1,195 @@
+//! House system prompt applied to every completion
+//!
+//! When `routing.system_prompt` (or `routing.system_prompt_file`) is set, the
+//! prompt sent to the backend carries the house system message. Routing still
+//! sees only the client's messages, so the house prompt never shifts the tier.
+//! `routing.system_prompt_mode` decides what happens to system messages the
+//! client sent itself.
+
+use crate::config::{RoutingConfig, SystemPromptMode};
+use crate::error::{AppError, AppResult};
+use crate::handlers::openai::types::{ChatMessage, MessageRole, messages_to_prompt};
+
+/// Resolved house system prompt and how it combines with client system messages
+#[derive(Debug, Clone)]
+pub struct SystemPrompt {
+    message: ChatMessage,
+    mode: SystemPromptMode,
+}
+
+impl SystemPrompt {
+    /// Create a house system prompt
+    ///
+    /// # Errors
+    /// Returns an error if `text` is empty or whitespace-only.
+    pub fn new(text: impl Into<String>, mode: SystemPromptMode) -> AppResult<Self> {
+        let message = ChatMessage::try_new(MessageRole::System, text).map_err(|_| {
+            AppError::Config(
+                "Configuration error: the house system prompt must not be empty".to_string(),
+            )
+        })?;
+        Ok(Self { message, mode })
+    }
+
+    /// Resolve the house system prompt from routing config, reading the file if one is set
+    ///
+    /// Returns `None` when neither `system_prompt` nor `system_prompt_file` is set.
+    /// Relative file paths are resolved against the working directory, like `--config`.
+    pub fn from_config(routing: &RoutingConfig) -> AppResult<Option<Self>> {
+        let text = match (&routing.system_prompt, &routing.system_prompt_file) {
+            (Some(text), _) => text.clone(),
+            (None, Some(path)) => {
+                std::fs::read_to_string(path).map_err(|source| AppError::ConfigFileRead {
+                    path: path.clone(),
+                    source,
+                    remediation: "\nCheck routing.system_prompt_file points to a readable file."
+                        .to_string(),
+                })?
+            }
+            (None, None) => return Ok(None),
+        };
+        Self::new(text, routing.system_prompt_mode).map(Some)
+    }
+
+    /// The house system prompt text
+    pub fn text(&self) -> &str {
+        self.message.content()
+    }
+
+    /// How client system messages are handled
+    pub fn mode(&self) -> SystemPromptMode {
+        self.mode
+    }
+
+    /// Messages to send to the backend: the client's messages with the house prompt applied
+    pub fn apply(&self, messages: &[ChatMessage]) -> Vec<ChatMessage> {
+        let is_system = |m: &&ChatMessage| m.role() == MessageRole::System;
+        let mut applied = Vec::with_capacity(messages.len() + 1);
+
+        match self.mode {
+            SystemPromptMode::Prepend => {
+                applied.push(self.message.clone());
+                applied.extend(messages.iter().cloned());
+            }
+            SystemPromptMode::Replace => {
+                applied.push(self.message.clone());
+                applied.extend(messages.iter().filter(|m| !is_system(m)).cloned());
+            }
+            SystemPromptMode::Merge => {
+                let merged = std::iter::once(self.text())
+                    .chain(messages.iter().filter(is_system).map(|m| m.content()))
+                    .collect::<Vec<_>>()
+                    .join("\n\n");
+                // Non-empty: the house prompt itself is validated non-empty
+                applied.push(
+                    ChatMessage::try_new(MessageRole::System, merged)
+                        .unwrap_or_else(|_| self.message.clone()),
+                );
+                applied.extend(messages.iter().filter(|m| !is_system(m)).cloned());
+            }
+        }
+
+        applied
+    }
+
+    /// Backend prompt string for an OpenAI-style conversation
+    pub fn backend_prompt(&self, messages: &[ChatMessage]) -> String {
+        messages_to_prompt(&self.apply(messages))
+    }
+
+    /// Backend prompt string for a single legacy `/chat` message
+    ///
+    /// Legacy requests carry no system message, so every mode prepends.
+    pub fn backend_prompt_for_message(&self, message: &str) -> String {
+        format!("System: {}\n\nUser: {}", self.text(), message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(role: MessageRole, content: &str) -> ChatMessage {
+        ChatMessage::try_new(role, content).unwrap()
+    }
+
+    fn conversation() -> Vec<ChatMessage> {
+        vec![
+            message(MessageRole::System, "Answer in French."),
+            message(MessageRole::User, "Hello"),
+        ]
+    }
+
+    fn contents(messages: &[ChatMessage]) -> Vec<(MessageRole, &str)> {
+        messages.iter().map(|m| (m.role(), m.content())).collect()
+    }
+
+    #[test]
+    fn test_prepend_keeps_client_system_message() {
+        let house = SystemPrompt::new("Be concise.", SystemPromptMode::Prepend).unwrap();
+
+        let applied = house.apply(&conversation());
+
+        assert_eq!(
+            contents(&applied),
+            [
+                (MessageRole::System, "Be concise."),
+                (MessageRole::System, "Answer in French."),
+                (MessageRole::User, "Hello"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_replace_drops_client_system_message() {
+        let house = SystemPrompt::new("Be concise.", SystemPromptMode::Replace).unwrap();
+
+        let applied = house.apply(&conversation());
+
+        assert_eq!(
+            contents(&applied),
+            [
+                (MessageRole::System, "Be concise."),
+                (MessageRole::User, "Hello"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_folds_client_system_message_after_house_prompt() {
+        let house = SystemPrompt::new("Be concise.", SystemPromptMode::Merge).unwrap();
+
+        let applied = house.apply(&conversation());
+
+        assert_eq!(
+            contents(&applied),
+            [
+                (MessageRole::System, "Be concise.\n\nAnswer in French."),
+                (MessageRole::User, "Hello"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_every_mode_prepends_without_client_system_message() {
+        let messages = vec![message(MessageRole::User, "Hello")];
+        for mode in [
+            SystemPromptMode::Prepend,
+            SystemPromptMode::Replace,
+            SystemPromptMode::Merge,
+        ] {
+            let house = SystemPrompt::new("Be concise.", mode).unwrap();
+            assert_eq!(
+                house.backend_prompt(&messages),
+                "System: Be concise.\n\nUser: Hello",
+                "mode {:?}",
+                mode
+            );
+        }
+    }
+
+    #[test]
+    fn test_rejects_empty_prompt() {
+        assert!(SystemPrompt::new("  \n", SystemPromptMode::Prepend).is_err());
+    }
+}
@@ -0,0 +1,230 @@
+//! Per-tier concurrency budgets
+//!
+//! Each tier with a `[server.tier_concurrency]` limit gets a semaphore. Chat
+//! handlers take a permit for the routed tier once routing is done and hold it
+//! until the response is finished (for streaming, until the stream ends or the
+//! client disconnects). When a tier's permits are all taken, further requests
+//! for it are shed with 503 instead of queueing behind the slow ones, while
+//! requests for other tiers are unaffected.
+//!
+//! With `server.max_queue_wait_ms` set, a request finding its tier full waits
+//! for a permit instead, in arrival order, and is only shed if none frees up
+//! within that time.
+
+use crate::config::TierConcurrencyConfig;
+use crate::error::{AppError, AppResult};
+use crate::router::TargetModel;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// `Retry-After` for requests shed by a tier budget
+///
+/// Permits free up as soon as any in-flight request for the tier finishes, so
+/// a short retry is appropriate (unlike waiting for an unhealthy endpoint).
+pub const TIER_BUDGET_RETRY_AFTER_SECS: u64 = 1;
+
+/// Semaphores for the tiers that have a concurrency budget
+#[derive(Debug, Default)]
+pub struct TierBudgets {
+    fast: Option<Arc<Semaphore>>,
+    balanced: Option<Arc<Semaphore>>,
+    deep: Option<Arc<Semaphore>>,
+    /// How long [`TierBudgets::acquire`] waits for a slot; `None` sheds at once
+    max_queue_wait: Option<Duration>,
+}
+
+impl TierBudgets {
+    /// Create budgets from `[server.tier_concurrency]`; unset tiers are unlimited
+    pub fn new(config: &TierConcurrencyConfig) -> Self {
+        let budget = |limit: Option<usize>| limit.map(|limit| Arc::new(Semaphore::new(limit)));
+        Self {
+            fast: budget(config.fast),
+            balanced: budget(config.balanced),
+            deep: budget(config.deep),
+            max_queue_wait: None,
+        }
+    }
+
+    /// Queue for up to `max_queue_wait` before shedding (builder pattern)
+    ///
+    /// See `server.max_queue_wait_ms`.
+    pub fn with_max_queue_wait(mut self, max_queue_wait: Option<Duration>) -> Self {
+        self.max_queue_wait = max_queue_wait;
+        self
+    }
+
+    fn semaphore(&self, tier: TargetModel) -> Option<&Arc<Semaphore>> {
+        match tier {
+            TargetModel::Fast => self.fast.as_ref(),
+            TargetModel::Balanced => self.balanced.as_ref(),
+            TargetModel::Deep => self.deep.as_ref(),
+        }
+    }
+
+    /// Claim a slot in `tier`'s budget, released when the permit drops
+    ///
+    /// Never waits: a tier without a budget always succeeds with an empty
+    /// permit.
+    ///
+    /// # Errors
+    /// Returns [`AppError::EndpointsUnavailable`] (503 with `Retry-After`) when
+    /// every slot in the tier's budget is taken.
+    pub fn try_acquire(&self, tier: TargetModel) -> AppResult<TierPermit> {
+        let Some(semaphore) = self.semaphore(tier) else {
+            return Ok(TierPermit { _permit: None });
+        };
+        match semaphore.clone().try_acquire_owned() {
+            Ok(permit) => Ok(TierPermit {
+                _permit: Some(permit),
+            }),
+            Err(_) => Err(at_limit(tier)),
+        }
+    }
+
+    /// Claim a slot in `tier`'s budget, queueing for one if configured
+    ///
+    /// Without a queue wait this is [`TierBudgets::try_acquire`]. With one, a
+    /// full tier is waited on in FIFO order (the semaphore is fair) for up to
+    /// the wait.
+    ///
+    /// # Errors
+    /// Returns [`AppError::EndpointsUnavailable`] (503 with `Retry-After`) when
+    /// no slot is free, or none frees up within the queue wait.
+    pub async fn acquire(&self, tier: TargetModel) -> AppResult<TierPermit> {
+        let (Some(semaphore), Some(max_wait)) = (self.semaphore(tier), self.max_queue_wait) else {
+            return self.try_acquire(tier);
+        };
+        match tokio::time::timeout(max_wait, semaphore.clone().acquire_owned()).await {
+            Ok(Ok(permit)) => Ok(TierPermit {
+                _permit: Some(permit),
+            }),
+            // Timed out (the semaphore is never closed)
+            _ => {
+                tracing::debug!(
+                    tier = ?tier,
+                    max_queue_wait_ms = max_wait.as_millis() as u64,
+                    "No tier concurrency slot freed up within the queue wait"
+                );
+                Err(at_limit(tier))
+            }
+        }
+    }
+
+    /// Free slots left in `tier`'s budget, or `None` if the tier is unlimited
+    pub fn available(&self, tier: TargetModel) -> Option<usize> {
+        self.semaphore(tier)
+            .map(|semaphore| semaphore.available_permits())
+    }
+}
+
+fn at_limit(tier: TargetModel) -> AppError {
+    AppError::EndpointsUnavailable {
+        message: format!(
+            "Tier {:?} is at its concurrency limit (server.tier_concurrency)",
+            tier
+        ),
+        retry_after_seconds: TIER_BUDGET_RETRY_AFTER_SECS,
+    }
+}
+
+/// A slot in a tier's concurrency budget, returned to the budget on drop
+#[derive(Debug)]
+pub struct TierPermit {
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn budgets(deep: Option<usize>) -> TierBudgets {
+        TierBudgets::new(&TierConcurrencyConfig {
+            fast: None,
+            balanced: None,
+            deep,
+        })
+    }
+
+    #[test]
+    fn test_exhausted_budget_sheds_until_a_permit_drops() {
+        let budgets = budgets(Some(2));
+
+        let first = budgets.try_acquire(TargetModel::Deep).expect("slot 1");
+        let _second = budgets.try_acquire(TargetModel::Deep).expect("slot 2");
+        assert_eq!(budgets.available(TargetModel::Deep), Some(0));
+
+        match budgets.try_acquire(TargetModel::Deep) {
+            Err(AppError::EndpointsUnavailable {
+                retry_after_seconds,
+                ..
+            }) => assert_eq!(retry_after_seconds, TIER_BUDGET_RETRY_AFTER_SECS),
+            other => panic!("expected EndpointsUnavailable, got: {:?}", other),
+        }
+
+        drop(first);
+        assert_eq!(budgets.available(TargetModel::Deep), Some(1));
+        budgets
+            .try_acquire(TargetModel::Deep)
+            .expect("released slot should be reusable");
+    }
+
+    #[test]
+    fn test_unlimited_tier_is_unaffected_by_saturated_tier() {
+        let budgets = budgets(Some(1));
+        let _deep = budgets.try_acquire(TargetModel::Deep).expect("deep slot");
+
+        assert_eq!(budgets.available(TargetModel::Fast), None);
+        let _fast: Vec<_> = (0..100)
+            .map(|_| {
+                budgets
+                    .try_acquire(TargetModel::Fast)
+                    .expect("unlimited tier never sheds")
+            })
+            .collect();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_queued_request_gets_permit_freed_within_wait() {
+        let budgets =
+            Arc::new(budgets(Some(1)).with_max_queue_wait(Some(Duration::from_millis(500))));
+        let held = budgets.try_acquire(TargetModel::Deep).expect("only slot");
+
+        let waiter = {
+            let budgets = Arc::clone(&budgets);
+            tokio::spawn(async move { budgets.acquire(TargetModel::Deep).await })
+        };
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(!waiter.is_finished(), "should still be queued");
+
+        drop(held);
+        waiter
+            .await
+            .unwrap()
+            .expect("freed slot should go to the queued request");
+        assert_eq!(budgets.available(TargetModel::Deep), Some(1));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_queued_request_is_shed_when_wait_is_exceeded() {
+        let budgets = budgets(Some(1)).with_max_queue_wait(Some(Duration::from_millis(500)));
+        let _held = budgets.try_acquire(TargetModel::Deep).expect("only slot");
+
+        let start = tokio::time::Instant::now();
+        match budgets.acquire(TargetModel::Deep).await {
+            Err(AppError::EndpointsUnavailable { .. }) => {}
+            other => panic!("expected EndpointsUnavailable, got: {:?}", other),
+        }
+        assert_eq!(start.elapsed(), Duration::from_millis(500));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_without_queue_wait_acquire_sheds_immediately() {
+        let budgets = budgets(Some(1));
+        let _held = budgets.try_acquire(TargetModel::Deep).expect("only slot");
+
+        let start = tokio::time::Instant::now();
+        assert!(budgets.acquire(TargetModel::Deep).await.is_err());
+        assert_eq!(start.elapsed(), Duration::ZERO);
+    }
+}
@@ -0,0 +1,173 @@
+//! Bounded in-memory cache with per-entry time-to-live
+//!
+//! Used for short-lived request-scoped state that must survive across requests
+//! (e.g., idempotent replay of completed responses). Entries expire after a fixed
+//! TTL, and the cache never holds more than `capacity` entries: when full, expired
+//! entries are purged first, then the entry closest to expiry is evicted.
+//!
+//! Expiry is checked lazily on access, so no background task is required.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Thread-safe bounded cache whose entries expire after a fixed TTL
+///
+/// Uses a `std::sync::Mutex` rather than an async lock because every operation
+/// is a short, non-blocking map access that never awaits while holding the lock.
+#[derive(Debug)]
+pub struct TtlCache<K, V> {
+    entries: Mutex<HashMap<K, (Instant, V)>>,
+    capacity: usize,
+    ttl: Duration,
+}
+
+impl<K, V> TtlCache<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    /// Create an empty cache holding at most `capacity` entries, each valid for `ttl`
+    ///
+    /// A capacity of 0 is treated as 1 so that `insert` always retains the newest entry.
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            capacity: capacity.max(1),
+            ttl,
+        }
+    }
+
+    /// Return a clone of the live value for `key`, removing it if it has expired
+    pub fn get(&self, key: &K) -> Option<V> {
+        let mut entries = self.lock();
+        let now = Instant::now();
+        match entries.get(key) {
+            Some((expires_at, value)) if *expires_at > now => Some(value.clone()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Insert or replace the value for `key`, restarting its TTL
+    ///
+    /// If the cache is full, expired entries are purged first; if it is still
+    /// full, the entry closest to expiry is evicted to make room.
+    pub fn insert(&self, key: K, value: V) {
+        self.insert_with_ttl(key, value, self.ttl);
+    }
+
+    /// Like [`insert`](Self::insert), but the entry lives for `ttl` instead of
+    /// the cache-wide TTL
+    pub fn insert_with_ttl(&self, key: K, value: V, ttl: Duration) {
+        let mut entries = self.lock();
+        let now = Instant::now();
+
+        if !entries.contains_key(&key) && entries.len() >= self.capacity {
+            entries.retain(|_, (expires_at, _)| *expires_at > now);
+
+            if entries.len() >= self.capacity {
+                let oldest = entries
+                    .iter()
+                    .min_by_key(|(_, (expires_at, _))| *expires_at)
+                    .map(|(k, _)| k.clone());
+                if let Some(oldest) = oldest {
+                    entries.remove(&oldest);
+                }
+            }
+        }
+
+        entries.insert(key, (now + ttl, value));
+    }
+
+    /// Number of entries currently stored (may include expired entries not yet purged)
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns true if the cache holds no entries
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Acquire the entry map, recovering from a poisoned lock
+    ///
+    /// A panic while holding the lock cannot leave the map in a logically
+    /// inconsistent state (every mutation is a single HashMap call), so the
+    /// data is still safe to use.
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<K, (Instant, V)>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_returns_inserted_value() {
+        let cache = TtlCache::new(10, Duration::from_secs(60));
+        cache.insert("a".to_string(), 1);
+
+        assert_eq!(cache.get(&"a".to_string()), Some(1));
+        assert_eq!(cache.get(&"b".to_string()), None);
+    }
+
+    #[test]
+    fn test_insert_replaces_existing_value() {
+        let cache = TtlCache::new(10, Duration::from_secs(60));
+        cache.insert("a".to_string(), 1);
+        cache.insert("a".to_string(), 2);
+
+        assert_eq!(cache.get(&"a".to_string()), Some(2));
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_expired_entry_is_not_returned() {
+        let cache = TtlCache::new(10, Duration::from_millis(20));
+        cache.insert("a".to_string(), 1);
+
+        std::thread::sleep(Duration::from_millis(40));
+
+        assert_eq!(cache.get(&"a".to_string()), None);
+        assert!(
+            cache.is_empty(),
+            "Expired entry should be removed on access"
+        );
+    }
+
+    #[test]
+    fn test_capacity_evicts_oldest_entry() {
+        let cache = TtlCache::new(2, Duration::from_secs(60));
+        cache.insert("a".to_string(), 1);
+        std::thread::sleep(Duration::from_millis(2));
+        cache.insert("b".to_string(), 2);
+        std::thread::sleep(Duration::from_millis(2));
+        cache.insert("c".to_string(), 3);
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&"a".to_string()), None, "Oldest entry evicted");
+        assert_eq!(cache.get(&"b".to_string()), Some(2));
+        assert_eq!(cache.get(&"c".to_string()), Some(3));
+    }
+
+    #[test]
+    fn test_capacity_prefers_purging_expired_entries() {
+        let cache = TtlCache::new(2, Duration::from_millis(20));
+        cache.insert("a".to_string(), 1);
+        cache.insert("b".to_string(), 2);
+
+        std::thread::sleep(Duration::from_millis(40));
+        cache.insert("c".to_string(), 3);
+
+        assert_eq!(cache.len(), 1, "Both expired entries should be purged");
+        assert_eq!(cache.get(&"c".to_string()), Some(3));
+    }
+}
@@ -0,0 +1,626 @@
+//! Completion queries to OpenAI-compatible backends
+//!
+//! Both chat handlers send their completion queries through the pooled HTTP
+//! client held in `AppState` and read the answer here. The request always asks
+//! for `stream: true`, but the response is read according to what the backend
+//! actually sent: a `text/event-stream` body chunk by chunk, and anything else
+//! whole, as the single `chat.completion` JSON body some backends answer with
+//! instead. Either way the caller sees the same stream of [`UpstreamDelta`]s,
+//! from one upstream call.
+
+use crate::config::ModelEndpoint;
+use crate::handlers::openai::types::{FinishReason, ToolCallDelta};
+use crate::shared::query::{SamplingParams, resolve_max_tokens};
+use futures::stream::{self, BoxStream, StreamExt};
+use serde::Deserialize;
+
+/// Content type of a streamed (SSE) response
+const EVENT_STREAM: &str = "text/event-stream";
+
+/// Most characters of a backend error body kept in [`UpstreamError::Status`]
+const MAX_ERROR_BODY_CHARS: usize = 500;
+
+/// What one upstream chunk adds to the completion
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UpstreamDelta {
+    /// Text to append to the message
+    pub content: Option<String>,
+    /// Tool call fragments, numbered as the backend numbered them
+    pub tool_calls: Vec<ToolCallDelta>,
+    /// Token log-probabilities of this chunk, as the backend returned them
+    pub logprobs: Option<serde_json::Value>,
+    /// Why the backend stopped, on the chunk that says so
+    pub finish_reason: Option<FinishReason>,
+}
+
+impl UpstreamDelta {
+    /// Whether the chunk adds anything to the message itself
+    ///
+    /// False for a chunk that only reports the finish reason.
+    pub fn has_output(&self) -> bool {
+        self.content.as_deref().is_some_and(|text| !text.is_empty())
+            || !self.tool_calls.is_empty()
+            || self.logprobs.is_some()
+    }
+
+    /// Whether the chunk adds nothing (e.g. the role announcement)
+    fn is_empty(&self) -> bool {
+        !self.has_output() && self.finish_reason.is_none()
+    }
+}
+
+/// Add the log-probabilities of a chunk to those of the completion so far
+///
+/// OpenAI-style `logprobs` hold per-token arrays (`content`, `refusal`); the
+/// arrays of later chunks are appended to the earlier ones, giving the object
+/// a non-streamed completion would carry. Anything else is kept as first seen.
+pub fn merge_logprobs(total: &mut Option<serde_json::Value>, chunk: serde_json::Value) {
+    let Some(total) = total else {
+        *total = Some(chunk);
+        return;
+    };
+    if let (Some(total), serde_json::Value::Object(chunk)) = (total.as_object_mut(), chunk) {
+        for (key, value) in chunk {
+            match (total.get_mut(&key), value) {
+                (Some(serde_json::Value::Array(tokens)), serde_json::Value::Array(more)) => {
+                    tokens.extend(more)
+                }
+                (None, value) => {
+                    total.insert(key, value);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Deltas of one upstream completion, in order
+pub type DeltaStream = BoxStream<'static, Result<UpstreamDelta, UpstreamError>>;
+
+/// Why an upstream completion failed
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum UpstreamError {
+    /// The request could not be sent, or the connection broke while reading
+    #[error("request failed: {0}")]
+    Request(String),
+    /// The backend answered with an error status
+    #[error("backend returned status {status}: {body}")]
+    Status { status: u16, body: String },
+    /// The body is neither an SSE stream nor a chat completion, or reports an error
+    #[error("unusable response: {0}")]
+    Body(String),
+}
+
+impl UpstreamError {
+    /// HTTP status the backend rejected the query with, if it did
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Self::Status { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+}
+
+/// JSON body of a completion query sending `prompt` to `endpoint`
+///
+/// The prompt goes out as a single user message. Request overrides win over
+/// the endpoint's defaults, and `max_tokens` is clamped to the endpoint's cap
+/// (see [`resolve_max_tokens`]). `top_p`, the penalties, `logit_bias` and the
+/// `logprobs` options have no endpoint default and are only sent when the
+/// request sets them.
+pub fn request_body(
+    endpoint: &ModelEndpoint,
+    prompt: &str,
+    sampling: Option<&SamplingParams>,
+) -> serde_json::Value {
+    let (max_tokens, _) = resolve_max_tokens(endpoint, sampling.and_then(|p| p.max_tokens));
+    let temperature = sampling
+        .and_then(|p| p.temperature)
+        .unwrap_or(endpoint.temperature());
+    let mut body = serde_json::json!({
+        "model": endpoint.model(),
+        "messages": [{ "role": "user", "content": prompt }],
+        "max_tokens": max_tokens,
+        "temperature": temperature,
+        "stream": true,
+    });
+    if let Some(params) = sampling {
+        if let Some(top_p) = params.top_p {
+            body["top_p"] = top_p.into();
+        }
+        if let Some(penalty) = params.presence_penalty {
+            body["presence_penalty"] = penalty.into();
+        }
+        if let Some(penalty) = params.frequency_penalty {
+            body["frequency_penalty"] = penalty.into();
+        }
+        if let Some(bias) = &params.logit_bias {
+            body["logit_bias"] = serde_json::json!(bias);
+        }
+        if let Some(logprobs) = params.logprobs {
+            body["logprobs"] = logprobs.into();
+        }
+        if let Some(top_logprobs) = params.top_logprobs {
+            body["top_logprobs"] = top_logprobs.into();
+        }
+    }
+    body
+}
+
+/// Send a completion query and return its deltas once the backend has answered
+///
+/// Resolves as soon as the response headers arrive. A `text/event-stream` body
+/// is then read as the stream is polled, up to `data: [DONE]`; any other body
+/// is read whole and its `chat.completion` message becomes a single delta.
+/// Chunks that add nothing are skipped, so a completion without content or
+/// tool calls yields at most a delta carrying its finish reason.
+///
+/// # Errors
+/// Returns [`UpstreamError::Request`] when the request can't be sent and
+/// [`UpstreamError::Status`] when the backend answers with an error status.
+pub async fn stream_completion(
+    client: &reqwest::Client,
+    endpoint: &ModelEndpoint,
+    body: &serde_json::Value,
+) -> Result<DeltaStream, UpstreamError> {
+    let response = client
+        .post(format!(
+            "{}/chat/completions",
+            endpoint.base_url().trim_end_matches('/')
+        ))
+        .json(body)
+        .send()
+        .await
+        .map_err(|e| UpstreamError::Request(e.to_string()))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(UpstreamError::Status {
+            status: status.as_u16(),
+            body: body.chars().take(MAX_ERROR_BODY_CHARS).collect(),
+        });
+    }
+
+    let is_sse = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with(EVENT_STREAM));
+    if is_sse {
+        return Ok(sse_deltas(response));
+    }
+
+    let body = response
+        .bytes()
+        .await
+        .map_err(|e| UpstreamError::Request(e.to_string()))?;
+    Ok(stream::iter(body_deltas(&body)).boxed())
+}
+
+/// Deltas of an SSE response, read chunk by chunk as the stream is polled
+fn sse_deltas(response: reqwest::Response) -> DeltaStream {
+    stream::unfold(Some((response, SseParser::default())), |state| async move {
+        let (mut response, mut parser) = state?;
+        loop {
+            if parser.done {
+                return None;
+            }
+            match response.chunk().await {
+                Ok(Some(bytes)) => {
+                    let deltas = parser.push(&bytes);
+                    if !deltas.is_empty() {
+                        return Some((stream::iter(deltas), Some((response, parser))));
+                    }
+                }
+                Ok(None) => return Some((stream::iter(parser.finish()), None)),
+                Err(e) => {
+                    let error = Err(UpstreamError::Request(e.to_string()));
+                    return Some((stream::iter(vec![error]), None));
+                }
+            }
+        }
+    })
+    .flatten()
+    .boxed()
+}
+
+/// Deltas of a response body read whole
+///
+/// A `chat.completion` body gives its first choice's message as one delta.
+/// A body that isn't JSON but holds `data:` lines is a stream sent without the
+/// SSE content type, and is parsed as one.
+fn body_deltas(body: &[u8]) -> Vec<Result<UpstreamDelta, UpstreamError>> {
+    if let Ok(completion) = serde_json::from_slice::<Completion>(body) {
+        return match completion.choices.into_iter().next() {
+            Some(choice) => Some(choice.into_delta())
+                .filter(|delta| !delta.is_empty())
+                .map(Ok)
+                .into_iter()
+                .collect(),
+            None => vec![Err(UpstreamError::Body(
+                "chat completion has no choices".to_string(),
+            ))],
+        };
+    }
+
+    let is_sse = String::from_utf8_lossy(body)
+        .lines()
+        .any(|line| line.starts_with("data:"));
+    if !is_sse {
+        return vec![Err(UpstreamError::Body(
+            "body is neither an SSE stream nor a chat completion".to_string(),
+        ))];
+    }
+    let mut parser = SseParser::default();
+    let mut deltas = parser.push(body);
+    deltas.extend(parser.finish());
+    deltas
+}
+
+/// Incremental reader of the `data:` lines of an SSE body
+#[derive(Debug, Default)]
+struct SseParser {
+    /// Bytes of the line still being received
+    buffer: Vec<u8>,
+    /// Set by `data: [DONE]` or an error; later lines are ignored
+    done: bool,
+}
+
+impl SseParser {
+    /// Add received bytes and return the deltas of every line they complete
+    fn push(&mut self, bytes: &[u8]) -> Vec<Result<UpstreamDelta, UpstreamError>> {
+        self.buffer.extend_from_slice(bytes);
+        let mut deltas = Vec::new();
+        while let Some(end) = self.buffer.iter().position(|byte| *byte == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=end).collect();
+            deltas.extend(self.line(&line));
+        }
+        deltas
+    }
+
+    /// Deltas of a last line the body ended without terminating
+    fn finish(&mut self) -> Vec<Result<UpstreamDelta, UpstreamError>> {
+        let line = std::mem::take(&mut self.buffer);
+        self.line(&line).into_iter().collect()
+    }
+
+    fn line(&mut self, line: &[u8]) -> Option<Result<UpstreamDelta, UpstreamError>> {
+        if self.done {
+            return None;
+        }
+        let line = String::from_utf8_lossy(line);
+        let data = line
+            .trim_end_matches(['\r', '\n'])
+            .strip_prefix("data:")?
+            .trim_start();
+        if data == "[DONE]" {
+            self.done = true;
+            return None;
+        }
+        let delta = match serde_json::from_str::<StreamChunk>(data) {
+            Ok(StreamChunk {
+                error: Some(error), ..
+            }) => Err(UpstreamError::Body(format!(
+                "stream reported an error: {}",
+                error
+            ))),
+            Ok(chunk) => match chunk.choices.into_iter().next() {
+                Some(choice) => Ok(choice.into_delta()),
+                // e.g. a trailing usage chunk
+                None => return None,
+            },
+            Err(e) => Err(UpstreamError::Body(format!("invalid stream chunk: {}", e))),
+        };
+        self.done = delta.is_err();
+        match delta {
+            Ok(delta) if delta.is_empty() => None,
+            delta => Some(delta),
+        }
+    }
+}
+
+/// One `chat.completion.chunk` of an SSE stream
+#[derive(Debug, Deserialize)]
+struct StreamChunk {
+    #[serde(default)]
+    choices: Vec<StreamChoice>,
+    #[serde(default)]
+    error: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamChoice {
+    #[serde(default)]
+    delta: StreamDelta,
+    #[serde(default)]
+    logprobs: Option<serde_json::Value>,
+    #[serde(default)]
+    finish_reason: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct StreamDelta {
+    #[serde(default)]
+    content: Option<String>,
+    #[serde(default)]
+    tool_calls: Vec<ToolCallDelta>,
+}
+
+impl StreamChoice {
+    fn into_delta(self) -> UpstreamDelta {
+        UpstreamDelta {
+            content: self.delta.content,
+            tool_calls: self.delta.tool_calls,
+            logprobs: self.logprobs,
+            finish_reason: self.finish_reason.as_deref().and_then(finish_reason),
+        }
+    }
+}
+
+/// A complete `chat.completion` body
+#[derive(Debug, Deserialize)]
+struct Completion {
+    choices: Vec<CompletionChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CompletionChoice {
+    message: CompletionMessage,
+    #[serde(default)]
+    logprobs: Option<serde_json::Value>,
+    #[serde(default)]
+    finish_reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CompletionMessage {
+    /// `null` for a message that only calls tools
+    #[serde(default)]
+    content: Option<String>,
+    #[serde(default)]
+    tool_calls: Vec<CompletionToolCall>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CompletionToolCall {
+    id: String,
+    function: CompletionFunction,
+}
+
+#[derive(Debug, Deserialize)]
+struct CompletionFunction {
+    name: String,
+    arguments: String,
+}
+
+impl CompletionChoice {
+    /// The whole message as one delta, each tool call as a single fragment
+    fn into_delta(self) -> UpstreamDelta {
+        let tool_calls = self
+            .message
+            .tool_calls
+            .iter()
+            .enumerate()
+            .map(|(index, call)| {
+                ToolCallDelta::complete(
+                    index as u32,
+                    &call.id,
+                    &call.function.name,
+                    &call.function.arguments,
+                )
+            })
+            .collect();
+        UpstreamDelta {
+            content: self.message.content,
+            tool_calls,
+            logprobs: self.logprobs,
+            finish_reason: self.finish_reason.as_deref().and_then(finish_reason),
+        }
+    }
+}
+
+/// A finish reason the backend reported, if it is one clients know
+fn finish_reason(reason: &str) -> Option<FinishReason> {
+    match reason {
+        "stop" => Some(FinishReason::Stop),
+        "length" => Some(FinishReason::Length),
+        "content_filter" => Some(FinishReason::ContentFilter),
+        // `function_call` is the legacy name for a tool call
+        "tool_calls" | "function_call" => Some(FinishReason::ToolCalls),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn text(content: &str) -> Result<UpstreamDelta, UpstreamError> {
+        Ok(UpstreamDelta {
+            content: Some(content.to_string()),
+            ..UpstreamDelta::default()
+        })
+    }
+
+    fn chunk(delta: serde_json::Value) -> String {
+        format!(
+            "data: {}\n\n",
+            json!({"object": "chat.completion.chunk", "choices": [{"index": 0, "delta": delta}]})
+        )
+    }
+
+    #[test]
+    fn test_completion_body_reads_first_choice() {
+        let body = json!({
+            "object": "chat.completion",
+            "choices": [
+                {"index": 0, "message": {"role": "assistant", "content": "Hello"}},
+                {"index": 1, "message": {"role": "assistant", "content": "Other"}}
+            ]
+        });
+        assert_eq!(
+            body_deltas(body.to_string().as_bytes()),
+            vec![text("Hello")]
+        );
+    }
+
+    #[test]
+    fn test_completion_body_tool_calls_become_complete_fragments() {
+        let body = json!({"choices": [{"message": {"role": "assistant", "content": null, "tool_calls": [
+            {"id": "call_1", "type": "function", "function": {"name": "get_weather", "arguments": "{\"city\":\"Paris\"}"}}
+        ]}}]});
+        let deltas = body_deltas(body.to_string().as_bytes());
+
+        assert_eq!(deltas.len(), 1);
+        let delta = deltas[0].as_ref().expect("tool call delta");
+        assert_eq!(delta.content, None);
+        let call = &delta.tool_calls[0];
+        assert_eq!(call.index, 0);
+        assert_eq!(call.id.as_deref(), Some("call_1"));
+        assert_eq!(call.function.name.as_deref(), Some("get_weather"));
+        assert_eq!(
+            call.function.arguments.as_deref(),
+            Some(r#"{"city":"Paris"}"#)
+        );
+    }
+
+    #[test]
+    fn test_completion_body_without_content_yields_nothing() {
+        let body = json!({"choices": [{"message": {"role": "assistant", "content": null}}]});
+        assert!(body_deltas(body.to_string().as_bytes()).is_empty());
+    }
+
+    #[test]
+    fn test_unusable_bodies_are_errors() {
+        for body in [
+            json!({"choices": []}).to_string(),
+            json!({"error": "overloaded"}).to_string(),
+            "<html>Bad Gateway</html>".to_string(),
+        ] {
+            let deltas = body_deltas(body.as_bytes());
+            assert!(
+                matches!(deltas.as_slice(), [Err(UpstreamError::Body(_))]),
+                "{}: {:?}",
+                body,
+                deltas
+            );
+        }
+    }
+
+    #[test]
+    fn test_sse_without_content_type_is_parsed_as_stream() {
+        let body = chunk(json!({"role": "assistant"}))
+            + &chunk(json!({"content": "Hi"}))
+            + "data: [DONE]\n\n";
+        assert_eq!(body_deltas(body.as_bytes()), vec![text("Hi")]);
+    }
+
+    #[test]
+    fn test_sse_lines_split_across_reads() {
+        let body = chunk(json!({"content": "Bonjour"})) + &chunk(json!({"content": " à tous"}));
+        // Small reads split lines (and the multi-byte 'à') at many points
+        for read_size in 1..20 {
+            let mut parser = SseParser::default();
+            let mut deltas = Vec::new();
+            for piece in body.as_bytes().chunks(read_size) {
+                deltas.extend(parser.push(piece));
+            }
+            deltas.extend(parser.finish());
+
+            assert_eq!(
+                deltas,
+                vec![text("Bonjour"), text(" à tous")],
+                "{read_size}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_sse_stops_at_done_and_after_errors() {
+        let mut parser = SseParser::default();
+        let body =
+            chunk(json!({"content": "a"})) + "data: [DONE]\n\n" + &chunk(json!({"content": "b"}));
+        assert_eq!(parser.push(body.as_bytes()), vec![text("a")]);
+
+        let mut parser = SseParser::default();
+        let body = "data: {\"error\": {\"message\": \"overloaded\"}}\n\n".to_string()
+            + &chunk(json!({"content": "b"}));
+        let deltas = parser.push(body.as_bytes());
+        assert!(
+            matches!(deltas.as_slice(), [Err(UpstreamError::Body(_))]),
+            "{:?}",
+            deltas
+        );
+    }
+
+    #[test]
+    fn test_logprobs_are_read_from_chunks_and_bodies() {
+        let logprobs = json!({"content": [{"token": "Hi", "logprob": -0.1, "top_logprobs": []}]});
+        let expected = vec![Ok(UpstreamDelta {
+            content: Some("Hi".to_string()),
+            logprobs: Some(logprobs.clone()),
+            ..UpstreamDelta::default()
+        })];
+
+        let stream = format!(
+            "data: {}\n\n",
+            json!({"choices": [{"index": 0, "delta": {"content": "Hi"}, "logprobs": logprobs}]})
+        );
+        assert_eq!(body_deltas(stream.as_bytes()), expected);
+
+        let body = json!({"choices": [{"message": {"content": "Hi"}, "logprobs": logprobs}]});
+        assert_eq!(body_deltas(body.to_string().as_bytes()), expected);
+    }
+
+    #[test]
+    fn test_merge_logprobs_appends_token_arrays() {
+        let mut total = None;
+        merge_logprobs(&mut total, json!({"content": [{"token": "Hel"}]}));
+        merge_logprobs(
+            &mut total,
+            json!({"content": [{"token": "lo"}], "refusal": null}),
+        );
+        merge_logprobs(&mut total, json!({"content": [{"token": "!"}]}));
+
+        assert_eq!(
+            total,
+            Some(json!({
+                "content": [{"token": "Hel"}, {"token": "lo"}, {"token": "!"}],
+                "refusal": null
+            }))
+        );
+    }
+
+    #[test]
+    fn test_finish_reason_is_read_from_its_chunk() {
+        let body = chunk(json!({"content": "Hi"}))
+            + "data: {\"choices\": [{\"index\": 0, \"delta\": {}, \"finish_reason\": \"length\"}]}\n\n"
+            + "data: [DONE]\n\n";
+        let finish_only = UpstreamDelta {
+            finish_reason: Some(FinishReason::Length),
+            ..UpstreamDelta::default()
+        };
+        assert!(!finish_only.has_output());
+        assert_eq!(
+            body_deltas(body.as_bytes()),
+            vec![text("Hi"), Ok(finish_only)]
+        );
+
+        // Unknown reasons are dropped rather than guessed at
+        let body = "data: {\"choices\": [{\"delta\": {\"content\": \"Hi\"}, \"finish_reason\": \"eos\"}]}\n\n";
+        assert_eq!(body_deltas(body.as_bytes()), vec![text("Hi")]);
+
+        let body =
+            json!({"choices": [{"message": {"content": "Hi"}, "finish_reason": "content_filter"}]});
+        assert_eq!(
+            body_deltas(body.to_string().as_bytes()),
+            vec![Ok(UpstreamDelta {
+                content: Some("Hi".to_string()),
+                finish_reason: Some(FinishReason::ContentFilter),
+                ..UpstreamDelta::default()
+            })]
+        );
+    }
+}
@@ -0,0 +1,154 @@
+//! Per-user request counting (`[server.user_tracking]`)
+//!
+//! Counts requests per OpenAI `user` value in fixed windows so a single
+//! client flooding the router shows up in logs (and, if configured, is
+//! refused) without turning user IDs into Prometheus labels. The map is
+//! bounded by `max_users`: when it is full, ended windows are purged first,
+//! then the window that started longest ago is dropped.
+
+use crate::config::UserTrackingConfig;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Outcome of counting one request for a user
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UserRequestCount {
+    /// Requests from the user in the current window, including this one
+    pub count: u64,
+    /// Time left until the window ends and the count starts over
+    pub window_remaining: Duration,
+}
+
+/// Fixed-window request counters keyed by user ID
+#[derive(Debug)]
+pub struct UserRequestTracker {
+    windows: Mutex<HashMap<String, (Instant, u64)>>,
+    config: UserTrackingConfig,
+}
+
+impl UserRequestTracker {
+    /// Create an empty tracker for `[server.user_tracking]`
+    pub fn new(config: UserTrackingConfig) -> Self {
+        Self {
+            windows: Mutex::new(HashMap::new()),
+            config,
+        }
+    }
+
+    /// Settings this tracker was built from
+    pub fn config(&self) -> &UserTrackingConfig {
+        &self.config
+    }
+
+    fn window(&self) -> Duration {
+        Duration::from_secs(self.config.window_seconds)
+    }
+
+    /// Count a request from `user`, starting a new window if the last one ended
+    pub fn record(&self, user: &str) -> UserRequestCount {
+        let window = self.window();
+        let now = Instant::now();
+        let mut windows = self.lock();
+
+        if !windows.contains_key(user) && windows.len() >= self.config.max_users {
+            windows.retain(|_, (started, _)| now.duration_since(*started) < window);
+
+            if windows.len() >= self.config.max_users {
+                let stalest = windows
+                    .iter()
+                    .min_by_key(|(_, (started, _))| *started)
+                    .map(|(user, _)| user.clone());
+                if let Some(stalest) = stalest {
+                    windows.remove(&stalest);
+                }
+            }
+        }
+
+        let entry = windows.entry(user.to_string()).or_insert((now, 0));
+        if now.duration_since(entry.0) >= window {
+            *entry = (now, 0);
+        }
+        entry.1 += 1;
+
+        UserRequestCount {
+            count: entry.1,
+            window_remaining: window.saturating_sub(now.duration_since(entry.0)),
+        }
+    }
+
+    /// Requests from `user` in the current window (0 if none or the window ended)
+    pub fn count(&self, user: &str) -> u64 {
+        let window = self.window();
+        self.lock()
+            .get(user)
+            .filter(|(started, _)| started.elapsed() < window)
+            .map_or(0, |(_, count)| *count)
+    }
+
+    /// Whether `count` is over the configured `max_requests`
+    pub fn is_over_limit(&self, count: &UserRequestCount) -> bool {
+        count.count > self.config.max_requests
+    }
+
+    /// Number of users currently tracked (may include ended windows not yet purged)
+    pub fn tracked_users(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Acquire the window map, recovering from a poisoned lock
+    ///
+    /// Every mutation is a single map operation, so a panic while holding the
+    /// lock cannot leave a half-updated entry behind.
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, (Instant, u64)>> {
+        self.windows
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracker(max_requests: u64, window_seconds: u64, max_users: usize) -> UserRequestTracker {
+        UserRequestTracker::new(UserTrackingConfig {
+            max_requests,
+            window_seconds,
+            reject_over_limit: false,
+            max_users,
+        })
+    }
+
+    #[test]
+    fn test_counts_per_user_and_flags_over_limit() {
+        let tracker = tracker(2, 60, 10);
+
+        let first = tracker.record("alice");
+        let second = tracker.record("alice");
+        let third = tracker.record("alice");
+        tracker.record("bob");
+
+        assert_eq!((first.count, second.count, third.count), (1, 2, 3));
+        assert!(!tracker.is_over_limit(&second));
+        assert!(tracker.is_over_limit(&third));
+        assert!(third.window_remaining <= Duration::from_secs(60));
+        assert_eq!(tracker.count("alice"), 3);
+        assert_eq!(tracker.count("bob"), 1);
+        assert_eq!(tracker.count("carol"), 0);
+    }
+
+    #[test]
+    fn test_map_is_bounded_by_max_users() {
+        let tracker = tracker(100, 60, 2);
+
+        tracker.record("alice");
+        tracker.record("bob");
+        tracker.record("carol");
+
+        assert_eq!(tracker.tracked_users(), 2);
+        assert_eq!(tracker.count("carol"), 1);
+        // One of the earlier windows was dropped to make room
+        assert_eq!(tracker.count("alice") + tracker.count("bob"), 1);
+    }
+}
@@ -11,20 +11,23 @@ static INIT: Once = Once::new();
 ///
 /// This can only be called once per process. Subsequent calls are silently ignored.
 ///
-/// Reads log level from RUST_LOG environment variable, defaulting to the
-/// level specified in config (or "info" if not set).
+/// The filter comes from `cli_level` (`--log-level`) when given, otherwise
+/// from the RUST_LOG environment variable, otherwise from `config_level`
+/// (`observability.log_level`); see [`resolve_filter`]. An unusable RUST_LOG
+/// falls back to the config level.
 ///
 /// # Examples
 ///
 /// ```no_run
-/// octoroute::telemetry::init("info");
+/// octoroute::telemetry::init(None, "info");
 /// tracing::info!("Application started");
 /// ```
-pub fn init(default_level: &str) {
+pub fn init(cli_level: Option<&str>, config_level: &str) {
     INIT.call_once(|| {
-        let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| {
-            EnvFilter::new(format!("octoroute={},tower_http=debug", default_level))
-        });
+        let rust_log = std::env::var(EnvFilter::DEFAULT_ENV).ok();
+        let directives = resolve_filter(cli_level, rust_log.as_deref(), config_level);
+        let filter = EnvFilter::try_new(&directives)
+            .unwrap_or_else(|_| EnvFilter::new(level_filter(config_level)));
 
         tracing_subscriber::registry()
             .with(filter)
@@ -33,8 +36,62 @@ pub fn init(default_level: &str) {
     });
 }
 
+/// Filter directives for the first log setting present
+///
+/// Precedence is `cli_level` (`--log-level`), then `rust_log` (RUST_LOG),
+/// then `config_level`. A level applies to Octoroute's own logs; RUST_LOG is
+/// a full filter and is used verbatim. A blank RUST_LOG counts as unset.
+pub fn resolve_filter(
+    cli_level: Option<&str>,
+    rust_log: Option<&str>,
+    config_level: &str,
+) -> String {
+    match (
+        cli_level,
+        rust_log.filter(|filter| !filter.trim().is_empty()),
+    ) {
+        (Some(level), _) => level_filter(level),
+        (None, Some(filter)) => filter.to_string(),
+        (None, None) => level_filter(config_level),
+    }
+}
+
+fn level_filter(level: &str) -> String {
+    format!("octoroute={},tower_http=debug", level)
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cli_level_wins_over_env_and_config() {
+        assert_eq!(
+            resolve_filter(Some("debug"), Some("warn"), "info"),
+            "octoroute=debug,tower_http=debug"
+        );
+        assert_eq!(
+            resolve_filter(Some("trace"), None, "info"),
+            "octoroute=trace,tower_http=debug"
+        );
+    }
+
+    #[test]
+    fn test_env_wins_over_config_without_cli_level() {
+        assert_eq!(
+            resolve_filter(None, Some("octoroute=warn"), "info"),
+            "octoroute=warn"
+        );
+        assert_eq!(
+            resolve_filter(None, Some("  "), "error"),
+            "octoroute=error,tower_http=debug"
+        );
+        assert_eq!(
+            resolve_filter(None, None, "info"),
+            "octoroute=info,tower_http=debug"
+        );
+    }
+
     #[test]
     fn test_telemetry_module_exists() {
         // Note: We can't actually test init() fully because it can only be called once
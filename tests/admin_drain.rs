@@ -0,0 +1,174 @@
+//! Integration tests for the admin drain/undrain API
+//!
+//! `POST /admin/endpoints/{name}/drain` takes an endpoint out of selection
+//! regardless of its automatic health, `/undrain` puts it back. Both require
+//! `Authorization: Bearer <server.admin_token>`.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::models::ExclusionSet;
+use octoroute::router::TargetModel;
+use octoroute::{
+    config::Config,
+    handlers::AppState,
+    middleware::{admin_auth_middleware, request_id_middleware},
+};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+const ADMIN_TOKEN: &str = "test-admin-token";
+
+fn create_config() -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+admin_token = "{ADMIN_TOKEN}"
+
+[[models.fast]]
+name = "fast-1"
+base_url = "http://localhost:9999/v1"
+max_tokens = 2048
+
+[[models.fast]]
+name = "fast-2"
+base_url = "http://localhost:9998/v1"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "http://localhost:9997/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:9996/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_test_app(state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/admin/endpoints/{name}/drain",
+            post(octoroute::handlers::admin::drain),
+        )
+        .route(
+            "/admin/endpoints/{name}/undrain",
+            post(octoroute::handlers::admin::undrain),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            admin_auth_middleware,
+        ))
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware))
+}
+
+fn admin_request(uri: &str, token: Option<&str>) -> Request<Body> {
+    let mut builder = Request::builder().method("POST").uri(uri);
+    if let Some(token) = token {
+        builder = builder.header("authorization", format!("Bearer {token}"));
+    }
+    builder.body(Body::empty()).unwrap()
+}
+
+/// Names of the Fast endpoints selection hands out over many draws
+async fn selectable_fast_endpoints(state: &AppState) -> HashSet<String> {
+    let mut seen = HashSet::new();
+    for _ in 0..200 {
+        let endpoint = state
+            .selector()
+            .select(TargetModel::Fast, &ExclusionSet::new())
+            .await
+            .expect("a Fast endpoint should be selectable");
+        seen.insert(endpoint.name().to_string());
+    }
+    seen
+}
+
+#[tokio::test]
+async fn test_drain_removes_endpoint_from_selection_and_undrain_restores_it() {
+    let state = AppState::new(Arc::new(create_config())).expect("AppState::new should succeed");
+
+    let drained = create_test_app(state.clone())
+        .oneshot(admin_request(
+            "/admin/endpoints/fast-1/drain",
+            Some(ADMIN_TOKEN),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(drained.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(drained.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["endpoint"], "fast-1");
+    assert_eq!(json["drained"], true);
+    assert_eq!(json["healthy"], false);
+
+    assert_eq!(
+        selectable_fast_endpoints(&state).await,
+        HashSet::from(["fast-2".to_string()])
+    );
+
+    let undrained = create_test_app(state.clone())
+        .oneshot(admin_request(
+            "/admin/endpoints/fast-1/undrain",
+            Some(ADMIN_TOKEN),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(undrained.status(), StatusCode::OK);
+
+    assert_eq!(
+        selectable_fast_endpoints(&state).await,
+        HashSet::from(["fast-1".to_string(), "fast-2".to_string()])
+    );
+}
+
+#[tokio::test]
+async fn test_admin_requests_without_valid_token_are_rejected() {
+    let state = AppState::new(Arc::new(create_config())).expect("AppState::new should succeed");
+
+    for token in [None, Some("wrong-token")] {
+        let response = create_test_app(state.clone())
+            .oneshot(admin_request("/admin/endpoints/fast-1/drain", token))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    // Nothing was drained
+    assert!(state.selector().health_checker().is_healthy("fast-1").await);
+}
+
+#[tokio::test]
+async fn test_draining_unknown_endpoint_is_a_bad_request() {
+    let state = AppState::new(Arc::new(create_config())).expect("AppState::new should succeed");
+
+    let response = create_test_app(state)
+        .oneshot(admin_request(
+            "/admin/endpoints/no-such-endpoint/drain",
+            Some(ADMIN_TOKEN),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert!(String::from_utf8_lossy(&body).contains("no-such-endpoint"));
+}
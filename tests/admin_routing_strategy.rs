@@ -0,0 +1,194 @@
+//! Integration tests for switching the routing strategy at runtime
+//! (`POST /admin/routing/strategy`)
+//!
+//! The switch swaps only the router: a hybrid server switched to `rule` stops
+//! consulting the LLM router for requests no rule matches. A strategy whose
+//! router tier has no endpoints is rejected with 400 and the running router
+//! stays in place.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+};
+use octoroute::{
+    config::{Config, RoutingStrategy},
+    handlers::AppState,
+    reload::ReloadableState,
+    server,
+};
+use std::sync::Arc;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+const ADMIN_TOKEN: &str = "test-admin-token";
+
+/// CasualChat + High importance matches no rule, so hybrid asks the LLM router
+const AMBIGUOUS_CHAT: &str =
+    r#"{"message": "test message", "task_type": "casual_chat", "importance": "high"}"#;
+
+/// Hybrid routing via the deep tier (the mock router); fast and balanced serve completions
+fn create_config(router_url: &str, backend_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+request_timeout_seconds = 30
+admin_token = "{ADMIN_TOKEN}"
+
+[[models.fast]]
+name = "fast-1"
+base_url = "{backend_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "{backend_url}"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-router"
+base_url = "{router_url}"
+max_tokens = 8192
+
+[routing]
+strategy = "hybrid"
+router_tier = "deep"
+default_tier = "balanced"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_sse_response(content: &str) -> String {
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#.to_string(),
+        format!(
+            r#"data: {{"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{{"index":0,"delta":{{"content":"{content}"}},"finish_reason":null}}]}}"#
+        ),
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#.to_string(),
+        "data: [DONE]".to_string(),
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+async fn start_server(content: &str) -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_sse_response(content))
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+async fn call_count(server: &MockServer) -> usize {
+    server.received_requests().await.unwrap().len()
+}
+
+async fn send(app: Router, request: Request<Body>) -> (StatusCode, String) {
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, String::from_utf8_lossy(&body).into_owned())
+}
+
+fn chat_request() -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri("/chat")
+        .header("content-type", "application/json")
+        .body(Body::from(AMBIGUOUS_CHAT))
+        .unwrap()
+}
+
+fn switch_request(strategy: &str) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri("/admin/routing/strategy")
+        .header("authorization", format!("Bearer {ADMIN_TOKEN}"))
+        .header("content-type", "application/json")
+        .body(Body::from(format!(r#"{{"strategy": "{strategy}"}}"#)))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_switch_from_hybrid_to_rule_stops_llm_routing() {
+    let router = start_server("BALANCED").await;
+    let backend = start_server("Hello").await;
+    let config = create_config(&router.uri(), &backend.uri());
+    let state = ReloadableState::from(AppState::new(Arc::new(config)).unwrap());
+    let app = server::public_app(state.clone());
+
+    let (status, body) = send(app.clone(), chat_request()).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(call_count(&router).await, 1);
+
+    let (status, body) = send(app.clone(), switch_request("rule")).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(json["previous"], "hybrid");
+    assert_eq!(json["strategy"], "rule");
+    assert_eq!(
+        state.current().config().routing.strategy,
+        RoutingStrategy::Rule
+    );
+
+    // No rule matches, so the default tier serves it without asking the router
+    let (status, body) = send(app, chat_request()).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(call_count(&router).await, 1);
+    assert_eq!(call_count(&backend).await, 2);
+}
+
+#[tokio::test]
+async fn test_switch_to_llm_without_router_tier_is_rejected() {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+admin_token = "{ADMIN_TOKEN}"
+
+[models]
+deep = []
+
+[[models.fast]]
+name = "fast-1"
+base_url = "http://localhost:9999/v1"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "http://localhost:9998/v1"
+max_tokens = 4096
+
+[routing]
+strategy = "rule"
+router_tier = "deep"
+"#
+    );
+    let config: Config = toml::from_str(&toml).expect("should parse TOML config");
+    let state = ReloadableState::from(AppState::new(Arc::new(config)).unwrap());
+    let app = server::public_app(state.clone());
+
+    let (status, body) = send(app, switch_request("llm")).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{}", body);
+    assert!(body.contains("Cannot switch routing strategy"), "{}", body);
+    assert_eq!(
+        state.current().config().routing.strategy,
+        RoutingStrategy::Rule
+    );
+}
@@ -268,8 +268,9 @@ async fn test_chat_endpoint_with_no_available_endpoints() {
 
     let response = app.oneshot(request).await.unwrap();
 
-    // Should return 500 Internal Server Error when no healthy endpoints available
-    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    // Unhealthy endpoints can recover, so this is 503 with Retry-After rather than 500
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert!(response.headers().contains_key("retry-after"));
 
     let body = axum::body::to_bytes(response.into_body(), usize::MAX)
         .await
@@ -69,6 +69,7 @@ fn test_query_result_carries_warnings() {
 
     let result = QueryResult {
         content: "Response content".to_string(),
+        logprobs: None,
         endpoint: endpoint.clone(),
         tier: TargetModel::Fast,
         strategy: RoutingStrategy::Rule,
@@ -90,6 +91,7 @@ fn test_chat_response_from_query_result_with_warnings() {
     // Simulate QueryResult with health tracking warning
     let query_result = QueryResult {
         content: "Model response".to_string(),
+        logprobs: None,
         endpoint: endpoint.clone(),
         tier: TargetModel::Balanced,
         strategy: RoutingStrategy::Llm,
@@ -141,6 +143,7 @@ fn test_multiple_health_tracking_warnings_accumulate() {
 
     let result = QueryResult {
         content: "Response after retries".to_string(),
+        logprobs: None,
         endpoint,
         tier: TargetModel::Fast,
         strategy: RoutingStrategy::Rule,
@@ -0,0 +1,193 @@
+//! Integration tests for streaming on the legacy `/chat` endpoint
+//!
+//! `"stream": true` sends the routed completion as Server-Sent Events in the
+//! `/v1/chat/completions` chunk format; without it `/chat` still answers with a
+//! single JSON body.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode, header},
+    middleware,
+    response::Response,
+    routing::post,
+};
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::sync::Arc;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+fn create_config(fast_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+request_timeout_seconds = 5
+
+[[models.fast]]
+name = "fast-1"
+base_url = "{fast_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "http://localhost:9998/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:9997/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_sse_response(parts: &[&str]) -> String {
+    parts
+        .iter()
+        .map(|content| {
+            format!(
+                r#"data: {{"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{{"index":0,"delta":{{"content":"{content}"}},"finish_reason":null}}]}}"#
+            )
+        })
+        .chain(std::iter::once("data: [DONE]".to_string()))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+        + "\n\n"
+}
+
+async fn start_backend() -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_sse_response(&["Hello", " there"]))
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+/// POST a casual chat message (rule-routed to the fast tier) to `/chat`
+async fn chat(mock_url: &str, body: serde_json::Value) -> Response {
+    let state =
+        AppState::new(Arc::new(create_config(mock_url))).expect("AppState::new should succeed");
+    let app = Router::new()
+        .route("/chat", post(octoroute::handlers::chat::handler))
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware));
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/chat")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    app.oneshot(request).await.unwrap()
+}
+
+async fn body_text(response: Response) -> String {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+fn content_type(response: &Response) -> String {
+    response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_string()
+}
+
+#[tokio::test]
+async fn test_stream_true_sends_sse_chunks() {
+    let mock_server = start_backend().await;
+
+    let response = chat(
+        &mock_server.uri(),
+        serde_json::json!({"message": "Hi!", "task_type": "casual_chat", "stream": true}),
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(
+        content_type(&response).starts_with("text/event-stream"),
+        "{}",
+        content_type(&response)
+    );
+    assert_eq!(
+        response
+            .headers()
+            .get("x-octoroute-routing-path")
+            .and_then(|value| value.to_str().ok()),
+        Some("rule")
+    );
+
+    let body = body_text(response).await;
+    let data: Vec<&str> = body
+        .lines()
+        .filter_map(|line| line.strip_prefix("data: "))
+        .collect();
+    assert_eq!(data.last(), Some(&"[DONE]"));
+    let content: String = data
+        .iter()
+        .filter(|data| **data != "[DONE]")
+        .map(|data| serde_json::from_str::<serde_json::Value>(data).unwrap())
+        .filter_map(|chunk| {
+            chunk["choices"][0]["delta"]["content"]
+                .as_str()
+                .map(str::to_string)
+        })
+        .collect();
+    assert_eq!(content, "Hello there");
+}
+
+#[tokio::test]
+async fn test_default_is_single_json_body() {
+    let mock_server = start_backend().await;
+
+    let response = chat(
+        &mock_server.uri(),
+        serde_json::json!({"message": "Hi!", "task_type": "casual_chat"}),
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(
+        content_type(&response).starts_with("application/json"),
+        "{}",
+        content_type(&response)
+    );
+    let json: serde_json::Value = serde_json::from_str(&body_text(response).await)
+        .expect("response should be a single JSON body");
+    assert_eq!(json["content"], "Hello there");
+    assert_eq!(json["model_name"], "fast-1");
+}
+
+#[tokio::test]
+async fn test_stream_false_is_single_json_body() {
+    let mock_server = start_backend().await;
+
+    let response = chat(
+        &mock_server.uri(),
+        serde_json::json!({"message": "Hi!", "task_type": "casual_chat", "stream": false}),
+    )
+    .await;
+
+    assert!(content_type(&response).starts_with("application/json"));
+    let json: serde_json::Value = serde_json::from_str(&body_text(response).await).unwrap();
+    assert_eq!(json["content"], "Hello there");
+}
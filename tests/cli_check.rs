@@ -0,0 +1,142 @@
+//! Integration tests for `octoroute --check`
+//!
+//! The check validates the config and builds the application state without
+//! binding a port: the traffic share report, "OK" and exit 0 for a valid
+//! config, the error and a non-zero exit otherwise.
+
+use octoroute::cli::check_config;
+use std::io::Write;
+use std::process::Command;
+use tempfile::NamedTempFile;
+
+const VALID_CONFIG: &str = r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+
+[[models.fast]]
+name = "fast-1"
+base_url = "http://localhost:1234/v1"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "http://localhost:1235/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:1236/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "hybrid"
+router_tier = "balanced"
+"#;
+
+fn create_temp_config(content: &str) -> NamedTempFile {
+    let mut temp_file = tempfile::Builder::new()
+        .suffix(".toml")
+        .tempfile()
+        .expect("Failed to create temp file");
+    temp_file
+        .write_all(content.as_bytes())
+        .expect("Failed to write temp file");
+    temp_file.flush().expect("Failed to flush temp file");
+    temp_file
+}
+
+fn invalid_config() -> String {
+    VALID_CONFIG.replacen("max_tokens = 2048", "max_tokens = 0", 1)
+}
+
+#[tokio::test]
+async fn test_check_config_accepts_valid_config() {
+    let config_file = create_temp_config(VALID_CONFIG);
+
+    let result = check_config(config_file.path().to_str().unwrap(), None).await;
+
+    assert!(result.is_ok(), "Valid config should pass: {:?}", result);
+}
+
+#[tokio::test]
+async fn test_check_config_rejects_invalid_config() {
+    let config_file = create_temp_config(&invalid_config());
+
+    let result = check_config(config_file.path().to_str().unwrap(), None).await;
+
+    assert!(result.is_err(), "max_tokens = 0 should fail the check");
+}
+
+#[test]
+fn test_check_flag_prints_ok_and_exits_zero() {
+    let config_file = create_temp_config(VALID_CONFIG);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_octoroute"))
+        .arg("--check")
+        .arg("--config")
+        .arg(config_file.path())
+        .output()
+        .expect("should run octoroute binary");
+
+    assert!(
+        output.status.success(),
+        "expected exit 0, stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().last(), Some("OK"));
+    assert!(
+        stdout.contains("fast-1 (priority 1, weight 1): 100.0%"),
+        "stdout should report effective traffic shares, got: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_check_flag_reports_error_and_exits_non_zero() {
+    let config_file = create_temp_config(&invalid_config());
+
+    let output = Command::new(env!("CARGO_BIN_EXE_octoroute"))
+        .arg("--check")
+        .arg("--config")
+        .arg(config_file.path())
+        .output()
+        .expect("should run octoroute binary");
+
+    assert!(
+        !output.status.success(),
+        "invalid config should exit non-zero"
+    );
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("max_tokens"),
+        "stderr should explain the validation error, got: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(
+        output.stdout.is_empty(),
+        "OK must not be printed on failure"
+    );
+}
+
+#[tokio::test]
+async fn test_check_config_warns_about_dominant_weight() {
+    let config = VALID_CONFIG.replacen(
+        "max_tokens = 2048",
+        "max_tokens = 2048\nweight = 100.0\n\n[[models.fast]]\nname = \"fast-2\"\nbase_url = \"http://localhost:1237/v1\"\nmax_tokens = 2048",
+        1,
+    );
+    let config_file = create_temp_config(&config);
+
+    let report = check_config(config_file.path().to_str().unwrap(), None)
+        .await
+        .expect("dominant weights are a warning, not an error");
+
+    assert!(report.contains("fast-1 (priority 1, weight 100): 99.0%"));
+    assert!(report.contains("fast-2 (priority 1, weight 1): 1.0%"));
+    assert!(
+        report.contains("warning: Endpoint 'fast-1' in tier 'fast'"),
+        "report should flag the dominant endpoint, got: {}",
+        report
+    );
+}
@@ -0,0 +1,152 @@
+//! Integration tests for partial config overrides (`Config::from_files`, `--config-override`)
+//!
+//! The override file is merged over the base before validation: tables merge
+//! key by key, other values (arrays included) replace the base, and arrays
+//! named in the override's `append_arrays` are appended to instead.
+
+use octoroute::config::Config;
+use octoroute::error::AppError;
+use octoroute::router::TargetModel;
+use std::io::Write;
+use tempfile::NamedTempFile;
+
+const BASE: &str = r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+
+[[models.fast]]
+name = "fast-1"
+base_url = "http://localhost:1234/v1"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "http://localhost:1235/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:1236/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#;
+
+/// Write `content` to a temp file whose name ends in `suffix`
+fn create_temp_config(content: &str, suffix: &str) -> NamedTempFile {
+    let mut temp_file = tempfile::Builder::new()
+        .suffix(suffix)
+        .tempfile()
+        .expect("Failed to create temp file");
+    temp_file
+        .write_all(content.as_bytes())
+        .expect("Failed to write temp file");
+    temp_file.flush().expect("Failed to flush temp file");
+    temp_file
+}
+
+fn load(override_content: &str, suffix: &str) -> Result<Config, AppError> {
+    let base = create_temp_config(BASE, ".toml");
+    let overlay = create_temp_config(override_content, suffix);
+    Config::from_files(base.path(), overlay.path())
+}
+
+fn endpoint_names(config: &Config, tier: TargetModel) -> Vec<&str> {
+    config
+        .models
+        .tier(tier)
+        .iter()
+        .map(|ep| ep.name())
+        .collect()
+}
+
+#[test]
+fn test_scalar_override_keeps_the_rest_of_the_table() {
+    let config = load("[server]\nport = 8080\n", ".toml").expect("merged config should load");
+
+    assert_eq!(config.server.port, 8080);
+    assert_eq!(config.server.host, "127.0.0.1");
+    assert_eq!(endpoint_names(&config, TargetModel::Fast), ["fast-1"]);
+}
+
+#[test]
+fn test_array_is_replaced_by_default() {
+    let config = load(
+        r#"
+[[models.fast]]
+name = "fast-local"
+base_url = "http://10.0.0.5:1234/v1"
+max_tokens = 2048
+"#,
+        ".toml",
+    )
+    .expect("merged config should load");
+
+    let fast = config.models.tier(TargetModel::Fast);
+    assert_eq!(fast.len(), 1);
+    assert_eq!(fast[0].name(), "fast-local");
+    assert_eq!(fast[0].base_url(), "http://10.0.0.5:1234/v1");
+    assert_eq!(
+        endpoint_names(&config, TargetModel::Balanced),
+        ["balanced-1"]
+    );
+}
+
+#[test]
+fn test_append_arrays_appends_instead_of_replacing() {
+    let config = load(
+        r#"
+append_arrays = ["models.fast"]
+
+[[models.fast]]
+name = "fast-2"
+base_url = "http://localhost:1237/v1"
+max_tokens = 2048
+"#,
+        ".toml",
+    )
+    .expect("merged config should load");
+
+    assert_eq!(
+        endpoint_names(&config, TargetModel::Fast),
+        ["fast-1", "fast-2"]
+    );
+}
+
+#[test]
+fn test_yaml_override_merges_over_toml_base() {
+    let config = load("routing:\n  strategy: heuristic\n", ".yaml")
+        .expect("YAML override should merge over a TOML base");
+
+    assert_eq!(
+        config.routing.strategy,
+        octoroute::config::RoutingStrategy::Heuristic
+    );
+}
+
+#[test]
+fn test_merged_config_is_validated() {
+    let result = load(
+        "[[models.fast]]\nname = \"fast-1\"\nbase_url = \"http://localhost:1234/v1\"\nmax_tokens = 0\n",
+        ".toml",
+    );
+
+    match result {
+        Err(AppError::ConfigValidationFailed { path, .. }) => {
+            assert!(path.contains("with overrides from"), "{}", path);
+        }
+        other => panic!("expected a validation failure, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_malformed_append_arrays_is_rejected() {
+    let result = load("append_arrays = \"models.fast\"\n", ".toml");
+    assert!(
+        matches!(result, Err(AppError::ConfigValidationFailed { ref reason, .. }) if reason.contains("append_arrays")),
+        "{:?}",
+        result
+    );
+}
@@ -0,0 +1,166 @@
+//! Integration tests for config reload (`POST /admin/reload`)
+//!
+//! A reload re-reads the config file and swaps in the new state, so routes
+//! see new endpoints right away. A file that fails validation is a 400 and
+//! the running config stays as it was. SIGHUP goes through the same
+//! `ReloadableState::reload`.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+};
+use octoroute::{config::Config, handlers::AppState, reload::ReloadableState, server};
+use std::io::Write;
+use std::sync::Arc;
+use tempfile::NamedTempFile;
+use tower::ServiceExt;
+
+const ADMIN_TOKEN: &str = "test-admin-token";
+
+const CONFIG: &str = r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+admin_token = "test-admin-token"
+
+[[models.fast]]
+name = "fast-1"
+base_url = "http://localhost:9999/v1"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "http://localhost:9997/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:9996/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#;
+
+const SECOND_FAST_ENDPOINT: &str = r#"
+[[models.fast]]
+name = "fast-2"
+base_url = "http://localhost:9998/v1"
+max_tokens = 2048
+"#;
+
+fn write_config(file: &mut NamedTempFile, content: &str) {
+    let file = file.as_file_mut();
+    file.set_len(0).expect("should truncate config file");
+    std::io::Seek::rewind(file).expect("should rewind config file");
+    file.write_all(content.as_bytes())
+        .expect("should write config file");
+    file.flush().expect("should flush config file");
+}
+
+/// Server state started from a config file, as `main` does
+fn start_from_file() -> (NamedTempFile, ReloadableState) {
+    let mut file = tempfile::Builder::new()
+        .suffix(".toml")
+        .tempfile()
+        .expect("should create temp file");
+    write_config(&mut file, CONFIG);
+
+    let config = Config::from_file(file.path()).expect("initial config should load");
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+    let state = ReloadableState::new(state, file.path());
+    (file, state)
+}
+
+async fn send(app: Router, request: Request<Body>) -> (StatusCode, String) {
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, String::from_utf8_lossy(&body).into_owned())
+}
+
+fn reload_request() -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri("/admin/reload")
+        .header("authorization", format!("Bearer {ADMIN_TOKEN}"))
+        .body(Body::empty())
+        .unwrap()
+}
+
+fn models_request() -> Request<Body> {
+    Request::builder()
+        .uri("/v1/models")
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_reload_picks_up_new_endpoint() {
+    let (mut file, state) = start_from_file();
+    let app = server::public_app(state.clone());
+
+    let (_, models) = send(app.clone(), models_request()).await;
+    assert!(!models.contains("fast-2"), "{}", models);
+
+    write_config(&mut file, &format!("{CONFIG}{SECOND_FAST_ENDPOINT}"));
+    let (status, body) = send(app.clone(), reload_request()).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let summary: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(summary["added"], serde_json::json!(["fast-2"]));
+    assert_eq!(summary["removed"], serde_json::json!([]));
+    assert_eq!(summary["endpoints"], 4);
+
+    // The already-built router serves the new state
+    let (_, models) = send(app, models_request()).await;
+    assert!(models.contains("fast-2"), "{}", models);
+    assert_eq!(state.current().config().models.fast.len(), 2);
+}
+
+#[tokio::test]
+async fn test_failed_reload_returns_400_and_keeps_old_config() {
+    let (mut file, state) = start_from_file();
+    let app = server::public_app(state.clone());
+
+    let invalid = format!("{CONFIG}{SECOND_FAST_ENDPOINT}").replace(
+        "name = \"fast-2\"\nbase_url = \"http://localhost:9998/v1\"\nmax_tokens = 2048",
+        "name = \"fast-2\"\nbase_url = \"http://localhost:9998/v1\"\nmax_tokens = 0",
+    );
+    write_config(&mut file, &invalid);
+
+    let (status, body) = send(app.clone(), reload_request()).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{}", body);
+    assert!(body.contains("Config reload failed"), "{}", body);
+
+    // Still serving the original config
+    let (status, models) = send(app, models_request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(!models.contains("fast-2"), "{}", models);
+    assert_eq!(state.current().config().models.fast.len(), 1);
+}
+
+#[tokio::test]
+async fn test_reload_requires_admin_token() {
+    let (_file, state) = start_from_file();
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/admin/reload")
+        .body(Body::empty())
+        .unwrap();
+    let (status, _) = send(server::public_app(state), request).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_state_without_config_file_cannot_reload() {
+    let config: Config = toml::from_str(CONFIG).expect("should parse TOML config");
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+
+    let (status, body) = send(server::public_app(state), reload_request()).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body.contains("not started from a config file"), "{}", body);
+}
@@ -0,0 +1,139 @@
+//! Integration tests for YAML configuration files
+//!
+//! `Config::from_file` picks the parser from the file extension: `.yaml` and
+//! `.yml` are YAML, `.toml` (or no extension) is TOML, and anything else is
+//! rejected with a clear error. YAML configs go through the same validation.
+
+use octoroute::config::Config;
+use octoroute::error::AppError;
+use std::io::Write;
+use tempfile::NamedTempFile;
+
+const VALID_YAML: &str = r#"
+server:
+  host: "127.0.0.1"
+  port: 3000
+
+models:
+  fast:
+    - name: fast-1
+      base_url: http://localhost:1234/v1
+      max_tokens: 2048
+  balanced:
+    - name: balanced-1
+      base_url: http://localhost:1235/v1
+      max_tokens: 4096
+  deep:
+    - name: deep-1
+      base_url: http://localhost:1236/v1
+      max_tokens: 8192
+
+routing:
+  strategy: rule
+"#;
+
+const VALID_TOML: &str = r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+
+[[models.fast]]
+name = "fast-1"
+base_url = "http://localhost:1234/v1"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "http://localhost:1235/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:1236/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#;
+
+/// Write `content` to a temp file whose name ends in `suffix`
+fn create_temp_config(content: &str, suffix: &str) -> NamedTempFile {
+    let mut temp_file = tempfile::Builder::new()
+        .suffix(suffix)
+        .tempfile()
+        .expect("Failed to create temp file");
+    temp_file
+        .write_all(content.as_bytes())
+        .expect("Failed to write temp file");
+    temp_file.flush().expect("Failed to flush temp file");
+    temp_file
+}
+
+#[test]
+fn test_yaml_file_loads_same_config_as_toml() {
+    let yaml_file = create_temp_config(VALID_YAML, ".yaml");
+    let toml_file = create_temp_config(VALID_TOML, ".toml");
+
+    let from_yaml = Config::from_file(yaml_file.path()).expect("YAML config should load");
+    let from_toml = Config::from_file(toml_file.path()).expect("TOML config should load");
+
+    assert_eq!(
+        serde_json::to_value(&from_yaml).unwrap(),
+        serde_json::to_value(&from_toml).unwrap()
+    );
+}
+
+#[test]
+fn test_yml_extension_is_yaml() {
+    let yml_file = create_temp_config(VALID_YAML, ".yml");
+
+    let config = Config::from_file(yml_file.path()).expect(".yml config should load");
+
+    assert_eq!(config.models.fast[0].name(), "fast-1");
+}
+
+#[test]
+fn test_yaml_config_is_validated() {
+    let invalid = VALID_YAML.replace("http://localhost:1234/v1", "http://localhost:1234");
+    let yaml_file = create_temp_config(&invalid, ".yaml");
+
+    let err = Config::from_file(yaml_file.path()).expect_err("base_url without /v1 should fail");
+
+    assert!(
+        matches!(err, AppError::ConfigValidationFailed { .. }),
+        "Expected ConfigValidationFailed, got: {:?}",
+        err
+    );
+}
+
+#[test]
+fn test_malformed_yaml_reports_parse_error() {
+    let yaml_file = create_temp_config("server: [unterminated", ".yaml");
+
+    let err = Config::from_file(yaml_file.path()).expect_err("malformed YAML should fail");
+
+    assert!(
+        matches!(err, AppError::ConfigYamlParseFailed { .. }),
+        "Expected ConfigYamlParseFailed, got: {:?}",
+        err
+    );
+}
+
+#[test]
+fn test_unrecognized_extension_is_rejected() {
+    let json_file = create_temp_config("{}", ".json");
+
+    let err = Config::from_file(json_file.path()).expect_err(".json should be rejected");
+
+    assert!(
+        matches!(err, AppError::ConfigFormatUnsupported { ref extension, .. } if extension == "json"),
+        "Expected ConfigFormatUnsupported, got: {:?}",
+        err
+    );
+    let message = err.to_string();
+    assert!(
+        message.contains(".toml") && message.contains(".yaml"),
+        "Error should list supported formats, got: {}",
+        message
+    );
+}
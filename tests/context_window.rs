@@ -0,0 +1,239 @@
+//! Integration tests for the context window pre-flight (`context_window`)
+//!
+//! A prompt larger than every `context_window` in the routed tier is rejected
+//! with 400 naming the limit by default, or served from a larger tier with
+//! `routing.context_overflow = "escalate"`. Endpoints too small for a prompt
+//! are never selected.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::sync::Arc;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+/// Fast tier capped at 100 tokens, Balanced at 1000, Deep unlimited
+fn create_config(fast_url: &str, balanced_url: &str, deep_url: &str, overflow: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+request_timeout_seconds = 30
+
+[[models.fast]]
+name = "fast-1"
+base_url = "{fast_url}"
+max_tokens = 2048
+context_window = 100
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "{balanced_url}"
+max_tokens = 4096
+context_window = 1000
+
+[[models.deep]]
+name = "deep-1"
+base_url = "{deep_url}"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+context_overflow = "{overflow}"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_sse_response(text: &str) -> String {
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#.to_string(),
+        format!(
+            r#"data: {{"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{{"index":0,"delta":{{"content":"{text}"}},"finish_reason":null}}]}}"#
+        ),
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#.to_string(),
+        "data: [DONE]".to_string(),
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+async fn start_backend(text: &str) -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_sse_response(text))
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+fn create_test_app(state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware))
+}
+
+/// About 500 tokens at 4 characters per token: too large for Fast only
+fn long_prompt() -> String {
+    "word ".repeat(400)
+}
+
+async fn complete(state: &AppState, model: &str, prompt: &str) -> axum::response::Response {
+    send(
+        state,
+        format!(
+            r#"{{"model": "{model}", "messages": [{{"role": "user", "content": "{prompt}"}}]}}"#
+        ),
+    )
+    .await
+}
+
+async fn complete_streaming(
+    state: &AppState,
+    model: &str,
+    prompt: &str,
+) -> axum::response::Response {
+    send(
+        state,
+        format!(
+            r#"{{"model": "{model}", "stream": true, "messages": [{{"role": "user", "content": "{prompt}"}}]}}"#
+        ),
+    )
+    .await
+}
+
+async fn send(state: &AppState, body: String) -> axum::response::Response {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(body))
+        .unwrap();
+    create_test_app(state.clone())
+        .oneshot(request)
+        .await
+        .unwrap()
+}
+
+async fn received(server: &MockServer) -> usize {
+    server.received_requests().await.map_or(0, |r| r.len())
+}
+
+#[tokio::test]
+async fn test_oversized_prompt_is_rejected_naming_the_limit() {
+    let fast = start_backend("fast").await;
+    let balanced = start_backend("balanced").await;
+    let deep = start_backend("deep").await;
+    let config = create_config(&fast.uri(), &balanced.uri(), &deep.uri(), "reject");
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+
+    let response = complete(&state, "fast", &long_prompt()).await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["error"]["type"], "invalid_request_error");
+    let message = json["error"]["message"].as_str().unwrap();
+    assert!(message.contains("100 token context window"), "{}", message);
+    assert_eq!(received(&fast).await, 0, "no backend should be called");
+
+    // A prompt that fits is served as usual
+    assert_eq!(
+        complete(&state, "fast", "Hello").await.status(),
+        StatusCode::OK
+    );
+    assert_eq!(received(&fast).await, 1);
+}
+
+#[tokio::test]
+async fn test_oversized_prompt_escalates_to_next_tier_that_fits() {
+    let fast = start_backend("fast").await;
+    let balanced = start_backend("balanced").await;
+    let deep = start_backend("deep").await;
+    let config = create_config(&fast.uri(), &balanced.uri(), &deep.uri(), "escalate");
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+
+    let response = complete(&state, "fast", &long_prompt()).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let warning = response
+        .headers()
+        .get("x-octoroute-warning")
+        .expect("escalation should be reported")
+        .to_str()
+        .unwrap()
+        .to_string();
+    assert!(warning.contains("Balanced tier instead"), "{}", warning);
+    assert_eq!(received(&fast).await, 0);
+    assert_eq!(received(&balanced).await, 1);
+    assert_eq!(received(&deep).await, 0);
+
+    // Too large for Balanced as well: Deep has no limit
+    let huge = "word ".repeat(2000);
+    assert_eq!(
+        complete(&state, "fast", &huge).await.status(),
+        StatusCode::OK
+    );
+    assert_eq!(received(&deep).await, 1);
+}
+
+#[tokio::test]
+async fn test_escalation_is_reported_in_streaming_warning_header() {
+    let fast = start_backend("fast").await;
+    let balanced = start_backend("balanced").await;
+    let deep = start_backend("deep").await;
+    let config = create_config(&fast.uri(), &balanced.uri(), &deep.uri(), "escalate");
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+
+    let response = complete_streaming(&state, "fast", &long_prompt()).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let warning = response
+        .headers()
+        .get("x-octoroute-warning")
+        .expect("the routing decision's warnings should go out with the stream")
+        .to_str()
+        .unwrap()
+        .to_string();
+    assert!(warning.contains("Balanced tier instead"), "{}", warning);
+    axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(received(&fast).await, 0);
+    assert_eq!(received(&balanced).await, 1);
+}
+
+#[tokio::test]
+async fn test_named_endpoint_is_never_escalated() {
+    let fast = start_backend("fast").await;
+    let balanced = start_backend("balanced").await;
+    let deep = start_backend("deep").await;
+    let config = create_config(&fast.uri(), &balanced.uri(), &deep.uri(), "escalate");
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+
+    let response = complete(&state, "fast-1", &long_prompt()).await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(received(&fast).await, 0);
+    assert_eq!(received(&balanced).await, 0);
+}
@@ -0,0 +1,217 @@
+//! Integration tests for `server.max_messages` and `server.max_total_prompt_chars`
+//!
+//! Conversations over either limit get a 400 naming the setting before any
+//! routing, on both chat endpoints, and never reach a backend. Routing sizes
+//! a conversation by all of its messages, not just the last one.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::middleware::request_id_middleware;
+use octoroute::{config::Config, handlers::AppState};
+use std::sync::Arc;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+fn create_config(balanced_url: &str, deep_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 8080
+request_timeout_seconds = 30
+max_messages = 4
+max_total_prompt_chars = 6000
+
+[[models.fast]]
+name = "test-fast-model"
+base_url = "http://localhost:9999/v1"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "test-balanced-model"
+base_url = "{balanced_url}"
+max_tokens = 4096
+
+[[models.deep]]
+name = "test-deep-model"
+base_url = "{deep_url}"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_sse_response() -> String {
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{"content":"Hello"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#,
+        "data: [DONE]",
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+async fn start_mock_backend() -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_sse_response())
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+fn create_test_app(config: Config) -> Router {
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+    Router::new()
+        .route("/chat", post(octoroute::handlers::chat::handler))
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware))
+}
+
+/// Completion request body with one message per entry of `contents`, alternating roles
+fn conversation_body(contents: &[String]) -> String {
+    let messages: Vec<String> = contents
+        .iter()
+        .enumerate()
+        .map(|(i, content)| {
+            let role = if i % 2 == 0 { "user" } else { "assistant" };
+            format!(r#"{{"role": "{role}", "content": "{content}"}}"#)
+        })
+        .collect();
+    format!(
+        r#"{{"model": "auto", "messages": [{}]}}"#,
+        messages.join(",")
+    )
+}
+
+fn json_request(uri: &str, body: String) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("content-type", "application/json")
+        .body(Body::from(body))
+        .unwrap()
+}
+
+async fn assert_rejected(response: axum::response::Response, setting: &str) {
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body = String::from_utf8_lossy(&body);
+    assert!(
+        body.contains(setting),
+        "400 should name {}: {}",
+        setting,
+        body
+    );
+}
+
+async fn request_count(server: &MockServer) -> usize {
+    server.received_requests().await.unwrap().len()
+}
+
+#[tokio::test]
+async fn test_too_many_messages_rejected_before_routing() {
+    let balanced = start_mock_backend().await;
+    let deep = start_mock_backend().await;
+    let app = create_test_app(create_config(&balanced.uri(), &deep.uri()));
+
+    let contents: Vec<String> = (0..5).map(|i| format!("Message {i}")).collect();
+    let response = app
+        .oneshot(json_request(
+            "/v1/chat/completions",
+            conversation_body(&contents),
+        ))
+        .await
+        .unwrap();
+
+    assert_rejected(response, "server.max_messages").await;
+    assert_eq!(
+        request_count(&balanced).await + request_count(&deep).await,
+        0
+    );
+}
+
+#[tokio::test]
+async fn test_too_many_characters_rejected_before_routing() {
+    let balanced = start_mock_backend().await;
+    let deep = start_mock_backend().await;
+    let app = create_test_app(create_config(&balanced.uri(), &deep.uri()));
+
+    // Each message is small; together they pass 6000 characters
+    let contents: Vec<String> = (0..4).map(|_| "a".repeat(1600)).collect();
+    let response = app
+        .clone()
+        .oneshot(json_request(
+            "/v1/chat/completions",
+            conversation_body(&contents),
+        ))
+        .await
+        .unwrap();
+    assert_rejected(response, "server.max_total_prompt_chars").await;
+
+    let body = format!(r#"{{"message": "{}"}}"#, "a".repeat(6001));
+    let response = app.oneshot(json_request("/chat", body)).await.unwrap();
+    assert_rejected(response, "server.max_total_prompt_chars").await;
+
+    assert_eq!(
+        request_count(&balanced).await + request_count(&deep).await,
+        0
+    );
+}
+
+#[tokio::test]
+async fn test_routing_token_estimate_covers_whole_conversation() {
+    let balanced = start_mock_backend().await;
+    let deep = start_mock_backend().await;
+    let app = create_test_app(create_config(&balanced.uri(), &deep.uri()));
+
+    // A short code request on its own is under the 1024-token Deep threshold
+    let question = "Now show me the code.".to_string();
+    let response = app
+        .clone()
+        .oneshot(json_request(
+            "/v1/chat/completions",
+            conversation_body(std::slice::from_ref(&question)),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(request_count(&balanced).await, 1);
+    assert_eq!(request_count(&deep).await, 0);
+
+    // The same request after ~1200 tokens of earlier conversation goes to Deep
+    let contents = vec!["x".repeat(2400), "y".repeat(2400), question];
+    let response = app
+        .oneshot(json_request(
+            "/v1/chat/completions",
+            conversation_body(&contents),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(request_count(&balanced).await, 1);
+    assert_eq!(request_count(&deep).await, 1);
+}
@@ -0,0 +1,233 @@
+//! Integration tests for empty completions (`server.on_empty_completion`)
+//!
+//! The fast tier has an endpoint answering with an empty stream, tried first
+//! for its higher priority, and a sibling that answers normally. The policy
+//! decides whether the empty answer reaches the client, is retried on the
+//! sibling, or fails the request; every empty answer is counted in
+//! `octoroute_empty_completions_total`.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::sync::Arc;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+fn create_config(empty_url: &str, sibling_url: &str, policy: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+request_timeout_seconds = 5
+on_empty_completion = "{policy}"
+
+[[models.fast]]
+name = "fast-empty"
+base_url = "{empty_url}"
+max_tokens = 2048
+priority = 2
+
+[[models.fast]]
+name = "fast-sibling"
+base_url = "{sibling_url}"
+max_tokens = 2048
+priority = 1
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "http://localhost:9998/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:9997/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_sse_response(content: &str) -> String {
+    [
+        format!(
+            r#"data: {{"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{{"index":0,"delta":{{"content":"{content}"}},"finish_reason":null}}]}}"#
+        ),
+        "data: [DONE]".to_string(),
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+async fn start_backend(body: String) -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(body)
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+struct Backends {
+    empty: MockServer,
+    sibling: MockServer,
+}
+
+impl Backends {
+    async fn start() -> Self {
+        Self {
+            empty: start_backend("data: [DONE]\n\n".to_string()).await,
+            sibling: start_backend(create_sse_response("Hello")).await,
+        }
+    }
+
+    async fn sibling_calls(&self) -> usize {
+        self.sibling.received_requests().await.unwrap().len()
+    }
+}
+
+/// Send a completion request for the fast tier; returns status, body and the
+/// empty completions counted for `fast-empty`
+async fn complete(backends: &Backends, policy: &str, stream: bool) -> (StatusCode, String, u64) {
+    let config = create_config(&backends.empty.uri(), &backends.sibling.uri(), policy);
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+    let metrics = state.metrics();
+    let app = Router::new()
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware));
+
+    let body = serde_json::json!({
+        "model": "fast",
+        "messages": [{"role": "user", "content": "Hello"}],
+        "stream": stream,
+    });
+    let request = Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (
+        status,
+        String::from_utf8_lossy(&bytes).into_owned(),
+        metrics.empty_completions_count("fast-empty"),
+    )
+}
+
+fn message_content(body: &str) -> String {
+    let json: serde_json::Value = serde_json::from_str(body).expect("response should be JSON");
+    json["choices"][0]["message"]["content"]
+        .as_str()
+        .expect("completion should have content")
+        .to_string()
+}
+
+/// Concatenated `delta.content` of every chunk in an SSE body
+fn streamed_content(body: &str) -> String {
+    body.lines()
+        .filter_map(|line| line.strip_prefix("data: "))
+        .filter(|data| *data != "[DONE]")
+        .filter_map(|data| serde_json::from_str::<serde_json::Value>(data).ok())
+        .filter_map(|chunk| {
+            chunk["choices"][0]["delta"]["content"]
+                .as_str()
+                .map(str::to_string)
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_retry_policy_retries_once_then_succeeds() {
+    let backends = Backends::start().await;
+
+    let (status, body, empty) = complete(&backends, "retry", false).await;
+
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(message_content(&body), "Hello");
+    assert_eq!(empty, 1);
+    assert_eq!(backends.empty.received_requests().await.unwrap().len(), 1);
+    assert_eq!(backends.sibling_calls().await, 1);
+}
+
+#[tokio::test]
+async fn test_error_policy_fails_without_retry() {
+    let backends = Backends::start().await;
+
+    let (status, body, empty) = complete(&backends, "error", false).await;
+
+    assert_eq!(status, StatusCode::BAD_GATEWAY);
+    assert!(body.contains("empty response"), "{}", body);
+    assert_eq!(empty, 1);
+    assert_eq!(backends.sibling_calls().await, 0);
+}
+
+#[tokio::test]
+async fn test_return_empty_policy_passes_empty_completion_through() {
+    let backends = Backends::start().await;
+
+    let (status, body, empty) = complete(&backends, "return_empty", false).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(message_content(&body), "");
+    assert_eq!(empty, 1);
+    assert_eq!(backends.sibling_calls().await, 0);
+}
+
+#[tokio::test]
+async fn test_retry_policy_fails_stream_over_to_sibling() {
+    let backends = Backends::start().await;
+
+    let (status, body, empty) = complete(&backends, "retry", true).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(streamed_content(&body), "Hello");
+    assert!(body.trim_end().ends_with("data: [DONE]"), "{}", body);
+    assert_eq!(empty, 1);
+    assert_eq!(backends.sibling_calls().await, 1);
+}
+
+#[tokio::test]
+async fn test_error_policy_reports_empty_stream() {
+    let backends = Backends::start().await;
+
+    let (status, body, empty) = complete(&backends, "error", true).await;
+
+    assert_eq!(
+        status,
+        StatusCode::OK,
+        "Stream headers are sent before the upstream answers"
+    );
+    assert!(
+        streamed_content(&body).contains("empty completion"),
+        "{}",
+        body
+    );
+    assert_eq!(empty, 1);
+    assert_eq!(backends.sibling_calls().await, 0);
+}
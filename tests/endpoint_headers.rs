@@ -0,0 +1,248 @@
+//! Integration tests for endpoint name headers (`observability.endpoint_headers`)
+//!
+//! With the setting on, chat responses name the serving endpoint in
+//! `x-octoroute-endpoint` and, when the LLM router chose the tier, the router
+//! endpoint in `x-octoroute-router-endpoint`. The balanced tier here has two
+//! endpoints on separate mock servers, so the header can be checked against
+//! the one that actually received the completion.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{HeaderMap, Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::handlers::{X_OCTOROUTE_ENDPOINT, X_OCTOROUTE_ROUTER_ENDPOINT};
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::sync::Arc;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+fn create_config(router_url: &str, first_url: &str, second_url: &str, enabled: bool) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+request_timeout_seconds = 30
+
+[[models.fast]]
+name = "fast-router"
+base_url = "{router_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "{first_url}"
+max_tokens = 4096
+
+[[models.balanced]]
+name = "balanced-2"
+base_url = "{second_url}"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:9997/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "llm"
+router_tier = "fast"
+
+[observability]
+endpoint_headers = {enabled}
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_sse_response(content: &str) -> String {
+    let content = serde_json::to_string(content).unwrap();
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#.to_string(),
+        format!(
+            r#"data: {{"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{{"index":0,"delta":{{"content":{content}}},"finish_reason":null}}]}}"#
+        ),
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#.to_string(),
+        "data: [DONE]".to_string(),
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+async fn start_server(content: &str) -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_sse_response(content))
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+struct Backends {
+    router: MockServer,
+    first: MockServer,
+    second: MockServer,
+}
+
+impl Backends {
+    async fn start() -> Self {
+        Self {
+            router: start_server("BALANCED").await,
+            first: start_server("From the first endpoint").await,
+            second: start_server("From the second endpoint").await,
+        }
+    }
+
+    fn config(&self, enabled: bool) -> Config {
+        create_config(
+            &self.router.uri(),
+            &self.first.uri(),
+            &self.second.uri(),
+            enabled,
+        )
+    }
+
+    /// Name of the balanced endpoint whose mock received the completion
+    async fn served_by(&self) -> &'static str {
+        let first = self.first.received_requests().await.unwrap().len();
+        let second = self.second.received_requests().await.unwrap().len();
+        match (first, second) {
+            (1, 0) => "balanced-1",
+            (0, 1) => "balanced-2",
+            other => panic!("expected exactly one completion call, got {:?}", other),
+        }
+    }
+}
+
+/// Send `body` to `uri` and return the response status and headers
+async fn send(config: Config, uri: &str, body: &str) -> (StatusCode, HeaderMap) {
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+    let app = Router::new()
+        .route("/chat", post(octoroute::handlers::chat::handler))
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware));
+    let request = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let headers = response.headers().clone();
+    // Drain the body so streaming responses finish their upstream call
+    axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, headers)
+}
+
+fn header<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name).map(|value| value.to_str().unwrap())
+}
+
+const COMPLETION: &str =
+    r#"{"model": "auto", "messages": [{"role": "user", "content": "Explain ownership"}]}"#;
+
+#[tokio::test]
+async fn test_completion_names_serving_and_router_endpoints() {
+    let backends = Backends::start().await;
+
+    let (status, headers) = send(backends.config(true), "/v1/chat/completions", COMPLETION).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        header(&headers, X_OCTOROUTE_ENDPOINT),
+        Some(backends.served_by().await)
+    );
+    assert_eq!(
+        header(&headers, X_OCTOROUTE_ROUTER_ENDPOINT),
+        Some("fast-router")
+    );
+}
+
+#[tokio::test]
+async fn test_chat_names_serving_endpoint() {
+    let backends = Backends::start().await;
+
+    let (status, headers) = send(
+        backends.config(true),
+        "/chat",
+        r#"{"message": "Explain ownership"}"#,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        header(&headers, X_OCTOROUTE_ENDPOINT),
+        Some(backends.served_by().await)
+    );
+    assert_eq!(
+        header(&headers, X_OCTOROUTE_ROUTER_ENDPOINT),
+        Some("fast-router")
+    );
+}
+
+#[tokio::test]
+async fn test_streaming_names_serving_endpoint() {
+    let backends = Backends::start().await;
+
+    let (status, headers) = send(
+        backends.config(true),
+        "/v1/chat/completions",
+        r#"{"model": "auto", "messages": [{"role": "user", "content": "Explain ownership"}], "stream": true}"#,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        header(&headers, X_OCTOROUTE_ENDPOINT),
+        Some(backends.served_by().await)
+    );
+}
+
+#[tokio::test]
+async fn test_explicit_tier_has_no_router_endpoint_header() {
+    let backends = Backends::start().await;
+
+    let (status, headers) = send(
+        backends.config(true),
+        "/v1/chat/completions",
+        r#"{"model": "balanced", "messages": [{"role": "user", "content": "Hello"}]}"#,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        header(&headers, X_OCTOROUTE_ENDPOINT),
+        Some(backends.served_by().await)
+    );
+    assert!(headers.get(X_OCTOROUTE_ROUTER_ENDPOINT).is_none());
+}
+
+#[tokio::test]
+async fn test_headers_omitted_when_disabled() {
+    let backends = Backends::start().await;
+
+    let (status, headers) = send(backends.config(false), "/v1/chat/completions", COMPLETION).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(headers.get(X_OCTOROUTE_ENDPOINT).is_none());
+    assert!(headers.get(X_OCTOROUTE_ROUTER_ENDPOINT).is_none());
+}
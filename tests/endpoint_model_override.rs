@@ -0,0 +1,190 @@
+//! Integration tests for the per-endpoint `model` override
+//!
+//! An endpoint's `model` is the string sent to the backend; its `name` stays
+//! the identifier clients select it by and the key for health tracking. An
+//! endpoint without `model` sends its `name`, as before.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::middleware::request_id_middleware;
+use octoroute::{config::Config, handlers::AppState};
+use std::sync::Arc;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+fn create_config(fast_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 8080
+request_timeout_seconds = 30
+
+[[models.fast]]
+name = "fast-primary"
+model = "llama-3.1-8b"
+base_url = "{fast_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "test-balanced-model"
+base_url = "{fast_url}"
+max_tokens = 4096
+
+[[models.deep]]
+name = "test-deep-model"
+base_url = "http://localhost:9997/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_sse_response() -> String {
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{"content":"Hello"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#,
+        "data: [DONE]",
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+async fn start_mock_backend(status: u16) -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(status)
+                .set_body_string(create_sse_response())
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+fn create_test_app(state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware))
+}
+
+fn completion_request(model: &str) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(format!(
+            r#"{{"model": "{model}", "messages": [{{"role": "user", "content": "Hello"}}]}}"#
+        )))
+        .unwrap()
+}
+
+/// `model` field of every request the backend received
+async fn backend_models(server: &MockServer) -> Vec<String> {
+    server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .map(|request| {
+            let body: serde_json::Value =
+                serde_json::from_slice(&request.body).expect("backend body should be JSON");
+            body["model"].as_str().unwrap_or_default().to_string()
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_configured_model_reaches_backend() {
+    let backend = start_mock_backend(200).await;
+    let state = AppState::new(Arc::new(create_config(&format!("{}/v1", backend.uri()))))
+        .expect("AppState::new should succeed");
+    let app = create_test_app(state);
+
+    // Selected by its name, and by tier
+    for model in ["fast-primary", "fast"] {
+        let response = app
+            .clone()
+            .oneshot(completion_request(model))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK, "{}", model);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["model"], "fast-primary", "clients see the name");
+    }
+
+    assert_eq!(
+        backend_models(&backend).await,
+        ["llama-3.1-8b", "llama-3.1-8b"]
+    );
+}
+
+#[tokio::test]
+async fn test_endpoint_without_model_sends_name() {
+    let backend = start_mock_backend(200).await;
+    let state = AppState::new(Arc::new(create_config(&format!("{}/v1", backend.uri()))))
+        .expect("AppState::new should succeed");
+
+    let response = create_test_app(state)
+        .oneshot(completion_request("test-balanced-model"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(backend_models(&backend).await, ["test-balanced-model"]);
+}
+
+#[tokio::test]
+async fn test_selection_and_health_key_on_name() {
+    let backend = start_mock_backend(500).await;
+    let state = AppState::new(Arc::new(create_config(&format!("{}/v1", backend.uri()))))
+        .expect("AppState::new should succeed");
+    let app = create_test_app(state.clone());
+
+    // The backend model string is not a selectable endpoint
+    let response = app
+        .clone()
+        .oneshot(completion_request("llama-3.1-8b"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // A failing query is recorded against the endpoint name
+    let response = app
+        .oneshot(completion_request("fast-primary"))
+        .await
+        .unwrap();
+    assert!(!response.status().is_success());
+
+    let statuses = state.selector().health_checker().get_all_statuses().await;
+    let primary = statuses
+        .iter()
+        .find(|health| health.name() == "fast-primary")
+        .expect("fast-primary should be tracked");
+    assert!(primary.consecutive_failures() > 0);
+    assert!(
+        statuses
+            .iter()
+            .all(|health| health.name() != "llama-3.1-8b")
+    );
+}
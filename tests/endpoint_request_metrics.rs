@@ -0,0 +1,179 @@
+//! Integration tests for `octoroute_endpoint_requests_total`
+//!
+//! Every upstream attempt is counted against its endpoint as a success or a
+//! failure. The fast tier here has a preferred (higher priority) endpoint
+//! answering 500 and a fallback answering normally, so tier-routed requests try
+//! the failing endpoint first and are retried on the other.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::{
+    config::Config, handlers::AppState, metrics::EndpointOutcome, middleware::request_id_middleware,
+};
+use std::sync::Arc;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+fn create_config(good_url: &str, bad_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+request_timeout_seconds = 30
+
+[[models.fast]]
+name = "fast-good"
+base_url = "{good_url}"
+max_tokens = 2048
+
+[[models.fast]]
+name = "fast-bad"
+base_url = "{bad_url}"
+max_tokens = 2048
+priority = 10
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "http://localhost:9998/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:9997/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_sse_response() -> String {
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{"content":"Hello"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#,
+        "data: [DONE]",
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+async fn start_server(status: u16) -> MockServer {
+    let mock_server = MockServer::start().await;
+    let response = if status == 200 {
+        ResponseTemplate::new(200)
+            .set_body_string(create_sse_response())
+            .insert_header("content-type", "text/event-stream")
+    } else {
+        ResponseTemplate::new(status)
+    };
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(response)
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+/// Send `body` to the completions endpoint and return the response status
+async fn send(state: AppState, body: &str) -> StatusCode {
+    let app = Router::new()
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware));
+    let request = Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    // Drain the body so streaming completions run to the end
+    axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    status
+}
+
+fn count(state: &AppState, endpoint: &str, outcome: EndpointOutcome) -> u64 {
+    state.metrics().endpoint_requests_count(endpoint, outcome)
+}
+
+#[tokio::test]
+async fn test_success_and_failure_are_counted_per_endpoint() {
+    let good = start_server(200).await;
+    let bad = start_server(500).await;
+    let state = AppState::new(Arc::new(create_config(&good.uri(), &bad.uri())))
+        .expect("AppState::new should succeed");
+
+    for _ in 0..6 {
+        let status = send(
+            state.clone(),
+            r#"{"model": "fast", "messages": [{"role": "user", "content": "Hello"}]}"#,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    // fast-bad is tried until health tracking marks it unhealthy
+    let bad_calls = bad.received_requests().await.unwrap().len() as u64;
+    assert!(bad_calls > 0);
+    assert_eq!(count(&state, "fast-good", EndpointOutcome::Success), 6);
+    assert_eq!(count(&state, "fast-good", EndpointOutcome::Failure), 0);
+    assert_eq!(
+        count(&state, "fast-bad", EndpointOutcome::Failure),
+        bad_calls
+    );
+    assert_eq!(count(&state, "fast-bad", EndpointOutcome::Success), 0);
+}
+
+#[tokio::test]
+async fn test_specific_model_failure_is_counted() {
+    let good = start_server(200).await;
+    let bad = start_server(500).await;
+    let state = AppState::new(Arc::new(create_config(&good.uri(), &bad.uri())))
+        .expect("AppState::new should succeed");
+
+    let status = send(
+        state.clone(),
+        r#"{"model": "fast-bad", "messages": [{"role": "user", "content": "Hello"}]}"#,
+    )
+    .await;
+
+    assert_ne!(status, StatusCode::OK);
+    assert_eq!(count(&state, "fast-bad", EndpointOutcome::Failure), 1);
+    assert_eq!(count(&state, "fast-good", EndpointOutcome::Success), 0);
+}
+
+#[tokio::test]
+async fn test_streaming_attempts_are_counted() {
+    let good = start_server(200).await;
+    let bad = start_server(500).await;
+    let state = AppState::new(Arc::new(create_config(&good.uri(), &bad.uri())))
+        .expect("AppState::new should succeed");
+
+    let status = send(
+        state.clone(),
+        r#"{"model": "fast-good", "messages": [{"role": "user", "content": "Hello"}], "stream": true}"#,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(count(&state, "fast-good", EndpointOutcome::Success), 1);
+    assert_eq!(count(&state, "fast-good", EndpointOutcome::Failure), 0);
+}
@@ -0,0 +1,270 @@
+//! Integration tests for per-endpoint upstream request timeouts
+//!
+//! Verifies that `request_timeout_seconds` on a `[[models.*]]` entry is enforced
+//! on the upstream query (overriding the server default), and that for streaming
+//! requests the timeout only applies until the first token arrives.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+/// Config whose fast endpoint has a 1s timeout while the server default is 30s
+fn create_config_with_endpoint_timeout(mock_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 8080
+request_timeout_seconds = 30
+
+[[models.fast]]
+name = "test-fast-model"
+base_url = "{mock_url}"
+max_tokens = 2048
+request_timeout_seconds = 1
+
+[[models.balanced]]
+name = "test-balanced-model"
+base_url = "http://localhost:9998/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "test-deep-model"
+base_url = "http://localhost:9997/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+/// Minimal OpenAI-format SSE body accepted by open-agent-sdk
+fn create_sse_response(content: &str) -> String {
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#.to_string(),
+        format!(
+            r#"data: {{"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{{"index":0,"delta":{{"content":"{}"}},"finish_reason":null}}]}}"#,
+            content
+        ),
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#.to_string(),
+        "data: [DONE]".to_string(),
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+fn create_test_app(state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware))
+}
+
+fn completion_request(model: &str, stream: bool) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(format!(
+            r#"{{"model": "{}", "messages": [{{"role": "user", "content": "Hello"}}], "stream": {}}}"#,
+            model, stream
+        )))
+        .unwrap()
+}
+
+async fn consecutive_failures(state: &AppState, name: &str) -> u32 {
+    state
+        .selector()
+        .health_checker()
+        .get_all_statuses()
+        .await
+        .iter()
+        .find(|s| s.name() == name)
+        .expect("endpoint should exist")
+        .consecutive_failures()
+}
+
+#[tokio::test]
+async fn test_streaming_stalled_backend_trips_endpoint_timeout() {
+    let mock_server = MockServer::start().await;
+
+    // Backend stalls for 10s before sending anything (well past the 1s endpoint timeout)
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_delay(Duration::from_secs(10))
+                .set_body_string(create_sse_response("late"))
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let config = create_config_with_endpoint_timeout(&mock_server.uri());
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+    let app = create_test_app(state.clone());
+
+    let start = Instant::now();
+    let response = app
+        .oneshot(completion_request("test-fast-model", true))
+        .await
+        .unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let elapsed = start.elapsed();
+    let body_str = String::from_utf8_lossy(&body);
+
+    assert!(
+        elapsed < Duration::from_secs(5),
+        "Endpoint timeout (1s) should fire well before the 10s backend delay and the 30s server default, took {:?}",
+        elapsed
+    );
+    assert!(
+        body_str.contains("timed out"),
+        "Stream should contain a timeout error event, got: {}",
+        body_str
+    );
+    assert!(
+        body_str.contains("[DONE]"),
+        "Stream should terminate with [DONE] after timeout, got: {}",
+        body_str
+    );
+    assert!(
+        consecutive_failures(&state, "test-fast-model").await > 0,
+        "Timed-out endpoint should be marked as failed for health tracking"
+    );
+}
+
+#[tokio::test]
+async fn test_non_streaming_stalled_backend_trips_endpoint_timeout() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_delay(Duration::from_secs(10))
+                .set_body_string(create_sse_response("late"))
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let config = create_config_with_endpoint_timeout(&mock_server.uri());
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+    let app = create_test_app(state.clone());
+
+    let start = Instant::now();
+    let response = app
+        .oneshot(completion_request("test-fast-model", false))
+        .await
+        .unwrap();
+    let elapsed = start.elapsed();
+
+    assert_eq!(
+        response.status(),
+        StatusCode::GATEWAY_TIMEOUT,
+        "Stalled backend should produce 504 Gateway Timeout"
+    );
+    assert!(
+        elapsed < Duration::from_secs(5),
+        "Endpoint timeout (1s) should be used instead of the 30s server default, took {:?}",
+        elapsed
+    );
+    assert!(
+        consecutive_failures(&state, "test-fast-model").await > 0,
+        "Timed-out endpoint should be marked as failed for health tracking"
+    );
+}
+
+#[tokio::test]
+async fn test_streaming_responsive_backend_does_not_trip_endpoint_timeout() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_sse_response("Hello"))
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let config = create_config_with_endpoint_timeout(&mock_server.uri());
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+    let app = create_test_app(state.clone());
+
+    let response = app
+        .oneshot(completion_request("test-fast-model", true))
+        .await
+        .unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body_str = String::from_utf8_lossy(&body);
+
+    assert!(
+        !body_str.contains("timed out"),
+        "Responsive backend should not trip the endpoint timeout, got: {}",
+        body_str
+    );
+    assert!(
+        body_str.contains("Hello"),
+        "Stream should contain backend content, got: {}",
+        body_str
+    );
+    assert_eq!(
+        consecutive_failures(&state, "test-fast-model").await,
+        0,
+        "Responsive endpoint should not be marked as failed"
+    );
+}
+
+#[tokio::test]
+async fn test_non_streaming_responsive_backend_does_not_trip_endpoint_timeout() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_sse_response("Hello"))
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let config = create_config_with_endpoint_timeout(&mock_server.uri());
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+    let app = create_test_app(state);
+
+    let response = app
+        .oneshot(completion_request("test-fast-model", false))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        response.status(),
+        StatusCode::OK,
+        "Responsive backend should complete within the endpoint timeout"
+    );
+}
@@ -92,6 +92,26 @@ fn test_stream_error_is_retryable() {
     );
 }
 
+/// Test that UpstreamStatus errors follow the default retryable statuses
+#[test]
+fn test_upstream_status_is_retryable_by_status() {
+    use octoroute::error::ModelQueryError;
+
+    let error = |status| ModelQueryError::UpstreamStatus {
+        endpoint: "http://localhost:1234/v1".to_string(),
+        status,
+        error_message: "rejected".to_string(),
+    };
+
+    assert!(error(429).is_retryable(), "429 means try another endpoint");
+    assert!(error(503).is_retryable());
+    assert!(
+        !error(400).is_retryable(),
+        "A malformed request fails the same way everywhere"
+    );
+    assert_eq!(error(400).status(), Some(400));
+}
+
 /// Test that AgentOptionsConfigError is NOT retryable
 #[test]
 fn test_agent_options_config_error_is_not_retryable() {
@@ -205,6 +225,8 @@ fn is_error_retryable(error: &octoroute::error::AppError) -> bool {
         AppError::Config(_)
         | AppError::ConfigFileRead { .. }
         | AppError::ConfigParseFailed { .. }
+        | AppError::ConfigYamlParseFailed { .. }
+        | AppError::ConfigFormatUnsupported { .. }
         | AppError::ConfigValidationFailed { .. } => false,
         _ => true, // Conservative: assume retryable for unknown errors
     }
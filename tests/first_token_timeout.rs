@@ -0,0 +1,208 @@
+//! Integration tests for the first-token budget (`server.first_token_timeout_ms`)
+//!
+//! An endpoint that hasn't produced its first chunk within the budget is
+//! abandoned and the request moves to a sibling, streaming or not, long before
+//! the endpoint timeout. An endpoint answering within the budget serves
+//! normally. Wiremock delays run on the mock server's own clock, so these
+//! tests use real time with wide margins rather than a paused runtime.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::models::HealthFailureKind;
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+const FIRST_TOKEN_TIMEOUT_MS: u64 = 500;
+
+fn create_sse_response(text: &str) -> String {
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#.to_string(),
+        format!(
+            r#"data: {{"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{{"index":0,"delta":{{"content":"{text}"}},"finish_reason":null}}]}}"#
+        ),
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#.to_string(),
+        "data: [DONE]".to_string(),
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+/// The primary endpoint has the higher priority, so it is always tried first
+fn create_config(primary_url: &str, sibling_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+request_timeout_seconds = 10
+first_token_timeout_ms = {FIRST_TOKEN_TIMEOUT_MS}
+
+[[models.fast]]
+name = "fast-primary"
+base_url = "{primary_url}"
+max_tokens = 2048
+priority = 2
+
+[[models.fast]]
+name = "fast-sibling"
+base_url = "{sibling_url}"
+max_tokens = 2048
+priority = 1
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "{sibling_url}"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "{sibling_url}"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+/// Backend that answers `text` after `delay`
+async fn start_backend(text: &str, delay: Duration) -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_delay(delay)
+                .set_body_string(create_sse_response(text))
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+/// Send a fast-tier completion and return its status and body
+async fn complete(state: &AppState, stream: bool) -> (StatusCode, String) {
+    let app = Router::new()
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state.clone())
+        .layer(middleware::from_fn(request_id_middleware));
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(format!(
+            r#"{{"model": "fast", "messages": [{{"role": "user", "content": "Hello"}}], "stream": {stream}}}"#
+        )))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, String::from_utf8_lossy(&bytes).into_owned())
+}
+
+async fn last_failure(state: &AppState, endpoint: &str) -> Option<HealthFailureKind> {
+    state
+        .selector()
+        .health_checker()
+        .get_all_statuses()
+        .await
+        .iter()
+        .find(|s| s.name() == endpoint)
+        .expect("endpoint should exist")
+        .last_failure()
+}
+
+async fn request_count(server: &MockServer) -> usize {
+    server.received_requests().await.unwrap().len()
+}
+
+#[tokio::test]
+async fn test_slow_first_token_fails_over_without_streaming() {
+    let primary = start_backend("Hello from primary", Duration::from_secs(4)).await;
+    let sibling = start_backend("Hello from sibling", Duration::ZERO).await;
+    let state = AppState::new(Arc::new(create_config(&primary.uri(), &sibling.uri())))
+        .expect("AppState::new should succeed");
+
+    let started = Instant::now();
+    let (status, body) = complete(&state, false).await;
+
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert!(body.contains("Hello from sibling"), "{}", body);
+    assert!(
+        started.elapsed() < Duration::from_secs(3),
+        "failover should not wait for the slow backend: {:?}",
+        started.elapsed()
+    );
+    assert_eq!(
+        last_failure(&state, "fast-primary").await,
+        Some(HealthFailureKind::Timeout)
+    );
+}
+
+#[tokio::test]
+async fn test_slow_first_token_fails_over_while_streaming() {
+    let primary = start_backend("Hello from primary", Duration::from_secs(4)).await;
+    let sibling = start_backend("Hello from sibling", Duration::ZERO).await;
+    let state = AppState::new(Arc::new(create_config(&primary.uri(), &sibling.uri())))
+        .expect("AppState::new should succeed");
+
+    let started = Instant::now();
+    let (status, body) = complete(&state, true).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(
+        body.contains("Hello from sibling") && body.contains("[DONE]"),
+        "sibling should serve the whole stream, got: {}",
+        body
+    );
+    assert!(
+        !body.contains("[Error"),
+        "no error event expected: {}",
+        body
+    );
+    assert!(
+        started.elapsed() < Duration::from_secs(3),
+        "failover should not wait for the slow backend: {:?}",
+        started.elapsed()
+    );
+    assert_eq!(
+        last_failure(&state, "fast-primary").await,
+        Some(HealthFailureKind::Timeout)
+    );
+}
+
+#[tokio::test]
+async fn test_first_token_within_budget_is_served() {
+    let primary = start_backend("Hello from primary", Duration::from_millis(100)).await;
+    let sibling = start_backend("Hello from sibling", Duration::ZERO).await;
+    let state = AppState::new(Arc::new(create_config(&primary.uri(), &sibling.uri())))
+        .expect("AppState::new should succeed");
+
+    for stream in [false, true] {
+        let (status, body) = complete(&state, stream).await;
+        assert_eq!(status, StatusCode::OK, "{}", body);
+        assert!(body.contains("Hello from primary"), "{}", body);
+    }
+    assert_eq!(request_count(&sibling).await, 0);
+    assert_eq!(last_failure(&state, "fast-primary").await, None);
+}
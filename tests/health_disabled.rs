@@ -0,0 +1,100 @@
+//! Integration tests for turning health checking off (`[health].enabled = false`)
+//!
+//! With health tracking disabled no background health task is spawned, and
+//! endpoints stay selectable however many failures are recorded against them,
+//! which keeps single-endpoint setups and tests deterministic.
+
+use octoroute::config::Config;
+use octoroute::metrics::Metrics;
+use octoroute::models::{ExclusionSet, ModelSelector};
+use octoroute::router::TargetModel;
+use std::sync::Arc;
+
+fn create_config(health_enabled: bool) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+
+[[models.fast]]
+name = "fast-1"
+base_url = "http://localhost:1234/v1"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "http://localhost:1235/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:1236/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+
+[health]
+enabled = {health_enabled}
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_selector(health_enabled: bool) -> ModelSelector {
+    let metrics = Arc::new(Metrics::new().expect("should create metrics"));
+    ModelSelector::new(Arc::new(create_config(health_enabled)), metrics)
+}
+
+/// Record enough failures against fast-1 to mark it unhealthy when tracking is on
+async fn fail_fast_endpoint(selector: &ModelSelector) {
+    for _ in 0..5 {
+        selector
+            .health_checker()
+            .mark_failure("fast-1")
+            .await
+            .expect("fast-1 is a known endpoint");
+    }
+}
+
+#[tokio::test]
+async fn test_disabled_health_spawns_no_background_task() {
+    let selector = create_selector(false);
+    // The task handle is stored asynchronously; give that a chance to run
+    tokio::task::yield_now().await;
+
+    assert!(!selector.health_checker().is_enabled());
+    assert!(!selector.health_checker().has_background_task().await);
+
+    // Shutdown with no task to cancel is a no-op
+    selector.health_checker().shutdown().await;
+}
+
+#[tokio::test]
+async fn test_disabled_health_keeps_failed_endpoints_selectable() {
+    let selector = create_selector(false);
+    fail_fast_endpoint(&selector).await;
+
+    assert!(selector.health_checker().is_healthy("fast-1").await);
+    let endpoint = selector
+        .select(TargetModel::Fast, &ExclusionSet::new())
+        .await
+        .expect("fast-1 should stay selectable");
+    assert_eq!(endpoint.name(), "fast-1");
+}
+
+#[tokio::test]
+async fn test_enabled_health_still_filters_failed_endpoints() {
+    let selector = create_selector(true);
+    fail_fast_endpoint(&selector).await;
+
+    assert!(!selector.health_checker().is_healthy("fast-1").await);
+    assert!(
+        selector
+            .select(TargetModel::Fast, &ExclusionSet::new())
+            .await
+            .is_none()
+    );
+    selector.health_checker().shutdown().await;
+}
@@ -0,0 +1,195 @@
+//! Integration tests for health check failure classification
+//!
+//! Each way an endpoint can fail its probe is recorded under its own kind: a
+//! closed port is a connection failure, a 500 an HTTP status failure, a
+//! non-HTTP answer a parse failure, and a probe that outlives the 5s health
+//! check timeout a timeout. The kind shows up in
+//! `octoroute_health_failures_total` and in the `/health` response.
+
+use axum::{Json, extract::State};
+use octoroute::config::Config;
+use octoroute::handlers::{AppState, health};
+use octoroute::metrics::Metrics;
+use octoroute::models::{EndpointHealth, HealthChecker, HealthFailureKind};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn create_config(fast_urls: &[(&str, &str)]) -> Config {
+    let fast: String = fast_urls
+        .iter()
+        .map(|(name, url)| {
+            format!(
+                "[[models.fast]]\nname = \"{name}\"\nbase_url = \"{url}\"\nmax_tokens = 2048\n\n"
+            )
+        })
+        .collect();
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+
+{fast}
+[[models.balanced]]
+name = "balanced-1"
+base_url = "{balanced_url}"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "{deep_url}"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#,
+        balanced_url = fast_urls[0].1,
+        deep_url = fast_urls[0].1,
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+/// Mock server answering health probes with `status` after `delay`
+async fn probe_server(status: u16, delay: Duration) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("HEAD"))
+        .and(path("/v1/models"))
+        .respond_with(ResponseTemplate::new(status).set_delay(delay))
+        .mount(&server)
+        .await;
+    server
+}
+
+/// Base URL of a local port nothing listens on
+async fn closed_port_url() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    drop(listener);
+    format!("http://{addr}/v1")
+}
+
+/// Base URL of a TCP server that answers every request with non-HTTP bytes
+async fn garbage_server_url() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            let mut buf = [0u8; 1024];
+            let _ = socket.read(&mut buf).await;
+            let _ = socket.write_all(b"this is not http\r\n\r\n").await;
+            let _ = socket.shutdown().await;
+        }
+    });
+    format!("http://{addr}/v1")
+}
+
+fn last_failure(statuses: &[EndpointHealth], name: &str) -> Option<HealthFailureKind> {
+    statuses
+        .iter()
+        .find(|h| h.name() == name)
+        .and_then(|h| h.last_failure())
+}
+
+#[tokio::test]
+async fn test_each_failure_kind_is_recorded_with_its_label() {
+    let healthy = probe_server(200, Duration::ZERO).await;
+    let erroring = probe_server(500, Duration::ZERO).await;
+    let healthy_url = format!("{}/v1", healthy.uri());
+    let erroring_url = format!("{}/v1", erroring.uri());
+    let closed_url = closed_port_url().await;
+    let garbage_url = garbage_server_url().await;
+
+    let config = Arc::new(create_config(&[
+        ("fast-healthy", &healthy_url),
+        ("fast-500", &erroring_url),
+        ("fast-closed", &closed_url),
+        ("fast-garbage", &garbage_url),
+    ]));
+    let metrics = Arc::new(Metrics::new().expect("should create metrics"));
+    let checker = HealthChecker::new_with_metrics(config, metrics.clone());
+
+    let report = checker.warmup().await;
+    assert_eq!(report.unreachable.len(), 3, "{:?}", report);
+
+    let statuses = checker.get_all_statuses().await;
+    let expected = [
+        ("fast-500", HealthFailureKind::HttpStatus(500)),
+        ("fast-closed", HealthFailureKind::Connection),
+        ("fast-garbage", HealthFailureKind::Parse),
+    ];
+    for (name, kind) in expected {
+        assert_eq!(last_failure(&statuses, name), Some(kind), "{}", name);
+        assert_eq!(
+            metrics.health_failures_count(name, kind.as_str()),
+            1,
+            "{}",
+            name
+        );
+    }
+    assert_eq!(last_failure(&statuses, "fast-healthy"), None);
+    for kind in ["connection", "timeout", "http_status", "parse"] {
+        assert_eq!(metrics.health_failures_count("fast-healthy", kind), 0);
+    }
+    // A failure is counted under its own kind only
+    assert_eq!(metrics.health_failures_count("fast-500", "connection"), 0);
+}
+
+#[tokio::test]
+async fn test_slow_endpoint_is_recorded_as_timeout() {
+    // The health check timeout is 5s
+    let slow = probe_server(200, Duration::from_secs(7)).await;
+    let slow_url = format!("{}/v1", slow.uri());
+
+    let config = Arc::new(create_config(&[("fast-slow", &slow_url)]));
+    let metrics = Arc::new(Metrics::new().expect("should create metrics"));
+    let checker = HealthChecker::new_with_metrics(config, metrics.clone());
+
+    checker.warmup().await;
+
+    assert_eq!(
+        last_failure(&checker.get_all_statuses().await, "fast-slow"),
+        Some(HealthFailureKind::Timeout)
+    );
+    assert_eq!(metrics.health_failures_count("fast-slow", "timeout"), 1);
+}
+
+#[tokio::test]
+async fn test_health_response_lists_failing_endpoints() {
+    let erroring = probe_server(503, Duration::ZERO).await;
+    let erroring_url = format!("{}/v1", erroring.uri());
+    let closed_url = closed_port_url().await;
+
+    let config = create_config(&[("fast-503", &erroring_url), ("fast-closed", &closed_url)]);
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+    state.selector().health_checker().warmup().await;
+
+    let (_, Json(response)) = health::handler(State(state.clone())).await;
+    let json = serde_json::to_value(&response).expect("should serialize");
+
+    let failures = json["endpoint_failures"]
+        .as_array()
+        .expect("endpoint_failures should be an array");
+    // balanced-1 and deep-1 share fast-503's URL, so they fail the same way
+    let by_name = |name: &str| {
+        failures
+            .iter()
+            .find(|f| f["endpoint"] == name)
+            .unwrap_or_else(|| panic!("{} should be listed: {}", name, json))
+    };
+    assert_eq!(by_name("fast-503")["kind"], "http_status");
+    assert_eq!(by_name("fast-503")["status"], 503);
+    assert_eq!(by_name("fast-503")["consecutive_failures"], 1);
+    assert_eq!(by_name("fast-closed")["kind"], "connection");
+    assert!(by_name("fast-closed").get("status").is_none());
+
+    assert_eq!(
+        state
+            .metrics()
+            .health_failures_count("fast-closed", "connection"),
+        1
+    );
+}
@@ -0,0 +1,113 @@
+//! Integration tests for the per-endpoint `health_path` probe
+//!
+//! An endpoint with `health_path` is probed with `GET {base_url}{health_path}`
+//! instead of `HEAD {base_url}/models`, and any 2xx there counts as healthy.
+//! The mock servers here don't answer `/models` at all, so a probe that fell
+//! back to the default path would fail with a 404.
+
+use octoroute::config::Config;
+use octoroute::metrics::Metrics;
+use octoroute::models::{EndpointHealth, HealthChecker, HealthFailureKind};
+use std::sync::Arc;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn create_config(fast_url: &str, fast_health_path: &str, other_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+
+[[models.fast]]
+name = "fast-1"
+base_url = "{fast_url}"
+max_tokens = 2048
+health_path = "{fast_health_path}"
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "{other_url}"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "{other_url}"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+/// Mock server answering `GET /v1/health` with `status`
+async fn health_server(status: u16) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/health"))
+        .respond_with(ResponseTemplate::new(status))
+        .mount(&server)
+        .await;
+    server
+}
+
+fn last_failure(statuses: &[EndpointHealth], name: &str) -> Option<HealthFailureKind> {
+    statuses
+        .iter()
+        .find(|h| h.name() == name)
+        .and_then(|h| h.last_failure())
+}
+
+/// Probe every endpoint three times (the unhealthy threshold) and return
+/// fast-1's health along with every endpoint's status
+async fn probe_fast(status: u16) -> (EndpointHealth, Vec<EndpointHealth>) {
+    let fast = health_server(status).await;
+    let other = health_server(200).await;
+    let config = create_config(
+        &format!("{}/v1", fast.uri()),
+        "/health",
+        &format!("{}/v1", other.uri()),
+    );
+    let metrics = Arc::new(Metrics::new().expect("should create metrics"));
+    let checker = HealthChecker::new_with_metrics(Arc::new(config), metrics);
+
+    for _ in 0..3 {
+        checker.warmup().await;
+    }
+    let statuses = checker.get_all_statuses().await;
+    let fast = statuses
+        .iter()
+        .find(|h| h.name() == "fast-1")
+        .expect("fast-1 should be tracked")
+        .clone();
+    (fast, statuses)
+}
+
+#[tokio::test]
+async fn test_200_on_health_path_marks_healthy() {
+    let (fast, _) = probe_fast(200).await;
+    assert!(fast.is_healthy());
+    assert_eq!(fast.last_failure(), None);
+}
+
+#[tokio::test]
+async fn test_500_on_health_path_marks_unhealthy() {
+    let (fast, _) = probe_fast(500).await;
+    assert!(!fast.is_healthy());
+    assert_eq!(
+        fast.last_failure(),
+        Some(HealthFailureKind::HttpStatus(500))
+    );
+}
+
+#[tokio::test]
+async fn test_endpoints_without_health_path_probe_models() {
+    // The other endpoints only serve /v1/health, so their default probe 404s
+    let (_, statuses) = probe_fast(200).await;
+    assert_eq!(
+        last_failure(&statuses, "balanced-1"),
+        Some(HealthFailureKind::HttpStatus(404))
+    );
+}
@@ -0,0 +1,113 @@
+//! Integration tests for the health probe timeouts (`[health]` section)
+//!
+//! The endpoints here point at a listener that accepts connections but never
+//! answers, so every probe hangs until `probe_read_timeout_ms` cuts it off.
+//! Three failed probes (the unhealthy threshold) must take roughly three read
+//! timeouts, not the five seconds per probe the checker used to wait.
+
+use octoroute::config::Config;
+use octoroute::metrics::Metrics;
+use octoroute::models::{HealthChecker, HealthFailureKind};
+use octoroute::shared::http_client::build_pooled_client;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::net::TcpListener;
+
+const READ_TIMEOUT_MS: u64 = 200;
+
+fn create_config(url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+
+[[models.fast]]
+name = "fast-1"
+base_url = "{url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "{url}"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "{url}"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+
+[health]
+probe_connect_timeout_ms = 500
+probe_read_timeout_ms = {READ_TIMEOUT_MS}
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+/// Listener that accepts every connection and never writes a byte
+async fn start_silent_listener() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let mut held = Vec::new();
+        while let Ok((socket, _)) = listener.accept().await {
+            held.push(socket);
+        }
+    });
+    format!("http://{}/v1", addr)
+}
+
+/// Probe three times and check fast-1 is unhealthy from timeouts, in time
+async fn assert_marked_unhealthy_within_read_timeout(checker: HealthChecker) {
+    let start = Instant::now();
+    for _ in 0..3 {
+        checker.warmup().await;
+    }
+    let elapsed = start.elapsed();
+
+    let fast = checker
+        .get_all_statuses()
+        .await
+        .into_iter()
+        .find(|h| h.name() == "fast-1")
+        .expect("fast-1 should be tracked");
+    assert!(!fast.is_healthy());
+    assert_eq!(fast.last_failure(), Some(HealthFailureKind::Timeout));
+    // Three probes of READ_TIMEOUT_MS each, with generous slack for slow CI
+    assert!(
+        elapsed < Duration::from_millis(READ_TIMEOUT_MS * 3 + 2000),
+        "probes took {:?}",
+        elapsed
+    );
+}
+
+#[tokio::test]
+async fn test_silent_endpoint_marked_unhealthy_within_read_timeout() {
+    let url = start_silent_listener().await;
+    let config = Arc::new(create_config(&url));
+    let metrics = Arc::new(Metrics::new().expect("should create metrics"));
+
+    assert_marked_unhealthy_within_read_timeout(HealthChecker::new_with_metrics(config, metrics))
+        .await;
+}
+
+#[tokio::test]
+async fn test_read_timeout_applies_with_shared_client() {
+    let url = start_silent_listener().await;
+    let config = Arc::new(create_config(&url));
+    let metrics = Arc::new(Metrics::new().expect("should create metrics"));
+    let client = build_pooled_client(
+        &config.server.http_pool,
+        Duration::from_millis(config.health.probe_connect_timeout_ms),
+    )
+    .expect("should build pooled client");
+
+    assert_marked_unhealthy_within_read_timeout(
+        HealthChecker::new_with_metrics(config, metrics).with_http_client(client),
+    )
+    .await;
+}
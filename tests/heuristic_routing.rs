@@ -0,0 +1,127 @@
+//! Integration tests for `strategy = "heuristic"`
+//!
+//! The heuristic router classifies requests locally, so `model: "auto"`
+//! requests are routed without any router query: a short chat goes to the
+//! fast tier and a long analysis request to the deep tier.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::sync::Arc;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+fn create_config(fast_url: &str, balanced_url: &str, deep_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 8080
+
+[[models.fast]]
+name = "fast-model"
+base_url = "{fast_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-model"
+base_url = "{balanced_url}"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-model"
+base_url = "{deep_url}"
+max_tokens = 8192
+
+[routing]
+strategy = "heuristic"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_sse_response() -> String {
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{"content":"Hello"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#,
+        "data: [DONE]",
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+async fn start_backend(expected_requests: u64) -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_sse_response())
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .expect(expected_requests)
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+fn create_test_app(config: Config) -> Router {
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+    Router::new()
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware))
+}
+
+fn auto_request(content: &str) -> Request<Body> {
+    let body = serde_json::json!({
+        "model": "auto",
+        "messages": [{"role": "user", "content": content}]
+    });
+    Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_short_chat_is_served_by_fast_tier() {
+    let fast = start_backend(1).await;
+    let balanced = start_backend(0).await;
+    let deep = start_backend(0).await;
+    let app = create_test_app(create_config(&fast.uri(), &balanced.uri(), &deep.uri()));
+
+    let response = app.oneshot(auto_request("hi there!")).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_long_analysis_is_served_by_deep_tier() {
+    let fast = start_backend(0).await;
+    let balanced = start_backend(0).await;
+    let deep = start_backend(1).await;
+    let app = create_test_app(create_config(&fast.uri(), &balanced.uri(), &deep.uri()));
+
+    let prompt = format!(
+        "Give a comprehensive, step-by-step analysis of this incident report. {}",
+        "The primary database failed over twice during peak traffic. ".repeat(40)
+    );
+    let response = app.oneshot(auto_request(&prompt)).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+}
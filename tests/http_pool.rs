@@ -0,0 +1,150 @@
+//! Integration tests for `[server].http_pool`
+//!
+//! A bare TCP server counts how many connections Octoroute opens while probing
+//! endpoints. With pooling, repeated probes reuse keep-alive connections; with
+//! `max_idle_per_host = 0`, every probe pays for a new connection.
+
+use octoroute::config::Config;
+use octoroute::handlers::AppState;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+
+const PROBE_ROUNDS: usize = 3;
+const ENDPOINTS: usize = 3;
+
+/// HTTP/1.1 server answering every request with an empty 200, keeping
+/// connections alive; returns its base URL and the accepted-connection count
+async fn start_counting_server() -> (String, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let connections = Arc::new(AtomicUsize::new(0));
+
+    let accepted = connections.clone();
+    tokio::spawn(async move {
+        loop {
+            let Ok((stream, _)) = listener.accept().await else {
+                return;
+            };
+            accepted.fetch_add(1, Ordering::SeqCst);
+            tokio::spawn(async move {
+                let (read, mut write) = stream.into_split();
+                let mut lines = BufReader::new(read).lines();
+                // Requests are bodiless HEADs: each ends at the first blank line
+                while let Ok(Some(line)) = lines.next_line().await {
+                    if line.is_empty()
+                        && write
+                            .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n")
+                            .await
+                            .is_err()
+                    {
+                        return;
+                    }
+                }
+            });
+        }
+    });
+
+    (format!("http://{}/v1", addr), connections)
+}
+
+fn create_config(base_url: &str, max_idle_per_host: usize) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+
+[server.http_pool]
+max_idle_per_host = {max_idle_per_host}
+
+[[models.fast]]
+name = "fast-1"
+base_url = "{base_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "{base_url}"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "{base_url}"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+/// Probe every endpoint several times and return how many connections were opened
+async fn connections_for_repeated_probes(max_idle_per_host: usize) -> usize {
+    let (base_url, connections) = start_counting_server().await;
+    let state = AppState::new(Arc::new(create_config(&base_url, max_idle_per_host)))
+        .expect("AppState::new should succeed");
+
+    for _ in 0..PROBE_ROUNDS {
+        let report = state.selector().health_checker().warmup().await;
+        assert_eq!(
+            report.reachable.len(),
+            ENDPOINTS,
+            "all probes should succeed"
+        );
+    }
+
+    connections.load(Ordering::SeqCst)
+}
+
+#[tokio::test]
+async fn test_pooled_client_reuses_connections_across_probes() {
+    let pooled = connections_for_repeated_probes(32).await;
+
+    // The first round probes concurrently; later rounds reuse those connections
+    assert!(
+        (1..=ENDPOINTS).contains(&pooled),
+        "expected at most {} connections with pooling, got {}",
+        ENDPOINTS,
+        pooled
+    );
+}
+
+#[tokio::test]
+async fn test_disabled_pool_opens_connection_per_probe() {
+    let unpooled = connections_for_repeated_probes(0).await;
+    let pooled = connections_for_repeated_probes(32).await;
+
+    assert_eq!(unpooled, PROBE_ROUNDS * ENDPOINTS);
+    assert!(
+        pooled < unpooled,
+        "pooling should open fewer connections ({} vs {})",
+        pooled,
+        unpooled
+    );
+}
+
+#[tokio::test]
+async fn test_app_state_shares_one_pool() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<AppState>();
+
+    let (base_url, connections) = start_counting_server().await;
+    let state = AppState::new(Arc::new(create_config(&base_url, 32)))
+        .expect("AppState::new should succeed");
+
+    // Clones of the state (one per request in axum) share the same client
+    for state in [state.clone(), state.clone()] {
+        let response = state
+            .http_client()
+            .head(format!("{}/models", base_url))
+            .send()
+            .await
+            .expect("request should succeed");
+        assert!(response.status().is_success());
+    }
+
+    assert_eq!(connections.load(Ordering::SeqCst), 1);
+}
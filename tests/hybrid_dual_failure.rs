@@ -65,7 +65,7 @@ async fn test_chat_endpoint_hybrid_fallback_all_balanced_unhealthy() {
     // 5. LLM router fails (cannot select from balanced tier)
     //
     // Verifies:
-    // - HTTP 500 Internal Server Error returned
+    // - HTTP 503 Service Unavailable with Retry-After returned (endpoints may recover)
     // - Error message mentions balanced tier or routing failure
     // - Error is informative for debugging
 
@@ -105,11 +105,15 @@ async fn test_chat_endpoint_hybrid_fallback_all_balanced_unhealthy() {
 
     let response = app.oneshot(request).await.unwrap();
 
-    // Verify HTTP 500 (Internal Server Error)
+    // Unhealthy balanced endpoints are transient exhaustion: 503 with a retry hint
     assert_eq!(
         response.status(),
-        StatusCode::INTERNAL_SERVER_ERROR,
-        "Should return 500 when both rule and LLM routing fail"
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Should return 503 when both rule and LLM routing fail on unhealthy endpoints"
+    );
+    assert!(
+        response.headers().contains_key("retry-after"),
+        "Transient exhaustion should include a Retry-After header"
     );
 
     // Verify error message is informative
@@ -76,7 +76,7 @@ async fn test_hybrid_router_propagates_original_llm_error() {
     // Verify hybrid router propagates original LLM routing errors without wrapping
     //
     // When LLM routing fails, hybrid router should propagate the original error
-    // (e.g., RoutingFailed) to preserve type information for retry logic.
+    // (e.g., EndpointsUnavailable) to preserve type information for retry logic.
     // Context is logged but not wrapped in the error type.
 
     let config = test_config();
@@ -101,20 +101,20 @@ async fn test_hybrid_router_propagates_original_llm_error() {
             .expect("mark_failure should succeed");
     }
 
-    // Attempt routing - should fail with original RoutingFailed error
+    // Attempt routing - should fail with original EndpointsUnavailable error
     let result = router.route("Test prompt", &meta).await;
     assert!(result.is_err(), "Should fail when LLM routing fails");
 
     let err = result.unwrap_err();
 
-    // Verify error is the original RoutingFailed, not wrapped in HybridRoutingFailed
+    // Verify error is the original EndpointsUnavailable, not wrapped in HybridRoutingFailed
     match err {
-        AppError::RoutingFailed(_) => {
+        AppError::EndpointsUnavailable { .. } => {
             // Success - original error type is preserved
             // This allows retry logic to determine if error is retryable
         }
         other => panic!(
-            "Expected RoutingFailed variant (original error propagated), got: {:?}",
+            "Expected EndpointsUnavailable variant (original error propagated), got: {:?}",
             other
         ),
     }
@@ -178,10 +178,10 @@ async fn test_hybrid_router_error_provides_meaningful_context() {
         err_string
     );
 
-    // Should be the original RoutingFailed error
+    // Should be the original EndpointsUnavailable error
     assert!(
-        matches!(err, AppError::RoutingFailed(_)),
-        "Should propagate original RoutingFailed error"
+        matches!(err, AppError::EndpointsUnavailable { .. }),
+        "Should propagate original EndpointsUnavailable error"
     );
 }
 
@@ -217,13 +217,13 @@ async fn test_propagated_error_preserves_source_chain() {
 
     let err = result.unwrap_err();
 
-    // Verify error is the original RoutingFailed
+    // Verify error is the original EndpointsUnavailable
     assert!(
-        matches!(err, AppError::RoutingFailed(_)),
-        "Should be original RoutingFailed error"
+        matches!(err, AppError::EndpointsUnavailable { .. }),
+        "Should be original EndpointsUnavailable error"
     );
 
-    // Verify error chain is accessible (RoutingFailed may or may not have a source,
+    // Verify error chain is accessible (EndpointsUnavailable may or may not have a source,
     // but the error should be usable for debugging)
     use std::error::Error;
     let err_string = format!("{}", err);
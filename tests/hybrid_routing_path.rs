@@ -0,0 +1,158 @@
+//! Integration tests for the `x-octoroute-routing-path` response header
+//!
+//! Under the hybrid strategy a request is answered either by the rule fast
+//! path or, when no rule matches, by the LLM router. The header names the
+//! path actually taken, on `/chat` and on auto-routed `/v1/chat/completions`.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::handlers::X_OCTOROUTE_ROUTING_PATH;
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::sync::Arc;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+/// Every tier, including the router tier, points at the same mock
+fn create_hybrid_config(mock_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+request_timeout_seconds = 30
+
+[[models.fast]]
+name = "fast-1"
+base_url = "{mock_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "{mock_url}"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "{mock_url}"
+max_tokens = 8192
+
+[routing]
+strategy = "hybrid"
+router_tier = "fast"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+/// SSE answer that the LLM router reads as a Fast decision
+fn create_sse_response() -> String {
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"fast-1","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"fast-1","choices":[{"index":0,"delta":{"content":"FAST"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"fast-1","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#,
+        "data: [DONE]",
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+/// Send `body` to `uri` and return the routing path header, if any
+async fn routing_path(uri: &str, body: &str) -> Option<String> {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_sse_response())
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let state = AppState::new(Arc::new(create_hybrid_config(&mock_server.uri())))
+        .expect("AppState::new should succeed");
+    let app = Router::new()
+        .route("/chat", post(octoroute::handlers::chat::handler))
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware));
+
+    let request = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    response
+        .headers()
+        .get(X_OCTOROUTE_ROUTING_PATH)
+        .map(|value| value.to_str().unwrap().to_string())
+}
+
+#[tokio::test]
+async fn test_chat_rule_match_reports_rule() {
+    // Casual chat matches the Fast rule
+    let path = routing_path(
+        "/chat",
+        r#"{"message": "Hi there", "task_type": "casual_chat"}"#,
+    )
+    .await;
+    assert_eq!(path.as_deref(), Some("rule"));
+}
+
+#[tokio::test]
+async fn test_chat_escalation_reports_llm() {
+    // A short question matches no rule, so the LLM router decides
+    let path = routing_path("/chat", r#"{"message": "What is Rust?"}"#).await;
+    assert_eq!(path.as_deref(), Some("llm"));
+}
+
+#[tokio::test]
+async fn test_completions_auto_reports_concrete_path() {
+    let rule = routing_path(
+        "/v1/chat/completions",
+        r#"{"model": "auto", "messages": [{"role": "user", "content": "Hello there"}]}"#,
+    )
+    .await;
+    assert_eq!(rule.as_deref(), Some("rule"));
+
+    let llm = routing_path(
+        "/v1/chat/completions",
+        r#"{"model": "auto", "messages": [{"role": "user", "content": "What is Rust?"}]}"#,
+    )
+    .await;
+    assert_eq!(llm.as_deref(), Some("llm"));
+}
+
+#[tokio::test]
+async fn test_streaming_auto_reports_concrete_path() {
+    let path = routing_path(
+        "/v1/chat/completions",
+        r#"{"model": "auto", "messages": [{"role": "user", "content": "What is Rust?"}], "stream": true}"#,
+    )
+    .await;
+    assert_eq!(path.as_deref(), Some("llm"));
+}
+
+#[tokio::test]
+async fn test_explicit_tier_has_no_routing_path() {
+    let path = routing_path(
+        "/v1/chat/completions",
+        r#"{"model": "deep", "messages": [{"role": "user", "content": "Hello"}]}"#,
+    )
+    .await;
+    assert_eq!(path, None);
+}
@@ -0,0 +1,309 @@
+//! Integration tests for `Idempotency-Key` replay on non-streaming completions
+//!
+//! The first request with a given key runs normally and its response is cached;
+//! repeats with the same key are served from the cache without a backend call.
+//! Entries are scoped to the request's `user`, and a key reused with a different
+//! body is rejected with 422.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::handlers::openai::completions::IDEMPOTENCY_KEY_HEADER;
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::sync::Arc;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+fn create_test_config_with_mock(mock_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 8080
+request_timeout_seconds = 30
+
+[[models.fast]]
+name = "test-fast-model"
+base_url = "{mock_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "test-balanced-model"
+base_url = "http://localhost:9998/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "test-deep-model"
+base_url = "http://localhost:9997/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_sse_response() -> String {
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{"content":"Hello"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#,
+        "data: [DONE]",
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+async fn start_mock_backend() -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_sse_response())
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+fn create_test_app(config: Config) -> Router {
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+    Router::new()
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware))
+}
+
+fn completion_request(idempotency_key: Option<&str>, stream: bool) -> Request<Body> {
+    let mut builder = Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json");
+    if let Some(key) = idempotency_key {
+        builder = builder.header(IDEMPOTENCY_KEY_HEADER, key);
+    }
+    builder
+        .body(Body::from(format!(
+            r#"{{"model": "fast", "messages": [{{"role": "user", "content": "Hello"}}], "stream": {}}}"#,
+            stream
+        )))
+        .unwrap()
+}
+
+/// Non-streaming request with `key`, asking `content`, optionally as `user`
+fn keyed_request(key: &str, content: &str, user: Option<&str>) -> Request<Body> {
+    let user = user.map_or(String::new(), |user| format!(r#", "user": "{user}""#));
+    Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .header(IDEMPOTENCY_KEY_HEADER, key)
+        .body(Body::from(format!(
+            r#"{{"model": "fast", "messages": [{{"role": "user", "content": "{content}"}}]{user}}}"#
+        )))
+        .unwrap()
+}
+
+async fn backend_call_count(mock_server: &MockServer) -> usize {
+    mock_server
+        .received_requests()
+        .await
+        .expect("request recording should be enabled")
+        .len()
+}
+
+async fn body_json(response: axum::response::Response) -> serde_json::Value {
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&body).expect("response should be JSON")
+}
+
+#[tokio::test]
+async fn test_same_idempotency_key_returns_cached_response() {
+    let mock_server = start_mock_backend().await;
+    let app = create_test_app(create_test_config_with_mock(&mock_server.uri()));
+
+    let first = app
+        .clone()
+        .oneshot(completion_request(Some("key-1"), false))
+        .await
+        .unwrap();
+    assert_eq!(first.status(), StatusCode::OK);
+    let first_body = body_json(first).await;
+
+    let second = app
+        .oneshot(completion_request(Some("key-1"), false))
+        .await
+        .unwrap();
+    assert_eq!(second.status(), StatusCode::OK);
+    let second_body = body_json(second).await;
+
+    assert_eq!(
+        first_body, second_body,
+        "Replayed response should be identical, including id and created"
+    );
+    assert_eq!(
+        backend_call_count(&mock_server).await,
+        1,
+        "Only the first request should reach the backend"
+    );
+}
+
+#[tokio::test]
+async fn test_different_idempotency_keys_query_independently() {
+    let mock_server = start_mock_backend().await;
+    let app = create_test_app(create_test_config_with_mock(&mock_server.uri()));
+
+    let first = app
+        .clone()
+        .oneshot(completion_request(Some("key-a"), false))
+        .await
+        .unwrap();
+    let first_body = body_json(first).await;
+
+    let second = app
+        .oneshot(completion_request(Some("key-b"), false))
+        .await
+        .unwrap();
+    let second_body = body_json(second).await;
+
+    assert_ne!(
+        first_body["id"], second_body["id"],
+        "Different keys should produce distinct completions"
+    );
+    assert_eq!(backend_call_count(&mock_server).await, 2);
+}
+
+#[tokio::test]
+async fn test_requests_without_idempotency_key_are_not_cached() {
+    let mock_server = start_mock_backend().await;
+    let app = create_test_app(create_test_config_with_mock(&mock_server.uri()));
+
+    for _ in 0..2 {
+        let response = app
+            .clone()
+            .oneshot(completion_request(None, false))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    assert_eq!(backend_call_count(&mock_server).await, 2);
+}
+
+#[tokio::test]
+async fn test_streaming_requests_ignore_idempotency_key() {
+    let mock_server = start_mock_backend().await;
+    let app = create_test_app(create_test_config_with_mock(&mock_server.uri()));
+
+    for _ in 0..2 {
+        let response = app
+            .clone()
+            .oneshot(completion_request(Some("stream-key"), true))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        // Drain the stream so the upstream request is issued
+        let _ = axum::body::to_bytes(response.into_body(), usize::MAX).await;
+    }
+
+    assert_eq!(
+        backend_call_count(&mock_server).await,
+        2,
+        "Streaming requests should always reach the backend"
+    );
+}
+
+#[tokio::test]
+async fn test_empty_idempotency_key_is_rejected() {
+    let mock_server = start_mock_backend().await;
+    let app = create_test_app(create_test_config_with_mock(&mock_server.uri()));
+
+    let response = app
+        .oneshot(completion_request(Some(" "), false))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(backend_call_count(&mock_server).await, 0);
+}
+
+#[tokio::test]
+async fn test_reused_key_with_different_body_is_rejected() {
+    let mock_server = start_mock_backend().await;
+    let app = create_test_app(create_test_config_with_mock(&mock_server.uri()));
+
+    let first = app
+        .clone()
+        .oneshot(keyed_request("key-1", "Hello", None))
+        .await
+        .unwrap();
+    assert_eq!(first.status(), StatusCode::OK);
+
+    let second = app
+        .oneshot(keyed_request("key-1", "Something else", None))
+        .await
+        .unwrap();
+    assert_eq!(second.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let body = body_json(second).await;
+    assert!(
+        body["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("different request body"),
+        "{}",
+        body
+    );
+    assert_eq!(
+        backend_call_count(&mock_server).await,
+        1,
+        "The rejected request should not reach the backend"
+    );
+}
+
+#[tokio::test]
+async fn test_same_key_is_scoped_per_user() {
+    let mock_server = start_mock_backend().await;
+    let app = create_test_app(create_test_config_with_mock(&mock_server.uri()));
+
+    let alice = app
+        .clone()
+        .oneshot(keyed_request("shared-key", "Hello", Some("alice")))
+        .await
+        .unwrap();
+    assert_eq!(alice.status(), StatusCode::OK);
+    let alice_body = body_json(alice).await;
+
+    let bob = app
+        .clone()
+        .oneshot(keyed_request("shared-key", "Hello", Some("bob")))
+        .await
+        .unwrap();
+    assert_eq!(bob.status(), StatusCode::OK);
+    let bob_body = body_json(bob).await;
+
+    assert_ne!(alice_body["id"], bob_body["id"]);
+    assert_eq!(backend_call_count(&mock_server).await, 2);
+
+    // Each user still gets their own response replayed
+    let alice_again = app
+        .oneshot(keyed_request("shared-key", "Hello", Some("alice")))
+        .await
+        .unwrap();
+    assert_eq!(body_json(alice_again).await, alice_body);
+    assert_eq!(backend_call_count(&mock_server).await, 2);
+}
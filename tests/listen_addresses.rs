@@ -0,0 +1,138 @@
+//! Integration tests for multiple public listen addresses
+//!
+//! `server.dual_stack` pairs `0.0.0.0` with `::` on the same port, which only
+//! works because [`server::bind_listener`] binds IPv6 sockets v6-only. Hosts
+//! without IPv6 skip the tests that need it.
+
+use axum::{Router, routing::get};
+use octoroute::{config::Config, server};
+use std::net::SocketAddr;
+
+fn create_config(host: &str, port: u16, dual_stack: bool) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "{host}"
+port = {port}
+dual_stack = {dual_stack}
+
+[[models.fast]]
+name = "fast-1"
+base_url = "http://localhost:11434/v1"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "http://localhost:1234/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:8080/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+/// Serve a one-route app on `listener`
+fn serve(listener: tokio::net::TcpListener) {
+    let app = Router::new().route("/livez", get(|| async { "ok" }));
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+}
+
+async fn livez(addr: SocketAddr) -> String {
+    reqwest::get(format!("http://{addr}/livez"))
+        .await
+        .expect("listener should accept connections")
+        .text()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_dual_stack_binds_ipv4_and_ipv6_on_one_port() {
+    // Take a free port from the IPv4 wildcard listener, then bind its pair
+    let v4 = server::bind_listener("0.0.0.0:0".parse().unwrap()).expect("IPv4 bind");
+    let port = v4.local_addr().unwrap().port();
+    let addrs = create_config("0.0.0.0", port, true)
+        .server
+        .listen_addrs()
+        .unwrap();
+    assert_eq!(
+        addrs,
+        vec![
+            SocketAddr::from(([0, 0, 0, 0], port)),
+            SocketAddr::from(([0u16; 8], port)),
+        ]
+    );
+
+    let v6 = match server::bind_listener(addrs[1]) {
+        Ok(listener) => listener,
+        Err(e) if e.kind() == std::io::ErrorKind::AddrInUse => {
+            panic!("[::]:{port} must not clash with 0.0.0.0:{port}: {e}")
+        }
+        // No IPv6 on this host
+        Err(_) => return,
+    };
+    serve(v4);
+    serve(v6);
+
+    assert_eq!(livez(SocketAddr::from(([127, 0, 0, 1], port))).await, "ok");
+    assert_eq!(
+        livez(SocketAddr::from(([0, 0, 0, 0, 0, 0, 0, 1], port))).await,
+        "ok"
+    );
+}
+
+#[test]
+fn test_single_host_yields_one_address() {
+    let addrs = create_config("127.0.0.1", 3000, false)
+        .server
+        .listen_addrs()
+        .unwrap();
+
+    assert_eq!(addrs, vec![SocketAddr::from(([127, 0, 0, 1], 3000))]);
+}
+
+#[test]
+fn test_invalid_host_errors_clearly() {
+    let toml = r#"
+[server]
+host = "not-an-ip"
+port = 3000
+
+[[models.fast]]
+name = "fast-1"
+base_url = "http://localhost:11434/v1"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "http://localhost:1234/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:8080/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#;
+    let err = toml
+        .parse::<Config>()
+        .expect_err("a host name is not a listen address");
+
+    assert!(
+        err.to_string()
+            .contains("server.host 'not-an-ip' is not a valid IP address"),
+        "{}",
+        err
+    );
+}
@@ -0,0 +1,149 @@
+//! Integration tests for `routing.llm_failure_fallback`
+//!
+//! With `strategy = "llm"`, a router model that answers with something
+//! unparseable is a systemic failure. By default the request fails; with a
+//! fallback configured, routing continues via the rule router or the default
+//! tier and the decision carries a warning.
+
+use octoroute::config::Config;
+use octoroute::error::AppError;
+use octoroute::handlers::AppState;
+use octoroute::router::{Importance, RouteMetadata, RoutingStrategy, TargetModel, TaskType};
+use std::sync::Arc;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+fn create_config(router_url: &str, routing_extra: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+
+[[models.fast]]
+name = "fast-router"
+base_url = "{router_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "http://localhost:9998/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:9997/v1"
+max_tokens = 8192
+priority = 2
+
+[routing]
+strategy = "llm"
+router_tier = "fast"
+{routing_extra}
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+/// SSE stream whose content names no tier
+fn create_unparseable_sse_response() -> String {
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"fast-router","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"fast-router","choices":[{"index":0,"delta":{"content":"PURPLE"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"fast-router","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#,
+        "data: [DONE]",
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+/// Router endpoint answering every query with an unparseable decision
+///
+/// Systemic failures are not retried, so exactly one router query is expected.
+async fn start_unparseable_router() -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_unparseable_sse_response())
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+/// Short code request: the rule router sends it to Balanced
+fn code_metadata() -> RouteMetadata {
+    RouteMetadata {
+        token_estimate: 20,
+        importance: Importance::Normal,
+        task_type: TaskType::Code,
+    }
+}
+
+#[tokio::test]
+async fn test_unparseable_router_response_fails_by_default() {
+    let mock_server = start_unparseable_router().await;
+    let state = AppState::new(Arc::new(create_config(&mock_server.uri(), "")))
+        .expect("AppState::new should succeed");
+
+    let result = state
+        .router()
+        .route("Fix this function", &code_metadata(), state.selector())
+        .await;
+
+    assert!(
+        matches!(result, Err(AppError::LlmRouting(_))),
+        "without a fallback the router error should surface, got: {:?}",
+        result
+    );
+}
+
+#[tokio::test]
+async fn test_rule_fallback_routes_after_unparseable_response() {
+    let mock_server = start_unparseable_router().await;
+    let config = create_config(&mock_server.uri(), r#"llm_failure_fallback = "rule""#);
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+
+    let decision = state
+        .router()
+        .route("Fix this function", &code_metadata(), state.selector())
+        .await
+        .expect("rule fallback should produce a decision");
+
+    assert_eq!(decision.target(), TargetModel::Balanced);
+    assert_eq!(decision.strategy(), RoutingStrategy::Rule);
+    assert!(
+        decision
+            .warnings()
+            .iter()
+            .any(|w| w.contains("LLM router failed") && w.contains("'rule' fallback")),
+        "decision should warn that the LLM was bypassed, got: {:?}",
+        decision.warnings()
+    );
+}
+
+#[tokio::test]
+async fn test_default_tier_fallback_ignores_rules() {
+    let mock_server = start_unparseable_router().await;
+    let config = create_config(
+        &mock_server.uri(),
+        r#"llm_failure_fallback = "default_tier""#,
+    );
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+
+    let decision = state
+        .router()
+        .route("Fix this function", &code_metadata(), state.selector())
+        .await
+        .expect("default tier fallback should produce a decision");
+
+    // Deep holds the highest-priority endpoint, so it is the default tier
+    assert_eq!(decision.target(), TargetModel::Deep);
+    assert!(!decision.warnings().is_empty());
+}
@@ -0,0 +1,242 @@
+//! Integration tests for `logprobs` / `top_logprobs`
+//!
+//! Both are validated (`top_logprobs` 0 to 20, and only with `logprobs: true`)
+//! and forwarded to the backend. Log-probabilities the backend returns are
+//! passed through on each choice, or on each chunk when streaming; when it
+//! returns none, the response carries a warning instead of invented values.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::sync::Arc;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+fn create_config(backend_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+request_timeout_seconds = 30
+
+[[models.fast]]
+name = "fast-1"
+base_url = "{backend_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "http://localhost:9998/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:9997/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+/// Token entry of an OpenAI `logprobs.content` array
+fn token(text: &str) -> serde_json::Value {
+    serde_json::json!({"token": text, "logprob": -0.25, "bytes": null, "top_logprobs": []})
+}
+
+/// SSE body answering "Hello", with `logprobs` on each chunk when `with_logprobs`
+fn create_sse_response(with_logprobs: bool) -> String {
+    let chunks = ["Hel", "lo"].map(|text| {
+        let mut choice = serde_json::json!({"index": 0, "delta": {"content": text}, "finish_reason": null});
+        if with_logprobs {
+            choice["logprobs"] = serde_json::json!({"content": [token(text)]});
+        }
+        format!(
+            "data: {}",
+            serde_json::json!({"id": "chatcmpl-test", "object": "chat.completion.chunk", "created": 1234567890, "model": "test", "choices": [choice]})
+        )
+    });
+    chunks.join("\n\n") + "\n\ndata: [DONE]\n\n"
+}
+
+async fn start_backend(with_logprobs: bool) -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_sse_response(with_logprobs))
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+/// Send `body` and return the status and response body
+async fn send(backend: &MockServer, body: &str) -> (StatusCode, Option<String>, String) {
+    let state = AppState::new(Arc::new(create_config(&backend.uri())))
+        .expect("AppState::new should succeed");
+    let app = Router::new()
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware));
+    let request = Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let warning = response
+        .headers()
+        .get("x-octoroute-warning")
+        .map(|value| value.to_str().unwrap().to_string());
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, warning, String::from_utf8(body.to_vec()).unwrap())
+}
+
+/// JSON bodies of every request the backend received
+async fn received_bodies(backend: &MockServer) -> Vec<serde_json::Value> {
+    backend
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .map(|request| serde_json::from_slice(&request.body).unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_logprobs_are_forwarded_and_passed_through() {
+    let backend = start_backend(true).await;
+
+    let (status, warning, body) = send(
+        &backend,
+        r#"{"model": "fast", "messages": [{"role": "user", "content": "Hi"}], "logprobs": true, "top_logprobs": 3}"#,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let sent = received_bodies(&backend).await;
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0]["logprobs"], true);
+    assert_eq!(sent[0]["top_logprobs"], 3);
+
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(json["choices"][0]["message"]["content"], "Hello");
+    // The chunks' token arrays add up to the whole completion's
+    assert_eq!(
+        json["choices"][0]["logprobs"],
+        serde_json::json!({"content": [token("Hel"), token("lo")]})
+    );
+    assert!(warning.is_none(), "{:?}", warning);
+}
+
+#[tokio::test]
+async fn test_streamed_logprobs_are_passed_through_per_chunk() {
+    let backend = start_backend(true).await;
+
+    let (status, _, body) = send(
+        &backend,
+        r#"{"model": "fast", "messages": [{"role": "user", "content": "Hi"}], "stream": true, "logprobs": true}"#,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(received_bodies(&backend).await[0]["logprobs"], true);
+    let chunks: Vec<serde_json::Value> = body
+        .lines()
+        .filter_map(|line| line.strip_prefix("data: "))
+        .filter(|data| *data != "[DONE]")
+        .map(|data| serde_json::from_str(data).unwrap())
+        .collect();
+    let content_chunks: Vec<&serde_json::Value> = chunks
+        .iter()
+        .filter(|chunk| chunk["choices"][0]["delta"]["content"].is_string())
+        .collect();
+    assert_eq!(content_chunks.len(), 2, "{}", body);
+    for (chunk, text) in content_chunks.iter().zip(["Hel", "lo"]) {
+        assert_eq!(
+            chunk["choices"][0]["logprobs"],
+            serde_json::json!({"content": [token(text)]})
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_missing_logprobs_are_warned_not_fabricated() {
+    let backend = start_backend(false).await;
+
+    let (status, warning, body) = send(
+        &backend,
+        r#"{"model": "fast", "messages": [{"role": "user", "content": "Hi"}], "logprobs": true}"#,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(json["choices"][0]["message"]["content"], "Hello");
+    assert!(json["choices"][0].get("logprobs").is_none(), "{}", body);
+    let warning = warning.expect("a warning header");
+    assert!(warning.contains("did not return them"), "{}", warning);
+    assert!(
+        json["octoroute_warnings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|w| w.as_str().unwrap().contains("did not return them")),
+        "{}",
+        body
+    );
+}
+
+#[tokio::test]
+async fn test_logprobs_false_is_served() {
+    let backend = start_backend(false).await;
+
+    let (status, _, body) = send(
+        &backend,
+        r#"{"model": "fast", "messages": [{"role": "user", "content": "Hi"}], "logprobs": false}"#,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(json["choices"][0]["message"]["content"], "Hello");
+    assert!(json["choices"][0].get("logprobs").is_none(), "{}", body);
+    assert_eq!(received_bodies(&backend).await[0]["logprobs"], false);
+}
+
+#[tokio::test]
+async fn test_out_of_range_top_logprobs_rejected_before_backend() {
+    let backend = start_backend(false).await;
+
+    let (status, _, body) = send(
+        &backend,
+        r#"{"model": "fast", "messages": [{"role": "user", "content": "Hi"}], "top_logprobs": 21}"#,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(body.contains("top_logprobs"), "{}", body);
+    assert!(backend.received_requests().await.unwrap().is_empty());
+}
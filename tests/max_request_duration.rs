@@ -0,0 +1,158 @@
+//! Integration tests for the total request duration backstop
+//!
+//! `server.max_request_duration_seconds` bounds the entire request lifecycle
+//! (routing + retries) independently of per-endpoint timeouts. Streaming
+//! responses are only bounded until the stream starts.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::middleware::{request_id_middleware, request_timeout_middleware};
+use octoroute::{config::Config, handlers::AppState};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+/// Upstream timeout (30s) is far above the 1s total request bound
+fn create_config(mock_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 8080
+request_timeout_seconds = 30
+max_request_duration_seconds = 1
+
+[[models.fast]]
+name = "test-fast-model"
+base_url = "{mock_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "test-balanced-model"
+base_url = "http://localhost:9998/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "test-deep-model"
+base_url = "http://localhost:9997/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_sse_response() -> String {
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{"content":"Hello"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#,
+        "data: [DONE]",
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+async fn start_slow_backend(delay: Duration) -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_delay(delay)
+                .set_body_string(create_sse_response())
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+fn create_test_app(config: Config) -> Router {
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+    Router::new()
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state.clone())
+        .layer(middleware::from_fn_with_state(
+            state,
+            request_timeout_middleware,
+        ))
+        .layer(middleware::from_fn(request_id_middleware))
+}
+
+fn completion_request(stream: bool) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(format!(
+            r#"{{"model": "fast", "messages": [{{"role": "user", "content": "Hello"}}], "stream": {}}}"#,
+            stream
+        )))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_request_exceeding_max_duration_returns_504() {
+    let mock_server = start_slow_backend(Duration::from_secs(10)).await;
+    let app = create_test_app(create_config(&mock_server.uri()));
+
+    let start = Instant::now();
+    let response = app.oneshot(completion_request(false)).await.unwrap();
+    let elapsed = start.elapsed();
+
+    assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+    assert!(
+        elapsed < Duration::from_secs(5),
+        "Total bound (1s) should fire before the 10s backend delay and 30s upstream timeout, took {:?}",
+        elapsed
+    );
+    assert!(
+        response.headers().get("x-request-id").is_some(),
+        "Timed-out responses should still carry a request ID"
+    );
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body_str = String::from_utf8_lossy(&body);
+    assert!(
+        body_str.contains("maximum duration"),
+        "Error should explain the request duration limit, got: {}",
+        body_str
+    );
+}
+
+#[tokio::test]
+async fn test_max_duration_does_not_cut_off_started_stream() {
+    // Backend takes 2s (longer than the 1s bound) but the stream has already started
+    let mock_server = start_slow_backend(Duration::from_secs(2)).await;
+    let app = create_test_app(create_config(&mock_server.uri()));
+
+    let response = app.oneshot(completion_request(true)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body_str = String::from_utf8_lossy(&body);
+    assert!(
+        body_str.contains("Hello"),
+        "Stream should run to completion past the total bound, got: {}",
+        body_str
+    );
+}
@@ -0,0 +1,191 @@
+//! Integration tests for clamping requested `max_tokens` to the endpoint cap
+//!
+//! Requests asking for more tokens than the selected endpoint's configured
+//! `max_tokens` must be clamped before reaching the backend, with an
+//! `X-Octoroute-Warning` header explaining the adjustment.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::handlers::openai::completions::X_OCTOROUTE_WARNING;
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::sync::Arc;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+fn create_test_config_with_mock(mock_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 8080
+request_timeout_seconds = 30
+
+[[models.fast]]
+name = "test-fast-model"
+base_url = "{mock_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "test-balanced-model"
+base_url = "http://localhost:9998/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "test-deep-model"
+base_url = "http://localhost:9997/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_sse_response() -> String {
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{"content":"Hello"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#,
+        "data: [DONE]",
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+async fn start_mock_backend() -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_sse_response())
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+fn create_test_app(config: Config) -> Router {
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+    Router::new()
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware))
+}
+
+fn completion_request(model: &str, max_tokens: u32, stream: bool) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(format!(
+            r#"{{"model": "{}", "messages": [{{"role": "user", "content": "Hello"}}], "max_tokens": {}, "stream": {}}}"#,
+            model, max_tokens, stream
+        )))
+        .unwrap()
+}
+
+/// Extract the `max_tokens` value forwarded to the backend
+async fn upstream_max_tokens(mock_server: &MockServer) -> u64 {
+    let requests = mock_server
+        .received_requests()
+        .await
+        .expect("request recording should be enabled");
+    let last = requests.last().expect("backend should receive a request");
+    let body: serde_json::Value =
+        serde_json::from_slice(&last.body).expect("upstream body should be JSON");
+    body["max_tokens"]
+        .as_u64()
+        .expect("upstream request should include max_tokens")
+}
+
+#[tokio::test]
+async fn test_max_tokens_above_endpoint_cap_is_clamped() {
+    let mock_server = start_mock_backend().await;
+    let app = create_test_app(create_test_config_with_mock(&mock_server.uri()));
+
+    let response = app
+        .oneshot(completion_request("fast", 100_000, false))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let warning = response
+        .headers()
+        .get(X_OCTOROUTE_WARNING)
+        .expect("clamping should add a warning header")
+        .to_str()
+        .unwrap()
+        .to_string();
+    assert!(
+        warning.contains("clamped to 2048"),
+        "Warning should explain the clamp, got: {}",
+        warning
+    );
+    assert_eq!(upstream_max_tokens(&mock_server).await, 2048);
+}
+
+#[tokio::test]
+async fn test_max_tokens_within_endpoint_cap_passes_through() {
+    let mock_server = start_mock_backend().await;
+    let app = create_test_app(create_test_config_with_mock(&mock_server.uri()));
+
+    let response = app
+        .oneshot(completion_request("fast", 512, false))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(
+        response.headers().get(X_OCTOROUTE_WARNING).is_none(),
+        "Within-limit request should not produce a warning"
+    );
+    assert_eq!(upstream_max_tokens(&mock_server).await, 512);
+}
+
+#[tokio::test]
+async fn test_max_tokens_clamped_for_specific_model() {
+    let mock_server = start_mock_backend().await;
+    let app = create_test_app(create_test_config_with_mock(&mock_server.uri()));
+
+    let response = app
+        .oneshot(completion_request("test-fast-model", 4096, false))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().get(X_OCTOROUTE_WARNING).is_some());
+    assert_eq!(upstream_max_tokens(&mock_server).await, 2048);
+}
+
+#[tokio::test]
+async fn test_max_tokens_clamped_for_streaming() {
+    let mock_server = start_mock_backend().await;
+    let app = create_test_app(create_test_config_with_mock(&mock_server.uri()));
+
+    let response = app
+        .oneshot(completion_request("fast", 100_000, true))
+        .await
+        .unwrap();
+
+    assert!(
+        response.headers().get(X_OCTOROUTE_WARNING).is_some(),
+        "Streaming responses should carry the clamp warning header"
+    );
+    // Drain the stream so the upstream request is issued
+    let _ = axum::body::to_bytes(response.into_body(), usize::MAX).await;
+    assert_eq!(upstream_max_tokens(&mock_server).await, 2048);
+}
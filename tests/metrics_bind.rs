@@ -0,0 +1,100 @@
+//! Integration tests for the internal metrics listener (`observability.metrics_bind`)
+//!
+//! With a metrics bind configured, `/metrics` and `/health` are served only by
+//! the internal router while the public router keeps serving the API. Without
+//! one, both stay on the public router.
+
+use axum::{Router, body::Body, http::Request, http::StatusCode};
+use octoroute::{config::Config, handlers::AppState, server};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tower::ServiceExt;
+
+fn create_config(metrics_bind: Option<&str>) -> Config {
+    let observability = metrics_bind
+        .map(|bind| format!("[observability]\nmetrics_bind = \"{bind}\"\n"))
+        .unwrap_or_default();
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+request_timeout_seconds = 30
+
+[[models.fast]]
+name = "fast-1"
+base_url = "http://localhost:11434/v1"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "http://localhost:1234/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:8080/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+
+{observability}
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+/// Serve `app` on an ephemeral local port and return its address
+async fn serve(app: Router) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    addr
+}
+
+async fn get_status(addr: SocketAddr, path: &str) -> StatusCode {
+    let response = reqwest::get(format!("http://{addr}{path}"))
+        .await
+        .expect("listener should accept connections");
+    StatusCode::from_u16(response.status().as_u16()).unwrap()
+}
+
+#[tokio::test]
+async fn test_metrics_bind_serves_diagnostics_on_internal_listener_only() {
+    let state = AppState::new(Arc::new(create_config(Some("127.0.0.1:9464"))))
+        .expect("AppState::new should succeed");
+    let public = serve(server::public_app(state.clone())).await;
+    let internal = serve(server::metrics_app(state)).await;
+
+    assert_eq!(get_status(internal, "/metrics").await, StatusCode::OK);
+    assert_eq!(get_status(internal, "/health").await, StatusCode::OK);
+
+    // The public listener no longer exposes them but still serves the API
+    assert_eq!(get_status(public, "/metrics").await, StatusCode::NOT_FOUND);
+    assert_eq!(get_status(public, "/health").await, StatusCode::NOT_FOUND);
+    assert_eq!(get_status(public, "/livez").await, StatusCode::OK);
+    assert_eq!(get_status(public, "/v1/models").await, StatusCode::OK);
+
+    // API routes are not served internally
+    assert_eq!(
+        get_status(internal, "/v1/models").await,
+        StatusCode::NOT_FOUND
+    );
+}
+
+#[tokio::test]
+async fn test_without_metrics_bind_public_app_serves_metrics() {
+    let state = AppState::new(Arc::new(create_config(None))).expect("AppState::new should succeed");
+
+    for path in ["/metrics", "/health", "/v1/models"] {
+        let response = server::public_app(state.clone())
+            .oneshot(Request::builder().uri(path).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK, "{}", path);
+    }
+}
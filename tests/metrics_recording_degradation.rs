@@ -0,0 +1,142 @@
+//! Integration tests for graceful degradation when metrics recording fails
+//!
+//! Request paths record through `Metrics::try_record_*`, so a failed recording
+//! only increments `octoroute_metrics_recording_failures_total` and is logged.
+//! The completion itself must be unaffected.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::metrics::{Strategy, Tier};
+use octoroute::middleware::RequestId;
+use octoroute::router::{RoutingDecision, RoutingStrategy, TargetModel};
+use octoroute::shared::query::record_routing_metrics;
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::sync::Arc;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+fn create_config(mock_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+request_timeout_seconds = 30
+
+[[models.fast]]
+name = "fast-1"
+base_url = "{mock_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "{mock_url}"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "{mock_url}"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_sse_response(text: &str) -> String {
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#.to_string(),
+        format!(
+            r#"data: {{"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{{"index":0,"delta":{{"content":"{text}"}},"finish_reason":null}}]}}"#
+        ),
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#.to_string(),
+        "data: [DONE]".to_string(),
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+fn create_test_app(state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware))
+}
+
+#[tokio::test]
+async fn test_forced_metrics_error_does_not_affect_completion() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_sse_response("Still served"))
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+    let state = AppState::new(Arc::new(create_config(&mock_server.uri())))
+        .expect("AppState::new should succeed");
+    let metrics = state.metrics();
+
+    // A NaN routing duration is rejected by the histogram: the request counter
+    // is still recorded, the duration is counted as a failure
+    let decision = RoutingDecision::new(TargetModel::Fast, RoutingStrategy::Rule);
+    record_routing_metrics(&state, &decision, f64::NAN, RequestId::new());
+    assert_eq!(metrics.metrics_recording_failures_count(), 1);
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(
+            r#"{"model": "fast", "messages": [{"role": "user", "content": "Hello"}]}"#,
+        ))
+        .unwrap();
+    let response = create_test_app(state.clone())
+        .oneshot(request)
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["choices"][0]["message"]["content"], "Still served");
+
+    // The completion recorded normally and added no failures of its own
+    assert_eq!(metrics.metrics_recording_failures_count(), 1);
+    let output = metrics.gather().expect("should gather metrics");
+    assert!(
+        output.contains(r#"operation="record_routing_duration""#),
+        "{}",
+        output
+    );
+    assert!(output.contains("octoroute_model_invocations_total"));
+}
+
+#[test]
+fn test_try_record_never_surfaces_an_error() {
+    let metrics = octoroute::metrics::Metrics::new().expect("should create Metrics");
+
+    // Each call returns (), so there is nothing to propagate with `?`
+    metrics.try_record_routing_duration(Strategy::Llm, f64::INFINITY, None);
+    metrics.try_record_router_llm_duration(Tier::Deep, f64::NAN);
+    metrics.try_record_request(Tier::Deep, Strategy::Llm, None);
+
+    assert_eq!(metrics.metrics_recording_failures_count(), 2);
+}
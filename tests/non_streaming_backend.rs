@@ -0,0 +1,233 @@
+//! Integration tests for backends that ignore `stream: true`
+//!
+//! A backend may answer a streaming request with one `chat.completion` JSON
+//! body. The handler recognizes it by content type on that response and
+//! replays the JSON content as a single SSE chunk, followed by the finish chunk
+//! and `[DONE]`, without querying the backend again.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::sync::Arc;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+/// `server` is added to the `[server]` section
+fn create_test_config(mock_url: &str, server: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 8080
+request_timeout_seconds = 30
+{server}
+
+[[models.fast]]
+name = "test-fast-model"
+base_url = "{mock_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "test-balanced-model"
+base_url = "http://localhost:9998/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "test-deep-model"
+base_url = "http://localhost:9997/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+const JSON_COMPLETION: &str = r#"{"id":"chatcmpl-test","object":"chat.completion","created":1234567890,"model":"test-model","choices":[{"index":0,"message":{"role":"assistant","content":"Hello from a JSON backend"},"finish_reason":"stop"}],"usage":{"prompt_tokens":5,"completion_tokens":5,"total_tokens":10}}"#;
+
+async fn start_backend(body: &str, content_type: &str) -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(body)
+                .insert_header("content-type", content_type),
+        )
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+/// Stream a request to the fast tier and return the `data:` payloads sent
+async fn stream_data(mock_url: &str) -> Vec<String> {
+    stream_model_data(mock_url, "fast", "").await
+}
+
+/// Stream a request for `model`, with `server` added to the config
+async fn stream_model_data(mock_url: &str, model: &str, server: &str) -> Vec<String> {
+    let state = AppState::new(Arc::new(create_test_config(mock_url, server)))
+        .expect("AppState::new should succeed");
+    let app = Router::new()
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware));
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(
+            serde_json::json!({
+                "model": model,
+                "messages": [{"role": "user", "content": "Hello"}],
+                "stream": true
+            })
+            .to_string(),
+        ))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+
+    String::from_utf8_lossy(&bytes)
+        .lines()
+        .filter_map(|line| line.strip_prefix("data: "))
+        .map(str::to_string)
+        .collect()
+}
+
+fn chunks(data: &[String]) -> Vec<serde_json::Value> {
+    data.iter()
+        .filter(|data| *data != "[DONE]")
+        .map(|data| serde_json::from_str(data).expect("every chunk should be valid JSON"))
+        .collect()
+}
+
+fn content(chunks: &[serde_json::Value]) -> Vec<&str> {
+    chunks
+        .iter()
+        .filter_map(|chunk| chunk["choices"][0]["delta"]["content"].as_str())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_json_response_is_sent_as_single_chunk() {
+    let mock_server = start_backend(JSON_COMPLETION, "application/json").await;
+
+    let data = stream_data(&mock_server.uri()).await;
+    let chunks = chunks(&data);
+
+    assert_eq!(data.last().map(String::as_str), Some("[DONE]"));
+    assert_eq!(content(&chunks), vec!["Hello from a JSON backend"]);
+    assert_eq!(mock_server.received_requests().await.unwrap().len(), 1);
+    assert_eq!(chunks[0]["choices"][0]["delta"]["role"], "assistant");
+    assert!(
+        chunks
+            .iter()
+            .all(|chunk| chunk["object"] == "chat.completion.chunk")
+    );
+    let finish_reasons: Vec<_> = chunks
+        .iter()
+        .filter_map(|chunk| chunk["choices"][0]["finish_reason"].as_str())
+        .collect();
+    assert_eq!(finish_reasons, vec!["stop"]);
+}
+
+#[tokio::test]
+async fn test_streaming_backend_is_queried_once() {
+    let sse = [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{"content":"Hello"},"finish_reason":null}]}"#,
+        "data: [DONE]",
+    ]
+    .join("\n\n")
+        + "\n\n";
+    let mock_server = start_backend(&sse, "text/event-stream").await;
+
+    let data = stream_data(&mock_server.uri()).await;
+
+    assert_eq!(content(&chunks(&data)), vec!["Hello"]);
+    assert_eq!(mock_server.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_empty_sse_stream_stays_empty() {
+    let mock_server = start_backend("data: [DONE]\n\n", "text/event-stream").await;
+
+    let data = stream_data(&mock_server.uri()).await;
+
+    assert!(content(&chunks(&data)).is_empty(), "{:?}", data);
+    assert_eq!(data.last().map(String::as_str), Some("[DONE]"));
+}
+
+#[tokio::test]
+async fn test_json_tool_calls_are_sent_as_complete_fragments() {
+    let body = serde_json::json!({
+        "object": "chat.completion",
+        "choices": [{
+            "index": 0,
+            "message": {
+                "role": "assistant",
+                "content": null,
+                "tool_calls": [{
+                    "id": "call_1",
+                    "type": "function",
+                    "function": {"name": "get_weather", "arguments": "{\"city\":\"Paris\"}"}
+                }]
+            },
+            "finish_reason": "tool_calls"
+        }]
+    });
+    let mock_server = start_backend(&body.to_string(), "application/json").await;
+
+    let chunks = chunks(&stream_data(&mock_server.uri()).await);
+
+    let calls: Vec<_> = chunks
+        .iter()
+        .filter_map(|chunk| chunk["choices"][0]["delta"]["tool_calls"].as_array())
+        .flatten()
+        .collect();
+    assert_eq!(calls.len(), 1, "{:?}", chunks);
+    assert_eq!(calls[0]["index"], 0);
+    assert_eq!(calls[0]["id"], "call_1");
+    assert_eq!(calls[0]["function"]["name"], "get_weather");
+    assert_eq!(calls[0]["function"]["arguments"], r#"{"city":"Paris"}"#);
+    assert!(content(&chunks).is_empty());
+}
+
+#[tokio::test]
+async fn test_json_response_needs_no_extra_upstream_call() {
+    let mock_server = start_backend(JSON_COMPLETION, "application/json").await;
+
+    for model in ["fast", "test-fast-model"] {
+        let before = mock_server.received_requests().await.unwrap().len();
+
+        let data = stream_model_data(&mock_server.uri(), model, "max_upstream_calls = 1").await;
+
+        assert_eq!(
+            content(&chunks(&data)),
+            vec!["Hello from a JSON backend"],
+            "{model}"
+        );
+        assert_eq!(
+            mock_server.received_requests().await.unwrap().len() - before,
+            1,
+            "{model}: the first attempt is the only call"
+        );
+    }
+}
@@ -0,0 +1,172 @@
+//! Integration tests for the `octoroute_warnings` response extension field
+//!
+//! Non-streaming completions carry the same non-fatal warnings as the
+//! `X-Octoroute-Warning` header in an `octoroute_warnings` array, omitted when
+//! there is nothing to report.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::handlers::openai::types::ChatCompletion;
+use octoroute::middleware::RequestId;
+use octoroute::router::{RoutingDecision, RoutingStrategy, TargetModel};
+use octoroute::shared::query::{QueryConfig, execute_query_with_retry};
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::sync::Arc;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+fn create_test_config_with_mock(mock_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 8080
+request_timeout_seconds = 30
+
+[[models.fast]]
+name = "test-fast-model"
+base_url = "{mock_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "test-balanced-model"
+base_url = "http://localhost:9998/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "test-deep-model"
+base_url = "http://localhost:9997/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_sse_response() -> String {
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{"content":"Hello"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#,
+        "data: [DONE]",
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+async fn start_mock_backend() -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_sse_response())
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+fn create_test_app(state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware))
+}
+
+async fn complete(app: Router, max_tokens: u32) -> serde_json::Value {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(format!(
+            r#"{{"model": "fast", "messages": [{{"role": "user", "content": "Hello"}}], "max_tokens": {}}}"#,
+            max_tokens
+        )))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&body).expect("response should be JSON")
+}
+
+#[tokio::test]
+async fn test_routing_decision_warning_surfaces_in_response_body() {
+    let mock_server = start_mock_backend().await;
+    let config = create_test_config_with_mock(&mock_server.uri());
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+
+    let decision = RoutingDecision::new(TargetModel::Fast, RoutingStrategy::Llm)
+        .with_warning("Router health tracking degraded".to_string());
+    let result = execute_query_with_retry(
+        &state,
+        &decision,
+        "Hello",
+        RequestId::new(),
+        &QueryConfig::default(),
+        None,
+    )
+    .await
+    .expect("query should succeed");
+
+    let completion = ChatCompletion::new(result.content, result.endpoint.name().to_string(), 5, 0)
+        .with_warnings(result.warnings);
+    let json = serde_json::to_value(&completion).unwrap();
+
+    assert_eq!(
+        json["octoroute_warnings"],
+        serde_json::json!(["Router health tracking degraded"])
+    );
+}
+
+#[tokio::test]
+async fn test_clamping_warning_surfaces_in_response_body() {
+    let mock_server = start_mock_backend().await;
+    let config = create_test_config_with_mock(&mock_server.uri());
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+
+    let json = complete(create_test_app(state), 100_000).await;
+
+    let warnings = json["octoroute_warnings"]
+        .as_array()
+        .expect("clamped request should include octoroute_warnings");
+    assert!(
+        warnings
+            .iter()
+            .any(|w| w.as_str().unwrap().contains("clamped to 2048")),
+        "Clamp warning should be in the body, got: {:?}",
+        warnings
+    );
+}
+
+#[tokio::test]
+async fn test_no_warnings_omits_field() {
+    let mock_server = start_mock_backend().await;
+    let config = create_test_config_with_mock(&mock_server.uri());
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+
+    let json = complete(create_test_app(state), 512).await;
+
+    assert!(
+        json.get("octoroute_warnings").is_none(),
+        "Field should be omitted without warnings, got: {}",
+        json
+    );
+}
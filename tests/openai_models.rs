@@ -21,6 +21,10 @@ struct ModelObject {
     #[allow(dead_code)]
     created: i64,
     owned_by: String,
+    octoroute_tier: Option<String>,
+    octoroute_healthy: Option<bool>,
+    octoroute_weight: Option<f64>,
+    octoroute_priority: Option<u8>,
 }
 
 /// OpenAI models list response
@@ -74,7 +78,10 @@ router_tier = "balanced"
 fn create_test_app() -> Router {
     let config = Arc::new(create_test_config());
     let state = AppState::new(config).expect("AppState::new should succeed");
+    create_test_app_with_state(state)
+}
 
+fn create_test_app_with_state(state: AppState) -> Router {
     Router::new()
         .route(
             "/v1/models",
@@ -274,3 +281,77 @@ async fn test_models_object_type() {
         );
     }
 }
+
+async fn fetch_models(app: Router) -> ModelsListResponse {
+    let request = Request::builder()
+        .method("GET")
+        .uri("/v1/models")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&body).expect("Response should be valid JSON")
+}
+
+#[tokio::test]
+async fn test_models_endpoints_include_tier_and_health() {
+    let config = Arc::new(create_test_config());
+    let state = AppState::new(config).expect("AppState::new should succeed");
+    for _ in 0..3 {
+        state
+            .selector()
+            .health_checker()
+            .mark_failure("test-deep-model")
+            .await
+            .expect("mark_failure should succeed");
+    }
+
+    let models = fetch_models(create_test_app_with_state(state)).await;
+
+    for (endpoint, tier, healthy) in [
+        ("test-fast-model", "fast", true),
+        ("test-balanced-model", "balanced", true),
+        ("test-deep-model", "deep", false),
+    ] {
+        let model = models
+            .data
+            .iter()
+            .find(|m| m.id == endpoint)
+            .unwrap_or_else(|| panic!("Should have model '{}'", endpoint));
+        assert_eq!(model.octoroute_tier.as_deref(), Some(tier));
+        assert_eq!(
+            model.octoroute_healthy,
+            Some(healthy),
+            "Endpoint '{}' health flag should match the health checker",
+            endpoint
+        );
+        assert_eq!(model.octoroute_weight, Some(1.0));
+        assert_eq!(model.octoroute_priority, Some(1));
+    }
+
+    // Tier entries report whether any endpoint in the tier is healthy
+    let deep = models.data.iter().find(|m| m.id == "deep").unwrap();
+    assert_eq!(deep.octoroute_tier.as_deref(), Some("deep"));
+    assert_eq!(deep.octoroute_healthy, Some(false));
+    let fast = models.data.iter().find(|m| m.id == "fast").unwrap();
+    assert_eq!(fast.octoroute_healthy, Some(true));
+}
+
+#[tokio::test]
+async fn test_models_lists_auto_without_routing_metadata() {
+    let models = fetch_models(create_test_app()).await;
+
+    let auto = models
+        .data
+        .iter()
+        .find(|m| m.id == "auto")
+        .expect("'auto' should be discoverable");
+    assert_eq!(auto.owned_by, "octoroute");
+    assert!(auto.octoroute_tier.is_none(), "'auto' has no fixed tier");
+    assert!(auto.octoroute_healthy.is_none());
+    assert!(auto.octoroute_weight.is_none());
+    assert!(auto.octoroute_priority.is_none());
+}
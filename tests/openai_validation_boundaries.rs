@@ -276,23 +276,18 @@ async fn test_temperature_invalid_negative_infinity() {
 }
 
 // -------------------------------------------------------------------------
-// top_p Boundary Tests (0.0, 1.0] - exclusive lower, inclusive upper
+// top_p Boundary Tests [0.0, 1.0] - inclusive
 // -------------------------------------------------------------------------
 
 #[tokio::test]
-async fn test_top_p_invalid_at_zero() {
-    // top_p = 0.0 is invalid (exclusive lower bound)
-    assert_value_rejected(
-        r#""top_p": 0.0"#,
-        "top_p",
-        "top_p=0.0 (invalid - exclusive lower)",
-    )
-    .await;
+async fn test_top_p_valid_at_zero() {
+    // top_p = 0.0 is the minimum valid value (inclusive)
+    assert_value_accepted(r#""top_p": 0.0"#, "top_p=0.0 (lower boundary)").await;
 }
 
 #[tokio::test]
 async fn test_top_p_valid_just_above_zero() {
-    // top_p = 0.001 should be valid (just above exclusive lower bound)
+    // top_p = 0.001 should be valid (just above lower bound)
     assert_value_accepted(r#""top_p": 0.001"#, "top_p=0.001 (just above lower bound)").await;
 }
 
@@ -424,6 +419,53 @@ async fn test_frequency_penalty_invalid_above_maximum() {
     .await;
 }
 
+// -------------------------------------------------------------------------
+// logit_bias Boundary Tests (token ID -> [-100, 100])
+// -------------------------------------------------------------------------
+
+#[tokio::test]
+async fn test_logit_bias_valid_at_boundaries() {
+    // -100 effectively bans a token, 100 effectively forces it
+    assert_value_accepted(
+        r#""logit_bias": {"50256": -100, "1234": 100}"#,
+        "logit_bias at -100 and 100",
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_logit_bias_invalid_above_maximum() {
+    assert_value_rejected(
+        r#""logit_bias": {"50256": 101}"#,
+        "logit_bias",
+        "logit_bias=101 (above maximum)",
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_logit_bias_invalid_below_minimum() {
+    assert_value_rejected(
+        r#""logit_bias": {"50256": -101}"#,
+        "logit_bias",
+        "logit_bias=-101 (below minimum)",
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_logit_bias_rejects_non_numeric_token_id() {
+    // Keys are token IDs, not token text; a key that isn't one is malformed JSON
+    // for the request type rather than a value out of range
+    let (status, body) = make_request_with_param(r#""logit_bias": {"hello": 5}"#).await;
+    assert_eq!(
+        status,
+        StatusCode::BAD_REQUEST,
+        "non-numeric token ID should return 400. Got body: {}",
+        body
+    );
+}
+
 // -------------------------------------------------------------------------
 // max_tokens Boundary Tests (> 0)
 // -------------------------------------------------------------------------
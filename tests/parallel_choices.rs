@@ -0,0 +1,231 @@
+//! Integration tests for `n` choices and the fan-out cap
+//! (`server.max_parallel_upstream_per_request`, `server.fan_out_overflow`)
+//!
+//! Each requested choice is one upstream call. The backend here takes 300ms
+//! per call, so how long a request takes shows how many calls ran at once.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+const BACKEND_DELAY: Duration = Duration::from_millis(300);
+
+fn create_config(backend_url: &str, fan_out: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+request_timeout_seconds = 30
+{fan_out}
+
+[[models.fast]]
+name = "fast-1"
+base_url = "{backend_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "http://localhost:9998/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:9997/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_sse_response() -> String {
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"fast-1","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"fast-1","choices":[{"index":0,"delta":{"content":"An answer"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"fast-1","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#,
+        "data: [DONE]",
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+async fn start_backend() -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_sse_response())
+                .insert_header("content-type", "text/event-stream")
+                .set_delay(BACKEND_DELAY),
+        )
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+/// Send `body` and return the status, the parsed body and how long it took
+async fn send(
+    backend: &MockServer,
+    fan_out: &str,
+    body: &str,
+) -> (StatusCode, serde_json::Value, Duration) {
+    let config = create_config(&backend.uri(), fan_out);
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+    let app = Router::new()
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware));
+    let request = Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+
+    let start = Instant::now();
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let elapsed = start.elapsed();
+    (status, serde_json::from_slice(&body).unwrap(), elapsed)
+}
+
+async fn call_count(server: &MockServer) -> usize {
+    server.received_requests().await.unwrap().len()
+}
+
+fn request_with_n(n: u32) -> String {
+    format!(
+        r#"{{"model": "fast", "messages": [{{"role": "user", "content": "Hello"}}], "n": {n}}}"#
+    )
+}
+
+#[tokio::test]
+async fn test_n_returns_one_choice_per_call() {
+    let backend = start_backend().await;
+
+    let (status, json, _) = send(&backend, "", &request_with_n(3)).await;
+
+    assert_eq!(status, StatusCode::OK, "{}", json);
+    let choices = json["choices"].as_array().expect("choices array");
+    let indices: Vec<u64> = choices
+        .iter()
+        .map(|c| c["index"].as_u64().unwrap())
+        .collect();
+    assert_eq!(indices, [0, 1, 2]);
+    assert!(
+        choices
+            .iter()
+            .all(|c| c["message"]["content"] == "An answer")
+    );
+    assert_eq!(call_count(&backend).await, 3);
+}
+
+#[tokio::test]
+async fn test_fan_out_beyond_cap_runs_at_most_cap_at_once() {
+    let backend = start_backend().await;
+
+    let (status, json, elapsed) = send(
+        &backend,
+        "max_parallel_upstream_per_request = 2",
+        &request_with_n(4),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK, "{}", json);
+    assert_eq!(json["choices"].as_array().unwrap().len(), 4);
+    assert_eq!(call_count(&backend).await, 4);
+    // Two at a time: two rounds of the backend delay
+    assert!(elapsed >= BACKEND_DELAY * 2, "took {:?}", elapsed);
+}
+
+#[tokio::test]
+async fn test_fan_out_within_cap_runs_in_parallel() {
+    let backend = start_backend().await;
+
+    let (status, json, elapsed) = send(
+        &backend,
+        "max_parallel_upstream_per_request = 2",
+        &request_with_n(2),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK, "{}", json);
+    assert_eq!(json["choices"].as_array().unwrap().len(), 2);
+    // One round; running them one after the other would take two
+    assert!(elapsed < BACKEND_DELAY * 2, "took {:?}", elapsed);
+}
+
+#[tokio::test]
+async fn test_each_parallel_choice_takes_a_tier_slot() {
+    let backend = start_backend().await;
+
+    // One Fast slot: the second choice queues for it instead of running alongside
+    let (status, json, elapsed) = send(
+        &backend,
+        "max_queue_wait_ms = 5000\n\n[server.tier_concurrency]\nfast = 1",
+        &request_with_n(2),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK, "{}", json);
+    assert_eq!(json["choices"].as_array().unwrap().len(), 2);
+    assert!(elapsed >= BACKEND_DELAY * 2, "took {:?}", elapsed);
+}
+
+#[tokio::test]
+async fn test_fan_out_beyond_cap_is_rejected_when_configured() {
+    let backend = start_backend().await;
+
+    let (status, json, _) = send(
+        &backend,
+        "max_parallel_upstream_per_request = 2\nfan_out_overflow = \"reject\"",
+        &request_with_n(3),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(
+        json.to_string()
+            .contains("max_parallel_upstream_per_request"),
+        "{}",
+        json
+    );
+    assert_eq!(call_count(&backend).await, 0);
+}
+
+#[tokio::test]
+async fn test_n_above_one_is_rejected_for_streaming() {
+    let backend = start_backend().await;
+
+    let (status, _, _) = send(
+        &backend,
+        "",
+        r#"{"model": "fast", "messages": [{"role": "user", "content": "Hello"}], "n": 2, "stream": true}"#,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(call_count(&backend).await, 0);
+}
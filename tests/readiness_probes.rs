@@ -0,0 +1,210 @@
+//! Integration tests for /livez and /readyz
+//!
+//! `/livez` always answers 200 while the process runs. `/readyz` answers 503
+//! until each required tier has an endpoint that passed a health check, and
+//! again when a required tier (here the LLM router tier) has no healthy
+//! endpoints, recovering to 200 as soon as one becomes healthy again.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    routing::get,
+};
+use octoroute::{config::Config, handlers};
+use std::sync::Arc;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+/// LLM routing via the deep tier, with tier fallback so only fast + deep are required
+fn create_state_with_url(base_url: &str) -> handlers::AppState {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+
+[[models.fast]]
+name = "fast-1"
+base_url = "{base_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "{base_url}"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "{base_url}"
+max_tokens = 8192
+
+[routing]
+strategy = "llm"
+router_tier = "deep"
+tier_fallback = true
+"#
+    );
+    let config: Config = toml::from_str(&toml).expect("should parse test config");
+    handlers::AppState::new(Arc::new(config)).expect("should create AppState")
+}
+
+/// Test state whose endpoints have never been checked
+fn create_test_state() -> handlers::AppState {
+    create_state_with_url("http://localhost:1234/v1")
+}
+
+/// Test state whose endpoints have all passed a health check
+async fn create_verified_state() -> handlers::AppState {
+    let state = create_test_state();
+    for name in ["fast-1", "balanced-1", "deep-1"] {
+        state
+            .selector()
+            .health_checker()
+            .mark_success(name)
+            .await
+            .unwrap();
+    }
+    state
+}
+
+fn create_test_app(state: handlers::AppState) -> Router {
+    Router::new()
+        .route("/livez", get(handlers::probes::livez))
+        .route("/readyz", get(handlers::probes::readyz))
+        .with_state(state)
+}
+
+async fn get_json(app: Router, uri: &str) -> (StatusCode, serde_json::Value) {
+    let response = app
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json = if body.is_empty() {
+        serde_json::Value::Null
+    } else {
+        serde_json::from_slice(&body).expect("body should be JSON")
+    };
+    (status, json)
+}
+
+async fn mark_unhealthy(state: &handlers::AppState, name: &str) {
+    let health = state.selector().health_checker();
+    for _ in 0..3 {
+        health.mark_failure(name).await.unwrap();
+    }
+    assert!(!health.is_healthy(name).await);
+}
+
+#[tokio::test]
+async fn test_livez_always_ok() {
+    let state = create_test_state();
+    for name in ["fast-1", "balanced-1", "deep-1"] {
+        mark_unhealthy(&state, name).await;
+    }
+
+    let (status, _) = get_json(create_test_app(state), "/livez").await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_readyz_ok_when_required_tiers_healthy() {
+    let state = create_verified_state().await;
+
+    let (status, json) = get_json(create_test_app(state), "/readyz").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["status"], "ready");
+    assert_eq!(json["unavailable_tiers"], serde_json::json!([]));
+}
+
+#[tokio::test]
+async fn test_readyz_unavailable_when_router_tier_unhealthy_then_recovers() {
+    let state = create_verified_state().await;
+    mark_unhealthy(&state, "deep-1").await;
+
+    let (status, json) = get_json(create_test_app(state.clone()), "/readyz").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(json["status"], "not_ready");
+    assert_eq!(json["unavailable_tiers"], serde_json::json!(["deep"]));
+
+    state
+        .selector()
+        .health_checker()
+        .mark_success("deep-1")
+        .await
+        .unwrap();
+
+    let (status, json) = get_json(create_test_app(state), "/readyz").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["status"], "ready");
+}
+
+#[tokio::test]
+async fn test_readyz_ignores_tiers_covered_by_fallback() {
+    let state = create_verified_state().await;
+    // Balanced is neither the router tier nor required with fallback enabled
+    mark_unhealthy(&state, "balanced-1").await;
+
+    let (status, _) = get_json(create_test_app(state), "/readyz").await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_readyz_unavailable_before_first_check() {
+    let state = create_test_state();
+
+    let (status, json) = get_json(create_test_app(state), "/readyz").await;
+
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(json["status"], "not_ready");
+    assert_eq!(
+        json["unavailable_tiers"],
+        serde_json::json!(["fast", "deep"])
+    );
+}
+
+#[tokio::test]
+async fn test_readyz_ready_after_first_successful_probe() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("HEAD"))
+        .and(path("/v1/models"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&mock_server)
+        .await;
+    let state = create_state_with_url(&format!("{}/v1", mock_server.uri()));
+
+    let (status, _) = get_json(create_test_app(state.clone()), "/readyz").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+
+    state.selector().health_checker().warmup().await;
+
+    let (status, json) = get_json(create_test_app(state), "/readyz").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["status"], "ready");
+}
+
+#[tokio::test]
+async fn test_failed_first_probe_keeps_readyz_unavailable() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("HEAD"))
+        .and(path("/v1/models"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&mock_server)
+        .await;
+    let state = create_state_with_url(&format!("{}/v1", mock_server.uri()));
+
+    // One failed probe leaves the endpoints healthy but still never verified
+    state.selector().health_checker().warmup().await;
+    assert!(state.selector().health_checker().is_healthy("fast-1").await);
+
+    let (status, _) = get_json(create_test_app(state), "/readyz").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+}
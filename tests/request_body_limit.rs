@@ -0,0 +1,193 @@
+//! Integration tests for `server.max_request_body_bytes`
+//!
+//! Oversized request bodies are rejected with an OpenAI-formatted 413 before
+//! routing, on both chat endpoints, whether or not the client declares a
+//! `Content-Length`. Bodies under the limit proceed normally.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::middleware::{body_limit_middleware, request_id_middleware};
+use octoroute::{config::Config, handlers::AppState};
+use std::sync::Arc;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+const BODY_LIMIT: usize = 1024;
+
+fn create_config(mock_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 8080
+request_timeout_seconds = 30
+max_request_body_bytes = {BODY_LIMIT}
+
+[[models.fast]]
+name = "test-fast-model"
+base_url = "{mock_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "test-balanced-model"
+base_url = "http://localhost:9998/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "test-deep-model"
+base_url = "http://localhost:9997/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_sse_response() -> String {
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{"content":"Hello"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#,
+        "data: [DONE]",
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+async fn start_mock_backend() -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_sse_response())
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+fn create_test_app(config: Config) -> Router {
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+    Router::new()
+        .route("/chat", post(octoroute::handlers::chat::handler))
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state.clone())
+        .layer(middleware::from_fn_with_state(state, body_limit_middleware))
+        .layer(middleware::from_fn(request_id_middleware))
+}
+
+fn completion_body(content: &str) -> String {
+    format!(
+        r#"{{"model": "fast", "messages": [{{"role": "user", "content": "{}"}}]}}"#,
+        content
+    )
+}
+
+fn json_request(uri: &str, body: String) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("content-type", "application/json")
+        .header("content-length", body.len())
+        .body(Body::from(body))
+        .unwrap()
+}
+
+async fn assert_payload_too_large(response: axum::response::Response) {
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value =
+        serde_json::from_slice(&body).expect("413 body should be OpenAI-formatted JSON");
+    let message = json["error"]["message"].as_str().unwrap_or_default();
+    assert!(
+        message.contains(&BODY_LIMIT.to_string()) && message.contains("max_request_body_bytes"),
+        "413 message should state the limit and the setting, got: {}",
+        message
+    );
+    assert_eq!(json["error"]["type"], "invalid_request_error");
+}
+
+#[tokio::test]
+async fn test_oversized_completion_body_returns_413() {
+    let mock_server = start_mock_backend().await;
+    let app = create_test_app(create_config(&mock_server.uri()));
+
+    let body = completion_body(&"a".repeat(BODY_LIMIT * 2));
+    let response = app
+        .oneshot(json_request("/v1/chat/completions", body))
+        .await
+        .unwrap();
+
+    assert_payload_too_large(response).await;
+    assert!(
+        mock_server.received_requests().await.unwrap().is_empty(),
+        "Oversized request must not reach the backend"
+    );
+}
+
+#[tokio::test]
+async fn test_oversized_legacy_chat_body_returns_413() {
+    let mock_server = start_mock_backend().await;
+    let app = create_test_app(create_config(&mock_server.uri()));
+
+    let body = format!(r#"{{"message": "{}"}}"#, "a".repeat(BODY_LIMIT * 2));
+    let response = app.oneshot(json_request("/chat", body)).await.unwrap();
+
+    assert_payload_too_large(response).await;
+}
+
+#[tokio::test]
+async fn test_oversized_chunked_body_returns_413() {
+    let mock_server = start_mock_backend().await;
+    let app = create_test_app(create_config(&mock_server.uri()));
+
+    // No Content-Length: the body arrives as a stream of chunks
+    let body = completion_body(&"a".repeat(BODY_LIMIT * 2));
+    let chunks: Vec<Result<String, std::io::Error>> = body
+        .as_bytes()
+        .chunks(256)
+        .map(|chunk| Ok(String::from_utf8(chunk.to_vec()).unwrap()))
+        .collect();
+    let request = Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from_stream(futures::stream::iter(chunks)))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_payload_too_large(response).await;
+}
+
+#[tokio::test]
+async fn test_body_under_limit_proceeds_normally() {
+    let mock_server = start_mock_backend().await;
+    let app = create_test_app(create_config(&mock_server.uri()));
+
+    let body = completion_body("Hello");
+    assert!(body.len() < BODY_LIMIT);
+    let response = app
+        .oneshot(json_request("/v1/chat/completions", body))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+}
@@ -0,0 +1,259 @@
+//! Integration tests for per-request cost accounting (`cost_per_1k_tokens`)
+//!
+//! A non-streaming completion served by an endpoint with a rate carries the
+//! estimated cost in the `octoroute_cost` extension field and adds it to
+//! `octoroute_request_cost_total{tier}`. Endpoints without a rate are uncosted.
+//! With `n` choices, each choice is priced at the endpoint that served it.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::metrics::Tier;
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::sync::Arc;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+/// Fast is priced at 2.0 per 1000 tokens; Balanced and Deep have no rate
+fn create_config(mock_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+request_timeout_seconds = 30
+
+[[models.fast]]
+name = "fast-1"
+base_url = "{mock_url}"
+max_tokens = 2048
+cost_per_1k_tokens = 2.0
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "{mock_url}"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "{mock_url}"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+/// Two priced Fast endpoints; fast-1 is preferred but serves one query at a time,
+/// spilling further queries over to fast-2
+fn create_two_rate_config(mock_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+request_timeout_seconds = 30
+
+[[models.fast]]
+name = "fast-1"
+base_url = "{mock_url}"
+max_tokens = 2048
+priority = 2
+max_in_flight = 1
+cost_per_1k_tokens = 2.0
+
+[[models.fast]]
+name = "fast-2"
+base_url = "{mock_url}"
+max_tokens = 2048
+priority = 1
+cost_per_1k_tokens = 1.0
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "{mock_url}"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "{mock_url}"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+spillover = true
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_sse_response(text: &str) -> String {
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#.to_string(),
+        format!(
+            r#"data: {{"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{{"index":0,"delta":{{"content":"{text}"}},"finish_reason":null}}]}}"#
+        ),
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#.to_string(),
+        "data: [DONE]".to_string(),
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+/// Backend answering every query with a 400-character reply (100 estimated tokens)
+async fn start_backend() -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_sse_response(&"abcd".repeat(100)))
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+fn create_test_app(state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware))
+}
+
+async fn complete(state: &AppState, model: &str) -> serde_json::Value {
+    complete_body(
+        state,
+        format!(
+            r#"{{"model": "{model}", "messages": [{{"role": "user", "content": "Summarize the tides"}}]}}"#
+        ),
+    )
+    .await
+}
+
+async fn complete_body(state: &AppState, body: String) -> serde_json::Value {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(body))
+        .unwrap();
+    let response = create_test_app(state.clone())
+        .oneshot(request)
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&body).expect("response should be JSON")
+}
+
+#[tokio::test]
+async fn test_cost_accumulates_from_usage_and_rate() {
+    let mock_server = start_backend().await;
+    let state = AppState::new(Arc::new(create_config(&mock_server.uri())))
+        .expect("AppState::new should succeed");
+
+    let first = complete(&state, "fast").await;
+    assert_eq!(first["usage"]["completion_tokens"], 100);
+    let total_tokens = first["usage"]["total_tokens"].as_u64().unwrap() as f64;
+    let expected = total_tokens / 1000.0 * 2.0;
+
+    let cost = first["octoroute_cost"]
+        .as_f64()
+        .expect("a priced endpoint should report octoroute_cost");
+    assert!((cost - expected).abs() < 1e-9, "got {}", cost);
+    assert!(
+        (state.metrics().request_cost_total(Tier::Fast) - expected).abs() < 1e-9,
+        "first request should be counted"
+    );
+
+    // The same request again doubles the counter
+    complete(&state, "fast").await;
+    assert!(
+        (state.metrics().request_cost_total(Tier::Fast) - 2.0 * expected).abs() < 1e-9,
+        "got {}",
+        state.metrics().request_cost_total(Tier::Fast)
+    );
+
+    // Specific-model requests are costed the same way
+    let specific = complete(&state, "fast-1").await;
+    assert!((specific["octoroute_cost"].as_f64().unwrap() - expected).abs() < 1e-9);
+    assert!((state.metrics().request_cost_total(Tier::Fast) - 3.0 * expected).abs() < 1e-9);
+
+    let metrics = state.metrics().gather().unwrap();
+    assert!(
+        metrics.contains("octoroute_request_cost_total{tier=\"fast\"}"),
+        "got: {}",
+        metrics
+    );
+}
+
+#[tokio::test]
+async fn test_endpoint_without_rate_is_uncosted() {
+    let mock_server = start_backend().await;
+    let state = AppState::new(Arc::new(create_config(&mock_server.uri())))
+        .expect("AppState::new should succeed");
+
+    let body = complete(&state, "balanced").await;
+
+    assert!(
+        body.get("octoroute_cost").is_none(),
+        "an unpriced endpoint should omit octoroute_cost, got: {}",
+        body
+    );
+    assert_eq!(state.metrics().request_cost_total(Tier::Balanced), 0.0);
+}
+
+#[tokio::test]
+async fn test_each_choice_is_priced_at_its_own_endpoint() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_sse_response(&"abcd".repeat(100)))
+                .insert_header("content-type", "text/event-stream")
+                .set_delay(std::time::Duration::from_millis(200)),
+        )
+        .mount(&mock_server)
+        .await;
+    let state = AppState::new(Arc::new(create_two_rate_config(&mock_server.uri())))
+        .expect("AppState::new should succeed");
+
+    // Both choices run at once, so the second goes to fast-2 while fast-1 is busy
+    let body = complete_body(
+        &state,
+        r#"{"model": "fast", "n": 2, "messages": [{"role": "user", "content": "Summarize the tides"}]}"#
+            .to_string(),
+    )
+    .await;
+
+    // Each call is charged the prompt plus its own 100-token answer
+    let call_tokens = body["usage"]["prompt_tokens"].as_u64().unwrap() as f64 + 100.0;
+    let expected = call_tokens / 1000.0 * 2.0 + call_tokens / 1000.0 * 1.0;
+    let cost = body["octoroute_cost"]
+        .as_f64()
+        .expect("priced endpoints should report octoroute_cost");
+    assert!(
+        (cost - expected).abs() < 1e-9,
+        "got {}, expected {}",
+        cost,
+        expected
+    );
+    assert!((state.metrics().request_cost_total(Tier::Fast) - expected).abs() < 1e-9);
+}
@@ -0,0 +1,205 @@
+//! Integration tests for client deadlines (`x-octoroute-deadline-ms`)
+//!
+//! A deadline too short to be met is rejected with 504 before any upstream
+//! query; otherwise it bounds the request like
+//! `server.max_request_duration_seconds`, which is left unset here, and also
+//! the wait for a stream's first token once the SSE response has started.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::middleware::{DEADLINE_HEADER, request_id_middleware, request_timeout_middleware};
+use octoroute::{config::Config, handlers::AppState};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+fn create_config(mock_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 8080
+request_timeout_seconds = 30
+
+[[models.fast]]
+name = "test-fast-model"
+base_url = "{mock_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "test-balanced-model"
+base_url = "http://localhost:9998/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "test-deep-model"
+base_url = "http://localhost:9997/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_sse_response() -> String {
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{"content":"Hello"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#,
+        "data: [DONE]",
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+async fn start_backend(delay: Duration) -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_delay(delay)
+                .set_body_string(create_sse_response())
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+fn create_test_app(config: Config) -> Router {
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+    Router::new()
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state.clone())
+        .layer(middleware::from_fn_with_state(
+            state,
+            request_timeout_middleware,
+        ))
+        .layer(middleware::from_fn(request_id_middleware))
+}
+
+fn completion_request(deadline: &str) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .header(DEADLINE_HEADER, deadline)
+        .body(Body::from(
+            r#"{"model": "fast", "messages": [{"role": "user", "content": "Hello"}]}"#,
+        ))
+        .unwrap()
+}
+
+fn streaming_request(deadline: &str) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .header(DEADLINE_HEADER, deadline)
+        .body(Body::from(
+            r#"{"model": "fast", "stream": true, "messages": [{"role": "user", "content": "Hello"}]}"#,
+        ))
+        .unwrap()
+}
+
+async fn body_string(response: axum::response::Response) -> String {
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    String::from_utf8_lossy(&body).into_owned()
+}
+
+#[tokio::test]
+async fn test_tiny_deadline_short_circuits_with_504() {
+    let mock_server = start_backend(Duration::ZERO).await;
+    let app = create_test_app(create_config(&mock_server.uri()));
+
+    let response = app.oneshot(completion_request("5")).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+    let body = body_string(response).await;
+    assert!(body.contains("deadline of 5 ms"), "{}", body);
+    assert_eq!(
+        mock_server.received_requests().await.unwrap().len(),
+        0,
+        "No upstream query should be started for a deadline that can't be met"
+    );
+}
+
+#[tokio::test]
+async fn test_generous_deadline_proceeds() {
+    let mock_server = start_backend(Duration::ZERO).await;
+    let app = create_test_app(create_config(&mock_server.uri()));
+
+    let response = app.oneshot(completion_request("30000")).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(mock_server.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_deadline_cuts_off_slow_backend() {
+    let mock_server = start_backend(Duration::from_secs(10)).await;
+    let app = create_test_app(create_config(&mock_server.uri()));
+
+    let start = Instant::now();
+    let response = app.oneshot(completion_request("300")).await.unwrap();
+    let elapsed = start.elapsed();
+
+    assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+    assert!(
+        elapsed < Duration::from_secs(5),
+        "The 300ms deadline should fire long before the 10s backend delay, took {:?}",
+        elapsed
+    );
+    let body = body_string(response).await;
+    assert!(body.contains("deadline of 300 ms"), "{}", body);
+}
+
+#[tokio::test]
+async fn test_deadline_ends_stream_waiting_for_slow_first_token() {
+    let mock_server = start_backend(Duration::from_secs(10)).await;
+    let app = create_test_app(create_config(&mock_server.uri()));
+
+    let start = Instant::now();
+    let response = app.oneshot(streaming_request("300")).await.unwrap();
+    // The SSE response starts before the first token, so the status is already 200
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = body_string(response).await;
+    let elapsed = start.elapsed();
+
+    assert!(
+        elapsed < Duration::from_secs(5),
+        "The 300ms deadline should end the stream long before the 10s backend delay, took {:?}",
+        elapsed
+    );
+    assert!(body.contains("deadline of 300 ms"), "{}", body);
+    assert!(body.contains("[DONE]"), "{}", body);
+}
+
+#[tokio::test]
+async fn test_malformed_deadline_is_rejected() {
+    let mock_server = start_backend(Duration::ZERO).await;
+    let app = create_test_app(create_config(&mock_server.uri()));
+
+    let response = app.oneshot(completion_request("soon")).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = body_string(response).await;
+    assert!(body.contains(DEADLINE_HEADER), "{}", body);
+}
@@ -0,0 +1,120 @@
+//! Integration tests for malformed JSON request bodies on /v1/chat/completions
+//!
+//! Bodies that are not valid JSON, or that carry a field of the wrong JSON type,
+//! are rejected with 400 and an OpenAI-formatted error naming the problem's
+//! position and, where known, the offending field. Well-formed requests that
+//! fail validation keep returning 422.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::sync::Arc;
+use tower::ServiceExt;
+
+fn create_test_app() -> Router {
+    let toml = r#"
+[server]
+host = "127.0.0.1"
+port = 8080
+
+[[models.fast]]
+name = "test-fast-model"
+base_url = "http://localhost:9999/v1"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "test-balanced-model"
+base_url = "http://localhost:9998/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "test-deep-model"
+base_url = "http://localhost:9997/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#;
+    let config: Config = toml::from_str(toml).expect("should parse TOML config");
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+    Router::new()
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware))
+}
+
+/// Send a completions request and return the status with the parsed error body
+async fn send(body: &str) -> (StatusCode, serde_json::Value) {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let response = create_test_app().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json = serde_json::from_slice(&bytes).expect("error body should be JSON");
+    (status, json)
+}
+
+#[tokio::test]
+async fn test_truncated_body_returns_400_with_position() {
+    let (status, body) = send(r#"{"model": "fast", "messages": [{"role": "user", "#).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["type"], "invalid_request_error");
+    let message = body["error"]["message"].as_str().unwrap();
+    assert!(
+        message.starts_with("Malformed JSON request body:") && message.contains("line 1 column"),
+        "message should explain the parse failure and its position, got: {}",
+        message
+    );
+}
+
+#[tokio::test]
+async fn test_wrong_typed_field_returns_400_naming_the_field() {
+    let (status, body) = send(
+        r#"{"model": "fast", "messages": [{"role": "user", "content": "Hello"}], "temperature": "hot"}"#,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["param"], "temperature");
+    let message = body["error"]["message"].as_str().unwrap();
+    assert!(
+        message.contains("temperature") && message.contains("invalid type"),
+        "message should name the field and the type problem, got: {}",
+        message
+    );
+}
+
+#[tokio::test]
+async fn test_nested_wrong_typed_field_reports_its_path() {
+    let (status, body) =
+        send(r#"{"model": "fast", "messages": [{"role": "user", "content": 42}]}"#).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["param"], "messages[0].content");
+}
+
+#[tokio::test]
+async fn test_validation_failure_still_returns_422() {
+    let (status, body) = send(
+        r#"{"model": "fast", "messages": [{"role": "user", "content": "Hello"}], "temperature": 5.0}"#,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["error"]["type"], "invalid_request_error");
+}
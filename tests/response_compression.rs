@@ -0,0 +1,184 @@
+//! Integration tests for gzip response compression (`server.enable_compression`)
+//!
+//! A client sending `Accept-Encoding: gzip` gets a gzip body that decompresses
+//! to the same SSE stream, keep-alive comments and `[DONE]` included, or to the
+//! same JSON completion. Clients without the header, and servers with
+//! compression off, get plain output.
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode, header},
+};
+use flate2::read::GzDecoder;
+use octoroute::{config::Config, handlers::AppState, server};
+use std::io::Read;
+use std::sync::Arc;
+use std::time::Duration;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+fn create_test_config(mock_url: &str, enable_compression: bool) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 8080
+request_timeout_seconds = 30
+sse_keepalive_seconds = 1
+enable_compression = {enable_compression}
+
+[[models.fast]]
+name = "test-fast-model"
+base_url = "{mock_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "test-balanced-model"
+base_url = "http://localhost:9998/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "test-deep-model"
+base_url = "http://localhost:9997/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_sse_response() -> String {
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{"content":"Hello"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#,
+        "data: [DONE]",
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+/// Backend slow enough for one keep-alive comment before the first token
+async fn start_backend() -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_delay(Duration::from_millis(1500))
+                .set_body_string(create_sse_response())
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+/// Request a completion, returning the Content-Encoding header and raw body
+async fn complete(
+    config: Config,
+    accept_encoding: Option<&str>,
+    stream: bool,
+) -> (Option<String>, Vec<u8>) {
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+
+    let mut request = Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json");
+    if let Some(accept_encoding) = accept_encoding {
+        request = request.header(header::ACCEPT_ENCODING, accept_encoding);
+    }
+    let request = request
+        .body(Body::from(format!(
+            r#"{{"model": "fast", "messages": [{{"role": "user", "content": "Hello"}}], "stream": {stream}}}"#
+        )))
+        .unwrap();
+
+    let response = server::public_app(state).oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let encoding = response
+        .headers()
+        .get(header::CONTENT_ENCODING)
+        .map(|value| value.to_str().unwrap().to_string());
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (encoding, body.to_vec())
+}
+
+/// Decompress a gzip body, checking the magic first
+fn gunzip(body: &[u8]) -> String {
+    assert!(
+        body.starts_with(&[0x1f, 0x8b]),
+        "body should start with the gzip magic"
+    );
+    let mut decompressed = String::new();
+    GzDecoder::new(body)
+        .read_to_string(&mut decompressed)
+        .expect("body should be valid gzip");
+    decompressed
+}
+
+fn assert_complete_stream(body: &str) {
+    assert!(body.contains(": keep-alive"), "{}", body);
+    assert!(body.contains("Hello"), "{}", body);
+    assert!(body.trim_end().ends_with("data: [DONE]"), "{}", body);
+}
+
+#[tokio::test]
+async fn test_gzip_client_receives_compressed_sse_stream() {
+    let mock_server = start_backend().await;
+    let (encoding, body) = complete(
+        create_test_config(&mock_server.uri(), true),
+        Some("gzip, deflate"),
+        true,
+    )
+    .await;
+
+    assert_eq!(encoding.as_deref(), Some("gzip"));
+    assert_complete_stream(&gunzip(&body));
+}
+
+#[tokio::test]
+async fn test_gzip_client_receives_compressed_completion() {
+    let mock_server = start_backend().await;
+    let (encoding, body) = complete(
+        create_test_config(&mock_server.uri(), true),
+        Some("gzip"),
+        false,
+    )
+    .await;
+
+    assert_eq!(encoding.as_deref(), Some("gzip"));
+    let json: serde_json::Value = serde_json::from_str(&gunzip(&body)).unwrap();
+    assert_eq!(json["choices"][0]["message"]["content"], "Hello");
+}
+
+#[tokio::test]
+async fn test_client_without_accept_encoding_gets_plain_stream() {
+    let mock_server = start_backend().await;
+    let (encoding, body) = complete(create_test_config(&mock_server.uri(), true), None, true).await;
+
+    assert_eq!(encoding, None);
+    assert_complete_stream(&String::from_utf8(body).unwrap());
+}
+
+#[tokio::test]
+async fn test_compression_disabled_ignores_accept_encoding() {
+    let mock_server = start_backend().await;
+    let (encoding, body) = complete(
+        create_test_config(&mock_server.uri(), false),
+        Some("gzip"),
+        true,
+    )
+    .await;
+
+    assert_eq!(encoding, None);
+    assert_complete_stream(&String::from_utf8(body).unwrap());
+}
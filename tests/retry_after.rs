@@ -0,0 +1,133 @@
+//! Integration tests for `Retry-After` on transient endpoint exhaustion
+//!
+//! When a tier's endpoints are configured but currently unhealthy, clients get
+//! a 503 with a `Retry-After` hint. When every endpoint was tried and failed in
+//! the request itself (complete exhaustion), there is no retry hint.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode, header},
+    middleware,
+    routing::post,
+};
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::sync::Arc;
+use tower::ServiceExt;
+
+fn create_test_config(fast_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 8080
+request_timeout_seconds = 5
+
+[[models.fast]]
+name = "test-fast-model"
+base_url = "{fast_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "test-balanced-model"
+base_url = "http://localhost:9998/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "test-deep-model"
+base_url = "http://localhost:9997/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_test_app(state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware))
+}
+
+fn completion_request(stream: bool) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(format!(
+            r#"{{"model": "fast", "messages": [{{"role": "user", "content": "Hello"}}], "stream": {}}}"#,
+            stream
+        )))
+        .unwrap()
+}
+
+async fn state_with_unhealthy_fast_tier() -> AppState {
+    let config = create_test_config("http://localhost:9999/v1");
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+    for _ in 0..3 {
+        state
+            .selector()
+            .health_checker()
+            .mark_failure("test-fast-model")
+            .await
+            .expect("mark_failure should succeed");
+    }
+    state
+}
+
+#[tokio::test]
+async fn test_unhealthy_tier_returns_503_with_retry_after() {
+    let app = create_test_app(state_with_unhealthy_fast_tier().await);
+
+    let response = app.oneshot(completion_request(false)).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    let retry_after: u64 = response
+        .headers()
+        .get(header::RETRY_AFTER)
+        .expect("transient exhaustion should include Retry-After")
+        .to_str()
+        .unwrap()
+        .parse()
+        .expect("Retry-After should be a number of seconds");
+    assert!(retry_after > 0, "Retry-After should be a positive delay");
+}
+
+#[tokio::test]
+async fn test_unhealthy_tier_streaming_returns_503_with_retry_after() {
+    let app = create_test_app(state_with_unhealthy_fast_tier().await);
+
+    let response = app.oneshot(completion_request(true)).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert!(
+        response.headers().contains_key(header::RETRY_AFTER),
+        "Streaming requests should get the same retry hint"
+    );
+}
+
+#[tokio::test]
+async fn test_complete_exhaustion_has_no_retry_after() {
+    // Nothing listens on port 1, so the only fast endpoint fails inside the request
+    let config = create_test_config("http://127.0.0.1:1/v1");
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+    let app = create_test_app(state);
+
+    let response = app.oneshot(completion_request(false)).await.unwrap();
+
+    assert!(
+        !response.status().is_success(),
+        "Request should fail when the only endpoint is unreachable"
+    );
+    assert_ne!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert!(
+        response.headers().get(header::RETRY_AFTER).is_none(),
+        "Complete exhaustion should not suggest a retry delay"
+    );
+}
@@ -0,0 +1,191 @@
+//! Integration tests for `routing.retryable_statuses`
+//!
+//! The fast tier's preferred endpoint rejects every query with a fixed HTTP
+//! status; its sibling answers normally. Statuses in the retryable set fail
+//! over to the sibling, any other status is returned to the client at once.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::sync::Arc;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+fn create_config(failing_url: &str, sibling_url: &str, routing_extra: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+request_timeout_seconds = 5
+
+[[models.fast]]
+name = "fast-failing"
+base_url = "{failing_url}"
+max_tokens = 2048
+priority = 2
+
+[[models.fast]]
+name = "fast-sibling"
+base_url = "{sibling_url}"
+max_tokens = 2048
+priority = 1
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "http://localhost:9998/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:9997/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+{routing_extra}
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_sse_response(content: &str) -> String {
+    [
+        format!(
+            r#"data: {{"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{{"index":0,"delta":{{"content":"{content}"}},"finish_reason":null}}]}}"#
+        ),
+        "data: [DONE]".to_string(),
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+struct Backends {
+    failing: MockServer,
+    sibling: MockServer,
+}
+
+impl Backends {
+    async fn start(status: u16) -> Self {
+        let failing = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(status).set_body_string(
+                r#"{"error":{"message":"rejected by test backend","type":"invalid_request_error"}}"#,
+            ))
+            .mount(&failing)
+            .await;
+
+        let sibling = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(create_sse_response("Hello"))
+                    .insert_header("content-type", "text/event-stream"),
+            )
+            .mount(&sibling)
+            .await;
+
+        Self { failing, sibling }
+    }
+
+    async fn sibling_calls(&self) -> usize {
+        self.sibling.received_requests().await.unwrap().len()
+    }
+
+    /// Send a fast-tier completion request; returns status and body
+    async fn complete(&self, routing_extra: &str, stream: bool) -> (StatusCode, String) {
+        let config = create_config(&self.failing.uri(), &self.sibling.uri(), routing_extra);
+        let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+        let app = Router::new()
+            .route(
+                "/v1/chat/completions",
+                post(octoroute::handlers::openai::completions::handler),
+            )
+            .with_state(state)
+            .layer(middleware::from_fn(request_id_middleware));
+
+        let body = serde_json::json!({
+            "model": "fast",
+            "messages": [{"role": "user", "content": "Hello"}],
+            "stream": stream,
+        });
+        let request = Request::builder()
+            .method("POST")
+            .uri("/v1/chat/completions")
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, String::from_utf8_lossy(&bytes).into_owned())
+    }
+}
+
+#[tokio::test]
+async fn test_429_fails_over_to_another_endpoint() {
+    let backends = Backends::start(429).await;
+
+    let (status, body) = backends.complete("", false).await;
+
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert!(body.contains("Hello"), "{}", body);
+    assert_eq!(backends.sibling_calls().await, 1);
+}
+
+#[tokio::test]
+async fn test_400_is_returned_without_failover() {
+    let backends = Backends::start(400).await;
+
+    let (status, body) = backends.complete("", false).await;
+
+    assert_eq!(status, StatusCode::BAD_GATEWAY);
+    assert!(body.contains("HTTP 400"), "{}", body);
+    assert_eq!(backends.sibling_calls().await, 0);
+    assert_eq!(backends.failing.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_configured_set_replaces_defaults() {
+    let backends = Backends::start(429).await;
+
+    let (status, _) = backends.complete("retryable_statuses = [503]", false).await;
+    assert_eq!(status, StatusCode::BAD_GATEWAY);
+    assert_eq!(backends.sibling_calls().await, 0);
+
+    let backends = Backends::start(400).await;
+    let (status, body) = backends.complete("retryable_statuses = [400]", false).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(backends.sibling_calls().await, 1);
+}
+
+#[tokio::test]
+async fn test_stream_fails_over_on_429_but_not_on_400() {
+    let backends = Backends::start(429).await;
+    let (_, body) = backends.complete("", true).await;
+    assert!(body.contains("Hello"), "{}", body);
+    assert_eq!(backends.sibling_calls().await, 1);
+
+    let backends = Backends::start(400).await;
+    let (status, body) = backends.complete("", true).await;
+    assert_eq!(
+        status,
+        StatusCode::OK,
+        "Stream headers are sent before the upstream answers"
+    );
+    assert!(body.contains("Failed to start model query"), "{}", body);
+    assert_eq!(backends.sibling_calls().await, 0);
+}
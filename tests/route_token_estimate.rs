@@ -0,0 +1,177 @@
+//! Integration tests for the routing token estimate cap
+//!
+//! Routing decides on the prompt's token estimate capped at
+//! `routing.token_estimate_cap` and at the largest `context_window` any tier
+//! accepts. Code requests show the effect: the rule router sends them to Deep
+//! above 1024 tokens and to Balanced otherwise. Every estimate used is
+//! recorded in `octoroute_route_token_estimate`.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::sync::Arc;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+/// `window` is added to every endpoint and `routing` to `[routing]`
+fn create_config(backend_url: &str, window: &str, routing: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+request_timeout_seconds = 30
+
+[[models.fast]]
+name = "fast-1"
+base_url = "{backend_url}"
+max_tokens = 2048
+{window}
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "{backend_url}"
+max_tokens = 4096
+{window}
+
+[[models.deep]]
+name = "deep-1"
+base_url = "{backend_url}"
+max_tokens = 8192
+{window}
+
+[routing]
+strategy = "rule"
+{routing}
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_sse_response() -> String {
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{"content":"fn main() {}"},"finish_reason":null}]}"#,
+        "data: [DONE]",
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+async fn start_backend() -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_sse_response())
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+/// A code request of `tokens` estimated tokens (4 characters each)
+fn code_request(tokens: usize) -> String {
+    serde_json::json!({ "message": "code".repeat(tokens), "task_type": "code" }).to_string()
+}
+
+/// Send a code request to `/chat`; returns status and served tier
+async fn chat(state: &AppState, tokens: usize) -> (StatusCode, Option<String>) {
+    let app = Router::new()
+        .route("/chat", post(octoroute::handlers::chat::handler))
+        .with_state(state.clone())
+        .layer(middleware::from_fn(request_id_middleware));
+    let request = Request::builder()
+        .method("POST")
+        .uri("/chat")
+        .header("content-type", "application/json")
+        .body(Body::from(code_request(tokens)))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap_or_default();
+    (status, json["model_tier"].as_str().map(str::to_string))
+}
+
+fn state(config: Config) -> AppState {
+    AppState::new(Arc::new(config)).expect("AppState::new should succeed")
+}
+
+#[tokio::test]
+async fn test_configured_cap_limits_routing_estimate() {
+    let backend = start_backend().await;
+    let uncapped = state(create_config(&backend.uri(), "", ""));
+    let capped = state(create_config(
+        &backend.uri(),
+        "",
+        "token_estimate_cap = 1000",
+    ));
+
+    assert_eq!(
+        chat(&uncapped, 2000).await,
+        (StatusCode::OK, Some("deep".to_string()))
+    );
+    assert_eq!(
+        chat(&capped, 2000).await,
+        (StatusCode::OK, Some("balanced".to_string()))
+    );
+
+    assert_eq!(
+        uncapped.metrics().route_token_estimate_stats(false),
+        (1, 2000.0)
+    );
+    assert_eq!(
+        capped.metrics().route_token_estimate_stats(true),
+        (1, 1000.0)
+    );
+    assert_eq!(capped.metrics().route_token_estimate_stats(false), (0, 0.0));
+}
+
+#[tokio::test]
+async fn test_estimate_below_cap_routes_unchanged() {
+    let backend = start_backend().await;
+    let uncapped = state(create_config(&backend.uri(), "", ""));
+    let capped = state(create_config(
+        &backend.uri(),
+        "",
+        "token_estimate_cap = 1000",
+    ));
+
+    for tokens in [100, 1000] {
+        assert_eq!(chat(&uncapped, tokens).await, chat(&capped, tokens).await);
+    }
+    assert_eq!(
+        capped.metrics().route_token_estimate_stats(false),
+        (2, 1100.0)
+    );
+    assert_eq!(capped.metrics().route_token_estimate_stats(true), (0, 0.0));
+}
+
+#[tokio::test]
+async fn test_estimate_is_capped_at_largest_context_window() {
+    let backend = start_backend().await;
+    let state = state(create_config(&backend.uri(), "context_window = 1500", ""));
+
+    // Fits no tier, so it is still rejected on its full size
+    let (status, _) = chat(&state, 3000).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(
+        state.metrics().route_token_estimate_stats(true),
+        (1, 1500.0)
+    );
+    assert_eq!(backend.received_requests().await.unwrap().len(), 0);
+}
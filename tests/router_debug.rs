@@ -0,0 +1,142 @@
+//! Integration tests for routing debug output (`observability.router_debug`)
+//!
+//! With the setting on, a completion requested with `x-octoroute-debug: true`
+//! carries an `octoroute_debug` field holding the decision and the router
+//! model's raw answer. Without the header, or with the setting off, the field
+//! is omitted.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::sync::Arc;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+const ROUTER_ANSWER: &str = "BALANCED - needs some reasoning but not deep analysis";
+
+fn create_config(router_url: &str, backend_url: &str, router_debug: bool) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+request_timeout_seconds = 30
+
+[[models.fast]]
+name = "fast-router"
+base_url = "{router_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "{backend_url}"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:9997/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "llm"
+router_tier = "fast"
+
+[observability]
+router_debug = {router_debug}
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_sse_response(content: &str) -> String {
+    let content = serde_json::to_string(content).unwrap();
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#.to_string(),
+        format!(
+            r#"data: {{"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{{"index":0,"delta":{{"content":{content}}},"finish_reason":null}}]}}"#
+        ),
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#.to_string(),
+        "data: [DONE]".to_string(),
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+async fn start_server(content: &str) -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_sse_response(content))
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+/// Send an auto-routed completion and return the JSON body
+async fn complete(router_debug: bool, debug_header: Option<&str>) -> serde_json::Value {
+    let router = start_server(ROUTER_ANSWER).await;
+    let backend = start_server("Here is an explanation").await;
+    let config = create_config(&router.uri(), &backend.uri(), router_debug);
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+    let app = Router::new()
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware));
+
+    let mut request = Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json");
+    if let Some(value) = debug_header {
+        request = request.header("x-octoroute-debug", value);
+    }
+    let request = request
+        .body(Body::from(
+            r#"{"model": "auto", "messages": [{"role": "user", "content": "Explain ownership"}]}"#,
+        ))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[tokio::test]
+async fn test_debug_mode_surfaces_raw_router_answer() {
+    let json = complete(true, Some("true")).await;
+
+    let debug = &json["octoroute_debug"];
+    assert_eq!(debug["tier"], "balanced");
+    assert_eq!(debug["strategy"], "llm");
+    assert_eq!(debug["router_response"], ROUTER_ANSWER);
+}
+
+#[tokio::test]
+async fn test_normal_mode_omits_debug_field() {
+    let json = complete(true, None).await;
+    assert!(json.get("octoroute_debug").is_none(), "{}", json);
+}
+
+#[tokio::test]
+async fn test_debug_header_ignored_when_disabled_in_config() {
+    let json = complete(false, Some("true")).await;
+    assert!(json.get("octoroute_debug").is_none(), "{}", json);
+}
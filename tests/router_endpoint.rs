@@ -0,0 +1,197 @@
+//! Integration tests for the dedicated router endpoint (`[routing.router_endpoint]`)
+//!
+//! With a router endpoint configured, routing queries go to it rather than to
+//! the `router_tier` endpoints, which keep serving completions as usual. When
+//! the router endpoint fails, the router tier answers instead.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::sync::Arc;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+fn create_config(router_endpoint_url: Option<&str>, fast_url: &str, balanced_url: &str) -> Config {
+    let router_endpoint = router_endpoint_url
+        .map(|url| {
+            format!(
+                r#"
+[routing.router_endpoint]
+name = "router-model"
+base_url = "{url}"
+max_tokens = 16
+temperature = 0.0
+"#
+            )
+        })
+        .unwrap_or_default();
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+request_timeout_seconds = 30
+
+[[models.fast]]
+name = "fast-1"
+base_url = "{fast_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "{balanced_url}"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:9997/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "llm"
+router_tier = "fast"
+retry_backoff_ms = 0
+{router_endpoint}
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_sse_response(content: &str) -> String {
+    let content = serde_json::to_string(content).unwrap();
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#.to_string(),
+        format!(
+            r#"data: {{"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{{"index":0,"delta":{{"content":{content}}},"finish_reason":null}}]}}"#
+        ),
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#.to_string(),
+        "data: [DONE]".to_string(),
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+async fn start_server(content: &str) -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_sse_response(content))
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+async fn start_failing_server() -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+async fn call_count(server: &MockServer) -> usize {
+    server.received_requests().await.unwrap().len()
+}
+
+/// Send a completion for `model`, returning the status and JSON body
+async fn complete(config: Config, model: &str) -> (StatusCode, serde_json::Value) {
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+    let app = Router::new()
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware));
+
+    let body = format!(
+        r#"{{"model": "{model}", "messages": [{{"role": "user", "content": "Explain ownership"}}]}}"#
+    );
+    let request = Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(body))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn test_routing_queries_target_the_router_endpoint() {
+    let router = start_server("BALANCED").await;
+    // The fast tier would answer DEEP if it were asked to route
+    let fast = start_server("DEEP").await;
+    let balanced = start_server("Ownership means...").await;
+    let config = create_config(Some(&router.uri()), &fast.uri(), &balanced.uri());
+
+    let (status, json) = complete(config, "auto").await;
+
+    assert_eq!(status, StatusCode::OK, "{}", json);
+    assert_eq!(json["model"], "balanced-1");
+    assert_eq!(call_count(&router).await, 1);
+    assert_eq!(call_count(&fast).await, 0);
+    assert_eq!(call_count(&balanced).await, 1);
+}
+
+#[tokio::test]
+async fn test_serving_tiers_are_unaffected_by_router_endpoint() {
+    let router = start_server("BALANCED").await;
+    let fast = start_server("Quick answer").await;
+    let balanced = start_server("Ownership means...").await;
+    let config = create_config(Some(&router.uri()), &fast.uri(), &balanced.uri());
+
+    let (status, json) = complete(config, "fast").await;
+
+    assert_eq!(status, StatusCode::OK, "{}", json);
+    assert_eq!(json["model"], "fast-1");
+    assert_eq!(call_count(&router).await, 0);
+    assert_eq!(call_count(&fast).await, 1);
+}
+
+#[tokio::test]
+async fn test_router_tier_is_the_fallback_when_router_endpoint_fails() {
+    let router = start_failing_server().await;
+    let fast = start_server("BALANCED").await;
+    let balanced = start_server("Ownership means...").await;
+    let config = create_config(Some(&router.uri()), &fast.uri(), &balanced.uri());
+
+    let (status, json) = complete(config, "auto").await;
+
+    assert_eq!(status, StatusCode::OK, "{}", json);
+    assert_eq!(json["model"], "balanced-1");
+    assert_eq!(call_count(&router).await, 1);
+    assert_eq!(call_count(&fast).await, 1);
+}
+
+#[tokio::test]
+async fn test_without_router_endpoint_router_tier_routes() {
+    let fast = start_server("BALANCED").await;
+    let balanced = start_server("Ownership means...").await;
+    let config = create_config(None, &fast.uri(), &balanced.uri());
+
+    let (status, json) = complete(config, "auto").await;
+
+    assert_eq!(status, StatusCode::OK, "{}", json);
+    assert_eq!(json["model"], "balanced-1");
+    assert_eq!(call_count(&fast).await, 1);
+}
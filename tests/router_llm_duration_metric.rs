@@ -0,0 +1,119 @@
+//! Integration tests for `octoroute_router_llm_duration_ms`
+//!
+//! Each LLM router query records its latency under the tier that made the
+//! routing decision, separately from the per-strategy routing duration.
+
+use octoroute::config::Config;
+use octoroute::metrics::{Metrics, Tier};
+use octoroute::models::ModelSelector;
+use octoroute::router::llm_based::LlmBasedRouter;
+use octoroute::router::{Importance, RouteMetadata, TargetModel, TaskType};
+use std::sync::Arc;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+fn create_config(fast_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+
+[[models.fast]]
+name = "fast-router"
+base_url = "{fast_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "http://localhost:9998/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:9997/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "llm"
+router_tier = "fast"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+/// SSE stream whose content is the routing decision "DEEP"
+fn create_router_sse_response() -> String {
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"fast-router","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"fast-router","choices":[{"index":0,"delta":{"content":"DEEP"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"fast-router","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#,
+        "data: [DONE]",
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+fn test_metadata() -> RouteMetadata {
+    RouteMetadata {
+        token_estimate: 100,
+        importance: Importance::Normal,
+        task_type: TaskType::QuestionAnswer,
+    }
+}
+
+#[tokio::test]
+async fn test_router_query_records_duration_with_router_tier_label() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_router_sse_response())
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let config = Arc::new(create_config(&mock_server.uri()));
+    let metrics = Arc::new(Metrics::new().expect("should create metrics"));
+    let selector = Arc::new(ModelSelector::new(config, metrics.clone()));
+    let router = LlmBasedRouter::new(selector, TargetModel::Fast, 10, metrics.clone())
+        .expect("should create LlmBasedRouter");
+
+    let decision = router
+        .route("Explain the trade-offs of B-trees", &test_metadata())
+        .await
+        .expect("mock router should return a decision");
+
+    assert_eq!(decision.target(), TargetModel::Deep);
+    assert_eq!(metrics.router_llm_duration_count(Tier::Fast), 1);
+    assert_eq!(
+        metrics.router_llm_duration_count(Tier::Deep),
+        0,
+        "latency is labeled by the deciding tier, not the chosen tier"
+    );
+    let output = metrics.gather().expect("should gather metrics");
+    assert!(output.contains("octoroute_router_llm_duration_ms_count{tier=\"fast\"} 1"));
+}
+
+#[tokio::test]
+async fn test_failed_router_query_still_records_duration() {
+    // Nothing listens on port 1, so every attempt fails to connect
+    let config = Arc::new(create_config("http://127.0.0.1:1/v1"));
+    let metrics = Arc::new(Metrics::new().expect("should create metrics"));
+    let selector = Arc::new(ModelSelector::new(config, metrics.clone()));
+    let router = LlmBasedRouter::new(selector, TargetModel::Fast, 10, metrics.clone())
+        .expect("should create LlmBasedRouter");
+
+    let result = router.route("Hello", &test_metadata()).await;
+
+    assert!(result.is_err(), "unreachable router endpoint should fail");
+    assert!(
+        metrics.router_llm_duration_count(Tier::Fast) >= 1,
+        "failed attempts should still be timed"
+    );
+}
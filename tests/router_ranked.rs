@@ -0,0 +1,129 @@
+//! Integration tests for `LlmBasedRouter::route_ranked`
+//!
+//! The router model is asked for a JSON array of scored tiers. A valid ranking
+//! comes back sorted by score; any other answer is parsed the way `route`
+//! parses it and returned as that single tier with a score of 1.0.
+
+use octoroute::config::Config;
+use octoroute::metrics::Metrics;
+use octoroute::models::ModelSelector;
+use octoroute::router::llm_based::LlmBasedRouter;
+use octoroute::router::{Importance, RouteMetadata, TargetModel, TaskType};
+use std::sync::Arc;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{body_string_contains, method, path},
+};
+
+fn create_config(fast_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+
+[[models.fast]]
+name = "fast-router"
+base_url = "{fast_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "http://localhost:9998/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:9997/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "llm"
+router_tier = "fast"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_sse_response(content: &str) -> String {
+    let content = serde_json::to_string(content).unwrap();
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"fast-router","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#.to_string(),
+        format!(
+            r#"data: {{"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"fast-router","choices":[{{"index":0,"delta":{{"content":{content}}},"finish_reason":null}}]}}"#
+        ),
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"fast-router","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#.to_string(),
+        "data: [DONE]".to_string(),
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+/// Router endpoint answering `answer`, but only to a prompt asking for a ranking
+async fn start_router_endpoint(answer: &str) -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .and(body_string_contains("rank all three tiers"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_sse_response(answer))
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+fn create_router(mock_url: &str) -> LlmBasedRouter {
+    let config = Arc::new(create_config(mock_url));
+    let metrics = Arc::new(Metrics::new().expect("should create metrics"));
+    let selector = Arc::new(ModelSelector::new(config, metrics.clone()));
+    LlmBasedRouter::new(selector, TargetModel::Fast, 10, metrics)
+        .expect("should create LlmBasedRouter")
+}
+
+fn test_metadata() -> RouteMetadata {
+    RouteMetadata {
+        token_estimate: 100,
+        importance: Importance::Normal,
+        task_type: TaskType::QuestionAnswer,
+    }
+}
+
+#[tokio::test]
+async fn test_ranked_json_parses_into_ordered_vec() {
+    let mock_server = start_router_endpoint(
+        r#"[{"tier": "BALANCED", "score": 0.6}, {"tier": "DEEP", "score": 0.3}, {"tier": "FAST", "score": 0.1}]"#,
+    )
+    .await;
+    let router = create_router(&mock_server.uri());
+
+    let ranking = router
+        .route_ranked("Compare two sorting algorithms", &test_metadata())
+        .await
+        .expect("ranking should succeed");
+
+    assert_eq!(
+        ranking,
+        vec![
+            (TargetModel::Balanced, 0.6),
+            (TargetModel::Deep, 0.3),
+            (TargetModel::Fast, 0.1),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_malformed_ranking_falls_back_to_single_tier() {
+    let mock_server = start_router_endpoint(r#"[{"tier": "DEEP", "score": "high"}]"#).await;
+    let router = create_router(&mock_server.uri());
+
+    let ranking = router
+        .route_ranked("Prove the halting problem undecidable", &test_metadata())
+        .await
+        .expect("single-tier fallback should succeed");
+
+    assert_eq!(ranking, vec![(TargetModel::Deep, 1.0)]);
+}
@@ -0,0 +1,156 @@
+//! Integration tests for the LLM router retry policy (`[routing.retry_policy]`)
+//!
+//! Connection failures (the router endpoint answers with an error before any
+//! text) and stream failures (here: the router timeout) each stop retrying once
+//! their own budget is used up, regardless of the other budget.
+
+use octoroute::config::{Config, RouterRetryPolicy};
+use octoroute::error::AppError;
+use octoroute::metrics::Metrics;
+use octoroute::models::ModelSelector;
+use octoroute::router::llm_based::{LlmBasedRouter, LlmRouterError, RouterFailureKind};
+use octoroute::router::{RouteMetadata, TargetModel};
+use std::sync::Arc;
+use std::time::Duration;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Four Balanced endpoints, all served by `router_url`, so every attempt lands there
+fn create_config(router_url: &str) -> Config {
+    let balanced: String = (1..=4)
+        .map(|i| {
+            format!(
+                "[[models.balanced]]\nname = \"balanced-{i}\"\nbase_url = \"{router_url}\"\nmax_tokens = 4096\n\n"
+            )
+        })
+        .collect();
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+request_timeout_seconds = 30
+
+[[models.fast]]
+name = "fast-1"
+base_url = "http://localhost:11434/v1"
+max_tokens = 2048
+
+{balanced}
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:8080/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "llm"
+router_tier = "balanced"
+retry_backoff_ms = 0
+"#
+    );
+    toml::from_str(&toml).expect("should parse test config")
+}
+
+fn create_router(router_url: &str, retry_policy: RouterRetryPolicy) -> LlmBasedRouter {
+    let config = Arc::new(create_config(router_url));
+    let metrics = Arc::new(Metrics::new().expect("should create metrics"));
+    let selector = Arc::new(ModelSelector::new(config, metrics.clone()));
+    LlmBasedRouter::new(selector, TargetModel::Balanced, 1, metrics)
+        .expect("balanced tier is configured")
+        .with_retry_backoff_ms(0)
+        .with_retry_policy(retry_policy)
+}
+
+async fn attempts_made(server: &MockServer) -> usize {
+    server.received_requests().await.map_or(0, |r| r.len())
+}
+
+#[tokio::test]
+async fn test_connection_failures_exhaust_connection_budget() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(503).set_body_string("Service Unavailable"))
+        .mount(&server)
+        .await;
+
+    // A single stream attempt must not cap connection retries
+    let router = create_router(
+        &server.uri(),
+        RouterRetryPolicy {
+            connection_attempts: 3,
+            stream_attempts: 1,
+        },
+    );
+    let result = router.route("Hello", &RouteMetadata::new(10)).await;
+
+    match result {
+        Err(AppError::LlmRouting(e)) => {
+            assert_eq!(e.failure_kind(), Some(RouterFailureKind::Connection), "{e}")
+        }
+        other => panic!("expected a connection failure, got {:?}", other),
+    }
+    assert_eq!(attempts_made(&server).await, 3);
+}
+
+#[tokio::test]
+async fn test_stream_failures_exhaust_stream_budget() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(5)))
+        .mount(&server)
+        .await;
+
+    // A generous connection budget must not extend stream retries
+    let router = create_router(
+        &server.uri(),
+        RouterRetryPolicy {
+            connection_attempts: 4,
+            stream_attempts: 2,
+        },
+    );
+    let result = router.route("Hello", &RouteMetadata::new(10)).await;
+
+    assert!(
+        matches!(
+            result,
+            Err(AppError::LlmRouting(LlmRouterError::Timeout { .. }))
+        ),
+        "expected the router timeout, got {:?}",
+        result
+    );
+    assert_eq!(attempts_made(&server).await, 2);
+}
+
+#[test]
+fn test_failure_kind_classification() {
+    let connection = LlmRouterError::StreamError {
+        endpoint: "test".to_string(),
+        bytes_received: 0,
+        error_message: "connection refused".to_string(),
+    };
+    let mid_stream = LlmRouterError::StreamError {
+        endpoint: "test".to_string(),
+        bytes_received: 12,
+        error_message: "connection reset".to_string(),
+    };
+    let timeout = LlmRouterError::Timeout {
+        endpoint: "test".to_string(),
+        timeout_seconds: 10,
+        attempt: 1,
+        max_attempts: 2,
+        router_tier: TargetModel::Balanced,
+    };
+    let empty = LlmRouterError::EmptyResponse {
+        endpoint: "test".to_string(),
+    };
+
+    assert_eq!(
+        connection.failure_kind(),
+        Some(RouterFailureKind::Connection)
+    );
+    assert_eq!(mid_stream.failure_kind(), Some(RouterFailureKind::Stream));
+    assert_eq!(timeout.failure_kind(), Some(RouterFailureKind::Stream));
+    assert_eq!(empty.failure_kind(), None);
+}
@@ -0,0 +1,172 @@
+//! Integration tests for `routing.router_same_endpoint_retries`
+//!
+//! The router tier has a single endpoint whose first answer is empty. With the
+//! option enabled the router asks that endpoint again and routes on the second
+//! answer; without it the empty answer fails fast as a systemic error.
+
+use octoroute::error::AppError;
+use octoroute::metrics::{EndpointOutcome, Metrics};
+use octoroute::models::ModelSelector;
+use octoroute::router::llm_based::{LlmBasedRouter, LlmRouterError};
+use octoroute::router::{RouteMetadata, TargetModel};
+use octoroute::{config::Config, shared::call_budget::CallBudget};
+use std::sync::Arc;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn create_config(router_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+request_timeout_seconds = 30
+
+[[models.fast]]
+name = "fast-1"
+base_url = "http://localhost:11434/v1"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-router"
+base_url = "{router_url}"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:8080/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "llm"
+router_tier = "balanced"
+retry_backoff_ms = 0
+"#
+    );
+    toml::from_str(&toml).expect("should parse test config")
+}
+
+fn sse_response(content: &str) -> ResponseTemplate {
+    let mut events = Vec::new();
+    if !content.is_empty() {
+        events.push(format!(
+            r#"data: {{"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{{"index":0,"delta":{{"content":"{content}"}},"finish_reason":null}}]}}"#
+        ));
+    }
+    events.push("data: [DONE]".to_string());
+    ResponseTemplate::new(200)
+        .set_body_string(events.join("\n\n") + "\n\n")
+        .insert_header("content-type", "text/event-stream")
+}
+
+/// Router endpoint answering empty `empty_answers` times, then "DEEP"
+async fn start_router(empty_answers: u64) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(sse_response(""))
+        .up_to_n_times(empty_answers)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(sse_response("DEEP"))
+        .mount(&server)
+        .await;
+    server
+}
+
+fn create_router(router_url: &str, retries: usize) -> (LlmBasedRouter, Arc<Metrics>) {
+    let config = Arc::new(create_config(router_url));
+    let metrics = Arc::new(Metrics::new().expect("should create metrics"));
+    let selector = Arc::new(ModelSelector::new(config, metrics.clone()));
+    let router = LlmBasedRouter::new(selector, TargetModel::Balanced, 5, metrics.clone())
+        .expect("balanced tier is configured")
+        .with_retry_backoff_ms(0)
+        .with_same_endpoint_retries(retries);
+    (router, metrics)
+}
+
+async fn queries(server: &MockServer) -> usize {
+    server.received_requests().await.map_or(0, |r| r.len())
+}
+
+#[tokio::test]
+async fn test_empty_answer_is_retried_once_on_same_endpoint() {
+    let server = start_router(1).await;
+    let (router, metrics) = create_router(&server.uri(), 1);
+
+    let decision = router
+        .route("Explain monads", &RouteMetadata::new(10))
+        .await
+        .expect("the second answer should route");
+
+    assert_eq!(decision.target(), TargetModel::Deep);
+    assert_eq!(queries(&server).await, 2);
+    assert_eq!(
+        metrics.endpoint_requests_count("balanced-router", EndpointOutcome::Failure),
+        1
+    );
+    assert_eq!(
+        metrics.endpoint_requests_count("balanced-router", EndpointOutcome::Success),
+        1
+    );
+}
+
+#[tokio::test]
+async fn test_empty_answer_fails_fast_when_disabled() {
+    let server = start_router(1).await;
+    let (router, _) = create_router(&server.uri(), 0);
+
+    let result = router
+        .route("Explain monads", &RouteMetadata::new(10))
+        .await;
+
+    assert!(
+        matches!(
+            result,
+            Err(AppError::LlmRouting(LlmRouterError::EmptyResponse { .. }))
+        ),
+        "expected the empty answer error, got {:?}",
+        result
+    );
+    assert_eq!(queries(&server).await, 1);
+}
+
+#[tokio::test]
+async fn test_second_empty_answer_fails() {
+    let server = start_router(2).await;
+    let (router, _) = create_router(&server.uri(), 1);
+
+    let result = router
+        .route("Explain monads", &RouteMetadata::new(10))
+        .await;
+
+    assert!(
+        matches!(
+            result,
+            Err(AppError::LlmRouting(LlmRouterError::EmptyResponse { .. }))
+        ),
+        "expected the empty answer error, got {:?}",
+        result
+    );
+    assert_eq!(queries(&server).await, 2);
+}
+
+#[tokio::test]
+async fn test_retry_spends_call_budget() {
+    let server = start_router(1).await;
+    let (router, _) = create_router(&server.uri(), 1);
+
+    let result = router
+        .route_with_budget(
+            "Explain monads",
+            &RouteMetadata::new(10),
+            &CallBudget::new(Some(1)),
+        )
+        .await;
+
+    assert!(result.is_err(), "no call left for the retry: {:?}", result);
+    assert_eq!(queries(&server).await, 1);
+}
@@ -0,0 +1,140 @@
+//! Integration tests for `octoroute_router_tier_exhaustion_total`
+//!
+//! Every router attempt that finds no router tier endpoint to query is counted
+//! by router tier and the reason selection failed. An empty router tier
+//! (`no_endpoints`) is rejected when the router is built, so only the reachable
+//! reasons are exercised here.
+
+use octoroute::config::Config;
+use octoroute::metrics::{Metrics, Tier};
+use octoroute::models::ModelSelector;
+use octoroute::router::llm_based::LlmBasedRouter;
+use octoroute::router::{RouteMetadata, TargetModel};
+use std::sync::Arc;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn create_config(router_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+request_timeout_seconds = 30
+
+[[models.fast]]
+name = "fast-1"
+base_url = "http://localhost:11434/v1"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "{router_url}"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:8080/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "llm"
+router_tier = "balanced"
+"#
+    );
+    toml::from_str(&toml).expect("should parse test config")
+}
+
+/// Router on the Balanced tier's single endpoint, with the shared metrics
+fn create_router(router_url: &str) -> (LlmBasedRouter, Arc<ModelSelector>, Arc<Metrics>) {
+    let metrics = Arc::new(Metrics::new().expect("should create metrics"));
+    let selector = Arc::new(ModelSelector::new(
+        Arc::new(create_config(router_url)),
+        metrics.clone(),
+    ));
+    let router = LlmBasedRouter::new(selector.clone(), TargetModel::Balanced, 1, metrics.clone())
+        .expect("balanced tier is configured")
+        .with_retry_backoff_ms(0);
+    (router, selector, metrics)
+}
+
+#[tokio::test]
+async fn test_failed_endpoint_counts_as_all_excluded() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(503).set_body_string("Service Unavailable"))
+        .mount(&server)
+        .await;
+    let (router, _selector, metrics) = create_router(&server.uri());
+
+    let result = router.route("Hello", &RouteMetadata::new(10)).await;
+
+    assert!(result.is_err());
+    // The first attempt fails at the endpoint, the retry finds it excluded
+    assert_eq!(
+        metrics.router_tier_exhaustion_count(Tier::Balanced, "all_excluded"),
+        1
+    );
+    assert_eq!(
+        metrics.router_tier_exhaustion_count(Tier::Balanced, "all_unhealthy"),
+        0
+    );
+}
+
+#[tokio::test]
+async fn test_unhealthy_tier_counts_every_attempt_as_all_unhealthy() {
+    let server = MockServer::start().await;
+    let (router, selector, metrics) = create_router(&server.uri());
+    for _ in 0..3 {
+        selector
+            .health_checker()
+            .mark_failure("balanced-1")
+            .await
+            .expect("mark_failure should succeed");
+    }
+
+    let result = router.route("Hello", &RouteMetadata::new(10)).await;
+
+    assert!(result.is_err());
+    assert_eq!(
+        metrics.router_tier_exhaustion_count(Tier::Balanced, "all_unhealthy"),
+        2,
+        "both default attempts should find the tier unhealthy"
+    );
+    assert_eq!(
+        metrics.router_tier_exhaustion_count(Tier::Balanced, "all_excluded"),
+        0
+    );
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_successful_routing_records_no_exhaustion() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(
+                    "data: {\"id\":\"chatcmpl-test\",\"object\":\"chat.completion.chunk\",\"created\":1234567890,\"model\":\"test\",\"choices\":[{\"index\":0,\"delta\":{\"content\":\"FAST\"},\"finish_reason\":null}]}\n\ndata: [DONE]\n\n",
+                )
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&server)
+        .await;
+    let (router, _selector, metrics) = create_router(&server.uri());
+
+    let decision = router
+        .route("Hello", &RouteMetadata::new(10))
+        .await
+        .expect("router should answer");
+
+    assert_eq!(decision.target(), TargetModel::Fast);
+    for reason in ["no_endpoints", "all_unhealthy", "all_excluded", "mixed"] {
+        assert_eq!(
+            metrics.router_tier_exhaustion_count(Tier::Balanced, reason),
+            0
+        );
+    }
+}
@@ -0,0 +1,176 @@
+//! Integration tests for `routing.on_unparseable`
+//!
+//! A router answer that names no tier fails the request by default. With a
+//! fallback configured the request is routed to the safe tier instead, with a
+//! warning on the decision and an increment of
+//! `octoroute_router_unparseable_fallback_total`. Other systemic failures
+//! (refusals, empty answers) are not covered by this setting.
+
+use octoroute::config::{Config, UnparseableFallback};
+use octoroute::error::AppError;
+use octoroute::metrics::{Metrics, Tier};
+use octoroute::models::ModelSelector;
+use octoroute::router::llm_based::{LlmBasedRouter, LlmRouterError};
+use octoroute::router::{Importance, RouteMetadata, TargetModel, TaskType};
+use std::sync::Arc;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+/// `default_tier = "deep"` so the default_tier fallback is distinguishable from balanced
+fn create_config(fast_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+
+[[models.fast]]
+name = "fast-router"
+base_url = "{fast_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "http://localhost:9998/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:9997/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "llm"
+router_tier = "fast"
+default_tier = "deep"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+/// SSE stream whose content is the router's whole answer
+fn create_router_sse_response(answer: &str) -> String {
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"fast-router","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#.to_string(),
+        format!(
+            r#"data: {{"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"fast-router","choices":[{{"index":0,"delta":{{"content":"{answer}"}},"finish_reason":null}}]}}"#
+        ),
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"fast-router","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#.to_string(),
+        "data: [DONE]".to_string(),
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+fn test_metadata() -> RouteMetadata {
+    RouteMetadata {
+        token_estimate: 100,
+        importance: Importance::Normal,
+        task_type: TaskType::QuestionAnswer,
+    }
+}
+
+/// Router endpoint answering exactly one query with `answer`
+async fn start_router_endpoint(answer: &str) -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_router_sse_response(answer))
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+fn create_router(
+    mock_url: &str,
+    on_unparseable: UnparseableFallback,
+) -> (LlmBasedRouter, Arc<Metrics>) {
+    let config = Arc::new(create_config(mock_url));
+    let metrics = Arc::new(Metrics::new().expect("should create metrics"));
+    let selector = Arc::new(ModelSelector::new(config, metrics.clone()));
+    let router = LlmBasedRouter::new(selector, TargetModel::Fast, 10, metrics.clone())
+        .expect("should create LlmBasedRouter")
+        .with_on_unparseable(on_unparseable);
+    (router, metrics)
+}
+
+#[tokio::test]
+async fn test_garbage_answer_routes_to_balanced_with_warning() {
+    let mock_server = start_router_endpoint("hmm, let me think about it").await;
+    let (router, metrics) = create_router(&mock_server.uri(), UnparseableFallback::Balanced);
+
+    let decision = router
+        .route("What is a monad?", &test_metadata())
+        .await
+        .expect("unparseable answer should fall back to balanced");
+
+    assert_eq!(decision.target(), TargetModel::Balanced);
+    assert_eq!(decision.warnings().len(), 1);
+    assert!(
+        decision.warnings()[0].contains("unparseable")
+            && decision.warnings()[0].contains("on_unparseable = 'balanced'"),
+        "got: {:?}",
+        decision.warnings()
+    );
+    assert_eq!(metrics.router_unparseable_fallback_count(Tier::Balanced), 1);
+}
+
+#[tokio::test]
+async fn test_garbage_answer_routes_to_default_tier() {
+    let mock_server = start_router_endpoint("hmm, let me think about it").await;
+    let (router, metrics) = create_router(&mock_server.uri(), UnparseableFallback::DefaultTier);
+
+    let decision = router
+        .route("What is a monad?", &test_metadata())
+        .await
+        .expect("unparseable answer should fall back to the default tier");
+
+    assert_eq!(decision.target(), TargetModel::Deep);
+    assert_eq!(metrics.router_unparseable_fallback_count(Tier::Deep), 1);
+}
+
+#[tokio::test]
+async fn test_garbage_answer_fails_with_error_setting() {
+    let mock_server = start_router_endpoint("hmm, let me think about it").await;
+    let (router, metrics) = create_router(&mock_server.uri(), UnparseableFallback::Error);
+
+    let err = router
+        .route("What is a monad?", &test_metadata())
+        .await
+        .expect_err("default setting keeps failing on unparseable answers");
+
+    assert!(
+        matches!(
+            err,
+            AppError::LlmRouting(LlmRouterError::UnparseableResponse { .. })
+        ),
+        "expected UnparseableResponse, got: {:?}",
+        err
+    );
+    assert_eq!(metrics.router_unparseable_fallback_count(Tier::Balanced), 0);
+}
+
+#[tokio::test]
+async fn test_refusal_is_not_covered_by_on_unparseable() {
+    let mock_server = start_router_endpoint("Sorry, I cannot help with that").await;
+    let (router, metrics) = create_router(&mock_server.uri(), UnparseableFallback::Balanced);
+
+    let err = router
+        .route("What is a monad?", &test_metadata())
+        .await
+        .expect_err("refusals still fail");
+
+    assert!(
+        matches!(err, AppError::LlmRouting(LlmRouterError::Refusal { .. })),
+        "expected Refusal, got: {:?}",
+        err
+    );
+    assert_eq!(metrics.router_unparseable_fallback_count(Tier::Balanced), 0);
+}
@@ -0,0 +1,194 @@
+//! Integration tests for the routing decision cache (`[routing.cache]`)
+//!
+//! With a cache configured, a prompt the router model has already classified
+//! reuses the cached tier instead of querying the router again. The Redis
+//! backend tests need the `redis` feature; the one sharing decisions also
+//! needs a server at `OCTOROUTE_TEST_REDIS_URL` and is ignored by default
+//! (run it with `cargo test --features redis -- --ignored`).
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::sync::Arc;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+fn create_config(router_url: &str, backend_url: &str, cache: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+request_timeout_seconds = 30
+
+[[models.fast]]
+name = "fast-router"
+base_url = "{router_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "{backend_url}"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:9997/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "llm"
+router_tier = "fast"
+{cache}
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_sse_response(content: &str) -> String {
+    let content = serde_json::to_string(content).unwrap();
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#.to_string(),
+        format!(
+            r#"data: {{"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{{"index":0,"delta":{{"content":{content}}},"finish_reason":null}}]}}"#
+        ),
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#.to_string(),
+        "data: [DONE]".to_string(),
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+async fn start_server(content: &str) -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_sse_response(content))
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+async fn call_count(server: &MockServer) -> usize {
+    server.received_requests().await.unwrap().len()
+}
+
+/// Send each message to `/chat` in turn, asserting every request succeeds
+async fn chat_all(config: Config, messages: &[&str]) {
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+    let app = Router::new()
+        .route("/chat", post(octoroute::handlers::chat::handler))
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware));
+
+    for message in messages {
+        let body = serde_json::json!({ "message": message }).to_string();
+        let request = Request::builder()
+            .method("POST")
+            .uri("/chat")
+            .header("content-type", "application/json")
+            .body(Body::from(body))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
+
+const SAME_PROMPT: [&str; 2] = [
+    "Explain the borrow checker",
+    "  Explain the\n\nborrow   checker ",
+];
+
+#[tokio::test]
+async fn test_cached_decision_skips_router_query() {
+    let router = start_server("BALANCED").await;
+    let backend = start_server("The borrow checker...").await;
+    let config = create_config(&router.uri(), &backend.uri(), "[routing.cache]\n");
+
+    chat_all(config, &SAME_PROMPT).await;
+
+    assert_eq!(call_count(&router).await, 1, "second prompt should hit");
+    assert_eq!(call_count(&backend).await, 2);
+}
+
+#[tokio::test]
+async fn test_different_prompts_each_query_router() {
+    let router = start_server("BALANCED").await;
+    let backend = start_server("Answer").await;
+    let config = create_config(&router.uri(), &backend.uri(), "[routing.cache]\n");
+
+    chat_all(config, &["Explain the borrow checker", "Explain lifetimes"]).await;
+
+    assert_eq!(call_count(&router).await, 2);
+}
+
+#[tokio::test]
+async fn test_without_cache_every_request_queries_router() {
+    let router = start_server("BALANCED").await;
+    let backend = start_server("The borrow checker...").await;
+    let config = create_config(&router.uri(), &backend.uri(), "");
+
+    chat_all(config, &SAME_PROMPT).await;
+
+    assert_eq!(call_count(&router).await, 2);
+}
+
+#[cfg(feature = "redis")]
+mod redis_backend {
+    use octoroute::router::RoutingCache;
+    use octoroute::router::TargetModel;
+    use octoroute::router::cache::{REDIS_TIMEOUT, RedisRoutingCache};
+    use std::time::{Duration, Instant};
+
+    /// Two caches on one server and prefix stand in for two router instances
+    #[tokio::test]
+    #[ignore = "needs a Redis server at OCTOROUTE_TEST_REDIS_URL"]
+    async fn test_decision_stored_by_one_instance_is_seen_by_another() {
+        let url = std::env::var("OCTOROUTE_TEST_REDIS_URL")
+            .expect("OCTOROUTE_TEST_REDIS_URL should name a Redis server");
+        let prefix = format!("octoroute-test:{}:", uuid::Uuid::new_v4().simple());
+        let first = RedisRoutingCache::new(&url, prefix.clone()).expect("valid Redis URL");
+        let second = RedisRoutingCache::new(&url, prefix).expect("valid Redis URL");
+
+        assert_eq!(second.get(42).await.unwrap(), None);
+        first
+            .put(42, TargetModel::Deep, Duration::from_secs(30))
+            .await
+            .unwrap();
+        assert_eq!(second.get(42).await.unwrap(), Some(TargetModel::Deep));
+
+        second
+            .put(42, TargetModel::Fast, Duration::from_secs(30))
+            .await
+            .unwrap();
+        assert_eq!(first.get(42).await.unwrap(), Some(TargetModel::Fast));
+    }
+
+    /// A server that never answers costs a lookup no more than the timeout
+    #[tokio::test]
+    async fn test_unreachable_server_fails_within_timeout() {
+        // TEST-NET-1: nothing listens there, so connecting hangs or fails
+        let cache = RedisRoutingCache::new("redis://192.0.2.1:6379", "test:".to_string())
+            .expect("valid Redis URL");
+
+        let started = Instant::now();
+        assert!(cache.get(42).await.is_err());
+        assert!(
+            started.elapsed() < REDIS_TIMEOUT + Duration::from_secs(1),
+            "took {:?}",
+            started.elapsed()
+        );
+    }
+}
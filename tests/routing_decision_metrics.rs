@@ -0,0 +1,159 @@
+//! Integration tests for `octoroute_routing_decisions_total`
+//!
+//! The counter records what the router decided, so it only moves when a router
+//! actually runs. Hybrid decisions are recorded under the concrete path taken
+//! (rule or llm), never as "hybrid".
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::metrics::{Strategy, Tier};
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::sync::Arc;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+/// Every tier, including the router tier, points at the same mock
+fn create_hybrid_config(mock_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+request_timeout_seconds = 30
+
+[[models.fast]]
+name = "fast-1"
+base_url = "{mock_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "{mock_url}"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "{mock_url}"
+max_tokens = 8192
+
+[routing]
+strategy = "hybrid"
+router_tier = "fast"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+/// SSE answer that the LLM router reads as a Fast decision
+fn create_sse_response() -> String {
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"fast-1","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"fast-1","choices":[{"index":0,"delta":{"content":"FAST"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"fast-1","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#,
+        "data: [DONE]",
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+fn create_test_app(state: AppState) -> Router {
+    Router::new()
+        .route("/chat", post(octoroute::handlers::chat::handler))
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware))
+}
+
+async fn send(state: &AppState, uri: &str, body: &str) {
+    let request = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let response = create_test_app(state.clone())
+        .oneshot(request)
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_hybrid_decisions_are_counted_by_concrete_path() {
+    let mock_server = MockServer::start().await;
+    // Rule request: 1 backend query. LLM request: router query + backend query.
+    // Explicit tier request: 1 backend query.
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_sse_response())
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .expect(4)
+        .mount(&mock_server)
+        .await;
+
+    let state = AppState::new(Arc::new(create_hybrid_config(&mock_server.uri())))
+        .expect("AppState::new should succeed");
+
+    // Casual chat matches the Fast rule
+    send(
+        &state,
+        "/chat",
+        r#"{"message": "Hi there", "task_type": "casual_chat"}"#,
+    )
+    .await;
+    // A short question matches no rule, so the LLM router decides
+    send(&state, "/chat", r#"{"message": "What is Rust?"}"#).await;
+    // Explicit tier: no routing decision is made
+    send(
+        &state,
+        "/v1/chat/completions",
+        r#"{"model": "deep", "messages": [{"role": "user", "content": "Hello"}]}"#,
+    )
+    .await;
+
+    let metrics = state.metrics();
+    assert_eq!(
+        metrics.routing_decisions_count(Tier::Fast, Strategy::Rule),
+        1
+    );
+    assert_eq!(
+        metrics.routing_decisions_count(Tier::Fast, Strategy::Llm),
+        1
+    );
+    assert_eq!(
+        metrics.routing_decisions_count(Tier::Deep, Strategy::Rule),
+        0
+    );
+    assert_eq!(
+        metrics.routing_decisions_count(Tier::Fast, Strategy::Hybrid),
+        0
+    );
+
+    let output = metrics.gather().expect("should gather metrics");
+    let decision_lines: Vec<&str> = output
+        .lines()
+        .filter(|line| line.starts_with("octoroute_routing_decisions_total{"))
+        .collect();
+    assert_eq!(decision_lines.len(), 2, "got: {:?}", decision_lines);
+    assert!(
+        decision_lines
+            .iter()
+            .all(|line| !line.contains("strategy=\"hybrid\"")),
+        "hybrid must never be recorded as a strategy, got: {:?}",
+        decision_lines
+    );
+}
@@ -0,0 +1,216 @@
+//! Integration tests for the `RoutingObserver` hook
+//!
+//! An observer attached to `AppState` receives the request metadata and final
+//! decision of every tier-routed chat request, across the legacy `/chat`
+//! endpoint and both OpenAI completion modes. Named-endpoint requests skip
+//! routing and are not reported.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::router::{
+    NoopRoutingObserver, RouteMetadata, RoutingDecision, RoutingObserver, RoutingStrategy,
+    TargetModel, TaskType,
+};
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::sync::{Arc, Mutex};
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+#[derive(Default)]
+struct RecordingObserver {
+    decisions: Mutex<Vec<(RouteMetadata, RoutingDecision)>>,
+}
+
+impl RoutingObserver for RecordingObserver {
+    fn on_decision(&self, prompt_meta: &RouteMetadata, decision: &RoutingDecision) {
+        self.decisions
+            .lock()
+            .unwrap()
+            .push((*prompt_meta, decision.clone()));
+    }
+}
+
+impl RecordingObserver {
+    fn recorded(&self) -> Vec<(RouteMetadata, RoutingDecision)> {
+        self.decisions.lock().unwrap().clone()
+    }
+}
+
+fn create_config(mock_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+request_timeout_seconds = 30
+
+[[models.fast]]
+name = "fast-1"
+base_url = "{mock_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "{mock_url}"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "{mock_url}"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_sse_response(text: &str) -> String {
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#.to_string(),
+        format!(
+            r#"data: {{"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{{"index":0,"delta":{{"content":"{text}"}},"finish_reason":null}}]}}"#
+        ),
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#.to_string(),
+        "data: [DONE]".to_string(),
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+async fn start_backend() -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_sse_response("Hello"))
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+fn create_test_app(state: AppState) -> Router {
+    Router::new()
+        .route("/chat", post(octoroute::handlers::chat::handler))
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware))
+}
+
+async fn post_json(state: &AppState, uri: &str, body: &str) -> StatusCode {
+    let request = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let response = create_test_app(state.clone())
+        .oneshot(request)
+        .await
+        .unwrap();
+    let status = response.status();
+    // Drain streaming bodies so the whole request runs
+    axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    status
+}
+
+#[tokio::test]
+async fn test_observer_records_decisions_across_requests() {
+    let mock_server = start_backend().await;
+    let observer = Arc::new(RecordingObserver::default());
+    let state = AppState::new(Arc::new(create_config(&mock_server.uri())))
+        .expect("AppState::new should succeed")
+        .with_routing_observer(observer.clone());
+
+    let requests = [
+        (
+            "/chat",
+            r#"{"message": "Write a function to sort a list", "task_type": "code"}"#,
+        ),
+        (
+            "/v1/chat/completions",
+            r#"{"model": "deep", "messages": [{"role": "user", "content": "Hello"}]}"#,
+        ),
+        (
+            "/v1/chat/completions",
+            r#"{"model": "balanced", "stream": true, "messages": [{"role": "user", "content": "Hello"}]}"#,
+        ),
+        (
+            "/v1/chat/completions",
+            r#"{"model": "auto", "messages": [{"role": "user", "content": "Hi"}]}"#,
+        ),
+    ];
+    for (uri, body) in requests {
+        assert_eq!(
+            post_json(&state, uri, body).await,
+            StatusCode::OK,
+            "{}",
+            body
+        );
+    }
+
+    let recorded = observer.recorded();
+    assert_eq!(recorded.len(), 4);
+
+    let (meta, decision) = &recorded[0];
+    assert_eq!(meta.task_type, TaskType::Code);
+    assert_eq!(decision.strategy(), RoutingStrategy::Rule);
+
+    assert_eq!(recorded[1].1.target(), TargetModel::Deep);
+    assert_eq!(recorded[2].1.target(), TargetModel::Balanced);
+    assert!(recorded[3].0.token_estimate < 10);
+}
+
+#[tokio::test]
+async fn test_named_endpoint_requests_are_not_observed() {
+    let mock_server = start_backend().await;
+    let observer = Arc::new(RecordingObserver::default());
+    let state = AppState::new(Arc::new(create_config(&mock_server.uri())))
+        .expect("AppState::new should succeed")
+        .with_routing_observer(observer.clone());
+
+    let status = post_json(
+        &state,
+        "/v1/chat/completions",
+        r#"{"model": "fast-1", "messages": [{"role": "user", "content": "Hello"}]}"#,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(observer.recorded().is_empty());
+}
+
+#[tokio::test]
+async fn test_state_without_observer_and_noop_observer() {
+    let mock_server = start_backend().await;
+    let state = AppState::new(Arc::new(create_config(&mock_server.uri())))
+        .expect("AppState::new should succeed");
+    assert!(state.routing_observer().is_none());
+
+    let state = state.with_routing_observer(Arc::new(NoopRoutingObserver));
+    assert!(state.routing_observer().is_some());
+    let status = post_json(
+        &state,
+        "/v1/chat/completions",
+        r#"{"model": "fast", "messages": [{"role": "user", "content": "Hello"}]}"#,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+}
@@ -0,0 +1,169 @@
+//! Integration tests for `shared::query::run_completion`
+//!
+//! Both chat handlers make their upstream calls through `run_completion`, so the
+//! attempt metric and endpoint health must come out the same whichever handler
+//! asked: a success marks the endpoint healthy, a failure before any content is
+//! recorded against it, and a status outside `routing.retryable_statuses` is
+//! returned without touching its health.
+
+use octoroute::{
+    config::Config, handlers::AppState, metrics::EndpointOutcome, middleware::RequestId,
+    models::HealthFailureKind, router::TargetModel, shared::query::run_completion,
+};
+use std::sync::Arc;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+fn create_config(fast_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+request_timeout_seconds = 5
+
+[[models.fast]]
+name = "fast-1"
+base_url = "{fast_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "http://localhost:9998/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:9997/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_sse_response(content: &str) -> String {
+    [
+        format!(
+            r#"data: {{"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{{"index":0,"delta":{{"content":"{content}"}},"finish_reason":null}}]}}"#
+        ),
+        "data: [DONE]".to_string(),
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+async fn start_backend(response: ResponseTemplate) -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(response)
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+/// Run one completion against `fast-1`; returns the state for inspection
+async fn complete(base_url: &str) -> (AppState, Result<String, String>, Vec<String>) {
+    let state =
+        AppState::new(Arc::new(create_config(base_url))).expect("AppState::new should succeed");
+    let endpoint = state.config().models.tier(TargetModel::Fast)[0].clone();
+    let mut warnings = Vec::new();
+
+    let result = run_completion(
+        &state,
+        &endpoint,
+        TargetModel::Fast,
+        "Hello",
+        RequestId::new(),
+        1,
+        1,
+        None,
+        &mut warnings,
+    )
+    .await
+    .map(|answer| answer.text)
+    .map_err(|e| e.to_string());
+    (state, result, warnings)
+}
+
+async fn fast_health(state: &AppState) -> (bool, u32, Option<HealthFailureKind>) {
+    let statuses = state.selector().health_checker().get_all_statuses().await;
+    let health = statuses
+        .iter()
+        .find(|h| h.name() == "fast-1")
+        .expect("fast-1 should be tracked");
+    (
+        health.is_healthy(),
+        health.consecutive_failures(),
+        health.last_failure(),
+    )
+}
+
+fn attempts(state: &AppState, outcome: EndpointOutcome) -> u64 {
+    state.metrics().endpoint_requests_count("fast-1", outcome)
+}
+
+#[tokio::test]
+async fn test_success_returns_content_and_marks_endpoint_healthy() {
+    let backend = start_backend(
+        ResponseTemplate::new(200)
+            .set_body_string(create_sse_response("Hello there"))
+            .insert_header("content-type", "text/event-stream"),
+    )
+    .await;
+
+    let (state, result, warnings) = complete(&backend.uri()).await;
+
+    assert_eq!(result.as_deref(), Ok("Hello there"));
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    assert_eq!(attempts(&state, EndpointOutcome::Success), 1);
+    assert_eq!(attempts(&state, EndpointOutcome::Failure), 0);
+    let (healthy, failures, _) = fast_health(&state).await;
+    assert!(healthy);
+    assert_eq!(failures, 0);
+}
+
+#[tokio::test]
+async fn test_failure_before_first_token_is_recorded_against_endpoint() {
+    let backend = start_backend(ResponseTemplate::new(503)).await;
+
+    let (state, result, _) = complete(&backend.uri()).await;
+
+    assert!(result.is_err());
+    assert_eq!(attempts(&state, EndpointOutcome::Success), 0);
+    assert_eq!(attempts(&state, EndpointOutcome::Failure), 1);
+    let (_, failures, last_failure) = fast_health(&state).await;
+    assert_eq!(failures, 1);
+    assert!(last_failure.is_some());
+}
+
+#[tokio::test]
+async fn test_connection_failure_is_recorded_as_connection() {
+    // Nothing listens on port 1
+    let (state, result, _) = complete("http://127.0.0.1:1/v1").await;
+
+    assert!(result.is_err());
+    assert_eq!(attempts(&state, EndpointOutcome::Failure), 1);
+    let (_, failures, last_failure) = fast_health(&state).await;
+    assert_eq!(failures, 1);
+    assert_eq!(last_failure, Some(HealthFailureKind::Connection));
+}
+
+#[tokio::test]
+async fn test_non_retryable_status_leaves_health_untouched() {
+    let backend = start_backend(ResponseTemplate::new(400)).await;
+
+    let (state, result, _) = complete(&backend.uri()).await;
+
+    assert!(result.is_err());
+    assert_eq!(attempts(&state, EndpointOutcome::Failure), 1);
+    let (healthy, failures, last_failure) = fast_health(&state).await;
+    assert!(healthy);
+    assert_eq!(failures, 0);
+    assert_eq!(last_failure, None);
+}
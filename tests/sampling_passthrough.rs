@@ -0,0 +1,257 @@
+//! Integration tests for per-request sampling overrides
+//!
+//! A request's `temperature` replaces the selected endpoint's configured
+//! temperature in the backend call; without one, the endpoint's value is sent.
+//! The LLM router's own query keeps the router endpoint's temperature.
+//! `top_p`, the penalties and `logit_bias` are sent only when the request sets
+//! them. Out-of-range `temperature` or `top_p` is rejected before any backend
+//! call.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::sync::Arc;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+fn create_config(router_url: &str, backend_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+request_timeout_seconds = 30
+
+[[models.fast]]
+name = "fast-router"
+base_url = "{router_url}"
+max_tokens = 2048
+temperature = 0.125
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "{backend_url}"
+max_tokens = 4096
+temperature = 0.75
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:9997/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "llm"
+router_tier = "fast"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_sse_response(content: &str) -> String {
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#.to_string(),
+        format!(
+            r#"data: {{"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{{"index":0,"delta":{{"content":"{content}"}},"finish_reason":null}}]}}"#
+        ),
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#.to_string(),
+        "data: [DONE]".to_string(),
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+async fn start_server(content: &str) -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_sse_response(content))
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+/// JSON body of every request the server received
+async fn received_bodies(server: &MockServer) -> Vec<serde_json::Value> {
+    server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .map(|request| serde_json::from_slice(&request.body).expect("backend body should be JSON"))
+        .collect()
+}
+
+/// `temperature` of every request the server received
+async fn received_temperatures(server: &MockServer) -> Vec<f64> {
+    received_bodies(server)
+        .await
+        .iter()
+        .map(|body| {
+            body["temperature"]
+                .as_f64()
+                .expect("backend request should carry a temperature")
+        })
+        .collect()
+}
+
+/// Send a completion with `extra` spliced into the body, returning the status
+async fn complete(
+    router: &MockServer,
+    backend: &MockServer,
+    model: &str,
+    extra: &str,
+) -> StatusCode {
+    let config = create_config(&router.uri(), &backend.uri());
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+    let app = Router::new()
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware));
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(format!(
+            r#"{{"model": "{model}", "messages": [{{"role": "user", "content": "Hello"}}]{extra}}}"#
+        )))
+        .unwrap();
+    app.oneshot(request).await.unwrap().status()
+}
+
+#[tokio::test]
+async fn test_request_temperature_reaches_backend() {
+    let router = start_server("BALANCED").await;
+    let backend = start_server("Hi").await;
+
+    let status = complete(&router, &backend, "balanced", r#", "temperature": 1.5"#).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(received_temperatures(&backend).await, [1.5]);
+}
+
+#[tokio::test]
+async fn test_endpoint_temperature_used_without_override() {
+    let router = start_server("BALANCED").await;
+    let backend = start_server("Hi").await;
+
+    let status = complete(&router, &backend, "balanced", "").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(received_temperatures(&backend).await, [0.75]);
+}
+
+#[tokio::test]
+async fn test_router_query_keeps_endpoint_temperature() {
+    let router = start_server("BALANCED").await;
+    let backend = start_server("Hi").await;
+
+    let status = complete(&router, &backend, "auto", r#", "temperature": 1.5"#).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(received_temperatures(&router).await, [0.125]);
+    assert_eq!(received_temperatures(&backend).await, [1.5]);
+}
+
+#[tokio::test]
+async fn test_out_of_range_sampling_rejected_before_backend() {
+    for extra in [
+        r#", "temperature": 2.5"#,
+        r#", "temperature": -0.1"#,
+        r#", "top_p": 1.5"#,
+        r#", "top_p": -0.1"#,
+    ] {
+        let router = start_server("BALANCED").await;
+        let backend = start_server("Hi").await;
+
+        let status = complete(&router, &backend, "balanced", extra).await;
+
+        assert!(
+            status.is_client_error(),
+            "{} should be rejected, got {}",
+            extra,
+            status
+        );
+        assert!(
+            received_temperatures(&backend).await.is_empty(),
+            "{}",
+            extra
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_top_p_bounds_reach_backend() {
+    for (extra, top_p) in [(r#", "top_p": 0.0"#, 0.0), (r#", "top_p": 1.0"#, 1.0)] {
+        let router = start_server("BALANCED").await;
+        let backend = start_server("Hi").await;
+
+        let status = complete(&router, &backend, "balanced", extra).await;
+
+        assert_eq!(status, StatusCode::OK, "{}", extra);
+        let bodies = received_bodies(&backend).await;
+        assert_eq!(bodies.len(), 1, "{}", extra);
+        assert_eq!(bodies[0]["top_p"], top_p, "{}: {}", extra, bodies[0]);
+    }
+}
+
+#[tokio::test]
+async fn test_penalties_and_logit_bias_reach_backend() {
+    let router = start_server("BALANCED").await;
+    let backend = start_server("Hi").await;
+
+    let status = complete(
+        &router,
+        &backend,
+        "balanced",
+        r#", "presence_penalty": 0.5, "frequency_penalty": -0.5, "logit_bias": {"50256": -100}"#,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    let bodies = received_bodies(&backend).await;
+    assert_eq!(bodies.len(), 1);
+    assert_eq!(bodies[0]["presence_penalty"], 0.5);
+    assert_eq!(bodies[0]["frequency_penalty"], -0.5);
+    assert_eq!(bodies[0]["logit_bias"], serde_json::json!({"50256": -100}));
+}
+
+#[tokio::test]
+async fn test_unset_sampling_parameters_are_not_sent() {
+    let router = start_server("BALANCED").await;
+    let backend = start_server("Hi").await;
+
+    let status = complete(&router, &backend, "balanced", "").await;
+
+    assert_eq!(status, StatusCode::OK);
+    let bodies = received_bodies(&backend).await;
+    assert_eq!(bodies.len(), 1);
+    for field in [
+        "top_p",
+        "presence_penalty",
+        "frequency_penalty",
+        "logit_bias",
+    ] {
+        assert!(
+            bodies[0].get(field).is_none(),
+            "{} was sent: {}",
+            field,
+            bodies[0]
+        );
+    }
+}
@@ -0,0 +1,227 @@
+//! Integration tests for the server-wide drain (`POST /admin/drain`)
+//!
+//! A draining server answers 503 on `/readyz` so load balancers take it out of
+//! rotation, keeps answering 200 on `/livez`, and finishes requests that are
+//! already in flight. `POST /admin/undrain` reverses it.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::{get, post},
+};
+use octoroute::{
+    config::Config,
+    handlers::{self, AppState},
+    middleware::{admin_auth_middleware, request_id_middleware},
+};
+use std::sync::Arc;
+use std::time::Duration;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+const ADMIN_TOKEN: &str = "test-admin-token";
+
+fn create_config(mock_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+request_timeout_seconds = 30
+admin_token = "{ADMIN_TOKEN}"
+
+[[models.fast]]
+name = "fast-1"
+base_url = "{mock_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "{mock_url}"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "{mock_url}"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_sse_response(text: &str) -> String {
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#.to_string(),
+        format!(
+            r#"data: {{"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{{"index":0,"delta":{{"content":"{text}"}},"finish_reason":null}}]}}"#
+        ),
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#.to_string(),
+        "data: [DONE]".to_string(),
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+fn create_test_app(state: AppState) -> Router {
+    let admin = Router::new()
+        .route("/admin/drain", post(handlers::admin::drain_server))
+        .route("/admin/undrain", post(handlers::admin::undrain_server))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            admin_auth_middleware,
+        ));
+    Router::new()
+        .route("/livez", get(handlers::probes::livez))
+        .route("/readyz", get(handlers::probes::readyz))
+        .route(
+            "/v1/chat/completions",
+            post(handlers::openai::completions::handler),
+        )
+        .merge(admin)
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware))
+}
+
+fn admin_request(uri: &str) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("authorization", format!("Bearer {ADMIN_TOKEN}"))
+        .body(Body::empty())
+        .unwrap()
+}
+
+/// Mark every endpoint as having passed a health check, so `/readyz` can be 200
+async fn mark_all_verified(state: &AppState) {
+    for name in ["fast-1", "balanced-1", "deep-1"] {
+        state
+            .selector()
+            .health_checker()
+            .mark_success(name)
+            .await
+            .unwrap();
+    }
+}
+
+async fn get_status(state: &AppState, uri: &str) -> (StatusCode, serde_json::Value) {
+    let response = create_test_app(state.clone())
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json = serde_json::from_slice(&body).unwrap_or(serde_json::Value::Null);
+    (status, json)
+}
+
+#[tokio::test]
+async fn test_drain_fails_readiness_keeps_liveness_and_finishes_in_flight() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_sse_response("still served"))
+                .insert_header("content-type", "text/event-stream")
+                .set_delay(Duration::from_millis(500)),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    let state = AppState::new(Arc::new(create_config(&mock_server.uri())))
+        .expect("AppState::new should succeed");
+    mark_all_verified(&state).await;
+
+    assert_eq!(get_status(&state, "/readyz").await.0, StatusCode::OK);
+
+    // Start a request and wait until it has reached the backend
+    let app = create_test_app(state.clone());
+    let in_flight = tokio::spawn(async move {
+        let request = Request::builder()
+            .method("POST")
+            .uri("/v1/chat/completions")
+            .header("content-type", "application/json")
+            .body(Body::from(
+                r#"{"model": "fast", "messages": [{"role": "user", "content": "Hello"}]}"#,
+            ))
+            .unwrap();
+        app.oneshot(request).await.unwrap().status()
+    });
+    for _ in 0..200 {
+        if mock_server
+            .received_requests()
+            .await
+            .is_some_and(|requests| !requests.is_empty())
+        {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+
+    let drained = create_test_app(state.clone())
+        .oneshot(admin_request("/admin/drain"))
+        .await
+        .unwrap();
+    assert_eq!(drained.status(), StatusCode::OK);
+    assert!(state.is_draining());
+
+    let (status, json) = get_status(&state, "/readyz").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(json["status"], "draining");
+    assert_eq!(get_status(&state, "/livez").await.0, StatusCode::OK);
+
+    // The request that was already running completes normally
+    assert_eq!(in_flight.await.unwrap(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_undrain_restores_readiness() {
+    let state = AppState::new(Arc::new(create_config("http://localhost:9999/v1")))
+        .expect("AppState::new should succeed");
+    mark_all_verified(&state).await;
+
+    create_test_app(state.clone())
+        .oneshot(admin_request("/admin/drain"))
+        .await
+        .unwrap();
+    assert_eq!(
+        get_status(&state, "/readyz").await.0,
+        StatusCode::SERVICE_UNAVAILABLE
+    );
+
+    let undrained = create_test_app(state.clone())
+        .oneshot(admin_request("/admin/undrain"))
+        .await
+        .unwrap();
+    assert_eq!(undrained.status(), StatusCode::OK);
+    assert_eq!(get_status(&state, "/readyz").await.0, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_drain_requires_admin_token() {
+    let state = AppState::new(Arc::new(create_config("http://localhost:9999/v1")))
+        .expect("AppState::new should succeed");
+
+    let response = create_test_app(state.clone())
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/admin/drain")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert!(!state.is_draining());
+}
@@ -0,0 +1,142 @@
+//! Integration tests for SSE keep-alive comments before the first token
+//!
+//! With a slow backend, the streaming response should carry `: keep-alive`
+//! comments at `server.sse_keepalive_seconds` until content arrives.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::sync::Arc;
+use std::time::Duration;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+fn create_test_config(mock_url: &str, keepalive_seconds: u64) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 8080
+request_timeout_seconds = 30
+sse_keepalive_seconds = {keepalive_seconds}
+
+[[models.fast]]
+name = "test-fast-model"
+base_url = "{mock_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "test-balanced-model"
+base_url = "http://localhost:9998/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "test-deep-model"
+base_url = "http://localhost:9997/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_sse_response() -> String {
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{"content":"Hello"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#,
+        "data: [DONE]",
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+async fn start_slow_backend(delay: Duration) -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_delay(delay)
+                .set_body_string(create_sse_response())
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+async fn stream_body(config: Config) -> String {
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+    let app = Router::new()
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware));
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(
+            r#"{"model": "fast", "messages": [{"role": "user", "content": "Hello"}], "stream": true}"#,
+        ))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    String::from_utf8_lossy(&body).to_string()
+}
+
+#[tokio::test]
+async fn test_keepalive_comments_sent_before_first_token() {
+    // Backend takes 2.5s to respond; keep-alive every 1s -> 2 comments
+    let mock_server = start_slow_backend(Duration::from_millis(2500)).await;
+    let body = stream_body(create_test_config(&mock_server.uri(), 1)).await;
+
+    let keepalives = body.matches(": keep-alive").count();
+    assert!(
+        keepalives >= 2,
+        "Expected keep-alive comments while waiting, got {} in: {}",
+        keepalives,
+        body
+    );
+
+    let last_keepalive = body.rfind(": keep-alive").unwrap();
+    let first_data = body.find("data:").expect("stream should contain data");
+    assert!(
+        last_keepalive < first_data,
+        "Keep-alive comments should only precede the first token, got: {}",
+        body
+    );
+    assert!(body.contains("Hello"));
+    assert!(body.contains("[DONE]"));
+}
+
+#[tokio::test]
+async fn test_keepalive_disabled_with_zero() {
+    let mock_server = start_slow_backend(Duration::from_millis(1500)).await;
+    let body = stream_body(create_test_config(&mock_server.uri(), 0)).await;
+
+    assert!(
+        !body.contains("keep-alive"),
+        "No keep-alive comments expected when disabled, got: {}",
+        body
+    );
+    assert!(body.contains("Hello"));
+}
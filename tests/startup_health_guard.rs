@@ -0,0 +1,119 @@
+//! Integration tests for `health.require_healthy_at_startup`
+//!
+//! Startup fails with a clear error when a required tier has no reachable
+//! endpoint once the grace period is over, and succeeds as soon as every
+//! required tier has at least one endpoint answering its probe.
+
+use octoroute::{config::Config, error::AppError, handlers::AppState};
+use std::sync::Arc;
+use wiremock::{Mock, MockServer, ResponseTemplate, matchers::method};
+
+/// Nothing listens on port 1, so probes fail immediately with connection refused
+const UNREACHABLE_URL: &str = "http://127.0.0.1:1/v1";
+
+/// LLM routing with Balanced as the router tier; the second balanced endpoint
+/// is configurable so tests can make the whole router tier unreachable
+fn create_config(mock_url: &str, second_balanced_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+
+[[models.fast]]
+name = "fast-1"
+base_url = "{mock_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-unreachable"
+base_url = "{UNREACHABLE_URL}"
+max_tokens = 4096
+
+[[models.balanced]]
+name = "balanced-2"
+base_url = "{second_balanced_url}"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "{mock_url}"
+max_tokens = 8192
+
+[routing]
+strategy = "llm"
+router_tier = "balanced"
+
+[health]
+require_healthy_at_startup = true
+startup_grace_period_seconds = 0
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+async fn start_probe_server() -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("HEAD"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+#[tokio::test]
+async fn test_startup_fails_when_router_tier_is_unreachable() {
+    let mock_server = start_probe_server().await;
+    let config = create_config(&mock_server.uri(), UNREACHABLE_URL);
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+
+    let result = state.require_healthy_tiers().await;
+
+    match result {
+        Err(AppError::Config(message)) => {
+            assert!(
+                message.contains("Balanced")
+                    && message.contains("balanced-unreachable")
+                    && message.contains("balanced-2"),
+                "error should name the tier and its endpoints, got: {}",
+                message
+            );
+            assert!(
+                !message.contains("Fast") && !message.contains("Deep"),
+                "reachable tiers should not be reported, got: {}",
+                message
+            );
+        }
+        other => panic!("expected a startup health error, got: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_startup_succeeds_when_one_endpoint_per_tier_responds() {
+    let mock_server = start_probe_server().await;
+    let config = create_config(&mock_server.uri(), &mock_server.uri());
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+
+    state
+        .require_healthy_tiers()
+        .await
+        .expect("one reachable balanced endpoint is enough");
+}
+
+#[tokio::test]
+async fn test_grace_period_keeps_probing_until_it_expires() {
+    let mock_server = start_probe_server().await;
+    let mut config = create_config(&mock_server.uri(), UNREACHABLE_URL);
+    config.health.startup_grace_period_seconds = 2;
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+
+    let start = std::time::Instant::now();
+    let result = state.require_healthy_tiers().await;
+
+    assert!(result.is_err());
+    assert!(
+        start.elapsed() >= std::time::Duration::from_secs(2),
+        "should keep probing for the whole grace period, gave up after {:?}",
+        start.elapsed()
+    );
+}
@@ -0,0 +1,108 @@
+//! Integration tests for `server.warmup`
+//!
+//! Warmup probes every configured endpoint exactly once, concurrently, and
+//! records the results in the health checker. Unreachable endpoints are
+//! reported without failing.
+
+use octoroute::{config::Config, handlers::AppState};
+use std::sync::Arc;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+/// Each tier points at its own path prefix on the same mock server
+fn create_config(mock_url: &str, deep_base_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 8080
+warmup = true
+
+[[models.fast]]
+name = "fast-1"
+base_url = "{mock_url}/fast-1/v1"
+max_tokens = 2048
+
+[[models.fast]]
+name = "fast-2"
+base_url = "{mock_url}/fast-2/v1"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "{mock_url}/balanced-1/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "{deep_base_url}"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+async fn mount_probe(mock_server: &MockServer, endpoint: &str, status: u16) {
+    Mock::given(method("HEAD"))
+        .and(path(format!("/{}/v1/models", endpoint)))
+        .respond_with(ResponseTemplate::new(status))
+        .expect(1)
+        .named(endpoint)
+        .mount(mock_server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_warmup_probes_each_endpoint_exactly_once() {
+    let mock_server = MockServer::start().await;
+    for endpoint in ["fast-1", "fast-2", "balanced-1", "deep-1"] {
+        mount_probe(&mock_server, endpoint, 200).await;
+    }
+    let deep_base_url = format!("{}/deep-1/v1", mock_server.uri());
+    let config = create_config(&mock_server.uri(), &deep_base_url);
+    assert!(config.server.warmup);
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+
+    let mut report = state.selector().health_checker().warmup().await;
+
+    report.reachable.sort();
+    assert_eq!(
+        report.reachable,
+        vec!["balanced-1", "deep-1", "fast-1", "fast-2"]
+    );
+    assert!(report.unreachable.is_empty());
+    // Each probe mock expects exactly one request
+    mock_server.verify().await;
+}
+
+#[tokio::test]
+async fn test_warmup_reports_unreachable_endpoints_without_failing() {
+    let mock_server = MockServer::start().await;
+    for endpoint in ["fast-1", "fast-2", "balanced-1"] {
+        mount_probe(&mock_server, endpoint, 200).await;
+    }
+    // Nothing listens on port 1
+    let config = create_config(&mock_server.uri(), "http://127.0.0.1:1/v1");
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+    let health_checker = state.selector().health_checker();
+
+    let report = health_checker.warmup().await;
+
+    assert_eq!(report.unreachable, vec!["deep-1"]);
+    assert_eq!(report.reachable.len(), 3);
+
+    // A single failed probe counts toward the threshold but doesn't mark it unhealthy
+    let deep = health_checker
+        .get_all_statuses()
+        .await
+        .into_iter()
+        .find(|status| status.name() == "deep-1")
+        .expect("deep-1 should be tracked");
+    assert_eq!(deep.consecutive_failures(), 1);
+    assert!(deep.is_healthy());
+}
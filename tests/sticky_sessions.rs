@@ -0,0 +1,228 @@
+//! Integration tests for sticky session routing
+//!
+//! With `routing.sticky_session_ttl_seconds` set, `model: "auto"` requests that
+//! share an `x-octoroute-session` header (or OpenAI `user` field) reuse the tier
+//! chosen for the session's first request until the TTL expires.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::handlers::openai::X_OCTOROUTE_SESSION;
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::sync::Arc;
+use std::time::Duration;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+// Rule router sends casual chat to Fast and analysis requests to Deep
+const FAST_PROMPT: &str = "Hello there";
+const DEEP_PROMPT: &str = "Please analyze the tradeoffs of these two designs";
+
+fn create_test_config(fast_url: &str, deep_url: &str, sticky_ttl: Option<u64>) -> Config {
+    let sticky = sticky_ttl
+        .map(|ttl| format!("sticky_session_ttl_seconds = {}", ttl))
+        .unwrap_or_default();
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 8080
+request_timeout_seconds = 30
+
+[[models.fast]]
+name = "test-fast-model"
+base_url = "{fast_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "test-balanced-model"
+base_url = "http://localhost:9998/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "test-deep-model"
+base_url = "{deep_url}"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+{sticky}
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_sse_response() -> String {
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{"content":"Hello"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#,
+        "data: [DONE]",
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+async fn start_mock_backend() -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_sse_response())
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+struct Backends {
+    fast: MockServer,
+    deep: MockServer,
+}
+
+impl Backends {
+    async fn start() -> Self {
+        Self {
+            fast: start_mock_backend().await,
+            deep: start_mock_backend().await,
+        }
+    }
+
+    fn app(&self, sticky_ttl: Option<u64>) -> Router {
+        let config = create_test_config(&self.fast.uri(), &self.deep.uri(), sticky_ttl);
+        let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+        Router::new()
+            .route(
+                "/v1/chat/completions",
+                post(octoroute::handlers::openai::completions::handler),
+            )
+            .with_state(state)
+            .layer(middleware::from_fn(request_id_middleware))
+    }
+
+    async fn calls(&self) -> (usize, usize) {
+        let fast = self.fast.received_requests().await.unwrap().len();
+        let deep = self.deep.received_requests().await.unwrap().len();
+        (fast, deep)
+    }
+}
+
+fn auto_request(
+    prompt: &str,
+    session: Option<&str>,
+    user: Option<&str>,
+    stream: bool,
+) -> Request<Body> {
+    let mut builder = Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json");
+    if let Some(session) = session {
+        builder = builder.header(X_OCTOROUTE_SESSION, session);
+    }
+    let mut body = serde_json::json!({
+        "model": "auto",
+        "messages": [{"role": "user", "content": prompt}],
+        "stream": stream,
+    });
+    if let Some(user) = user {
+        body["user"] = serde_json::json!(user);
+    }
+    builder.body(Body::from(body.to_string())).unwrap()
+}
+
+async fn send(app: &Router, request: Request<Body>) -> StatusCode {
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    // Drain the body so streaming requests reach the backend
+    let _ = axum::body::to_bytes(response.into_body(), usize::MAX).await;
+    status
+}
+
+#[tokio::test]
+async fn test_same_session_reuses_first_tier() {
+    let backends = Backends::start().await;
+    let app = backends.app(Some(300));
+
+    let first = send(&app, auto_request(FAST_PROMPT, Some("conv-1"), None, false)).await;
+    assert_eq!(first, StatusCode::OK);
+    assert_eq!(backends.calls().await, (1, 0), "First turn routes to Fast");
+
+    // The router would pick Deep for this prompt, but the session is pinned to Fast
+    let second = send(&app, auto_request(DEEP_PROMPT, Some("conv-1"), None, false)).await;
+    assert_eq!(second, StatusCode::OK);
+    assert_eq!(
+        backends.calls().await,
+        (2, 0),
+        "Second turn should stick to the Fast tier"
+    );
+}
+
+#[tokio::test]
+async fn test_user_field_acts_as_session_key() {
+    let backends = Backends::start().await;
+    let app = backends.app(Some(300));
+
+    send(&app, auto_request(FAST_PROMPT, None, Some("alice"), false)).await;
+    send(&app, auto_request(DEEP_PROMPT, None, Some("alice"), false)).await;
+
+    assert_eq!(backends.calls().await, (2, 0));
+}
+
+#[tokio::test]
+async fn test_streaming_requests_honor_session() {
+    let backends = Backends::start().await;
+    let app = backends.app(Some(300));
+
+    send(&app, auto_request(FAST_PROMPT, Some("conv-s"), None, true)).await;
+    send(&app, auto_request(DEEP_PROMPT, Some("conv-s"), None, true)).await;
+
+    assert_eq!(backends.calls().await, (2, 0));
+}
+
+#[tokio::test]
+async fn test_different_sessions_route_independently() {
+    let backends = Backends::start().await;
+    let app = backends.app(Some(300));
+
+    send(&app, auto_request(FAST_PROMPT, Some("conv-a"), None, false)).await;
+    send(&app, auto_request(DEEP_PROMPT, Some("conv-b"), None, false)).await;
+
+    assert_eq!(backends.calls().await, (1, 1));
+}
+
+#[tokio::test]
+async fn test_stickiness_expires_after_ttl() {
+    let backends = Backends::start().await;
+    let app = backends.app(Some(1));
+
+    send(&app, auto_request(FAST_PROMPT, Some("conv-1"), None, false)).await;
+    tokio::time::sleep(Duration::from_millis(1200)).await;
+    send(&app, auto_request(DEEP_PROMPT, Some("conv-1"), None, false)).await;
+
+    assert_eq!(
+        backends.calls().await,
+        (1, 1),
+        "After the TTL the router should be consulted again"
+    );
+}
+
+#[tokio::test]
+async fn test_sessions_ignored_when_sticky_routing_disabled() {
+    let backends = Backends::start().await;
+    let app = backends.app(None);
+
+    send(&app, auto_request(FAST_PROMPT, Some("conv-1"), None, false)).await;
+    send(&app, auto_request(DEEP_PROMPT, Some("conv-1"), None, false)).await;
+
+    assert_eq!(backends.calls().await, (1, 1));
+}
@@ -0,0 +1,262 @@
+//! Integration tests for streaming failover before the first token
+//!
+//! A stream that fails before any token reached the client is restarted on a
+//! sibling endpoint of the tier, and the failure counts against the endpoint's health.
+//! Once a token has been streamed the endpoint is committed: a later failure is
+//! reported in-stream and counted in `octoroute_mid_stream_failures_total`.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+const ROLE_CHUNK: &str = r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#;
+
+fn content_chunk(text: &str) -> String {
+    format!(
+        r#"data: {{"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{{"index":0,"delta":{{"content":"{text}"}},"finish_reason":null}}]}}"#
+    )
+}
+
+fn create_sse_response(text: &str) -> String {
+    [
+        ROLE_CHUNK.to_string(),
+        content_chunk(text),
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#.to_string(),
+        "data: [DONE]".to_string(),
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+/// The primary endpoint has the higher priority, so it is always tried first
+fn create_config(primary_url: &str, sibling_url: &str, failover_attempts: usize) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+request_timeout_seconds = 5
+stream_failover_attempts = {failover_attempts}
+
+[[models.fast]]
+name = "fast-primary"
+base_url = "{primary_url}"
+max_tokens = 2048
+priority = 2
+
+[[models.fast]]
+name = "fast-sibling"
+base_url = "{sibling_url}"
+max_tokens = 2048
+priority = 1
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "{sibling_url}"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "{sibling_url}"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+/// Raw backend that runs `respond` on every connection once the request head
+/// has been read; returns its base URL and the accepted-connection count
+async fn start_raw_backend<F, Fut>(respond: F) -> (String, Arc<AtomicUsize>)
+where
+    F: Fn(TcpStream) -> Fut + Send + Sync + 'static,
+    Fut: std::future::Future<Output = ()> + Send,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let connections = Arc::new(AtomicUsize::new(0));
+
+    let accepted = connections.clone();
+    let respond = Arc::new(respond);
+    tokio::spawn(async move {
+        loop {
+            let Ok((mut stream, _)) = listener.accept().await else {
+                return;
+            };
+            accepted.fetch_add(1, Ordering::SeqCst);
+            let respond = respond.clone();
+            tokio::spawn(async move {
+                {
+                    let mut reader = BufReader::new(&mut stream);
+                    let mut line = String::new();
+                    while reader.read_line(&mut line).await.unwrap_or(0) > 0 {
+                        if line == "\r\n" {
+                            break;
+                        }
+                        line.clear();
+                    }
+                }
+                respond(stream).await;
+            });
+        }
+    });
+
+    (format!("http://{}/v1", addr), connections)
+}
+
+/// Backend that hangs up without answering (connection reset before any token)
+async fn start_resetting_backend() -> (String, Arc<AtomicUsize>) {
+    start_raw_backend(|stream| async move { drop(stream) }).await
+}
+
+/// Backend that streams one token, then dies in the middle of the chunked body
+async fn start_partial_backend() -> (String, Arc<AtomicUsize>) {
+    start_raw_backend(|mut stream| async move {
+        let head = "HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\n\
+                    transfer-encoding: chunked\r\n\r\n";
+        let events = format!("{}\n\n{}\n\n", ROLE_CHUNK, content_chunk("partial"));
+        let body = format!("{:x}\r\n{}\r\n", events.len(), events);
+        let _ = stream.write_all(head.as_bytes()).await;
+        let _ = stream.write_all(body.as_bytes()).await;
+        let _ = stream.flush().await;
+        // No terminating chunk: the body ends abruptly
+        drop(stream);
+    })
+    .await
+}
+
+async fn start_sibling(expected_requests: u64) -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_sse_response("Hello from sibling"))
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .expect(expected_requests)
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+/// Send a streaming request for the fast tier and return the whole SSE body
+async fn stream_body(state: &AppState) -> String {
+    let app = Router::new()
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state.clone())
+        .layer(middleware::from_fn(request_id_middleware));
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(
+            r#"{"model": "fast", "messages": [{"role": "user", "content": "Hello"}], "stream": true}"#,
+        ))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+async fn consecutive_failures(state: &AppState, endpoint: &str) -> u32 {
+    state
+        .selector()
+        .health_checker()
+        .get_all_statuses()
+        .await
+        .iter()
+        .find(|s| s.name() == endpoint)
+        .map(|s| s.consecutive_failures())
+        .expect("endpoint should exist")
+}
+
+#[tokio::test]
+async fn test_failure_before_first_token_restarts_on_sibling() {
+    let (primary_url, connections) = start_resetting_backend().await;
+    let sibling = start_sibling(1).await;
+    let state = AppState::new(Arc::new(create_config(&primary_url, &sibling.uri(), 2)))
+        .expect("AppState::new should succeed");
+
+    let body = stream_body(&state).await;
+
+    assert!(
+        connections.load(Ordering::SeqCst) >= 1,
+        "primary was not tried"
+    );
+    assert!(
+        body.contains("Hello from sibling") && body.contains("[DONE]"),
+        "sibling should serve the whole stream, got: {}",
+        body
+    );
+    assert!(
+        !body.contains("[Error"),
+        "no error event expected: {}",
+        body
+    );
+    assert!(
+        body.contains(r#""model":"fast-sibling""#),
+        "chunks should name the serving endpoint, got: {}",
+        body
+    );
+    assert!(consecutive_failures(&state, "fast-primary").await > 0);
+    assert_eq!(state.metrics().mid_stream_failures_count("fast-primary"), 0);
+}
+
+#[tokio::test]
+async fn test_failure_after_first_token_is_not_retried() {
+    let (primary_url, _) = start_partial_backend().await;
+    let sibling = start_sibling(0).await;
+    let state = AppState::new(Arc::new(create_config(&primary_url, &sibling.uri(), 2)))
+        .expect("AppState::new should succeed");
+
+    let body = stream_body(&state).await;
+
+    assert!(
+        body.contains("partial") && body.contains("[Stream Error"),
+        "the token already sent should be followed by an error event, got: {}",
+        body
+    );
+    assert!(!body.contains("Hello from sibling"));
+    assert_eq!(state.metrics().mid_stream_failures_count("fast-primary"), 1);
+}
+
+#[tokio::test]
+async fn test_failover_disabled_with_zero_attempts() {
+    let (primary_url, _) = start_resetting_backend().await;
+    let sibling = start_sibling(0).await;
+    let state = AppState::new(Arc::new(create_config(&primary_url, &sibling.uri(), 0)))
+        .expect("AppState::new should succeed");
+
+    let body = stream_body(&state).await;
+
+    assert!(
+        body.contains("[Error: Failed to start model query") && body.contains("[DONE]"),
+        "without failover the start error is returned, got: {}",
+        body
+    );
+}
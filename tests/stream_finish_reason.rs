@@ -0,0 +1,184 @@
+//! Integration tests for the `finish_reason` of streaming responses
+//!
+//! The final chunk carries the backend's own `finish_reason`, so clients that
+//! continue answers truncated with `"length"` can tell, however the backend
+//! batched its tokens. A backend that reports no finish reason gets `"stop"`:
+//! `"length"` is never inferred.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::sync::Arc;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+fn create_test_config(mock_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 8080
+request_timeout_seconds = 30
+
+[[models.fast]]
+name = "test-fast-model"
+base_url = "{mock_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "test-balanced-model"
+base_url = "http://localhost:9998/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "test-deep-model"
+base_url = "http://localhost:9997/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+/// Backend stream with one chunk per token, ending with `backend_finish_reason`
+/// (JSON, so `null` for none)
+fn create_sse_response(tokens: &[&str], backend_finish_reason: &str) -> String {
+    let role = r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#.to_string();
+    let content = tokens.iter().map(|token| {
+        format!(
+            r#"data: {{"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{{"index":0,"delta":{{"content":"{token}"}},"finish_reason":null}}]}}"#
+        )
+    });
+    let finish = format!(
+        r#"data: {{"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{{"index":0,"delta":{{}},"finish_reason":{backend_finish_reason}}}]}}"#
+    );
+
+    std::iter::once(role)
+        .chain(content)
+        .chain([finish, "data: [DONE]".to_string()])
+        .collect::<Vec<_>>()
+        .join("\n\n")
+        + "\n\n"
+}
+
+async fn start_backend(sse_body: String) -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(sse_body)
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+/// Stream a request with the given `max_tokens` and return every finish reason sent
+async fn finish_reasons(mock_url: &str, max_tokens: u32) -> Vec<String> {
+    let state = AppState::new(Arc::new(create_test_config(mock_url)))
+        .expect("AppState::new should succeed");
+    let app = Router::new()
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware));
+
+    let body = format!(
+        r#"{{"model": "fast", "messages": [{{"role": "user", "content": "Count"}}], "max_tokens": {max_tokens}, "stream": true}}"#
+    );
+    let request = Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(body))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+
+    String::from_utf8_lossy(&bytes)
+        .lines()
+        .filter_map(|line| line.strip_prefix("data: "))
+        .filter(|data| *data != "[DONE]")
+        .filter_map(|data| {
+            let chunk: serde_json::Value = serde_json::from_str(data).ok()?;
+            chunk["choices"][0]["finish_reason"]
+                .as_str()
+                .map(str::to_string)
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_stream_truncated_at_max_tokens_finishes_with_length() {
+    let mock_server = start_backend(create_sse_response(
+        &["one", " two", " three"],
+        r#""length""#,
+    ))
+    .await;
+
+    let reasons = finish_reasons(&mock_server.uri(), 3).await;
+
+    assert_eq!(reasons, vec!["length"]);
+}
+
+#[tokio::test]
+async fn test_stream_ending_below_max_tokens_finishes_with_stop() {
+    let mock_server = start_backend(create_sse_response(&["Hello", "!"], r#""stop""#)).await;
+
+    let reasons = finish_reasons(&mock_server.uri(), 100).await;
+
+    assert_eq!(reasons, vec!["stop"]);
+}
+
+#[tokio::test]
+async fn test_batched_chunks_truncated_by_backend_finish_with_length() {
+    // Four tokens in two chunks: the backend says it hit max_tokens, whatever
+    // the chunk count
+    let mock_server = start_backend(create_sse_response(
+        &["one two", " three four"],
+        r#""length""#,
+    ))
+    .await;
+
+    let reasons = finish_reasons(&mock_server.uri(), 4).await;
+
+    assert_eq!(reasons, vec!["length"]);
+}
+
+#[tokio::test]
+async fn test_content_filter_is_passed_through() {
+    let mock_server = start_backend(create_sse_response(&["I can't"], r#""content_filter""#)).await;
+
+    let reasons = finish_reasons(&mock_server.uri(), 100).await;
+
+    assert_eq!(reasons, vec!["content_filter"]);
+}
+
+#[tokio::test]
+async fn test_stream_without_backend_reason_finishes_with_stop() {
+    // As many chunks as max_tokens, but nothing says the limit was hit
+    let mock_server = start_backend(create_sse_response(&["one", " two"], "null")).await;
+
+    let reasons = finish_reasons(&mock_server.uri(), 2).await;
+
+    assert_eq!(reasons, vec!["stop"]);
+}
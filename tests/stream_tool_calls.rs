@@ -0,0 +1,244 @@
+//! Integration tests for tool calls in streaming responses
+//!
+//! The backend streams two tool calls with their arguments split into
+//! interleaved fragments. The forwarded SSE must carry `delta.tool_calls`
+//! entries that, concatenated per `index` the way OpenAI clients do, rebuild
+//! both calls exactly once, and end with `finish_reason: "tool_calls"`.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+fn create_test_config(mock_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 8080
+request_timeout_seconds = 30
+
+[[models.fast]]
+name = "test-fast-model"
+base_url = "{mock_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "test-balanced-model"
+base_url = "http://localhost:9998/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "test-deep-model"
+base_url = "http://localhost:9997/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+/// Backend stream with one chunk per delta, ending with `finish_reason`
+fn create_sse_response(deltas: &[serde_json::Value], finish_reason: &str) -> String {
+    let chunk = |delta: &serde_json::Value, finish_reason: Option<&str>| {
+        let chunk = serde_json::json!({
+            "id": "chatcmpl-test",
+            "object": "chat.completion.chunk",
+            "created": 1234567890,
+            "model": "test-model",
+            "choices": [{"index": 0, "delta": delta, "finish_reason": finish_reason}]
+        });
+        format!("data: {chunk}")
+    };
+
+    std::iter::once(chunk(&serde_json::json!({"role": "assistant"}), None))
+        .chain(deltas.iter().map(|delta| chunk(delta, None)))
+        .chain([
+            chunk(&serde_json::json!({}), Some(finish_reason)),
+            "data: [DONE]".to_string(),
+        ])
+        .collect::<Vec<_>>()
+        .join("\n\n")
+        + "\n\n"
+}
+
+/// Fragment of tool call `index`; the first one carries `id` and `name`
+fn tool_call_delta(index: u32, first: Option<(&str, &str)>, arguments: &str) -> serde_json::Value {
+    let mut call = serde_json::json!({
+        "index": index,
+        "function": {"arguments": arguments}
+    });
+    if let Some((id, name)) = first {
+        call["id"] = id.into();
+        call["type"] = "function".into();
+        call["function"]["name"] = name.into();
+    }
+    serde_json::json!({ "tool_calls": [call] })
+}
+
+async fn start_backend(sse_body: String) -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(sse_body)
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+/// Stream a request to the fast tier and return every chunk sent
+async fn stream_chunks(mock_url: &str) -> Vec<serde_json::Value> {
+    let state = AppState::new(Arc::new(create_test_config(mock_url)))
+        .expect("AppState::new should succeed");
+    let app = Router::new()
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware));
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(
+            r#"{"model": "fast", "messages": [{"role": "user", "content": "Weather and time in Paris?"}], "stream": true}"#,
+        ))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+
+    String::from_utf8_lossy(&bytes)
+        .lines()
+        .filter_map(|line| line.strip_prefix("data: "))
+        .filter(|data| *data != "[DONE]")
+        .map(|data| serde_json::from_str(data).expect("every chunk should be valid JSON"))
+        .collect()
+}
+
+/// A tool call rebuilt from its forwarded fragments
+#[derive(Debug, Default, PartialEq)]
+struct ToolCall {
+    ids: Vec<String>,
+    types: Vec<String>,
+    names: Vec<String>,
+    arguments: String,
+}
+
+/// Concatenate forwarded `delta.tool_calls` fragments per index, as clients do
+fn merge_tool_calls(chunks: &[serde_json::Value]) -> BTreeMap<u64, ToolCall> {
+    let mut calls = BTreeMap::<u64, ToolCall>::new();
+    let fragments = chunks
+        .iter()
+        .filter_map(|chunk| chunk["choices"][0]["delta"]["tool_calls"].as_array())
+        .flatten();
+    for fragment in fragments {
+        let index = fragment["index"]
+            .as_u64()
+            .expect("every fragment has an index");
+        let call = calls.entry(index).or_default();
+        if let Some(id) = fragment["id"].as_str() {
+            call.ids.push(id.to_string());
+        }
+        if let Some(call_type) = fragment["type"].as_str() {
+            call.types.push(call_type.to_string());
+        }
+        if let Some(name) = fragment["function"]["name"].as_str() {
+            call.names.push(name.to_string());
+        }
+        if let Some(arguments) = fragment["function"]["arguments"].as_str() {
+            call.arguments.push_str(arguments);
+        }
+    }
+    calls
+}
+
+#[tokio::test]
+async fn test_fragmented_tool_calls_are_forwarded_once_per_index() {
+    let deltas = [
+        tool_call_delta(0, Some(("call_weather", "get_weather")), ""),
+        tool_call_delta(0, None, r#"{"city":"#),
+        tool_call_delta(1, Some(("call_time", "get_time")), r#"{"tz""#),
+        tool_call_delta(0, None, r#""Paris"}"#),
+        tool_call_delta(1, None, r#":"CET"}"#),
+    ];
+    let mock_server = start_backend(create_sse_response(&deltas, "tool_calls")).await;
+
+    let chunks = stream_chunks(&mock_server.uri()).await;
+    let calls = merge_tool_calls(&chunks);
+
+    assert_eq!(calls.len(), 2, "{:?}", chunks);
+    let by_id: BTreeMap<&str, &ToolCall> = calls
+        .values()
+        .map(|call| (call.ids[0].as_str(), call))
+        .collect();
+    for (id, name, arguments) in [
+        (
+            "call_weather",
+            "get_weather",
+            serde_json::json!({"city": "Paris"}),
+        ),
+        ("call_time", "get_time", serde_json::json!({"tz": "CET"})),
+    ] {
+        let call = by_id
+            .get(id)
+            .unwrap_or_else(|| panic!("{id} missing: {:?}", calls));
+        assert_eq!(call.ids, vec![id], "id sent once");
+        assert_eq!(call.types, vec!["function"]);
+        assert_eq!(call.names, vec![name], "name sent once");
+        let parsed: serde_json::Value =
+            serde_json::from_str(&call.arguments).expect("merged arguments should be JSON");
+        assert_eq!(parsed, arguments);
+    }
+
+    let finish_reasons: Vec<_> = chunks
+        .iter()
+        .filter_map(|chunk| chunk["choices"][0]["finish_reason"].as_str())
+        .collect();
+    assert_eq!(finish_reasons, vec!["tool_calls"]);
+    assert!(
+        chunks
+            .iter()
+            .all(|chunk| chunk["choices"][0]["delta"]["content"].is_null()),
+        "tool calls are not sent as content: {:?}",
+        chunks
+    );
+}
+
+#[tokio::test]
+async fn test_text_stream_has_no_tool_calls() {
+    let deltas = [serde_json::json!({"content": "Sunny"})];
+    let mock_server = start_backend(create_sse_response(&deltas, "stop")).await;
+
+    let chunks = stream_chunks(&mock_server.uri()).await;
+
+    assert!(merge_tool_calls(&chunks).is_empty());
+    let finish_reasons: Vec<_> = chunks
+        .iter()
+        .filter_map(|chunk| chunk["choices"][0]["finish_reason"].as_str())
+        .collect();
+    assert_eq!(finish_reasons, vec!["stop"]);
+}
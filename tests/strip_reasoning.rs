@@ -0,0 +1,186 @@
+//! Integration tests for reasoning block removal (`strip_reasoning_tags`)
+//!
+//! Endpoints with `strip_reasoning_tags = true` return responses without their
+//! `<think>...</think>` blocks. Non-streaming responses are filtered whole;
+//! streams hold text back until a block closes, so no chunk sent to the client
+//! carries any of the reasoning, even when tags are split across chunks.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::sync::Arc;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+/// Fast strips reasoning; Balanced keeps it
+fn create_config(mock_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+request_timeout_seconds = 30
+
+[[models.fast]]
+name = "fast-1"
+base_url = "{mock_url}"
+max_tokens = 2048
+strip_reasoning_tags = true
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "{mock_url}"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "{mock_url}"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+/// One upstream SSE chunk per entry of `pieces`
+fn create_sse_response(pieces: &[&str]) -> String {
+    let chunk = |delta: serde_json::Value, finish_reason: Option<&str>| {
+        let chunk = serde_json::json!({
+            "id": "chatcmpl-test",
+            "object": "chat.completion.chunk",
+            "created": 1234567890,
+            "model": "test",
+            "choices": [{"index": 0, "delta": delta, "finish_reason": finish_reason}],
+        });
+        format!("data: {}", chunk)
+    };
+
+    std::iter::once(chunk(serde_json::json!({"role": "assistant"}), None))
+        .chain(
+            pieces
+                .iter()
+                .map(|piece| chunk(serde_json::json!({"content": piece}), None)),
+        )
+        .chain([
+            chunk(serde_json::json!({}), Some("stop")),
+            "data: [DONE]".to_string(),
+        ])
+        .collect::<Vec<_>>()
+        .join("\n\n")
+        + "\n\n"
+}
+
+const REASONING_PIECES: &[&str] = &[
+    "<th",
+    "ink>The user wants ",
+    "a secret plan.</th",
+    "ink>\n\nHere is ",
+    "the answer.",
+];
+
+async fn start_backend() -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_sse_response(REASONING_PIECES))
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+async fn complete(state: &AppState, model: &str, stream: bool) -> String {
+    let app = Router::new()
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state.clone())
+        .layer(middleware::from_fn(request_id_middleware));
+    let request = Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(format!(
+            r#"{{"model": "{model}", "stream": {stream}, "messages": [{{"role": "user", "content": "Plan?"}}]}}"#
+        )))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+/// Content deltas of every chunk in an SSE body, in order
+fn streamed_contents(body: &str) -> Vec<String> {
+    body.lines()
+        .filter_map(|line| line.strip_prefix("data: "))
+        .filter(|data| *data != "[DONE]")
+        .filter_map(|data| {
+            let chunk: serde_json::Value = serde_json::from_str(data).ok()?;
+            chunk["choices"][0]["delta"]["content"]
+                .as_str()
+                .map(str::to_string)
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_reasoning_removed_from_non_streaming_response() {
+    let mock_server = start_backend().await;
+    let state = AppState::new(Arc::new(create_config(&mock_server.uri())))
+        .expect("AppState::new should succeed");
+
+    let body = complete(&state, "fast", false).await;
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+    assert_eq!(
+        json["choices"][0]["message"]["content"],
+        "Here is the answer."
+    );
+}
+
+#[tokio::test]
+async fn test_reasoning_not_leaked_while_streaming() {
+    let mock_server = start_backend().await;
+    let state = AppState::new(Arc::new(create_config(&mock_server.uri())))
+        .expect("AppState::new should succeed");
+
+    let contents = streamed_contents(&complete(&state, "fast", true).await);
+
+    for content in &contents {
+        assert!(
+            !content.contains("secret") && !content.contains("think>") && !content.contains('<'),
+            "reasoning leaked in chunk {:?}",
+            content
+        );
+    }
+    assert_eq!(contents.concat(), "Here is the answer.");
+}
+
+#[tokio::test]
+async fn test_endpoints_without_the_option_keep_reasoning() {
+    let mock_server = start_backend().await;
+    let state = AppState::new(Arc::new(create_config(&mock_server.uri())))
+        .expect("AppState::new should succeed");
+
+    let contents = streamed_contents(&complete(&state, "balanced", true).await);
+
+    assert_eq!(contents.concat(), REASONING_PIECES.concat());
+}
@@ -0,0 +1,243 @@
+//! Integration tests for the house system prompt (`routing.system_prompt`)
+//!
+//! The configured prompt reaches the backend on both chat endpoints, and
+//! `routing.system_prompt_mode` controls what happens to a system message the
+//! client sent: prepend keeps it, replace drops it, merge folds it into one
+//! leading system message.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::io::Write;
+use std::sync::Arc;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+const HOUSE_PROMPT: &str = "House rules apply.";
+
+fn create_config(mock_url: &str, routing_extra: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 8080
+request_timeout_seconds = 30
+
+[[models.fast]]
+name = "test-fast-model"
+base_url = "{mock_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "test-balanced-model"
+base_url = "http://localhost:9998/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "test-deep-model"
+base_url = "http://localhost:9997/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+{routing_extra}
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn house_prompt_config(mock_url: &str, mode: &str) -> Config {
+    create_config(
+        mock_url,
+        &format!("system_prompt = \"{HOUSE_PROMPT}\"\nsystem_prompt_mode = \"{mode}\""),
+    )
+}
+
+fn create_sse_response() -> String {
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{"content":"Bonjour"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#,
+        "data: [DONE]",
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+async fn start_mock_backend() -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_sse_response())
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+fn create_test_app(config: Config) -> Router {
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+    Router::new()
+        .route("/chat", post(octoroute::handlers::chat::handler))
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware))
+}
+
+fn json_request(uri: &str, body: &str) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+fn completion_request(stream: bool) -> Request<Body> {
+    json_request(
+        "/v1/chat/completions",
+        &format!(
+            r#"{{"model": "fast", "stream": {stream}, "messages": [
+                {{"role": "system", "content": "Answer in French."}},
+                {{"role": "user", "content": "Hello"}}
+            ]}}"#
+        ),
+    )
+}
+
+/// Message text the backend received, across every message in the request
+async fn prompt_sent_to_backend(mock_server: &MockServer) -> String {
+    let requests = mock_server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 1, "expected exactly one backend request");
+    let body: serde_json::Value =
+        serde_json::from_slice(&requests[0].body).expect("backend body should be JSON");
+    body["messages"]
+        .as_array()
+        .expect("backend body should carry messages")
+        .iter()
+        .filter_map(|message| message["content"].as_str())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+async fn send(app: Router, request: Request<Body>) {
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    // Drain the body so streaming responses finish their upstream query
+    axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_prepend_mode_keeps_client_system_message() {
+    let mock_server = start_mock_backend().await;
+    let app = create_test_app(house_prompt_config(&mock_server.uri(), "prepend"));
+
+    send(app, completion_request(false)).await;
+
+    let prompt = prompt_sent_to_backend(&mock_server).await;
+    assert!(
+        prompt.contains("System: House rules apply.\n\nSystem: Answer in French.\n\nUser: Hello"),
+        "house prompt should precede the client's messages, got: {prompt}"
+    );
+}
+
+#[tokio::test]
+async fn test_replace_mode_drops_client_system_message() {
+    let mock_server = start_mock_backend().await;
+    let app = create_test_app(house_prompt_config(&mock_server.uri(), "replace"));
+
+    send(app, completion_request(false)).await;
+
+    let prompt = prompt_sent_to_backend(&mock_server).await;
+    assert!(prompt.contains("System: House rules apply.\n\nUser: Hello"));
+    assert!(
+        !prompt.contains("Answer in French."),
+        "client system message should be replaced, got: {prompt}"
+    );
+}
+
+#[tokio::test]
+async fn test_merge_mode_combines_system_messages() {
+    let mock_server = start_mock_backend().await;
+    let app = create_test_app(house_prompt_config(&mock_server.uri(), "merge"));
+
+    send(app, completion_request(false)).await;
+
+    let prompt = prompt_sent_to_backend(&mock_server).await;
+    assert!(
+        prompt.contains("System: House rules apply.\n\nAnswer in French.\n\nUser: Hello"),
+        "house and client system text should share one system message, got: {prompt}"
+    );
+    assert_eq!(prompt.matches("System:").count(), 1);
+}
+
+#[tokio::test]
+async fn test_streaming_request_gets_house_prompt() {
+    let mock_server = start_mock_backend().await;
+    let app = create_test_app(house_prompt_config(&mock_server.uri(), "replace"));
+
+    send(app, completion_request(true)).await;
+
+    let prompt = prompt_sent_to_backend(&mock_server).await;
+    assert!(prompt.contains("System: House rules apply.\n\nUser: Hello"));
+}
+
+#[tokio::test]
+async fn test_legacy_chat_gets_house_prompt() {
+    let mock_server = start_mock_backend().await;
+    let app = create_test_app(house_prompt_config(&mock_server.uri(), "prepend"));
+
+    send(app, json_request("/chat", r#"{"message": "Hello"}"#)).await;
+
+    let prompt = prompt_sent_to_backend(&mock_server).await;
+    assert!(prompt.contains("System: House rules apply.\n\nUser: Hello"));
+}
+
+#[tokio::test]
+async fn test_system_prompt_file_is_read_at_startup() {
+    let mock_server = start_mock_backend().await;
+    let mut prompt_file = tempfile::NamedTempFile::new().unwrap();
+    write!(prompt_file, "{HOUSE_PROMPT}").unwrap();
+    let config = create_config(
+        &mock_server.uri(),
+        &format!("system_prompt_file = \"{}\"", prompt_file.path().display()),
+    );
+    let app = create_test_app(config);
+
+    send(app, json_request("/chat", r#"{"message": "Hello"}"#)).await;
+
+    let prompt = prompt_sent_to_backend(&mock_server).await;
+    assert!(prompt.contains("System: House rules apply."));
+}
+
+#[tokio::test]
+async fn test_unreadable_system_prompt_file_fails_startup() {
+    let config = create_config(
+        "http://localhost:9999/v1",
+        r#"system_prompt_file = "/nonexistent/octoroute/house-prompt.txt""#,
+    );
+
+    let result = AppState::new(Arc::new(config));
+
+    assert!(
+        result.is_err(),
+        "a missing system prompt file should fail startup"
+    );
+}
@@ -0,0 +1,158 @@
+//! Integration tests for task type inference (`routing.auto_classify_task`)
+//!
+//! With the setting on, a `/chat` request that omits `task_type` is
+//! classified from its message before routing; here that is observable
+//! through a `creative_writing = "deep"` task affinity. An explicit
+//! `task_type` always wins, and the classifier on `AppState` can be swapped
+//! for an embedder's own.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::router::{TaskClassifier, TaskType};
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::sync::Arc;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+fn create_config(mock_url: &str, auto_classify_task: bool) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+request_timeout_seconds = 30
+
+[[models.fast]]
+name = "fast-1"
+base_url = "{mock_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "{mock_url}"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "{mock_url}"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+auto_classify_task = {auto_classify_task}
+
+[routing.task_affinity]
+creative_writing = "deep"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_sse_response() -> String {
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{"content":"Once upon a time"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#,
+        "data: [DONE]",
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+async fn start_backend() -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_sse_response())
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+fn create_test_app(state: AppState) -> Router {
+    Router::new()
+        .route("/chat", post(octoroute::handlers::chat::handler))
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware))
+}
+
+/// Send a `/chat` request and return the tier it was served from
+async fn chat_tier(state: AppState, body: &str) -> String {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/chat")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let response = create_test_app(state).oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    json["model_tier"].as_str().unwrap().to_string()
+}
+
+const STORY: &str = r#"{"message": "Write a story about a lighthouse keeper"}"#;
+
+#[tokio::test]
+async fn test_omitted_task_type_is_classified() {
+    let mock_server = start_backend().await;
+    let state = AppState::new(Arc::new(create_config(&mock_server.uri(), true)))
+        .expect("AppState::new should succeed");
+
+    assert_eq!(chat_tier(state, STORY).await, "deep");
+}
+
+#[tokio::test]
+async fn test_explicit_task_type_is_not_overridden() {
+    let mock_server = start_backend().await;
+    let state = AppState::new(Arc::new(create_config(&mock_server.uri(), true)))
+        .expect("AppState::new should succeed");
+
+    let body =
+        r#"{"message": "Write a story about a lighthouse keeper", "task_type": "question_answer"}"#;
+    assert_ne!(chat_tier(state, body).await, "deep");
+}
+
+#[tokio::test]
+async fn test_classification_is_off_by_default() {
+    let mock_server = start_backend().await;
+    let state = AppState::new(Arc::new(create_config(&mock_server.uri(), false)))
+        .expect("AppState::new should succeed");
+
+    assert_ne!(chat_tier(state, STORY).await, "deep");
+}
+
+struct EverythingIsCreative;
+
+impl TaskClassifier for EverythingIsCreative {
+    fn classify(&self, _prompt: &str) -> Option<TaskType> {
+        Some(TaskType::CreativeWriting)
+    }
+}
+
+#[tokio::test]
+async fn test_custom_classifier_replaces_keyword_heuristic() {
+    let mock_server = start_backend().await;
+    let state = AppState::new(Arc::new(create_config(&mock_server.uri(), true)))
+        .expect("AppState::new should succeed")
+        .with_task_classifier(Arc::new(EverythingIsCreative));
+
+    assert_eq!(
+        chat_tier(state, r#"{"message": "What is 2+2?"}"#).await,
+        "deep"
+    );
+}
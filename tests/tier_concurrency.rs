@@ -0,0 +1,203 @@
+//! Integration tests for `[server.tier_concurrency]`
+//!
+//! A tier whose budget is used up sheds further requests with 503 and a short
+//! `Retry-After`, while requests for other tiers keep being served.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::router::TargetModel;
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::sync::Arc;
+use std::time::Duration;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+/// Deep may serve one request at a time; Fast and Balanced are unlimited
+fn create_config(fast_url: &str, deep_url: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+request_timeout_seconds = 30
+
+[server.tier_concurrency]
+deep = 1
+
+[[models.fast]]
+name = "fast-1"
+base_url = "{fast_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "{fast_url}"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "{deep_url}"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_sse_response(text: &str) -> String {
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#.to_string(),
+        format!(
+            r#"data: {{"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{{"index":0,"delta":{{"content":"{text}"}},"finish_reason":null}}]}}"#
+        ),
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#.to_string(),
+        "data: [DONE]".to_string(),
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+/// Backend answering `expected` queries with `text` after `delay`
+async fn start_backend(text: &str, delay: Duration, expected: u64) -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_sse_response(text))
+                .insert_header("content-type", "text/event-stream")
+                .set_delay(delay),
+        )
+        .expect(expected)
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+fn create_test_app(state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware))
+}
+
+fn completion_request(model: &str, stream: bool) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(format!(
+            r#"{{"model": "{model}", "messages": [{{"role": "user", "content": "Hello"}}], "stream": {stream}}}"#
+        )))
+        .unwrap()
+}
+
+/// Start a slow Deep request in the background and wait until it holds the budget
+async fn saturate_deep(state: &AppState) -> tokio::task::JoinHandle<StatusCode> {
+    let app = create_test_app(state.clone());
+    let in_flight = tokio::spawn(async move {
+        let response = app
+            .oneshot(completion_request("deep", false))
+            .await
+            .unwrap();
+        response.status()
+    });
+
+    for _ in 0..200 {
+        if state.tier_budgets().available(TargetModel::Deep) == Some(0) {
+            return in_flight;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("the first Deep request never took the Deep budget");
+}
+
+#[tokio::test]
+async fn test_saturated_deep_budget_sheds_deep_but_serves_fast() {
+    let fast = start_backend("fast answer", Duration::ZERO, 1).await;
+    // Only the request holding the budget reaches the Deep backend
+    let deep = start_backend("deep answer", Duration::from_millis(1500), 1).await;
+    let state = AppState::new(Arc::new(create_config(&fast.uri(), &deep.uri())))
+        .expect("AppState::new should succeed");
+
+    let in_flight = saturate_deep(&state).await;
+
+    // A second Deep request is shed without waiting for the first
+    let shed = create_test_app(state.clone())
+        .oneshot(completion_request("deep", false))
+        .await
+        .unwrap();
+    assert_eq!(shed.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(
+        shed.headers()
+            .get("retry-after")
+            .and_then(|v| v.to_str().ok()),
+        Some("1")
+    );
+    let body = axum::body::to_bytes(shed.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert!(
+        String::from_utf8_lossy(&body).contains("concurrency limit"),
+        "error should name the budget, got: {}",
+        String::from_utf8_lossy(&body)
+    );
+
+    // Fast has its own (unlimited) budget and is served while Deep is saturated
+    let served = create_test_app(state.clone())
+        .oneshot(completion_request("fast", false))
+        .await
+        .unwrap();
+    assert_eq!(served.status(), StatusCode::OK);
+    assert_eq!(
+        state.tier_budgets().available(TargetModel::Deep),
+        Some(0),
+        "Deep should still be busy while Fast is served"
+    );
+
+    assert_eq!(in_flight.await.unwrap(), StatusCode::OK);
+    assert_eq!(state.tier_budgets().available(TargetModel::Deep), Some(1));
+}
+
+#[tokio::test]
+async fn test_streaming_deep_request_is_shed_at_budget_and_releases_permit() {
+    let fast = start_backend("fast answer", Duration::ZERO, 0).await;
+    let deep = start_backend("deep answer", Duration::from_millis(1500), 2).await;
+    let state = AppState::new(Arc::new(create_config(&fast.uri(), &deep.uri())))
+        .expect("AppState::new should succeed");
+
+    let in_flight = saturate_deep(&state).await;
+
+    let shed = create_test_app(state.clone())
+        .oneshot(completion_request("deep", true))
+        .await
+        .unwrap();
+    assert_eq!(shed.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+    assert_eq!(in_flight.await.unwrap(), StatusCode::OK);
+
+    // Once the budget is free a stream is served, and holds the permit until it ends
+    let streamed = create_test_app(state.clone())
+        .oneshot(completion_request("deep", true))
+        .await
+        .unwrap();
+    assert_eq!(streamed.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(streamed.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert!(String::from_utf8_lossy(&body).contains("deep answer"));
+    assert_eq!(state.tier_budgets().available(TargetModel::Deep), Some(1));
+}
@@ -0,0 +1,164 @@
+//! Integration tests for cross-tier fallback
+//!
+//! With `routing.tier_fallback` enabled, a request whose tier has no working
+//! endpoint is served by the next lower tier, tagged with an `X-Octoroute-Warning`
+//! header, and counted in `octoroute_tier_fallback_total`. With it disabled
+//! (the default), the request fails as before.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::handlers::openai::completions::X_OCTOROUTE_WARNING;
+use octoroute::metrics::Tier;
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::sync::Arc;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+/// Deep points at a closed port; balanced is served by the mock backend
+fn create_config(mock_url: &str, tier_fallback: bool) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 8080
+request_timeout_seconds = 30
+
+[[models.fast]]
+name = "test-fast-model"
+base_url = "http://localhost:9998/v1"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "test-balanced-model"
+base_url = "{mock_url}"
+max_tokens = 4096
+
+[[models.deep]]
+name = "test-deep-model"
+base_url = "http://localhost:9/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+tier_fallback = {tier_fallback}
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_sse_response() -> String {
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{"content":"Hello"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test-model","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#,
+        "data: [DONE]",
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+async fn start_mock_backend() -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_sse_response())
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+fn create_test_app(state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware))
+}
+
+fn deep_request() -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(
+            r#"{"model": "deep", "messages": [{"role": "user", "content": "Hello"}]}"#,
+        ))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_unavailable_tier_falls_back_when_enabled() {
+    let mock_server = start_mock_backend().await;
+    let state = AppState::new(Arc::new(create_config(&mock_server.uri(), true)))
+        .expect("AppState::new should succeed");
+    let app = create_test_app(state.clone());
+
+    let response = app.oneshot(deep_request()).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let warning = response
+        .headers()
+        .get(X_OCTOROUTE_WARNING)
+        .expect("fallback should add a warning header")
+        .to_str()
+        .unwrap()
+        .to_string();
+    assert!(
+        warning.contains("served by Balanced tier"),
+        "Warning should name the fallback tier, got: {}",
+        warning
+    );
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["model"], "test-balanced-model");
+
+    assert_eq!(
+        state
+            .metrics()
+            .tier_fallback_count(Tier::Deep, Tier::Balanced),
+        1
+    );
+}
+
+#[tokio::test]
+async fn test_unavailable_tier_fails_when_fallback_disabled() {
+    let mock_server = start_mock_backend().await;
+    let state = AppState::new(Arc::new(create_config(&mock_server.uri(), false)))
+        .expect("AppState::new should succeed");
+    let app = create_test_app(state.clone());
+
+    let response = app.oneshot(deep_request()).await.unwrap();
+
+    assert!(
+        !response.status().is_success(),
+        "Without fallback the deep request should fail, got {}",
+        response.status()
+    );
+    assert!(
+        mock_server.received_requests().await.unwrap().is_empty(),
+        "Balanced backend must not be queried without fallback"
+    );
+    assert_eq!(
+        state
+            .metrics()
+            .tier_fallback_count(Tier::Deep, Tier::Balanced),
+        0
+    );
+}
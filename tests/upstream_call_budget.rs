@@ -0,0 +1,205 @@
+//! Integration tests for the per-request upstream call budget (`server.max_upstream_calls`)
+//!
+//! The LLM router's query and every completion attempt (retries and streaming
+//! failover included) draw from one budget. The router here always answers
+//! BALANCED in a single call, and every balanced endpoint fails with a 500, so
+//! without a budget a request makes one router call plus one call per
+//! balanced endpoint.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::sync::Arc;
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+fn create_config(router_url: &str, backend_url: &str, max_upstream_calls: Option<usize>) -> Config {
+    let budget = max_upstream_calls
+        .map(|calls| format!("max_upstream_calls = {calls}"))
+        .unwrap_or_default();
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+request_timeout_seconds = 30
+{budget}
+
+[[models.fast]]
+name = "fast-router"
+base_url = "{router_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "{backend_url}"
+max_tokens = 4096
+
+[[models.balanced]]
+name = "balanced-2"
+base_url = "{backend_url}"
+max_tokens = 4096
+
+[[models.balanced]]
+name = "balanced-3"
+base_url = "{backend_url}"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:9997/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "llm"
+router_tier = "fast"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_balanced_sse_response() -> String {
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"fast-router","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"fast-router","choices":[{"index":0,"delta":{"content":"BALANCED"},"finish_reason":null}]}"#,
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"fast-router","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#,
+        "data: [DONE]",
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+/// Router endpoint that routes every request to the balanced tier
+async fn start_router() -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_balanced_sse_response())
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+/// Backend shared by all balanced endpoints, failing every completion
+async fn start_failing_backend() -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+async fn call_count(server: &MockServer) -> usize {
+    server.received_requests().await.unwrap().len()
+}
+
+fn create_test_app(state: AppState) -> Router {
+    Router::new()
+        .route("/chat", post(octoroute::handlers::chat::handler))
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware))
+}
+
+/// Send `body` to `uri`, returning the status and response body
+async fn send(
+    router: &MockServer,
+    backend: &MockServer,
+    max_upstream_calls: Option<usize>,
+    uri: &str,
+    body: &str,
+) -> (StatusCode, String) {
+    let config = create_config(&router.uri(), &backend.uri(), max_upstream_calls);
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+    let request = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let response = create_test_app(state).oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+const CHAT: &str = r#"{"message": "Explain the borrow checker"}"#;
+
+#[tokio::test]
+async fn test_without_budget_every_retry_reaches_upstream() {
+    let router = start_router().await;
+    let backend = start_failing_backend().await;
+
+    let (status, _) = send(&router, &backend, None, "/chat", CHAT).await;
+
+    assert_eq!(status, StatusCode::BAD_GATEWAY);
+    assert_eq!(call_count(&router).await, 1);
+    assert_eq!(call_count(&backend).await, 3);
+}
+
+#[tokio::test]
+async fn test_budget_caps_router_and_completion_calls_combined() {
+    let router = start_router().await;
+    let backend = start_failing_backend().await;
+
+    let (status, body) = send(&router, &backend, Some(2), "/chat", CHAT).await;
+
+    // The router call leaves one completion attempt, whose failure is returned
+    assert_eq!(status, StatusCode::BAD_GATEWAY);
+    assert!(!body.contains("call budget"), "{}", body);
+    assert_eq!(call_count(&router).await, 1);
+    assert_eq!(call_count(&backend).await, 1);
+}
+
+#[tokio::test]
+async fn test_budget_spent_by_routing_fails_before_any_completion() {
+    let router = start_router().await;
+    let backend = start_failing_backend().await;
+
+    let (status, body) = send(&router, &backend, Some(1), "/chat", CHAT).await;
+
+    assert_eq!(status, StatusCode::BAD_GATEWAY);
+    assert!(body.contains("call budget of 1"), "{}", body);
+    assert_eq!(call_count(&router).await, 1);
+    assert_eq!(call_count(&backend).await, 0);
+}
+
+#[tokio::test]
+async fn test_budget_caps_streaming_failover() {
+    let router = start_router().await;
+    let backend = start_failing_backend().await;
+
+    // Explicit tier: no router call, so the first attempt plus one failover
+    let (status, body) = send(
+        &router,
+        &backend,
+        Some(2),
+        "/v1/chat/completions",
+        r#"{"model": "balanced", "messages": [{"role": "user", "content": "Hello"}], "stream": true}"#,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.trim_end().ends_with("data: [DONE]"), "{}", body);
+    assert_eq!(call_count(&router).await, 0);
+    assert_eq!(call_count(&backend).await, 2);
+}
@@ -0,0 +1,244 @@
+//! Integration tests for per-user request tracking (`[server.user_tracking]`)
+//!
+//! The OpenAI `user` field is parsed, appears on the request's log lines, and
+//! drives a per-user counter when tracking is enabled. With
+//! `reject_over_limit`, requests past `max_requests` get 429 and `Retry-After`.
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use octoroute::{config::Config, handlers::AppState, middleware::request_id_middleware};
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use tower::ServiceExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+fn create_config(mock_url: &str, user_tracking: &str) -> Config {
+    let toml = format!(
+        r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+request_timeout_seconds = 30
+
+{user_tracking}
+
+[[models.fast]]
+name = "fast-1"
+base_url = "{mock_url}"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "{mock_url}"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "{mock_url}"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#
+    );
+    toml::from_str(&toml).expect("should parse TOML config")
+}
+
+fn create_sse_response(text: &str) -> String {
+    [
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{"role":"assistant"},"finish_reason":null}]}"#.to_string(),
+        format!(
+            r#"data: {{"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{{"index":0,"delta":{{"content":"{text}"}},"finish_reason":null}}]}}"#
+        ),
+        r#"data: {"id":"chatcmpl-test","object":"chat.completion.chunk","created":1234567890,"model":"test","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#.to_string(),
+        "data: [DONE]".to_string(),
+    ]
+    .join("\n\n")
+        + "\n\n"
+}
+
+async fn start_backend() -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(create_sse_response("Hello"))
+                .insert_header("content-type", "text/event-stream"),
+        )
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+fn create_test_app(state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/v1/chat/completions",
+            post(octoroute::handlers::openai::completions::handler),
+        )
+        .with_state(state)
+        .layer(middleware::from_fn(request_id_middleware))
+}
+
+async fn complete_as(state: &AppState, user: &str) -> axum::response::Response {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(format!(
+            r#"{{"model": "fast", "user": "{user}", "messages": [{{"role": "user", "content": "Hello"}}]}}"#
+        )))
+        .unwrap();
+    create_test_app(state.clone())
+        .oneshot(request)
+        .await
+        .unwrap()
+}
+
+#[derive(Clone, Default)]
+struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+impl Write for CapturedLogs {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_user_field_is_counted_when_tracking_enabled() {
+    let mock_server = start_backend().await;
+    let config = create_config(
+        &mock_server.uri(),
+        "[server.user_tracking]\nmax_requests = 10",
+    );
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+    let tracker = state.user_tracker().expect("tracking should be enabled");
+
+    for _ in 0..3 {
+        assert_eq!(
+            complete_as(&state, "tenant-7").await.status(),
+            StatusCode::OK
+        );
+    }
+    complete_as(&state, "tenant-8").await;
+
+    assert_eq!(tracker.count("tenant-7"), 3);
+    assert_eq!(tracker.count("tenant-8"), 1);
+    assert_eq!(tracker.tracked_users(), 2);
+}
+
+#[tokio::test]
+async fn test_tracking_disabled_by_default() {
+    let mock_server = start_backend().await;
+    let state = AppState::new(Arc::new(create_config(&mock_server.uri(), "")))
+        .expect("AppState::new should succeed");
+
+    assert!(state.user_tracker().is_none());
+    assert_eq!(
+        complete_as(&state, "tenant-7").await.status(),
+        StatusCode::OK
+    );
+}
+
+#[tokio::test]
+async fn test_reject_over_limit_returns_429_with_retry_after() {
+    let mock_server = start_backend().await;
+    let config = create_config(
+        &mock_server.uri(),
+        "[server.user_tracking]\nmax_requests = 2\nwindow_seconds = 30\nreject_over_limit = true",
+    );
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+
+    assert_eq!(
+        complete_as(&state, "tenant-7").await.status(),
+        StatusCode::OK
+    );
+    assert_eq!(
+        complete_as(&state, "tenant-7").await.status(),
+        StatusCode::OK
+    );
+
+    let rejected = complete_as(&state, "tenant-7").await;
+    assert_eq!(rejected.status(), StatusCode::TOO_MANY_REQUESTS);
+    let retry_after: u64 = rejected
+        .headers()
+        .get("retry-after")
+        .expect("429 should carry Retry-After")
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!((1..=30).contains(&retry_after), "got {}", retry_after);
+    let body = axum::body::to_bytes(rejected.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["error"]["type"], "rate_limit_error");
+
+    // Other users are unaffected, and the rejected request never hit a backend
+    assert_eq!(
+        complete_as(&state, "tenant-8").await.status(),
+        StatusCode::OK
+    );
+    assert_eq!(mock_server.received_requests().await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_user_appears_in_request_logs() {
+    let mock_server = start_backend().await;
+    let config = create_config(
+        &mock_server.uri(),
+        "[server.user_tracking]\nmax_requests = 1",
+    );
+    let state = AppState::new(Arc::new(config)).expect("AppState::new should succeed");
+
+    let logs = CapturedLogs::default();
+    let subscriber = tracing_subscriber::fmt()
+        .with_writer({
+            let logs = logs.clone();
+            move || logs.clone()
+        })
+        .with_ansi(false)
+        .with_max_level(tracing::Level::DEBUG)
+        .finish();
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    complete_as(&state, "tenant-7").await;
+    complete_as(&state, "tenant-7").await;
+
+    let output = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+    let completed = output
+        .lines()
+        .find(|line| line.contains("Chat completion successful"))
+        .expect("completion should be logged");
+    assert!(
+        completed.contains("user=\"tenant-7\""),
+        "got: {}",
+        completed
+    );
+    assert!(completed.contains("request_id="), "got: {}", completed);
+
+    // The second request crossed the limit of 1
+    let exceeded = output
+        .lines()
+        .find(|line| line.contains("exceeded the per-user request limit"))
+        .expect("going over the limit should be logged");
+    assert!(exceeded.contains("tenant-7"), "got: {}", exceeded);
+}
@@ -0,0 +1,85 @@
+//! Integration tests for GET /version and the octoroute_build_info metric
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    routing::get,
+};
+use octoroute::{build_info, config::Config, handlers};
+use std::sync::Arc;
+use tower::ServiceExt;
+
+fn create_test_state() -> handlers::AppState {
+    let toml = r#"
+[server]
+host = "127.0.0.1"
+port = 3000
+
+[[models.fast]]
+name = "fast-1"
+base_url = "http://localhost:1234/v1"
+max_tokens = 2048
+
+[[models.balanced]]
+name = "balanced-1"
+base_url = "http://localhost:1235/v1"
+max_tokens = 4096
+
+[[models.deep]]
+name = "deep-1"
+base_url = "http://localhost:1236/v1"
+max_tokens = 8192
+
+[routing]
+strategy = "rule"
+"#;
+    let config: Config = toml::from_str(toml).expect("should parse test config");
+    handlers::AppState::new(Arc::new(config)).expect("should create AppState")
+}
+
+fn create_test_app() -> Router {
+    Router::new()
+        .route("/version", get(handlers::version::handler))
+        .route("/metrics", get(handlers::metrics::handler))
+        .with_state(create_test_state())
+}
+
+async fn get_body(app: Router, uri: &str) -> (StatusCode, String) {
+    let response = app
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, String::from_utf8_lossy(&body).into_owned())
+}
+
+#[tokio::test]
+async fn test_version_endpoint_returns_build_info() {
+    let (status, body) = get_body(create_test_app(), "/version").await;
+
+    assert_eq!(status, StatusCode::OK);
+    let json: serde_json::Value = serde_json::from_str(&body).expect("should parse JSON");
+    assert_eq!(json["version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(json["git_sha"], build_info::GIT_SHA);
+    assert_eq!(json["rustc"], build_info::RUSTC_VERSION);
+}
+
+#[tokio::test]
+async fn test_metrics_endpoint_exports_build_info_gauge() {
+    let (status, body) = get_body(create_test_app(), "/metrics").await;
+
+    assert_eq!(status, StatusCode::OK);
+    let line = body
+        .lines()
+        .find(|l| l.starts_with("octoroute_build_info{"))
+        .unwrap_or_else(|| panic!("build_info gauge missing from /metrics: {}", body));
+    assert!(
+        line.contains(&format!("version=\"{}\"", env!("CARGO_PKG_VERSION"))),
+        "build_info should carry a non-empty version label, got: {}",
+        line
+    );
+}